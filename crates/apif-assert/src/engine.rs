@@ -1,6 +1,7 @@
 // Assertion engine using embedded jaq and operators fallback
 
 use anyhow::Result;
+use apif_ast::MetadataMap;
 use serde_json::Value;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -75,8 +76,10 @@ static JAQ_FILTER_CACHE: LazyLock<Mutex<HashMap<String, Arc<JaqFilter>>>> =
 /// expression (the jaq-fallback path).
 const JAQ_CONTEXT_ONLY_PLUGINS: &[&str] = &[
     "header",
+    "headers",
     "has_header",
     "trailer",
+    "trailers",
     "has_trailer",
     "elapsed_ms",
     "total_elapsed_ms",
@@ -346,8 +349,8 @@ impl AssertionEngine {
         &self,
         assertion: &str,
         response: &Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
     ) -> Result<AssertionResult> {
         self.evaluate_with_timing(
             assertion,
@@ -365,8 +368,8 @@ impl AssertionEngine {
         &self,
         assertion: &str,
         response: &Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
         timing: Option<&AssertionTiming>,
         variables: &HashMap<String, Value>,
         protocol: Option<&str>,
@@ -536,8 +539,8 @@ impl AssertionEngine {
         &self,
         assertions: &[String],
         response: &serde_json::Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
     ) -> Vec<AssertionResult> {
         self.evaluate_all_with_timing(
             assertions,
@@ -555,8 +558,8 @@ impl AssertionEngine {
         &self,
         assertions: &[String],
         response: &serde_json::Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
         timing: Option<&AssertionTiming>,
         variables: &HashMap<String, Value>,
         protocol: Option<&str>,
@@ -577,8 +580,8 @@ impl AssertionEngine {
         &self,
         assertions: &[String],
         response: &serde_json::Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
         timing: Option<&AssertionTiming>,
         variables: &HashMap<String, Value>,
         protocol: Option<&str>,
@@ -4,6 +4,7 @@ pub mod engine;
 pub mod operators;
 pub mod registry;
 
+pub use apif_ast::MetadataMap;
 pub use comparator::JsonComparator;
 pub use diff::get_json_diff;
 pub use engine::{AssertionEngine, AssertionResult};
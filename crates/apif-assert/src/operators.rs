@@ -2,6 +2,7 @@
 // All evaluation goes through the AssertionExpr AST — no string-based parsing.
 
 use anyhow::Result;
+use apif_ast::MetadataMap;
 use regex::Regex;
 use serde_json::Value;
 use std::cell::RefCell;
@@ -64,8 +65,8 @@ pub fn cached_regex(pattern: &str) -> std::result::Result<Rc<Regex>, String> {
 /// `eval_plugin_as_assertion` — they all just pass `ctx` through unchanged.
 pub(crate) struct EvalCtx<'a> {
     pub response: &'a Value,
-    pub headers: Option<&'a HashMap<String, String>>,
-    pub trailers: Option<&'a HashMap<String, String>>,
+    pub headers: Option<&'a MetadataMap>,
+    pub trailers: Option<&'a MetadataMap>,
     pub timing: Option<&'a AssertionTiming>,
     pub variables: &'a HashMap<String, Value>,
     /// Wire protocol that produced `response` (`"grpc"`/`"grpc-web"`/
@@ -84,11 +85,11 @@ impl<'a> EvalCtx<'a> {
             protocol: None,
         }
     }
-    pub fn with_headers(mut self, headers: Option<&'a HashMap<String, String>>) -> Self {
+    pub fn with_headers(mut self, headers: Option<&'a MetadataMap>) -> Self {
         self.headers = headers;
         self
     }
-    pub fn with_trailers(mut self, trailers: Option<&'a HashMap<String, String>>) -> Self {
+    pub fn with_trailers(mut self, trailers: Option<&'a MetadataMap>) -> Self {
         self.trailers = trailers;
         self
     }
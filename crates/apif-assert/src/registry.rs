@@ -1,15 +1,15 @@
 use crate::engine::AssertionResult;
 use anyhow::Result;
+use apif_ast::MetadataMap;
 use serde_json::Value;
-use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Context passed to plugins during assertion evaluation.
 #[derive(Debug, Clone)]
 pub struct PluginContext<'a> {
     pub response: &'a Value,
-    pub headers: Option<&'a HashMap<String, String>>,
-    pub trailers: Option<&'a HashMap<String, String>>,
+    pub headers: Option<&'a MetadataMap>,
+    pub trailers: Option<&'a MetadataMap>,
     pub timing: Option<&'a AssertionTiming>,
     /// Wire protocol that produced this response — `"grpc"`, `"grpc-web"`, or
     /// `"connectrpc"` (the same canonical strings `OPTIONS.protocol:`
@@ -28,11 +28,11 @@ impl<'a> PluginContext<'a> {
             protocol: None,
         }
     }
-    pub fn with_headers(mut self, headers: Option<&'a HashMap<String, String>>) -> Self {
+    pub fn with_headers(mut self, headers: Option<&'a MetadataMap>) -> Self {
         self.headers = headers;
         self
     }
-    pub fn with_trailers(mut self, trailers: Option<&'a HashMap<String, String>>) -> Self {
+    pub fn with_trailers(mut self, trailers: Option<&'a MetadataMap>) -> Self {
         self.trailers = trailers;
         self
     }
@@ -1,6 +1,7 @@
 // AST (Abstract Syntax Tree) for .gctf files
 // Represents the parsed structure of a .gctf test file
 
+use crate::metadata::MetadataMap;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,7 +9,9 @@ use std::collections::HashMap;
 /// Insertion-ordered string map backing KV/EXTRACT section content. Ordered
 /// (not `HashMap`) so serialized output and every consumer iteration follow the
 /// author's source order deterministically; keeps map ergonomics (`get`/dedup)
-/// since duplicate keys are already rejected at parse time.
+/// since duplicate keys are already rejected at parse time — except in
+/// REQUEST_HEADERS, where a repeated key is legal metadata and its values are
+/// joined with `\n` (see [`GctfDocument::get_request_headers`]).
 pub type OrderedStringMap = IndexMap<String, String>;
 
 /// Complete .gctf document
@@ -84,6 +87,13 @@ pub struct FileMeta {
     /// Related links (docs, jira, etc)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub links: Vec<String>,
+    /// Test files that must pass before this one, relative to this file;
+    /// their EXTRACT variables are visible here
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    /// Known-flaky: failures are reported but don't fail the run
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub quarantine: bool,
 }
 
 impl FileMeta {
@@ -95,6 +105,8 @@ impl FileMeta {
             && self.tags.is_empty()
             && self.owner.is_none()
             && self.links.is_empty()
+            && self.depends_on.is_empty()
+            && !self.quarantine
     }
 }
 
@@ -468,7 +480,10 @@ impl SectionType {
     }
 
     pub fn supports_inline_options(&self) -> bool {
-        matches!(self, SectionType::Response | SectionType::Error)
+        matches!(
+            self,
+            SectionType::Request | SectionType::Response | SectionType::Error
+        )
     }
 
     pub fn preamble_rank(&self) -> Option<usize> {
@@ -738,12 +753,18 @@ impl GctfDocument {
             .collect()
     }
 
-    /// Get request headers
-    pub fn get_request_headers(&self) -> Option<OrderedStringMap> {
+    /// Get request headers, one entry per value: a key repeated in the section
+    /// is stored `\n`-joined and comes back as several entries, in source order.
+    pub fn get_request_headers(&self) -> Option<MetadataMap> {
         if let Some(section) = self.first_section(SectionType::RequestHeaders)
             && let SectionContent::KeyValues(headers) = &section.content
         {
-            return Some(headers.clone());
+            return Some(
+                headers
+                    .iter()
+                    .flat_map(|(k, v)| v.split('\n').map(|v| (k.clone(), v.to_string())))
+                    .collect(),
+            );
         }
         None
     }
@@ -893,7 +914,7 @@ mod tests {
     fn section_type_supports_inline_options() {
         assert!(SectionType::Response.supports_inline_options());
         assert!(SectionType::Error.supports_inline_options());
-        assert!(!SectionType::Request.supports_inline_options());
+        assert!(SectionType::Request.supports_inline_options());
         assert!(!SectionType::Address.supports_inline_options());
     }
 
@@ -1227,6 +1248,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn gctf_document_get_request_headers_splits_repeated_values() {
+        let mut doc = GctfDocument::new("test.gctf".to_string());
+        doc.sections.push(Section {
+            section_type: SectionType::RequestHeaders,
+            content: SectionContent::KeyValues(OrderedStringMap::from([(
+                "x-tag".to_string(),
+                "a\nb".to_string(),
+            )])),
+            ..Section::default()
+        });
+
+        let result = doc.get_request_headers().unwrap();
+        assert_eq!(result.get_all("x-tag").collect::<Vec<_>>(), ["a", "b"]);
+    }
+
     #[test]
     fn gctf_document_get_tls_config() {
         let mut doc = GctfDocument::new("test.gctf".to_string());
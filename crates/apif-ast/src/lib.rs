@@ -1,6 +1,7 @@
 pub mod assertion_ast;
 pub mod ast;
 pub mod gctf_tokenizer;
+pub mod metadata;
 pub mod tokenizer;
 
 pub use assertion_ast::{
@@ -16,6 +17,7 @@ pub use gctf_tokenizer::{
     GctfToken, GctfTokenKind, scan_miscased_section_header_name, strip_gctf_comment_lines,
     tokenize_extract_line, tokenize_gctf, tokenize_inline_options, tokenize_kv_line,
 };
+pub use metadata::MetadataMap;
 pub use tokenizer::{
     Span, Token, TokenKind, collect_identifiers, collect_operators, collect_plugin_calls,
     tokenize_assertion,
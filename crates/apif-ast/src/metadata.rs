@@ -0,0 +1,268 @@
+// Ordered multimap for gRPC metadata (request headers, response headers and
+// trailers).
+
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// gRPC metadata as an insertion-ordered multimap.
+///
+/// A key may carry several values (repeated headers are legal in both HTTP/2
+/// and gRPC), and order is preserved across keys and within one key. Lookups
+/// are ASCII case-insensitive, as metadata keys are. Values of `-bin` keys are
+/// held as standard base64 — the transports decode/encode at the wire.
+///
+/// Serializes as a JSON object: a key with one value maps to a string, a key
+/// with several values to an array of strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataMap {
+    entries: Vec<(String, String)>,
+}
+
+impl MetadataMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Whether `key` names binary metadata (`-bin` suffix).
+    pub fn is_binary_key(key: &str) -> bool {
+        let key = key.as_bytes();
+        key.len() > 4 && key[key.len() - 4..].eq_ignore_ascii_case(b"-bin")
+    }
+
+    /// Add a value for `key`, keeping any values already present.
+    pub fn append(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((key.into(), value.into()));
+    }
+
+    /// Set `key` to a single value, dropping any previous values. Returns the
+    /// first value that was replaced.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        let key = key.into();
+        let previous = self.remove(&key);
+        self.entries.push((key, value.into()));
+        previous
+    }
+
+    /// Remove every value of `key`, returning the first one.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        let mut first = None;
+        self.entries.retain(|(k, v)| {
+            if k.eq_ignore_ascii_case(key) {
+                if first.is_none() {
+                    first = Some(v.clone());
+                }
+                false
+            } else {
+                true
+            }
+        });
+        first
+    }
+
+    /// First value of `key`.
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    /// Every value of `key`, in arrival order.
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a String> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Distinct keys, in order of first appearance.
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(i, (k, _))| {
+                !self.entries[..*i]
+                    .iter()
+                    .any(|(seen, _)| seen.eq_ignore_ascii_case(k))
+            })
+            .map(|(_, (k, _))| k)
+    }
+
+    /// Every `(key, value)` entry, repeated keys included.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Number of entries (values), not distinct keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add every entry of `other` after the existing ones.
+    pub fn extend_from(&mut self, other: &MetadataMap) {
+        self.entries.extend(other.entries.iter().cloned());
+    }
+}
+
+impl FromIterator<(String, String)> for MetadataMap {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            entries: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(String, String)> for MetadataMap {
+    fn extend<I: IntoIterator<Item = (String, String)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+    }
+}
+
+impl IntoIterator for MetadataMap {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a MetadataMap {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, String)>,
+        fn(&'a (String, String)) -> (&'a String, &'a String),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl<const N: usize> From<[(String, String); N]> for MetadataMap {
+    fn from(entries: [(String, String); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl Serialize for MetadataMap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let keys: Vec<&String> = self.keys().collect();
+        let mut map = serializer.serialize_map(Some(keys.len()))?;
+        for key in keys {
+            let values: Vec<&String> = self.get_all(key).collect();
+            if let [single] = values.as_slice() {
+                map.serialize_entry(key, single)?;
+            } else {
+                map.serialize_entry(key, &values)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for MetadataMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        struct MetadataVisitor;
+
+        impl<'de> Visitor<'de> for MetadataVisitor {
+            type Value = MetadataMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of metadata keys to a string or an array of strings")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<MetadataMap, A::Error> {
+                let mut out = MetadataMap::with_capacity(access.size_hint().unwrap_or(0));
+                while let Some((key, value)) = access.next_entry::<String, OneOrMany>()? {
+                    match value {
+                        OneOrMany::One(v) => out.append(key, v),
+                        OneOrMany::Many(vs) => {
+                            for v in vs {
+                                out.append(key.clone(), v);
+                            }
+                        }
+                    }
+                }
+                Ok(out)
+            }
+        }
+
+        deserializer.deserialize_map(MetadataVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> MetadataMap {
+        let mut m = MetadataMap::new();
+        m.append("x-trace", "a");
+        m.append("content-type", "application/grpc");
+        m.append("X-Trace", "b");
+        m
+    }
+
+    #[test]
+    fn repeated_keys_keep_every_value_in_order() {
+        let m = sample();
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.get("x-trace"), Some(&"a".to_string()));
+        assert_eq!(m.get_all("X-TRACE").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(m.keys().collect::<Vec<_>>(), ["x-trace", "content-type"]);
+    }
+
+    #[test]
+    fn insert_replaces_all_values() {
+        let mut m = sample();
+        assert_eq!(m.insert("x-trace", "c"), Some("a".to_string()));
+        assert_eq!(m.get_all("x-trace").collect::<Vec<_>>(), ["c"]);
+    }
+
+    #[test]
+    fn binary_key_detection() {
+        assert!(MetadataMap::is_binary_key("x-trace-bin"));
+        assert!(MetadataMap::is_binary_key("X-Trace-BIN"));
+        assert!(!MetadataMap::is_binary_key("-bin"));
+        assert!(!MetadataMap::is_binary_key("x-binary"));
+        assert!(!MetadataMap::is_binary_key("xébin"));
+        assert!(MetadataMap::is_binary_key("é-bin"));
+    }
+
+    #[test]
+    fn serde_round_trip_uses_arrays_only_for_repeated_keys() {
+        let m = sample();
+        let json = serde_json::to_value(&m).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"x-trace": ["a", "b"], "content-type": "application/grpc"})
+        );
+        let back: MetadataMap = serde_json::from_value(json).unwrap();
+        assert_eq!(back.get_all("x-trace").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(back.len(), 3);
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
+use apif_ast::MetadataMap;
 use async_trait::async_trait;
 use serde_json::Value;
-use std::collections::HashMap;
 use std::pin::Pin;
 
 /// How the client communicates with the server.
@@ -25,7 +25,7 @@ pub struct EndpointMeta {
 #[derive(Debug, Clone)]
 pub enum CallStreamItem {
     Message(Value),
-    Trailers(HashMap<String, String>),
+    Trailers(MetadataMap),
 }
 
 /// Protocol-agnostic call error.
@@ -70,7 +70,6 @@ pub trait CallClientFactory: Send + Sync {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
 
     #[test]
     fn rpc_mode_debug() {
@@ -101,8 +100,8 @@ mod tests {
 
     #[test]
     fn call_stream_item_trailers() {
-        let mut h = HashMap::new();
-        h.insert("x-status".into(), "ok".into());
+        let mut h = MetadataMap::new();
+        h.append("x-status", "ok");
         let item = CallStreamItem::Trailers(h);
         match item {
             CallStreamItem::Trailers(t) => assert_eq!(t.get("x-status"), Some(&"ok".into())),
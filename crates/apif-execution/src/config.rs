@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use apif_ast::MetadataMap;
 
 /// Protocol-agnostic TLS configuration.
 #[derive(Debug, Clone, Default)]
@@ -16,7 +16,7 @@ pub struct CallClientConfig {
     pub address: String,
     pub timeout_seconds: u64,
     pub tls: Option<TlsConfig>,
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<MetadataMap>,
     pub compression: Option<String>,
 }
 
@@ -54,8 +54,8 @@ mod tests {
 
     #[test]
     fn call_client_config_custom() {
-        let mut meta = HashMap::new();
-        meta.insert("authorization".into(), "token123".into());
+        let mut meta = MetadataMap::new();
+        meta.append("authorization", "token123");
         let cfg = CallClientConfig {
             address: "localhost:8080".into(),
             timeout_seconds: 30,
@@ -64,6 +64,9 @@ mod tests {
         };
         assert_eq!(cfg.address, "localhost:8080");
         assert_eq!(cfg.timeout_seconds, 30);
-        assert_eq!(cfg.metadata.as_ref().unwrap()["authorization"], "token123");
+        assert_eq!(
+            cfg.metadata.as_ref().unwrap().get("authorization").unwrap(),
+            "token123"
+        );
     }
 }
@@ -4,8 +4,10 @@
 //! that don't require `self` access: variable substitution, TLS defaults,
 //! JSON formatting, and metadata conversion.
 
-use apif_ast::GctfDocument;
+use crate::template::{self, TemplateContext};
+use apif_ast::{GctfDocument, MetadataMap};
 use apif_cfg_runtime as runtime;
+use apif_grpc_transport::encoding::base64_encode;
 use apif_grpc_transport::{
     CompressionMode, ProtoConfig, TlsConfig, WireProtocol, default_address_for,
 };
@@ -76,6 +78,16 @@ pub fn effective_address(
         })
 }
 
+/// Resolve the HTTP CONNECT proxy override: OPTIONS `proxy:`, then the CLI
+/// `--proxy` value. `None` leaves the choice to `HTTP(S)_PROXY`/`NO_PROXY`.
+pub fn effective_proxy(document: &GctfDocument, cli_proxy: Option<&str>) -> Option<String> {
+    document
+        .get_options()
+        .and_then(|o| o.get("proxy").map(|v| v.trim().to_string()))
+        .filter(|v| !v.is_empty())
+        .or_else(|| cli_proxy.map(str::to_string))
+}
+
 /// Resolve compression setting from OPTIONS section with env fallback.
 pub fn parse_compression_option(options: &apif_ast::OrderedStringMap) -> Option<CompressionMode> {
     options
@@ -479,8 +491,33 @@ pub fn format_json_pretty(value: &Value) -> String {
 
 /// Interpolate variables in a string template.
 /// Replaces `{{var}}` patterns with values from the variables map.
-/// Returns `None` if no substitutions were made.
+/// Returns `None` if no substitutions were made. Template functions and
+/// filters are evaluated with an unseeded [`TemplateContext`]; a placeholder
+/// whose evaluation fails is left verbatim.
 pub fn interpolate_variables(template: &str, variables: &HashMap<String, Value>) -> Option<String> {
+    try_interpolate_with(template, variables, &TemplateContext::unseeded(), true)
+        .ok()
+        .flatten()
+}
+
+/// Like [`interpolate_variables`], but placeholders may use template
+/// functions and filters (see [`crate::template`]) drawing randomness from
+/// `ctx`. A failing expression (unknown function, unset `env()`...) is an
+/// error rather than a verbatim placeholder.
+pub fn try_interpolate_variables(
+    template: &str,
+    variables: &HashMap<String, Value>,
+    ctx: &TemplateContext,
+) -> Result<Option<String>, String> {
+    try_interpolate_with(template, variables, ctx, false)
+}
+
+fn try_interpolate_with(
+    template: &str,
+    variables: &HashMap<String, Value>,
+    ctx: &TemplateContext,
+    lenient: bool,
+) -> Result<Option<String>, String> {
     let mut out = String::with_capacity(template.len());
     let mut cursor = 0usize;
     let mut changed = false;
@@ -492,25 +529,40 @@ pub fn interpolate_variables(template: &str, variables: &HashMap<String, Value>)
         let after_open = open + 2;
         if let Some(close_rel) = template[after_open..].find("}}") {
             let close = after_open + close_rel;
-            let var_name = template[after_open..close].trim();
-
-            if let Some(var_value) = variables.get(var_name) {
-                tracing::trace!("interpolate: {{{{{var_name}}}}} -> {var_value:?}");
-                if let Value::String(s) = var_value {
-                    out.push_str(s);
-                } else {
-                    out.push_str(&var_value.to_string());
+            let body = template[after_open..close].trim();
+
+            // A variable named like `user-id` or `first name` (CSV and
+            // `--data` columns) isn't an expression, so look it up first.
+            let evaluated = match variables.get(body) {
+                Some(v) => Ok(Some(v.clone())),
+                None => template::evaluate(body, variables, ctx),
+            };
+            match evaluated {
+                Ok(Some(var_value)) => {
+                    tracing::trace!("interpolate: {{{{{body}}}}} -> {var_value:?}");
+                    if let Value::String(s) = var_value {
+                        out.push_str(&s);
+                    } else {
+                        out.push_str(&var_value.to_string());
+                    }
+                    changed = true;
+                }
+                Ok(None) => {
+                    tracing::trace!(
+                        "interpolate: {{{{{body}}}}} has no matching variable, left as-is"
+                    );
+                    out.push_str(&template[open..close + 2]);
+                }
+                Err(e) if lenient => {
+                    tracing::trace!("interpolate: {{{{{body}}}}} failed ({e}), left as-is");
+                    out.push_str(&template[open..close + 2]);
                 }
-                changed = true;
-            } else {
-                tracing::trace!(
-                    "interpolate: {{{{{var_name}}}}} has no matching variable, left as-is"
-                );
-                out.push_str(&template[open..close + 2]);
+                Err(e) => return Err(format!("{{{{{body}}}}}: {e}")),
             }
             cursor = close + 2;
         } else {
             out.push_str(&template[cursor..]);
+            cursor = template.len();
             break;
         }
     }
@@ -519,46 +571,76 @@ pub fn interpolate_variables(template: &str, variables: &HashMap<String, Value>)
         out.push_str(&template[cursor..]);
     }
 
-    if changed { Some(out) } else { None }
+    Ok(if changed { Some(out) } else { None })
 }
 
 /// Recursively substitute variables in a JSON value.
 /// If a string is exactly `{{var}}`, it's replaced with the actual Value type.
 /// Otherwise, string interpolation is performed.
 pub fn substitute_variables(value: &mut Value, variables: &HashMap<String, Value>) {
+    let _ = substitute_with(value, variables, &TemplateContext::unseeded(), true);
+}
+
+/// Like [`substitute_variables`], with template functions evaluated against
+/// `ctx`. An exact `{{ expr }}` keeps the result's JSON type, so
+/// `{{random_int(1, 9)}}` becomes a number.
+pub fn try_substitute_variables(
+    value: &mut Value,
+    variables: &HashMap<String, Value>,
+    ctx: &TemplateContext,
+) -> Result<(), String> {
+    substitute_with(value, variables, ctx, false)
+}
+
+fn substitute_with(
+    value: &mut Value,
+    variables: &HashMap<String, Value>,
+    ctx: &TemplateContext,
+    lenient: bool,
+) -> Result<(), String> {
     match value {
         Value::String(s) => {
             // Without this, every placeholder-free string still pays for
             // `interpolate_variables`'s `String::with_capacity` + full copy
             // before it can report "nothing changed".
             if !s.contains("{{") {
-                return;
+                return Ok(());
             }
             if s.starts_with("{{") && s.ends_with("}}") {
                 let inner = s[2..s.len() - 2].trim();
-                if !inner.contains("{{")
-                    && let Some(val) = variables.get(inner)
-                {
-                    *value = val.clone();
-                    return;
+                if !inner.contains("{{") && !inner.contains("}}") {
+                    let evaluated = match variables.get(inner) {
+                        Some(v) => Ok(Some(v.clone())),
+                        None => template::evaluate(inner, variables, ctx),
+                    };
+                    match evaluated {
+                        Ok(Some(val)) => {
+                            *value = val;
+                            return Ok(());
+                        }
+                        Ok(None) => return Ok(()),
+                        Err(_) if lenient => return Ok(()),
+                        Err(e) => return Err(format!("{{{{{inner}}}}}: {e}")),
+                    }
                 }
             }
-            if let Some(replaced) = interpolate_variables(s, variables) {
+            if let Some(replaced) = try_interpolate_with(s, variables, ctx, lenient)? {
                 *s = replaced;
             }
         }
         Value::Array(items) => {
             for item in items {
-                substitute_variables(item, variables);
+                substitute_with(item, variables, ctx, lenient)?;
             }
         }
         Value::Object(map) => {
             for (_, val) in map.iter_mut() {
-                substitute_variables(val, variables);
+                substitute_with(val, variables, ctx, lenient)?;
             }
         }
         _ => {}
     }
+    Ok(())
 }
 
 /// True when `body` is a single well-formed variable identifier, i.e. the kind
@@ -589,10 +671,13 @@ pub fn find_unresolved_placeholders(
         };
         let close = after_open + close_rel;
         let name = text[after_open..close].trim();
-        if is_variable_placeholder(name)
-            && !variables.contains_key(name)
-            && !out.iter().any(|n| n == name)
-        {
+        let unresolved = if is_variable_placeholder(name) {
+            !variables.contains_key(name)
+        } else {
+            // A function/filter expression still in the text was never evaluated.
+            template::is_expression(name)
+        };
+        if unresolved && !out.iter().any(|n| n == name) {
             out.push(name.to_string());
         }
         cursor = close + 2;
@@ -631,16 +716,21 @@ pub fn format_unresolved_placeholders(names: &[String]) -> String {
         .join(", ")
 }
 
-/// Convert tonic metadata map to HashMap.
-pub fn metadata_map_to_hashmap(metadata: &tonic::metadata::MetadataMap) -> HashMap<String, String> {
-    let mut out = HashMap::new();
+/// Convert tonic metadata to the ordered multimap, keeping repeated keys and
+/// rendering `-bin` values as standard base64.
+pub fn metadata_map_to_multimap(metadata: &tonic::metadata::MetadataMap) -> MetadataMap {
+    let mut out = MetadataMap::with_capacity(metadata.len());
     for kv in metadata.iter() {
-        if let tonic::metadata::KeyAndValueRef::Ascii(key, value) = kv {
-            if let Ok(v) = value.to_str() {
-                out.insert(key.to_string(), v.to_string());
+        match kv {
+            tonic::metadata::KeyAndValueRef::Ascii(key, value) => {
+                if let Ok(v) = value.to_str() {
+                    out.append(key.to_string(), v);
+                }
+            }
+            tonic::metadata::KeyAndValueRef::Binary(key, value) => {
+                let bytes = value.to_bytes().unwrap_or_default();
+                out.append(key.to_string(), base64_encode(&bytes));
             }
-        } else if let tonic::metadata::KeyAndValueRef::Binary(key, value) = kv {
-            out.insert(key.to_string(), format!("{:?}", value));
         }
     }
     out
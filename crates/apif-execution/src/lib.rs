@@ -1,6 +1,7 @@
 pub mod client;
 pub mod config;
 pub mod helpers;
+pub mod template;
 
 pub use client::{
     CallClient, CallClientFactory, CallError, CallRequest, CallStreamItem, EndpointMeta, RpcMode,
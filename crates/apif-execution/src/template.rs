@@ -0,0 +1,674 @@
+//! Built-in functions and filters for `{{ ... }}` placeholders.
+//!
+//! A placeholder body that isn't a plain variable name is parsed as a small
+//! expression: a primary (variable, `fake.<kind>`, function call or literal)
+//! followed by `| filter` stages, e.g. `{{ uuid() }}`, `{{ now("rfc3339", "+5m") }}`,
+//! `{{ token | base64 }}`, `{{ user | default("anon") | upper }}`.
+//!
+//! Every source of randomness draws from the [`TemplateContext`]'s RNG, and a
+//! seeded context also stops the clock, so a run with `--seed` produces the
+//! same values each time.
+
+use apif_cfg_runtime as runtime;
+use apif_grpc_transport::encoding::{base64_decode, base64_encode};
+use chrono::{DateTime, Utc};
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// What `now()` reads in a seeded context: 2025-01-01T00:00:00Z.
+pub const SEEDED_NOW_UNIX_MS: i64 = 1_735_689_600_000;
+
+/// Randomness for one test run. Seeded contexts are deterministic per
+/// `(seed, scope)` and read a fixed clock; unseeded ones draw a fresh seed on
+/// first use and read the real one.
+pub struct TemplateContext {
+    seed: Option<u64>,
+    rng: Mutex<Option<StdRng>>,
+}
+
+impl TemplateContext {
+    pub fn unseeded() -> Self {
+        Self {
+            seed: None,
+            rng: Mutex::new(None),
+        }
+    }
+
+    /// Deterministic context: the same `seed` and `scope` (typically the test
+    /// file plus its data row index) always yield the same sequence.
+    pub fn seeded(seed: u64, scope: &str) -> Self {
+        Self {
+            seed: Some(seed ^ fnv1a(scope.as_bytes())),
+            rng: Mutex::new(None),
+        }
+    }
+
+    /// `seeded` when a seed is given, `unseeded` otherwise.
+    pub fn new(seed: Option<u64>, scope: &str) -> Self {
+        match seed {
+            Some(seed) => Self::seeded(seed, scope),
+            None => Self::unseeded(),
+        }
+    }
+
+    fn with_rng<T>(&self, f: impl FnOnce(&mut StdRng) -> T) -> T {
+        let mut guard = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let rng = guard.get_or_insert_with(|| match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => rand::make_rng(),
+        });
+        f(rng)
+    }
+
+    /// The instant `now()` formats: [`SEEDED_NOW_UNIX_MS`] when seeded, so a
+    /// timestamp in a request doesn't break reproducibility.
+    fn now_unix_millis(&self) -> i64 {
+        match self.seed {
+            Some(_) => SEEDED_NOW_UNIX_MS,
+            None => runtime::now_unix_millis() as i64,
+        }
+    }
+}
+
+impl Default for TemplateContext {
+    fn default() -> Self {
+        Self::unseeded()
+    }
+}
+
+/// Stable across Rust versions, unlike `DefaultHasher`, so a seed reproduces
+/// the same values after a toolchain upgrade.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(*b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Num(f64, String),
+    LParen,
+    RParen,
+    Comma,
+    Pipe,
+}
+
+fn tokenize(body: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = body.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '|' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    ',' => Token::Comma,
+                    _ => Token::Pipe,
+                });
+            }
+            '"' | '\'' => {
+                let quote = c;
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => s.push(escaped),
+                            None => return Err("unterminated string".to_string()),
+                        },
+                        Some(ch) if ch == quote => break,
+                        Some(ch) => s.push(ch),
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut raw = String::new();
+                raw.push(c);
+                chars.next();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        raw.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let n = raw
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number '{raw}'"))?;
+                tokens.push(Token::Num(n, raw));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_alphanumeric() || d == '_' || d == '.' {
+                        ident.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            other => return Err(format!("unexpected character '{other}'")),
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Primary {
+    Var(String),
+    Call(String, Vec<Value>),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Expr {
+    primary: Primary,
+    filters: Vec<(String, Vec<Value>)>,
+}
+
+fn parse(body: &str) -> Result<Expr, String> {
+    let tokens = tokenize(body)?;
+    let mut pos = 0;
+
+    fn args(tokens: &[Token], pos: &mut usize) -> Result<Vec<Value>, String> {
+        // Caller has consumed `(`.
+        let mut out = Vec::new();
+        if tokens.get(*pos) == Some(&Token::RParen) {
+            *pos += 1;
+            return Ok(out);
+        }
+        loop {
+            out.push(match tokens.get(*pos) {
+                Some(Token::Str(s)) => Value::String(s.clone()),
+                Some(Token::Num(_, raw)) => {
+                    serde_json::from_str(raw).map_err(|_| format!("invalid number '{raw}'"))?
+                }
+                Some(Token::Ident(b)) if b == "true" || b == "false" => Value::Bool(b == "true"),
+                _ => return Err("expected a string or number argument".to_string()),
+            });
+            *pos += 1;
+            match tokens.get(*pos) {
+                Some(Token::Comma) => *pos += 1,
+                Some(Token::RParen) => {
+                    *pos += 1;
+                    return Ok(out);
+                }
+                _ => return Err("expected ',' or ')'".to_string()),
+            }
+        }
+    }
+
+    let primary = match tokens.get(pos) {
+        Some(Token::Ident(name)) => {
+            pos += 1;
+            if tokens.get(pos) == Some(&Token::LParen) {
+                pos += 1;
+                Primary::Call(name.clone(), args(&tokens, &mut pos)?)
+            } else {
+                Primary::Var(name.clone())
+            }
+        }
+        Some(Token::Str(s)) => {
+            pos += 1;
+            Primary::Literal(Value::String(s.clone()))
+        }
+        Some(Token::Num(_, raw)) => {
+            pos += 1;
+            Primary::Literal(
+                serde_json::from_str(raw).map_err(|_| format!("invalid number '{raw}'"))?,
+            )
+        }
+        _ => return Err("empty expression".to_string()),
+    };
+
+    let mut filters = Vec::new();
+    while pos < tokens.len() {
+        if tokens[pos] != Token::Pipe {
+            return Err("expected '|' between filters".to_string());
+        }
+        pos += 1;
+        let Some(Token::Ident(name)) = tokens.get(pos) else {
+            return Err("expected a filter name after '|'".to_string());
+        };
+        pos += 1;
+        let filter_args = if tokens.get(pos) == Some(&Token::LParen) {
+            pos += 1;
+            args(&tokens, &mut pos)?
+        } else {
+            Vec::new()
+        };
+        filters.push((name.clone(), filter_args));
+    }
+    Ok(Expr { primary, filters })
+}
+
+/// Whether `body` is a function/filter expression rather than a plain
+/// variable name — used to report such placeholders when they are left
+/// unevaluated.
+pub fn is_expression(body: &str) -> bool {
+    parse(body).is_ok_and(|e| !matches!(e.primary, Primary::Var(_)) || !e.filters.is_empty())
+}
+
+/// Evaluate a placeholder body. `Ok(None)` means "a plain variable that isn't
+/// defined" or "not an expression at all" (free text that merely contains
+/// `{{`) — the caller leaves the placeholder verbatim, as before.
+pub fn evaluate(
+    body: &str,
+    variables: &HashMap<String, Value>,
+    ctx: &TemplateContext,
+) -> Result<Option<Value>, String> {
+    let Ok(expr) = parse(body) else {
+        return Ok(None);
+    };
+    let mut current = match &expr.primary {
+        Primary::Var(name) => match variables.get(name) {
+            Some(v) => Some(v.clone()),
+            None => match name.strip_prefix("fake.") {
+                Some(kind) => Some(Value::String(fake_value(kind, ctx)?)),
+                None => None,
+            },
+        },
+        Primary::Call(name, args) => Some(call(name, args, ctx)?),
+        Primary::Literal(v) => Some(v.clone()),
+    };
+    for (name, args) in &expr.filters {
+        current = Some(filter(name, args, current, &expr.primary)?);
+    }
+    Ok(current)
+}
+
+fn arg_str<'a>(args: &'a [Value], i: usize, func: &str) -> Result<Option<&'a str>, String> {
+    match args.get(i) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(other) => Err(format!(
+            "{func}(): argument {} must be a string, got {other}",
+            i + 1
+        )),
+    }
+}
+
+fn arity(func: &str, args: &[Value], min: usize, max: usize) -> Result<(), String> {
+    if (min..=max).contains(&args.len()) {
+        Ok(())
+    } else if min == max {
+        Err(format!(
+            "{func}() takes {min} argument(s), got {}",
+            args.len()
+        ))
+    } else {
+        Err(format!(
+            "{func}() takes {min}-{max} arguments, got {}",
+            args.len()
+        ))
+    }
+}
+
+fn call(name: &str, args: &[Value], ctx: &TemplateContext) -> Result<Value, String> {
+    match name {
+        "uuid" => {
+            arity(name, args, 0, 0)?;
+            let bytes: [u8; 16] = ctx.with_rng(|rng| rng.random());
+            Ok(Value::String(
+                uuid::Builder::from_random_bytes(bytes)
+                    .into_uuid()
+                    .to_string(),
+            ))
+        }
+        "now" => {
+            arity(name, args, 0, 2)?;
+            let format = arg_str(args, 0, name)?.unwrap_or("rfc3339");
+            let offset = arg_str(args, 1, name)?.unwrap_or("0s");
+            let now_ms = ctx
+                .now_unix_millis()
+                .checked_add(parse_offset_ms(offset)?)
+                .ok_or_else(|| format!("time offset '{offset}' is out of range"))?;
+            format_time(now_ms, format)
+        }
+        "env" => {
+            arity(name, args, 1, 2)?;
+            let var = arg_str(args, 0, name)?.unwrap_or_default();
+            match std::env::var(var) {
+                Ok(v) => Ok(Value::String(v)),
+                Err(_) => match args.get(1) {
+                    Some(default) => Ok(default.clone()),
+                    None => Err(format!(
+                        "environment variable '{var}' is not set (pass a default: env(\"{var}\", \"...\"))"
+                    )),
+                },
+            }
+        }
+        "random_int" => {
+            arity(name, args, 2, 2)?;
+            let bound = |v: &Value| {
+                v.as_i64()
+                    .ok_or_else(|| format!("random_int(): bounds must be integers, got {v}"))
+            };
+            let (min, max) = (bound(&args[0])?, bound(&args[1])?);
+            if min > max {
+                return Err(format!("random_int({min}, {max}): min is greater than max"));
+            }
+            Ok(Value::from(ctx.with_rng(|rng| rng.random_range(min..=max))))
+        }
+        other => Err(format!("unknown function '{other}()'")),
+    }
+}
+
+/// `+5m`, `-1h30m`, `+500ms`, `2d` → milliseconds.
+fn parse_offset_ms(offset: &str) -> Result<i64, String> {
+    let trimmed = offset.trim();
+    let (sign, rest) = match trimmed.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let invalid = || format!("invalid time offset '{offset}' (expected e.g. \"+5m\", \"-1h30m\")");
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+    let mut total: i64 = 0;
+    let mut digits = String::new();
+    let mut chars = rest.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let n: i64 = digits.parse().map_err(|_| invalid())?;
+        digits.clear();
+        let unit_ms = match c {
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                1
+            }
+            's' => 1_000,
+            'm' => 60_000,
+            'h' => 3_600_000,
+            'd' => 86_400_000,
+            _ => return Err(invalid()),
+        };
+        total = n
+            .checked_mul(unit_ms)
+            .and_then(|ms| total.checked_add(ms))
+            .ok_or_else(|| format!("time offset '{offset}' is out of range"))?;
+    }
+    if !digits.is_empty() {
+        return Err(invalid());
+    }
+    Ok(sign * total)
+}
+
+fn format_time(unix_ms: i64, format: &str) -> Result<Value, String> {
+    let time: DateTime<Utc> = DateTime::from_timestamp_millis(unix_ms)
+        .ok_or_else(|| format!("time {unix_ms}ms is out of range"))?;
+    Ok(match format {
+        "rfc3339" => Value::String(time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+        "rfc3339_ms" => Value::String(time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+        "unix" => Value::from(time.timestamp()),
+        "unix_ms" => Value::from(time.timestamp_millis()),
+        "date" => Value::String(time.format("%Y-%m-%d").to_string()),
+        custom if custom.contains('%') => {
+            use std::fmt::Write;
+            let mut out = String::new();
+            write!(out, "{}", time.format(custom))
+                .map_err(|_| format!("invalid time format '{custom}'"))?;
+            Value::String(out)
+        }
+        other => {
+            return Err(format!(
+                "unknown time format '{other}' (rfc3339, rfc3339_ms, unix, unix_ms, date, or a strftime pattern)"
+            ));
+        }
+    })
+}
+
+fn fake_value(kind: &str, ctx: &TemplateContext) -> Result<String, String> {
+    use fake::faker::{address::en::*, company::en::*, internet::en::*, job::en::Title};
+    use fake::faker::{lorem::en::*, name::en::*, phone_number::en::PhoneNumber};
+    ctx.with_rng(|rng| {
+        Ok(match kind {
+            "email" => FreeEmail().fake_with_rng(rng),
+            "name" => Name().fake_with_rng(rng),
+            "first_name" => FirstName().fake_with_rng(rng),
+            "last_name" => LastName().fake_with_rng(rng),
+            "username" => Username().fake_with_rng(rng),
+            "phone" => PhoneNumber().fake_with_rng(rng),
+            "company" => CompanyName().fake_with_rng(rng),
+            "job_title" => Title().fake_with_rng(rng),
+            "street" => StreetName().fake_with_rng(rng),
+            "city" => CityName().fake_with_rng(rng),
+            "country" => CountryName().fake_with_rng(rng),
+            "zip" => PostCode().fake_with_rng(rng),
+            "word" => Word().fake_with_rng(rng),
+            "sentence" => Sentence(3..8).fake_with_rng(rng),
+            other => {
+                return Err(format!(
+                    "unknown fake kind 'fake.{other}' (email, name, first_name, last_name, username, phone, company, job_title, street, city, country, zip, word, sentence)"
+                ));
+            }
+        })
+    })
+}
+
+fn as_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// RFC 3986 percent-encoding: everything but unreserved characters.
+fn percent_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for b in text.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+fn filter(
+    name: &str,
+    args: &[Value],
+    input: Option<Value>,
+    primary: &Primary,
+) -> Result<Value, String> {
+    if name == "default" {
+        arity(name, args, 1, 1)?;
+        return Ok(match input {
+            Some(Value::Null) | None => args[0].clone(),
+            Some(v) => v,
+        });
+    }
+    let Some(input) = input else {
+        let Primary::Var(var) = primary else {
+            return Err(format!("'{name}' has no input"));
+        };
+        return Err(format!(
+            "undefined variable '{var}' (add `| default(...)` for a fallback)"
+        ));
+    };
+    arity(name, args, 0, 0)?;
+    let text = as_text(&input);
+    Ok(Value::String(match name {
+        "upper" => text.to_uppercase(),
+        "lower" => text.to_lowercase(),
+        "trim" => text.trim().to_string(),
+        "base64" => base64_encode(text.as_bytes()),
+        "base64url" => base64_encode(text.as_bytes())
+            .replace('+', "-")
+            .replace('/', "_")
+            .trim_end_matches('=')
+            .to_string(),
+        "base64_decode" => {
+            let bytes = base64_decode(text.as_bytes())
+                .ok_or_else(|| format!("base64_decode: '{text}' is not valid base64"))?;
+            String::from_utf8(bytes)
+                .map_err(|_| "base64_decode: decoded bytes are not UTF-8".to_string())?
+        }
+        "urlencode" => percent_encode(&text),
+        "json" => input.to_string(),
+        other => {
+            return Err(format!(
+                "unknown filter '{other}' (upper, lower, trim, base64, base64url, base64_decode, urlencode, json, default)"
+            ));
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
+    use super::*;
+    use serde_json::json;
+
+    fn eval(body: &str, vars: &[(&str, Value)]) -> Result<Option<Value>, String> {
+        let vars: HashMap<String, Value> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        evaluate(body, &vars, &TemplateContext::seeded(7, "t.gctf"))
+    }
+
+    #[test]
+    fn plain_undefined_variable_is_not_an_error() {
+        assert_eq!(eval("missing", &[]), Ok(None));
+        assert_eq!(eval("not a var", &[]), Ok(None));
+        assert!(!is_expression("missing"));
+        assert!(is_expression("uuid()"));
+        assert!(is_expression("x | upper"));
+        assert!(!is_expression("not a var"));
+    }
+
+    #[test]
+    fn filters_chain_left_to_right() {
+        assert_eq!(
+            eval("name | trim | upper", &[("name", json!(" ada "))]),
+            Ok(Some(json!("ADA")))
+        );
+        assert_eq!(eval("'hi' | base64", &[]), Ok(Some(json!("aGk="))));
+        assert_eq!(eval("\"aGk=\" | base64_decode", &[]), Ok(Some(json!("hi"))));
+        assert_eq!(
+            eval("'a b&c' | urlencode", &[]),
+            Ok(Some(json!("a%20b%26c")))
+        );
+        assert_eq!(
+            eval("obj | json", &[("obj", json!({"a": 1}))]),
+            Ok(Some(json!("{\"a\":1}")))
+        );
+    }
+
+    #[test]
+    fn default_filter_covers_undefined_variables() {
+        assert_eq!(
+            eval("who | default('anon') | upper", &[]),
+            Ok(Some(json!("ANON")))
+        );
+        assert!(
+            eval("who | upper", &[])
+                .unwrap_err()
+                .contains("undefined variable 'who'")
+        );
+    }
+
+    #[test]
+    fn random_int_keeps_its_number_type_and_bounds() {
+        for _ in 0..20 {
+            let v = eval("random_int(1, 3)", &[]).unwrap().unwrap();
+            assert!((1..=3).contains(&v.as_i64().unwrap()), "{v}");
+        }
+        assert!(eval("random_int(5, 1)", &[]).is_err());
+    }
+
+    #[test]
+    fn seeded_contexts_are_reproducible_per_scope() {
+        let draw = |ctx: &TemplateContext| {
+            ["uuid()", "fake.email", "random_int(0, 1000000)"]
+                .iter()
+                .map(|b| evaluate(b, &HashMap::new(), ctx).unwrap().unwrap())
+                .collect::<Vec<_>>()
+        };
+        let a = draw(&TemplateContext::seeded(42, "a.gctf"));
+        assert_eq!(a, draw(&TemplateContext::seeded(42, "a.gctf")));
+        assert_ne!(a, draw(&TemplateContext::seeded(42, "b.gctf")));
+        assert_ne!(a, draw(&TemplateContext::seeded(43, "a.gctf")));
+        assert!(uuid::Uuid::parse_str(a[0].as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn now_formats_and_offsets() {
+        let unix = eval("now('unix')", &[]).unwrap().unwrap().as_i64().unwrap();
+        let later = eval("now('unix', '+5m')", &[])
+            .unwrap()
+            .unwrap()
+            .as_i64()
+            .unwrap();
+        assert!((299..=301).contains(&(later - unix)), "{unix} {later}");
+        assert_eq!(parse_offset_ms("-1h30m"), Ok(-5_400_000));
+        assert_eq!(parse_offset_ms("+250ms"), Ok(250));
+        assert!(parse_offset_ms("soon").is_err());
+        assert_eq!(format_time(0, "date"), Ok(json!("1970-01-01")));
+        assert_eq!(format_time(0, "rfc3339"), Ok(json!("1970-01-01T00:00:00Z")));
+        assert_eq!(format_time(0, "%H:%M"), Ok(json!("00:00")));
+    }
+
+    #[test]
+    fn out_of_range_offsets_are_errors() {
+        let err = parse_offset_ms("+99999999999999d").unwrap_err();
+        assert!(err.contains("out of range"), "{err}");
+        let err = eval("now('rfc3339', '+9223372036854775807ms')", &[]).unwrap_err();
+        assert!(err.contains("out of range"), "{err}");
+    }
+
+    #[test]
+    fn seeded_now_is_frozen() {
+        assert_eq!(
+            eval("now('rfc3339', '+1h')", &[]),
+            Ok(Some(json!("2025-01-01T01:00:00Z")))
+        );
+        let live = evaluate(
+            "now('unix_ms')",
+            &HashMap::new(),
+            &TemplateContext::unseeded(),
+        )
+        .unwrap()
+        .unwrap();
+        assert!(live.as_i64().unwrap() > SEEDED_NOW_UNIX_MS, "{live}");
+    }
+
+    #[test]
+    fn env_with_and_without_default() {
+        assert_eq!(
+            eval("env('APIF_TEMPLATE_TEST_UNSET', 'fallback')", &[]),
+            Ok(Some(json!("fallback")))
+        );
+        assert!(eval("env('APIF_TEMPLATE_TEST_UNSET')", &[]).is_err());
+        assert!(
+            eval("nope()", &[])
+                .unwrap_err()
+                .contains("unknown function")
+        );
+        assert!(
+            eval("fake.unicorn", &[])
+                .unwrap_err()
+                .contains("unknown fake kind")
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use anyhow::Result;
+use apif_ast::MetadataMap;
 use async_trait::async_trait;
 use futures::stream::Stream;
 use serde_json::Value;
-use std::collections::HashMap;
 use std::pin::Pin;
 
 use crate::config::GrpcClientConfig;
@@ -18,7 +18,7 @@ pub trait GrpcClient: Send {
         requests: Pin<Box<dyn Stream<Item = Value> + Send>>,
     ) -> Result<
         (
-            HashMap<String, String>,
+            MetadataMap,
             Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send>>,
         ),
         GrpcError,
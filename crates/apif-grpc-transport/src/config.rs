@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use apif_ast::MetadataMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum WireProtocol {
@@ -48,12 +48,15 @@ pub struct GrpcClientConfig {
     pub timeout_seconds: u64,
     pub tls_config: Option<TlsConfig>,
     pub proto_config: Option<ProtoConfig>,
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<MetadataMap>,
     pub target_service: Option<String>,
     pub compression: CompressionMode,
     pub connection_id: u64,
     pub protocol: WireProtocol,
     pub version: String,
+    /// Explicit HTTP CONNECT proxy URL (`--proxy` / OPTIONS `proxy:`), taking
+    /// precedence over `HTTP(S)_PROXY`; `"none"` forces a direct connection.
+    pub proxy: Option<String>,
 }
 
 impl Default for GrpcClientConfig {
@@ -69,6 +72,7 @@ impl Default for GrpcClientConfig {
             connection_id: 0,
             protocol: WireProtocol::default(),
             version: "unknown".to_string(),
+            proxy: None,
         }
     }
 }
@@ -0,0 +1,96 @@
+//! Standard-alphabet base64, shared by the HTTP transports (gRPC-Web text
+//! framing, `-bin` metadata) and the CONNECT proxy tunnel.
+
+/// Decode a standard-alphabet base64 body, ignoring ASCII whitespace/newlines.
+/// gRPC-Web text encodes the whole response stream as one base64 blob; returns
+/// `None` on any invalid input so the caller can fall back to the raw bytes.
+pub fn base64_decode(input: &[u8]) -> Option<Vec<u8>> {
+    fn val(b: u8) -> Option<u8> {
+        match b {
+            b'A'..=b'Z' => Some(b - b'A'),
+            b'a'..=b'z' => Some(b - b'a' + 26),
+            b'0'..=b'9' => Some(b - b'0' + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let mut quad = [0u8; 4];
+    let mut n = 0;
+    let mut pads = 0;
+    for &b in input {
+        if b.is_ascii_whitespace() {
+            continue;
+        }
+        if b == b'=' {
+            quad[n] = 0;
+            pads += 1;
+            n += 1;
+        } else {
+            if pads != 0 {
+                return None; // data after padding
+            }
+            quad[n] = val(b)?;
+            n += 1;
+        }
+        if n == 4 {
+            out.push((quad[0] << 2) | (quad[1] >> 4));
+            if pads < 2 {
+                out.push((quad[1] << 4) | (quad[2] >> 2));
+            }
+            if pads < 1 {
+                out.push((quad[2] << 6) | quad[3]);
+            }
+            n = 0;
+            pads = 0;
+        }
+    }
+    if n != 0 {
+        return None; // truncated (non-multiple-of-4) input
+    }
+    Some(out)
+}
+
+/// Standard-alphabet base64 encoder (with `=` padding). Used to emit
+/// gRPC-Web text (base64) request bodies and `Proxy-Authorization` credentials.
+pub fn base64_encode(input: &[u8]) -> String {
+    const A: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b0 = chunk[0] as usize;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as usize;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as usize;
+        out.push(A[b0 >> 2] as char);
+        out.push(A[((b0 & 0x03) << 4) | (b1 >> 4)] as char);
+        out.push(if chunk.len() > 1 {
+            A[((b1 & 0x0f) << 2) | (b2 >> 6)] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            A[b2 & 0x3f] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+/// Decode base64 that MAY omit padding: gRPC binary metadata (`-bin` headers
+/// such as `grpc-status-details-bin`) is base64 and frequently sent unpadded.
+/// Whitespace is ignored; the tail is re-padded so the strict [`base64_decode`]
+/// does the actual decoding.
+pub fn base64_decode_lenient(input: &[u8]) -> Option<Vec<u8>> {
+    let mut cleaned: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .collect();
+    match cleaned.len() % 4 {
+        0 => {}
+        1 => return None, // invalid base64 length
+        r => cleaned.resize(cleaned.len() + (4 - r), b'='),
+    }
+    base64_decode(&cleaned)
+}
@@ -1,13 +1,15 @@
 pub mod client;
 pub mod config;
+pub mod encoding;
 pub mod error;
 pub mod transport;
 pub mod types;
 
+pub use apif_ast::MetadataMap;
 pub use client::{GrpcClient, GrpcClientFactory};
 pub use config::{CompressionMode, GrpcClientConfig, ProtoConfig, TlsConfig, WireProtocol};
 pub use error::GrpcError;
-pub use transport::{TransportResult, default_address_for};
+pub use transport::{TransportResult, default_address_for, unix_socket_path};
 pub use types::{EndpointMeta, GrpcResponse, MethodInfo, RpcMode, StreamItem};
 
 #[cfg(feature = "tonic-transport")]
@@ -1,22 +1,24 @@
-use super::proxy::ProxyEnv;
+use super::proxy::{self, ProxyEnv, ProxyUrl};
 use crate::config::{GrpcClientConfig, TlsConfig};
+use crate::transport::unix_socket_path;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
-use std::sync::{LazyLock, OnceLock};
+use std::sync::LazyLock;
 use std::time::Duration;
 use tokio::sync::RwLock;
-use tonic::transport::{Certificate, Channel, ClientTlsConfig, Identity};
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct ChannelCacheKey {
     address: String,
     timeout_seconds: u64,
     tls_config: Option<TlsConfig>,
+    proxy: Option<String>,
 }
 
 static CHANNEL_CACHE: LazyLock<RwLock<HashMap<ChannelCacheKey, Channel>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
-static PROXY_WARNED: OnceLock<()> = OnceLock::new();
+static PROXY_ENV: LazyLock<ProxyEnv> = LazyLock::new(ProxyEnv::from_env);
 
 /// Upper bound on cached channels. When the cap is reached the cache is
 /// cleared; channels are cheap to recreate (lazy connect) and this prevents
@@ -29,7 +31,7 @@ pub async fn create_channel(config: &GrpcClientConfig) -> Result<Channel> {
     }
     if !config.address.contains(':') {
         return Err(anyhow::anyhow!(
-            "Invalid gRPC address format '{}'. Expected format: host:port or scheme://host:port",
+            "Invalid gRPC address format '{}'. Expected format: host:port, scheme://host:port or unix:/path",
             config.address
         ));
     }
@@ -38,6 +40,7 @@ pub async fn create_channel(config: &GrpcClientConfig) -> Result<Channel> {
         address: config.address.clone(),
         timeout_seconds: config.timeout_seconds,
         tls_config: config.tls_config.clone(),
+        proxy: config.proxy.clone(),
     };
     if config.connection_id > 0 {
         cache_key.address = format!("{}/conn-{}", cache_key.address, config.connection_id);
@@ -51,7 +54,6 @@ pub async fn create_channel(config: &GrpcClientConfig) -> Result<Channel> {
         }
     }
 
-    PROXY_WARNED.get_or_init(|| ProxyEnv::from_env().warn_if_set());
     let channel = if let Some(tls_config) = &config.tls_config {
         tracing::debug!(
             "channel: connecting to {} with TLS (server_name={:?}, client_cert={}, insecure_skip_verify={})",
@@ -90,12 +92,7 @@ async fn create_tls_channel(config: &GrpcClientConfig, tls_config: &TlsConfig) -
         let key_pem = std::fs::read_to_string(key_path).context("Failed to read client key")?;
         tls = tls.identity(Identity::from_pem(cert_pem, key_pem));
     }
-    let addr = if !config.address.contains("://") {
-        format!("https://{}", config.address)
-    } else {
-        config.address.clone()
-    };
-    let endpoint = Channel::from_shared(addr)
+    let endpoint = Channel::from_shared(endpoint_uri(&config.address, "https"))
         .context("Invalid address format")?
         .timeout(Duration::from_secs(config.timeout_seconds))
         .connect_timeout(Duration::from_secs(5));
@@ -106,20 +103,20 @@ async fn create_tls_channel(config: &GrpcClientConfig, tls_config: &TlsConfig) -
         // A custom verifier replaces the default one entirely, so CA
         // certificates/trust anchors must not be set alongside it (tonic
         // rejects that combination). Client identity and SNI still apply.
-        return Ok(endpoint
+        let endpoint = endpoint
             .tls_config_with_verifier(tls, insecure::danger_accept_any_server_cert())
-            .context("Failed to configure TLS (insecure)")?
-            .connect_lazy());
+            .context("Failed to configure TLS (insecure)")?;
+        return connect_lazy(endpoint, config);
     }
     if let Some(ca_path) = &tls_config.ca_cert_path {
         tls = tls.ca_certificate(Certificate::from_pem(
             std::fs::read_to_string(ca_path).context("Failed to read CA certificate")?,
         ));
     }
-    Ok(endpoint
+    let endpoint = endpoint
         .tls_config(tls)
-        .context("Failed to configure TLS")?
-        .connect_lazy())
+        .context("Failed to configure TLS")?;
+    connect_lazy(endpoint, config)
 }
 
 /// Support for `insecure_skip_verify` (explicit user opt-in, equivalent to
@@ -179,16 +176,96 @@ mod insecure {
 }
 
 async fn create_plaintext_channel(config: &GrpcClientConfig) -> Result<Channel> {
-    let addr = if !config.address.contains("://") {
-        format!("http://{}", config.address)
-    } else {
-        config.address.clone()
-    };
-    let endpoint = Channel::from_shared(addr)
+    let endpoint = Channel::from_shared(endpoint_uri(&config.address, "http"))
         .context("Invalid address format")?
         .timeout(Duration::from_secs(config.timeout_seconds))
         .connect_timeout(Duration::from_secs(5));
-    Ok(endpoint.connect_lazy())
+    connect_lazy(endpoint, config)
+}
+
+/// The endpoint URI for `address`: as-is when it carries a scheme, else
+/// prefixed with `scheme`. A `unix:` target's URI only supplies the
+/// `:authority` (`localhost`, as grpc-go and grpc-java send) — the socket
+/// itself is dialled by [`connect_lazy`].
+fn endpoint_uri(address: &str, scheme: &str) -> String {
+    if unix_socket_path(address).is_some() {
+        format!("{scheme}://localhost")
+    } else if !address.contains("://") {
+        format!("{scheme}://{address}")
+    } else {
+        address.to_string()
+    }
+}
+
+/// Lazily connect `endpoint`: over the Unix domain socket named by a `unix:` /
+/// `unix-abstract:` address, through an HTTP CONNECT proxy when one applies
+/// (see [`ProxyEnv::proxy_for`]), or directly over TCP. TLS configured on the
+/// endpoint applies end-to-end in every case.
+fn connect_lazy(endpoint: Endpoint, config: &GrpcClientConfig) -> Result<Channel> {
+    if let Some(path) = unix_socket_path(&config.address) {
+        return connect_unix_lazy(endpoint, &config.address, path);
+    }
+    let uri = endpoint.uri();
+    let tls = config.tls_config.is_some();
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("https") {
+            443
+        } else {
+            80
+        });
+    let target = format!("{}:{}", uri.host().unwrap_or_default(), port);
+    let Some(proxy_url) = PROXY_ENV.proxy_for(
+        config.proxy.as_deref(),
+        uri.host().unwrap_or_default(),
+        port,
+        tls,
+    ) else {
+        return Ok(endpoint.connect_lazy());
+    };
+    let proxy_url = ProxyUrl::parse(proxy_url)?;
+    tracing::debug!(
+        "channel: tunnelling to {} through CONNECT proxy {}",
+        target,
+        proxy_url
+    );
+    let connector = tower::service_fn(move |_: tonic::transport::Uri| {
+        let proxy_url = proxy_url.clone();
+        let target = target.clone();
+        async move {
+            let stream = proxy::connect_via_proxy(&proxy_url, &target).await?;
+            Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(stream))
+        }
+    });
+    Ok(endpoint.connect_with_connector_lazy(connector))
+}
+
+#[cfg(unix)]
+fn connect_unix_lazy(
+    endpoint: Endpoint,
+    _address: &str,
+    path: std::path::PathBuf,
+) -> Result<Channel> {
+    let connector = tower::service_fn(move |_: tonic::transport::Uri| {
+        let path = path.clone();
+        async move {
+            let stream = tokio::net::UnixStream::connect(path).await?;
+            Ok::<_, std::io::Error>(hyper_util::rt::TokioIo::new(stream))
+        }
+    });
+    Ok(endpoint.connect_with_connector_lazy(connector))
+}
+
+#[cfg(not(unix))]
+fn connect_unix_lazy(
+    _endpoint: Endpoint,
+    address: &str,
+    _path: std::path::PathBuf,
+) -> Result<Channel> {
+    Err(anyhow::anyhow!(
+        "Unix domain socket address '{}' is not supported on this platform",
+        address
+    ))
 }
 
 #[cfg(test)]
@@ -232,6 +309,26 @@ mod tests {
         assert!(result.is_ok(), "CA path must be ignored in insecure mode");
     }
 
+    #[test]
+    fn endpoint_uri_keeps_scheme_and_maps_unix_targets_to_localhost() {
+        assert_eq!(
+            endpoint_uri("localhost:4770", "http"),
+            "http://localhost:4770"
+        );
+        assert_eq!(
+            endpoint_uri("https://api.example.com", "http"),
+            "https://api.example.com"
+        );
+        assert_eq!(
+            endpoint_uri("unix:///run/app.sock", "http"),
+            "http://localhost"
+        );
+        assert_eq!(
+            endpoint_uri("unix-abstract:daemon", "https"),
+            "https://localhost"
+        );
+    }
+
     #[tokio::test]
     async fn secure_tls_channel_builds() {
         let config = GrpcClientConfig {
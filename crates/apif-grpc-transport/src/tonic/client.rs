@@ -4,17 +4,18 @@ use async_trait::async_trait;
 use futures::stream::{Stream, StreamExt};
 use prost_reflect::{DynamicMessage, Kind, MessageDescriptor, SerializeOptions};
 use serde_json::Value;
-use std::collections::HashMap;
 use std::pin::Pin;
 use std::str::FromStr;
 use std::sync::Arc;
 use tonic::codec::CompressionEncoding;
-use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+use tonic::metadata::{Binary, MetadataKey, MetadataMap, MetadataValue};
 use tonic::transport::Uri;
 use tonic::{Request, Status};
 
+use crate::MetadataMap as Metadata;
 use crate::client::GrpcClient;
 use crate::config::{CompressionMode, GrpcClientConfig};
+use crate::encoding::{base64_decode_lenient, base64_encode};
 use crate::error::GrpcError;
 use crate::tonic::channel::create_channel;
 use crate::tonic::codec::DynamicCodec;
@@ -62,7 +63,7 @@ impl GrpcClient for TonicGrpcClient {
         requests: Pin<Box<dyn Stream<Item = Value> + Send>>,
     ) -> Result<
         (
-            HashMap<String, String>,
+            Metadata,
             Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send>>,
         ),
         GrpcError,
@@ -96,7 +97,7 @@ impl GrpcClient for TonicGrpcClient {
 
         let mut result: Result<
             (
-                HashMap<String, String>,
+                Metadata,
                 Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send>>,
             ),
             GrpcError,
@@ -129,13 +130,13 @@ impl GrpcClient for TonicGrpcClient {
                 req.metadata_mut(),
                 self.config.metadata.as_ref(),
                 &self.config.version,
-            );
+            )?;
             result = if is_ss {
                 client
                     .streaming(req, path, codec)
                     .await
                     .map(|r| {
-                        let h = metadata_map_to_hashmap(r.metadata());
+                        let h = metadata_from_tonic(r.metadata());
                         let s = streaming_response_to_items(
                             r.into_inner(),
                             Some(conversion_error.clone()),
@@ -175,12 +176,12 @@ impl GrpcClient for TonicGrpcClient {
                     req.metadata_mut(),
                     self.config.metadata.as_ref(),
                     &self.config.version,
-                );
+                )?;
                 client
                     .server_streaming(req, path, codec)
                     .await
                     .map(|r| {
-                        let h = metadata_map_to_hashmap(r.metadata());
+                        let h = metadata_from_tonic(r.metadata());
                         let s = streaming_response_to_items(r.into_inner(), None);
                         (h, s)
                     })
@@ -194,7 +195,7 @@ impl GrpcClient for TonicGrpcClient {
                     req.metadata_mut(),
                     self.config.metadata.as_ref(),
                     &self.config.version,
-                );
+                )?;
                 match client.streaming(req, path, codec).await {
                     Ok(r) => single_response_to_items(r).await,
                     Err(s) => Err(tonic_status_to_grpc_error(&s)),
@@ -325,7 +326,7 @@ fn streaming_response_to_items(
                 )),
                 Ok(None) => match body.trailers().await {
                     Ok(Some(trailers)) => {
-                        let map = metadata_map_to_hashmap(&trailers);
+                        let map = metadata_from_tonic(&trailers);
                         if map.is_empty() {
                             None
                         } else {
@@ -365,8 +366,8 @@ fn streaming_response_to_items(
 /// emit them as a separate `StreamItem::Trailers`.
 async fn single_response_to_items(
     response: tonic::Response<tonic::Streaming<DynamicMessage>>,
-) -> Result<(HashMap<String, String>, ItemStream), GrpcError> {
-    let headers = metadata_map_to_hashmap(response.metadata());
+) -> Result<(Metadata, ItemStream), GrpcError> {
+    let headers = metadata_from_tonic(response.metadata());
     let mut body = response.into_inner();
     let first = match body.message().await {
         Ok(Some(msg)) => msg,
@@ -383,8 +384,8 @@ async fn single_response_to_items(
     };
     let val = dynamic_message_to_json(&first);
     let trailers = match body.trailers().await {
-        Ok(Some(t)) => metadata_map_to_hashmap(&t),
-        Ok(None) => HashMap::new(),
+        Ok(Some(t)) => metadata_from_tonic(&t),
+        Ok(None) => Metadata::new(),
         Err(status) => return Err(tonic_status_to_grpc_error(&status)),
     };
     let mut items: Vec<Result<StreamItem, GrpcError>> = vec![Ok(StreamItem::Message(val))];
@@ -394,18 +395,17 @@ async fn single_response_to_items(
     Ok((headers, Box::pin(futures::stream::iter(items))))
 }
 
+/// Copy the configured request metadata onto a tonic request. A `-bin` value
+/// that is not valid base64 is an INVALID_ARGUMENT error rather than being
+/// sent (or dropped) silently.
 fn insert_metadata(
     meta: &mut MetadataMap,
-    custom: Option<&HashMap<String, String>>,
+    custom: Option<&Metadata>,
     version: &str,
-) {
+) -> Result<(), GrpcError> {
     let default_ua = format!("grpctestify/{}", version);
     let ua = custom
-        .and_then(|m| {
-            m.iter()
-                .find(|(k, _)| k.eq_ignore_ascii_case("user-agent"))
-                .map(|(_, v)| v.as_str())
-        })
+        .and_then(|m| m.get("user-agent").map(String::as_str))
         .unwrap_or(&default_ua);
     if let Ok(val) = MetadataValue::from_str(ua) {
         meta.insert("user-agent", val);
@@ -415,27 +415,41 @@ fn insert_metadata(
             if k.eq_ignore_ascii_case("user-agent") {
                 continue;
             }
-            if let Ok(key) = MetadataKey::from_str(&k.to_ascii_lowercase())
+            let key = k.to_ascii_lowercase();
+            if Metadata::is_binary_key(&key) {
+                // `-bin` values are written base64 in the test file; tonic
+                // wants the raw bytes and does its own wire encoding.
+                let bytes = base64_decode_lenient(v.as_bytes()).ok_or_else(|| {
+                    GrpcError::new(
+                        3,
+                        format!("binary metadata '{k}' is not valid base64: {v:?}"),
+                    )
+                })?;
+                if let Ok(key) = MetadataKey::<Binary>::from_bytes(key.as_bytes()) {
+                    meta.append_bin(key, MetadataValue::from_bytes(&bytes));
+                }
+            } else if let Ok(key) = MetadataKey::from_str(&key)
                 && let Ok(val) = MetadataValue::from_str(v)
             {
-                meta.insert(key, val);
+                meta.append(key, val);
             }
         }
     }
+    Ok(())
 }
 
-fn metadata_map_to_hashmap(metadata: &MetadataMap) -> HashMap<String, String> {
-    let mut map = HashMap::new();
+/// Convert tonic metadata to the ordered multimap, keeping repeated keys and
+/// rendering `-bin` values as standard base64.
+fn metadata_from_tonic(metadata: &MetadataMap) -> Metadata {
+    let mut map = Metadata::with_capacity(metadata.len());
     for e in metadata.iter() {
         match e {
             tonic::metadata::KeyAndValueRef::Ascii(k, v) => {
-                map.insert(k.to_string(), v.to_str().unwrap_or("?").to_string());
+                map.append(k.to_string(), v.to_str().unwrap_or("?"));
             }
             tonic::metadata::KeyAndValueRef::Binary(k, v) => {
-                map.insert(
-                    k.to_string(),
-                    v.to_bytes().iter().map(|b| format!("{:02x}", b)).collect(),
-                );
+                let bytes = v.to_bytes().unwrap_or_default();
+                map.append(k.to_string(), base64_encode(&bytes));
             }
         }
     }
@@ -693,7 +707,7 @@ mod tests {
     #[test]
     fn insert_metadata_version() {
         let mut meta = MetadataMap::new();
-        insert_metadata(&mut meta, None, "test-version");
+        insert_metadata(&mut meta, None, "test-version").unwrap();
         let ua = meta.get("user-agent").unwrap();
         assert_eq!(ua.to_str().unwrap(), "grpctestify/test-version");
     }
@@ -701,14 +715,41 @@ mod tests {
     #[test]
     fn insert_metadata_with_custom_user_agent() {
         let mut meta = MetadataMap::new();
-        let mut custom = HashMap::new();
-        custom.insert("user-agent".to_string(), "custom-ua/2.0".to_string());
-        custom.insert("x-custom".to_string(), "value1".to_string());
-        insert_metadata(&mut meta, Some(&custom), "test-version");
+        let mut custom = Metadata::new();
+        custom.append("user-agent", "custom-ua/2.0");
+        custom.append("x-custom", "value1");
+        insert_metadata(&mut meta, Some(&custom), "test-version").unwrap();
         assert_eq!(
             meta.get("user-agent").unwrap().to_str().unwrap(),
             "custom-ua/2.0"
         );
         assert_eq!(meta.get("x-custom").unwrap().to_str().unwrap(), "value1");
     }
+
+    #[test]
+    fn insert_metadata_keeps_repeated_keys_and_decodes_binary() {
+        let mut meta = MetadataMap::new();
+        let mut custom = Metadata::new();
+        custom.append("x-tag", "a");
+        custom.append("x-tag", "b");
+        custom.append("x-trace-bin", "AAEC");
+        insert_metadata(&mut meta, Some(&custom), "test-version").unwrap();
+        let tags: Vec<_> = meta
+            .get_all("x-tag")
+            .iter()
+            .map(|v| v.to_str().unwrap())
+            .collect();
+        assert_eq!(tags, ["a", "b"]);
+        let bin = meta.get_bin("x-trace-bin").unwrap().to_bytes().unwrap();
+        assert_eq!(bin.as_ref(), [0u8, 1, 2]);
+
+        let back = metadata_from_tonic(&meta);
+        assert_eq!(back.get_all("x-tag").collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(back.get("x-trace-bin"), Some(&"AAEC".to_string()));
+
+        let mut bad = Metadata::new();
+        bad.append("x-trace-bin", "not base64!");
+        let err = insert_metadata(&mut MetadataMap::new(), Some(&bad), "v").unwrap_err();
+        assert_eq!(err.code, 3);
+    }
 }
@@ -19,15 +19,137 @@ use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, LazyLock};
 use tokio::sync::{Mutex as TokioMutex, RwLock};
 use tonic::Request;
-use tonic_reflection::pb::v1::ServerReflectionRequest;
+use tonic::transport::Channel;
 use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
 use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::{ServerReflectionRequest, ServerReflectionResponse};
+use tonic_reflection::pb::v1alpha;
 
 static DESCRIPTOR_CACHE: LazyLock<RwLock<HashMap<String, Arc<DescriptorPool>>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
 
 static DESCRIPTOR_LOAD_MUTEX: LazyLock<TokioMutex<()>> = LazyLock::new(|| TokioMutex::new(()));
 
+/// Reflection protocol version that last answered at a given address, so only
+/// the first load against a v1alpha-only server pays for the failed v1 probe.
+static REFLECTION_VERSION_CACHE: LazyLock<RwLock<HashMap<String, ReflectionVersion>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Which `ServerReflection` service a server registers. Newer grpc-go (≥1.60)
+/// and grpc-java servers may expose only the stable `grpc.reflection.v1`,
+/// older ones only `grpc.reflection.v1alpha`; the stable one is tried first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReflectionVersion {
+    V1,
+    V1Alpha,
+}
+
+impl ReflectionVersion {
+    pub fn service_name(self) -> &'static str {
+        match self {
+            Self::V1 => "grpc.reflection.v1.ServerReflection",
+            Self::V1Alpha => "grpc.reflection.v1alpha.ServerReflection",
+        }
+    }
+}
+
+/// Reflection client speaking whichever protocol version the server answers.
+///
+/// The two versions are wire-identical (same messages, same field numbers) and
+/// differ only in the service path, so requests and responses are always built
+/// and read as `v1` types and re-encoded for a v1alpha server.
+struct ReflectionClient {
+    v1: ServerReflectionClient<Channel>,
+    v1alpha: v1alpha::server_reflection_client::ServerReflectionClient<Channel>,
+    version: ReflectionVersion,
+}
+
+impl ReflectionClient {
+    /// Build a client for `address`, probing with a `ListServices` request
+    /// (v1 first, then v1alpha on `UNIMPLEMENTED`) unless a version is already
+    /// cached for it. A server that answers neither keeps `V1` uncached, so
+    /// callers see the same empty result they always did.
+    async fn connect(channel: Channel, address: &str) -> Self {
+        let mut client = Self {
+            v1: ServerReflectionClient::new(channel.clone()),
+            v1alpha: v1alpha::server_reflection_client::ServerReflectionClient::new(channel),
+            version: ReflectionVersion::V1,
+        };
+        if let Some(version) = REFLECTION_VERSION_CACHE.read().await.get(address) {
+            client.version = *version;
+            return client;
+        }
+        let probe = MessageRequest::ListServices(String::new());
+        for version in [ReflectionVersion::V1, ReflectionVersion::V1Alpha] {
+            client.version = version;
+            match client.send(address, probe.clone()).await {
+                Ok(_) => {
+                    tracing::debug!("reflection: {} answers {}", address, version.service_name());
+                    REFLECTION_VERSION_CACHE
+                        .write()
+                        .await
+                        .insert(address.to_string(), version);
+                    return client;
+                }
+                Err(status) if status.code() == tonic::Code::Unimplemented => {
+                    tracing::debug!(
+                        "reflection: {} does not implement {}",
+                        address,
+                        version.service_name()
+                    );
+                }
+                Err(status) => {
+                    tracing::debug!("reflection: probe against {} failed: {}", address, status);
+                    break;
+                }
+            }
+        }
+        client.version = ReflectionVersion::V1;
+        client
+    }
+
+    /// Send one request on a fresh reflection stream and return the first
+    /// response message.
+    async fn send(
+        &mut self,
+        host: &str,
+        request: MessageRequest,
+    ) -> std::result::Result<Option<MessageResponse>, tonic::Status> {
+        let req = ServerReflectionRequest {
+            host: host.to_string(),
+            message_request: Some(request),
+        };
+        let msg = match self.version {
+            ReflectionVersion::V1 => {
+                let mut stream = self
+                    .v1
+                    .server_reflection_info(Request::new(futures::stream::iter(vec![req])))
+                    .await?
+                    .into_inner();
+                stream.next().await.transpose()?
+            }
+            ReflectionVersion::V1Alpha => {
+                let req = v1alpha::ServerReflectionRequest::decode(req.encode_to_vec().as_slice())
+                    .map_err(|e| tonic::Status::internal(e.to_string()))?;
+                let mut stream = self
+                    .v1alpha
+                    .server_reflection_info(Request::new(futures::stream::iter(vec![req])))
+                    .await?
+                    .into_inner();
+                match stream.next().await.transpose()? {
+                    Some(resp) => Some(
+                        ServerReflectionResponse::decode(resp.encode_to_vec().as_slice())
+                            .map_err(|e| tonic::Status::internal(e.to_string()))?,
+                    ),
+                    None => None,
+                }
+            }
+        };
+        Ok(msg.and_then(|m| m.message_response))
+    }
+}
+
 pub async fn load_descriptors(config: &GrpcClientConfig) -> Result<Arc<DescriptorPool>> {
     let cache_key = build_cache_key(config);
     {
@@ -71,6 +193,16 @@ pub async fn load_descriptors(config: &GrpcClientConfig) -> Result<Arc<Descripto
     Ok(pool_arc)
 }
 
+/// Drop every pool compiled from local `.proto` or descriptor files so the
+/// next load re-reads them (`run --watch` after a proto edit). Pools fetched
+/// by reflection stay cached.
+pub async fn forget_local_descriptors() {
+    DESCRIPTOR_CACHE
+        .write()
+        .await
+        .retain(|key, _| !key.starts_with("descriptor:") && !key.starts_with("proto:"));
+}
+
 fn build_cache_key(config: &GrpcClientConfig) -> String {
     match &config.proto_config {
         Some(cfg) if cfg.descriptor.is_some() => {
@@ -95,7 +227,8 @@ fn build_cache_key(config: &GrpcClientConfig) -> String {
     }
 }
 
-fn load_from_descriptor_file(path: &str) -> Result<DescriptorPool> {
+/// Load a pool (well-known types included) from a serialized `FileDescriptorSet`.
+pub fn load_from_descriptor_file(path: &str) -> Result<DescriptorPool> {
     let bytes =
         std::fs::read(path).with_context(|| format!("Failed to read descriptor file: {}", path))?;
     let set = prost_types::FileDescriptorSet::decode(bytes.as_slice())
@@ -109,7 +242,8 @@ fn load_from_descriptor_file(path: &str) -> Result<DescriptorPool> {
     Ok(pool)
 }
 
-fn load_from_proto_files(files: &[String], import_paths: &[String]) -> Result<DescriptorPool> {
+/// Compile `.proto` sources with protox into a pool (well-known types included).
+pub fn load_from_proto_files(files: &[String], import_paths: &[String]) -> Result<DescriptorPool> {
     let fds = protox::compile(files, import_paths)
         .map_err(|e| anyhow!("Failed to compile proto files: {}", e))?;
     let mut pool = new_pool_with_wkt();
@@ -118,27 +252,15 @@ fn load_from_proto_files(files: &[String], import_paths: &[String]) -> Result<De
     Ok(pool)
 }
 
-async fn list_services(
-    client: &mut ServerReflectionClient<tonic::transport::Channel>,
-    host: &str,
-) -> Vec<String> {
-    let req = ServerReflectionRequest {
-        host: host.to_string(),
-        message_request: Some(MessageRequest::ListServices("".to_string())),
-    };
-    let Ok(stream) = client
-        .server_reflection_info(Request::new(futures::stream::iter(vec![req])))
-        .await
-    else {
-        return Vec::new();
-    };
-    let mut stream = stream.into_inner();
+async fn list_services(client: &mut ReflectionClient, host: &str) -> Vec<String> {
     let mut out = Vec::new();
-    if let Some(Ok(msg)) = stream.next().await
-        && let Some(tonic_reflection::pb::v1::server_reflection_response::MessageResponse::ListServicesResponse(resp)) = msg.message_response {
+    if let Ok(Some(MessageResponse::ListServicesResponse(resp))) = client
+        .send(host, MessageRequest::ListServices(String::new()))
+        .await
+    {
         for s in resp.service {
-            if s.name != "grpc.reflection.v1alpha.ServerReflection"
-                && s.name != "grpc.reflection.v1.ServerReflection"
+            if s.name != ReflectionVersion::V1Alpha.service_name()
+                && s.name != ReflectionVersion::V1.service_name()
             {
                 out.push(s.name);
             }
@@ -148,7 +270,7 @@ async fn list_services(
 }
 
 async fn fetch_descriptors(
-    client: &mut ServerReflectionClient<tonic::transport::Channel>,
+    client: &mut ReflectionClient,
     host: &str,
     seed: Vec<String>,
 ) -> HashMap<String, FileDescriptorProto> {
@@ -160,35 +282,29 @@ async fn fetch_descriptors(
             continue;
         }
         let req = if sym.ends_with(".proto") {
-            ServerReflectionRequest {
-                host: host.to_string(),
-                message_request: Some(MessageRequest::FileByFilename(sym.clone())),
-            }
+            MessageRequest::FileByFilename(sym.clone())
         } else {
-            ServerReflectionRequest {
-                host: host.to_string(),
-                message_request: Some(MessageRequest::FileContainingSymbol(sym.clone())),
-            }
+            MessageRequest::FileContainingSymbol(sym.clone())
         };
-        let mut stream = match client
-            .server_reflection_info(Request::new(futures::stream::iter(vec![req])))
-            .await
-        {
-            Ok(s) => s.into_inner(),
+        let resp = match client.send(host, req).await {
+            Ok(resp) => resp,
             Err(_) => continue,
         };
-        if let Some(Ok(msg)) = stream.next().await
-            && let Some(tonic_reflection::pb::v1::server_reflection_response::MessageResponse::FileDescriptorResponse(resp)) = msg.message_response {
-                for b in resp.file_descriptor_proto {
-                    if let Ok(fd) = FileDescriptorProto::decode(b.as_slice()) {
-                        if let Some(name) = &fd.name {
-                            if processed.insert(name.clone()) {
-                                let deps = fd.dependency.clone();
-                                fd_bytes.insert(name.clone(), fd);
-                                for dep in &deps { if !processed.contains(dep) { files_to_process.push(dep.clone()); } }
+        if let Some(MessageResponse::FileDescriptorResponse(resp)) = resp {
+            for b in resp.file_descriptor_proto {
+                if let Ok(fd) = FileDescriptorProto::decode(b.as_slice()) {
+                    if let Some(name) = &fd.name {
+                        if processed.insert(name.clone()) {
+                            let deps = fd.dependency.clone();
+                            fd_bytes.insert(name.clone(), fd);
+                            for dep in &deps {
+                                if !processed.contains(dep) {
+                                    files_to_process.push(dep.clone());
+                                }
                             }
                         }
                     }
+                }
             }
         }
     }
@@ -197,8 +313,8 @@ async fn fetch_descriptors(
 
 async fn load_via_reflection(config: &GrpcClientConfig) -> Result<DescriptorPool> {
     let channel = create_channel(config).await?;
-    let mut client = ServerReflectionClient::new(channel);
     let host = config.address.clone();
+    let mut client = ReflectionClient::connect(channel, &host).await;
 
     let seed = if let Some(target) = &config.target_service {
         vec![target.clone()]
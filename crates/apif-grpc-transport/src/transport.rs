@@ -1,5 +1,5 @@
+use apif_ast::MetadataMap;
 use serde_json::Value;
-use std::collections::HashMap;
 
 use crate::config::WireProtocol;
 use crate::error::GrpcError;
@@ -7,8 +7,8 @@ use crate::error::GrpcError;
 #[derive(Debug, Default)]
 pub struct TransportResult {
     pub messages: Vec<Value>,
-    pub headers: HashMap<String, String>,
-    pub trailers: HashMap<String, String>,
+    pub headers: MetadataMap,
+    pub trailers: MetadataMap,
     /// Structured status carried verbatim across the transport boundary — no
     /// format-then-reparse. Producers build it directly from `tonic::Status`
     /// (grpc) or the parsed Connect/grpc-web error (HTTP).
@@ -22,3 +22,52 @@ pub fn default_address_for(protocol: WireProtocol) -> &'static str {
         WireProtocol::ConnectRpc => "localhost:4769",
     }
 }
+
+/// Socket path for a gRPC `unix:` / `unix-abstract:` target URI, or `None` for
+/// an ordinary `host:port` / `scheme://host:port` address.
+///
+/// Follows the gRPC naming spec: `unix:relative/path`, `unix:/abs/path` and
+/// `unix:///abs/path` name a filesystem socket; `unix-abstract:name` names a
+/// Linux abstract-namespace socket, returned with the leading NUL byte tokio
+/// expects.
+pub fn unix_socket_path(address: &str) -> Option<std::path::PathBuf> {
+    if let Some(name) = address.strip_prefix("unix-abstract:") {
+        return Some(format!("\0{name}").into());
+    }
+    let path = address.strip_prefix("unix:")?;
+    let path = path.strip_prefix("//").unwrap_or(path);
+    (!path.is_empty()).then(|| path.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn unix_socket_path_parses_target_uris() {
+        assert_eq!(
+            unix_socket_path("unix:///run/app.sock"),
+            Some(PathBuf::from("/run/app.sock"))
+        );
+        assert_eq!(
+            unix_socket_path("unix:/run/app.sock"),
+            Some(PathBuf::from("/run/app.sock"))
+        );
+        assert_eq!(
+            unix_socket_path("unix:app.sock"),
+            Some(PathBuf::from("app.sock"))
+        );
+        assert_eq!(
+            unix_socket_path("unix-abstract:grpc-daemon"),
+            Some(PathBuf::from("\0grpc-daemon"))
+        );
+    }
+
+    #[test]
+    fn unix_socket_path_ignores_tcp_addresses() {
+        assert_eq!(unix_socket_path("localhost:4770"), None);
+        assert_eq!(unix_socket_path("https://api.example.com:443"), None);
+        assert_eq!(unix_socket_path("unix:"), None);
+    }
+}
@@ -1,16 +1,16 @@
+use apif_ast::MetadataMap;
 use serde_json::Value;
-use std::collections::HashMap;
 
 #[derive(Debug)]
 pub enum StreamItem {
     Message(Value),
-    Trailers(HashMap<String, String>),
+    Trailers(MetadataMap),
 }
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct GrpcResponse {
-    pub headers: HashMap<String, String>,
-    pub trailers: HashMap<String, String>,
+    pub headers: MetadataMap,
+    pub trailers: MetadataMap,
     pub messages: Vec<Value>,
     pub error: Option<String>,
 }
@@ -434,6 +434,18 @@ fn rule_metadata(rule_id: RuleId) -> Option<&'static RewriteRuleMetadata> {
     REWRITE_RULES.iter().find(|r| r.id == rule_id)
 }
 
+impl RuleId {
+    /// Why the rewrite preserves meaning, e.g. "Boolean identity: ...".
+    pub fn proof_note(self) -> Option<&'static str> {
+        rule_metadata(self).map(|m| m.proof_note)
+    }
+
+    /// When the rule fires.
+    pub fn preconditions(self) -> Option<&'static str> {
+        rule_metadata(self).map(|m| m.preconditions)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OptimizationHint {
     pub rule_id: RuleId,
@@ -2822,7 +2834,7 @@ not not @has_header("x")
             (
                 "status_200_with_header",
                 json!({ "status": 200, "name": "abc-xyz", "x": 1, "items": [1, 2] }),
-                Some(std::collections::HashMap::from([(
+                Some(apif_assert::MetadataMap::from([(
                     "x".to_string(),
                     "1".to_string(),
                 )])),
@@ -2955,7 +2967,7 @@ not (.status == 200)
             (
                 "status_200_with_header",
                 json!({ "status": 200, "name": "abc-xyz", "x": 1, "items": [1, 2], "user": { "id": 1 } }),
-                Some(std::collections::HashMap::from([(
+                Some(apif_assert::MetadataMap::from([(
                     "x".to_string(),
                     "1".to_string(),
                 )])),
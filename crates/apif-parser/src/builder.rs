@@ -39,8 +39,14 @@ impl GctfDocumentBuilder {
         self
     }
 
+    /// Add a REQUEST_HEADERS section. A key may repeat; its values are kept in
+    /// order (see `GctfDocument::get_request_headers`).
     pub fn request_headers(mut self, headers: impl IntoIterator<Item = (String, String)>) -> Self {
-        let headers: OrderedStringMap = headers.into_iter().collect();
+        let mut joined = OrderedStringMap::new();
+        for (key, value) in headers {
+            crate::content_parser::append_header_value(&mut joined, key, value);
+        }
+        let headers = joined;
         if !headers.is_empty() {
             self.push_section(
                 SectionType::RequestHeaders,
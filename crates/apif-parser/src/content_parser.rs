@@ -97,10 +97,11 @@ pub fn parse_section_content(section_type: SectionType, content: &str) -> Result
         }
 
         // Key-value sections
-        SectionType::RequestHeaders
-        | SectionType::Tls
-        | SectionType::Proto
-        | SectionType::Options => {
+        SectionType::RequestHeaders => {
+            let key_values = parse_request_headers_section(content);
+            Ok(SectionContent::KeyValues(key_values))
+        }
+        SectionType::Tls | SectionType::Proto | SectionType::Options => {
             let key_values = parse_key_value_section(content)?;
             Ok(SectionContent::KeyValues(key_values))
         }
@@ -386,6 +387,36 @@ fn parse_key_value_section(content: &str) -> Result<crate::ast::OrderedStringMap
     Ok(key_values)
 }
 
+/// Parse REQUEST_HEADERS. Unlike other key-value sections a repeated key is
+/// legal — gRPC metadata is a multimap — so its values are kept, `\n`-joined
+/// in source order, for `GctfDocument::get_request_headers` to split.
+pub(crate) fn parse_request_headers_section(content: &str) -> crate::ast::OrderedStringMap {
+    let mut key_values = crate::ast::OrderedStringMap::new();
+    for line in content.lines() {
+        if let Some((key, value)) = tokenize_kv_line(line) {
+            append_header_value(&mut key_values, key, value);
+        }
+    }
+    key_values
+}
+
+/// Add one REQUEST_HEADERS value, joining it onto an earlier value of `key`.
+pub(crate) fn append_header_value(
+    headers: &mut crate::ast::OrderedStringMap,
+    key: String,
+    value: String,
+) {
+    match headers.get_mut(&key) {
+        Some(existing) => {
+            existing.push('\n');
+            existing.push_str(&value);
+        }
+        None => {
+            headers.insert(key, value);
+        }
+    }
+}
+
 /// Like `parse_key_value_section`, but an indented line is appended (with its
 /// original indentation) to the previous key's value instead of being
 /// tokenized on its own — needed for `sources:`'s nested YAML list.
@@ -691,6 +722,18 @@ mod tests {
         assert!(err.to_string().contains("duplicate key 'timeout'"), "{err}");
     }
 
+    #[test]
+    fn parse_section_content_request_headers_repeated_key_is_multi_valued() {
+        let input = "x-tag: a\nauthorization: Bearer t\nx-tag: b";
+        let SectionContent::KeyValues(kv) =
+            parse_section_content(SectionType::RequestHeaders, input).unwrap()
+        else {
+            panic!("expected KeyValues");
+        };
+        assert_eq!(kv.get("x-tag"), Some(&"a\nb".to_string()));
+        assert_eq!(kv.len(), 2);
+    }
+
     #[test]
     fn parse_section_content_extract_duplicate_variable_is_an_error() {
         let input = "total = .a\ntotal = .b";
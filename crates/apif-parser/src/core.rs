@@ -304,7 +304,14 @@ pub fn serialize_gctf(doc: &GctfDocument) -> String {
                     sorted.sort_by(|a, b| a.0.cmp(b.0));
                 }
                 for (k, v) in sorted {
-                    let _ = writeln!(output, "{}: {}", k, v);
+                    if section.section_type == SectionType::RequestHeaders {
+                        // Repeated header keys are stored `\n`-joined.
+                        for v in v.split('\n') {
+                            let _ = writeln!(output, "{}: {}", k, v);
+                        }
+                    } else {
+                        let _ = writeln!(output, "{}: {}", k, v);
+                    }
                 }
             }
             SectionContent::Assertions(lines) => {
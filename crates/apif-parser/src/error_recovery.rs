@@ -452,6 +452,11 @@ fn parse_section_content(
                 // tokenizer, same as the strict path's `content_parser.rs` —
                 // this file used to hand-roll its own `.find(':')` here.
                 if let Some((key, value)) = gctf_tokenizer::tokenize_kv_line(line) {
+                    // A repeated REQUEST_HEADERS key is multi-valued metadata.
+                    if section_type == SectionType::RequestHeaders {
+                        crate::content_parser::append_header_value(&mut key_values, key, value);
+                        continue;
+                    }
                     if key_values.contains_key(&key) {
                         diagnostics.warning(
                             DiagnosticCode::DuplicateKey,
@@ -1367,6 +1372,23 @@ x-custom: value
         }
     }
 
+    #[test]
+    fn parse_with_recovery_repeated_request_header_keeps_every_value() {
+        let content = "--- ENDPOINT ---\nsvc.S/M\n\n--- REQUEST_HEADERS ---\nx-tag: a\nx-tag: b\n\n--- REQUEST ---\n{}\n";
+        let result = parse_content_with_recovery(content, "test.gctf");
+        assert!(
+            !result
+                .diagnostics
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("Duplicate key")),
+            "{:?}",
+            result.diagnostics
+        );
+        let headers = result.document.get_request_headers().unwrap();
+        assert_eq!(headers.get_all("x-tag").collect::<Vec<_>>(), ["a", "b"]);
+    }
+
     #[test]
     fn parse_with_recovery_invalid_key_value_syntax() {
         let content = r#"--- TLS ---
@@ -313,8 +313,10 @@ fn validate_content(document: &GctfDocument, errors: &mut Vec<ValidationError>)
         });
     }
 
+    // A templated address (`{{env("API_ADDR")}}`) is only known at run time.
     if let Some(address) = document.get_address(None)
         && !address.contains(':')
+        && !address.contains("{{")
     {
         errors.push(ValidationError {
             message: format!(
@@ -524,10 +526,26 @@ fn validate_content(document: &GctfDocument, errors: &mut Vec<ValidationError>)
                                     });
                                 }
                             }
+                            "proxy" => {
+                                let v = value.trim();
+                                let scheme = v.split_once("://").map(|(s, _)| s);
+                                if v.is_empty()
+                                    || scheme.is_some_and(|s| !s.eq_ignore_ascii_case("http"))
+                                {
+                                    errors.push(ValidationError {
+                                        message: format!(
+                                            "OPTIONS.proxy must be an http://[user:pass@]host:port URL or 'none', got '{}'",
+                                            value
+                                        ),
+                                        line: Some(section.start_line),
+                                        severity: ErrorSeverity::Error,
+                                    });
+                                }
+                            }
                             _ => {
                                 errors.push(ValidationError {
                                     message: format!(
-                                        "Unknown OPTIONS key '{}'. Supported keys: timeout, retry, retry_delay, no_retry, compression",
+                                        "Unknown OPTIONS key '{}'. Supported keys: timeout, retry, retry_delay, no_retry, compression, proxy",
                                         key
                                     ),
                                     // Point at the actual key line, not the
@@ -1122,6 +1140,22 @@ fn validate_structure(document: &GctfDocument, errors: &mut Vec<ValidationError>
             SectionType::Response => {
                 // All known options are supported for RESPONSE section
             }
+            SectionType::Request => {
+                // `partial` is for `mock` request matching; nothing else
+                // applies to a payload that is sent rather than compared.
+                if section.inline_options.with_asserts
+                    || section.inline_options.tolerance.is_some()
+                    || !section.inline_options.redact.is_empty()
+                    || section.inline_options.unordered_arrays
+                {
+                    errors.push(ValidationError {
+                        message: "REQUEST section only supports the partial inline option"
+                            .to_string(),
+                        line: Some(section.start_line),
+                        severity: ErrorSeverity::Warning,
+                    });
+                }
+            }
             SectionType::Error => {
                 if section.inline_options.tolerance.is_some()
                     || !section.inline_options.redact.is_empty()
@@ -1405,6 +1439,18 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn validate_address_allows_templates() {
+        let mut doc = create_test_document();
+        doc.sections[0].content = SectionContent::Single("{{ env(\"API_ADDR\") }}".to_string());
+
+        let errors = validate_document_diagnostics(&doc);
+        assert!(
+            !errors.iter().any(|e| e.message.contains("Invalid address")),
+            "{errors:?}"
+        );
+    }
+
     #[test]
     fn test_validation_passed() {
         let errors = vec![
@@ -2055,7 +2101,7 @@ mod tests {
         assert!(diagnostics.iter().any(|d| {
             d.severity == ErrorSeverity::Warning
                 && d.message
-                    .contains("Unknown OPTIONS key 'dry_run'. Supported keys: timeout, retry, retry_delay, no_retry, compression")
+                    .contains("Unknown OPTIONS key 'dry_run'. Supported keys: timeout, retry, retry_delay, no_retry, compression, proxy")
         }));
     }
 
@@ -2102,6 +2148,7 @@ mod tests {
         options.insert("retry_delay".to_string(), "0.5".to_string());
         options.insert("no_retry".to_string(), "false".to_string());
         options.insert("compression".to_string(), "gzip".to_string());
+        options.insert("proxy".to_string(), "http://proxy.corp:3128".to_string());
         doc.sections.push(Section {
             section_type: SectionType::Options,
             content: SectionContent::KeyValues(options),
@@ -2170,6 +2217,28 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn validate_options_proxy_rejects_non_http_scheme() {
+        let mut doc = create_test_document();
+        let mut options = crate::ast::OrderedStringMap::new();
+        options.insert("proxy".to_string(), "socks5://proxy:1080".to_string());
+        doc.sections.push(Section {
+            section_type: SectionType::Options,
+            content: SectionContent::KeyValues(options),
+            inline_options: InlineOptions::default(),
+            raw_content: "proxy: socks5://proxy:1080".to_string(),
+            start_line: 5,
+            end_line: 6,
+            attributes: Vec::new(),
+            span: SectionSpan::default(),
+        });
+
+        let diagnostics = validate_document_diagnostics(&doc);
+        assert!(diagnostics.iter().any(|d| {
+            d.severity == ErrorSeverity::Error && d.message.contains("OPTIONS.proxy must be")
+        }));
+    }
+
     #[test]
     fn validate_options_kebab_case_keys_accepted_without_error() {
         // The validator still ACCEPTS + value-validates the kebab aliases (no
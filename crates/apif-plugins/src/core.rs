@@ -223,8 +223,10 @@ impl PluginManager {
 
         // Non-deprecated plugins
         self.register(Arc::new(crate::header_extract::HeaderExtractPlugin));
+        self.register(Arc::new(crate::header_extract::HeadersExtractPlugin));
         self.register(Arc::new(crate::header_extract::HasHeaderPlugin));
         self.register(Arc::new(crate::trailer_extract::TrailerExtractPlugin));
+        self.register(Arc::new(crate::trailer_extract::TrailersExtractPlugin));
         self.register(Arc::new(crate::trailer_extract::HasTrailerPlugin));
         self.register(Arc::new(crate::len::LenPlugin));
         self.register(Arc::new(crate::env::EnvPlugin));
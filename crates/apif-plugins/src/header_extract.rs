@@ -147,17 +147,75 @@ impl Plugin for HasHeaderPlugin {
     }
 }
 
+/// Headers plugin - extracts every value of a repeated header
+#[derive(Debug, Clone, Default)]
+pub struct HeadersExtractPlugin;
+
+impl Plugin for HeadersExtractPlugin {
+    fn name(&self) -> &'static str {
+        "headers"
+    }
+
+    fn description(&self) -> &'static str {
+        "Extract all values of a gRPC metadata header as an array"
+    }
+
+    fn signature(&self) -> PluginSignature {
+        PluginSignature {
+            return_type: TypeInfo::Json,
+            arg_types: &[ArgTypeInfo {
+                expected: TypeInfo::String,
+                required: true,
+                default: None,
+            }],
+            purity: PluginPurity::ContextDependent,
+            deterministic: true,
+            idempotent: true,
+            safe_for_rewrite: false,
+            arg_names: &["name"],
+            replacement: None,
+        }
+    }
+
+    fn execute(&self, args: &[Value], context: &PluginContext) -> Result<PluginResult> {
+        let name = match args {
+            [Value::String(name)] => name,
+            [_] => {
+                return Ok(PluginResult::Assertion(AssertionResult::fail(
+                    "@headers argument must be a string",
+                )));
+            }
+            _ => {
+                return Ok(PluginResult::Assertion(AssertionResult::fail(
+                    "@headers requires exactly 1 argument: the header name",
+                )));
+            }
+        };
+
+        // Absent header (or no headers at all) is an empty array, so
+        // `@headers("k") | length == 0` reads naturally.
+        let values = context
+            .headers
+            .map(|m| m.get_all(name).cloned().map(Value::String).collect())
+            .unwrap_or_default();
+
+        Ok(PluginResult::Value(Value::Array(values)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use apif_assert::MetadataMap;
     use std::sync::LazyLock;
 
-    static HEADERS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
-        let mut h = HashMap::new();
-        h.insert("content-type".to_string(), "application/json".to_string());
-        h.insert("authorization".to_string(), "Bearer token123".to_string());
-        h.insert("x-request-id".to_string(), "req-456".to_string());
+    static HEADERS: LazyLock<MetadataMap> = LazyLock::new(|| {
+        let mut h = MetadataMap::new();
+        h.append("content-type", "application/json");
+        h.append("authorization", "Bearer token123");
+        h.append("x-request-id", "req-456");
+        h.append("x-tag", "blue");
+        h.append("X-Tag", "green");
         h
     });
 
@@ -414,4 +472,54 @@ mod tests {
             PluginResult::Assertion(AssertionResult::Fail { .. })
         ));
     }
+
+    #[test]
+    fn headers_plugin_returns_every_value_in_order() {
+        let context = create_context_with_headers();
+
+        let result = HeadersExtractPlugin
+            .execute(&[Value::String("X-TAG".to_string())], &context)
+            .unwrap();
+
+        assert!(matches!(
+            result,
+            PluginResult::Value(Value::Array(values))
+                if values == [Value::String("blue".into()), Value::String("green".into())]
+        ));
+    }
+
+    #[test]
+    fn header_plugin_returns_first_of_repeated_values() {
+        let context = create_context_with_headers();
+
+        let result = HeaderExtractPlugin
+            .execute(&[Value::String("x-tag".to_string())], &context)
+            .unwrap();
+
+        assert!(matches!(result, PluginResult::Value(Value::String(s)) if s == "blue"));
+    }
+
+    #[test]
+    fn headers_plugin_missing_is_empty_array() {
+        for context in [create_context_with_headers(), create_context_no_headers()] {
+            let result = HeadersExtractPlugin
+                .execute(&[Value::String("x-nonexistent".to_string())], &context)
+                .unwrap();
+
+            assert!(matches!(result, PluginResult::Value(Value::Array(v)) if v.is_empty()));
+        }
+    }
+
+    #[test]
+    fn headers_plugin_rejects_bad_args() {
+        let context = create_context_with_headers();
+
+        for args in [vec![], vec![Value::Number(1.into())]] {
+            let result = HeadersExtractPlugin.execute(&args, &context).unwrap();
+            assert!(matches!(
+                result,
+                PluginResult::Assertion(AssertionResult::Fail { .. })
+            ));
+        }
+    }
 }
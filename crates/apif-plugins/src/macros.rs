@@ -174,13 +174,13 @@ mod tests {
         ArgTypeInfo, Plugin, PluginContext, PluginPurity, PluginResult, PluginSignature, TypeInfo,
     };
     use anyhow::Result;
+    use apif_assert::MetadataMap;
     use apif_assert::engine::AssertionResult;
     use serde_json::Value;
-    use std::collections::HashMap;
 
     // A free function (rather than a closure) so the returned reference can be
     // tied to the context's lifetime parameter.
-    fn header_accessor<'a>(ctx: &PluginContext<'a>) -> Option<&'a HashMap<String, String>> {
+    fn header_accessor<'a>(ctx: &PluginContext<'a>) -> Option<&'a MetadataMap> {
         ctx.headers
     }
 
@@ -293,8 +293,8 @@ mod tests {
 
     #[test]
     fn metadata_macro_compiles_and_runs() {
-        let mut headers = HashMap::new();
-        headers.insert("x-id".to_string(), "42".to_string());
+        let mut headers = MetadataMap::new();
+        headers.append("x-id", "42");
         let ctx = PluginContext::new(&Value::Null).with_headers(Some(&headers));
 
         let found = MetaHeaderPlugin
@@ -146,17 +146,75 @@ impl Plugin for HasTrailerPlugin {
     }
 }
 
+/// Trailers plugin - extracts every value of a repeated trailer
+#[derive(Debug, Clone, Default)]
+pub struct TrailersExtractPlugin;
+
+impl Plugin for TrailersExtractPlugin {
+    fn name(&self) -> &'static str {
+        "trailers"
+    }
+
+    fn description(&self) -> &'static str {
+        "Extract all values of a gRPC metadata trailer as an array"
+    }
+
+    fn signature(&self) -> PluginSignature {
+        PluginSignature {
+            return_type: TypeInfo::Json,
+            arg_types: &[ArgTypeInfo {
+                expected: TypeInfo::String,
+                required: true,
+                default: None,
+            }],
+            purity: PluginPurity::ContextDependent,
+            deterministic: true,
+            idempotent: true,
+            safe_for_rewrite: false,
+            arg_names: &["name"],
+            replacement: None,
+        }
+    }
+
+    fn execute(&self, args: &[Value], context: &PluginContext) -> Result<PluginResult> {
+        let name = match args {
+            [Value::String(name)] => name,
+            [_] => {
+                return Ok(PluginResult::Assertion(AssertionResult::fail(
+                    "@trailers argument must be a string",
+                )));
+            }
+            _ => {
+                return Ok(PluginResult::Assertion(AssertionResult::fail(
+                    "@trailers requires exactly 1 argument: the trailer name",
+                )));
+            }
+        };
+
+        // Absent trailer (or no trailers at all) is an empty array, so
+        // `@trailers("k") | length == 0` reads naturally.
+        let values = context
+            .trailers
+            .map(|m| m.get_all(name).cloned().map(Value::String).collect())
+            .unwrap_or_default();
+
+        Ok(PluginResult::Value(Value::Array(values)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use apif_assert::MetadataMap;
     use std::sync::LazyLock;
 
-    static TRAILERS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
-        let mut t = HashMap::new();
-        t.insert("x-status".to_string(), "success".to_string());
-        t.insert("x-checksum".to_string(), "abc123".to_string());
-        t.insert("x-processing-time-ms".to_string(), "42".to_string());
+    static TRAILERS: LazyLock<MetadataMap> = LazyLock::new(|| {
+        let mut t = MetadataMap::new();
+        t.append("x-status", "success");
+        t.append("x-checksum", "abc123");
+        t.append("x-processing-time-ms", "42");
+        t.append("x-warning", "slow-backend");
+        t.append("x-warning", "stale-cache");
         t
     });
 
@@ -411,4 +469,54 @@ mod tests {
         let plugin = HasTrailerPlugin;
         assert!(plugin.description().contains("trailer"));
     }
+
+    #[test]
+    fn trailers_plugin_returns_every_value_in_order() {
+        let context = create_context_with_trailers();
+
+        let result = TrailersExtractPlugin
+            .execute(&[Value::String("X-WARNING".to_string())], &context)
+            .unwrap();
+
+        assert!(matches!(
+            result,
+            PluginResult::Value(Value::Array(values))
+                if values == [Value::String("slow-backend".into()), Value::String("stale-cache".into())]
+        ));
+    }
+
+    #[test]
+    fn trailer_plugin_returns_first_of_repeated_values() {
+        let context = create_context_with_trailers();
+
+        let result = TrailerExtractPlugin
+            .execute(&[Value::String("x-warning".to_string())], &context)
+            .unwrap();
+
+        assert!(matches!(result, PluginResult::Value(Value::String(s)) if s == "slow-backend"));
+    }
+
+    #[test]
+    fn trailers_plugin_missing_is_empty_array() {
+        for context in [create_context_with_trailers(), create_context_no_trailers()] {
+            let result = TrailersExtractPlugin
+                .execute(&[Value::String("x-nonexistent".to_string())], &context)
+                .unwrap();
+
+            assert!(matches!(result, PluginResult::Value(Value::Array(v)) if v.is_empty()));
+        }
+    }
+
+    #[test]
+    fn trailers_plugin_rejects_bad_args() {
+        let context = create_context_with_trailers();
+
+        for args in [vec![], vec![Value::Number(1.into())]] {
+            let result = TrailersExtractPlugin.execute(&args, &context).unwrap();
+            assert!(matches!(
+                result,
+                PluginResult::Assertion(AssertionResult::Fail { .. })
+            ));
+        }
+    }
 }
@@ -32,6 +32,7 @@ pub struct ConsoleReporter {
     dots_lock: Mutex<()>,
     dots_count: AtomicUsize,
     results: Mutex<Vec<TestResult>>,
+    compact: bool,
 }
 
 impl ConsoleReporter {
@@ -42,9 +43,66 @@ impl ConsoleReporter {
             dots_lock: Mutex::new(()),
             dots_count: AtomicUsize::new(0),
             results: Mutex::new(Vec::new()),
+            compact: false,
         }
     }
 
+    /// End each run with [`Self::render_compact`] instead of the full
+    /// statistics block — one line per `run --watch` cycle.
+    #[must_use]
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// The outcome on one line, then each failing test with the first line
+    /// of its error.
+    pub fn render_compact(&self, results: &[TestResult], duration_ms: u64) -> String {
+        let dim = style::dim_style();
+        let passed = results
+            .iter()
+            .filter(|r| r.status == TestStatus::Pass)
+            .count();
+        let failures: Vec<&TestResult> =
+            results.iter().filter(|r| r.is_blocking_failure()).collect();
+        let quarantined = results
+            .iter()
+            .filter(|r| r.status == TestStatus::Fail && r.quarantined)
+            .count();
+
+        let mut line = if failures.is_empty() {
+            format!("{} {passed} passed", style::pass_style().apply_to("✓"))
+        } else {
+            format!(
+                "{} {} · {passed} passed",
+                style::fail_style().apply_to("✗"),
+                style::fail_style().apply_to(format!("{} failed", failures.len()))
+            )
+        };
+        if quarantined > 0 {
+            let _ = write!(
+                line,
+                " · {}",
+                style::warn_style().apply_to(format!("{quarantined} quarantined"))
+            );
+        }
+        let _ = write!(
+            line,
+            " · {}",
+            dim.apply_to(style::format_duration_ms(duration_ms))
+        );
+
+        let mut o = String::new();
+        let _ = writeln!(o, "{line}");
+        for r in failures {
+            let _ = writeln!(o, "   • {}", r.meta.name.as_deref().unwrap_or(&r.name));
+            if let Some(first) = r.error_message.as_deref().and_then(|e| e.lines().next()) {
+                let _ = writeln!(o, "     {}", dim.apply_to(first));
+            }
+        }
+        o
+    }
+
     /// Render the end-of-run statistics block as a String.
     #[expect(clippy::too_many_arguments)]
     pub fn render_summary(
@@ -207,10 +265,15 @@ impl ConsoleReporter {
             let _ = writeln!(o);
             let _ = writeln!(
                 o,
-                "   {}  {}  {}",
+                "   {}  {}  {}{}",
                 styled,
                 file,
-                dim.apply_to(style::format_duration_ms(r.duration_ms))
+                dim.apply_to(style::format_duration_ms(r.duration_ms)),
+                if r.quarantined {
+                    dim.apply_to("  [quarantined]").to_string()
+                } else {
+                    String::new()
+                }
             );
 
             if r.assertions.is_empty() {
@@ -219,7 +282,10 @@ impl ConsoleReporter {
                         style::pass_icon().to_string(),
                         "RESPONSE matched".to_string(),
                     ),
-                    TestStatus::Skip => (style::skip_icon().to_string(), "skipped".to_string()),
+                    TestStatus::Skip => (
+                        style::skip_icon().to_string(),
+                        r.error_message.as_deref().unwrap_or("skipped").to_string(),
+                    ),
                     TestStatus::Fail => (
                         style::fail_icon().to_string(),
                         r.error_message.as_deref().unwrap_or("failed").to_string(),
@@ -313,6 +379,93 @@ impl ConsoleReporter {
         o
     }
 
+    /// Failures of quarantined tests, listed apart from the failures that
+    /// count. Empty String when there are none.
+    pub fn render_quarantined(&self, test_results: &[TestResult]) -> String {
+        let failures: Vec<&TestResult> = test_results
+            .iter()
+            .filter(|r| r.status == TestStatus::Fail && r.quarantined)
+            .collect();
+        if failures.is_empty() {
+            return String::new();
+        }
+        let mut o = String::new();
+        let _ = writeln!(
+            o,
+            "{}",
+            style::warn_style().apply_to("🚧 Quarantined Failures (not counted):")
+        );
+        for r in failures {
+            let _ = writeln!(o, "   • {}", r.meta.name.as_deref().unwrap_or(&r.name));
+            if let Some(first) = r.error_message.as_deref().and_then(|m| m.lines().next()) {
+                let _ = writeln!(o, "      Error: {first}");
+            }
+        }
+        let _ = writeln!(o);
+        o
+    }
+
+    /// Per-test spread of a `run --repeat` run: pass rate and latency for
+    /// every test that failed at least once (least reliable first), with its
+    /// failure messages. Empty String when nothing was repeated.
+    pub fn render_repeat(&self, test_results: &[TestResult]) -> String {
+        let mut repeated: Vec<(&TestResult, &apif_state::RepeatStats)> = test_results
+            .iter()
+            .filter_map(|r| r.repeat.as_ref().map(|stats| (r, stats)))
+            .collect();
+        if repeated.is_empty() {
+            return String::new();
+        }
+        repeated.sort_by(|a, b| a.1.pass_rate.total_cmp(&b.1.pass_rate));
+        let dim = style::dim_style();
+
+        let mut o = String::new();
+        let _ = writeln!(o, "🔁 Repeat Results:");
+        let stable = repeated
+            .iter()
+            .filter(|(_, stats)| stats.passed == stats.runs)
+            .count();
+        for (r, stats) in repeated.iter().filter(|(_, s)| s.passed < s.runs) {
+            let rate = format!(
+                "{}/{} passed ({:.0}%)",
+                stats.passed,
+                stats.runs,
+                stats.pass_rate * 100.0
+            );
+            let rate = if stats.passed == 0 {
+                style::fail_style().apply_to(rate)
+            } else {
+                style::warn_style().apply_to(rate)
+            };
+            let _ = writeln!(
+                o,
+                "   • {}  {rate}  {}",
+                r.meta.name.as_deref().unwrap_or(&r.name),
+                dim.apply_to(format!(
+                    "min {} · median {} · max {}",
+                    style::format_duration_ms(stats.min_ms),
+                    style::format_duration_ms(stats.median_ms),
+                    style::format_duration_ms(stats.max_ms)
+                ))
+            );
+            for cluster in &stats.failures {
+                let _ = writeln!(o, "      {}× {}", cluster.count, cluster.message);
+            }
+        }
+        if stable > 0 {
+            let _ = writeln!(
+                o,
+                "   {}",
+                style::pass_style().apply_to(format!(
+                    "{stable} test{} passed every run",
+                    if stable == 1 { "" } else { "s" }
+                ))
+            );
+        }
+        let _ = writeln!(o);
+        o
+    }
+
     /// Slowest whole-test lines (verbose only). Empty String when not verbose
     /// or nothing to show.
     pub fn render_slowest_tests(
@@ -389,6 +542,7 @@ impl super::Reporter for ConsoleReporter {
         if matches!(self.mode, ConsoleMode::Dots) {
             let ch = match result.status {
                 TestStatus::Pass => ".",
+                TestStatus::Fail if result.quarantined => "Q",
                 TestStatus::Fail => "E",
                 TestStatus::Skip => "S",
             };
@@ -418,7 +572,7 @@ impl super::Reporter for ConsoleReporter {
             .count();
         let failed = results_guard
             .iter()
-            .filter(|r| r.status == TestStatus::Fail)
+            .filter(|r| r.is_blocking_failure())
             .count();
         let skipped = results_guard
             .iter()
@@ -427,7 +581,7 @@ impl super::Reporter for ConsoleReporter {
 
         let mut errors = Vec::new();
         for result in results_guard {
-            if result.status == TestStatus::Fail {
+            if result.is_blocking_failure() {
                 let display_name = result.meta.name.as_ref().unwrap_or(&result.name);
                 let mut error_line = format!("{} ({}ms)", display_name, result.duration_ms);
                 if let Some(ref error_msg) = result.error_message {
@@ -445,6 +599,11 @@ impl super::Reporter for ConsoleReporter {
         if matches!(self.mode, ConsoleMode::Verbose) {
             out.push_str(&self.render_verbose(results_guard));
         }
+        if self.compact {
+            out.push_str(&self.render_compact(results_guard, metrics.total_duration_ms));
+            print!("{out}");
+            return Ok(());
+        }
         out.push_str(&self.render_summary(
             total,
             passed,
@@ -454,6 +613,8 @@ impl super::Reporter for ConsoleReporter {
             &errors,
             metrics,
         ));
+        out.push_str(&self.render_quarantined(results_guard));
+        out.push_str(&self.render_repeat(results_guard));
         out.push_str(&self.render_slowest_tests(results_guard, 5));
         out.push_str(&self.render_slowest_assertions(results_guard, 5));
         print!("{out}");
@@ -633,6 +794,50 @@ mod tests {
         assert!(reporter.render_slowest_tests(&results, 5).is_empty());
     }
 
+    #[test]
+    fn render_quarantined_lists_only_quarantined_failures() {
+        let reporter = ConsoleReporter::new(ConsoleMode::Silent, 0, env_info());
+        let results = vec![
+            TestResult::fail("flaky.gctf", "timeout\nmore".into(), 5, None).with_quarantined(true),
+            TestResult::fail("broken.gctf", "boom".into(), 5, None),
+        ];
+        let out = reporter.render_quarantined(&results);
+        assert!(out.contains("flaky.gctf") && out.contains("Error: timeout"));
+        assert!(!out.contains("broken.gctf"));
+        assert!(reporter.render_quarantined(&results[1..]).is_empty());
+    }
+
+    #[test]
+    fn render_repeat_shows_unreliable_tests_with_clusters() {
+        let reporter = ConsoleReporter::new(ConsoleMode::Silent, 0, env_info());
+        let stats = |passed, failures| apif_state::RepeatStats {
+            runs: 10,
+            passed,
+            pass_rate: passed as f64 / 10.0,
+            min_ms: 12,
+            median_ms: 15,
+            max_ms: 40,
+            failures,
+        };
+        let results = vec![
+            TestResult::pass("stable.gctf", 5, None).with_repeat(stats(10, vec![])),
+            TestResult::fail("flaky.gctf", "timeout".into(), 5, None).with_repeat(stats(
+                7,
+                vec![apif_state::FailureCluster {
+                    message: "timeout".into(),
+                    count: 3,
+                }],
+            )),
+        ];
+        let out = reporter.render_repeat(&results);
+        assert!(out.contains("flaky.gctf"), "{out}");
+        assert!(out.contains("7/10 passed (70%)"), "{out}");
+        assert!(out.contains("min 12ms · median 15ms · max 40ms"), "{out}");
+        assert!(out.contains("3× timeout"), "{out}");
+        assert!(!out.contains("stable.gctf"), "{out}");
+        assert!(out.contains("1 test passed every run"), "{out}");
+    }
+
     #[test]
     fn console_reporter_lifecycle_silent() {
         let reporter = ConsoleReporter::new(ConsoleMode::Silent, 2, env_info());
@@ -652,4 +857,19 @@ mod tests {
         let results = apif_state::TestResults::new();
         assert!(reporter.on_suite_end(&results).is_ok());
     }
+
+    #[test]
+    fn compact_summary_lists_failures_on_one_line_each() {
+        let reporter = ConsoleReporter::new(ConsoleMode::Dots, 3, env_info()).with_compact(true);
+        let results = vec![
+            TestResult::pass("ok.gctf", 5, None),
+            TestResult::fail("bad.gctf", "status mismatch\n  expected OK".into(), 5, None),
+            TestResult::fail("flaky.gctf", "timeout".into(), 5, None).with_quarantined(true),
+        ];
+        let out = reporter.render_compact(&results, 1500);
+        assert_eq!(
+            out,
+            "✗ 1 failed · 1 passed · 1 quarantined · 1.50s\n   • bad.gctf\n     status mismatch\n"
+        );
+    }
 }
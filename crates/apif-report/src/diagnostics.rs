@@ -117,7 +117,10 @@ impl Diagnostic {
             file: file.to_string(),
             range: DiagnosticRange {
                 start: Position { line, column: 1 },
-                end: Position { line, column: 1000 },
+                end: Position {
+                    line,
+                    column: DiagnosticRange::WHOLE_LINE_END,
+                },
             },
             severity: DiagnosticSeverity::Error,
             code: code.to_string(),
@@ -132,7 +135,10 @@ impl Diagnostic {
             file: file.to_string(),
             range: DiagnosticRange {
                 start: Position { line, column: 1 },
-                end: Position { line, column: 1000 },
+                end: Position {
+                    line,
+                    column: DiagnosticRange::WHOLE_LINE_END,
+                },
             },
             severity: DiagnosticSeverity::Warning,
             code: code.to_string(),
@@ -147,7 +153,10 @@ impl Diagnostic {
             file: file.to_string(),
             range: DiagnosticRange {
                 start: Position { line, column: 1 },
-                end: Position { line, column: 1000 },
+                end: Position {
+                    line,
+                    column: DiagnosticRange::WHOLE_LINE_END,
+                },
             },
             severity: DiagnosticSeverity::Info,
             code: code.to_string(),
@@ -162,7 +171,10 @@ impl Diagnostic {
             file: file.to_string(),
             range: DiagnosticRange {
                 start: Position { line, column: 1 },
-                end: Position { line, column: 1000 },
+                end: Position {
+                    line,
+                    column: DiagnosticRange::WHOLE_LINE_END,
+                },
             },
             severity: DiagnosticSeverity::Hint,
             code: code.to_string(),
@@ -176,6 +188,47 @@ impl Diagnostic {
         self.hint = Some(hint.to_string());
         self
     }
+
+    /// Narrows a whole-line diagnostic to where `needle` first occurs in
+    /// `line_text` (the source of its line). Columns are 1-based character
+    /// offsets, the end one exclusive; when `needle` isn't on the line the
+    /// range is left alone.
+    pub fn narrowed_to(mut self, line_text: &str, needle: &str) -> Self {
+        if needle.is_empty() {
+            return self;
+        }
+        if let Some(byte_start) = line_text.find(needle) {
+            let start = line_text[..byte_start].chars().count() + 1;
+            self.range.start.column = start;
+            self.range.end.column = start + needle.chars().count();
+        }
+        self
+    }
+
+    /// Attaches a suggested rewrite: replace this diagnostic's range with
+    /// `new_text`.
+    pub fn with_replacement(mut self, title: &str, new_text: &str) -> Self {
+        self.quick_fix = Some(QuickFix {
+            title: title.to_string(),
+            edits: vec![TextEdit {
+                range: self.range.clone(),
+                new_text: new_text.to_string(),
+            }],
+        });
+        self
+    }
+}
+
+impl DiagnosticRange {
+    /// `Diagnostic::error` and friends only know the line; their range
+    /// spans it with this placeholder end column.
+    pub const WHOLE_LINE_END: usize = 1000;
+
+    pub fn is_whole_line(&self) -> bool {
+        self.start.line == self.end.line
+            && self.start.column == 1
+            && self.end.column == Self::WHOLE_LINE_END
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +268,28 @@ mod tests {
         assert_eq!(d.hint, Some("try fixing X".into()));
     }
 
+    #[test]
+    fn diagnostic_narrowed_to_counts_characters() {
+        let d = Diagnostic::warning("t.gctf", "SEM_D001", "msg", 3)
+            .narrowed_to("  .name == \"é\" && @uuid(.id)", "@uuid(.id)");
+        assert_eq!(d.range.start.column, 19);
+        assert_eq!(d.range.end.column, 29);
+        assert!(!d.range.is_whole_line());
+
+        let untouched = Diagnostic::warning("t.gctf", "X", "msg", 3).narrowed_to("abc", "zzz");
+        assert!(untouched.range.is_whole_line());
+    }
+
+    #[test]
+    fn diagnostic_with_replacement_edits_its_range() {
+        let d = Diagnostic::warning("t.gctf", "OPT_B001", "msg", 2)
+            .narrowed_to("@is_uuid(.id) == true", "@is_uuid(.id) == true")
+            .with_replacement("Simplify", "@is_uuid(.id)");
+        let fix = d.quick_fix.unwrap();
+        assert_eq!(fix.edits[0].range.end.column, 22);
+        assert_eq!(fix.edits[0].new_text, "@is_uuid(.id)");
+    }
+
     #[test]
     fn diagnostic_serialization() {
         let d = Diagnostic::error("test.gctf", "E001", "msg", 5).with_hint("hint");
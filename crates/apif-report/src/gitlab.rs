@@ -0,0 +1,86 @@
+//! GitLab Code Quality report (the Code Climate issue subset GitLab reads)
+//! for `check`/`fmt` diagnostics, so merge requests show them inline.
+//!
+//! GitLab matches issues between the source and target branch by
+//! `fingerprint`, so it's built from what identifies the problem — file,
+//! code, message — and not the line, which moves with unrelated edits.
+//! Diagnostics without a file (`<suite>`) can't be placed and are left out.
+
+use crate::diagnostics::{Diagnostic, DiagnosticSeverity};
+use crate::sarif::is_located;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+pub fn code_quality_report(diagnostics: &[Diagnostic]) -> Value {
+    let mut seen: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let issues: Vec<Value> = diagnostics
+        .iter()
+        .filter(|d| is_located(&d.file))
+        .map(|d| {
+            // The same finding twice in one file still needs two fingerprints.
+            let occurrence = seen
+                .entry((d.file.as_str(), d.code.as_str(), d.message.as_str()))
+                .and_modify(|n| *n += 1)
+                .or_insert(0);
+            let fingerprint = Sha256::digest(
+                format!("{}\0{}\0{}\0{}", d.file, d.code, d.message, occurrence).as_bytes(),
+            )
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+            let mut issue = json!({
+                "type": "issue",
+                "check_name": d.code,
+                "description": d.message,
+                "severity": severity(d.severity),
+                "fingerprint": fingerprint,
+                "location": {
+                    "path": d.file.strip_prefix("./").unwrap_or(&d.file).replace('\\', "/"),
+                    "lines": { "begin": d.range.start.line.max(1) },
+                },
+            });
+            if let Some(hint) = d.hint.as_deref().filter(|h| !h.is_empty()) {
+                issue["content"] = json!({ "body": hint });
+            }
+            issue
+        })
+        .collect();
+    Value::Array(issues)
+}
+
+fn severity(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "major",
+        DiagnosticSeverity::Warning => "minor",
+        DiagnosticSeverity::Info | DiagnosticSeverity::Hint => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_quality_issues_are_placed_and_fingerprinted() {
+        let diagnostics = vec![
+            Diagnostic::error("./t/a.gctf", "PARSE_ERROR", "bad", 3).with_hint("look"),
+            Diagnostic::warning("t/a.gctf", "SEM_C002", "dup", 5),
+            Diagnostic::warning("t/a.gctf", "SEM_C002", "dup", 9),
+            Diagnostic::info("<suite>", "NO_ASSERTS_COVERAGE", "none", 1),
+        ];
+        let report = code_quality_report(&diagnostics);
+        let issues = report.as_array().unwrap();
+        assert_eq!(issues.len(), 3);
+        assert_eq!(issues[0]["location"]["path"], "t/a.gctf");
+        assert_eq!(issues[0]["location"]["lines"]["begin"], 3);
+        assert_eq!(issues[0]["severity"], "major");
+        assert_eq!(issues[0]["content"]["body"], "look");
+        assert_eq!(issues[1]["severity"], "minor");
+        assert_ne!(issues[1]["fingerprint"], issues[2]["fingerprint"]);
+
+        // Moving a finding to another line keeps its fingerprint.
+        let moved = code_quality_report(&[Diagnostic::warning("t/a.gctf", "SEM_C002", "dup", 40)]);
+        assert_eq!(moved[0]["fingerprint"], issues[1]["fingerprint"]);
+    }
+}
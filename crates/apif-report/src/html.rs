@@ -109,6 +109,15 @@ struct TestCard<'a> {
     /// This case's `--data`/`DATASET` row values, when the test was expanded
     /// from a data source.
     row_params: &'a [(String, String)],
+    /// A quarantined test: its failure is shown but doesn't count.
+    quarantined: bool,
+    /// The W3C trace the test ran under (`run --trace`).
+    trace: Option<&'a apif_state::TraceInfo>,
+    /// `"7/10 passed (70%) · min 12ms · median 15ms · max 40ms"` for a test
+    /// run with `--repeat`.
+    repeat_summary: Option<String>,
+    /// Failure messages across the repeated runs, most frequent first.
+    repeat_failures: &'a [apif_state::FailureCluster],
     /// Expanded by default when the test failed — diagnosing a failure is why
     /// this report gets opened, so that card shouldn't need an extra click.
     open: bool,
@@ -136,6 +145,9 @@ struct ReportContext<'a> {
     pass_pct: u32,
     fail_pct: u32,
     skip_pct: u32,
+    /// Failures of quarantined tests, kept out of `failed`.
+    quarantined: usize,
+    quarantined_pct: u32,
     /// Every test, once — failed first (expanded), then the rest (collapsed).
     /// A single list instead of separate "Failed"/"All" sections, so no test
     /// is ever rendered twice.
@@ -157,7 +169,10 @@ fn status_icon_class(status: TestStatus) -> (&'static str, &'static str) {
 }
 
 fn build_test_card(r: &apif_state::TestResult) -> TestCard<'_> {
-    let (status_icon, status_class) = status_icon_class(r.status);
+    let (status_icon, status_class) = match status_icon_class(r.status) {
+        (icon, "status-fail") if r.quarantined => (icon, "status-quarantined"),
+        other => other,
+    };
     let assertions: Vec<AssertionDetail> = r
         .assertions
         .iter()
@@ -185,6 +200,17 @@ fn build_test_card(r: &apif_state::TestResult) -> TestCard<'_> {
             truncated: ex.truncated,
         }
     });
+    let repeat_summary = r.repeat.as_ref().map(|stats| {
+        format!(
+            "{}/{} passed ({:.0}%) · min {} · median {} · max {}",
+            stats.passed,
+            stats.runs,
+            stats.pass_rate * 100.0,
+            crate::style::format_duration_ms(stats.min_ms),
+            crate::style::format_duration_ms(stats.median_ms),
+            crate::style::format_duration_ms(stats.max_ms)
+        )
+    });
     let owner = r.meta.owner.as_deref();
     let error = r.error_message.as_deref();
     let has_detail = owner.is_some()
@@ -193,7 +219,9 @@ fn build_test_card(r: &apif_state::TestResult) -> TestCard<'_> {
         || exchange.is_some()
         || r.retried
         || !r.document_durations_ms.is_empty()
-        || !r.row_params.is_empty();
+        || !r.row_params.is_empty()
+        || repeat_summary.is_some()
+        || r.trace.is_some();
     TestCard {
         status_icon,
         status_class,
@@ -208,7 +236,11 @@ fn build_test_card(r: &apif_state::TestResult) -> TestCard<'_> {
         retried: r.retried,
         document_durations_ms: &r.document_durations_ms,
         row_params: &r.row_params,
-        open: r.status == TestStatus::Fail,
+        quarantined: r.quarantined,
+        trace: r.trace.as_ref(),
+        repeat_summary,
+        repeat_failures: r.repeat.as_ref().map_or(&[], |stats| &stats.failures),
+        open: r.is_blocking_failure(),
         has_detail,
     }
 }
@@ -220,12 +252,14 @@ impl Reporter for HtmlReporter {
         let passed = results.passed();
         let failed = results.failed();
         let skipped = results.skipped();
+        let quarantined = results.quarantined();
         let metrics = results.metrics();
 
         let total_u64 = total as u64;
         let pass_pct = pct_of(passed as u64, total_u64);
         let fail_pct = pct_of(failed as u64, total_u64);
         let skip_pct = pct_of(skipped as u64, total_u64);
+        let quarantined_pct = pct_of(quarantined as u64, total_u64);
 
         let mut by_duration = all.iter().collect::<Vec<_>>();
         by_duration.sort_by_key(|r| Reverse(r.duration_ms));
@@ -281,6 +315,8 @@ impl Reporter for HtmlReporter {
             pass_pct,
             fail_pct,
             skip_pct,
+            quarantined,
+            quarantined_pct,
             tests,
             slowest_tests,
             slowest_assertions,
@@ -754,4 +790,46 @@ mod tests {
         );
         assert!(content.contains("No tests ran"));
     }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn html_reporter_marks_quarantined_failures_and_repeat_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.html");
+        let reporter = HtmlReporter::new(path.clone());
+
+        let mut results = TestResults::new();
+        results.add(TestResult::pass("ok.gctf", 10, None));
+        results.add(
+            TestResult::fail("flaky.gctf", "timeout".into(), 10, None)
+                .with_quarantined(true)
+                .with_repeat(apif_state::RepeatStats {
+                    runs: 10,
+                    passed: 7,
+                    pass_rate: 0.7,
+                    min_ms: 12,
+                    median_ms: 15,
+                    max_ms: 40,
+                    failures: vec![apif_state::FailureCluster {
+                        message: "timeout".into(),
+                        count: 3,
+                    }],
+                }),
+        );
+        reporter.on_suite_end(&results).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(
+            content.contains("hero-pass"),
+            "a quarantined failure doesn't fail the verdict: {content}"
+        );
+        assert!(content.contains("1 quarantined failure"), "{content}");
+        assert!(
+            content.contains(r#"<span class="tag tag-quarantined">quarantined</span>"#),
+            "quarantine badge: {content}"
+        );
+        assert!(content.contains("test-card status-quarantined"));
+        assert!(content.contains("7&#x2f;10 passed (70%) · min 12ms · median 15ms · max 40ms"));
+        assert!(content.contains("3× timeout"));
+    }
 }
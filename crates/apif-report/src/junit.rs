@@ -44,6 +44,9 @@ struct TestCaseBuilder {
     /// parse) — reported as JUnit `<error>` instead of `<failure>`, which is
     /// reserved for an evaluated assertion that didn't hold.
     is_execution_error: bool,
+    /// A quarantined test's failure is reported as `<skipped>` (with the
+    /// failure kept in its body), so CI parsers don't fail the build on it.
+    quarantined: bool,
     tags: Vec<String>,
     extra_properties: Vec<(String, String)>,
 }
@@ -80,6 +83,15 @@ impl TestCaseBuilder {
         }
 
         match self.status {
+            TestStatus::Fail if self.quarantined => {
+                let msg = self.error_message.as_deref().unwrap_or("Test failed");
+                let body = self.failure_body.as_deref().unwrap_or(msg);
+                xml.push_str(&format!(
+                    "      <skipped message=\"Quarantined failure: {}\">{}</skipped>\n",
+                    escape_xml(msg),
+                    escape_xml(body)
+                ));
+            }
             TestStatus::Fail if self.is_execution_error => {
                 // No assertion was evaluated — the run failed before ASSERTS
                 // (connection/timeout/parse/etc), which JUnit models as <error>.
@@ -202,7 +214,7 @@ impl Reporter for JunitReporter {
         let metrics = results.metrics();
         let duration = metrics.total_duration_ms as f64 / 1000.0;
         let total = results.total();
-        let skipped = results.skipped();
+        let skipped = results.skipped() + results.quarantined();
 
         // Split Fail results into JUnit `failures` (an assertion was evaluated
         // and didn't hold) vs `errors` (failed before any assertion ran —
@@ -210,7 +222,7 @@ impl Reporter for JunitReporter {
         let (failures, errors): (usize, usize) = results
             .all()
             .iter()
-            .filter(|r| r.status == TestStatus::Fail)
+            .filter(|r| r.is_blocking_failure())
             .fold((0, 0), |(f, e), r| {
                 if is_execution_error(r) {
                     (f, e + 1)
@@ -245,6 +257,28 @@ impl Reporter for JunitReporter {
             for link in &result.meta.links {
                 extra_properties.push(("link".to_string(), link.clone()));
             }
+            if result.quarantined {
+                extra_properties.push(("quarantined".to_string(), "true".to_string()));
+            }
+            if let Some(trace) = &result.trace {
+                extra_properties.push(("trace_id".to_string(), trace.trace_id.clone()));
+                if let Some(url) = &trace.url {
+                    extra_properties.push(("trace_url".to_string(), url.clone()));
+                }
+            }
+            if let Some(repeat) = &result.repeat {
+                extra_properties.extend([
+                    ("repeat.runs".to_string(), repeat.runs.to_string()),
+                    ("repeat.passed".to_string(), repeat.passed.to_string()),
+                    (
+                        "repeat.pass_rate".to_string(),
+                        format!("{:.3}", repeat.pass_rate),
+                    ),
+                    ("repeat.min_ms".to_string(), repeat.min_ms.to_string()),
+                    ("repeat.median_ms".to_string(), repeat.median_ms.to_string()),
+                    ("repeat.max_ms".to_string(), repeat.max_ms.to_string()),
+                ]);
+            }
 
             let tc = TestCaseBuilder {
                 name: display_name.to_string(),
@@ -255,6 +289,7 @@ impl Reporter for JunitReporter {
                 failure_body: build_failure_body(result),
                 system_out: build_system_out(result),
                 is_execution_error: is_execution_error(result),
+                quarantined: result.quarantined,
                 tags: result.meta.tags.clone(),
                 extra_properties,
             };
@@ -317,6 +352,7 @@ mod tests {
             failure_body: None,
             system_out: None,
             is_execution_error: false,
+            quarantined: false,
             tags: vec![],
             extra_properties: vec![],
         };
@@ -339,6 +375,7 @@ mod tests {
             failure_body: None,
             system_out: None,
             is_execution_error: false,
+            quarantined: false,
             tags: vec![],
             extra_properties: vec![],
         };
@@ -368,6 +405,7 @@ mod tests {
             failure_body: None,
             system_out: None,
             is_execution_error: false,
+            quarantined: false,
             tags: vec![],
             extra_properties: vec![],
         };
@@ -387,6 +425,7 @@ mod tests {
             failure_body: None,
             system_out: None,
             is_execution_error: false,
+            quarantined: false,
             tags: vec![],
             extra_properties: vec![],
         };
@@ -405,6 +444,7 @@ mod tests {
             failure_body: None,
             system_out: None,
             is_execution_error: false,
+            quarantined: false,
             tags: vec!["api".into(), "smoke".into()],
             extra_properties: vec![("env".into(), "prod".into())],
         };
@@ -568,4 +608,47 @@ mod tests {
         assert!(content.contains("failure"));
         assert!(content.contains("error msg"));
     }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn junit_quarantined_failure_is_skipped_not_failed() {
+        use crate::Reporter;
+        use apif_state::{RepeatStats, TestResult};
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test_junit_quarantine.xml");
+        let reporter = JunitReporter::new(path.clone());
+        let mut results = TestResults::new();
+        results.add(
+            TestResult::fail("flaky.gctf", "timeout".into(), 100, None)
+                .with_quarantined(true)
+                .with_repeat(RepeatStats {
+                    runs: 4,
+                    passed: 3,
+                    pass_rate: 0.75,
+                    min_ms: 10,
+                    median_ms: 20,
+                    max_ms: 90,
+                    failures: vec![],
+                }),
+        );
+        reporter.on_suite_end(&results).unwrap();
+
+        let xml = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            xml.contains("failures=\"0\" errors=\"0\" skipped=\"1\""),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<skipped message=\"Quarantined failure: timeout\">"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<property name=\"quarantined\" value=\"true\"/>"),
+            "{xml}"
+        );
+        assert!(
+            xml.contains("<property name=\"repeat.pass_rate\" value=\"0.750\"/>"),
+            "{xml}"
+        );
+    }
 }
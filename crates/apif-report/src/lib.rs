@@ -3,9 +3,11 @@
 
 pub mod console;
 pub mod diagnostics;
+pub mod gitlab;
 pub mod html;
 pub mod json;
 pub mod junit;
+pub mod sarif;
 pub mod streaming;
 pub mod style;
 pub mod yaml;
@@ -0,0 +1,290 @@
+//! SARIF 2.1.0 log for `check`/`fmt` diagnostics — what code-scanning
+//! dashboards (GitHub's among them) ingest to annotate the offending lines
+//! of a pull request.
+//!
+//! Every distinct diagnostic code becomes a rule (`tool.driver.rules`) with
+//! the help text the caller supplies; suggested rewrites (`quick_fix`)
+//! become SARIF `fixes`. Diagnostics that aren't about a file (`<suite>`)
+//! have nowhere to point, so they go out as tool notifications instead of
+//! results.
+
+use crate::diagnostics::{Diagnostic, DiagnosticRange, DiagnosticSeverity};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The producing tool, as SARIF names it.
+#[derive(Debug, Clone)]
+pub struct SarifTool {
+    pub name: String,
+    pub version: String,
+    pub information_uri: String,
+}
+
+/// Documentation for one rule (diagnostic code).
+#[derive(Debug, Clone, Default)]
+pub struct RuleHelp {
+    /// One sentence: what the rule flags.
+    pub summary: String,
+    /// How to fix it; shown in the dashboard's rule panel.
+    pub help: String,
+    /// Where the rule is documented in full.
+    pub help_uri: Option<String>,
+}
+
+/// Whether `file` names a real artifact rather than a pseudo-location
+/// such as `<suite>`.
+pub fn is_located(file: &str) -> bool {
+    !file.starts_with('<')
+}
+
+/// Builds the SARIF log. `rule_help` documents each diagnostic code.
+pub fn sarif_log(
+    tool: &SarifTool,
+    diagnostics: &[Diagnostic],
+    rule_help: impl Fn(&str) -> RuleHelp,
+) -> Value {
+    // Rule level = the most severe use of the code, so a dashboard that
+    // filters by rule doesn't hide errors behind a warning-level default.
+    let mut rules: BTreeMap<&str, DiagnosticSeverity> = BTreeMap::new();
+    for d in diagnostics {
+        rules
+            .entry(d.code.as_str())
+            .and_modify(|s| {
+                if severity_rank(d.severity) < severity_rank(*s) {
+                    *s = d.severity;
+                }
+            })
+            .or_insert(d.severity);
+    }
+    let rule_index: BTreeMap<&str, usize> =
+        rules.keys().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let rule_descriptors: Vec<Value> = rules
+        .iter()
+        .map(|(id, severity)| {
+            let help = rule_help(id);
+            let mut rule = json!({
+                "id": id,
+                "name": rule_name(id),
+                "shortDescription": { "text": help.summary },
+                "fullDescription": { "text": help.summary },
+                "help": { "text": help.help, "markdown": help.help },
+                "defaultConfiguration": { "level": level(*severity) },
+            });
+            if let Some(uri) = help.help_uri {
+                rule["helpUri"] = json!(uri);
+            }
+            rule
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    let mut notifications = Vec::new();
+    for d in diagnostics {
+        let message = match &d.hint {
+            Some(hint) if !hint.is_empty() => format!("{}\n{}", d.message, hint),
+            _ => d.message.clone(),
+        };
+        if !is_located(&d.file) {
+            notifications.push(json!({
+                "descriptor": { "id": d.code },
+                "level": level(d.severity),
+                "message": { "text": message },
+            }));
+            continue;
+        }
+        let uri = artifact_uri(&d.file);
+        let mut result = json!({
+            "ruleId": d.code,
+            "ruleIndex": rule_index.get(d.code.as_str()).copied().unwrap_or_default(),
+            "level": level(d.severity),
+            "message": { "text": message },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": { "uri": uri },
+                    "region": region(&d.range),
+                }
+            }],
+        });
+        if let Some(fix) = &d.quick_fix {
+            result["fixes"] = json!([{
+                "description": { "text": fix.title },
+                "artifactChanges": [{
+                    "artifactLocation": { "uri": uri },
+                    "replacements": fix.edits.iter().map(|edit| json!({
+                        "deletedRegion": region(&edit.range),
+                        "insertedContent": { "text": edit.new_text },
+                    })).collect::<Vec<_>>(),
+                }],
+            }]);
+        }
+        results.push(result);
+    }
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool.name,
+                    "version": tool.version,
+                    "informationUri": tool.information_uri,
+                    "rules": rule_descriptors,
+                }
+            },
+            "invocations": [{
+                "executionSuccessful": true,
+                "toolExecutionNotifications": notifications,
+            }],
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }]
+    })
+}
+
+fn severity_rank(severity: DiagnosticSeverity) -> u8 {
+    match severity {
+        DiagnosticSeverity::Error => 0,
+        DiagnosticSeverity::Warning => 1,
+        DiagnosticSeverity::Info => 2,
+        DiagnosticSeverity::Hint => 3,
+    }
+}
+
+fn level(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Info | DiagnosticSeverity::Hint => "note",
+    }
+}
+
+/// `SEM_T001` -> `SemT001`, `PARSE_ERROR` -> `ParseError`: SARIF wants
+/// rule names as PascalCase identifiers distinct from the opaque id.
+fn rule_name(id: &str) -> String {
+    id.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let lower = part.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// A whole-line range carries no real columns; SARIF then gets just the
+/// line, which viewers highlight in full.
+fn region(range: &DiagnosticRange) -> Value {
+    if range.is_whole_line() {
+        json!({ "startLine": range.start.line.max(1) })
+    } else {
+        json!({
+            "startLine": range.start.line.max(1),
+            "startColumn": range.start.column.max(1),
+            "endLine": range.end.line.max(1),
+            "endColumn": range.end.column.max(1),
+        })
+    }
+}
+
+/// Relative paths stay relative (resolved against the checkout root by
+/// the consumer); absolute ones become `file://` URIs.
+fn artifact_uri(file: &str) -> String {
+    let path = file.replace('\\', "/");
+    let path = path.strip_prefix("./").unwrap_or(&path);
+    let encoded: String = path
+        .chars()
+        .map(|c| match c {
+            ' ' => "%20".to_string(),
+            '%' => "%25".to_string(),
+            '#' => "%23".to_string(),
+            '?' => "%3F".to_string(),
+            c => c.to_string(),
+        })
+        .collect();
+    if encoded.starts_with('/') {
+        format!("file://{encoded}")
+    } else if encoded.as_bytes().get(1) == Some(&b':') {
+        format!("file:///{encoded}")
+    } else {
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool() -> SarifTool {
+        SarifTool {
+            name: "grpctestify".into(),
+            version: "1.0.0".into(),
+            information_uri: "https://example.test".into(),
+        }
+    }
+
+    fn help(id: &str) -> RuleHelp {
+        RuleHelp {
+            summary: format!("{id} summary"),
+            help: format!("{id} help"),
+            help_uri: None,
+        }
+    }
+
+    #[test]
+    fn sarif_log_maps_rules_results_and_fixes() {
+        let diagnostics = vec![
+            Diagnostic::warning("./tests/a b.gctf", "OPT_B001", "x == true → x", 7)
+                .narrowed_to("  @ok(.x) == true", "@ok(.x) == true")
+                .with_replacement("Simplify", "@ok(.x)"),
+            Diagnostic::error("tests/b.gctf", "PARSE_ERROR", "bad", 1),
+            Diagnostic::warning("tests/c.gctf", "PARSE_ERROR", "meh", 2).with_hint("fix it"),
+            Diagnostic::info("<suite>", "NO_ASSERTS_COVERAGE", "no asserts", 1),
+        ];
+        let log = sarif_log(&tool(), &diagnostics, help);
+        let run = &log["runs"][0];
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let ids: Vec<_> = rules.iter().map(|r| r["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, ["NO_ASSERTS_COVERAGE", "OPT_B001", "PARSE_ERROR"]);
+        assert_eq!(rules[2]["name"], "ParseError");
+        assert_eq!(rules[2]["defaultConfiguration"]["level"], "error");
+        assert_eq!(rules[1]["help"]["text"], "OPT_B001 help");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let opt = &results[0];
+        assert_eq!(opt["ruleIndex"], 1);
+        let location = &opt["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "tests/a%20b.gctf");
+        assert_eq!(location["region"]["startColumn"], 3);
+        assert_eq!(location["region"]["endColumn"], 18);
+        let replacement = &opt["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["insertedContent"]["text"], "@ok(.x)");
+        assert_eq!(replacement["deletedRegion"]["startLine"], 7);
+
+        let whole_line = &results[1]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(whole_line, &json!({ "startLine": 1 }));
+        assert_eq!(results[2]["message"]["text"], "meh\nfix it");
+
+        let notes = run["invocations"][0]["toolExecutionNotifications"]
+            .as_array()
+            .unwrap();
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0]["descriptor"]["id"], "NO_ASSERTS_COVERAGE");
+        assert_eq!(notes[0]["level"], "note");
+    }
+
+    #[test]
+    fn artifact_uri_forms() {
+        assert_eq!(artifact_uri("tests\\x.gctf"), "tests/x.gctf");
+        assert_eq!(artifact_uri("/abs/x.gctf"), "file:///abs/x.gctf");
+        assert_eq!(artifact_uri("C:\\t\\x.gctf"), "file:///C:/t/x.gctf");
+    }
+}
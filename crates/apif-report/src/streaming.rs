@@ -6,12 +6,16 @@ use std::sync::Once;
 
 use super::Reporter;
 
+/// Receives each event line instead of stdout.
+pub type StreamSink = Box<dyn Fn(&str) + Send + Sync>;
+
 pub struct StreamingJsonReporter {
     /// Guarantees `suite_start` is emitted exactly once, and that the emit
     /// completes before any concurrent caller proceeds — so no `test_start`
     /// can ever race ahead of `suite_start` under parallel execution.
     suite_started: Once,
     test_count: usize,
+    sink: Option<StreamSink>,
     #[cfg(test)]
     captured: std::sync::Mutex<Vec<String>>,
 }
@@ -21,11 +25,19 @@ impl StreamingJsonReporter {
         Self {
             suite_started: Once::new(),
             test_count,
+            sink: None,
             #[cfg(test)]
             captured: std::sync::Mutex::new(Vec::new()),
         }
     }
 
+    /// Hand every event line to `sink` instead of writing it to stdout, for
+    /// a run embedded in another process's output (the playground).
+    pub fn with_sink(mut self, sink: StreamSink) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
     /// Emit `suite_start` exactly once, blocking concurrent callers until it has
     /// been written. `Once::call_once` provides the ordering guarantee.
     fn ensure_suite_started(&self) {
@@ -52,6 +64,10 @@ impl StreamingJsonReporter {
         if let Ok(mut cap) = self.captured.lock() {
             cap.push(s.to_string());
         }
+        if let Some(sink) = &self.sink {
+            sink(s);
+            return;
+        }
         let mut stdout = io::stdout().lock();
         if let Err(e) = writeln!(stdout, "{}", s) {
             tracing::warn!("Failed to write streaming JSON to stdout: {e}");
@@ -128,15 +144,19 @@ impl Reporter for StreamingJsonReporter {
     }
 
     fn on_suite_end(&self, results: &TestResults) -> Result<()> {
+        let mut summary = json!({
+            "total": results.total(),
+            "passed": results.passed(),
+            "failed": results.failed(),
+            "skipped": results.skipped(),
+            "duration": results.metrics.total_duration_ms
+        });
+        if results.quarantined() > 0 {
+            summary["quarantined"] = json!(results.quarantined());
+        }
         self.emit(&json!({
             "event": "suite_end",
-            "summary": {
-                "total": results.total(),
-                "passed": results.passed(),
-                "failed": results.failed(),
-                "skipped": results.skipped(),
-                "duration": results.metrics.total_duration_ms
-            },
+            "summary": summary,
             "timestamp": apif_cfg_runtime::now_rfc3339()
         }));
 
@@ -278,4 +298,33 @@ mod tests {
             "suite_start must precede every test_start: {cap:?}"
         );
     }
+
+    #[test]
+    fn streaming_sink_receives_every_event_line() {
+        use std::sync::{Arc, Mutex};
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&lines);
+        let reporter = StreamingJsonReporter::new(1).with_sink(Box::new(move |line| {
+            seen.lock().unwrap().push(line.to_string())
+        }));
+        reporter.on_test_start("test");
+        reporter.on_test_end("test", &TestResult::pass("test.gctf", 10, None));
+        reporter.on_suite_end(&TestResults::new()).unwrap();
+
+        let events: Vec<String> = lines
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["event"].to_string())
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "\"suite_start\"",
+                "\"test_start\"",
+                "\"test_pass\"",
+                "\"suite_end\""
+            ]
+        );
+    }
 }
@@ -2,7 +2,10 @@ pub mod metrics;
 pub mod result;
 
 pub use metrics::ExecutionMetrics;
-pub use result::{AssertionRecord, CapturedExchange, ConfigSummary, TestMeta, TestResult};
+pub use result::{
+    AssertionRecord, CapturedExchange, ConfigSummary, FailureCluster, RepeatStats, TestMeta,
+    TestResult, TraceInfo,
+};
 
 use serde::Serialize;
 
@@ -20,10 +23,18 @@ pub struct TestResults {
     passed: usize,
     failed: usize,
     skipped: usize,
+    /// Failures of quarantined tests — kept out of `failed`, so they don't
+    /// fail the run.
+    #[serde(skip_serializing_if = "is_zero")]
+    quarantined: usize,
     results: Vec<TestResult>,
     pub metrics: ExecutionMetrics,
 }
 
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
 impl Default for TestResults {
     fn default() -> Self {
         Self::new()
@@ -37,6 +48,7 @@ impl TestResults {
             passed: 0,
             failed: 0,
             skipped: 0,
+            quarantined: 0,
             results: Vec::new(),
             metrics: ExecutionMetrics::default(),
         }
@@ -52,6 +64,7 @@ impl TestResults {
         self.total += 1;
         match result.status {
             TestStatus::Pass => self.passed += 1,
+            TestStatus::Fail if result.quarantined => self.quarantined += 1,
             TestStatus::Fail => self.failed += 1,
             TestStatus::Skip => self.skipped += 1,
         }
@@ -69,6 +82,9 @@ impl TestResults {
     pub fn skipped(&self) -> usize {
         self.skipped
     }
+    pub fn quarantined(&self) -> usize {
+        self.quarantined
+    }
 
     pub fn all(&self) -> &[TestResult] {
         &self.results
@@ -158,9 +174,14 @@ mod tests {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         });
         assert_eq!(r.total(), 1);
         assert_eq!(r.skipped(), 1);
@@ -186,4 +207,18 @@ mod tests {
         assert_eq!(m.rpc_calls, 0);
         assert_eq!(m.parallel_jobs, 1);
     }
+
+    #[test]
+    fn quarantined_failures_do_not_fail_the_run() {
+        let mut r = TestResults::new();
+        r.add(TestResult::pass("a.gctf", 10, None));
+        r.add(TestResult::fail("b.gctf", "err".into(), 10, None).with_quarantined(true));
+        assert_eq!(r.total(), 2);
+        assert_eq!(r.failed(), 0);
+        assert_eq!(r.quarantined(), 1);
+        assert!(r.all_passed());
+
+        r.add(TestResult::fail("c.gctf", "err".into(), 10, None));
+        assert!(!r.all_passed());
+    }
 }
@@ -1,6 +1,7 @@
 use crate::TestStatus;
+use apif_ast::MetadataMap;
 use serde::Serialize;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::BTreeMap;
 
 /// Metadata extracted from META section for test reports
 #[derive(Debug, Clone, PartialEq, Serialize, Default)]
@@ -152,7 +153,8 @@ pub struct CapturedExchange {
     /// `BTreeMap`, not `HashMap` — keeps serialized key order stable across
     /// runs (a `HashMap`'s iteration order is randomized per-process), so
     /// JSON/YAML/JUnit/Allure output diffs cleanly and golden-file tests
-    /// don't flake on header ordering.
+    /// don't flake on header ordering. A repeated key's values are joined
+    /// with `", "`, as HTTP combines repeated fields.
     pub headers: BTreeMap<String, String>,
     pub trailers: BTreeMap<String, String>,
     pub response: Vec<serde_json::Value>,
@@ -167,14 +169,26 @@ impl CapturedExchange {
     /// Max total serialized response bytes kept before truncating.
     const MAX_BYTES: usize = 256 * 1024;
 
+    fn combine(metadata: &MetadataMap) -> BTreeMap<String, String> {
+        metadata
+            .keys()
+            .map(|k| {
+                (
+                    k.clone(),
+                    metadata.get_all(k).cloned().collect::<Vec<_>>().join(", "),
+                )
+            })
+            .collect()
+    }
+
     #[must_use]
     pub fn capture(
-        headers: HashMap<String, String>,
-        trailers: HashMap<String, String>,
+        headers: MetadataMap,
+        trailers: MetadataMap,
         messages: Vec<serde_json::Value>,
     ) -> Self {
-        let headers = headers.into_iter().collect();
-        let trailers = trailers.into_iter().collect();
+        let headers = Self::combine(&headers);
+        let trailers = Self::combine(&trailers);
         let mut response = Vec::new();
         let mut total_bytes = 0usize;
         let mut truncated = false;
@@ -226,6 +240,11 @@ pub struct TestResult {
     /// succeed — a `Pass` with `retried = true` is flaky, not a clean pass.
     #[serde(default, skip_serializing_if = "is_false")]
     pub retried: bool,
+    /// How many times the whole test ran under `--retry` before its result
+    /// stood — `1` for a first-try result. `None` when the runner didn't
+    /// record it (setup fixtures, expansion errors).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<u32>,
     /// Real wall-clock duration of each document in a multi-document chain,
     /// in source order. Empty when the runner didn't record per-document
     /// timing. Lets reporters give each chain step its actual timing instead
@@ -243,6 +262,55 @@ pub struct TestResult {
     /// (what happened) — this is the test's shape.
     #[serde(default, skip_serializing_if = "ConfigSummary::is_empty")]
     pub config_summary: ConfigSummary,
+    /// `true` when the test is quarantined (`.grpctestify/quarantine` or
+    /// `META.quarantine`): a failure is still reported, but doesn't fail the run.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub quarantined: bool,
+    /// How the test fared over `run --repeat`. `None` for a single run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat: Option<RepeatStats>,
+    /// Numeric gRPC status code of the last call (`0` for OK). `None` when
+    /// the test never got a status — it failed before connecting, or was
+    /// skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc_status: Option<u32>,
+    /// W3C trace the test ran under (`run --trace`). `None` when tracing is off.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TraceInfo>,
+}
+
+/// A test's W3C trace, so its server-side spans can be found.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceInfo {
+    /// 32 lowercase hex digits, as in `traceparent`.
+    pub trace_id: String,
+    /// The trace in a trace viewer (`run --trace-url`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Outcome spread of one test executed several times (`run --repeat`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepeatStats {
+    /// Runs executed — fewer than requested when `--until-failure` stopped early.
+    pub runs: usize,
+    pub passed: usize,
+    /// `passed / runs`, 0.0–1.0.
+    pub pass_rate: f64,
+    pub min_ms: u64,
+    pub median_ms: u64,
+    pub max_ms: u64,
+    /// Distinct failure messages, most frequent first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<FailureCluster>,
+}
+
+/// Failures of a repeated test that share a message once numbers are masked.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailureCluster {
+    /// The first message seen in this cluster.
+    pub message: String,
+    pub count: usize,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -263,9 +331,14 @@ impl TestResult {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         }
     }
 
@@ -287,9 +360,14 @@ impl TestResult {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         }
     }
 
@@ -311,9 +389,14 @@ impl TestResult {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         }
     }
 
@@ -336,9 +419,22 @@ impl TestResult {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
+        }
+    }
+
+    /// Create a skip result; `reason` says why the test did not run
+    pub fn skip(name: impl Into<String>, reason: String) -> Self {
+        Self {
+            status: TestStatus::Skip,
+            ..Self::fail(name, reason, 0, None)
         }
     }
 
@@ -364,6 +460,13 @@ impl TestResult {
         self
     }
 
+    /// Record how many times the test ran before its result stood.
+    #[must_use]
+    pub fn with_attempts(mut self, attempts: Option<u32>) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
     /// Attach each chain document's real wall-clock duration, in source order.
     #[must_use]
     pub fn with_document_durations(mut self, document_durations_ms: Vec<u64>) -> Self {
@@ -385,12 +488,57 @@ impl TestResult {
         self.config_summary = config_summary;
         self
     }
+
+    /// Mark the test as quarantined: its failure doesn't fail the run.
+    #[must_use]
+    pub fn with_quarantined(mut self, quarantined: bool) -> Self {
+        self.quarantined = quarantined;
+        self
+    }
+
+    /// Attach the outcome spread of a `--repeat` run.
+    #[must_use]
+    pub fn with_repeat(mut self, repeat: RepeatStats) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+
+    /// Attach the gRPC status code the call ended with.
+    #[must_use]
+    pub fn with_grpc_status(mut self, grpc_status: Option<u32>) -> Self {
+        self.grpc_status = grpc_status;
+        self
+    }
+
+    /// Attach the W3C trace the test ran under.
+    #[must_use]
+    pub fn with_trace(mut self, trace: Option<TraceInfo>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// A failure that counts against the run — i.e. not a quarantined one.
+    #[must_use]
+    pub fn is_blocking_failure(&self) -> bool {
+        self.status == TestStatus::Fail && !self.quarantined
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn capture_joins_repeated_metadata_values() {
+        let mut headers = MetadataMap::new();
+        headers.append("x-tag", "a");
+        headers.append("content-type", "application/grpc");
+        headers.append("x-tag", "b");
+        let ex = CapturedExchange::capture(headers, MetadataMap::new(), Vec::new());
+        assert_eq!(ex.headers.get("x-tag").map(String::as_str), Some("a, b"));
+        assert_eq!(ex.headers.len(), 2);
+    }
+
     #[test]
     fn result_pass() {
         let result = TestResult::pass("test.gctf", 100, Some(50));
@@ -550,6 +698,8 @@ mod tests {
             tags: vec!["tag".into()],
             owner: Some("me".into()),
             links: vec!["http://link".into()],
+            depends_on: vec![],
+            quarantine: false,
         };
         let meta = TestMeta::from_file_meta(&file_meta);
         assert_eq!(meta.name, Some("test.gctf".into()));
@@ -1,18 +1,29 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
 //! gRPC method and protobuf message field coverage collector.
 //!
-//! Tracks which gRPC service/method calls were made during test execution
-//! and which protobuf message fields were covered by assertions.
+//! Tracks which gRPC service/method calls were made during test execution,
+//! which protobuf message fields were covered by assertions, and which
+//! variants the calls exercised: the status codes each method returned, the
+//! enum values and the oneof branches seen in requests and responses.
+//!
+//! A [`CoverageReport`] can also be merged with the reports of other shards
+//! of the same suite ([`CoverageReport::merge`]) and checked against minimum
+//! percentages ([`CoverageReport::check`]).
 
 use prost_reflect::{DescriptorPool, MessageDescriptor};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
 use std::sync::{Arc, Mutex};
 
 /// Coverage data for a single file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoverageFile {
     pub uri: String,
+    /// Fully-qualified service name (`package.Service`); `uri` only carries
+    /// the short name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
     pub statements: CoverageStats,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub branches: Option<CoverageStats>,
@@ -29,10 +40,20 @@ pub struct CoverageFile {
 pub struct MethodCoverage {
     pub name: String,
     pub calls: u64,
+    /// Fully-qualified request message type; ties the method's share of the
+    /// field coverage back to its service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_type: Option<String>,
+    /// Fully-qualified response message type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_type: Option<String>,
+    /// Calls per returned gRPC status code name (`OK`, `NotFound`, ...).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub statuses: BTreeMap<String, u64>,
 }
 
 /// Coverage statistics (covered vs total).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct CoverageStats {
     pub covered: usize,
     pub total: usize,
@@ -48,13 +69,38 @@ pub struct MessageFieldCoverage {
     pub missing_fields: Vec<String>,
 }
 
+/// Which values of an enum, or branches of a oneof, requests and responses
+/// used. `name` is the enum's full name, or the oneof's as
+/// `package.Message.oneof`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantCoverage {
+    pub name: String,
+    pub covered: Vec<String>,
+    pub total: usize,
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
 /// Full coverage report with file and message-level statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoverageReport {
     pub files: Vec<CoverageFile>,
     pub messages: Vec<MessageFieldCoverage>,
+    /// Enums reachable from the services' request and response types.
+    #[serde(default)]
+    pub enums: Vec<VariantCoverage>,
+    /// Oneofs reachable from the services' request and response types.
+    #[serde(default)]
+    pub oneofs: Vec<VariantCoverage>,
     pub summary: CoverageStats,
     pub field_summary: CoverageStats,
+    /// Methods that returned at least one non-`OK` status.
+    #[serde(default)]
+    pub error_summary: CoverageStats,
+    #[serde(default)]
+    pub enum_summary: CoverageStats,
+    #[serde(default)]
+    pub oneof_summary: CoverageStats,
 }
 
 /// Collects gRPC method call and protobuf field coverage during test execution.
@@ -63,6 +109,10 @@ pub struct CoverageCollector {
     calls: Arc<Mutex<HashMap<String, HashMap<String, u64>>>>,
     pool: Arc<Mutex<DescriptorPool>>,
     fields_covered: Arc<Mutex<HashMap<String, HashSet<String>>>>,
+    /// `service/method` -> status code name -> calls
+    statuses: Arc<Mutex<HashMap<String, BTreeMap<String, u64>>>>,
+    /// Enum or oneof full name -> value or branch names seen.
+    variants: Arc<Mutex<HashMap<String, HashSet<String>>>>,
 }
 
 impl CoverageCollector {
@@ -71,6 +121,8 @@ impl CoverageCollector {
             calls: Arc::new(Mutex::new(HashMap::new())),
             pool: Arc::new(Mutex::new(DescriptorPool::new())),
             fields_covered: Arc::new(Mutex::new(HashMap::new())),
+            statuses: Arc::new(Mutex::new(HashMap::new())),
+            variants: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -80,6 +132,107 @@ impl CoverageCollector {
         *service_calls.entry(method.to_string()).or_insert(0) += 1;
     }
 
+    /// Record the status a call returned, by code name (`OK`, `NotFound`).
+    pub fn record_status(&self, service: &str, method: &str, code: &str) {
+        let mut statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        let method_statuses = statuses.entry(format!("{service}/{method}")).or_default();
+        *method_statuses.entry(code.to_string()).or_insert(0) += 1;
+    }
+
+    /// Record the enum values and oneof branches a request or response
+    /// message uses. Needs the message's descriptors registered first.
+    pub fn record_variants_from_json(&self, message_type: &str, json: &serde_json::Value) {
+        let pool = self.pool.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(message) = pool.get_message_by_name(message_type) else {
+            return;
+        };
+        let mut variants = self.variants.lock().unwrap_or_else(|e| e.into_inner());
+        Self::extract_variants_from_json(&message, json, &mut variants);
+    }
+
+    /// Walk a proto3-JSON message alongside its descriptor: set fields of a
+    /// oneof are its branches, enum-typed values (by name or number) are
+    /// enum values.
+    fn extract_variants_from_json(
+        message: &MessageDescriptor,
+        json: &serde_json::Value,
+        variants: &mut HashMap<String, HashSet<String>>,
+    ) {
+        // Well-known types have their own JSON mappings (Struct, Any, ...).
+        if message.full_name().starts_with("google.protobuf.") {
+            return;
+        }
+        let serde_json::Value::Object(map) = json else {
+            return;
+        };
+        for (key, value) in map {
+            let Some(field) = message
+                .get_field_by_json_name(key)
+                .or_else(|| message.get_field_by_name(key))
+            else {
+                continue;
+            };
+            if value.is_null() {
+                continue;
+            }
+            if let Some(oneof) = field.containing_oneof().filter(|o| !o.is_synthetic()) {
+                variants
+                    .entry(oneof.full_name().to_string())
+                    .or_default()
+                    .insert(field.name().to_string());
+            }
+
+            let (kind, values): (_, Vec<&serde_json::Value>) = if field.is_map() {
+                let kind = match field.kind() {
+                    prost_reflect::Kind::Message(entry) => entry.map_entry_value_field().kind(),
+                    kind => kind,
+                };
+                (
+                    kind,
+                    value
+                        .as_object()
+                        .into_iter()
+                        .flat_map(|m| m.values())
+                        .collect(),
+                )
+            } else if field.is_list() {
+                (
+                    field.kind(),
+                    value.as_array().into_iter().flatten().collect(),
+                )
+            } else {
+                (field.kind(), vec![value])
+            };
+            for value in values {
+                match &kind {
+                    prost_reflect::Kind::Enum(e) => {
+                        let name = match value {
+                            serde_json::Value::String(name) => {
+                                e.get_value_by_name(name).map(|v| v.name().to_string())
+                            }
+                            serde_json::Value::Number(n) => n
+                                .as_i64()
+                                .and_then(|n| i32::try_from(n).ok())
+                                .and_then(|n| e.get_value(n))
+                                .map(|v| v.name().to_string()),
+                            _ => None,
+                        };
+                        if let Some(name) = name {
+                            variants
+                                .entry(e.full_name().to_string())
+                                .or_default()
+                                .insert(name);
+                        }
+                    }
+                    prost_reflect::Kind::Message(sub) => {
+                        Self::extract_variants_from_json(sub, value, variants);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
     pub fn record_fields_from_json(&self, message_type: &str, json: &serde_json::Value) {
         let mut fields = self
             .fields_covered
@@ -199,13 +352,14 @@ impl CoverageCollector {
             .fields_covered
             .lock()
             .unwrap_or_else(|e| e.into_inner());
+        let statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        let variants = self.variants.lock().unwrap_or_else(|e| e.into_inner());
 
         let mut files = Vec::new();
         let mut messages = Vec::new();
-        let mut total_covered = 0;
-        let mut total_methods = 0;
-        let mut total_fields_covered = 0;
-        let mut total_fields = 0;
+        let mut enum_values = BTreeMap::new();
+        let mut oneof_branches = BTreeMap::new();
+        let mut visited = HashSet::new();
 
         // Method coverage - deduplicated iteration pattern
         let mut services: Vec<_> = pool.services().collect();
@@ -223,30 +377,39 @@ impl CoverageCollector {
             // FQN so services inside a proto `package` aren't reported 0%.
             let called_methods = calls.get(service.full_name()).cloned().unwrap_or_default();
 
-            let covered = methods
-                .iter()
-                .filter(|m| called_methods.get(m.name()).unwrap_or(&0) > &0)
-                .count();
-            let total = methods.len();
-
-            if total > 0 {
-                total_covered += covered;
-                total_methods += total;
+            if !methods.is_empty() {
+                for method in &methods {
+                    for message in [method.input(), method.output()] {
+                        Self::collect_variants(
+                            &message,
+                            &mut visited,
+                            &mut enum_values,
+                            &mut oneof_branches,
+                        );
+                    }
+                }
 
                 let mut method_rows: Vec<MethodCoverage> = methods
                     .iter()
                     .map(|m| MethodCoverage {
                         name: m.name().to_string(),
                         calls: *called_methods.get(m.name()).unwrap_or(&0),
+                        input_type: Some(m.input().full_name().to_string()),
+                        output_type: Some(m.output().full_name().to_string()),
+                        statuses: statuses
+                            .get(&format!("{}/{}", service.full_name(), m.name()))
+                            .cloned()
+                            .unwrap_or_default(),
                     })
                     .collect();
                 method_rows.sort_by(|a, b| a.name.cmp(&b.name));
 
                 files.push(CoverageFile {
                     uri: format!("grpc://{}", service_name),
-                    statements: CoverageStats { covered, total },
+                    service: Some(service.full_name().to_string()),
+                    statements: CoverageStats::default(),
                     branches: None,
-                    functions: Some(CoverageStats { covered, total }),
+                    functions: None,
                     fields: None,
                     methods: method_rows,
                 });
@@ -254,25 +417,13 @@ impl CoverageCollector {
         }
 
         // Message field coverage
-        let mut all_message_types: HashSet<String> = HashSet::new();
-        for message_type in fields_covered.keys() {
-            all_message_types.insert(message_type.clone());
-        }
-
-        let mut sorted_messages: Vec<_> = all_message_types.into_iter().collect();
+        let mut sorted_messages: Vec<_> = fields_covered.keys().cloned().collect();
         sorted_messages.sort();
 
         for message_type in sorted_messages {
-            let covered = fields_covered
-                .get(&message_type)
-                .map(|s| s.len())
-                .unwrap_or(0);
             let total = Self::count_message_fields(&pool, &message_type);
 
             if total > 0 {
-                total_fields_covered += covered.min(total);
-                total_fields += total;
-
                 let covered_set = fields_covered
                     .get(&message_type)
                     .cloned()
@@ -297,134 +448,451 @@ impl CoverageCollector {
             }
         }
 
-        CoverageReport {
+        let variant_rows = |all: BTreeMap<String, Vec<String>>| -> Vec<VariantCoverage> {
+            all.into_iter()
+                .map(|(name, mut values)| {
+                    values.sort();
+                    let seen = variants.get(&name);
+                    let (covered, missing) = values
+                        .iter()
+                        .cloned()
+                        .partition(|v| seen.is_some_and(|s| s.contains(v)));
+                    VariantCoverage {
+                        name,
+                        covered,
+                        total: values.len(),
+                        missing,
+                    }
+                })
+                .collect()
+        };
+        CoverageReport::from_parts(
             files,
             messages,
-            summary: CoverageStats {
-                covered: total_covered,
-                total: total_methods,
-            },
-            field_summary: CoverageStats {
-                covered: total_fields_covered,
-                total: total_fields,
-            },
+            variant_rows(enum_values),
+            variant_rows(oneof_branches),
+        )
+    }
+
+    /// Every enum and (non-synthetic) oneof reachable from `message`, by
+    /// full name, with its value or branch names.
+    fn collect_variants(
+        message: &MessageDescriptor,
+        visited: &mut HashSet<String>,
+        enums: &mut BTreeMap<String, Vec<String>>,
+        oneofs: &mut BTreeMap<String, Vec<String>>,
+    ) {
+        if message.full_name().starts_with("google.protobuf.")
+            || !visited.insert(message.full_name().to_string())
+        {
+            return;
+        }
+        for oneof in message.oneofs().filter(|o| !o.is_synthetic()) {
+            oneofs.insert(
+                oneof.full_name().to_string(),
+                oneof.fields().map(|f| f.name().to_string()).collect(),
+            );
+        }
+        for field in message.fields() {
+            let kind = match field.kind() {
+                prost_reflect::Kind::Message(entry) if field.is_map() => {
+                    entry.map_entry_value_field().kind()
+                }
+                kind => kind,
+            };
+            match kind {
+                prost_reflect::Kind::Enum(e) if !e.full_name().starts_with("google.protobuf.") => {
+                    enums.insert(
+                        e.full_name().to_string(),
+                        e.values().map(|v| v.name().to_string()).collect(),
+                    );
+                }
+                prost_reflect::Kind::Message(sub) => {
+                    Self::collect_variants(&sub, visited, enums, oneofs);
+                }
+                _ => {}
+            }
         }
     }
 
     pub fn generate_text_report(&self) -> String {
-        let calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
-        let pool = self.pool.lock().unwrap_or_else(|e| e.into_inner());
-        let fields_covered = self
-            .fields_covered
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
+        self.generate_json_report().render_text()
+    }
 
-        let mut report = String::new();
-        report.push_str("--- gRPC API Coverage Report ---\n\n");
+    pub fn generate_html_report(&self) -> String {
+        self.generate_json_report().render_html()
+    }
+}
 
-        // Method coverage
-        let mut services: Vec<_> = pool.services().collect();
-        services.sort_by(|a, b| a.name().cmp(b.name()));
+impl CoverageReport {
+    /// Build a report from per-service method rows and per-message field
+    /// rows, deriving every count (per service and overall) from them.
+    fn from_parts(
+        mut files: Vec<CoverageFile>,
+        messages: Vec<MessageFieldCoverage>,
+        enums: Vec<VariantCoverage>,
+        oneofs: Vec<VariantCoverage>,
+    ) -> Self {
+        let message_stats: HashMap<&str, &MessageFieldCoverage> = messages
+            .iter()
+            .map(|m| (m.message_type.as_str(), m))
+            .collect();
 
-        if services.is_empty() {
-            report.push_str("No services found in descriptors.\n");
-            return report;
+        for file in &mut files {
+            let covered = file.methods.iter().filter(|m| m.calls > 0).count();
+            let total = file.methods.len();
+            file.statements = CoverageStats { covered, total };
+            file.functions = Some(CoverageStats { covered, total });
+
+            // Reports written before methods carried their message types
+            // can't attribute fields to a service.
+            let typed = file
+                .methods
+                .iter()
+                .any(|m| m.input_type.is_some() || m.output_type.is_some());
+            file.fields = typed.then(|| {
+                field_stats(
+                    message_types(std::slice::from_ref(file))
+                        .iter()
+                        .filter_map(|t| message_stats.get(t.as_str()).copied()),
+                )
+            });
         }
 
-        for service in services {
-            let service_name = service.name();
-            if service_name == "grpc.reflection.v1alpha.ServerReflection"
-                || service_name == "grpc.reflection.v1.ServerReflection"
-            {
-                continue;
+        let summary = CoverageStats {
+            covered: files.iter().map(|f| f.statements.covered).sum(),
+            total: files.iter().map(|f| f.statements.total).sum(),
+        };
+        let field_summary = field_stats(messages.iter());
+        let methods = files.iter().flat_map(|f| &f.methods);
+        let error_summary = CoverageStats {
+            covered: methods
+                .clone()
+                .filter(|m| m.statuses.keys().any(|code| code != "OK"))
+                .count(),
+            total: methods.count(),
+        };
+        let enum_summary = variant_stats(&enums);
+        let oneof_summary = variant_stats(&oneofs);
+
+        Self {
+            files,
+            messages,
+            enums,
+            oneofs,
+            summary,
+            field_summary,
+            error_summary,
+            enum_summary,
+            oneof_summary,
+        }
+    }
+
+    /// Combine the reports of several runs over the same API, e.g. the shards
+    /// of a suite split across CI jobs. A method's calls are summed and a
+    /// field is covered when any report covered it; totals come from the
+    /// schema, so they don't grow with the number of reports.
+    pub fn merge(reports: impl IntoIterator<Item = CoverageReport>) -> Self {
+        let mut files: BTreeMap<String, CoverageFile> = BTreeMap::new();
+        let mut messages: BTreeMap<String, Merged> = BTreeMap::new();
+        let mut enums: BTreeMap<String, Merged> = BTreeMap::new();
+        let mut oneofs: BTreeMap<String, Merged> = BTreeMap::new();
+
+        for report in reports {
+            for file in report.files {
+                let Some(merged) = files.get_mut(&file.uri) else {
+                    files.insert(file.uri.clone(), file);
+                    continue;
+                };
+                if merged.service.is_none() {
+                    merged.service = file.service;
+                }
+                for method in file.methods {
+                    match merged.methods.iter_mut().find(|m| m.name == method.name) {
+                        Some(existing) => {
+                            existing.calls += method.calls;
+                            for (code, calls) in method.statuses {
+                                *existing.statuses.entry(code).or_insert(0) += calls;
+                            }
+                            if existing.input_type.is_none() {
+                                existing.input_type = method.input_type;
+                            }
+                            if existing.output_type.is_none() {
+                                existing.output_type = method.output_type;
+                            }
+                        }
+                        None => merged.methods.push(method),
+                    }
+                }
             }
 
-            report.push_str(&format!("Service: {}\n", service_name));
+            for message in report.messages {
+                messages.entry(message.message_type).or_default().add(
+                    message.covered_fields,
+                    message.missing_fields,
+                    message.total_fields,
+                );
+            }
+            for (merged, variants) in [(&mut enums, report.enums), (&mut oneofs, report.oneofs)] {
+                for variant in variants {
+                    merged.entry(variant.name).or_default().add(
+                        variant.covered,
+                        variant.missing,
+                        variant.total,
+                    );
+                }
+            }
+        }
 
-            // Match the fully-qualified name used when recording calls.
-            let called_methods = calls.get(service.full_name()).cloned().unwrap_or_default();
+        let files = files
+            .into_values()
+            .map(|mut file| {
+                file.methods.sort_by(|a, b| a.name.cmp(&b.name));
+                file
+            })
+            .collect();
+        let messages = messages
+            .into_iter()
+            .map(|(message_type, merged)| {
+                let (covered_fields, missing_fields, total_fields) = merged.finish();
+                MessageFieldCoverage {
+                    message_type,
+                    covered_fields,
+                    total_fields,
+                    missing_fields,
+                }
+            })
+            .collect();
+        let variants = |merged: BTreeMap<String, Merged>| {
+            merged
+                .into_iter()
+                .map(|(name, merged)| {
+                    let (covered, missing, total) = merged.finish();
+                    VariantCoverage {
+                        name,
+                        covered,
+                        total,
+                        missing,
+                    }
+                })
+                .collect()
+        };
+        Self::from_parts(files, messages, variants(enums), variants(oneofs))
+    }
 
-            let mut methods: Vec<_> = service.methods().collect();
-            methods.sort_by(|a, b| a.name().cmp(b.name()));
+    /// Check the report against `thresholds`, returning every minimum it
+    /// misses. Fails when an override names a service the report doesn't
+    /// have, so a typo can't silently pass the gate.
+    pub fn check(&self, thresholds: &CoverageThresholds) -> Result<Vec<CoverageShortfall>, String> {
+        let mut shortfalls = Vec::new();
+        let mut overridden = HashSet::new();
+
+        for threshold in &thresholds.services {
+            let Some(index) = self
+                .files
+                .iter()
+                .position(|f| f.matches_service(&threshold.service))
+            else {
+                return Err(format!(
+                    "no service `{}` in the coverage report",
+                    threshold.service
+                ));
+            };
+            overridden.insert(index);
+            let file = std::slice::from_ref(&self.files[index]);
+            self.check_group(
+                Some(file[0].service_name().to_string()),
+                file,
+                None,
+                threshold.min_methods,
+                threshold.min_fields,
+                &mut shortfalls,
+            );
+        }
+
+        let rest: Vec<CoverageFile> = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !overridden.contains(i))
+            .map(|(_, f)| f.clone())
+            .collect();
+        // Messages no service is known to use (e.g. from an older report)
+        // stay with the overall minimum.
+        let attributed = message_types(&self.files);
+        self.check_group(
+            None,
+            &rest,
+            Some(&attributed),
+            thresholds.min_methods,
+            thresholds.min_fields,
+            &mut shortfalls,
+        );
+        Ok(shortfalls)
+    }
+
+    fn check_group(
+        &self,
+        service: Option<String>,
+        files: &[CoverageFile],
+        attributed: Option<&BTreeSet<String>>,
+        min_methods: Option<f64>,
+        min_fields: Option<f64>,
+        shortfalls: &mut Vec<CoverageShortfall>,
+    ) {
+        if let Some(required) = min_methods {
+            let stats = CoverageStats {
+                covered: files.iter().map(|f| f.statements.covered).sum(),
+                total: files.iter().map(|f| f.statements.total).sum(),
+            };
+            if stats.percent() < required {
+                let uncovered = files
+                    .iter()
+                    .flat_map(|f| {
+                        f.methods
+                            .iter()
+                            .filter(|m| m.calls == 0)
+                            .map(move |m| format!("{}/{}", f.service_name(), m.name))
+                    })
+                    .collect();
+                shortfalls.push(CoverageShortfall {
+                    service: service.clone(),
+                    dimension: CoverageDimension::Methods,
+                    stats,
+                    required,
+                    uncovered,
+                });
+            }
+        }
+
+        if let Some(required) = min_fields {
+            let used = message_types(files);
+            let messages: Vec<&MessageFieldCoverage> = self
+                .messages
+                .iter()
+                .filter(|m| {
+                    used.contains(&m.message_type)
+                        || attributed.is_some_and(|a| !a.contains(&m.message_type))
+                })
+                .collect();
+            let stats = field_stats(messages.iter().copied());
+            if stats.percent() < required {
+                let uncovered = messages
+                    .iter()
+                    .flat_map(|m| {
+                        m.missing_fields
+                            .iter()
+                            .map(move |f| format!("{}.{}", m.message_type, f))
+                    })
+                    .collect();
+                shortfalls.push(CoverageShortfall {
+                    service,
+                    dimension: CoverageDimension::Fields,
+                    stats,
+                    required,
+                    uncovered,
+                });
+            }
+        }
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut report = String::new();
+        report.push_str("--- gRPC API Coverage Report ---\n\n");
 
-            let mut covered_count = 0;
-            let total_count = methods.len();
+        if self.files.is_empty() {
+            report.push_str("No services found in descriptors.\n");
+            return report;
+        }
 
-            for method in methods {
-                let method_name = method.name();
-                let count = called_methods.get(method_name).unwrap_or(&0);
+        for file in &self.files {
+            report.push_str(&format!(
+                "Service: {}\n",
+                file.uri.trim_start_matches("grpc://")
+            ));
 
-                let status = if *count > 0 {
-                    covered_count += 1;
-                    format!("✅ ({} calls)", count)
+            for method in &file.methods {
+                let status = if method.calls > 0 {
+                    format!("✅ ({} calls)", method.calls)
                 } else {
                     "❌ (0 calls)".to_string()
                 };
-
-                report.push_str(&format!("  - {}: {}\n", method_name, status));
+                report.push_str(&format!("  - {}: {}", method.name, status));
+                if !method.statuses.is_empty() {
+                    let codes: Vec<&str> = method.statuses.keys().map(String::as_str).collect();
+                    report.push_str(&format!(" [{}]", codes.join(", ")));
+                }
+                report.push('\n');
             }
 
-            let coverage_pct = if total_count > 0 {
-                (covered_count as f64 / total_count as f64) * 100.0
-            } else {
-                0.0
-            };
-
             report.push_str(&format!(
                 "  Coverage: {:.1}% ({}/{})\n\n",
-                coverage_pct, covered_count, total_count
+                file.statements.percent(),
+                file.statements.covered,
+                file.statements.total
             ));
         }
 
         // Message field coverage
-        if !fields_covered.is_empty() {
+        if !self.messages.is_empty() {
             report.push_str("--- Message Field Coverage ---\n\n");
 
-            let mut message_types: Vec<_> = fields_covered.keys().cloned().collect();
-            message_types.sort();
+            for message in &self.messages {
+                let covered = message.covered_fields.len().min(message.total_fields);
+                let status = if covered >= message.total_fields {
+                    "✅"
+                } else if covered > 0 {
+                    "⚠️"
+                } else {
+                    "❌"
+                };
+                report.push_str(&format!(
+                    "{} {} ({}/{})\n",
+                    status, message.message_type, covered, message.total_fields
+                ));
+            }
+        }
 
-            for message_type in message_types {
-                let covered = fields_covered
-                    .get(&message_type)
-                    .map(|s| s.len())
-                    .unwrap_or(0);
-                let total = Self::count_message_fields(&pool, &message_type);
-
-                if total > 0 {
-                    let pct = (covered.min(total) as f64 / total as f64) * 100.0;
-                    let status = if pct >= 100.0 {
-                        "✅"
-                    } else if pct > 0.0 {
-                        "⚠️"
-                    } else {
-                        "❌"
-                    };
-                    report.push_str(&format!(
-                        "{} {} ({}/{})\n",
-                        status,
-                        message_type,
-                        covered.min(total),
-                        total
-                    ));
+        for (title, variants) in [
+            ("Enum Value Coverage", &self.enums),
+            ("Oneof Branch Coverage", &self.oneofs),
+        ] {
+            if variants.is_empty() {
+                continue;
+            }
+            report.push_str(&format!("\n--- {title} ---\n\n"));
+            for variant in variants {
+                let covered = variant.covered.len().min(variant.total);
+                let status = if covered >= variant.total {
+                    "✅"
+                } else if covered > 0 {
+                    "⚠️"
+                } else {
+                    "❌"
+                };
+                report.push_str(&format!(
+                    "{} {} ({}/{})",
+                    status, variant.name, covered, variant.total
+                ));
+                if !variant.missing.is_empty() {
+                    report.push_str(&format!(" missing: {}", variant.missing.join(", ")));
                 }
+                report.push('\n');
             }
         }
 
+        if self.error_summary.total > 0 {
+            report.push_str(&format!(
+                "\nError paths: {}/{} methods returned a non-OK status\n",
+                self.error_summary.covered, self.error_summary.total
+            ));
+        }
+
         report
     }
 
-    pub fn generate_html_report(&self) -> String {
-        let report = self.generate_json_report();
-
-        let pct = |covered: usize, total: usize| -> f64 {
-            if total > 0 {
-                covered as f64 / total as f64 * 100.0
-            } else {
-                0.0
-            }
-        };
-
-        let services: Vec<ServiceRow> = report
+    pub fn render_html(&self) -> String {
+        let services: Vec<ServiceRow> = self
             .files
             .iter()
             .map(|f| ServiceRow {
@@ -439,12 +907,13 @@ impl CoverageCollector {
                         name: m.name.clone(),
                         calls: m.calls,
                         covered: m.calls > 0,
+                        statuses: m.statuses.keys().cloned().collect(),
                     })
                     .collect(),
             })
             .collect();
 
-        let messages: Vec<MessageRow> = report
+        let messages: Vec<MessageRow> = self
             .messages
             .iter()
             .map(|m| {
@@ -466,18 +935,51 @@ impl CoverageCollector {
             })
             .collect();
 
+        let variant_rows = |variants: &[VariantCoverage]| -> Vec<VariantRow> {
+            variants
+                .iter()
+                .map(|v| {
+                    let covered = v.covered.len().min(v.total);
+                    VariantRow {
+                        name: v.name.clone(),
+                        covered,
+                        total: v.total,
+                        pct: format!("{:.1}", pct(covered, v.total)),
+                        missing: v.missing.clone(),
+                        status: if covered == 0 {
+                            "empty"
+                        } else if covered >= v.total {
+                            "full"
+                        } else {
+                            "partial"
+                        },
+                    }
+                })
+                .collect()
+        };
+        let summary = |stats: &CoverageStats| SummaryStat {
+            covered: stats.covered,
+            total: stats.total,
+            pct: format!("{:.1}", pct(stats.covered, stats.total)),
+        };
+
         let ctx = CoverageHtmlContext {
-            method_covered: report.summary.covered,
-            method_total: report.summary.total,
-            method_pct: format!("{:.1}", pct(report.summary.covered, report.summary.total)),
-            field_covered: report.field_summary.covered,
-            field_total: report.field_summary.total,
+            method_covered: self.summary.covered,
+            method_total: self.summary.total,
+            method_pct: format!("{:.1}", pct(self.summary.covered, self.summary.total)),
+            field_covered: self.field_summary.covered,
+            field_total: self.field_summary.total,
             field_pct: format!(
                 "{:.1}",
-                pct(report.field_summary.covered, report.field_summary.total)
+                pct(self.field_summary.covered, self.field_summary.total)
             ),
+            error_paths: summary(&self.error_summary),
+            enum_values: summary(&self.enum_summary),
+            oneof_branches: summary(&self.oneof_summary),
             services,
             messages,
+            enums: variant_rows(&self.enums),
+            oneofs: variant_rows(&self.oneofs),
         };
 
         let mut env = minijinja::Environment::new();
@@ -491,6 +993,192 @@ impl CoverageCollector {
     }
 }
 
+impl CoverageFile {
+    /// The fully-qualified service name when known, else the short one.
+    pub fn service_name(&self) -> &str {
+        self.service
+            .as_deref()
+            .unwrap_or_else(|| self.uri.trim_start_matches("grpc://"))
+    }
+
+    /// Whether `name` is this service's fully-qualified or short name.
+    fn matches_service(&self, name: &str) -> bool {
+        self.service.as_deref() == Some(name) || self.uri.trim_start_matches("grpc://") == name
+    }
+}
+
+impl CoverageStats {
+    /// Covered share in percent; nothing to cover counts as fully covered.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// A message's fields, or an enum's values, accumulated across reports.
+#[derive(Default)]
+struct Merged {
+    covered: BTreeSet<String>,
+    missing: BTreeSet<String>,
+    total: usize,
+}
+
+impl Merged {
+    fn add(&mut self, covered: Vec<String>, missing: Vec<String>, total: usize) {
+        self.total = self.total.max(total);
+        self.covered.extend(covered);
+        self.missing.extend(missing);
+    }
+
+    /// Covered, still-missing and total: missing in one report but covered
+    /// in another counts as covered.
+    fn finish(self) -> (Vec<String>, Vec<String>, usize) {
+        let missing = self.missing.difference(&self.covered).cloned().collect();
+        (self.covered.into_iter().collect(), missing, self.total)
+    }
+}
+
+/// Request and response message types of every method in `files`.
+fn message_types(files: &[CoverageFile]) -> BTreeSet<String> {
+    files
+        .iter()
+        .flat_map(|f| &f.methods)
+        .flat_map(|m| [&m.input_type, &m.output_type])
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+fn field_stats<'a>(messages: impl Iterator<Item = &'a MessageFieldCoverage>) -> CoverageStats {
+    let mut stats = CoverageStats::default();
+    for message in messages {
+        stats.covered += message.covered_fields.len().min(message.total_fields);
+        stats.total += message.total_fields;
+    }
+    stats
+}
+
+fn variant_stats(variants: &[VariantCoverage]) -> CoverageStats {
+    let mut stats = CoverageStats::default();
+    for variant in variants {
+        stats.covered += variant.covered.len().min(variant.total);
+        stats.total += variant.total;
+    }
+    stats
+}
+
+fn pct(covered: usize, total: usize) -> f64 {
+    if total > 0 {
+        covered as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Minimum coverage percentages a report must reach (`--coverage-min-*`).
+#[derive(Debug, Clone, Default)]
+pub struct CoverageThresholds {
+    pub min_methods: Option<f64>,
+    pub min_fields: Option<f64>,
+    /// Per-service overrides. A service named here is held to its own
+    /// minimums only, and left out of the overall ones.
+    pub services: Vec<ServiceThreshold>,
+}
+
+impl CoverageThresholds {
+    pub fn is_empty(&self) -> bool {
+        self.min_methods.is_none() && self.min_fields.is_none() && self.services.is_empty()
+    }
+}
+
+/// Minimums for one service, parsed from `SERVICE=METHODS[:FIELDS]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceThreshold {
+    /// Fully-qualified (`package.Service`) or short service name.
+    pub service: String,
+    pub min_methods: Option<f64>,
+    pub min_fields: Option<f64>,
+}
+
+impl std::str::FromStr for ServiceThreshold {
+    type Err = String;
+
+    /// `users.Users=90` gates methods, `users.Users=90:70` methods and
+    /// fields, `users.Users=:70` fields only.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((service, minimums)) = s.split_once('=') else {
+            return Err(format!("`{s}` is not SERVICE=METHODS[:FIELDS]"));
+        };
+        let (methods, fields) = minimums.split_once(':').unwrap_or((minimums, ""));
+        let threshold = Self {
+            service: service.trim().to_string(),
+            min_methods: parse_percent(methods)?,
+            min_fields: parse_percent(fields)?,
+        };
+        if threshold.service.is_empty()
+            || (threshold.min_methods.is_none() && threshold.min_fields.is_none())
+        {
+            return Err(format!("`{s}` is not SERVICE=METHODS[:FIELDS]"));
+        }
+        Ok(threshold)
+    }
+}
+
+/// A percentage between 0 and 100; empty means no minimum.
+fn parse_percent(value: &str) -> Result<Option<f64>, String> {
+    let value = value.trim().trim_end_matches('%');
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<f64>() {
+        Ok(pct) if (0.0..=100.0).contains(&pct) => Ok(Some(pct)),
+        _ => Err(format!("`{value}` is not a percentage between 0 and 100")),
+    }
+}
+
+/// What a minimum applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageDimension {
+    Methods,
+    Fields,
+}
+
+/// A minimum the report didn't reach, with what was left uncovered.
+#[derive(Debug, Clone)]
+pub struct CoverageShortfall {
+    /// The overridden service, or `None` for the overall minimum.
+    pub service: Option<String>,
+    pub dimension: CoverageDimension,
+    pub stats: CoverageStats,
+    pub required: f64,
+    /// `package.Service/Method` or `package.Message.field.path` entries.
+    pub uncovered: Vec<String>,
+}
+
+impl fmt::Display for CoverageShortfall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dimension = match self.dimension {
+            CoverageDimension::Methods => "Method",
+            CoverageDimension::Fields => "Field",
+        };
+        match &self.service {
+            Some(service) => write!(f, "{dimension} coverage of {service}")?,
+            None => write!(f, "{dimension} coverage")?,
+        }
+        write!(
+            f,
+            " is {:.1}% ({}/{}), below the {}% minimum",
+            self.stats.percent(),
+            self.stats.covered,
+            self.stats.total,
+            self.required
+        )
+    }
+}
+
 const TEMPLATE: &str = include_str!("../templates/coverage.html");
 
 #[derive(Serialize)]
@@ -507,6 +1195,7 @@ struct MethodRow {
     name: String,
     calls: u64,
     covered: bool,
+    statuses: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -519,6 +1208,23 @@ struct MessageRow {
     status: &'static str,
 }
 
+#[derive(Serialize)]
+struct VariantRow {
+    name: String,
+    covered: usize,
+    total: usize,
+    pct: String,
+    missing: Vec<String>,
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct SummaryStat {
+    covered: usize,
+    total: usize,
+    pct: String,
+}
+
 #[derive(Serialize)]
 struct CoverageHtmlContext {
     method_covered: usize,
@@ -527,8 +1233,13 @@ struct CoverageHtmlContext {
     field_covered: usize,
     field_total: usize,
     field_pct: String,
+    error_paths: SummaryStat,
+    enum_values: SummaryStat,
+    oneof_branches: SummaryStat,
     services: Vec<ServiceRow>,
     messages: Vec<MessageRow>,
+    enums: Vec<VariantRow>,
+    oneofs: Vec<VariantRow>,
 }
 
 impl Default for CoverageCollector {
@@ -747,4 +1458,306 @@ mod tests {
         assert!(html.contains("SayHello"), "method name listed: {html}");
         assert!(html.contains("1 call<"), "call count rendered: {html}");
     }
+
+    /// `shop.Orders { Get, Cancel }`, both `GetOrder { id } -> Order { id, status }`.
+    fn pool_with_two_methods() -> DescriptorPool {
+        use prost_reflect::prost_types::FieldDescriptorProto;
+        use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+
+        let field = |name: &str, number: i32| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::String as i32),
+            ..Default::default()
+        };
+        let method = |name: &str| MethodDescriptorProto {
+            name: Some(name.to_string()),
+            input_type: Some(".shop.GetOrder".to_string()),
+            output_type: Some(".shop.Order".to_string()),
+            ..Default::default()
+        };
+
+        let mut pool = DescriptorPool::new();
+        let file = FileDescriptorProto {
+            name: Some("shop.proto".to_string()),
+            package: Some("shop".to_string()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("GetOrder".to_string()),
+                    field: vec![field("id", 1)],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Order".to_string()),
+                    field: vec![field("id", 1), field("status", 2)],
+                    ..Default::default()
+                },
+            ],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Orders".to_string()),
+                method: vec![method("Get"), method("Cancel")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        pool.add_file_descriptor_proto(file).unwrap();
+        pool
+    }
+
+    fn shard(method: &str, response: serde_json::Value) -> CoverageReport {
+        let collector = CoverageCollector::new();
+        collector.register_pool(&pool_with_two_methods());
+        collector.record_call("shop.Orders", method);
+        collector.record_fields_from_json("shop.GetOrder", &serde_json::json!({"id": "1"}));
+        collector.record_fields_from_json("shop.Order", &response);
+        // Round-trip through JSON, the way shards reach `coverage merge`.
+        serde_json::from_str(&serde_json::to_string(&collector.generate_json_report()).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn merge_sums_calls_and_unions_fields() {
+        let a = shard("Get", serde_json::json!({"id": "1"}));
+        let b = shard("Get", serde_json::json!({"status": "OPEN"}));
+        assert_eq!(a.summary.covered, 1);
+        assert_eq!(a.field_summary.covered, 2);
+
+        let merged = CoverageReport::merge([a, b]);
+        assert_eq!(merged.files.len(), 1);
+        let get = &merged.files[0].methods[1];
+        assert_eq!((get.name.as_str(), get.calls), ("Get", 2));
+        assert_eq!(
+            merged.summary,
+            CoverageStats {
+                covered: 1,
+                total: 2
+            }
+        );
+        assert_eq!(
+            merged.field_summary,
+            CoverageStats {
+                covered: 3,
+                total: 3
+            }
+        );
+        assert!(merged.messages.iter().all(|m| m.missing_fields.is_empty()));
+        assert_eq!(
+            merged.files[0].fields,
+            Some(CoverageStats {
+                covered: 3,
+                total: 3
+            })
+        );
+    }
+
+    #[test]
+    fn check_lists_what_is_uncovered() {
+        let report = shard("Get", serde_json::json!({"id": "1"}));
+        let thresholds = CoverageThresholds {
+            min_methods: Some(80.0),
+            min_fields: Some(60.0),
+            services: Vec::new(),
+        };
+        let shortfalls = report.check(&thresholds).unwrap();
+        assert_eq!(shortfalls.len(), 1, "fields are at 66.7%: {shortfalls:?}");
+        assert_eq!(shortfalls[0].dimension, CoverageDimension::Methods);
+        assert_eq!(shortfalls[0].uncovered, ["shop.Orders/Cancel"]);
+        assert_eq!(
+            shortfalls[0].to_string(),
+            "Method coverage is 50.0% (1/2), below the 80% minimum"
+        );
+    }
+
+    #[test]
+    fn service_override_replaces_the_overall_minimum() {
+        let report = shard("Get", serde_json::json!({"id": "1"}));
+        let thresholds = CoverageThresholds {
+            min_methods: Some(80.0),
+            min_fields: None,
+            services: vec!["Orders=:90".parse().unwrap()],
+        };
+        let shortfalls = report.check(&thresholds).unwrap();
+        assert_eq!(shortfalls.len(), 1, "{shortfalls:?}");
+        assert_eq!(shortfalls[0].service.as_deref(), Some("shop.Orders"));
+        assert_eq!(shortfalls[0].dimension, CoverageDimension::Fields);
+        assert_eq!(shortfalls[0].uncovered, ["shop.Order.status"]);
+
+        let unknown = CoverageThresholds {
+            services: vec!["shop.Carts=50".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(report.check(&unknown).is_err());
+    }
+
+    #[test]
+    fn service_threshold_syntax() {
+        let parsed: ServiceThreshold = "users.Users=90:70".parse().unwrap();
+        assert_eq!(
+            parsed,
+            ServiceThreshold {
+                service: "users.Users".to_string(),
+                min_methods: Some(90.0),
+                min_fields: Some(70.0),
+            }
+        );
+        assert!("users.Users".parse::<ServiceThreshold>().is_err());
+        assert!("users.Users=".parse::<ServiceThreshold>().is_err());
+        assert!("users.Users=120".parse::<ServiceThreshold>().is_err());
+    }
+
+    /// `pay.Payments/Pay(Charge) returns (Charge)` with
+    /// `Charge { oneof method { card, iban }, Currency currency, repeated Currency accepted }`
+    /// and `Currency { CURRENCY_UNSPECIFIED, EUR, USD }`.
+    fn pool_with_variants() -> DescriptorPool {
+        use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+        use prost_reflect::prost_types::{
+            EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+            OneofDescriptorProto,
+        };
+
+        let field = |name: &str, number: i32, ty: Type, oneof: Option<i32>| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            json_name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            type_name: (ty == Type::Enum).then(|| ".pay.Currency".to_string()),
+            oneof_index: oneof,
+            ..Default::default()
+        };
+        let value = |name: &str, number: i32| EnumValueDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            ..Default::default()
+        };
+
+        let mut pool = DescriptorPool::new();
+        let file = FileDescriptorProto {
+            name: Some("pay.proto".to_string()),
+            package: Some("pay".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Charge".to_string()),
+                field: vec![
+                    field("card", 1, Type::String, Some(0)),
+                    field("iban", 2, Type::String, Some(0)),
+                    field("currency", 3, Type::Enum, None),
+                    FieldDescriptorProto {
+                        label: Some(Label::Repeated as i32),
+                        ..field("accepted", 4, Type::Enum, None)
+                    },
+                ],
+                oneof_decl: vec![OneofDescriptorProto {
+                    name: Some("method".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Currency".to_string()),
+                value: vec![
+                    value("CURRENCY_UNSPECIFIED", 0),
+                    value("EUR", 1),
+                    value("USD", 2),
+                ],
+                ..Default::default()
+            }],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Payments".to_string()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("Pay".to_string()),
+                    input_type: Some(".pay.Charge".to_string()),
+                    output_type: Some(".pay.Charge".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        pool.add_file_descriptor_proto(file).unwrap();
+        pool
+    }
+
+    #[test]
+    fn enum_values_and_oneof_branches_are_measured_against_the_schema() {
+        let collector = CoverageCollector::new();
+        collector.register_pool(&pool_with_variants());
+        collector.record_variants_from_json(
+            "pay.Charge",
+            &serde_json::json!({"card": "4242", "currency": "EUR", "accepted": [2]}),
+        );
+
+        let report = collector.generate_json_report();
+        assert_eq!(report.enums.len(), 1);
+        let currency = &report.enums[0];
+        assert_eq!(currency.name, "pay.Currency");
+        assert_eq!(currency.covered, ["EUR", "USD"]);
+        assert_eq!(currency.missing, ["CURRENCY_UNSPECIFIED"]);
+        assert_eq!(
+            report.enum_summary,
+            CoverageStats {
+                covered: 2,
+                total: 3
+            }
+        );
+
+        assert_eq!(report.oneofs.len(), 1);
+        assert_eq!(report.oneofs[0].name, "pay.Charge.method");
+        assert_eq!(report.oneofs[0].missing, ["iban"]);
+        assert_eq!(
+            report.oneof_summary,
+            CoverageStats {
+                covered: 1,
+                total: 2
+            }
+        );
+
+        let text = report.render_text();
+        assert!(
+            text.contains("⚠️ pay.Currency (2/3) missing: CURRENCY_UNSPECIFIED"),
+            "{text}"
+        );
+        let html = report.render_html();
+        assert!(html.contains("Oneof Branch Coverage"), "{html}");
+        assert!(html.contains("<code>iban</code>"), "{html}");
+    }
+
+    #[test]
+    fn statuses_count_per_method_and_merge() {
+        let shard = |code: &str| {
+            let collector = CoverageCollector::new();
+            collector.register_pool(&pool_with_variants());
+            collector.record_call("pay.Payments", "Pay");
+            collector.record_status("pay.Payments", "Pay", code);
+            collector.generate_json_report()
+        };
+        let ok = shard("OK");
+        assert_eq!(
+            ok.error_summary,
+            CoverageStats {
+                covered: 0,
+                total: 1
+            }
+        );
+
+        let merged = CoverageReport::merge([ok, shard("InvalidArgument"), shard("OK")]);
+        let statuses = &merged.files[0].methods[0].statuses;
+        assert_eq!(statuses.get("OK"), Some(&2));
+        assert_eq!(statuses.get("InvalidArgument"), Some(&1));
+        assert_eq!(
+            merged.error_summary,
+            CoverageStats {
+                covered: 1,
+                total: 1
+            }
+        );
+        assert!(
+            merged
+                .render_text()
+                .contains("  - Pay: ✅ (3 calls) [InvalidArgument, OK]"),
+            "{}",
+            merged.render_text()
+        );
+    }
 }
@@ -64,6 +64,12 @@ export default defineConfig({
             { text: 'Data Sources', link: '/guides/bench-sources' },
             { text: 'Query', link: '/guides/query' },
             { text: 'Playground', link: '/guides/playground' },
+            { text: 'Mock Server', link: '/guides/features/mock' },
+            { text: 'Record and Replay', link: '/guides/features/record-replay' },
+            { text: 'Flaky Tests', link: '/guides/features/flaky-tests' },
+            { text: 'Tracing', link: '/guides/features/tracing' },
+            { text: 'Code Scanning', link: '/guides/features/code-scanning' },
+            { text: 'Schema Diff', link: '/guides/features/schema-diff' },
             { text: 'Indexes', link: '/guides/features/indexes' },
             { text: 'LSP Support', link: '/guides/features/lsp' }
           ]
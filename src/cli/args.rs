@@ -32,6 +32,34 @@ impl std::str::FromStr for ProgressMode {
     }
 }
 
+/// One slice of the suite for `--shard K/N`: `index` is 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub total: usize,
+}
+
+impl std::str::FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected K/N with 1 <= K <= N (e.g. 2/4), got '{s}'");
+        let (index, total) = s.split_once('/').ok_or_else(invalid)?;
+        let index: usize = index.trim().parse().map_err(|_| invalid())?;
+        let total: usize = total.trim().parse().map_err(|_| invalid())?;
+        if index == 0 || index > total {
+            return Err(invalid());
+        }
+        Ok(Self { index, total })
+    }
+}
+
+impl std::fmt::Display for Shard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.index, self.total)
+    }
+}
+
 /// Log format types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogFormat {
@@ -41,6 +69,8 @@ pub enum LogFormat {
     JUnit,
     Allure,
     Html,
+    Tap,
+    Ctrf,
 }
 
 /// gRPC testing utility written in Rust
@@ -132,6 +162,14 @@ pub enum Commands {
     /// Compare two bench reports & gate regressions
     BenchCompare(BenchCompareArgs),
 
+    // Coverage.
+    /// Merge coverage reports from several runs & gate on minimums
+    Coverage(CoverageArgs),
+
+    // Schema evolution.
+    /// Find breaking changes between two schemas & the tests they hit
+    SchemaDiff(SchemaDiffArgs),
+
     // Data sources.
     /// Build & manage data-source indexes
     Index(IndexArgs),
@@ -141,11 +179,38 @@ pub enum Commands {
     // Servers & tooling.
     /// Launch the web playground
     Play(PlayArgs),
+    /// Serve .gctf files as a stub gRPC server
+    Mock(MockArgs),
     /// Run the .gctf language server (LSP)
     Lsp(LspArgs),
 
     /// Install/manage .rhai plugins from a git host
     Plugins(PluginsArgs),
+    /// Inspect grpctestify.toml / .grpctestify/config.yaml settings
+    Config(ConfigArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigAction {
+    /// Print the effective configuration, annotated with where each value comes from
+    Show(ConfigShowArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ConfigShowArgs {
+    /// Test paths to resolve the project config from (defaults to the current directory)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Output format: text, json
+    #[arg(long, default_value = "text", value_name = "FORMAT")]
+    pub format: String,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -225,7 +290,7 @@ pub struct ScaffoldArgs {
     #[arg(long, default_value_t = false)]
     pub reflect: bool,
 
-    /// Server address (host:port)
+    /// Server address (host:port, or unix:/path / unix-abstract:name)
     #[arg(long, value_name = "ADDRESS")]
     pub address: Option<String>,
 
@@ -256,7 +321,7 @@ pub struct ScaffoldArgs {
 
 #[derive(Args, Debug, Clone)]
 pub struct HealthArgs {
-    /// Server address (host:port)
+    /// Server address (host:port, or unix:/path / unix-abstract:name)
     #[arg(required = true, value_name = "ADDRESS")]
     pub address: String,
 
@@ -295,6 +360,11 @@ pub struct HealthArgs {
     /// Give up waiting after this many seconds in --watch mode
     #[arg(long, default_value_t = 60.0, value_name = "SECS")]
     pub watch_timeout: f64,
+
+    /// HTTP CONNECT proxy URL (http://[user:pass@]host:port), overriding
+    /// $HTTPS_PROXY/$HTTP_PROXY; `none` connects directly
+    #[arg(long, value_name = "URL")]
+    pub proxy: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -317,6 +387,11 @@ pub struct LspArgs {
     /// Use stdio for communication (default)
     #[arg(long, default_value_t = true)]
     pub stdio: bool,
+
+    /// Project environment whose variables hover shows for `{{VAR}}`;
+    /// defaults to settings.json `active_env`
+    #[arg(long = "env", value_name = "NAME")]
+    pub env: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -502,6 +577,11 @@ pub struct BenchArgs {
     /// Inline JSON request body (used with --call)
     #[arg(long, value_name = "JSON")]
     pub data: Option<String>,
+
+    /// Project environment from .grpctestify (.env.NAME + .env.NAME.local);
+    /// defaults to settings.json `active_env`, `none` disables
+    #[arg(long = "env", value_name = "NAME")]
+    pub env: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -522,7 +602,8 @@ pub struct BenchCompareArgs {
     #[arg(long, value_name = "POINTS", default_value_t = 1.0)]
     pub max_error_rate_regression: f64,
 
-    /// Max tolerated throughput (rps) drop (percent) before failing
+    /// Max tolerated throughput (rps) drop (percent) before failing; also gates
+    /// the adaptive max sustainable rps
     #[arg(long, value_name = "PCT", default_value_t = 5.0)]
     pub min_throughput: f64,
 
@@ -531,6 +612,66 @@ pub struct BenchCompareArgs {
     pub format: String,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct CoverageArgs {
+    #[command(subcommand)]
+    pub action: CoverageAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CoverageAction {
+    /// Combine JSON coverage reports (e.g. from CI shards) into one
+    Merge(CoverageMergeArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct CoverageMergeArgs {
+    /// Reports written by `run --coverage --coverage-format json`
+    #[arg(required = true, value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+
+    /// Write the merged report to this file instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Output format: json, text, html
+    #[arg(long, default_value = "json", value_name = "FORMAT")]
+    pub format: String,
+
+    /// Fail unless at least this percent of methods were called
+    #[arg(long, value_name = "PCT")]
+    pub min_methods: Option<f64>,
+
+    /// Fail unless at least this percent of message fields were covered
+    #[arg(long, value_name = "PCT")]
+    pub min_fields: Option<f64>,
+
+    /// Per-service minimums replacing the overall ones for that service
+    /// (repeatable), e.g. users.Users=90:70
+    #[arg(long, value_name = "SERVICE=METHODS[:FIELDS]")]
+    pub min_service: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SchemaDiffArgs {
+    /// Schema the tests were written for: a .proto file, a directory of them,
+    /// a descriptor set, or a server address (reflection)
+    #[arg(required = true, value_name = "OLD")]
+    pub old: String,
+
+    /// Schema to check against, in any of the forms OLD accepts
+    #[arg(required = true, value_name = "NEW")]
+    pub new: String,
+
+    /// .gctf files or directories to search for lines each breaking change hits
+    #[arg(long = "tests", value_name = "PATH", default_value = ".")]
+    pub tests: Vec<PathBuf>,
+
+    /// Output format: text, json
+    #[arg(long, default_value = "text", value_name = "FORMAT")]
+    pub format: String,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct IndexArgs {
     /// .gctf file(s) or directory with BENCH.sources definitions
@@ -606,6 +747,14 @@ pub struct ListArgs {
     /// Include test range information
     #[arg(long, default_value_t = false)]
     pub with_range: bool,
+
+    /// List only what `run --shard K/N` would run
+    #[arg(long, value_name = "K/N")]
+    pub shard: Option<Shard>,
+
+    /// Balance --shard by per-test durations from a prior `--log-format json` report
+    #[arg(long, value_name = "PATH", requires = "shard")]
+    pub shard_timings: Option<PathBuf>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -668,13 +817,22 @@ pub struct CheckArgs {
     #[arg(required = true, value_name = "FILES")]
     pub files: Vec<PathBuf>,
 
-    /// Output format: text, json
+    /// Output format: text, json, sarif, gitlab
     #[arg(long, default_value = "text", value_name = "FORMAT")]
     pub format: String,
 
     /// Validate BENCH section configuration
     #[arg(long, default_value_t = false)]
     pub bench: bool,
+
+    /// Check REQUEST/RESPONSE against this proto file or directory (documents
+    /// with their own PROTO section use that instead)
+    #[arg(long, value_name = "FILE_OR_DIR", conflicts_with = "descriptor")]
+    pub proto: Option<PathBuf>,
+
+    /// Check REQUEST/RESPONSE against a pre-compiled FileDescriptorSet
+    #[arg(long, value_name = "FILE")]
+    pub descriptor: Option<PathBuf>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -739,6 +897,27 @@ pub struct RunArgs {
     )]
     pub since: String,
 
+    /// Rerun only the tests that failed last time (everything if none did)
+    #[arg(long, default_value_t = false, help_heading = "Test Selection")]
+    pub last_failed: bool,
+
+    /// Run last time's failures before everything else
+    #[arg(long, default_value_t = false, help_heading = "Test Selection")]
+    pub failed_first: bool,
+
+    /// Run only shard K of N (1-based); each directory's fixtures stay with its tests
+    #[arg(long, value_name = "K/N", help_heading = "Test Selection")]
+    pub shard: Option<Shard>,
+
+    /// Balance --shard by per-test durations from a prior `--log-format json` report
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "shard",
+        help_heading = "Test Selection"
+    )]
+    pub shard_timings: Option<PathBuf>,
+
     /// Wire protocol: grpc, grpc-web, connectrpc
     #[arg(
         long,
@@ -791,6 +970,51 @@ pub struct RunArgs {
     #[arg(long, default_value_t = false, help_heading = "Execution")]
     pub no_retry: bool,
 
+    /// Run each selected test N times, reporting its pass rate, latency
+    /// spread and failure messages
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help_heading = "Execution"
+    )]
+    pub repeat: Option<u32>,
+
+    /// Stop repeating a test at its first failure (at most `--repeat` runs,
+    /// 100 without it)
+    #[arg(long, default_value_t = false, help_heading = "Execution")]
+    pub until_failure: bool,
+
+    /// HTTP CONNECT proxy URL (http://[user:pass@]host:port), overriding
+    /// $HTTPS_PROXY/$HTTP_PROXY; `none` connects directly. OPTIONS `proxy:` wins
+    #[arg(long, value_name = "URL", help_heading = "Execution")]
+    pub proxy: Option<String>,
+
+    /// Seed for template functions (`uuid()`, `fake.*`, `random_int`; also
+    /// freezes `now()`) so generated values are identical across runs
+    #[arg(long, value_name = "N", help_heading = "Execution")]
+    pub seed: Option<u64>,
+
+    /// Project environment from .grpctestify (.env.NAME + .env.NAME.local);
+    /// defaults to settings.json `active_env`, `none` disables
+    #[arg(long = "env", value_name = "NAME", help_heading = "Execution")]
+    pub env: Option<String>,
+
+    /// Record every call's exchange into cassettes under DIR (one JSON file
+    /// per test file) for later `--replay`
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with = "replay",
+        help_heading = "Execution"
+    )]
+    pub record: Option<PathBuf>,
+
+    /// Answer every call from the cassettes under DIR instead of the network;
+    /// a call with no recorded match fails
+    #[arg(long, value_name = "DIR", help_heading = "Execution")]
+    pub replay: Option<PathBuf>,
+
     /// Skip assertions and print raw server responses
     #[arg(long, default_value_t = false, help_heading = "Execution")]
     pub no_assert: bool,
@@ -803,7 +1027,17 @@ pub struct RunArgs {
     #[arg(short = 'd', long, default_value_t = false, help_heading = "Execution")]
     pub dry_run: bool,
 
-    /// Report format: junit, json, yaml, allure, html (comma-separated for several, e.g. junit,html)
+    /// Keep running: after the first run, re-run the tests affected by each
+    /// change to a test, fixture, proto, data source or plugin
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["write", "stream"],
+        help_heading = "Execution"
+    )]
+    pub watch: bool,
+
+    /// Report format: junit, json, yaml, allure, html, tap, ctrf (comma-separated for several, e.g. junit,html)
     #[arg(long, value_name = "FORMAT", help_heading = "Output & Reports")]
     pub log_format: Option<String>,
 
@@ -838,11 +1072,63 @@ pub struct RunArgs {
     )]
     pub coverage_format: String,
 
+    /// Write the coverage report to this file instead of stdout
+    #[arg(long, value_name = "PATH", help_heading = "Output & Reports")]
+    pub coverage_output: Option<PathBuf>,
+
+    /// Fail the run unless at least this percent of methods were called.
+    /// Implies --coverage
+    #[arg(long, value_name = "PCT", help_heading = "Output & Reports")]
+    pub coverage_min_methods: Option<f64>,
+
+    /// Fail the run unless at least this percent of message fields were
+    /// covered. Implies --coverage
+    #[arg(long, value_name = "PCT", help_heading = "Output & Reports")]
+    pub coverage_min_fields: Option<f64>,
+
+    /// Per-service coverage minimums replacing the overall ones for that
+    /// service (repeatable), e.g. users.Users=90:70. Implies --coverage
+    #[arg(
+        long,
+        value_name = "SERVICE=METHODS[:FIELDS]",
+        help_heading = "Output & Reports"
+    )]
+    pub coverage_min_service: Vec<String>,
+
     /// Force-capture the request/response exchange even when the active
     /// reporter wouldn't otherwise need it (e.g. plain console, or a report
     /// format that doesn't render it)
     #[arg(long, default_value_t = false, help_heading = "Output & Reports")]
     pub capture_exchange: bool,
+
+    /// Send a W3C traceparent with every call (one trace per test) and show
+    /// each test's trace id in the reports
+    #[arg(long, default_value_t = false, help_heading = "Tracing")]
+    pub trace: bool,
+
+    /// W3C baggage entry sent with every traced call (repeatable)
+    #[arg(long, value_name = "KEY=VALUE", help_heading = "Tracing")]
+    pub trace_baggage: Vec<String>,
+
+    /// Link reports to a trace viewer; `{trace_id}` is replaced by the trace id
+    /// (e.g. http://localhost:16686/trace/{trace_id})
+    #[arg(long, value_name = "URL", help_heading = "Tracing")]
+    pub trace_url: Option<String>,
+
+    /// Export the run's spans to this OTLP/HTTP collector (JSON encoding;
+    /// `/v1/traces` is appended unless present). Implies --trace
+    #[arg(long, value_name = "URL", help_heading = "Tracing")]
+    pub otel_endpoint: Option<String>,
+
+    /// Append the run's spans to this file as OTLP-JSON, one line per run.
+    /// Implies --trace
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "otel_endpoint",
+        help_heading = "Tracing"
+    )]
+    pub otel_file: Option<PathBuf>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -854,7 +1140,7 @@ pub struct ReflectArgs {
     /// Service symbol, or service/method symbol (e.g. `pkg.Service/Method`)
     pub symbol: Option<String>,
 
-    /// Server address (host:port); overrides $GRPCTESTIFY_ADDRESS
+    /// Server address (host:port, or unix:/path / unix-abstract:name); overrides $GRPCTESTIFY_ADDRESS
     #[arg(long, value_name = "ADDRESS")]
     pub address: Option<String>,
 
@@ -894,6 +1180,11 @@ pub struct ReflectArgs {
     /// Client key path for TLS
     #[arg(long)]
     pub tls_key: Option<String>,
+
+    /// HTTP CONNECT proxy URL (http://[user:pass@]host:port), overriding
+    /// $HTTPS_PROXY/$HTTP_PROXY; `none` connects directly
+    #[arg(long, value_name = "URL")]
+    pub proxy: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -905,6 +1196,14 @@ pub struct FmtArgs {
     /// Write changes to file instead of stdout
     #[arg(short = 'w', long, default_value_t = false)]
     pub write: bool,
+
+    /// Only report files that need formatting (the default without --write)
+    #[arg(long, default_value_t = false, conflicts_with = "write")]
+    pub check: bool,
+
+    /// Report format: text, sarif, gitlab (sarif and gitlab only report)
+    #[arg(long, default_value = "text", value_name = "FORMAT")]
+    pub format: String,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -985,6 +1284,11 @@ pub struct CallArgs {
     #[arg(long)]
     pub tls_key: Option<String>,
 
+    /// HTTP CONNECT proxy URL (http://[user:pass@]host:port), overriding
+    /// $HTTPS_PROXY/$HTTP_PROXY; `none` connects directly
+    #[arg(long, value_name = "URL")]
+    pub proxy: Option<String>,
+
     /// Request timeout in seconds
     #[arg(long, default_value_t = 60, value_name = "SECS")]
     pub max_time: u64,
@@ -1004,6 +1308,11 @@ pub struct CallArgs {
     /// Benchmark duration (with --bench), e.g. "30s"
     #[arg(long, requires = "bench")]
     pub duration: Option<String>,
+
+    /// Project environment from .grpctestify (.env.NAME + .env.NAME.local);
+    /// defaults to settings.json `active_env`, `none` disables
+    #[arg(long = "env", value_name = "NAME")]
+    pub env: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -1102,6 +1411,8 @@ impl Cli {
                 "yaml" => LogFormat::Yaml,
                 "allure" => LogFormat::Allure,
                 "html" => LogFormat::Html,
+                "tap" => LogFormat::Tap,
+                "ctrf" => LogFormat::Ctrf,
                 _ => LogFormat::Console,
             })
             .filter(|fmt| seen.insert(*fmt))
@@ -1175,6 +1486,12 @@ impl HasFormat for CheckArgs {
     }
 }
 
+impl HasFormat for ConfigShowArgs {
+    fn format(&self) -> &str {
+        &self.format
+    }
+}
+
 impl HasFormat for BenchArgs {
     fn format(&self) -> &str {
         &self.format
@@ -1184,7 +1501,8 @@ impl HasFormat for BenchArgs {
 #[derive(Args, Debug, Clone)]
 pub struct PlayArgs {
     /// Host/interface to bind. Defaults to loopback only; pass e.g. 0.0.0.0
-    /// to expose the playground on the network (no auth — trusted networks only)
+    /// to expose the playground on the network, together with --auth-token
+    /// or --htpasswd
     #[arg(long, default_value = "127.0.0.1")]
     pub host: String,
 
@@ -1203,9 +1521,68 @@ pub struct PlayArgs {
     /// Initialize .grpctestify project directory
     #[arg(long, default_value_t = false)]
     pub init: bool,
+
+    /// Require this token: `Authorization: Bearer <TOKEN>`, or open the UI
+    /// once at `/?token=<TOKEN>` to get it as a cookie
+    #[arg(long, value_name = "TOKEN")]
+    pub auth_token: Option<String>,
+
+    /// Require HTTP basic auth from the users in this htpasswd file
+    /// (SHA-crypt hashes, e.g. from `openssl passwd -6`)
+    #[arg(long, value_name = "FILE")]
+    pub htpasswd: Option<PathBuf>,
+
+    /// Refuse saving, moving and deleting collections, shares, settings and
+    /// environment files; calls and test runs still work
+    #[arg(long, default_value_t = false)]
+    pub read_only: bool,
+
+    /// Append an NDJSON entry per state-changing request (who, from where,
+    /// which route and call) to FILE, or `-` for stdout
+    #[arg(long, value_name = "FILE")]
+    pub audit_log: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MockArgs {
+    /// .gctf files or directories to serve stubs from (default: current dir)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Host/interface to bind. Defaults to loopback only
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on (default: 4770)
+    #[arg(long, default_value = "4770")]
+    pub port: u16,
+
+    /// Proto file or directory describing the mocked services (documents'
+    /// own PROTO sections are loaded too)
+    #[arg(long, value_name = "FILE_OR_DIR", conflicts_with = "descriptor")]
+    pub proto: Option<PathBuf>,
+
+    /// Pre-compiled FileDescriptorSet describing the mocked services
+    #[arg(long, value_name = "FILE")]
+    pub descriptor: Option<PathBuf>,
 }
 
 impl RunArgs {
+    /// `--trace`, or an exporter that needs the spans.
+    #[must_use]
+    pub fn is_tracing(&self) -> bool {
+        self.trace || self.otel_endpoint.is_some() || self.otel_file.is_some()
+    }
+
+    /// `--coverage`, or a minimum that needs the report.
+    #[must_use]
+    pub fn is_coverage(&self) -> bool {
+        self.coverage
+            || self.coverage_min_methods.is_some()
+            || self.coverage_min_fields.is_some()
+            || !self.coverage_min_service.is_empty()
+    }
+
     #[must_use]
     pub fn is_json_coverage(&self) -> bool {
         is_json_format(&self.coverage_format)
@@ -1215,6 +1592,16 @@ impl RunArgs {
     pub fn is_html_coverage(&self) -> bool {
         self.coverage_format.eq_ignore_ascii_case("html")
     }
+
+    /// How many times each test runs (`--repeat`, `--until-failure`).
+    #[must_use]
+    pub fn repeat_times(&self) -> u32 {
+        match self.repeat {
+            Some(times) => times,
+            None if self.until_failure => crate::execution::repeat::UNTIL_FAILURE_LIMIT,
+            None => 1,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1256,6 +1643,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn log_format_modes_parse_tap_and_ctrf() {
+        let cli = Cli::parse_from(["grpctestify", "run", "--log-format", "tap,ctrf", "t.gctf"]);
+        assert_eq!(
+            cli.log_format_modes(),
+            vec![LogFormat::Tap, LogFormat::Ctrf]
+        );
+    }
+
     #[test]
     fn log_format_modes_dedups_and_trims() {
         let cli = Cli::parse_from([
@@ -1278,6 +1674,39 @@ mod tests {
         assert_eq!(cli.log_format_mode(), None);
     }
 
+    #[test]
+    fn shard_parses_k_of_n() {
+        let cli = Cli::parse_from(["grpctestify", "run", "--shard", "2/4", "tests"]);
+        assert_eq!(cli.get_run_args().shard, Some(Shard { index: 2, total: 4 }));
+        for bad in ["0/4", "5/4", "2", "a/b"] {
+            assert!(
+                Cli::try_parse_from(["grpctestify", "run", "--shard", bad, "tests"]).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn record_and_replay_are_mutually_exclusive() {
+        let cli = Cli::parse_from(["grpctestify", "run", "--replay", "cassettes", "t.gctf"]);
+        assert_eq!(
+            cli.get_run_args().replay.as_deref(),
+            Some(std::path::Path::new("cassettes"))
+        );
+        assert!(
+            Cli::try_parse_from([
+                "grpctestify",
+                "run",
+                "--record",
+                "a",
+                "--replay",
+                "b",
+                "t.gctf"
+            ])
+            .is_err()
+        );
+    }
+
     #[test]
     fn capture_exchange_flag_defaults_false_and_parses() {
         let cli = Cli::parse_from(["grpctestify", "run", "t.gctf"]);
@@ -4,8 +4,8 @@ use crate::bench::schema::bench_value;
 use crate::cli::args::BenchArgs;
 use crate::parser::ast::{GctfDocument, SectionContent, SectionType};
 use crate::report::bench::{
-    BENCH_REPORT_SCHEMA_VERSION, BenchHistogramBucket, BenchPercentile, BenchReport, BenchRunInfo,
-    BenchThresholdResult,
+    BENCH_REPORT_SCHEMA_VERSION, BenchAdaptiveResult, BenchAdaptiveStep, BenchHistogramBucket,
+    BenchPercentile, BenchReport, BenchRunInfo, BenchThresholdResult,
 };
 use crate::utils::FileUtils;
 use anyhow::{Context, Result};
@@ -119,6 +119,11 @@ pub struct BenchConfigResolved {
     pub thresholds: HashMap<String, String>,
     pub option_sources: HashMap<String, BenchOptionSource>,
     pub sources: Vec<crate::bench::sources::SourceDefinition>,
+    /// Project environment (`--env`) variables; source row values override.
+    pub env_variables: HashMap<String, serde_json::Value>,
+    /// Address for files without an ADDRESS section: the project environment's
+    /// `GRPC_ADDRESS`, else `$GRPCTESTIFY_ADDRESS`, else the config file.
+    pub default_address: Option<String>,
 }
 
 impl Default for BenchConfigResolved {
@@ -187,6 +192,8 @@ impl Default for BenchConfigResolved {
                 s
             },
             sources: Vec::new(),
+            env_variables: HashMap::new(),
+            default_address: None,
         }
     }
 }
@@ -1309,8 +1316,7 @@ fn categorize_error(message: &str) -> String {
 /// - `cpus`: the tokio worker-thread count is fixed when the runtime starts in
 ///   `main.rs`; the bench harness cannot repartition it, so `cpus` is a no-op.
 ///   Use `--concurrency` to control the number of parallel workers.
-/// - `mode`: only the closed-loop execution model is implemented. Any other
-///   mode (e.g. `open`/`adaptive`) is accepted but ignored.
+/// - `mode`: an unrecognized mode is accepted but runs closed-loop.
 fn warn_ineffective_options(config: &BenchConfigResolved) {
     if config.cpus.is_some() {
         warn!(
@@ -1319,12 +1325,7 @@ fn warn_ineffective_options(config: &BenchConfigResolved) {
     }
     let mode = config.mode.trim_ascii().to_ascii_lowercase();
     match exec_model_for(&mode) {
-        ExecModel::Open if mode == "adaptive" => {
-            warn!(
-                "bench: `mode` = 'adaptive' runs the open-model (arrival-rate) executor; adaptive rate control is not yet implemented"
-            );
-        }
-        ExecModel::Open => {}
+        ExecModel::Open | ExecModel::Adaptive => {}
         ExecModel::Closed
             if !matches!(
                 mode.as_str(),
@@ -1358,11 +1359,13 @@ fn warn_ineffective_options(config: &BenchConfigResolved) {
     }
 }
 
-/// Run actual benchmark with the given config
-async fn run_benchmark(
+/// Run actual benchmark with the given config. Progress goes to stderr and
+/// Ctrl-C stops the run, unless an `observer` takes over both.
+pub(crate) async fn run_benchmark(
     test_paths: &[std::path::PathBuf],
     config: &BenchConfigResolved,
     exclude: &[String],
+    observer: Option<Arc<BenchObserver>>,
 ) -> Result<BenchReport> {
     let start_ts = crate::polyfill::runtime::now_timestamp();
 
@@ -1391,9 +1394,12 @@ async fn run_benchmark(
     info!("Bench: found {} test files", test_files.len());
     warn_ineffective_options(config);
 
-    // Graceful shutdown via SIGINT/SIGTERM
-    let shutdown_requested = Arc::new(AtomicBool::new(false));
-    {
+    // Graceful shutdown via SIGINT/SIGTERM, or the observer's cancel flag
+    let shutdown_requested = match &observer {
+        Some(observer) => Arc::clone(&observer.cancel),
+        None => Arc::new(AtomicBool::new(false)),
+    };
+    if observer.is_none() {
         let flag = Arc::clone(&shutdown_requested);
         tokio::spawn(async move {
             tokio::signal::ctrl_c().await.ok();
@@ -1404,8 +1410,7 @@ async fn run_benchmark(
 
     // Metrics collector (merged from per-worker local metrics)
     let mut metrics = BenchMetrics::default();
-    let progress_count = Arc::new(AtomicU64::new(0));
-    let progress_errors = Arc::new(AtomicU64::new(0));
+    let progress = Arc::new(ProgressCounters::default());
     let progress_done = Arc::new(AtomicBool::new(false));
 
     // Calculate total iterations
@@ -1457,12 +1462,17 @@ async fn run_benchmark(
 
     eprintln!("Starting benchmark...");
     let run_start = Instant::now();
+    // Rate the adaptive search is currently probing (f64 bits), so progress
+    // lines report it instead of the static schedule.
+    let adaptive_target = Arc::new(AtomicU64::new(0f64.to_bits()));
     let progress_task = {
-        let count = Arc::clone(&progress_count);
-        let errors = Arc::clone(&progress_errors);
+        let progress = Arc::clone(&progress);
         let done = Arc::clone(&progress_done);
+        let adaptive_target = Arc::clone(&adaptive_target);
         let cfg = config.clone();
+        let observer = observer.clone();
         tokio::spawn(async move {
+            let mut window = ProgressWindow::default();
             let mut interval = tokio::time::interval(cfg.progress_interval);
             interval.tick().await;
             loop {
@@ -1470,32 +1480,50 @@ async fn run_benchmark(
                 if done.load(Ordering::Relaxed) {
                     break;
                 }
-                print_progress_snapshot(run_start, &count, &errors, &cfg);
+                let snapshot =
+                    progress_snapshot(run_start, &progress, &cfg, &adaptive_target, &mut window);
+                report_progress(snapshot, observer.as_deref());
             }
+            window
         })
     };
 
-    // Select the execution model. `open`/`adaptive` need a target rate; without
-    // one the open model has no defined arrival schedule, so fall back to
-    // closed-loop with a warning.
+    // Select the execution model. `open` needs a target rate; without one the
+    // open model has no defined arrival schedule, so fall back to closed-loop
+    // with a warning. `adaptive` picks its own rates.
     let exec_model = exec_model_for(&config.mode);
     let use_open = exec_model == ExecModel::Open && has_target_rate(config);
     if exec_model == ExecModel::Open && !has_target_rate(config) {
         warn!(
-            "bench: open/adaptive mode needs a target rate (max_rps / load_start / load schedule); none configured — falling back to closed-loop"
+            "bench: open mode needs a target rate (max_rps / load_start / load schedule); none configured — falling back to closed-loop"
         );
     }
     eprintln!(
         "Execution model: {}",
-        if use_open {
-            "open (arrival-rate)"
-        } else {
-            "closed-loop"
+        match exec_model {
+            ExecModel::Adaptive => "adaptive (arrival-rate search)",
+            _ if use_open => "open (arrival-rate)",
+            _ => "closed-loop",
         }
     );
 
+    let mut adaptive = None;
+
     // Run with duration or count limit
-    if use_open && (has_duration || total_requests > 0) {
+    if exec_model == ExecModel::Adaptive {
+        let (run_metrics, outcome) = run_adaptive(
+            &test_docs,
+            config,
+            run_start,
+            Arc::clone(&progress),
+            Arc::clone(&shutdown_requested),
+            source_config.clone(),
+            &adaptive_target,
+        )
+        .await;
+        metrics = run_metrics;
+        adaptive = Some(outcome);
+    } else if use_open && (has_duration || total_requests > 0) {
         let bound = if let Some(dur) = config.duration {
             RunBound::Duration(dur)
         } else {
@@ -1506,8 +1534,7 @@ async fn run_benchmark(
             config,
             bound,
             run_start,
-            Arc::clone(&progress_count),
-            Arc::clone(&progress_errors),
+            Arc::clone(&progress),
             Arc::clone(&shutdown_requested),
             source_config.clone(),
         )
@@ -1519,8 +1546,7 @@ async fn run_benchmark(
         for worker_id in 0..config.concurrency {
             let docs = test_docs.clone();
             let cfg = config.clone();
-            let progress_count = Arc::clone(&progress_count);
-            let progress_errors = Arc::clone(&progress_errors);
+            let progress = Arc::clone(&progress);
             let sc = source_config.clone();
             let shutdown = Arc::clone(&shutdown_requested);
             // Spread workers across `connections` distinct client channels.
@@ -1574,10 +1600,7 @@ async fn run_benchmark(
                         let finished_at = Instant::now();
                         if should_record_after_deadline(cfg.duration_stop, finished_at, deadline) {
                             local.record(lat_ns, &status, error.as_deref(), &endpoint);
-                            progress_count.fetch_add(1, Ordering::Relaxed);
-                            if status != "OK" {
-                                progress_errors.fetch_add(1, Ordering::Relaxed);
-                            }
+                            progress.record(lat_ns, status == "OK");
                         }
 
                         if finished_at >= deadline
@@ -1612,8 +1635,7 @@ async fn run_benchmark(
         for worker_id in 0..config.concurrency {
             let docs = test_docs.clone();
             let cfg = config.clone();
-            let progress_count = Arc::clone(&progress_count);
-            let progress_errors = Arc::clone(&progress_errors);
+            let progress = Arc::clone(&progress);
             let is_last = worker_id == config.concurrency - 1;
             let worker_requests = if is_last {
                 passes_per_worker + (total_passes % config.concurrency as u64)
@@ -1686,10 +1708,7 @@ async fn run_benchmark(
                             )
                             .await;
                         local.record(lat_ns, &status, error.as_deref(), &endpoint);
-                        progress_count.fetch_add(1, Ordering::Relaxed);
-                        if status != "OK" {
-                            progress_errors.fetch_add(1, Ordering::Relaxed);
-                        }
+                        progress.record(lat_ns, status == "OK");
                     }
                 }
 
@@ -1705,19 +1724,26 @@ async fn run_benchmark(
     }
 
     progress_done.store(true, Ordering::Relaxed);
-    let _ = progress_task.await;
-    print_progress_snapshot(run_start, &progress_count, &progress_errors, config);
+    let mut window = progress_task.await.unwrap_or_default();
+    report_progress(
+        progress_snapshot(run_start, &progress, config, &adaptive_target, &mut window),
+        observer.as_deref(),
+    );
 
     let run_elapsed = run_start.elapsed();
     let end_ts = crate::polyfill::runtime::now_timestamp();
 
     let user_cancelled = shutdown_requested.load(Ordering::Relaxed);
-    let end_reason = derive_end_reason(
-        has_duration,
-        config.max_duration,
-        run_elapsed,
-        user_cancelled,
-    );
+    let end_reason = if adaptive.is_some() && !user_cancelled {
+        "adaptive_search_completed"
+    } else {
+        derive_end_reason(
+            has_duration,
+            config.max_duration,
+            run_elapsed,
+            user_cancelled,
+        )
+    };
 
     build_report(
         start_ts,
@@ -1727,6 +1753,7 @@ async fn run_benchmark(
         metrics,
         run_elapsed,
         source_config.as_ref(),
+        adaptive,
     )
 }
 
@@ -1872,14 +1899,18 @@ enum ExecModel {
     /// Requests arrive on a fixed schedule regardless of completion; latency is
     /// measured from the *scheduled* arrival so backpressure is captured.
     Open,
+    /// Open-model probe windows at rates chosen by [`AdaptiveSearch`], to find
+    /// the highest rate that holds the configured guards.
+    Adaptive,
 }
 
-/// Pure `mode` → execution-model dispatch. `open`/`adaptive` select the open
-/// model (adaptive currently maps to open — no adaptive rate control yet);
-/// everything else (`fixed`/`closed`/`closed-loop`/unknown) stays closed-loop.
+/// Pure `mode` → execution-model dispatch. `open` selects the open model and
+/// `adaptive` the rate search; everything else
+/// (`fixed`/`closed`/`closed-loop`/unknown) stays closed-loop.
 fn exec_model_for(mode: &str) -> ExecModel {
     match mode.trim_ascii().to_ascii_lowercase().as_str() {
-        "open" | "adaptive" => ExecModel::Open,
+        "open" => ExecModel::Open,
+        "adaptive" => ExecModel::Adaptive,
         _ => ExecModel::Closed,
     }
 }
@@ -2050,14 +2081,12 @@ async fn run_request_with_runner(
 /// backpressure to requests but never stalls arrival scheduling. Each sample's
 /// latency is taken from `latency_ns_from_arrival`, i.e. the intended slot, so
 /// permit-wait time counts against latency — the coordinated-omission fix.
-#[allow(clippy::too_many_arguments)]
 async fn run_open_model(
     test_docs: &[(std::path::PathBuf, GctfDocument)],
     config: &BenchConfigResolved,
     bound: RunBound,
     schedule_start: Instant,
-    progress_count: Arc<AtomicU64>,
-    progress_errors: Arc<AtomicU64>,
+    progress: Arc<ProgressCounters>,
     shutdown: Arc<AtomicBool>,
     source_config: Option<Arc<crate::bench::sources::SourceDrivenConfig>>,
 ) -> BenchMetrics {
@@ -2084,7 +2113,9 @@ async fn run_open_model(
             Arc::new(
                 TestRunner::new(false, timeout_seconds, no_assert, false, false, None)
                     .with_protocol(config.protocol)
-                    .with_connection_id(i as u64),
+                    .with_connection_id(i as u64)
+                    .with_base_variables(config.env_variables.clone())
+                    .with_default_address(config.default_address.clone()),
             )
         })
         .collect();
@@ -2148,8 +2179,7 @@ async fn run_open_model(
         // Round-robin task k across the `connections` channels: k -> runners[k % N].
         let runner = Arc::clone(&runners[round_robin_index(doc_cursor, runners.len())]);
         let metrics = Arc::clone(&metrics);
-        let progress_count = Arc::clone(&progress_count);
-        let progress_errors = Arc::clone(&progress_errors);
+        let progress = Arc::clone(&progress);
         let duration_stop = config.duration_stop;
 
         tasks.spawn(async move {
@@ -2169,10 +2199,7 @@ async fn run_open_model(
                 let mut m = metrics.lock().await;
                 m.record(lat_ns, &status, error.as_deref(), &endpoint);
                 drop(m);
-                progress_count.fetch_add(1, Ordering::Relaxed);
-                if status != "OK" {
-                    progress_errors.fetch_add(1, Ordering::Relaxed);
-                }
+                progress.record(lat_ns, status == "OK");
             }
         });
 
@@ -2195,27 +2222,400 @@ async fn run_open_model(
         .unwrap_or_else(|_| unreachable!("all bench tasks joined; metrics Arc must be unique"))
 }
 
-fn print_progress_snapshot(
+/// Starting probe rate when `load_start` is unset.
+const ADAPTIVE_DEFAULT_START_RPS: f64 = 10.0;
+/// Probe window length when `load_step_duration` is unset.
+const ADAPTIVE_DEFAULT_WINDOW: Duration = Duration::from_secs(5);
+/// Fraction of its target rate a window must complete to count as keeping up.
+/// Below it requests are queuing behind the concurrency cap — past the knee
+/// even when no latency guard is configured.
+const ADAPTIVE_MIN_ACHIEVED: f64 = 0.9;
+/// The search stops once the sustainable/breaking bracket is narrower than
+/// this fraction of the breaking rate (and never resolves below 1 rps).
+const ADAPTIVE_RESOLUTION: f64 = 0.05;
+/// Upper bound on probe windows, so a search with no `duration` budget against
+/// a target that never breaches still ends.
+const ADAPTIVE_MAX_STEPS: usize = 32;
+/// Guard applied when no rate-independent `thresholds.*` key is configured.
+const ADAPTIVE_DEFAULT_GUARD: (&str, &str) = ("error_rate_pct", "<1%");
+
+/// Why the adaptive search stopped on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AdaptiveStop {
+    /// The sustainable and breaking rates are within the search resolution.
+    Converged,
+    /// The rate cap (`load_end`, else `max_rps`) held, so there is no higher
+    /// rate to try.
+    RateCap,
+}
+
+impl AdaptiveStop {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Converged => "converged",
+            Self::RateCap => "rate_cap_reached",
+        }
+    }
+}
+
+/// Rate controller for `mode: adaptive`. While every window holds, the rate
+/// climbs (by `load_step`, or doubling when unset) up to the cap; after the
+/// first breach it bisects between the best sustainable and the lowest
+/// breaking rate. Pure and time-free, so the search is unit-testable.
+#[derive(Debug, Clone)]
+struct AdaptiveSearch {
+    rate: f64,
+    step: Option<f64>,
+    cap: Option<f64>,
+    best: Option<f64>,
+    breaking: Option<f64>,
+}
+
+impl AdaptiveSearch {
+    fn new(config: &BenchConfigResolved) -> Self {
+        let cap = config.load_end.or(config.max_rps).filter(|c| *c > 0.0);
+        let start = config
+            .load_start
+            .filter(|s| *s > 0.0)
+            .unwrap_or(ADAPTIVE_DEFAULT_START_RPS);
+        Self {
+            rate: cap.map_or(start, |c| start.min(c)),
+            step: config.load_step.filter(|s| *s > 0.0),
+            cap,
+            best: None,
+            breaking: None,
+        }
+    }
+
+    /// Rate to probe next.
+    fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Record whether the window probed at [`Self::rate`] held, and move to the
+    /// next rate. Returns why the search is over once it is.
+    fn observe(&mut self, sustainable: bool) -> Option<AdaptiveStop> {
+        if sustainable {
+            self.best = Some(self.best.map_or(self.rate, |b| b.max(self.rate)));
+        } else {
+            self.breaking = Some(self.breaking.map_or(self.rate, |b| b.min(self.rate)));
+        }
+        let floor = self.best.unwrap_or(0.0);
+        match self.breaking {
+            Some(ceiling) => {
+                if ceiling - floor <= (ceiling * ADAPTIVE_RESOLUTION).max(1.0) {
+                    return Some(AdaptiveStop::Converged);
+                }
+                self.rate = (floor + ceiling) / 2.0;
+            }
+            None => {
+                if self.cap.is_some_and(|c| self.rate >= c) {
+                    return Some(AdaptiveStop::RateCap);
+                }
+                let next = self.step.map_or(self.rate * 2.0, |s| self.rate + s);
+                self.rate = self.cap.map_or(next, |c| next.min(c));
+            }
+        }
+        None
+    }
+}
+
+/// The `thresholds.*` entries that can judge a single probe window: latency
+/// and error-rate metrics. Totals (`count`, `ok`, `errors`, `total_ns`) grow
+/// with window length and unknown metrics can't be resolved, so both are left
+/// to the final threshold evaluation. Falls back to
+/// [`ADAPTIVE_DEFAULT_GUARD`] when nothing qualifies.
+fn adaptive_guards(thresholds: &HashMap<String, String>) -> HashMap<String, String> {
+    let empty = BenchMetrics::default();
+    let guards: HashMap<String, String> = thresholds
+        .iter()
+        .filter(|(key, _)| {
+            let k = key.trim_ascii().to_ascii_lowercase();
+            !matches!(k.as_str(), "count" | "ok" | "errors" | "total_ns")
+                && resolve_metric_value(&empty, &k).is_some()
+        })
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if guards.is_empty() {
+        let (key, expr) = ADAPTIVE_DEFAULT_GUARD;
+        return HashMap::from([(key.to_string(), expr.to_string())]);
+    }
+    guards
+}
+
+/// Judge one probe window against the guards. A window is sustainable when
+/// it completed requests, every guard passed, and it kept up with at least
+/// [`ADAPTIVE_MIN_ACHIEVED`] of the target rate.
+fn judge_adaptive_window(
+    metrics: &BenchMetrics,
+    guards: &HashMap<String, String>,
+    target_rps: f64,
+    elapsed: Duration,
+) -> BenchAdaptiveStep {
+    let observed_rps = if elapsed.as_secs_f64() > 0.0 {
+        metrics.count as f64 / elapsed.as_secs_f64()
+    } else {
+        0.0
+    };
+    let mut breaches: Vec<String> = evaluate_thresholds(metrics, guards)
+        .into_iter()
+        .filter(|t| !t.passed)
+        .map(|t| format!("{}: {}", t.metric, t.reason.unwrap_or(t.actual)))
+        .collect();
+    breaches.sort();
+    if metrics.count == 0 {
+        breaches.push("no requests completed".to_string());
+    } else if observed_rps < target_rps * ADAPTIVE_MIN_ACHIEVED {
+        breaches.push(format!(
+            "throughput: {observed_rps:.2} rps < {:.0}% of target",
+            ADAPTIVE_MIN_ACHIEVED * 100.0
+        ));
+    }
+    BenchAdaptiveStep {
+        target_rps,
+        observed_rps,
+        count: metrics.count,
+        errors: metrics.errors,
+        latency_p99_ns: metrics.compute_percentile(99.0),
+        sustainable: breaches.is_empty(),
+        breaches,
+    }
+}
+
+/// Result of an adaptive run handed to [`build_report`]: the search trace and
+/// the `thresholds.*` evaluation of the window at the knee.
+struct AdaptiveRun {
+    result: BenchAdaptiveResult,
+    threshold_evaluation: Vec<BenchThresholdResult>,
+}
+
+/// Adaptive (`mode: adaptive`) executor. Runs the open model at one fixed
+/// rate per `load_step_duration` window, steered by [`AdaptiveSearch`] on the
+/// verdict of [`judge_adaptive_window`], until the search stops, the run
+/// budget (`duration`, else `max_duration`) can't fit another window, or the
+/// run is cancelled. Every window's samples are merged into the returned
+/// metrics; `thresholds.*` are evaluated against the highest sustainable
+/// window (the first window when none held).
+async fn run_adaptive(
+    test_docs: &[(std::path::PathBuf, GctfDocument)],
+    config: &BenchConfigResolved,
     run_start: Instant,
-    progress_count: &Arc<AtomicU64>,
-    progress_errors: &Arc<AtomicU64>,
+    progress: Arc<ProgressCounters>,
+    shutdown: Arc<AtomicBool>,
+    source_config: Option<Arc<crate::bench::sources::SourceDrivenConfig>>,
+    current_target: &AtomicU64,
+) -> (BenchMetrics, AdaptiveRun) {
+    let guards = adaptive_guards(&config.thresholds);
+    let budget = config.duration.or(config.max_duration);
+    let mut window = config.load_step_duration.unwrap_or(ADAPTIVE_DEFAULT_WINDOW);
+    if let Some(budget) = budget {
+        window = window.min(budget);
+    }
+
+    // Each window is a constant-rate open-model run; schedule shaping and
+    // ramp/cool-down overlays don't apply to a probe.
+    let mut probe = config.clone();
+    probe.load_schedule = "const".to_string();
+    probe.load_start = None;
+    probe.ramp_up = None;
+    probe.cool_down = None;
+
+    let mut search = AdaptiveSearch::new(config);
+    let mut metrics = BenchMetrics::with_capacity(0);
+    let mut steps = Vec::new();
+    let mut knee_thresholds = None;
+    let mut first_thresholds = None;
+
+    let stop_reason = loop {
+        if shutdown.load(Ordering::Relaxed) {
+            break "user_cancelled";
+        }
+        if steps.len() >= ADAPTIVE_MAX_STEPS {
+            break "step_limit_reached";
+        }
+        if !steps.is_empty() && budget.is_some_and(|b| run_start.elapsed() + window > b) {
+            break "duration_reached";
+        }
+
+        let target = search.rate();
+        current_target.store(target.to_bits(), Ordering::Relaxed);
+        probe.max_rps = Some(target);
+        let window_start = Instant::now();
+        let window_metrics = run_open_model(
+            test_docs,
+            &probe,
+            RunBound::Duration(window),
+            window_start,
+            Arc::clone(&progress),
+            Arc::clone(&shutdown),
+            source_config.clone(),
+        )
+        .await;
+        // The schedule ends at its last arrival, short of the nominal window;
+        // measure over at least the full window so a keeping-up target reads
+        // as its own rate.
+        let elapsed = window_start.elapsed().max(window);
+        let step = judge_adaptive_window(&window_metrics, &guards, target, elapsed);
+        eprintln!(
+            "[adaptive] target={:.2} rps observed={:.2} rps p99={} {}",
+            step.target_rps,
+            step.observed_rps,
+            format_ns_value(step.latency_p99_ns),
+            if step.sustainable {
+                "ok".to_string()
+            } else {
+                format!("breach ({})", step.breaches.join("; "))
+            }
+        );
+
+        let thresholds = evaluate_thresholds(&window_metrics, &config.thresholds);
+        if step.sustainable && search.best.is_none_or(|b| target > b) {
+            knee_thresholds = Some(thresholds);
+        } else if first_thresholds.is_none() {
+            first_thresholds = Some(thresholds);
+        }
+        let sustainable = step.sustainable;
+        steps.push(step);
+        metrics.merge_from(window_metrics);
+
+        // A cancelled window was cut short; don't let it steer the search.
+        if shutdown.load(Ordering::Relaxed) {
+            break "user_cancelled";
+        }
+        if let Some(stop) = search.observe(sustainable) {
+            break stop.as_str();
+        }
+    };
+
+    let threshold_evaluation = knee_thresholds
+        .or(first_thresholds)
+        .unwrap_or_else(|| evaluate_thresholds(&metrics, &config.thresholds));
+    let mut guard_list: Vec<String> = guards.iter().map(|(k, v)| format!("{k}: {v}")).collect();
+    guard_list.sort();
+
+    let run = AdaptiveRun {
+        result: BenchAdaptiveResult {
+            max_sustainable_rps: search.best,
+            breaking_rps: search.breaking,
+            stop_reason: stop_reason.to_string(),
+            guards: guard_list,
+            steps,
+        },
+        threshold_evaluation,
+    };
+    (metrics, run)
+}
+
+/// Requests completed so far, shared by every worker and read by the
+/// progress task.
+#[derive(Default)]
+struct ProgressCounters {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    latency_ns: AtomicU64,
+}
+
+impl ProgressCounters {
+    fn record(&self, latency_ns: u64, ok: bool) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.latency_ns.fetch_add(latency_ns, Ordering::Relaxed);
+        if !ok {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The counters at the previous snapshot, so latency reads per interval.
+#[derive(Default)]
+struct ProgressWindow {
+    requests: u64,
+    latency_ns: u64,
+}
+
+/// A live view of a running benchmark, taken every `progress_interval`.
+/// Everything is cumulative since the start except `latency_ms`, the mean
+/// latency of the requests completed since the previous snapshot.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProgressSnapshot {
+    pub elapsed_s: f64,
+    pub requests: u64,
+    pub rps: f64,
+    pub target_rps: f64,
+    pub error_pct: f64,
+    pub latency_ms: Option<f64>,
+}
+
+/// Takes over a benchmark's progress reporting and cancellation, for a run
+/// that isn't attached to a terminal (the playground's `/api/bench`).
+pub struct BenchObserver {
+    pub on_progress: Box<dyn Fn(&ProgressSnapshot) + Send + Sync>,
+    /// Set to stop the run; stands in for Ctrl-C.
+    pub cancel: Arc<AtomicBool>,
+}
+
+fn progress_snapshot(
+    run_start: Instant,
+    progress: &ProgressCounters,
     config: &BenchConfigResolved,
-) {
-    let count = progress_count.load(Ordering::Relaxed);
+    adaptive_target: &AtomicU64,
+    window: &mut ProgressWindow,
+) -> Option<ProgressSnapshot> {
+    let count = progress.requests.load(Ordering::Relaxed);
     if count == 0 {
-        return;
+        return None;
     }
     let elapsed = run_start.elapsed().as_secs_f64();
     if elapsed <= 0.0 {
+        return None;
+    }
+    let err = progress.errors.load(Ordering::Relaxed);
+    let latency_ns = progress.latency_ns.load(Ordering::Relaxed);
+    let target_rps = if exec_model_for(&config.mode) == ExecModel::Adaptive {
+        f64::from_bits(adaptive_target.load(Ordering::Relaxed))
+    } else {
+        target_rps_at(config, run_start.elapsed())
+    };
+    let completed = count.saturating_sub(window.requests);
+    let latency_ms = (completed > 0).then(|| {
+        latency_ns.saturating_sub(window.latency_ns) as f64 / completed as f64 / 1_000_000.0
+    });
+    *window = ProgressWindow {
+        requests: count,
+        latency_ns,
+    };
+    Some(ProgressSnapshot {
+        elapsed_s: elapsed,
+        requests: count,
+        rps: count as f64 / elapsed,
+        target_rps,
+        error_pct: (err as f64 / count as f64) * 100.0,
+        latency_ms,
+    })
+}
+
+fn report_progress(snapshot: Option<ProgressSnapshot>, observer: Option<&BenchObserver>) {
+    let Some(snapshot) = snapshot else {
         return;
+    };
+    match observer {
+        Some(observer) => (observer.on_progress)(&snapshot),
+        None => print_progress_snapshot(&snapshot),
     }
-    let err = progress_errors.load(Ordering::Relaxed);
-    let rps = count as f64 / elapsed;
-    let err_pct = (err as f64 / count as f64) * 100.0;
-    let target_rps = target_rps_at(config, run_start.elapsed());
+}
+
+fn print_progress_snapshot(snapshot: &ProgressSnapshot) {
+    let latency = snapshot
+        .latency_ms
+        .map(|ms| format!(" lat={ms:.2}ms"))
+        .unwrap_or_default();
     eprintln!(
-        "[bench] t={:.1}s req={} rps={:.2} target={:.2} err={:.2}%",
-        elapsed, count, rps, target_rps, err_pct
+        "[bench] t={:.1}s req={} rps={:.2} target={:.2} err={:.2}%{}",
+        snapshot.elapsed_s,
+        snapshot.requests,
+        snapshot.rps,
+        snapshot.target_rps,
+        snapshot.error_pct,
+        latency
     );
 }
 
@@ -2245,7 +2645,9 @@ async fn execute_single_bench_iteration_with_vars(
 
     let runner = TestRunner::new(false, timeout_seconds, no_assert, false, false, None)
         .with_protocol(config.protocol)
-        .with_connection_id(connection_id);
+        .with_connection_id(connection_id)
+        .with_base_variables(config.env_variables.clone())
+        .with_default_address(config.default_address.clone());
     match runner.run_test_with_variables(doc, source_variables).await {
         Ok(result) => {
             let latency = start.elapsed().as_nanos() as u64;
@@ -2510,6 +2912,7 @@ fn derive_end_reason(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_report(
     start_ts: i64,
     end_ts: i64,
@@ -2518,6 +2921,7 @@ fn build_report(
     metrics: BenchMetrics,
     elapsed: Duration,
     source_config: Option<&std::sync::Arc<crate::bench::sources::SourceDrivenConfig>>,
+    adaptive: Option<AdaptiveRun>,
 ) -> Result<BenchReport> {
     let source_for = |key: &str| {
         config
@@ -2544,7 +2948,12 @@ fn build_report(
     let latency_dist = metrics.to_percentiles(&config.latency_percentiles);
     let histogram = metrics.to_histogram(10);
 
-    let threshold_results = evaluate_thresholds(&metrics, &config.thresholds);
+    // An adaptive run deliberately drives the target past its limits, so its
+    // thresholds are judged at the knee rather than over every probe window.
+    let (threshold_results, adaptive) = match adaptive {
+        Some(run) => (run.threshold_evaluation, Some(run.result)),
+        None => (evaluate_thresholds(&metrics, &config.thresholds), None),
+    };
 
     let mut options_resolved = BTreeMap::new();
     options_resolved.insert(
@@ -2633,6 +3042,7 @@ fn build_report(
             fastest_ns: metrics.fastest_ns,
             slowest_ns: metrics.slowest_ns,
             rps_observed: rps,
+            max_sustainable_rps: adaptive.as_ref().and_then(|a| a.max_sustainable_rps),
         },
         latency_distribution: latency_dist,
         histogram,
@@ -2643,12 +3053,11 @@ fn build_report(
         tags: {
             // Record the execution model actually used (open falls back to
             // closed when no target rate is configured).
-            let effective_model =
-                if exec_model_for(&config.mode) == ExecModel::Open && has_target_rate(config) {
-                    "open"
-                } else {
-                    "closed"
-                };
+            let effective_model = match exec_model_for(&config.mode) {
+                ExecModel::Adaptive => "adaptive",
+                ExecModel::Open if has_target_rate(config) => "open",
+                _ => "closed",
+            };
             let mut tags = BTreeMap::new();
             tags.insert("exec_model".to_string(), effective_model.to_string());
             tags.insert("mode".to_string(), config.mode.clone());
@@ -2685,6 +3094,7 @@ fn build_report(
                 },
             )
             .collect(),
+        adaptive,
     };
 
     Ok(report)
@@ -2707,6 +3117,38 @@ fn canonical_bench_format(fmt: &str) -> &str {
     }
 }
 
+/// Resolve the run's configuration: CLI flags over the first file's BENCH
+/// section, plus the project environment's variables and address.
+pub(crate) fn resolve_config(
+    args: &BenchArgs,
+    test_paths: &[std::path::PathBuf],
+) -> Result<BenchConfigResolved> {
+    // Parse first test file to extract BENCH section
+    let Some(first_file) = test_paths.first() else {
+        anyhow::bail!("No test paths provided");
+    };
+    if !first_file.exists() {
+        anyhow::bail!("File not found: {}", first_file.display());
+    }
+
+    let parse_result = crate::parser::parse_with_recovery(first_file);
+    let doc = parse_result.document;
+    let bench_section = extract_bench_section(&doc);
+
+    let mut config = BenchConfigResolved::from_cli_and_bench(args, bench_section.as_ref())?;
+    if let Some(env) = crate::serve::project::resolve_project_env(
+        &crate::serve::project::env_search_start(&args.test_paths),
+        args.env.as_deref(),
+    )? {
+        config.env_variables = env.variable_map();
+        config.default_address = env.address;
+    }
+    if config.default_address.is_none() {
+        config.default_address = crate::config::env_or_file_address();
+    }
+    Ok(config)
+}
+
 pub async fn handle_bench(args: &BenchArgs) -> Result<()> {
     // Handle --list-profiles
     if args.list_profiles {
@@ -2777,21 +3219,10 @@ pub async fn handle_bench(args: &BenchArgs) -> Result<()> {
     eprintln!("BENCH MODE - Running benchmarks...");
     eprintln!();
 
-    // Parse first test file to extract BENCH section
-    let first_file = &test_paths[0];
-    if !first_file.exists() {
-        anyhow::bail!("File not found: {}", first_file.display());
-    }
-
     // Store synthetic path in first_file for cleanup later
     let _ = synthetic_path;
 
-    let parse_result = crate::parser::parse_with_recovery(first_file);
-    let doc = parse_result.document;
-    let bench_section = extract_bench_section(&doc);
-
-    // Resolve configuration
-    let config = BenchConfigResolved::from_cli_and_bench(args, bench_section.as_ref())?;
+    let config = resolve_config(args, &test_paths)?;
 
     // Print configuration
     eprintln!("Configuration:");
@@ -2892,7 +3323,7 @@ pub async fn handle_bench(args: &BenchArgs) -> Result<()> {
     }
     eprintln!();
 
-    let report = run_benchmark(&test_paths, &config, &args.exclude).await?;
+    let report = run_benchmark(&test_paths, &config, &args.exclude, None).await?;
 
     // Allure output: the raw report as a standalone file, plus the shared
     // allure-results contract so a benchmark run shows up in the same Allure
@@ -3120,7 +3551,7 @@ mod tests {
     #[test]
     fn exec_model_dispatch() {
         assert_eq!(exec_model_for("open"), ExecModel::Open);
-        assert_eq!(exec_model_for("adaptive"), ExecModel::Open);
+        assert_eq!(exec_model_for("adaptive"), ExecModel::Adaptive);
         assert_eq!(exec_model_for(" OPEN "), ExecModel::Open);
         assert_eq!(exec_model_for("closed"), ExecModel::Closed);
         assert_eq!(exec_model_for("fixed"), ExecModel::Closed);
@@ -3130,6 +3561,125 @@ mod tests {
         assert_eq!(exec_model_for("stepping"), ExecModel::Closed);
     }
 
+    fn adaptive_config(
+        start: Option<f64>,
+        step: Option<f64>,
+        cap: Option<f64>,
+    ) -> BenchConfigResolved {
+        BenchConfigResolved {
+            mode: "adaptive".to_string(),
+            load_start: start,
+            load_step: step,
+            load_end: cap,
+            ..BenchConfigResolved::default()
+        }
+    }
+
+    #[test]
+    fn adaptive_search_doubles_then_bisects_to_the_knee() {
+        // The target holds up to 300 rps.
+        let mut search = AdaptiveSearch::new(&adaptive_config(Some(50.0), None, None));
+        let mut probed = Vec::new();
+        let stop = loop {
+            let rate = search.rate();
+            probed.push(rate);
+            if let Some(stop) = search.observe(rate <= 300.0) {
+                break stop;
+            }
+        };
+        assert_eq!(stop, AdaptiveStop::Converged);
+        assert_eq!(&probed[..4], &[50.0, 100.0, 200.0, 400.0]);
+        let best = search.best.unwrap();
+        let breaking = search.breaking.unwrap();
+        assert!(best <= 300.0 && breaking > 300.0, "{best}..{breaking}");
+        assert!(breaking - best <= breaking * ADAPTIVE_RESOLUTION);
+    }
+
+    #[test]
+    fn adaptive_search_steps_additively_and_stops_at_the_cap() {
+        let mut search = AdaptiveSearch::new(&adaptive_config(Some(10.0), Some(15.0), Some(40.0)));
+        let mut probed = Vec::new();
+        let stop = loop {
+            probed.push(search.rate());
+            if let Some(stop) = search.observe(true) {
+                break stop;
+            }
+        };
+        assert_eq!(stop, AdaptiveStop::RateCap);
+        assert_eq!(probed, [10.0, 25.0, 40.0]);
+        assert_eq!(search.best, Some(40.0));
+        assert_eq!(search.breaking, None);
+    }
+
+    #[test]
+    fn adaptive_search_without_any_sustainable_rate_converges_to_none() {
+        let mut search = AdaptiveSearch::new(&adaptive_config(None, None, None));
+        assert_eq!(search.rate(), ADAPTIVE_DEFAULT_START_RPS);
+        let mut windows = 0;
+        while search.observe(false).is_none() {
+            windows += 1;
+            assert!(windows < ADAPTIVE_MAX_STEPS, "search must terminate");
+        }
+        assert_eq!(search.best, None);
+        assert!(search.breaking.unwrap() <= 1.0);
+    }
+
+    #[test]
+    fn adaptive_guards_keep_rate_independent_thresholds() {
+        let thresholds: HashMap<String, String> = [
+            ("latency_ms.p(99)", "<50"),
+            ("error_rate_pct", "<1%"),
+            ("count", ">1000"),
+            ("bogus_metric", "<1"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let guards = adaptive_guards(&thresholds);
+        let mut keys: Vec<&String> = guards.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["error_rate_pct", "latency_ms.p(99)"]);
+
+        let fallback = adaptive_guards(&HashMap::from([("count".to_string(), ">1".to_string())]));
+        assert_eq!(
+            fallback.get(ADAPTIVE_DEFAULT_GUARD.0).map(String::as_str),
+            Some(ADAPTIVE_DEFAULT_GUARD.1)
+        );
+    }
+
+    #[test]
+    fn adaptive_window_breaches_on_guard_and_on_backlog() {
+        let guards = HashMap::from([("error_rate_pct".to_string(), "<10%".to_string())]);
+        let mut m = BenchMetrics::with_capacity(0);
+        for _ in 0..100 {
+            m.record(1_000_000, "OK", None, "svc/M");
+        }
+
+        let step = judge_adaptive_window(&m, &guards, 100.0, Duration::from_secs(1));
+        assert!(step.sustainable, "{:?}", step.breaches);
+        assert_eq!(step.observed_rps, 100.0);
+
+        // Same 100 completions took 2s at a 100 rps target: it fell behind.
+        let step = judge_adaptive_window(&m, &guards, 100.0, Duration::from_secs(2));
+        assert!(!step.sustainable);
+        assert!(
+            step.breaches[0].starts_with("throughput:"),
+            "{:?}",
+            step.breaches
+        );
+
+        for _ in 0..20 {
+            m.record(1_000_000, "UNAVAILABLE", Some("unavailable"), "svc/M");
+        }
+        let step = judge_adaptive_window(&m, &guards, 100.0, Duration::from_secs(1));
+        assert!(!step.sustainable);
+        assert!(
+            step.breaches[0].starts_with("error_rate_pct:"),
+            "{:?}",
+            step.breaches
+        );
+    }
+
     #[test]
     fn open_schedule_count_exactly_n() {
         // Request-count open mode schedules exactly N arrivals.
@@ -3425,6 +3975,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3502,6 +4053,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3565,6 +4117,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3622,6 +4175,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3711,6 +4265,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3764,6 +4319,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
@@ -3815,6 +4371,7 @@ mod tests {
             profile_file: None,
             call: None,
             data: None,
+            env: None,
             list_profiles: false,
         };
 
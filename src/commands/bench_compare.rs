@@ -2,8 +2,9 @@
 // The reports are parsed as schema-tolerant `serde_json::Value` so this command
 // stays decoupled from the exact `BenchReport` struct. It reads the stable,
 // versioned `bench_report_schema_v1` fields: `summary.{count,errors,rps_observed,
-// average_ns}`, the `latency_distribution` array of `{percentile, latency_ns}`,
-// and the `per_endpoint` array of `{endpoint, latency_p99}`.
+// average_ns}`, the optional `summary.max_sustainable_rps` of adaptive runs, the
+// `latency_distribution` array of `{percentile, latency_ns}`, and the
+// `per_endpoint` array of `{endpoint, latency_p99}`.
 
 use crate::cli::args::BenchCompareArgs;
 use anyhow::{Context, Result, bail};
@@ -41,6 +42,8 @@ struct Metrics {
     errors: f64,
     rps: f64,
     mean_ns: f64,
+    /// Adaptive-mode knee; `None` for other modes or when no rate held.
+    max_sustainable_rps: Option<f64>,
     /// percentile label ("p50", "p90", ...) -> latency in ns
     percentiles: BTreeMap<String, f64>,
     /// endpoint name -> p99 latency in ns
@@ -146,6 +149,10 @@ fn extract_metrics(v: &Value) -> Result<Metrics> {
     let errors = require_f64(v, "summary", "errors")?;
     let rps = require_f64(v, "summary", "rps_observed")?;
     let mean_ns = require_f64(v, "summary", "average_ns")?;
+    let max_sustainable_rps = v
+        .get("summary")
+        .and_then(|s| s.get("max_sustainable_rps"))
+        .and_then(Value::as_f64);
 
     let mut percentiles = BTreeMap::new();
     if let Some(arr) = v.get("latency_distribution").and_then(Value::as_array) {
@@ -178,6 +185,7 @@ fn extract_metrics(v: &Value) -> Result<Metrics> {
         errors,
         rps,
         mean_ns,
+        max_sustainable_rps,
         percentiles,
         endpoint_p99,
     })
@@ -211,6 +219,20 @@ fn compare_aggregate(base: &Metrics, cur: &Metrics, th: &Thresholds) -> Vec<Metr
         ),
     });
 
+    // Max sustainable rps (adaptive runs) — higher is better, gated like
+    // throughput; only when both reports found a knee.
+    if let (Some(b), Some(c)) = (base.max_sustainable_rps, cur.max_sustainable_rps) {
+        rows.push(MetricRow {
+            name: "max_sustainable_rps".to_string(),
+            baseline: b,
+            current: c,
+            abs_delta: c - b,
+            pct_delta: pct_change(b, c),
+            threshold: th.min_throughput,
+            verdict: verdict_for_metric(b, c, Direction::HigherIsBetter, th.min_throughput),
+        });
+    }
+
     // Error rate — compared in percentage points.
     let base_rate = error_rate(base);
     let cur_rate = error_rate(cur);
@@ -454,6 +476,7 @@ mod tests {
             errors,
             rps,
             mean_ns: mean,
+            max_sustainable_rps: None,
             percentiles,
             endpoint_p99: BTreeMap::new(),
         }
@@ -603,6 +626,39 @@ mod tests {
         assert!((error_rate(&m) - 0.02).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn max_sustainable_rps_is_compared_when_both_reports_have_it() {
+        let mut base = metrics(1000.0, 1000.0, 0.0, 100.0, 200.0);
+        let mut cur = metrics(1000.0, 1000.0, 0.0, 100.0, 200.0);
+        let rows = compare_aggregate(&base, &cur, &thresholds());
+        assert!(rows.iter().all(|r| r.name != "max_sustainable_rps"));
+
+        base.max_sustainable_rps = Some(400.0);
+        cur.max_sustainable_rps = Some(300.0); // knee dropped 25% with a 5% tolerance
+        let rows = compare_aggregate(&base, &cur, &thresholds());
+        let knee = rows
+            .iter()
+            .find(|r| r.name == "max_sustainable_rps")
+            .unwrap();
+        assert_eq!(knee.verdict, Verdict::Regressed);
+        assert!(!overall_pass(&rows));
+    }
+
+    #[test]
+    fn extract_metrics_reads_adaptive_knee() {
+        let v = json!({
+            "summary": {"count": 10, "errors": 0, "rps_observed": 50.0, "average_ns": 5,
+                        "max_sustainable_rps": 240.0}
+        });
+        assert_eq!(
+            extract_metrics(&v).unwrap().max_sustainable_rps,
+            Some(240.0)
+        );
+        let v =
+            json!({"summary": {"count": 10, "errors": 0, "rps_observed": 50.0, "average_ns": 5}});
+        assert_eq!(extract_metrics(&v).unwrap().max_sustainable_rps, None);
+    }
+
     #[test]
     fn extract_metrics_errors_on_missing_metric() {
         // missing rps_observed -> clear error, no panic.
@@ -10,8 +10,10 @@ use tokio_stream::wrappers::ReceiverStream;
 
 use crate::cli::args::CallArgs;
 use crate::execution::runner_helpers;
-use crate::grpc::{GrpcClient, GrpcClientConfig, client::StreamItem, proxy::ProxyEnv};
+use crate::grpc::proxy::{ProxyEnv, ProxyUrl};
+use crate::grpc::{GrpcClient, GrpcClientConfig, client::StreamItem};
 use crate::parser;
+use crate::serve::project::ProjectEnv;
 
 /// Resolve the overall request-deadline (seconds) handed to the transport.
 ///
@@ -42,11 +44,18 @@ struct CallOptions<'a> {
     tls_ca: Option<String>,
     tls_cert: Option<String>,
     tls_key: Option<String>,
+    proxy: Option<String>,
     protocol: crate::grpc::WireProtocol,
+    /// Project environment (`--env`): `{{VAR}}` values and a fallback address.
+    env: Option<&'a ProjectEnv>,
 }
 
 /// Handle inline call with synthetic document (no file)
-async fn handle_call_document_inline(doc: &parser::GctfDocument, args: &CallArgs) -> Result<()> {
+async fn handle_call_document_inline(
+    doc: &parser::GctfDocument,
+    args: &CallArgs,
+    env: Option<&ProjectEnv>,
+) -> Result<()> {
     let mut output_file: Option<File> = if let Some(ref path) = args.output {
         Some(File::create(path)?)
     } else {
@@ -76,10 +85,12 @@ async fn handle_call_document_inline(doc: &parser::GctfDocument, args: &CallArgs
         tls_ca: args.tls_ca.clone(),
         tls_cert: args.tls_cert.clone(),
         tls_key: args.tls_key.clone(),
+        proxy: args.proxy.clone(),
         protocol: args
             .protocol
             .parse::<crate::grpc::WireProtocol>()
             .unwrap_or(crate::grpc::WireProtocol::Grpc),
+        env,
     };
 
     handle_call_document(doc, Path::new("<inline>"), opts).await
@@ -136,10 +147,16 @@ pub async fn handle_call(args: &CallArgs) -> Result<()> {
             exclude: vec![],
             list_profiles: false,
             profile_file: None,
+            env: args.env.clone(),
         };
         return crate::commands::bench::handle_bench(&bench_args).await;
     }
 
+    let env = crate::serve::project::resolve_project_env(
+        &crate::serve::project::env_search_start(args.file.as_slice()),
+        args.env.as_deref(),
+    )?;
+
     // Inline endpoint mode (-e): build synthetic document in memory
     if let Some(ref endpoint) = args.endpoint {
         let body = args.data.as_deref().unwrap_or("{}");
@@ -150,7 +167,7 @@ pub async fn handle_call(args: &CallArgs) -> Result<()> {
             .endpoint(endpoint)
             .request(request_value)
             .build();
-        return handle_call_document_inline(&doc, args).await;
+        return handle_call_document_inline(&doc, args, env.as_ref()).await;
     }
 
     if args.doc_index == Some(0) {
@@ -215,10 +232,12 @@ pub async fn handle_call(args: &CallArgs) -> Result<()> {
             tls_ca: args.tls_ca.clone(),
             tls_cert: args.tls_cert.clone(),
             tls_key: args.tls_key.clone(),
+            proxy: args.proxy.clone(),
             protocol: args
                 .protocol
                 .parse::<crate::grpc::WireProtocol>()
                 .unwrap_or(crate::grpc::WireProtocol::Grpc),
+            env: env.as_ref(),
         };
 
         handle_call_document(d, &file_path, opts).await?;
@@ -259,7 +278,7 @@ fn vrecv(silent: bool, line: &str) {
     }
 }
 
-fn print_send_metadata(silent: bool, entries: &crate::parser::OrderedStringMap) {
+fn print_send_metadata(silent: bool, entries: &crate::grpc::MetadataMap) {
     let mut pairs: Vec<_> = entries.iter().collect();
     pairs.sort_by_key(|(k, _)| k.as_str());
     for (k, v) in pairs {
@@ -267,7 +286,7 @@ fn print_send_metadata(silent: bool, entries: &crate::parser::OrderedStringMap)
     }
 }
 
-fn print_recv_metadata(silent: bool, entries: &std::collections::HashMap<String, String>) {
+fn print_recv_metadata(silent: bool, entries: &crate::grpc::MetadataMap) {
     let mut pairs: Vec<_> = entries.iter().collect();
     pairs.sort_by_key(|(k, _)| k.as_str());
     for (k, v) in pairs {
@@ -286,7 +305,30 @@ async fn handle_call_document(
     };
     let full_service = runner_helpers::full_service_name(&package, &service);
 
-    let address = runner_helpers::effective_address(doc, Some(opts.protocol));
+    // `{{VAR}}` and template functions resolve as in `run`; a placeholder with
+    // no value is sent verbatim, curl-style.
+    let variables = opts.env.map(ProjectEnv::variable_map).unwrap_or_default();
+    let templates = runner_helpers::TemplateContext::unseeded();
+    let address = doc
+        .get_address(opts.env.and_then(|env| env.address.as_deref()))
+        .unwrap_or_else(|| runner_helpers::effective_address(doc, Some(opts.protocol)));
+    let address = runner_helpers::try_interpolate_variables(&address, &variables, &templates)
+        .map_err(|e| anyhow::anyhow!("ADDRESS: {e}"))?
+        .unwrap_or(address);
+    let metadata = match doc.get_request_headers() {
+        Some(headers) => {
+            let mut substituted = crate::grpc::MetadataMap::with_capacity(headers.len());
+            for (key, value) in headers {
+                let value =
+                    runner_helpers::try_interpolate_variables(&value, &variables, &templates)
+                        .map_err(|e| anyhow::anyhow!("REQUEST_HEADERS '{key}': {e}"))?
+                        .unwrap_or(value);
+                substituted.append(key, value);
+            }
+            Some(substituted)
+        }
+        None => None,
+    };
     // CLI TLS flags win over the file's TLS section (matching how `--protocol`
     // overrides), and are the *sole* source in inline mode (`-e`, no file).
     // `--plaintext` forces no TLS; `--tls-ca/-cert/-key` build a fresh config.
@@ -331,6 +373,12 @@ async fn handle_call_document(
         "plaintext"
     };
 
+    // CLI `--proxy` overrides the file's OPTIONS `proxy:`, like the TLS flags.
+    let proxy_override = opts
+        .proxy
+        .clone()
+        .or_else(|| doc.get_options().and_then(|o| o.get("proxy").cloned()));
+
     if opts.verbose {
         // Log proxy env vars curl-style before the connection attempt
         let proxy = ProxyEnv::from_env();
@@ -345,20 +393,17 @@ async fn handle_call_document(
                 opts.silent,
                 &format!("Uses proxy env variable HTTPS_PROXY == '{}'", v),
             );
-            vinfo(
-                opts.silent,
-                "WARNING: gRPC transport does not support HTTP CONNECT proxies; HTTPS_PROXY is ignored",
-            );
         }
         if let Some(v) = &proxy.http_proxy {
             vinfo(
                 opts.silent,
                 &format!("Uses proxy env variable HTTP_PROXY == '{}'", v),
             );
-            vinfo(
-                opts.silent,
-                "WARNING: gRPC transport does not support HTTP CONNECT proxies; HTTP_PROXY is ignored",
-            );
+        }
+        if let Some(p) = &proxy_override {
+            // Credentials stay out of the log: show the parsed authority only.
+            let shown = ProxyUrl::parse(p).map_or_else(|_| p.clone(), |u| u.to_string());
+            vinfo(opts.silent, &format!("Uses proxy '{}'", shown));
         }
 
         vinfo(
@@ -370,11 +415,11 @@ async fn handle_call_document(
             &format!("gRPC method: {}/{}", full_service, method),
         );
 
-        if let Some(headers) = doc.get_request_headers()
+        if let Some(headers) = &metadata
             && !headers.is_empty()
         {
             vsend(opts.silent, "");
-            print_send_metadata(opts.silent, &headers);
+            print_send_metadata(opts.silent, headers);
         }
         vsend(opts.silent, "");
     }
@@ -386,21 +431,26 @@ async fn handle_call_document(
         timeout_seconds,
         tls_config,
         proto_config: None,
-        metadata: doc.get_request_headers().map(|m| m.into_iter().collect()),
+        metadata,
         target_service: Some(full_service.clone()),
         compression: Default::default(),
         connection_id: 0,
         protocol: opts.protocol,
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: proxy_override,
     };
 
     let start = Instant::now();
     let mut client = GrpcClient::new(config).await?;
 
-    let requests: Vec<Value> = doc.get_requests();
+    let mut requests: Vec<Value> = doc.get_requests();
     if requests.is_empty() {
         return Err(anyhow::anyhow!("No REQUEST section found"));
     }
+    for request in &mut requests {
+        runner_helpers::try_substitute_variables(request, &variables, &templates)
+            .map_err(|e| anyhow::anyhow!("REQUEST: {e}"))?;
+    }
 
     if opts.very_verbose {
         let size: usize = requests
@@ -1,15 +1,16 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
 
 use crate::cli::Cli;
-use crate::cli::args::HasFormat;
 use anyhow::Result;
 use std::path::PathBuf;
 use tracing::info;
 
 use crate::cli::args::CheckArgs;
+use crate::execution::depends::{self, DependencyGraph};
 use crate::optimizer::OptimizeLevel;
 use crate::parser;
 use crate::parser::ErrorSeverity;
+use crate::report::code_scanning::DiagnosticFormat;
 use crate::report::{CheckReport, CheckSummary, Diagnostic, DiagnosticSeverity, DocumentStructure};
 use crate::semantics;
 use crate::utils::FileUtils;
@@ -36,6 +37,25 @@ fn sort_diagnostics(diagnostics: &mut [Diagnostic]) {
     });
 }
 
+/// The text of 1-based `line` in `source`, for narrowing a diagnostic to
+/// the part of the line it is about.
+fn source_line(source: &str, line: usize) -> &str {
+    source
+        .lines()
+        .nth(line.saturating_sub(1))
+        .unwrap_or_default()
+}
+
+/// Attach a rewrite of a `narrowed_to` range, unless the needle wasn't found:
+/// a fix over the whole-line placeholder would replace the entire line.
+fn with_narrowed_fix(d: Diagnostic, title: &str, new_text: &str) -> Diagnostic {
+    if d.range.is_whole_line() {
+        d
+    } else {
+        d.with_replacement(title, new_text)
+    }
+}
+
 fn build_summary(
     diagnostics: &[Diagnostic],
     total_files: usize,
@@ -176,6 +196,7 @@ fn check_preamble_section_order(doc: &parser::GctfDocument) -> Vec<(usize, Strin
 }
 
 pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
+    let format = DiagnosticFormat::parse(&args.format)?;
     let mut files = Vec::new();
     let mut diagnostics: Vec<Diagnostic> = Vec::new();
     let mut structure: Vec<DocumentStructure> = Vec::new();
@@ -193,6 +214,8 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
     crate::parser::register_extra_inline_option_keys(
         crate::plugins::rhai_plugin::load_all_inline_option_keys(),
     );
+    let schema_pool =
+        semantics::schema::load_flag_pool(args.proto.as_deref(), args.descriptor.as_deref())?;
 
     for path in &args.files {
         if path.is_dir() {
@@ -214,7 +237,9 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
     sort_diagnostics(&mut diagnostics);
 
     if files.is_empty() {
-        if args.is_json() {
+        if let Some(report) = format.render(&diagnostics)? {
+            println!("{report}");
+        } else if format == DiagnosticFormat::Json {
             let summary = build_summary(&diagnostics, files.len(), files_with_errors);
             let report = CheckReport {
                 diagnostics,
@@ -234,20 +259,25 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
 
     info!("Checking {} file(s)...", files.len());
 
+    let dependency_graph = DependencyGraph::build(&files);
+    let dependency_cycles = dependency_graph.cycles();
+
     for file in &files {
         let file_str = file.to_string_lossy().to_string();
         let mut file_has_error = false;
         match parser::parse_gctf(file) {
             Ok(doc) => {
+                let source = doc.metadata.source.as_deref().unwrap_or_default();
                 // Deprecated spellings (HEADERS alias, kebab OPTIONS keys,
                 // kebab attributes) all come from the one shared token-level
                 // `detect_deprecations` (§7.1) — the same source the lenient
                 // commands use — instead of check's own scans + the validator's
                 // per-form warning blocks (removed). `metadata.source` carries
                 // the raw file content the parser already read.
-                if let Some(source) = doc.metadata.source.as_deref() {
+                if !source.is_empty() {
                     for dep in parser::detect_deprecations(&parser::tokenize_gctf(source)) {
                         let line = dep.range.start.line + 1;
+                        let text = source_line(source, line);
                         let (code, hint) = if dep.message.contains("HEADERS") {
                             (
                                 "DEPRECATED_SECTION",
@@ -256,7 +286,8 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                         } else {
                             ("DEPRECATED_KEY_SPELLING", None)
                         };
-                        let mut d = Diagnostic::warning(&file_str, code, &dep.message, line);
+                        let mut d = Diagnostic::warning(&file_str, code, &dep.message, line)
+                            .narrowed_to(text, text.trim());
                         if let Some(h) = hint {
                             d = d.with_hint(h);
                         }
@@ -265,8 +296,10 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                 }
 
                 for (line, msg, hint) in check_preamble_section_order(&doc) {
+                    let text = source_line(source, line);
                     diagnostics.push(
                         Diagnostic::warning(&file_str, "SECTION_ORDER", &msg, line)
+                            .narrowed_to(text, text.trim())
                             .with_hint(&hint),
                     );
                 }
@@ -302,6 +335,7 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                             &mismatch.message,
                             mismatch.line,
                         )
+                        .narrowed_to(source_line(source, mismatch.line), &mismatch.expression)
                         .with_hint(&format!(
                             "Type contract violation in assertion: {}",
                             mismatch.expression
@@ -320,6 +354,10 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                             &unknown.message,
                             unknown.line,
                         )
+                        .narrowed_to(
+                            source_line(source, unknown.line),
+                            &format!("@{}", unknown.plugin_name),
+                        )
                         .with_hint(&format!("Assertion: {}", unknown.expression)),
                     );
                     file_has_error = true;
@@ -327,13 +365,20 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
 
                 let deprecated_plugins = semantics::collect_deprecated_plugin_calls(&doc);
                 for dep in deprecated_plugins {
-                    diagnostics.push(
-                        Diagnostic::warning(&file_str, &dep.rule_id, &dep.message, dep.line)
-                            .with_hint(&format!(
-                                "`fmt --write` auto-fixes this to `{}`",
-                                dep.replacement
-                            )),
-                    );
+                    let d = Diagnostic::warning(&file_str, &dep.rule_id, &dep.message, dep.line)
+                        .narrowed_to(
+                            source_line(source, dep.line),
+                            &format!("@{}(", dep.plugin_name),
+                        )
+                        .with_hint(&format!(
+                            "`fmt --write` auto-fixes this to `{}`",
+                            dep.replacement
+                        ));
+                    diagnostics.push(with_narrowed_fix(
+                        d,
+                        &format!("Rename to `{}`", dep.replacement),
+                        &format!("{}(", dep.replacement),
+                    ));
                 }
 
                 for constant in semantics::collect_constant_assertions(&doc) {
@@ -344,6 +389,7 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                             &constant.message,
                             constant.line,
                         )
+                        .narrowed_to(source_line(source, constant.line), &constant.expression)
                         .with_hint(&format!(
                             "Replace with a real check against the response, e.g. `.field {} \"{}\"`",
                             if constant.always { "==" } else { "!=" },
@@ -355,6 +401,7 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                 for dup in semantics::collect_duplicate_assertions(&doc) {
                     diagnostics.push(
                         Diagnostic::warning(&file_str, &dup.rule_id, &dup.message, dup.line)
+                            .narrowed_to(source_line(source, dup.line), &dup.expression)
                             .with_hint("Remove the duplicate or change it to check something else"),
                     );
                 }
@@ -367,15 +414,84 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                             &redundant.message,
                             redundant.line,
                         )
+                        .narrowed_to(source_line(source, redundant.line), &redundant.expression)
                         .with_hint("Remove this ASSERTS line, or move the field out of RESPONSE if you want ASSERTS to be its only check"),
                     );
                 }
 
+                for finding in
+                    semantics::schema::collect_schema_findings(&doc, schema_pool.as_ref())
+                {
+                    let mut d = if finding.is_error() {
+                        file_has_error = true;
+                        Diagnostic::error(
+                            &file_str,
+                            finding.rule_id,
+                            &finding.message,
+                            finding.line,
+                        )
+                    } else {
+                        Diagnostic::warning(
+                            &file_str,
+                            finding.rule_id,
+                            &finding.message,
+                            finding.line,
+                        )
+                    };
+                    d = d.narrowed_to(source_line(source, finding.line), &finding.token);
+                    if let Some(hint) = &finding.hint {
+                        d = d.with_hint(hint);
+                    }
+                    diagnostics.push(d);
+                }
+
+                // `META.depends_on`: every listed file must exist, and no file
+                // may (transitively) depend on itself — `run` would never
+                // start it.
+                let meta_line = doc
+                    .sections
+                    .iter()
+                    .find(|s| s.section_type == parser::ast::SectionType::Meta)
+                    .map_or(1, |s| s.start_line + 1);
+                for dep in dependency_graph.dependencies(file) {
+                    if dependency_graph.is_missing(dep) {
+                        file_has_error = true;
+                        diagnostics.push(
+                            Diagnostic::error(
+                                &file_str,
+                                "DEPENDENCY_NOT_FOUND",
+                                &format!("META.depends_on file not found: {}", dep.display()),
+                                meta_line,
+                            )
+                            .with_hint("depends_on paths are relative to this file's directory"),
+                        );
+                    }
+                }
+                let node = depends::normalize_path(file);
+                if let Some(cycle) = dependency_cycles.iter().find(|c| c.files.contains(&node)) {
+                    file_has_error = true;
+                    diagnostics.push(
+                        Diagnostic::error(
+                            &file_str,
+                            "DEPENDENCY_CYCLE",
+                            &format!("META.depends_on cycle: {cycle}"),
+                            meta_line,
+                        )
+                        .with_hint("Remove one depends_on entry in the loop"),
+                    );
+                }
+
                 // Same detection the LSP already surfaces
                 // (`crate::lsp::handlers::collect_unused_variables`) — `check`
                 // never called it, so a dead EXTRACT variable was only ever
                 // visible in the editor, not in CI/terminal `check` output.
-                for unused in crate::lsp::handlers::collect_unused_variables(&doc) {
+                // A file other tests depend on extracts for them, not for itself.
+                let unused_variables = if dependency_graph.has_dependents(file) {
+                    Vec::new()
+                } else {
+                    crate::lsp::handlers::collect_unused_variables(&doc)
+                };
+                for unused in unused_variables {
                     diagnostics.push(
                         Diagnostic::warning(
                             &file_str,
@@ -463,15 +579,21 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                 let opt_level = cli.optimize_level(OptimizeLevel::Safe);
                 let opt_hints = crate::optimizer::collect_assertion_optimizations(&doc, opt_level);
                 for hint in opt_hints {
-                    diagnostics.push(
-                        Diagnostic::warning(
-                            &file_str,
-                            hint.rule_id.as_str(),
-                            &format!("{} → {}", hint.before, hint.after),
-                            hint.line,
-                        )
-                        .with_hint(&hint.preconditions.unwrap_or_default()),
-                    );
+                    // Same span `fmt` rewrites (`apply_optimizer_rewrites`),
+                    // so the attached fix is exactly what `fmt --write` does.
+                    let d = Diagnostic::warning(
+                        &file_str,
+                        hint.rule_id.as_str(),
+                        &format!("{} → {}", hint.before, hint.after),
+                        hint.line,
+                    )
+                    .narrowed_to(source_line(source, hint.line), &hint.before)
+                    .with_hint(&hint.preconditions.unwrap_or_default());
+                    diagnostics.push(with_narrowed_fix(
+                        d,
+                        &format!("Rewrite to `{}`", hint.after),
+                        &hint.after,
+                    ));
                 }
 
                 if args.bench
@@ -487,7 +609,7 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
                     file_has_error = true;
                 }
 
-                if !args.is_json() && !file_has_error {
+                if format == DiagnosticFormat::Text && !file_has_error {
                     println!(
                         "{} {} ... OK",
                         crate::report::style::pass_icon(),
@@ -533,14 +655,13 @@ pub async fn handle_check(args: &CheckArgs, cli: &Cli) -> Result<()> {
         ).with_hint("Exact-match RESPONSE/ERROR is a real check, but ASSERTS lets you check specific fields without pinning the whole payload"));
     }
 
-    if !args.is_json() {
-        sort_diagnostics(&mut diagnostics);
+    sort_diagnostics(&mut diagnostics);
+    if let Some(report) = format.render(&diagnostics)? {
+        println!("{report}");
+    } else if format == DiagnosticFormat::Text {
         print_text_diagnostics(&diagnostics);
         print_check_summary(&diagnostics, files.len(), files_with_errors);
-    }
-
-    if args.is_json() {
-        sort_diagnostics(&mut diagnostics);
+    } else {
         let summary = build_summary(&diagnostics, files.len(), files_with_errors);
         let report = CheckReport {
             diagnostics,
@@ -723,4 +844,17 @@ mod tests {
             "fmt now reorders preamble sections for real: {hint}"
         );
     }
+
+    #[test]
+    fn fix_is_attached_only_to_a_narrowed_range() {
+        let fix = |line: &str| {
+            let d = Diagnostic::warning("t.gctf", "SEM_D001", "deprecated", 3)
+                .narrowed_to(line, "@uuid(");
+            with_narrowed_fix(d, "Rename to `@is_uuid`", "@is_uuid(").quick_fix
+        };
+        let edit = &fix("  @uuid(.id)").unwrap().edits[0];
+        assert_eq!((edit.range.start.column, edit.range.end.column), (3, 9));
+        // Spaced differently: nothing to point the rewrite at.
+        assert!(fix("  @uuid (.id)").is_none());
+    }
 }
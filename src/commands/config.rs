@@ -0,0 +1,180 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use std::path::PathBuf;
+
+use crate::cli::args::{ConfigAction, ConfigArgs, ConfigShowArgs, HasFormat, RunArgs};
+use crate::cli::{Cli, Commands};
+use crate::config::{self, CONFIG_KEYS, LoadedConfig};
+use crate::serve::project::env_search_start;
+
+/// Load the config files that apply to this invocation and merge them under
+/// the flags given on the command line, then install the result for the
+/// process. Only `run`, `bench`, `check` and `play` read config files.
+pub fn apply_file_config(cli: &mut Cli, matches: &ArgMatches) -> Result<()> {
+    let start = match &cli.command {
+        Some(Commands::Run(args)) => env_search_start(&args.test_paths),
+        None => env_search_start(&cli.run_args.test_paths),
+        Some(Commands::Bench(args)) => env_search_start(&args.test_paths),
+        Some(Commands::Check(args)) => env_search_start(&args.files),
+        Some(Commands::Play(args)) => args.dir.clone(),
+        _ => return Ok(()),
+    };
+    let loaded = config::load_config(&start)?;
+
+    match &mut cli.command {
+        Some(Commands::Run(args)) => {
+            if let Some(matches) = matches.subcommand_matches("run") {
+                merge_run_args(args, matches, &loaded);
+            }
+        }
+        None => merge_run_args(&mut cli.run_args, matches, &loaded),
+        _ => {}
+    }
+
+    if loaded.source("progress.color").is_some() && !loaded.config.progress.color {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+
+    config::install(loaded);
+    Ok(())
+}
+
+/// Fill every `run` flag not given on the command line from the config files.
+fn merge_run_args(args: &mut RunArgs, matches: &ArgMatches, loaded: &LoadedConfig) {
+    let from_file = |key: &str, id: &str| {
+        loaded.source(key).is_some() && matches.value_source(id) != Some(ValueSource::CommandLine)
+    };
+    let general = &loaded.config.general;
+
+    if from_file("general.parallel", "parallel") {
+        args.parallel = general.parallel.clone();
+    }
+    if from_file("general.timeout", "timeout") {
+        args.timeout = general.timeout;
+    }
+    if from_file("general.retry", "retry") {
+        args.retry = general.retry;
+    }
+    if from_file("general.retry_delay", "retry_delay") {
+        args.retry_delay = general.retry_delay;
+    }
+    if from_file("general.log_format", "log_format") {
+        args.log_format = general.log_format.clone();
+    }
+    if from_file("general.log_output", "log_output") {
+        args.log_output = general.log_output.as_ref().map(PathBuf::from);
+    }
+    if from_file("progress.mode", "progress") {
+        args.progress = loaded.config.progress.mode.clone();
+    }
+    if from_file("coverage.enabled", "coverage") {
+        args.coverage = loaded.config.coverage.enabled;
+    }
+    if from_file("coverage.output", "coverage_output") {
+        args.coverage_output = loaded.config.coverage.output.as_ref().map(PathBuf::from);
+    }
+}
+
+pub fn handle_config(args: &ConfigArgs) -> Result<()> {
+    match &args.action {
+        ConfigAction::Show(show) => handle_config_show(show),
+    }
+}
+
+/// One effective setting: its value and where that value came from.
+struct Setting {
+    key: &'static str,
+    value: serde_json::Value,
+    source: String,
+}
+
+fn effective_settings(loaded: &LoadedConfig) -> Result<Vec<Setting>> {
+    let values = serde_json::to_value(&loaded.config)?;
+    let env_address = std::env::var(config::ENV_GRPCTESTIFY_ADDRESS).ok();
+
+    Ok(CONFIG_KEYS
+        .iter()
+        .map(|&key| {
+            let (section, field) = key.split_once('.').unwrap_or((key, ""));
+            let mut value = values[section][field].clone();
+            let mut source = loaded
+                .source(key)
+                .map_or_else(|| "default".to_string(), |p| p.display().to_string());
+            // The environment variable outranks config files for the address.
+            if key == "general.address"
+                && let Some(address) = &env_address
+            {
+                value = serde_json::Value::String(address.clone());
+                source = format!("${}", config::ENV_GRPCTESTIFY_ADDRESS);
+            }
+            Setting { key, value, source }
+        })
+        .collect())
+}
+
+fn handle_config_show(args: &ConfigShowArgs) -> Result<()> {
+    let loaded = config::load_config(&env_search_start(&args.paths))?;
+    let settings = effective_settings(&loaded)?;
+
+    if args.is_json() {
+        let settings: serde_json::Map<String, serde_json::Value> = settings
+            .into_iter()
+            .map(|s| {
+                (
+                    s.key.to_string(),
+                    serde_json::json!({ "value": s.value, "source": s.source }),
+                )
+            })
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "files": loaded.files,
+                "settings": settings,
+            }))?
+        );
+        return Ok(());
+    }
+
+    let dim = crate::report::style::dim_style();
+    let bold = crate::report::style::bold_style();
+
+    if loaded.files.is_empty() {
+        println!(
+            "{}",
+            dim.apply_to("No config files found — built-in defaults apply.")
+        );
+    } else {
+        println!("Config files (lowest precedence first):");
+        for file in &loaded.files {
+            println!("  {}", file.display());
+        }
+    }
+
+    let width = settings
+        .iter()
+        .map(|s| s.key.split_once('.').map_or(0, |(_, f)| f.len()))
+        .max()
+        .unwrap_or(0);
+    let mut current_section = "";
+    for setting in &settings {
+        let (section, field) = setting.key.split_once('.').unwrap_or((setting.key, ""));
+        if section != current_section {
+            println!("\n{}", bold.apply_to(format!("[{section}]")));
+            current_section = section;
+        }
+        let value = if setting.value.is_null() {
+            "(unset)".to_string()
+        } else {
+            setting.value.to_string()
+        };
+        println!(
+            "{field:<width$} = {value}  {}",
+            dim.apply_to(format!("# {}", setting.source))
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+// `coverage merge <files...>`: one report out of the JSON coverage reports of
+// several runs, e.g. a suite split across CI jobs, rendered in any coverage
+// format and optionally gated on minimum percentages.
+//
+// The gate is shared with `run --coverage-min-*`.
+
+use anyhow::{Context, Result};
+
+use crate::cli::args::{CoverageAction, CoverageArgs, CoverageMergeArgs};
+use crate::report::coverage::{CoverageReport, CoverageShortfall, CoverageThresholds};
+
+pub fn handle_coverage(args: &CoverageArgs) -> Result<()> {
+    match &args.action {
+        CoverageAction::Merge(merge) => handle_coverage_merge(merge),
+    }
+}
+
+fn handle_coverage_merge(args: &CoverageMergeArgs) -> Result<()> {
+    let thresholds = thresholds(args.min_methods, args.min_fields, &args.min_service)?;
+
+    let mut reports = Vec::new();
+    for file in &args.files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let report: CoverageReport = serde_json::from_str(&content).with_context(|| {
+            format!(
+                "{} is not a JSON coverage report (run --coverage --coverage-format json)",
+                file.display()
+            )
+        })?;
+        reports.push(report);
+    }
+    let merged = CoverageReport::merge(reports);
+
+    let rendered = match args.format.to_ascii_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&merged)?,
+        "text" => merged.render_text(),
+        "html" => merged.render_html(),
+        other => anyhow::bail!("Unknown coverage format `{other}` (expected json, text or html)"),
+    };
+    if let Some(path) = &args.output {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, rendered)
+            .with_context(|| format!("Failed to write coverage report to {}", path.display()))?;
+    } else {
+        println!("{rendered}");
+    }
+
+    let shortfalls = check(&merged, &thresholds)?;
+    if !shortfalls.is_empty() {
+        eprint!("{}", render_shortfalls(&shortfalls));
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Gather the minimum-coverage flags, rejecting percentages outside 0–100
+/// before any test runs.
+pub(crate) fn thresholds(
+    min_methods: Option<f64>,
+    min_fields: Option<f64>,
+    services: &[String],
+) -> Result<CoverageThresholds> {
+    for pct in [min_methods, min_fields].into_iter().flatten() {
+        if !(0.0..=100.0).contains(&pct) {
+            anyhow::bail!("Coverage minimum {pct} is not a percentage between 0 and 100");
+        }
+    }
+    let services = services
+        .iter()
+        .map(|s| s.parse().map_err(anyhow::Error::msg))
+        .collect::<Result<_>>()
+        .context("Invalid per-service coverage minimum")?;
+    Ok(CoverageThresholds {
+        min_methods,
+        min_fields,
+        services,
+    })
+}
+
+pub(crate) fn check(
+    report: &CoverageReport,
+    thresholds: &CoverageThresholds,
+) -> Result<Vec<CoverageShortfall>> {
+    report.check(thresholds).map_err(anyhow::Error::msg)
+}
+
+/// Each missed minimum, followed by the methods or fields left uncovered.
+pub(crate) fn render_shortfalls(shortfalls: &[CoverageShortfall]) -> String {
+    use crate::report::style::{dim_style, fail_icon};
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    for shortfall in shortfalls {
+        let _ = writeln!(out, "{} {shortfall}", fail_icon());
+        for uncovered in &shortfall.uncovered {
+            let _ = writeln!(out, "    {}", dim_style().apply_to(uncovered));
+        }
+    }
+    out
+}
@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use crate::cli::args::DocsArgs;
 use crate::parser::ast::{SectionContent, SectionType};
 use crate::parser::{self};
-use crate::report::coverage::CoverageReport;
+use crate::report::coverage::{CoverageFile, CoverageReport};
 use crate::report::kernel;
 use crate::utils::FileUtils;
 
@@ -194,14 +194,21 @@ fn build_flow_step(d: &parser::ast::GctfDocument) -> FlowStep {
     }
 }
 
-fn coverage_line(coverage: Option<&CoverageReport>, service: &str) -> Option<String> {
+fn coverage_file<'a>(
+    coverage: Option<&'a CoverageReport>,
+    service: &str,
+) -> Option<&'a CoverageFile> {
     let report = coverage?;
     // `CoverageFile.uri` is built from `Service::name()` (the bare service
     // name), never the `package.Service` key used to group pages here — a
     // packaged service's coverage would never match otherwise.
     let short_name = service.rsplit('.').next().unwrap_or(service);
     let uri = format!("grpc://{short_name}");
-    let file = report.files.iter().find(|f| f.uri == uri)?;
+    report.files.iter().find(|f| f.uri == uri)
+}
+
+fn coverage_line(coverage: Option<&CoverageReport>, service: &str) -> Option<String> {
+    let file = coverage_file(coverage, service)?;
     let pct = if file.statements.total > 0 {
         (file.statements.covered as f64 / file.statements.total as f64) * 100.0
     } else {
@@ -213,6 +220,34 @@ fn coverage_line(coverage: Option<&CoverageReport>, service: &str) -> Option<Str
     ))
 }
 
+/// Per-method calls and the status codes they returned, when the report
+/// recorded any statuses for the service.
+fn method_status_table(coverage: Option<&CoverageReport>, service: &str) -> Option<String> {
+    let file = coverage_file(coverage, service)?;
+    if file.methods.iter().all(|m| m.statuses.is_empty()) {
+        return None;
+    }
+    let mut out = String::from("| Method | Calls | Status codes |\n|---|---|---|\n");
+    for method in &file.methods {
+        let codes: Vec<String> = method
+            .statuses
+            .iter()
+            .map(|(code, calls)| format!("`{code}` ×{calls}"))
+            .collect();
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            method.name,
+            method.calls,
+            if codes.is_empty() {
+                "—".to_string()
+            } else {
+                codes.join(", ")
+            }
+        ));
+    }
+    Some(out)
+}
+
 fn render_index(
     by_service: &BTreeMap<String, Vec<MethodFlow>>,
     coverage: Option<&CoverageReport>,
@@ -229,6 +264,23 @@ fn render_index(
             "**Overall coverage:** {}/{} methods called ({:.1}%)\n\n",
             report.summary.covered, report.summary.total, pct
         ));
+        for (label, stats) in [
+            ("Error paths", &report.error_summary),
+            ("Enum values", &report.enum_summary),
+            ("Oneof branches", &report.oneof_summary),
+        ] {
+            if stats.total > 0 {
+                out.push_str(&format!(
+                    "- {label}: {}/{} ({:.1}%)\n",
+                    stats.covered,
+                    stats.total,
+                    stats.covered as f64 / stats.total as f64 * 100.0
+                ));
+            }
+        }
+        if report.error_summary.total + report.enum_summary.total + report.oneof_summary.total > 0 {
+            out.push('\n');
+        }
     }
 
     out.push_str("| Service | Methods | Tests |\n|---|---|---|\n");
@@ -256,6 +308,10 @@ fn render_service_page(
         out.push_str(&line);
         out.push('\n');
     }
+    if let Some(table) = method_status_table(coverage, service) {
+        out.push_str(&table);
+        out.push('\n');
+    }
 
     for flow in flows {
         out.push_str(&format!("## {}\n\n", flow.title));
@@ -353,10 +409,11 @@ mod tests {
     }
 
     fn report_with_uri(uri: &str, covered: usize, total: usize) -> CoverageReport {
-        use crate::report::coverage::{CoverageFile, CoverageStats};
+        use crate::report::coverage::CoverageStats;
         CoverageReport {
             files: vec![CoverageFile {
                 uri: uri.to_string(),
+                service: None,
                 statements: CoverageStats { covered, total },
                 branches: None,
                 functions: None,
@@ -364,11 +421,13 @@ mod tests {
                 methods: Vec::new(),
             }],
             messages: Vec::new(),
+            enums: Vec::new(),
+            oneofs: Vec::new(),
             summary: CoverageStats { covered, total },
-            field_summary: CoverageStats {
-                covered: 0,
-                total: 0,
-            },
+            field_summary: CoverageStats::default(),
+            error_summary: CoverageStats::default(),
+            enum_summary: CoverageStats::default(),
+            oneof_summary: CoverageStats::default(),
         }
     }
 
@@ -393,6 +452,33 @@ mod tests {
         assert!(coverage_line(None, "users.UserService").is_none());
     }
 
+    #[test]
+    fn method_status_table_lists_returned_codes() {
+        use crate::report::coverage::MethodCoverage;
+        let mut report = report_with_uri("grpc://UserService", 1, 2);
+        let method = |name: &str, calls: u64, statuses: &[(&str, u64)]| MethodCoverage {
+            name: name.to_string(),
+            calls,
+            input_type: None,
+            output_type: None,
+            statuses: statuses.iter().map(|(c, n)| (c.to_string(), *n)).collect(),
+        };
+        report.files[0].methods = vec![
+            method("Delete", 0, &[]),
+            method("Get", 3, &[("NotFound", 1), ("OK", 2)]),
+        ];
+
+        let table = method_status_table(Some(&report), "users.UserService").unwrap();
+        assert!(table.contains("| Delete | 0 | — |"), "{table}");
+        assert!(
+            table.contains("| Get | 3 | `NotFound` ×1, `OK` ×2 |"),
+            "{table}"
+        );
+
+        report.files[0].methods[1].statuses.clear();
+        assert!(method_status_table(Some(&report), "users.UserService").is_none());
+    }
+
     #[test]
     fn render_sequence_diagram_marks_error_steps_distinctly() {
         let flow = MethodFlow {
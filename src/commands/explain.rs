@@ -45,8 +45,13 @@ fn validation_passed_from_workflow(workflow: &Workflow) -> bool {
     true
 }
 
-fn sorted_key_values(map: &crate::parser::OrderedStringMap) -> Vec<(&str, &str)> {
-    let mut pairs: Vec<(&str, &str)> = map.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+fn sorted_key_values<'a>(
+    map: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> Vec<(&'a str, &'a str)> {
+    let mut pairs: Vec<(&str, &str)> = map
+        .into_iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect();
     pairs.sort_by_key(|(ka, _)| *ka);
     pairs
 }
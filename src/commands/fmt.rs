@@ -1,12 +1,16 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
 
-use anyhow::Result;
+use anyhow::{Result, bail};
+use std::path::Path;
 use tracing::{debug, error, warn};
 
 use crate::cli::args::{Cli, FmtArgs};
 use crate::optimizer;
 use crate::optimizer::OptimizeLevel;
 use crate::parser;
+use crate::report::Diagnostic;
+use crate::report::code_scanning::DiagnosticFormat;
+use crate::report::diagnostics::{DiagnosticRange, Position, QuickFix, TextEdit};
 use crate::semantics;
 use crate::utils::FileUtils;
 
@@ -913,7 +917,72 @@ fn apply_optimizer_rewrites(
     rewritten
 }
 
+/// Logs a problem the way text mode always has, and keeps it for a SARIF or
+/// GitLab report.
+fn report_error(report: &mut Vec<Diagnostic>, file: &Path, code: &str, message: &str, line: usize) {
+    error!("{}:{}: [{}] {}", file.display(), line, code, message);
+    report.push(Diagnostic::error(
+        &file.display().to_string(),
+        code,
+        message,
+        line,
+    ));
+}
+
+/// `FORMAT_NEEDED` placed at the first line formatting changes, with the
+/// formatted file as a whole-file replacement.
+fn format_needed_diagnostic(file: &Path, original: &str, formatted: &str) -> Diagnostic {
+    let first_change = original
+        .lines()
+        .zip(formatted.lines())
+        .position(|(a, b)| a != b)
+        .unwrap_or_else(|| original.lines().count().min(formatted.lines().count()))
+        + 1;
+    let line_count = original.lines().count();
+    let end = if original.is_empty() || original.ends_with('\n') {
+        Position {
+            line: line_count + 1,
+            column: 1,
+        }
+    } else {
+        Position {
+            line: line_count,
+            column: original.lines().last().unwrap_or_default().chars().count() + 1,
+        }
+    };
+    let mut d = Diagnostic::warning(
+        &file.display().to_string(),
+        "FORMAT_NEEDED",
+        "File is not formatted",
+        first_change,
+    )
+    .with_hint(&format!("Run `grpctestify fmt -w {}`", file.display()));
+    d.quick_fix = Some(QuickFix {
+        title: "Format file".to_string(),
+        edits: vec![TextEdit {
+            range: DiagnosticRange {
+                start: Position { line: 1, column: 1 },
+                end,
+            },
+            new_text: formatted.to_string(),
+        }],
+    });
+    d
+}
+
 pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
+    let format = DiagnosticFormat::parse(&args.format)?;
+    if format == DiagnosticFormat::Json {
+        bail!("fmt supports --format text, sarif or gitlab");
+    }
+    if format.is_code_scanning() && args.write {
+        bail!(
+            "--format {} only reports; it can't be combined with --write",
+            args.format
+        );
+    }
+    let text = format == DiagnosticFormat::Text;
+    let mut report: Vec<Diagnostic> = Vec::new();
     let level = cli.optimize_level(optimizer::OptimizeLevel::Safe);
     let mut files = Vec::new();
     let mut has_error = false;
@@ -938,6 +1007,12 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
             files.push(path.clone());
         } else {
             error!("Path not found: {}", path.display());
+            report.push(Diagnostic::error(
+                &path.display().to_string(),
+                "FILE_NOT_FOUND",
+                "Path not found",
+                1,
+            ));
             has_error = true;
         }
     }
@@ -946,6 +1021,9 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
         if !has_error {
             warn!("No .gctf files found to format");
         }
+        if let Some(rendered) = format.render(&report)? {
+            println!("{rendered}");
+        }
         return Ok(());
     }
 
@@ -955,6 +1033,12 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
             Ok(content) => content,
             Err(e) => {
                 error!("Failed to read {}: {}", file.display(), e);
+                report.push(Diagnostic::error(
+                    &file.display().to_string(),
+                    "FILE_NOT_FOUND",
+                    &format!("Failed to read: {e}"),
+                    1,
+                ));
                 has_error = true;
                 continue;
             }
@@ -964,7 +1048,7 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
         let doc = match parser::parse_gctf_from_str(&original, &file_name) {
             Ok(doc) => doc,
             Err(e) => {
-                error!("{}:1: [PARSE_ERROR] {}", file.display(), e);
+                report_error(&mut report, &file, "PARSE_ERROR", &e.to_string(), 1);
                 has_error = true;
                 continue;
             }
@@ -974,30 +1058,30 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
         let mut chain_has_error = false;
         for d in doc.iter_chain() {
             if let Err(e) = parser::validate_document(d) {
-                error!("{}:1: [VALIDATION_ERROR] {}", file.display(), e);
+                report_error(&mut report, &file, "VALIDATION_ERROR", &e.to_string(), 1);
                 chain_has_error = true;
             }
             // detached: the semantic passes below are chain-aware internally too
             let single = d.detached();
             for mismatch in semantics::collect_assertion_type_mismatches(&single) {
-                error!(
-                    "{}:{}: [{}] {}",
-                    file.display(),
+                report_error(
+                    &mut report,
+                    &file,
+                    &mismatch.rule_id,
+                    &mismatch.message,
                     mismatch.line,
-                    mismatch.rule_id,
-                    mismatch.message
                 );
                 chain_has_error = true;
             }
             for unknown in
                 semantics::collect_unknown_plugin_calls_with_extra(&single, &rhai_plugin_names)
             {
-                error!(
-                    "{}:{}: [{}] {}",
-                    file.display(),
+                report_error(
+                    &mut report,
+                    &file,
+                    &unknown.rule_id,
+                    &unknown.message,
                     unknown.line,
-                    unknown.rule_id,
-                    unknown.message
                 );
                 chain_has_error = true;
             }
@@ -1012,32 +1096,26 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
                 );
             }
             for constant in semantics::collect_constant_assertions(&single) {
-                error!(
-                    "{}:{}: [{}] {}",
-                    file.display(),
+                report_error(
+                    &mut report,
+                    &file,
+                    &constant.rule_id,
+                    &constant.message,
                     constant.line,
-                    constant.rule_id,
-                    constant.message
                 );
                 chain_has_error = true;
             }
             for dup in semantics::collect_duplicate_assertions(&single) {
-                error!(
-                    "{}:{}: [{}] {}",
-                    file.display(),
-                    dup.line,
-                    dup.rule_id,
-                    dup.message
-                );
+                report_error(&mut report, &file, &dup.rule_id, &dup.message, dup.line);
                 chain_has_error = true;
             }
             for redundant in semantics::collect_redundant_response_assertions(&single) {
-                error!(
-                    "{}:{}: [{}] {}",
-                    file.display(),
+                report_error(
+                    &mut report,
+                    &file,
+                    &redundant.rule_id,
+                    &redundant.message,
                     redundant.line,
-                    redundant.rule_id,
-                    redundant.message
                 );
                 chain_has_error = true;
             }
@@ -1051,7 +1129,7 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
         let formatted = match format_gctf_content_with_level(&original, &file_name, level) {
             Ok(f) => f,
             Err(e) => {
-                error!("{}:1: [FORMAT_ERROR] {}", file.display(), e);
+                report_error(&mut report, &file, "FORMAT_ERROR", &e.to_string(), 1);
                 has_error = true;
                 continue;
             }
@@ -1077,15 +1155,18 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
                 }
             }
         } else if changed {
-            println!(
-                "{} {}:1: [FORMAT_NEEDED] File is not formatted",
-                crate::report::style::fail_icon(),
-                file.display()
-            );
-            println!("    hint: Run `grpctestify fmt -w {}`", file.display());
+            if text {
+                println!(
+                    "{} {}:1: [FORMAT_NEEDED] File is not formatted",
+                    crate::report::style::fail_icon(),
+                    file.display()
+                );
+                println!("    hint: Run `grpctestify fmt -w {}`", file.display());
+            }
+            report.push(format_needed_diagnostic(&file, &original, &formatted));
             has_error = true;
             files_needing_format += 1;
-        } else {
+        } else if text {
             println!(
                 "{} {} ... OK",
                 crate::report::style::pass_icon(),
@@ -1094,7 +1175,11 @@ pub async fn handle_fmt(args: &FmtArgs, cli: &Cli) -> Result<()> {
         }
     }
 
-    print_fmt_summary(args.write, total_files, files_written, files_needing_format);
+    if let Some(rendered) = format.render(&report)? {
+        println!("{rendered}");
+    } else {
+        print_fmt_summary(args.write, total_files, files_written, files_needing_format);
+    }
 
     if has_error {
         return Err(anyhow::anyhow!("Formatting failed with errors"));
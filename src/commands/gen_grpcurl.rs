@@ -104,6 +104,7 @@ async fn execute_call(
             .and_then(|p| p.parse::<WireProtocol>().ok())
             .unwrap_or_default(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
     let mut transport = TransportRef::new(&config)
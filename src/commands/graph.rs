@@ -3,15 +3,17 @@
 // No new grammar, no new persistent state — this renders structure that
 // already exists: `_setup.gctf`/`_teardown.gctf` directory fixtures (same
 // convention `run` uses, via `run::partition_fixtures`) and multi-document
-// chain steps within a single file. Two cheap text formats (tree, Mermaid
-// flowchart) — no interactive HTML graph library.
+// chain steps within a single file, plus `META.depends_on` edges between
+// files. Two cheap text formats (tree, Mermaid flowchart) — no interactive
+// HTML graph library.
 
 use anyhow::Result;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use crate::cli::args::GraphArgs;
 use crate::commands::run::DirFixtures;
+use crate::execution::depends::{self, DependencyGraph};
 use crate::parser;
 use crate::utils::FileUtils;
 
@@ -30,20 +32,26 @@ pub fn handle_graph(args: &GraphArgs) -> Result<()> {
             files.push(path.clone());
         }
     }
-    files.sort();
-    files.dedup();
 
     if files.is_empty() {
         println!("No .gctf test files found.");
         return Ok(());
     }
 
+    // Dependencies outside the given paths are drawn too, as `run` would
+    // pull them in.
+    let dependencies = DependencyGraph::build(&files);
+    files = files.iter().map(|f| depends::normalize_path(f)).collect();
+    files.extend(dependencies.files().cloned());
+    files.sort();
+    files.dedup();
+
     let by_dir = group_by_directory(files);
 
     if args.format.eq_ignore_ascii_case("mermaid") {
-        print!("{}", render_mermaid(&by_dir));
+        print!("{}", render_mermaid(&by_dir, &dependencies));
     } else {
-        print!("{}", render_text(&by_dir));
+        print!("{}", render_text(&by_dir, &dependencies));
     }
     Ok(())
 }
@@ -120,7 +128,17 @@ fn node_label(path: &Path) -> String {
     }
 }
 
-fn render_text(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
+/// `name (depends on a.gctf, b.gctf)` for a test with `META.depends_on`.
+fn with_dependencies(label: String, path: &Path, dependencies: &DependencyGraph) -> String {
+    let deps = dependencies.dependencies(path);
+    if deps.is_empty() {
+        return label;
+    }
+    let deps: Vec<String> = deps.iter().map(|d| d.display().to_string()).collect();
+    format!("{label} (depends on {})", deps.join(", "))
+}
+
+fn render_text(by_dir: &BTreeMap<PathBuf, DirGroup>, dependencies: &DependencyGraph) -> String {
     let mut out = String::new();
     for (dir, group) in by_dir {
         let dir_display = if dir.as_os_str().is_empty() {
@@ -136,7 +154,10 @@ fn render_text(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
             rows.push((format!("{} (setup)", node_label(setup)), false));
         }
         for test in &group.tests {
-            rows.push((node_label(test), false));
+            rows.push((
+                with_dependencies(node_label(test), test, dependencies),
+                false,
+            ));
         }
         if let Some(teardown) = &group.fixtures.teardown {
             rows.push((format!("{} (teardown)", node_label(teardown)), false));
@@ -154,8 +175,9 @@ fn render_text(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
     out
 }
 
-fn render_mermaid(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
+fn render_mermaid(by_dir: &BTreeMap<PathBuf, DirGroup>, dependencies: &DependencyGraph) -> String {
     let mut out = String::from("```mermaid\nflowchart TD\n");
+    let mut test_nodes: HashMap<PathBuf, String> = HashMap::new();
     let mut next_id = 0usize;
     let mut id = || {
         let n = next_id;
@@ -192,6 +214,7 @@ fn render_mermaid(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
                     "    {node}[\"{}\"]\n",
                     mermaid_escape(&node_label(p))
                 ));
+                test_nodes.insert(depends::normalize_path(p), node.clone());
                 node
             })
             .collect();
@@ -221,6 +244,20 @@ fn render_mermaid(by_dir: &BTreeMap<PathBuf, DirGroup>) -> String {
         }
         out.push_str("  end\n");
     }
+
+    // `META.depends_on`: dotted edges from each prerequisite to its dependent.
+    for group in by_dir.values() {
+        for test in &group.tests {
+            let Some(node) = test_nodes.get(&depends::normalize_path(test)) else {
+                continue;
+            };
+            for dep in dependencies.dependencies(test) {
+                if let Some(dep_node) = test_nodes.get(dep) {
+                    out.push_str(&format!("  {dep_node} -. depends_on .-> {node}\n"));
+                }
+            }
+        }
+    }
     out.push_str("```\n");
     out
 }
@@ -249,7 +286,7 @@ mod tests {
                 },
             },
         );
-        let text = render_text(&by_dir);
+        let text = render_text(&by_dir, &DependencyGraph::default());
         let setup_pos = text.find("_setup.gctf (setup)").unwrap();
         let get_pos = text.find("get.gctf").unwrap();
         let list_pos = text.find("list.gctf").unwrap();
@@ -270,7 +307,7 @@ mod tests {
                 fixtures: DirFixtures::default(),
             },
         );
-        let text = render_text(&by_dir);
+        let text = render_text(&by_dir, &DependencyGraph::default());
         assert!(!text.contains("setup"));
         assert!(!text.contains("teardown"));
         assert!(text.contains("a.gctf"));
@@ -289,7 +326,7 @@ mod tests {
                 },
             },
         );
-        let mermaid = render_mermaid(&by_dir);
+        let mermaid = render_mermaid(&by_dir, &DependencyGraph::default());
         assert!(mermaid.starts_with("```mermaid\nflowchart TD\n"));
         assert!(mermaid.contains("n0 --> n1"));
         assert!(mermaid.contains("n1 --> n2"));
@@ -309,7 +346,7 @@ mod tests {
                 },
             },
         );
-        let mermaid = render_mermaid(&by_dir);
+        let mermaid = render_mermaid(&by_dir, &DependencyGraph::default());
         assert!(mermaid.contains("n0 --> n1"));
     }
 
@@ -337,4 +374,31 @@ mod tests {
         .unwrap();
         assert_eq!(node_label(&file), "single.gctf");
     }
+
+    #[test]
+    fn render_mermaid_draws_depends_on_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        let login = dir.path().join("auth/login.gctf");
+        let order = dir.path().join("orders/create.gctf");
+        std::fs::create_dir_all(login.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(order.parent().unwrap()).unwrap();
+        std::fs::write(
+            &login,
+            "--- ENDPOINT ---\nauth.Auth/Login\n\n--- REQUEST ---\n{}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &order,
+            "--- META ---\ndepends_on: [../auth/login.gctf]\n\n--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{}\n",
+        )
+        .unwrap();
+
+        let dependencies = DependencyGraph::build(std::slice::from_ref(&order));
+        let files: Vec<PathBuf> = dependencies.files().cloned().collect();
+        let by_dir = group_by_directory(files);
+        let mermaid = render_mermaid(&by_dir, &dependencies);
+        assert!(mermaid.contains("n0 -. depends_on .-> n1"), "{mermaid}");
+        let text = render_text(&by_dir, &dependencies);
+        assert!(text.contains("create.gctf (depends on "), "{text}");
+    }
 }
@@ -34,6 +34,15 @@ fn grpcurl_address_parts(raw: &str) -> (String, bool) {
     (raw.to_string(), true)
 }
 
+/// grpcurl's `-unix` address for a `unix:` / `unix-abstract:` target: the
+/// socket path, with Go's `@` prefix for an abstract-namespace name.
+fn grpcurl_unix_address(raw: &str) -> Option<String> {
+    if let Some(name) = raw.strip_prefix("unix-abstract:") {
+        return Some(format!("@{name}"));
+    }
+    crate::grpc::unix_socket_path(raw).map(|p| p.to_string_lossy().into_owned())
+}
+
 fn path_for_invocation(resolved: &Path, cwd: &Path) -> String {
     let normalize = |p: &Path| p.to_string_lossy().replace('\\', "/");
 
@@ -56,7 +65,11 @@ pub fn build_grpcurl_command(
         .ok_or_else(|| anyhow::anyhow!("Missing ENDPOINT section"))?;
 
     let address_raw = runner_helpers::effective_address(doc, None);
-    let (address, plaintext_from_address) = grpcurl_address_parts(&address_raw);
+    let unix_address = grpcurl_unix_address(&address_raw);
+    let (address, plaintext_from_address) = match &unix_address {
+        Some(path) => (path.clone(), true),
+        None => grpcurl_address_parts(&address_raw),
+    };
 
     let tls_config = runner_helpers::build_tls_config(doc, gctf_file);
     let plaintext = plaintext_from_address && tls_config.is_none();
@@ -65,6 +78,9 @@ pub fn build_grpcurl_command(
     if plaintext {
         parts.push("-plaintext".to_string());
     }
+    if unix_address.is_some() {
+        parts.push("-unix".to_string());
+    }
 
     let options = doc.get_options().unwrap_or_default();
     if runner_helpers::parse_compression_option(&options) == Some(CompressionMode::Gzip) {
@@ -237,6 +253,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_grpcurl_unix_address() {
+        assert_eq!(
+            grpcurl_unix_address("unix:///run/app.sock"),
+            Some("/run/app.sock".to_string())
+        );
+        assert_eq!(
+            grpcurl_unix_address("unix-abstract:daemon"),
+            Some("@daemon".to_string())
+        );
+        assert_eq!(grpcurl_unix_address("localhost:50051"), None);
+    }
+
     #[test]
     fn test_shell_quote() {
         assert_eq!(shell_quote("a b"), "'a b'");
@@ -30,6 +30,7 @@ fn client_config(args: &HealthArgs) -> GrpcClientConfig {
             .parse::<WireProtocol>()
             .unwrap_or(crate::grpc::WireProtocol::Grpc),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: args.proxy.clone(),
     }
 }
 
@@ -281,6 +282,7 @@ mod tests {
             watch: false,
             interval: 1.0,
             watch_timeout: 60.0,
+            proxy: None,
         }
     }
 
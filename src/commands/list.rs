@@ -1,9 +1,12 @@
 use crate::cli::args::HasFormat;
 use anyhow::Result;
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use tracing::error;
 
-use crate::cli::args::ListArgs;
+use crate::cli::args::{ListArgs, Shard};
+use crate::execution::depends::{self, DependencyGraph};
+use crate::execution::shard;
 use crate::parser::ast::{SectionContent, SectionType};
 use crate::parser::{self};
 use crate::utils::FileUtils;
@@ -16,7 +19,10 @@ pub fn handle_list(args: &ListArgs) -> Result<()> {
         std::process::exit(1);
     }
 
-    let files = FileUtils::collect_test_files(path, &[]);
+    let mut files = FileUtils::collect_test_files(path, &[]);
+    if let Some(shard) = args.shard {
+        files = shard_files(files, shard, args.shard_timings.as_deref())?;
+    }
 
     if args.is_json() {
         let tests: Vec<serde_json::Value> = files
@@ -84,3 +90,43 @@ pub fn handle_list(args: &ListArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// What `run --shard` would run from `files`: the shard's tests, the
+/// fixtures of their directories, in collection order, then the
+/// `META.depends_on` prerequisites from outside `files`.
+fn shard_files(files: Vec<PathBuf>, shard: Shard, timings: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let timings = timings.map(shard::load_timings).transpose()?;
+    let (tests, fixtures) = crate::commands::run::partition_fixtures(files.clone());
+    let fixture_dirs: HashSet<PathBuf> = fixtures.keys().cloned().collect();
+    let dependencies = DependencyGraph::build(&tests);
+    let mut selected = shard::select(tests, &fixture_dirs, &dependencies, shard, timings.as_ref());
+    let shard_len = selected.len();
+    depends::add_dependencies(&mut selected);
+    let pulled_in = selected.split_off(shard_len);
+    let runs: HashSet<PathBuf> = selected
+        .iter()
+        .chain(&pulled_in)
+        .map(|f| depends::normalize_path(f))
+        .collect();
+    let active_dirs: HashSet<&Path> = runs.iter().filter_map(|t| t.parent()).collect();
+    let is_fixture = |file: &Path| {
+        fixtures.values().any(|fx| {
+            [&fx.setup, &fx.teardown]
+                .into_iter()
+                .flatten()
+                .any(|f| depends::normalize_path(f) == file)
+        })
+    };
+    let mut listed: Vec<PathBuf> = files
+        .iter()
+        .filter(|f| {
+            let file = depends::normalize_path(f);
+            runs.contains(&file)
+                || (is_fixture(&file) && file.parent().is_some_and(|d| active_dirs.contains(d)))
+        })
+        .cloned()
+        .collect();
+    let listed_keys: HashSet<PathBuf> = listed.iter().map(|f| depends::normalize_path(f)).collect();
+    listed.extend(pulled_in.into_iter().filter(|f| !listed_keys.contains(f)));
+    Ok(listed)
+}
@@ -2,6 +2,6 @@ use crate::cli::args::LspArgs;
 use crate::lsp::server::start_lsp_server;
 use anyhow::Result;
 
-pub async fn handle_lsp(_args: &LspArgs) -> Result<()> {
-    start_lsp_server().await
+pub async fn handle_lsp(args: &LspArgs) -> Result<()> {
+    start_lsp_server(args.env.clone()).await
 }
@@ -0,0 +1,72 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::cli::args::MockArgs;
+use crate::mock;
+
+pub async fn handle_mock(args: &MockArgs) -> Result<()> {
+    let paths = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+    let flag_pool =
+        crate::semantics::schema::load_flag_pool(args.proto.as_deref(), args.descriptor.as_deref())
+            .context("Failed to load --proto/--descriptor")?;
+    let stubs = mock::load_stubs(&paths, flag_pool)?;
+    anyhow::ensure!(
+        stubs.stub_count() > 0,
+        "No stubs found: no .gctf file under {} has an ENDPOINT",
+        paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let bold = crate::report::style::bold_style();
+    let dim = crate::report::style::dim_style();
+    println!();
+    println!(
+        "🧪 {} v{}",
+        bold.apply_to("grpctestify mock"),
+        env!("CARGO_PKG_VERSION")
+    );
+    println!(
+        "   {}  {}:{}  (gRPC, gRPC-Web, Connect)",
+        bold.apply_to("➜"),
+        args.host,
+        args.port
+    );
+    println!(
+        "   stubs    {} in {} file(s)",
+        stubs.stub_count(),
+        stubs.files
+    );
+    for (endpoint, endpoint_stubs) in &stubs.stubs {
+        let schema = if stubs.method(endpoint).is_some() {
+            ""
+        } else {
+            " (no schema: JSON only)"
+        };
+        println!(
+            "   {}",
+            dim.apply_to(format!("{endpoint} ×{}{schema}", endpoint_stubs.len()))
+        );
+    }
+    println!();
+
+    let app = mock::build_app(Arc::new(stubs))?;
+    // Bracket bare IPv6 literals for SocketAddr syntax
+    let addr = if args.host.contains(':') && !args.host.starts_with('[') {
+        format!("[{}]:{}", args.host, args.port)
+    } else {
+        format!("{}:{}", args.host, args.port)
+    };
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {addr}"))?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
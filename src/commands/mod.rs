@@ -6,6 +6,8 @@ pub mod bench;
 pub mod bench_compare;
 pub mod call;
 pub mod check;
+pub mod config;
+pub mod coverage;
 pub mod docs;
 pub mod explain;
 pub mod fmt;
@@ -17,16 +19,20 @@ pub mod index_cmd;
 pub mod inspect;
 pub mod list;
 pub mod lsp;
+pub mod mock;
 pub mod plugins;
 pub mod query_cmd;
 pub mod reflect;
 pub mod run;
 pub mod scaffold;
+pub mod schema_diff;
 pub mod serve;
 
 pub use bench::handle_bench;
 pub use call::handle_call;
 pub use check::handle_check;
+pub use config::{apply_file_config, handle_config};
+pub use coverage::handle_coverage;
 pub use docs::handle_docs;
 pub use explain::handle_explain;
 pub use fmt::handle_fmt;
@@ -38,6 +44,7 @@ pub use index_cmd::handle_index;
 pub use inspect::handle_inspect;
 pub use list::handle_list;
 pub use lsp::handle_lsp;
+pub use mock::handle_mock;
 pub use plugins::{
     handle_plugins_install, handle_plugins_list, handle_plugins_remove, handle_plugins_update,
 };
@@ -45,6 +52,7 @@ pub use query_cmd::handle_query;
 pub use reflect::handle_reflect;
 pub use run::run_tests;
 pub use scaffold::handle_scaffold;
+pub use schema_diff::handle_schema_diff;
 pub use serve::handle_play;
 
 /// Build a `TlsConfig` from resolved CLI cert paths — the field-construction
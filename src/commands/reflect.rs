@@ -239,6 +239,7 @@ pub async fn handle_reflect(args: &ReflectArgs) -> Result<()> {
             .parse::<WireProtocol>()
             .unwrap_or(crate::grpc::WireProtocol::Grpc),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: args.proxy.clone(),
     };
 
     let display_address = config.address.clone();
@@ -1,26 +1,31 @@
-use anyhow::Result;
-use futures::stream::StreamExt;
+use anyhow::{Context, Result};
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use tracing::{info, warn};
 
 use crate::cli::Cli;
 use crate::cli::args::RunArgs;
 use crate::config;
 use crate::execution;
+use crate::execution::depends::{self, DependencyGraph};
 use crate::parser;
 use crate::parser::ast::{GctfDocument, SectionContent, SectionType};
 use crate::report;
 use crate::state::{TestMeta, TestResult, TestResults};
 use crate::utils::FileUtils;
 
+/// Template variables, as bound by fixtures, dependencies and data rows.
+type Vars = HashMap<String, serde_json::Value>;
+
 /// A single unit of work fed through the parallel execution stream.
 ///
 /// Without `--data`, files map 1:1 to a [`WorkItem::File`]. With `--data`, each
 /// target file is treated as a template and expanded up-front into one
 /// [`WorkItem::Row`] per data row (all sharing the parsed template document), or
 /// a single [`WorkItem::Error`] when the source is empty/unreadable.
+#[derive(Clone)]
 enum WorkItem {
     /// Ordinary file: parsed and executed as today.
     File(PathBuf),
@@ -29,6 +34,8 @@ enum WorkItem {
         doc: Arc<GctfDocument>,
         vars: HashMap<String, serde_json::Value>,
         name: String,
+        /// Position in the data source; keys the row's `--seed` sequence.
+        row: usize,
     },
     /// A pre-determined failure (empty source, bad source, `--write` with `--data`).
     Error { name: String, message: String },
@@ -189,6 +196,7 @@ fn expand_templates_over_data(
                 doc: doc.clone(),
                 vars: vars.clone(),
                 name,
+                row: i,
             });
         }
     }
@@ -214,7 +222,9 @@ fn dataset_row_vars(row: &serde_json::Value) -> HashMap<String, serde_json::Valu
 }
 
 /// Split `files` into (files without a DATASET section, DATASET-bearing file
-/// paths, work items already expanded from those files' own rows).
+/// paths, work items already expanded from those files' own rows, and the
+/// files of the first kind whose `META.quarantine` is set — read here since
+/// every file is parsed anyway).
 ///
 /// A DATASET section makes its file self-contained — no `--data` needed —
 /// expanding through the identical [`WorkItem::Row`] mechanism `--data`
@@ -223,10 +233,11 @@ fn dataset_row_vars(row: &serde_json::Value) -> HashMap<String, serde_json::Valu
 fn expand_dataset_files(
     files: Vec<PathBuf>,
     write: bool,
-) -> (Vec<PathBuf>, Vec<PathBuf>, Vec<WorkItem>) {
+) -> (Vec<PathBuf>, Vec<PathBuf>, Vec<WorkItem>, HashSet<PathBuf>) {
     let mut plain = Vec::new();
     let mut dataset_files = Vec::new();
     let mut items = Vec::new();
+    let mut quarantined = HashSet::new();
 
     for file in files {
         let doc = match parser::parse_gctf(&file) {
@@ -238,6 +249,9 @@ fn expand_dataset_files(
             }
         };
         let Some(section) = doc.first_section(SectionType::Dataset) else {
+            if execution::quarantine::meta_quarantined(&doc) {
+                quarantined.insert(file.clone());
+            }
             plain.push(file);
             continue;
         };
@@ -283,11 +297,12 @@ fn expand_dataset_files(
                 doc: doc.clone(),
                 vars,
                 name,
+                row: i,
             });
         }
     }
 
-    (plain, dataset_files, items)
+    (plain, dataset_files, items, quarantined)
 }
 
 pub(crate) fn extract_test_meta(doc: &parser::ast::GctfDocument) -> TestMeta {
@@ -465,6 +480,58 @@ fn item_skipped_by_setup(item_dir: Option<&Path>, dirs_setup_failed: &HashSet<Pa
     item_dir.is_some_and(|d| dirs_setup_failed.contains(d))
 }
 
+/// The test file a work item runs, as a `META.depends_on` graph node.
+fn work_item_file(item: &WorkItem) -> Option<PathBuf> {
+    match item {
+        WorkItem::File(path) => Some(depends::normalize_path(path)),
+        WorkItem::Row { doc, .. } => Some(depends::normalize_path(Path::new(&doc.file_path))),
+        WorkItem::Error { name, .. } => Some(depends::normalize_path(Path::new(name))),
+    }
+}
+
+/// Check a work item's `META.depends_on` prerequisites once they have all
+/// finished. Returns their exported variables, merged in declared order, or
+/// the result to report instead of running: a failure when a dependency is
+/// missing, a skip when one did not pass. A dependency run as several rows
+/// passes only if every row did.
+fn dependency_vars(
+    name: &str,
+    dependencies: &[PathBuf],
+    graph: &DependencyGraph,
+    items_by_file: &HashMap<PathBuf, Vec<usize>>,
+    outputs: &[Option<(TestResult, Vars)>],
+) -> std::result::Result<Vars, Box<TestResult>> {
+    let mut vars = Vars::new();
+    for dep in dependencies {
+        let items = items_by_file
+            .get(dep)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        if graph.is_missing(dep) || items.is_empty() {
+            return Err(Box::new(TestResult::fail(
+                name,
+                format!("Dependency not found: {} (META.depends_on)", dep.display()),
+                0,
+                None,
+            )));
+        }
+        for output in items.iter().map(|&i| outputs[i].as_ref()) {
+            match output {
+                Some((result, exported)) if result.status == crate::state::TestStatus::Pass => {
+                    vars.extend(exported.clone());
+                }
+                _ => {
+                    return Err(Box::new(TestResult::skip(
+                        name,
+                        format!("Skipped: dependency {} did not pass", dep.display()),
+                    )));
+                }
+            }
+        }
+    }
+    Ok(vars)
+}
+
 /// Run one fixture file as its own reportable unit, mirroring the reporter
 /// lifecycle of a normal test. Returns `(passed, captured_vars, result)`: the
 /// captured vars are the fixture's EXTRACT bindings (empty for teardown or on
@@ -552,6 +619,8 @@ fn report_output_path(base: &Path, format: crate::cli::LogFormat, multiple: bool
         crate::cli::LogFormat::Yaml => "yaml.yaml",
         crate::cli::LogFormat::JUnit => "junit.xml",
         crate::cli::LogFormat::Html => "html.html",
+        crate::cli::LogFormat::Tap => "tap.tap",
+        crate::cli::LogFormat::Ctrf => "ctrf.json",
         crate::cli::LogFormat::Allure => "allure",
         crate::cli::LogFormat::Console => "console",
     };
@@ -559,6 +628,110 @@ fn report_output_path(base: &Path, format: crate::cli::LogFormat, multiple: bool
 }
 
 pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
+    if args.watch {
+        return watch_tests(cli, args).await;
+    }
+    let test_results = run_cycle(cli, args, None, None, None).await?;
+    if !test_results.all_passed() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `run --watch`: a full run, then a cycle for each burst of changes that
+/// runs only the tests it affects, until interrupted. Channels and descriptor
+/// pools are cached per process, so they stay warm from cycle to cycle.
+async fn watch_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
+    use execution::watch::{WatchSet, Watcher};
+
+    let mut watch_set = WatchSet::build(args);
+    let mut watcher = Watcher::start(&watch_set)?;
+    run_cycle(cli, args, None, None, None).await?;
+    loop {
+        println!(
+            "\n{}",
+            report::style::dim_style().apply_to(format!(
+                "Watching {} director{} for changes (Ctrl-C to stop)",
+                watcher.watched(),
+                if watcher.watched() == 1 { "y" } else { "ies" }
+            ))
+        );
+        // Ctrl-C keeps its default meaning: the process ends, mid-cycle too.
+        let (changed, affected) = loop {
+            let Some(changed) = watcher.next_batch().await else {
+                return Ok(());
+            };
+            let affected = watch_set.affected(&changed);
+            if !affected.is_empty() {
+                break (changed, affected);
+            }
+        };
+
+        if affected.plugins {
+            execution::plugin_dir::reload_plugins();
+        }
+        if affected.protos {
+            apif_grpc_transport::tonic::descriptor::forget_local_descriptors().await;
+        }
+        let names: Vec<String> = changed
+            .iter()
+            .take(3)
+            .map(|p| {
+                p.strip_prefix(std::env::current_dir().unwrap_or_default())
+                    .unwrap_or(p)
+                    .display()
+                    .to_string()
+            })
+            .collect();
+        let more = changed.len().saturating_sub(names.len());
+        println!(
+            "\n{} {}{}",
+            report::style::bold_style().apply_to("↻ Changed:"),
+            names.join(", "),
+            if more > 0 {
+                format!(" (+{more} more)")
+            } else {
+                String::new()
+            }
+        );
+
+        let scope = (!affected.everything).then_some(&affected.tests);
+        match run_cycle(cli, args, scope, None, None).await {
+            Ok(results) if results.total() == 0 => println!("No affected tests to run"),
+            Ok(_) => {}
+            // A bad edit (say, an unreadable --data file) is reported, and the
+            // next change gets another try.
+            Err(e) => eprintln!("{} {e:#}", report::style::fail_style().apply_to("error:")),
+        }
+        watch_set = WatchSet::build(args);
+        watcher.update(&watch_set);
+    }
+}
+
+/// A run embedded in the playground: every reporter event goes to `events`
+/// as a `--stream` line instead of the console, an empty selection is an
+/// error rather than the end of the process, and setting `cancel` stops it
+/// the way a UI that goes away should.
+pub async fn run_streaming(
+    cli: &Cli,
+    args: &RunArgs,
+    events: report::streaming::StreamSink,
+    cancel: &AtomicBool,
+) -> Result<TestResults> {
+    run_cycle(cli, args, None, Some(events), Some(cancel)).await
+}
+
+/// One run over the selected tests — all of them, or with `scope` (in
+/// [`execution::watch::path_key`] form) only those. With `events` the run
+/// reports only through that sink (see [`run_streaming`]). Once `cancel` is
+/// set no further test starts; running tests and teardown fixtures finish.
+async fn run_cycle(
+    cli: &Cli,
+    args: &RunArgs,
+    scope: Option<&BTreeSet<PathBuf>>,
+    events: Option<report::streaming::StreamSink>,
+    cancel: Option<&AtomicBool>,
+) -> Result<TestResults> {
     // Unlike the boolean-plugin/unknown-plugin registries (which `run` never
     // needs — it executes against a real `PluginManager`, not a static
     // snapshot), `parse_inline_options` runs unconditionally for every
@@ -568,7 +741,7 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         crate::plugins::rhai_plugin::load_all_inline_option_keys(),
     );
 
-    // Defensive clamp: buffer_unordered(0) never polls and deadlocks the run.
+    // Defensive clamp: zero worker slots would never start a test.
     let parallel_jobs = cli.parallel_jobs().max(1);
     info!("Parallel jobs: {}", parallel_jobs);
 
@@ -580,6 +753,18 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         info!("No-assert mode enabled (skipping assertions)");
     }
 
+    let project_env = crate::serve::project::resolve_project_env(
+        &crate::serve::project::env_search_start(&args.test_paths),
+        args.env.as_deref(),
+    )?;
+    if let Some(env) = &project_env {
+        info!(
+            "Environment: {} ({} variable(s))",
+            env.name,
+            env.variables.len()
+        );
+    }
+
     let mut collected = Vec::new();
     let exclude_patterns = &args.exclude;
     for path in &args.test_paths {
@@ -642,8 +827,81 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         info!("Filtered to {} test file(s) by META", test_files.len());
     }
 
+    if let Some(scope) = scope {
+        test_files.retain(|f| scope.contains(&execution::watch::path_key(f)));
+    }
+
     info!("Found {} test file(s)", test_files.len());
 
+    // What failed last time, for `--last-failed` / `--failed-first`. Rows are
+    // narrowed further once data sources are expanded below.
+    let last_run_cache = crate::last_failed::cache_path(&args.test_paths);
+    let last_run = match &last_run_cache {
+        Some(cache) if args.last_failed || args.failed_first => {
+            crate::last_failed::LastRun::load(cache)
+        }
+        None if args.last_failed || args.failed_first => {
+            warn!(
+                "--last-failed and --failed-first need a .grpctestify project to keep outcomes in \
+                 (create one with `grpctestify play --init`); running every test"
+            );
+            crate::last_failed::LastRun::default()
+        }
+        _ => crate::last_failed::LastRun::default(),
+    };
+    let last_failures = last_run.failed();
+    let mut rerun_failures_only = false;
+    if args.last_failed {
+        let before = test_files.len();
+        let failed_files: Vec<PathBuf> = test_files
+            .iter()
+            .filter(|f| crate::last_failed::file_failed(&last_failures, f))
+            .cloned()
+            .collect();
+        if failed_files.is_empty() {
+            info!("--last-failed: no recorded failures among {before} test file(s), running all");
+        } else {
+            info!(
+                "--last-failed: rerunning {} of {before} test file(s)",
+                failed_files.len()
+            );
+            test_files = failed_files;
+            rerun_failures_only = true;
+        }
+    }
+
+    // `--shard K/N` runs one deterministic slice of what survived the
+    // filters. An empty slice (more shards than tests) is not an error.
+    if let Some(shard) = args.shard
+        && !test_files.is_empty()
+    {
+        let timings = args
+            .shard_timings
+            .as_deref()
+            .map(execution::shard::load_timings)
+            .transpose()?;
+        let fixture_dirs: HashSet<PathBuf> = fixtures.keys().cloned().collect();
+        let dependencies = DependencyGraph::build(&test_files);
+        test_files = execution::shard::select(
+            test_files,
+            &fixture_dirs,
+            &dependencies,
+            shard,
+            timings.as_ref(),
+        );
+        info!("Shard {shard}: {} test file(s)", test_files.len());
+        if test_files.is_empty() {
+            println!("Shard {shard} has no tests to run");
+            return Ok(TestResults::new());
+        }
+    }
+
+    if test_files.is_empty() && scope.is_some() {
+        return Ok(TestResults::new());
+    }
+    if test_files.is_empty() && events.is_some() {
+        anyhow::bail!("No test files found (paths or tag filters matched nothing)");
+    }
     if test_files.is_empty() {
         // An empty (or fully filtered) test set is almost always a mistake
         // (typo in path or --tags); exit non-zero so CI cannot silently pass.
@@ -659,7 +917,18 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         std::process::exit(1);
     }
 
+    // `META.depends_on`: prerequisites the paths, filters or shard left out
+    // still run. Sharding kept each dependency group on one shard.
+    let (dependency_graph, pulled_in) = depends::add_dependencies(&mut test_files);
+    if pulled_in > 0 {
+        info!("Added {pulled_in} dependency file(s) via META.depends_on");
+    }
+
     FileUtils::sort_files(&mut test_files, &args.sort);
+    if args.failed_first {
+        // Stable, so `--sort` still orders each group.
+        test_files.sort_by_key(|f| !crate::last_failed::file_failed(&last_failures, f));
+    }
 
     // Directories with surviving (post-filter) tests. A dir whose tests were all
     // filtered out is inactive: its setup+teardown are skipped entirely.
@@ -679,7 +948,7 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
     // split those off first so `--data` only ever applies to plain files,
     // and reject combining the two rather than silently guessing which row
     // source wins for a file that somehow had both.
-    let (test_files, dataset_files, dataset_work_items) =
+    let (test_files, dataset_files, dataset_work_items, meta_quarantined) =
         expand_dataset_files(test_files, args.write);
     if args.data.is_some() && !dataset_files.is_empty() {
         anyhow::bail!(
@@ -702,10 +971,70 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         None => test_files.into_iter().map(WorkItem::File).collect(),
     };
     work_items.extend(dataset_work_items);
+    if rerun_failures_only {
+        // Of a file's data rows, only the ones that failed — unless the file
+        // failed as a whole (e.g. its data source was unreadable).
+        work_items.retain(|item| match item {
+            WorkItem::Row { doc, name, .. } => {
+                crate::last_failed::name_failed(&last_failures, name)
+                    || crate::last_failed::name_failed(&last_failures, &doc.file_path)
+            }
+            _ => true,
+        });
+    }
+
+    // Each item waits for every item of each file it depends on. Files in a
+    // dependency cycle could never start: they fail up-front, and whatever
+    // depends on them is skipped.
+    let item_files: Vec<Option<PathBuf>> = work_items.iter().map(work_item_file).collect();
+    let mut items_by_file: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+    for (index, file) in item_files.iter().enumerate() {
+        if let Some(file) = file {
+            items_by_file.entry(file.clone()).or_default().push(index);
+        }
+    }
+    for cycle in dependency_graph.cycles() {
+        for file in &cycle.files {
+            for &index in items_by_file.get(file).into_iter().flatten() {
+                work_items[index] = WorkItem::Error {
+                    name: work_items[index].display_name(),
+                    message: format!("Dependency cycle: {cycle}"),
+                };
+            }
+        }
+    }
+    let item_deps: Vec<Vec<usize>> = work_items
+        .iter()
+        .zip(&item_files)
+        .map(|(item, file)| match (item, file) {
+            (WorkItem::Error { .. }, _) | (_, None) => Vec::new(),
+            (_, Some(file)) => dependency_graph
+                .dependencies(file)
+                .iter()
+                .flat_map(|dep| items_by_file.get(dep).into_iter().flatten().copied())
+                .collect(),
+        })
+        .collect();
+
+    // Quarantined tests still run and report their failures; those just don't
+    // count against the run.
+    let quarantine = execution::quarantine::QuarantineList::load(&args.test_paths)?;
+    let item_quarantined: Vec<bool> = work_items
+        .iter()
+        .map(|item| match item {
+            WorkItem::File(path) => quarantine.contains(path) || meta_quarantined.contains(path),
+            WorkItem::Row { doc, .. } => {
+                quarantine.contains(Path::new(&doc.file_path))
+                    || execution::quarantine::meta_quarantined(doc)
+            }
+            WorkItem::Error { name, .. } => quarantine.contains(Path::new(name)),
+        })
+        .collect();
+
     let total_work = work_items.len();
     let total_reported = total_work + fixture_count;
 
-    if args.stream {
+    if args.stream || events.is_some() {
         // Silent mode - streaming output only
     } else {
         let noun = if total_work == 1 { "test" } else { "tests" };
@@ -724,8 +1053,11 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
 
     let mut reporters: Vec<Box<dyn report::Reporter>> = Vec::new();
 
-    let target_address = std::env::var(config::ENV_GRPCTESTIFY_ADDRESS)
-        .unwrap_or_else(|_| config::default_address());
+    let target_address = project_env
+        .as_ref()
+        .and_then(|env| env.address.clone())
+        .or_else(config::env_or_file_address)
+        .unwrap_or_else(config::default_address);
 
     let env_info = report::console::EnvironmentInfo {
         address: target_address.clone(),
@@ -734,7 +1066,11 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         dry_run: args.dry_run,
     };
 
-    if args.stream {
+    if let Some(events) = events {
+        reporters.push(Box::new(
+            report::StreamingJsonReporter::new(total_reported).with_sink(events),
+        ));
+    } else if args.stream {
         reporters.push(Box::new(report::StreamingJsonReporter::new(total_reported)));
     } else {
         // Always add console reporter (unless streaming)
@@ -743,11 +1079,10 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
             crate::cli::args::ProgressMode::Verbose => report::ConsoleMode::Verbose,
             crate::cli::args::ProgressMode::None => report::ConsoleMode::Silent,
         };
-        reporters.push(Box::new(report::ConsoleReporter::new(
-            mode,
-            total_reported as u64,
-            env_info,
-        )));
+        reporters.push(Box::new(
+            report::ConsoleReporter::new(mode, total_reported as u64, env_info)
+                .with_compact(args.watch),
+        ));
     }
 
     let requested_formats = cli.log_format_modes();
@@ -781,6 +1116,12 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
                     crate::cli::LogFormat::Html => {
                         reporters.push(Box::new(report::HtmlReporter::new(path)));
                     }
+                    crate::cli::LogFormat::Tap => {
+                        reporters.push(Box::new(report::TapReporter::new(path)));
+                    }
+                    crate::cli::LogFormat::Ctrf => {
+                        reporters.push(Box::new(report::CtrfReporter::new(path)));
+                    }
                     crate::cli::LogFormat::Console => {}
                 }
             }
@@ -797,16 +1138,22 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
 
     let mut test_results = TestResults::new();
 
-    let coverage_collector = if args.coverage {
+    let coverage_collector = if args.is_coverage() {
         Some(Arc::new(report::CoverageCollector::new()))
     } else {
         None
     };
+    let coverage_thresholds = crate::commands::coverage::thresholds(
+        args.coverage_min_methods,
+        args.coverage_min_fields,
+        &args.coverage_min_service,
+    )?;
 
     // Capture the real request/response exchange whenever a reporter can render
     // it: verbose console (show actual response on failure), a file format
     // that serialises/attaches it — Allure (attachment), JSON/YAML (serialised),
-    // HTML (detail), JUnit (system-out) — or the user explicitly asked to via
+    // HTML (detail), JUnit (system-out), TAP/CTRF (the gRPC status from its
+    // trailers) — or the user explicitly asked to via
     // --capture-exchange. Console-only runs otherwise skip the buffering.
     let verbose_console = matches!(cli.progress_mode(), crate::cli::args::ProgressMode::Verbose);
     let format_uses_exchange = cli.log_format_modes().iter().any(|f| {
@@ -817,6 +1164,8 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
                 | crate::cli::LogFormat::Yaml
                 | crate::cli::LogFormat::Html
                 | crate::cli::LogFormat::JUnit
+                | crate::cli::LogFormat::Tap
+                | crate::cli::LogFormat::Ctrf
         )
     });
     let capture_exchange = should_capture_exchange(
@@ -826,6 +1175,30 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         args.log_output.is_some(),
     );
 
+    let cassette = match (&args.record, &args.replay) {
+        (Some(dir), _) => Some(Arc::new(crate::grpc::cassette::Cassette::record(dir))),
+        (None, Some(dir)) => {
+            info!("Replaying calls from {}", dir.display());
+            Some(Arc::new(crate::grpc::cassette::Cassette::replay(dir)?))
+        }
+        (None, None) => None,
+    };
+
+    let tracer = if args.is_tracing() {
+        let export = match (&args.otel_endpoint, &args.otel_file) {
+            (Some(endpoint), _) => Some(execution::trace::TraceExport::Endpoint(endpoint.clone())),
+            (None, Some(path)) => Some(execution::trace::TraceExport::File(path.clone())),
+            (None, None) => None,
+        };
+        Some(Arc::new(execution::trace::Tracer::new(
+            &args.trace_baggage,
+            args.trace_url.clone(),
+            export,
+        )?))
+    } else {
+        None
+    };
+
     let start_time = std::time::Instant::now();
     let runner = Arc::new(
         execution::TestRunner::new(
@@ -837,7 +1210,23 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
             coverage_collector.clone(),
         )
         .with_protocol(args.protocol.parse().unwrap_or_default())
-        .with_capture_exchange(capture_exchange),
+        .with_proxy(args.proxy.clone())
+        .with_template_seed(args.seed)
+        .with_base_variables(
+            project_env
+                .as_ref()
+                .map(crate::serve::project::ProjectEnv::variable_map)
+                .unwrap_or_default(),
+        )
+        .with_default_address(
+            project_env
+                .as_ref()
+                .and_then(|env| env.address.clone())
+                .or_else(config::env_or_file_address),
+        )
+        .with_capture_exchange(capture_exchange)
+        .with_cassette(cassette.clone())
+        .with_tracer(tracer.clone()),
     );
 
     let reporters: Arc<Vec<Box<dyn report::Reporter>>> = Arc::new(reporters);
@@ -866,14 +1255,32 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
     let dir_setup_vars = Arc::new(dir_setup_vars);
     let dirs_setup_failed = Arc::new(dirs_setup_failed);
 
-    // Use a stream for bounded parallelism
-    let stream = futures::stream::iter(work_items)
-        .map(|item| {
+    // Bounded parallelism along the `META.depends_on` graph; without
+    // dependencies every item is ready at once.
+    let never = AtomicBool::new(false);
+    let outputs = depends::run_in_dependency_order(
+        &item_deps,
+        parallel_jobs,
+        cancel.unwrap_or(&never),
+        |index, finished: &[Option<(TestResult, Vars)>]| {
+            let item = work_items[index].clone();
             let runner = runner.clone();
             let reporters = reporters.clone();
             let dir_setup_vars = dir_setup_vars.clone();
             let dirs_setup_failed = dirs_setup_failed.clone();
             let name = item.display_name();
+            let quarantined = item_quarantined[index];
+            let dependencies = match (&item, &item_files[index]) {
+                (WorkItem::Error { .. }, _) | (_, None) => &[][..],
+                (_, Some(file)) => dependency_graph.dependencies(file),
+            };
+            let prerequisites = dependency_vars(
+                &name,
+                dependencies,
+                &dependency_graph,
+                &items_by_file,
+                finished,
+            );
 
             async move {
                 for r in reporters.iter() {
@@ -883,94 +1290,52 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
                 let test_start = std::time::Instant::now();
                 let item_dir = work_item_dir(&item);
 
-                let mut test_result =
-                    if item_skipped_by_setup(item_dir.as_deref(), &dirs_setup_failed) {
+                let mut exported = Vars::new();
+                let mut test_result = match prerequisites {
+                    _ if item_skipped_by_setup(item_dir.as_deref(), &dirs_setup_failed) => {
                         TestResult::fail(
                             name.clone(),
                             "Skipped: directory setup fixture (_setup.gctf) failed".to_string(),
                             0,
                             None,
                         )
-                    } else {
+                    }
+                    Err(not_run) => *not_run,
+                    Ok(dependency_vars) => {
                         // Tests in a dir with a passing setup are seeded with its
                         // captured variables; dirs without setup get an empty map,
                         // which is byte-for-byte the normal `run_test` path.
-                        let initial_vars = item_dir
+                        let mut initial_vars = item_dir
                             .as_ref()
                             .and_then(|d| dir_setup_vars.get(d))
                             .cloned()
                             .unwrap_or_default();
-                        match item {
-                            WorkItem::File(file) => {
-                                let file_path_str = file.to_string_lossy().to_string();
-                                match run_single_test(
-                                    &runner,
-                                    &file,
-                                    initial_vars,
-                                    args.retry,
-                                    args.retry_delay,
-                                    args.no_retry,
-                                )
-                                .await
-                                {
-                                    Ok(res) => execution_result_to_test_result(file_path_str, res),
-                                    Err(e) => TestResult::fail(
-                                        file_path_str,
-                                        format!("Execution error: {}", e),
-                                        0,
-                                        None,
-                                    ),
-                                }
-                            }
-                            WorkItem::Row { doc, vars, name } => {
-                                // Captured before `vars` is merged/consumed below, so
-                                // the report can show which row produced this case
-                                // (e.g. as Allure parameters) independent of execution.
-                                let params = row_params(&vars);
-                                // Fixtures + `--data`: setup vars seed the row, but
-                                // row vars win on key conflicts (row identity is
-                                // explicit and must not be overwritten by a fixture).
-                                let mut merged = initial_vars;
-                                merged.extend(vars);
-                                match run_template_row(
-                                    &runner,
-                                    &doc,
-                                    merged,
-                                    args.retry,
-                                    args.retry_delay,
-                                    args.no_retry,
-                                )
-                                .await
-                                {
-                                    Ok(res) => execution_result_to_test_result(name, res)
-                                        .with_row_params(params),
-                                    Err(e) => TestResult::fail(
-                                        name,
-                                        format!("Execution error: {}", e),
-                                        0,
-                                        None,
-                                    )
-                                    .with_row_params(params),
-                                }
-                            }
-                            WorkItem::Error { name, message } => {
-                                TestResult::fail(name, message, 0, None)
-                            }
-                        }
-                    };
+                        // `META.depends_on` files' EXTRACT bindings layer on top.
+                        initial_vars.extend(dependency_vars);
+                        let (result, vars) = execution::repeat::run_repeated(
+                            args.repeat_times(),
+                            args.until_failure,
+                            || run_work_item(&runner, &item, initial_vars.clone(), args),
+                        )
+                        .await;
+                        exported = vars;
+                        result
+                    }
+                };
 
                 test_result.duration_ms = test_start.elapsed().as_millis() as u64;
+                test_result.quarantined = quarantined;
 
                 for r in reporters.iter() {
                     r.on_test_end(&name, &test_result);
                 }
 
-                test_result
+                (test_result, exported)
             }
-        })
-        .buffer_unordered(parallel_jobs);
-
-    let results: Vec<TestResult> = stream.collect().await;
+        },
+    )
+    .await;
+    let results: Vec<TestResult> = outputs.into_iter().map(|(result, _)| result).collect();
 
     // Teardown barrier: run each active directory's `_teardown.gctf` after its
     // tests drain, ALWAYS — whether the tests or the setup passed or failed. A
@@ -983,6 +1348,19 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         fixture_results.push(result);
     }
 
+    if let (Some(cassette), Some(dir)) = (&cassette, &args.record) {
+        let recorded = cassette.save()?;
+        info!("Recorded {recorded} call(s) into {}", dir.display());
+    }
+
+    // Dry runs execute nothing, so they must not overwrite real outcomes.
+    if let Some(cache) = last_run_cache.as_deref().filter(|_| !args.dry_run) {
+        let ran = results.iter().chain(&fixture_results);
+        if let Err(e) = crate::last_failed::LastRun::update(cache, ran) {
+            warn!("Could not update the last-run cache: {e:#}");
+        }
+    }
+
     for result in results {
         test_results.add(result);
     }
@@ -999,25 +1377,119 @@ pub async fn run_tests(cli: &Cli, args: &RunArgs) -> Result<()> {
         r.on_suite_end(&test_results)?;
     }
 
+    // A collector being down must not fail an otherwise green run.
+    if let Some(tracer) = &tracer {
+        match tracer.export().await {
+            Ok(0) => {}
+            Ok(spans) => info!("Exported {spans} span(s)"),
+            Err(e) => warn!("Could not export trace spans: {e:#}"),
+        }
+    }
+
     if let Some(collector) = coverage_collector {
-        if args.is_json_coverage() {
-            let report = collector.generate_json_report();
-            println!("{}", serde_json::to_string_pretty(&report)?);
+        let coverage = collector.generate_json_report();
+        let report = if args.is_json_coverage() {
+            serde_json::to_string_pretty(&coverage)?
         } else if args.is_html_coverage() {
-            println!("{}", collector.generate_html_report());
+            coverage.render_html()
         } else {
-            let report = collector.generate_text_report();
-            if !args.stream {
-                println!("\n{}", report);
+            coverage.render_text()
+        };
+        if let Some(path) = &args.coverage_output {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
             }
+            std::fs::write(path, report).with_context(|| {
+                format!("Failed to write coverage report to {}", path.display())
+            })?;
+        } else if args.is_json_coverage() || args.is_html_coverage() {
+            println!("{report}");
+        } else if !args.stream {
+            println!("\n{report}");
         }
-    }
 
-    if !test_results.all_passed() {
-        std::process::exit(1);
+        let shortfalls = crate::commands::coverage::check(&coverage, &coverage_thresholds)?;
+        if !shortfalls.is_empty() {
+            anyhow::bail!(
+                "Coverage is below the minimum\n{}",
+                crate::commands::coverage::render_shortfalls(&shortfalls).trim_end()
+            );
+        }
     }
 
-    Ok(())
+    Ok(test_results)
+}
+
+/// Execute one work item once, seeded with `initial_vars`. Returns its result
+/// and the variables it exported for `META.depends_on` dependents.
+async fn run_work_item(
+    runner: &execution::TestRunner,
+    item: &WorkItem,
+    initial_vars: Vars,
+    args: &RunArgs,
+) -> (TestResult, Vars) {
+    match item {
+        WorkItem::File(file) => {
+            let file_path_str = file.to_string_lossy().to_string();
+            match run_single_test(
+                runner,
+                file,
+                initial_vars,
+                args.retry,
+                args.retry_delay,
+                args.no_retry,
+            )
+            .await
+            {
+                Ok((res, vars)) => (execution_result_to_test_result(file_path_str, res), vars),
+                Err(e) => (
+                    TestResult::fail(file_path_str, format!("Execution error: {}", e), 0, None),
+                    Vars::new(),
+                ),
+            }
+        }
+        WorkItem::Row {
+            doc,
+            vars,
+            name,
+            row,
+        } => {
+            // Captured separately from the merged variables, so the report can
+            // show which row produced this case (e.g. as Allure parameters)
+            // independent of execution.
+            let params = row_params(vars);
+            // Fixtures + `--data`: setup vars seed the row, but row vars win on
+            // key conflicts (row identity is explicit and must not be
+            // overwritten by a fixture).
+            let mut merged = initial_vars;
+            merged.extend(vars.clone());
+            match run_template_row(
+                runner,
+                doc,
+                merged,
+                *row,
+                args.retry,
+                args.retry_delay,
+                args.no_retry,
+            )
+            .await
+            {
+                Ok((res, vars)) => (
+                    execution_result_to_test_result(name.clone(), res).with_row_params(params),
+                    vars,
+                ),
+                Err(e) => (
+                    TestResult::fail(name.clone(), format!("Execution error: {}", e), 0, None)
+                        .with_row_params(params),
+                    Vars::new(),
+                ),
+            }
+        }
+        WorkItem::Error { name, message } => (
+            TestResult::fail(name.clone(), message.clone(), 0, None),
+            Vars::new(),
+        ),
+    }
 }
 
 /// Map a completed execution result onto a reportable [`TestResult`] with the
@@ -1031,16 +1503,27 @@ fn execution_result_to_test_result(
     let config_summary = res.config_summary;
     let assertions = res.assertions;
     let retried = res.retried;
+    let attempts = res.attempts;
+    let trace = res.trace;
     let document_durations_ms = res.document_durations_ms;
     let exchange = res.captured_response.map(|resp| {
         crate::state::CapturedExchange::capture(resp.headers, resp.trailers, resp.messages)
     });
+    // The runner leaves `grpc_status` unset on an assertion failure (bench
+    // counts those as errors, not by status), but the call still ended with
+    // one — recover it from the captured trailers so reports can show it.
+    let grpc_status = res
+        .grpc_status
+        .or_else(|| exchange.as_ref()?.trailers.get("grpc-status")?.parse().ok());
     match res.status {
         execution::TestExecutionStatus::Pass => {
             TestResult::pass_with_meta(name, 0, call_duration, meta)
                 .with_assertions(assertions)
                 .with_exchange(exchange)
                 .with_retried(retried)
+                .with_attempts(attempts)
+                .with_grpc_status(grpc_status)
+                .with_trace(trace.clone())
                 .with_document_durations(document_durations_ms)
                 .with_config_summary(config_summary)
         }
@@ -1049,6 +1532,9 @@ fn execution_result_to_test_result(
                 .with_assertions(assertions)
                 .with_exchange(exchange)
                 .with_retried(retried)
+                .with_attempts(attempts)
+                .with_grpc_status(grpc_status)
+                .with_trace(trace.clone())
                 .with_document_durations(document_durations_ms)
                 .with_config_summary(config_summary)
         }
@@ -1058,25 +1544,27 @@ fn execution_result_to_test_result(
 /// Execute a single parameterized row against a shared template document.
 ///
 /// Mirrors [`run_single_test`] (validation + retry loop) but runs the already
-/// parsed `doc` with `vars` via the existing `run_test_with_variables`. No
+/// parsed `doc` with `vars` via `run_row_exporting_vars`. No
 /// `--write` handling: template runs reject `--write` during expansion.
 async fn run_template_row(
     runner: &execution::TestRunner,
     doc: &GctfDocument,
     vars: HashMap<String, serde_json::Value>,
+    row: usize,
     retry: u32,
     retry_delay: f64,
     no_retry: bool,
-) -> Result<execution::TestExecutionResult> {
+) -> Result<(execution::TestExecutionResult, Vars)> {
     let test_meta = extract_test_meta(doc);
     let config_summary = apif_state::ConfigSummary::from_document(doc);
 
     if let Err(e) = parser::validate_document_chain(doc) {
-        return Ok(
+        return Ok((
             execution::TestExecutionResult::fail(format!("Validation error: {}", e), None)
                 .with_meta(test_meta)
                 .with_config_summary(config_summary),
-        );
+            Vars::new(),
+        ));
     }
 
     let effective_runtime = match execution::runner_helpers::resolve_effective_runtime_options(
@@ -1090,12 +1578,12 @@ async fn run_template_row(
     ) {
         Ok(v) => v,
         Err(e) => {
-            return Ok(execution::TestExecutionResult::fail(
-                format!("Validation error: {}", e),
-                None,
-            )
-            .with_meta(test_meta)
-            .with_config_summary(config_summary));
+            return Ok((
+                execution::TestExecutionResult::fail(format!("Validation error: {}", e), None)
+                    .with_meta(test_meta)
+                    .with_config_summary(config_summary),
+                Vars::new(),
+            ));
         }
     };
 
@@ -1106,11 +1594,13 @@ async fn run_template_row(
     };
 
     let mut attempt = 0u32;
-    let result = loop {
-        let current = runner.run_test_with_variables(doc, vars.clone()).await?;
+    let (result, exported) = loop {
+        let (current, exported) = runner
+            .run_row_exporting_vars(doc, vars.clone(), row)
+            .await?;
 
         if !should_retry_result(&current) || attempt >= max_retries {
-            break current;
+            break (current, exported);
         }
 
         attempt += 1;
@@ -1122,11 +1612,17 @@ async fn run_template_row(
         }
     };
 
-    Ok(result
-        .with_meta(test_meta)
-        .with_config_summary(config_summary))
+    Ok((
+        result
+            .with_attempts(attempt + 1)
+            .with_meta(test_meta)
+            .with_config_summary(config_summary),
+        exported,
+    ))
 }
 
+/// Parse and run one test file with retries (and `--write`), returning its
+/// result and the variables it exported for `META.depends_on` dependents.
 async fn run_single_test(
     runner: &execution::TestRunner,
     file: &std::path::Path,
@@ -1134,13 +1630,13 @@ async fn run_single_test(
     retry: u32,
     retry_delay: f64,
     no_retry: bool,
-) -> Result<execution::TestExecutionResult> {
+) -> Result<(execution::TestExecutionResult, Vars)> {
     let doc = match parser::parse_gctf(file) {
         Ok(d) => d,
         Err(e) => {
-            return Ok(execution::TestExecutionResult::fail(
-                format!("Parse error: {}", e),
-                None,
+            return Ok((
+                execution::TestExecutionResult::fail(format!("Parse error: {}", e), None),
+                Vars::new(),
             ));
         }
     };
@@ -1150,11 +1646,12 @@ async fn run_single_test(
     let config_summary = apif_state::ConfigSummary::from_document(&doc);
 
     if let Err(e) = parser::validate_document_chain(&doc) {
-        return Ok(
+        return Ok((
             execution::TestExecutionResult::fail(format!("Validation error: {}", e), None)
                 .with_meta(test_meta)
                 .with_config_summary(config_summary),
-        );
+            Vars::new(),
+        ));
     }
 
     let effective_runtime = match execution::runner_helpers::resolve_effective_runtime_options(
@@ -1168,12 +1665,12 @@ async fn run_single_test(
     ) {
         Ok(v) => v,
         Err(e) => {
-            return Ok(execution::TestExecutionResult::fail(
-                format!("Validation error: {}", e),
-                None,
-            )
-            .with_meta(test_meta)
-            .with_config_summary(config_summary));
+            return Ok((
+                execution::TestExecutionResult::fail(format!("Validation error: {}", e), None)
+                    .with_meta(test_meta)
+                    .with_config_summary(config_summary),
+                Vars::new(),
+            ));
         }
     };
 
@@ -1184,17 +1681,17 @@ async fn run_single_test(
     };
 
     let mut attempt = 0u32;
-    let result = loop {
+    let (result, exported) = loop {
         // An empty `initial_vars` map makes this identical to `run_test`; a
         // non-empty one seeds the chain with a directory's `_setup.gctf` vars.
-        let current = runner
-            .run_test_with_variables(&doc, initial_vars.clone())
+        let (current, exported) = runner
+            .run_test_exporting_vars(&doc, initial_vars.clone())
             .await?;
 
         let should_retry = should_retry_result(&current);
 
         if !should_retry || attempt >= max_retries {
-            break current;
+            break (current, exported);
         }
 
         attempt += 1;
@@ -1206,20 +1703,28 @@ async fn run_single_test(
         }
     };
 
-    if let Some(resp) = &result.captured_response
+    if runner.write_mode()
+        && let Some(resp) = &result.captured_response
         && let Err(e) = crate::utils::file::update_test_file(file, &doc, resp)
     {
-        return Ok(execution::TestExecutionResult::fail(
-            format!("Failed to update test file: {}", e),
-            result.call_duration_ms,
-        )
-        .with_meta(test_meta)
-        .with_config_summary(config_summary));
+        return Ok((
+            execution::TestExecutionResult::fail(
+                format!("Failed to update test file: {}", e),
+                result.call_duration_ms,
+            )
+            .with_meta(test_meta)
+            .with_config_summary(config_summary),
+            exported,
+        ));
     }
 
-    Ok(result
-        .with_meta(test_meta)
-        .with_config_summary(config_summary))
+    Ok((
+        result
+            .with_attempts(attempt + 1)
+            .with_meta(test_meta)
+            .with_config_summary(config_summary),
+        exported,
+    ))
 }
 
 #[cfg(test)]
@@ -1268,6 +1773,10 @@ mod tests {
             report_output_path(&base, crate::cli::LogFormat::Allure, true),
             base.join("allure")
         );
+        assert_eq!(
+            report_output_path(&base, crate::cli::LogFormat::Ctrf, true),
+            base.join("ctrf.json")
+        );
     }
 
     #[test]
@@ -1352,6 +1861,8 @@ mod tests {
             owner: Some("team-a".to_string()),
             summary: Some("test summary".to_string()),
             links: vec![],
+            depends_on: vec![],
+            quarantine: false,
         };
         doc.sections.push(Section {
             section_type: SectionType::Meta,
@@ -1596,7 +2107,7 @@ mod tests {
         let gctf = dir.join("template.gctf");
         std::fs::write(&gctf, DATASET_GCTF).unwrap();
 
-        let (plain, dataset_files, items) = expand_dataset_files(vec![gctf.clone()], false);
+        let (plain, dataset_files, items, _) = expand_dataset_files(vec![gctf.clone()], false);
         assert!(plain.is_empty());
         assert_eq!(dataset_files, vec![gctf]);
         assert_eq!(items.len(), 2);
@@ -1620,7 +2131,7 @@ mod tests {
     #[test]
     #[cfg_attr(miri, ignore)]
     fn expand_dataset_files_passes_through_files_without_a_dataset_section() {
-        let (plain, dataset_files, items) =
+        let (plain, dataset_files, items, _) =
             expand_dataset_files(vec![PathBuf::from("plain.gctf")], false);
         assert_eq!(plain, vec![PathBuf::from("plain.gctf")]);
         assert!(dataset_files.is_empty());
@@ -1636,7 +2147,7 @@ mod tests {
         let gctf = dir.join("template.gctf");
         std::fs::write(&gctf, DATASET_GCTF).unwrap();
 
-        let (_, _, items) = expand_dataset_files(vec![gctf], true);
+        let (_, _, items, _) = expand_dataset_files(vec![gctf], true);
         assert_eq!(items.len(), 1);
         match &items[0] {
             WorkItem::Error { message, .. } => assert!(message.contains("--write")),
@@ -1656,7 +2167,7 @@ mod tests {
         let content = "--- ENDPOINT ---\nsvc.Svc/Call\n\n--- DATASET ---\n[]\n\n--- REQUEST ---\n{}\n\n--- RESPONSE ---\n{}\n";
         std::fs::write(&gctf, content).unwrap();
 
-        let (_, _, items) = expand_dataset_files(vec![gctf], false);
+        let (_, _, items, _) = expand_dataset_files(vec![gctf], false);
         assert_eq!(items.len(), 1);
         match &items[0] {
             WorkItem::Error { message, .. } => assert!(message.contains("zero rows")),
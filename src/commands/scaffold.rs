@@ -129,7 +129,7 @@ fn load_from_descriptor(descriptor: &Path) -> Result<(DescriptorPool, ProtoRef)>
 /// Resolve `--plaintext`/`--tls`/`--insecure` into a `TlsConfig` (or `None`
 /// for plaintext) — pulled out of [`load_via_reflection`] so it's testable
 /// without a live server.
-fn resolve_tls_config(
+pub(crate) fn resolve_tls_config(
     plaintext: bool,
     tls: bool,
     insecure: bool,
@@ -175,6 +175,7 @@ async fn load_via_reflection(args: &ScaffoldArgs, service: &str) -> Result<Descr
         connection_id: 0,
         protocol: args.protocol.parse::<WireProtocol>().unwrap_or_default(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
     eprintln!("Connecting to {} for reflection...", config.address);
@@ -273,7 +274,7 @@ fn emit(content: &str, output: Option<&Path>, force: bool) -> Result<()> {
 
 use crate::utils::file::write_atomic;
 
-fn collect_proto_files(dir: &Path) -> Result<Vec<PathBuf>> {
+pub(crate) fn collect_proto_files(dir: &Path) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
     let mut stack = vec![dir.to_path_buf()];
     while let Some(current) = stack.pop() {
@@ -0,0 +1,175 @@
+// `schema-diff <old> <new>`: breaking-change detection between two versions
+// of an API's schema, and the `.gctf` lines each breaking change would hit.
+//
+// Each side is a `.proto` file, a directory of them, a serialized
+// `FileDescriptorSet`, or the address of a server with reflection.
+
+use anyhow::{Context, Result};
+use prost_reflect::DescriptorPool;
+use serde_json::json;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::SchemaDiffArgs;
+use crate::grpc::{GrpcClient, GrpcClientConfig, WireProtocol};
+use crate::parser;
+use crate::semantics::schema::load_flag_pool;
+use crate::semantics::schema_diff::{References, SchemaChange, diff_pools};
+use crate::utils::FileUtils;
+
+pub async fn handle_schema_diff(args: &SchemaDiffArgs) -> Result<()> {
+    let old = load_source(&args.old)
+        .await
+        .with_context(|| format!("old schema `{}`", args.old))?;
+    let new = load_source(&args.new)
+        .await
+        .with_context(|| format!("new schema `{}`", args.new))?;
+
+    let mut changes = diff_pools(&old, &new);
+    let mut references = References::default();
+    for file in test_files(&args.tests) {
+        if let Ok(doc) = parser::parse_gctf(&file) {
+            references.collect(&doc, &old);
+        }
+    }
+    references.attach(&mut changes);
+
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+    if args.format.eq_ignore_ascii_case("json") {
+        let output = json!({
+            "old": args.old,
+            "new": args.new,
+            "breaking": breaking,
+            "non_breaking": changes.len() - breaking,
+            "impacted_tests": impacted_files(&changes),
+            "changes": changes,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        print!("{}", render_text(&args.old, &args.new, &changes));
+    }
+
+    if breaking > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// A path that exists is a `.proto` file, a directory of them or a
+/// descriptor set; anything else is a server address to reflect, over TLS
+/// when it's an `https://` URL.
+async fn load_source(source: &str) -> Result<DescriptorPool> {
+    let path = Path::new(source);
+    if path.exists() {
+        let is_proto = path.is_dir() || path.extension().is_some_and(|e| e == "proto");
+        let pool = if is_proto {
+            load_flag_pool(Some(path), None)?
+        } else {
+            load_flag_pool(None, Some(path))?
+        };
+        return pool.context("no descriptors loaded");
+    }
+
+    let config = GrpcClientConfig {
+        address: source.to_string(),
+        timeout_seconds: 30,
+        tls_config: crate::commands::scaffold::resolve_tls_config(
+            !source.starts_with("https://"),
+            false,
+            false,
+            source,
+        )?,
+        proto_config: None,
+        metadata: None,
+        target_service: None,
+        compression: Default::default(),
+        connection_id: 0,
+        protocol: WireProtocol::Grpc,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
+    };
+    eprintln!("Connecting to {source} for reflection...");
+    let client = GrpcClient::new(config)
+        .await
+        .context("Failed to load descriptors via reflection")?;
+    Ok(client.descriptor_pool().clone())
+}
+
+fn test_files(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(FileUtils::collect_test_files(path, &[]));
+        } else if path.is_file() {
+            files.push(path.clone());
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn impacted_files(changes: &[SchemaChange]) -> BTreeSet<&str> {
+    changes
+        .iter()
+        .flat_map(|c| &c.impacts)
+        .map(|i| i.file.as_str())
+        .collect()
+}
+
+fn render_text(old: &str, new: &str, changes: &[SchemaChange]) -> String {
+    use crate::report::style::{
+        dim_style, fail_icon, fail_style, header, pass_icon, pass_style, warn_icon,
+    };
+    use std::fmt::Write;
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}\n", header(&format!("Schema diff ({old} → {new})")));
+    if changes.is_empty() {
+        let _ = writeln!(out, "{} No schema changes", pass_icon());
+        return out;
+    }
+    for change in changes {
+        let icon = if change.breaking {
+            fail_icon().to_string()
+        } else {
+            pass_icon().to_string()
+        };
+        let _ = writeln!(out, "{icon} {}", change.message);
+        for impact in &change.impacts {
+            let _ = writeln!(
+                out,
+                "    {} {}",
+                dim_style().apply_to(format!(
+                    "{}:{} {}",
+                    impact.file, impact.line, impact.section
+                )),
+                impact.text
+            );
+        }
+    }
+
+    let breaking = changes.iter().filter(|c| c.breaking).count();
+    let files = impacted_files(changes);
+    let _ = writeln!(out);
+    if breaking == 0 {
+        let _ = writeln!(
+            out,
+            "{}",
+            pass_style().apply_to(format!("PASS: {} non-breaking change(s)", changes.len()))
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "{}",
+            fail_style().apply_to(format!(
+                "FAIL: {breaking} breaking, {} non-breaking change(s)",
+                changes.len() - breaking
+            ))
+        );
+        if !files.is_empty() {
+            let _ = writeln!(out, "{} {} test file(s) affected", warn_icon(), files.len());
+        }
+    }
+    out
+}
@@ -30,5 +30,11 @@ pub async fn handle_play(args: &PlayArgs) -> Result<()> {
         return Ok(());
     }
 
-    crate::serve::start_play_server(&args.host, args.port, args.dir.clone()).await
+    let access = crate::serve::access::AccessControl::new(
+        args.auth_token.clone(),
+        args.htpasswd.as_deref(),
+        args.read_only,
+        args.audit_log.as_deref(),
+    )?;
+    crate::serve::start_play_server(&args.host, args.port, args.dir.clone(), access).await
 }
@@ -1,6 +1,11 @@
-use serde::{Deserialize, Serialize};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     #[serde(default)]
     pub general: GeneralConfig,
@@ -13,13 +18,17 @@ pub struct Config {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct GeneralConfig {
     /// Default gRPC server address
     #[serde(default = "default_address")]
     pub address: String,
 
-    /// Number of parallel workers
-    #[serde(default = "default_parallel")]
+    /// Number of parallel workers (`auto` or a count)
+    #[serde(
+        default = "default_parallel",
+        deserialize_with = "deserialize_parallel"
+    )]
     pub parallel: String,
 
     /// Test timeout in seconds
@@ -58,6 +67,7 @@ impl Default for GeneralConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProgressConfig {
     /// Progress indicator mode
     #[serde(default = "default_progress")]
@@ -78,6 +88,7 @@ impl Default for ProgressConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
 pub struct CoverageConfig {
     /// Enable coverage collection
     #[serde(default)]
@@ -128,8 +139,9 @@ pub fn default_timeout() -> u64 {
     30
 }
 
+/// No retries, as for `run --retry`.
 fn default_retry() -> u32 {
-    3
+    0
 }
 
 fn default_retry_delay() -> f64 {
@@ -144,6 +156,219 @@ fn default_color() -> bool {
     true
 }
 
+/// `parallel = 4` and `parallel = "auto"` are both natural in a config file.
+fn deserialize_parallel<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Parallel {
+        Count(u64),
+        Text(String),
+    }
+
+    Ok(match Parallel::deserialize(deserializer)? {
+        Parallel::Count(n) => n.to_string(),
+        Parallel::Text(s) => s,
+    })
+}
+
+// Config files
+
+/// Project config files, checked in this order in each directory while
+/// walking up from the test paths. The nearest directory holding one wins.
+pub const PROJECT_CONFIG_FILES: [&str; 2] = ["grpctestify.toml", ".grpctestify/config.yaml"];
+
+/// User config files under `$XDG_CONFIG_HOME/grpctestify` (default
+/// `~/.config/grpctestify`), below any project file.
+pub const USER_CONFIG_FILES: [&str; 2] = ["config.toml", "config.yaml"];
+
+/// Every key a config file can set, as `section.field`, in display order.
+pub const CONFIG_KEYS: [&str; 11] = [
+    "general.address",
+    "general.parallel",
+    "general.timeout",
+    "general.retry",
+    "general.retry_delay",
+    "general.log_format",
+    "general.log_output",
+    "progress.mode",
+    "progress.color",
+    "coverage.enabled",
+    "coverage.output",
+];
+
+/// Keys holding paths. In a project file they are relative to the project
+/// root (the directory holding `grpctestify.toml` or `.grpctestify/`).
+const PATH_KEYS: [&str; 2] = ["general.log_output", "coverage.output"];
+
+/// A [`Config`] merged from config files, remembering which file set each key.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    pub config: Config,
+    /// Files merged into `config`, lowest precedence first.
+    pub files: Vec<PathBuf>,
+    sources: BTreeMap<String, PathBuf>,
+}
+
+impl LoadedConfig {
+    /// The file that set `key`, or `None` when it holds the built-in default.
+    pub fn source(&self, key: &str) -> Option<&Path> {
+        self.sources.get(key).map(PathBuf::as_path)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/grpctestify`, falling back to `~/.config/grpctestify`.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let non_empty = |name: &str| std::env::var_os(name).filter(|v| !v.is_empty());
+    non_empty("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("grpctestify"))
+}
+
+/// Config files for a command started from `start`, lowest precedence first:
+/// the user file, then the nearest project file at or above `start`.
+pub fn discover_config_files(start: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = user_config_dir()
+        .and_then(|dir| {
+            USER_CONFIG_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+        .into_iter()
+        .collect();
+
+    let start = std::path::absolute(start).unwrap_or_else(|_| start.to_path_buf());
+    let project = start.ancestors().find_map(|dir| {
+        PROJECT_CONFIG_FILES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    });
+    if let Some(project) = project
+        && !files.contains(&project)
+    {
+        files.push(project);
+    }
+    files
+}
+
+/// Discover and merge the config files that apply from `start`.
+pub fn load_config(start: &Path) -> Result<LoadedConfig> {
+    load_config_files(&discover_config_files(start))
+}
+
+/// Merge `files` (lowest precedence first) over the built-in defaults. Each
+/// file must be valid on its own; unknown keys are an error so a typo can't
+/// silently fall back to a default.
+pub fn load_config_files(files: &[PathBuf]) -> Result<LoadedConfig> {
+    let user_dir = user_config_dir();
+    let mut merged = serde_json::Map::new();
+    let mut sources = BTreeMap::new();
+
+    for path in files {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        let sections = parse_config_file(path, &content)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        // User-level paths stay relative to the working directory.
+        let root = project_root_of(path).filter(|_| path.parent() != user_dir.as_deref());
+
+        for (section, fields) in sections {
+            let serde_json::Value::Object(fields) = fields else {
+                continue;
+            };
+            let target = merged
+                .entry(section.clone())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            let serde_json::Value::Object(target) = target else {
+                continue;
+            };
+            for (field, mut value) in fields {
+                let key = format!("{section}.{field}");
+                if PATH_KEYS.contains(&key.as_str())
+                    && let (Some(root), Some(raw)) = (&root, value.as_str())
+                    && Path::new(raw).is_relative()
+                {
+                    value = serde_json::Value::String(root.join(raw).display().to_string());
+                }
+                target.insert(field, value);
+                sources.insert(key, path.clone());
+            }
+        }
+    }
+
+    let config = serde_json::from_value(serde_json::Value::Object(merged))
+        .context("Failed to merge config files")?;
+    Ok(LoadedConfig {
+        config,
+        files: files.to_vec(),
+        sources,
+    })
+}
+
+/// Parse one config file (TOML by extension, YAML otherwise) into its
+/// sections, validating it against [`Config`].
+fn parse_config_file(
+    path: &Path,
+    content: &str,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+    let value: serde_json::Value = if is_toml {
+        toml::from_str(content)?
+    } else {
+        serde_yaml_ng::from_str(content)?
+    };
+    let sections = match value {
+        serde_json::Value::Object(sections) => sections,
+        // An empty YAML file.
+        serde_json::Value::Null => serde_json::Map::new(),
+        _ => bail!("expected a mapping of sections (general, progress, coverage)"),
+    };
+    serde_json::from_value::<Config>(serde_json::Value::Object(sections.clone()))?;
+    Ok(sections)
+}
+
+/// The directory a project config file belongs to: its own directory for
+/// `grpctestify.toml`, the parent of `.grpctestify/` for `config.yaml`.
+fn project_root_of(path: &Path) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    if dir.file_name().is_some_and(|name| name == ".grpctestify") {
+        dir.parent().map(Path::to_path_buf)
+    } else {
+        Some(dir.to_path_buf())
+    }
+}
+
+static INSTALLED: OnceLock<LoadedConfig> = OnceLock::new();
+
+/// Make `loaded` the config of this process (called once, from `main`, after
+/// the CLI flags have been merged over it).
+pub fn install(loaded: LoadedConfig) {
+    let _ = INSTALLED.set(loaded);
+}
+
+/// The config installed by [`install`], if any.
+pub fn installed() -> Option<&'static LoadedConfig> {
+    INSTALLED.get()
+}
+
+/// `general.address` when a config file set it.
+pub fn file_address() -> Option<String> {
+    installed()
+        .filter(|loaded| loaded.source("general.address").is_some())
+        .map(|loaded| loaded.config.general.address.clone())
+}
+
+/// The address for a test without an ADDRESS section (before the protocol
+/// default): `$GRPCTESTIFY_ADDRESS`, then the config file.
+pub fn env_or_file_address() -> Option<String> {
+    std::env::var(ENV_GRPCTESTIFY_ADDRESS)
+        .ok()
+        .or_else(file_address)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,7 +379,7 @@ mod tests {
         assert_eq!(config.general.address, "localhost:4770");
         assert_eq!(config.general.parallel, "auto");
         assert_eq!(config.general.timeout, 30);
-        assert_eq!(config.general.retry, 3);
+        assert_eq!(config.general.retry, 0);
         assert_eq!(config.general.retry_delay, 1.0);
         assert_eq!(config.progress.mode, "auto");
         assert!(config.progress.color);
@@ -168,7 +393,7 @@ mod tests {
         assert_eq!(general.address, "localhost:4770");
         assert_eq!(general.parallel, "auto");
         assert_eq!(general.timeout, 30);
-        assert_eq!(general.retry, 3);
+        assert_eq!(general.retry, 0);
         assert_eq!(general.retry_delay, 1.0);
         assert!(general.log_format.is_none());
         assert!(general.log_output.is_none());
@@ -215,4 +440,51 @@ mod tests {
         let config2 = config1.clone();
         assert_eq!(config1.general.address, config2.general.address);
     }
+
+    #[test]
+    fn project_file_overrides_user_file_key_by_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let user = dir.path().join("user.yaml");
+        std::fs::write(
+            &user,
+            "general:\n  timeout: 5\n  retry: 2\nprogress:\n  color: false\n",
+        )
+        .unwrap();
+        let project = dir.path().join("repo");
+        std::fs::create_dir_all(project.join("tests")).unwrap();
+        let toml = project.join("grpctestify.toml");
+        std::fs::write(
+            &toml,
+            "[general]\ntimeout = 10\nparallel = 4\nlog_output = \"reports/junit.xml\"\n",
+        )
+        .unwrap();
+
+        let loaded = load_config_files(&[user.clone(), toml.clone()]).unwrap();
+        assert_eq!(loaded.config.general.timeout, 10);
+        assert_eq!(loaded.config.general.retry, 2);
+        assert_eq!(loaded.config.general.parallel, "4");
+        assert!(!loaded.config.progress.color);
+        assert_eq!(
+            loaded.config.general.log_output.as_deref(),
+            Some(project.join("reports/junit.xml").to_str().unwrap())
+        );
+        assert_eq!(loaded.source("general.timeout"), Some(toml.as_path()));
+        assert_eq!(loaded.source("general.retry"), Some(user.as_path()));
+        assert_eq!(loaded.source("general.address"), None);
+
+        let found = discover_config_files(&project.join("tests"));
+        assert_eq!(found.last(), Some(&std::path::absolute(&toml).unwrap()));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected_with_the_file_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("grpctestify.toml");
+        std::fs::write(&path, "[general]\nparalel = 4\n").unwrap();
+        let err = format!("{:#}", load_config_files(&[path]).unwrap_err());
+        assert!(
+            err.contains("grpctestify.toml") && err.contains("paralel"),
+            "{err}"
+        );
+    }
 }
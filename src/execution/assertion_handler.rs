@@ -1,12 +1,11 @@
 use crate::assert::AssertionEngine;
+use crate::grpc::MetadataMap;
 #[cfg(test)]
 use crate::parser::ast::{Section, SectionContent, SectionType};
 use crate::plugins::AssertionTiming;
 use crate::utils::section_content_line;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::LazyLock;
 
 /// Assertion evaluation result
 #[derive(Debug, Clone)]
@@ -23,15 +22,11 @@ pub struct AssertionHandler {
     engine: AssertionEngine,
 }
 
-/// Global plugin registry for assertion evaluation.
-static PLUGIN_REGISTRY: LazyLock<Arc<dyn apif_assert::registry::PluginRegistry>> =
-    LazyLock::new(|| Arc::new(crate::execution::plugin_dir::build_plugin_manager()));
-
 impl AssertionHandler {
     /// Create new assertion handler
     pub fn new(_verbose: bool) -> Self {
         Self {
-            engine: AssertionEngine::with_registry(PLUGIN_REGISTRY.clone()),
+            engine: AssertionEngine::with_registry(crate::execution::plugin_dir::plugin_registry()),
         }
     }
 
@@ -41,8 +36,8 @@ impl AssertionHandler {
         &self,
         sections: &[Section],
         target_value: &Value,
-        headers: &HashMap<String, String>,
-        trailers: &HashMap<String, String>,
+        headers: &MetadataMap,
+        trailers: &MetadataMap,
     ) -> AssertionResult {
         let mut failure_messages = Vec::new();
 
@@ -75,8 +70,8 @@ impl AssertionHandler {
         &self,
         section: &Section,
         target_value: &Value,
-        headers: &HashMap<String, String>,
-        trailers: &HashMap<String, String>,
+        headers: &MetadataMap,
+        trailers: &MetadataMap,
     ) -> AssertionResult {
         let mut failure_messages = Vec::new();
 
@@ -123,8 +118,8 @@ impl AssertionHandler {
         &self,
         assertion: &str,
         target_value: &Value,
-        headers: Option<&HashMap<String, String>>,
-        trailers: Option<&HashMap<String, String>>,
+        headers: Option<&MetadataMap>,
+        trailers: Option<&MetadataMap>,
     ) -> Result<crate::assert::AssertionResult, String> {
         self.engine
             .evaluate(assertion, target_value, headers, trailers)
@@ -137,8 +132,8 @@ impl AssertionHandler {
         &self,
         lines: &[String],
         target_value: &Value,
-        headers: &HashMap<String, String>,
-        trailers: &HashMap<String, String>,
+        headers: &MetadataMap,
+        trailers: &MetadataMap,
         section_context: &str,
         start_line: usize,
         timing: Option<&AssertionTiming>,
@@ -240,8 +235,8 @@ mod tests {
         }];
 
         let target = json!({"id": 123, "name": "test"});
-        let headers = HashMap::new();
-        let trailers = HashMap::new();
+        let headers = MetadataMap::new();
+        let trailers = MetadataMap::new();
 
         let result = handler.evaluate_assertions(&sections, &target, &headers, &trailers);
         assert!(result.passed);
@@ -263,8 +258,8 @@ mod tests {
         }];
 
         let target = json!({"id": 123, "name": "test"});
-        let headers = HashMap::new();
-        let trailers = HashMap::new();
+        let headers = MetadataMap::new();
+        let trailers = MetadataMap::new();
 
         let result = handler.evaluate_assertions(&sections, &target, &headers, &trailers);
         assert!(!result.passed);
@@ -325,8 +320,8 @@ mod tests {
     fn test_evaluate_single_assertion() {
         let handler = AssertionHandler::new(false);
         let target = json!({"id": 123, "name": "test"});
-        let headers = HashMap::new();
-        let trailers = HashMap::new();
+        let headers = MetadataMap::new();
+        let trailers = MetadataMap::new();
 
         let result = handler.evaluate_single_assertion(
             ".id == 123",
@@ -352,8 +347,8 @@ mod tests {
         };
 
         let target = json!({"id": 123});
-        let headers = HashMap::new();
-        let trailers = HashMap::new();
+        let headers = MetadataMap::new();
+        let trailers = MetadataMap::new();
 
         let result = handler.evaluate_section_assertions(&section, &target, &headers, &trailers);
         assert!(result.passed);
@@ -0,0 +1,422 @@
+//! Cross-file test dependencies: `META.depends_on` names test files that must
+//! pass before a file runs, and whose EXTRACT variables it starts with.
+//!
+//! `run` schedules along this graph, `check` reports missing files and
+//! cycles, and `graph --format mermaid` draws the edges.
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::future::Future;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::parser;
+use crate::parser::ast::{GctfDocument, SectionContent, SectionType};
+
+/// `path` with `.` and `..` components folded away, without touching the
+/// filesystem, so `tests/orders/../auth/login.gctf` and
+/// `tests/auth/login.gctf` name the same node.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) {
+                    out.pop();
+                } else {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// A document's `META.depends_on`, resolved against the directory of `file`.
+pub fn dependencies_of(doc: &GctfDocument, file: &Path) -> Vec<PathBuf> {
+    let dir = file.parent().unwrap_or(Path::new(""));
+    doc.sections
+        .iter()
+        .find_map(|s| match &s.content {
+            SectionContent::Meta(meta) if s.section_type == SectionType::Meta => Some(
+                meta.depends_on
+                    .iter()
+                    .map(|dep| normalize_path(&dir.join(dep)))
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// The `META.depends_on` edges reachable from a set of files.
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    /// Every existing file reached, with the files it depends on in declared
+    /// order.
+    edges: BTreeMap<PathBuf, Vec<PathBuf>>,
+    /// Declared dependencies that are not files.
+    missing: BTreeSet<PathBuf>,
+}
+
+impl DependencyGraph {
+    /// Follow `META.depends_on` from `roots`, including dependencies of
+    /// dependencies. A file that doesn't parse contributes no edges (its
+    /// parse error surfaces wherever it is run or checked).
+    pub fn build(roots: &[PathBuf]) -> Self {
+        let mut graph = Self::default();
+        let mut queue: Vec<PathBuf> = roots.iter().map(|p| normalize_path(p)).collect();
+        while let Some(file) = queue.pop() {
+            if graph.edges.contains_key(&file) || graph.missing.contains(&file) {
+                continue;
+            }
+            if !file.is_file() {
+                graph.missing.insert(file);
+                continue;
+            }
+            let deps = parser::parse_gctf(&file)
+                .map(|doc| dependencies_of(&doc, &file))
+                .unwrap_or_default();
+            queue.extend(deps.iter().cloned());
+            graph.edges.insert(file, deps);
+        }
+        graph
+    }
+
+    /// Whether any file declares a dependency.
+    pub fn has_edges(&self) -> bool {
+        self.edges.values().any(|deps| !deps.is_empty())
+    }
+
+    /// Every existing file in the graph.
+    pub fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.edges.keys()
+    }
+
+    /// What `file` depends on (normalized paths), in declared order.
+    pub fn dependencies(&self, file: &Path) -> &[PathBuf] {
+        self.edges
+            .get(&normalize_path(file))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn is_missing(&self, file: &Path) -> bool {
+        self.missing.contains(file)
+    }
+
+    /// Whether some file in the graph depends on `file`.
+    pub fn has_dependents(&self, file: &Path) -> bool {
+        let file = normalize_path(file);
+        self.edges.values().any(|deps| deps.contains(&file))
+    }
+
+    /// Every group of files that depend on each other in a loop. No file in
+    /// such a group can ever have its dependencies met.
+    pub fn cycles(&self) -> Vec<Cycle> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: HashMap::new(),
+            low: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            next: 0,
+            groups: Vec::new(),
+        };
+        for file in self.edges.keys() {
+            if !tarjan.index.contains_key(file) {
+                tarjan.visit(file);
+            }
+        }
+        tarjan
+            .groups
+            .into_iter()
+            .filter_map(|group| {
+                let path = self.cycle_through(&group)?;
+                Some(Cycle {
+                    files: group.into_iter().cloned().collect(),
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    /// A shortest path from the group's first file back to itself.
+    fn cycle_through(&self, group: &BTreeSet<&PathBuf>) -> Option<Vec<PathBuf>> {
+        let start = *group.iter().next()?;
+        let mut parent: HashMap<&PathBuf, &PathBuf> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(file) = queue.pop_front() {
+            for dep in &self.edges[file] {
+                if dep == start {
+                    let mut path = vec![start.clone()];
+                    let mut at = file;
+                    while at != start {
+                        path.push(at.clone());
+                        at = parent[at];
+                    }
+                    path[1..].reverse();
+                    path.push(start.clone());
+                    return Some(path);
+                }
+                if group.contains(dep) && !parent.contains_key(dep) {
+                    parent.insert(dep, file);
+                    queue.push_back(dep);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Tarjan's strongly connected components; keeps only groups that contain a
+/// cycle (more than one file, or a file depending on itself).
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    index: HashMap<&'a PathBuf, usize>,
+    low: HashMap<&'a PathBuf, usize>,
+    stack: Vec<&'a PathBuf>,
+    on_stack: BTreeSet<&'a PathBuf>,
+    next: usize,
+    groups: Vec<BTreeSet<&'a PathBuf>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, file: &'a PathBuf) {
+        self.index.insert(file, self.next);
+        self.low.insert(file, self.next);
+        self.next += 1;
+        self.stack.push(file);
+        self.on_stack.insert(file);
+
+        for dep in &self.graph.edges[file] {
+            if !self.graph.edges.contains_key(dep) {
+                continue;
+            }
+            if !self.index.contains_key(dep) {
+                self.visit(dep);
+                let low = self.low[file].min(self.low[dep]);
+                self.low.insert(file, low);
+            } else if self.on_stack.contains(dep) {
+                let low = self.low[file].min(self.index[dep]);
+                self.low.insert(file, low);
+            }
+        }
+
+        if self.low[file] == self.index[file] {
+            let mut group = BTreeSet::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                group.insert(member);
+                if member == file {
+                    break;
+                }
+            }
+            if group.len() > 1 || self.graph.edges[file].contains(file) {
+                self.groups.push(group);
+            }
+        }
+    }
+}
+
+/// Add to `files` the prerequisites they reach through `META.depends_on` but
+/// don't include, so a test always sees the variables it was written against.
+/// Returns the graph and how many files were added.
+pub fn add_dependencies(files: &mut Vec<PathBuf>) -> (DependencyGraph, usize) {
+    let graph = DependencyGraph::build(files);
+    if !graph.has_edges() {
+        return (graph, 0);
+    }
+    let selected: BTreeSet<PathBuf> = files.iter().map(|f| normalize_path(f)).collect();
+    let before = files.len();
+    files.extend(graph.files().filter(|f| !selected.contains(*f)).cloned());
+    let added = files.len() - before;
+    (graph, added)
+}
+
+/// Files that depend on each other in a loop.
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    /// Every file in the loop (a strongly connected group).
+    pub files: BTreeSet<PathBuf>,
+    /// One path around it, ending where it starts.
+    pub path: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for Cycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path: Vec<String> = self.path.iter().map(|p| p.display().to_string()).collect();
+        f.write_str(&path.join(" → "))
+    }
+}
+
+/// Run every node once the nodes in its `deps` (indices) have finished, at
+/// most `parallel` at a time. `start` is handed the outputs of all nodes
+/// finished so far. Outputs come back in node order.
+///
+/// Once `stop` is set no further node starts; those already running finish
+/// and the rest are missing from the result, as are nodes whose dependencies
+/// never finish — `deps` must be acyclic.
+pub async fn run_in_dependency_order<O, F, Fut>(
+    deps: &[Vec<usize>],
+    parallel: usize,
+    stop: &AtomicBool,
+    mut start: F,
+) -> Vec<O>
+where
+    F: FnMut(usize, &[Option<O>]) -> Fut,
+    Fut: Future<Output = O>,
+{
+    let mut waiting: Vec<usize> = deps.iter().map(Vec::len).collect();
+    let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); deps.len()];
+    for (node, node_deps) in deps.iter().enumerate() {
+        for &dep in node_deps {
+            dependents[dep].push(node);
+        }
+    }
+    let mut ready: VecDeque<usize> = (0..deps.len()).filter(|&n| waiting[n] == 0).collect();
+    let mut outputs: Vec<Option<O>> = deps.iter().map(|_| None).collect();
+    let mut running = FuturesUnordered::new();
+
+    loop {
+        while running.len() < parallel.max(1)
+            && !stop.load(Ordering::Relaxed)
+            && let Some(node) = ready.pop_front()
+        {
+            let future = start(node, &outputs);
+            running.push(async move { (node, future.await) });
+        }
+        let Some((node, output)) = running.next().await else {
+            break;
+        };
+        for &dependent in &dependents[node] {
+            waiting[dependent] -= 1;
+            if waiting[dependent] == 0 {
+                ready.push_back(dependent);
+            }
+        }
+        outputs[node] = Some(output);
+    }
+    outputs.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, rel: &str, depends_on: &[&str]) {
+        let path = dir.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let meta = if depends_on.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "--- META ---\ndepends_on: [{}]\n\n",
+                depends_on
+                    .iter()
+                    .map(|d| format!("\"{d}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        };
+        std::fs::write(
+            path,
+            format!("{meta}--- ENDPOINT ---\na.B/C\n\n--- REQUEST ---\n{{}}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn normalize_folds_dot_components() {
+        assert_eq!(
+            normalize_path(Path::new("./tests/orders/../auth/login.gctf")),
+            PathBuf::from("tests/auth/login.gctf")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../shared/x.gctf")),
+            PathBuf::from("../shared/x.gctf")
+        );
+    }
+
+    #[test]
+    fn build_follows_dependencies_transitively_and_records_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "orders/create.gctf", &["../auth/login.gctf"]);
+        write(dir.path(), "auth/login.gctf", &["signup.gctf", "gone.gctf"]);
+        write(dir.path(), "auth/signup.gctf", &[]);
+
+        let root = dir.path().join("orders/create.gctf");
+        let graph = DependencyGraph::build(std::slice::from_ref(&root));
+        assert!(graph.has_edges());
+        assert_eq!(graph.files().count(), 3);
+        assert_eq!(
+            graph.dependencies(&root),
+            [normalize_path(&dir.path().join("auth/login.gctf"))]
+        );
+        assert!(graph.is_missing(&normalize_path(&dir.path().join("auth/gone.gctf"))));
+        assert!(graph.has_dependents(&dir.path().join("auth/signup.gctf")));
+        assert!(graph.cycles().is_empty());
+    }
+
+    #[test]
+    fn cycles_are_reported_as_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.gctf", &["b.gctf"]);
+        write(dir.path(), "b.gctf", &["c.gctf"]);
+        write(dir.path(), "c.gctf", &["a.gctf"]);
+        write(dir.path(), "self.gctf", &["self.gctf"]);
+        write(dir.path(), "ok.gctf", &["a.gctf"]);
+
+        let graph =
+            DependencyGraph::build(&[dir.path().join("ok.gctf"), dir.path().join("self.gctf")]);
+        let cycles: Vec<String> = graph
+            .cycles()
+            .iter()
+            .map(|c| {
+                c.path
+                    .iter()
+                    .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" → ")
+            })
+            .collect();
+        assert_eq!(cycles.len(), 2, "{cycles:?}");
+        assert!(cycles.contains(&"a.gctf → b.gctf → c.gctf → a.gctf".to_string()));
+        assert!(cycles.contains(&"self.gctf → self.gctf".to_string()));
+    }
+
+    #[tokio::test]
+    async fn scheduler_starts_nodes_after_their_dependencies() {
+        // 0 ← 1 ← 2, and 3 independent.
+        let deps = vec![vec![], vec![0], vec![1], vec![]];
+        let order = std::sync::Mutex::new(Vec::new());
+        let stop = AtomicBool::new(false);
+        let outputs = run_in_dependency_order(&deps, 2, &stop, |node, done: &[Option<usize>]| {
+            let finished = done.iter().flatten().count();
+            order.lock().unwrap().push(node);
+            async move { node * 10 + finished }
+        })
+        .await;
+        let order = order.into_inner().unwrap();
+        let pos = |n| order.iter().position(|&x| x == n).unwrap();
+        assert!(pos(0) < pos(1) && pos(1) < pos(2));
+        assert_eq!(outputs.len(), 4);
+        assert_eq!(outputs[2] / 10, 2);
+    }
+
+    #[tokio::test]
+    async fn scheduler_starts_nothing_new_once_stopped() {
+        let deps = vec![vec![], vec![], vec![], vec![]];
+        let stop = AtomicBool::new(false);
+        let outputs = run_in_dependency_order(&deps, 1, &stop, |node, _: &[Option<usize>]| {
+            if node == 1 {
+                stop.store(true, Ordering::Relaxed);
+            }
+            async move { node }
+        })
+        .await;
+        assert_eq!(outputs, vec![0, 1], "the running node finishes");
+    }
+}
@@ -1,11 +1,17 @@
 pub mod assertion_handler;
+pub mod depends;
 pub mod error_handler;
 pub mod plugin_dir;
+pub mod quarantine;
+pub mod repeat;
 pub mod request_handler;
 pub mod response_handler;
 pub mod runner;
 pub mod runner_helpers;
+pub mod shard;
+pub mod trace;
 pub mod validator;
+pub mod watch;
 pub mod workflow_events;
 
 pub use assertion_handler::AssertionHandler;
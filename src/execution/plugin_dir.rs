@@ -1,9 +1,34 @@
-// Assertion-plugin registry shared by the execution-side plugin registries.
+// Assertion-plugin registry shared by the execution-side assertion engines.
 //
-// `response_handler.rs`/`assertion_handler.rs`/`runner.rs` each hold their
-// own `LazyLock<Arc<dyn PluginRegistry>>` (one `PluginManager` per file,
-// first-populated on first use); this module is where each of those builds
-// its `PluginManager` from.
+// `response_handler.rs`/`assertion_handler.rs`/`runner.rs` all take their
+// registry from [`plugin_registry`] when an engine is built — populated on
+// first use, and rebuilt by [`reload_plugins`] when `run --watch` sees a
+// `.rhai` file change, so the next cycle's runner picks up the new scripts.
+
+use std::sync::{Arc, LazyLock, RwLock};
+
+type Registry = Arc<dyn apif_assert::registry::PluginRegistry>;
+
+static REGISTRY: LazyLock<RwLock<Registry>> =
+    LazyLock::new(|| RwLock::new(Arc::new(build_plugin_manager())));
+
+/// The current plugin registry.
+pub fn plugin_registry() -> Registry {
+    match REGISTRY.read() {
+        Ok(registry) => registry.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Rebuild the registry from the plugin directories. Engines built before
+/// keep the registry they started with.
+pub fn reload_plugins() {
+    let fresh: Registry = Arc::new(build_plugin_manager());
+    match REGISTRY.write() {
+        Ok(mut registry) => *registry = fresh,
+        Err(poisoned) => *poisoned.into_inner() = fresh,
+    }
+}
 
 /// A default `PluginManager` (built-ins registered) plus every `.rhai`
 /// plugin found under the configured convention directories — the
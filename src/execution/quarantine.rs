@@ -0,0 +1,109 @@
+//! Quarantined tests: known-flaky files whose failures are still run and
+//! reported, but don't fail the run.
+//!
+//! A test is quarantined by `META.quarantine: true`, or by a line in the
+//! project's `.grpctestify/quarantine` file: a path or glob relative to the
+//! project root (the directory holding `.grpctestify`). A line naming a
+//! directory covers every test below it; `#` starts a comment.
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+use crate::parser::ast::{GctfDocument, SectionContent, SectionType};
+
+/// The project's `.grpctestify/quarantine` list.
+#[derive(Debug, Default)]
+pub struct QuarantineList {
+    root: PathBuf,
+    patterns: Option<GlobSet>,
+}
+
+impl QuarantineList {
+    /// Read the list of the nearest `.grpctestify` project of the run's first
+    /// path. No project or no file is an empty list.
+    pub fn load(test_paths: &[PathBuf]) -> Result<Self> {
+        let start = crate::serve::project::env_search_start(test_paths);
+        let Some(project) = crate::serve::project::find_project_dir(&start) else {
+            return Ok(Self::default());
+        };
+        let path = project.join("quarantine");
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(Self::default());
+        };
+        let root = project.parent().map(Path::to_path_buf).unwrap_or_default();
+        Self::parse(root, &content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    fn parse(root: PathBuf, content: &str) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut any = false;
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let pattern = line.trim_start_matches("./").trim_end_matches('/');
+            let glob = Glob::new(pattern)
+                .with_context(|| format!("line {}: bad pattern '{line}'", index + 1))?;
+            builder.add(glob);
+            any = true;
+        }
+        let patterns = if any { Some(builder.build()?) } else { None };
+        Ok(Self { root, patterns })
+    }
+
+    /// Whether `file`, or a directory it is in, is on the list.
+    pub fn contains(&self, file: &Path) -> bool {
+        let Some(patterns) = &self.patterns else {
+            return false;
+        };
+        let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+        let Ok(relative) = file.strip_prefix(&self.root) else {
+            return false;
+        };
+        relative
+            .ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| patterns.is_match(p))
+    }
+}
+
+/// `META.quarantine` of a parsed test.
+pub fn meta_quarantined(doc: &GctfDocument) -> bool {
+    doc.sections.iter().any(|s| {
+        matches!(&s.content, SectionContent::Meta(meta)
+            if s.section_type == SectionType::Meta && meta.quarantine)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_matches_files_globs_and_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for file in ["flaky.gctf", "slow/a.gctf", "net/x_retry.gctf", "ok.gctf"] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let list = QuarantineList::parse(
+            root.clone(),
+            "# known flaky\n./flaky.gctf\nslow/\nnet/*_retry.gctf  # upstream bug\n",
+        )
+        .unwrap();
+        assert!(list.contains(&root.join("flaky.gctf")));
+        assert!(list.contains(&root.join("slow/a.gctf")));
+        assert!(list.contains(&root.join("net/x_retry.gctf")));
+        assert!(!list.contains(&root.join("ok.gctf")));
+    }
+
+    #[test]
+    fn bad_pattern_names_its_line() {
+        let err = QuarantineList::parse(PathBuf::new(), "ok.gctf\n[oops\n").unwrap_err();
+        assert!(format!("{err:#}").contains("line 2"), "{err:#}");
+    }
+}
@@ -0,0 +1,197 @@
+//! `run --repeat N` / `--until-failure`: execute each test several times and
+//! report it once, with the spread of its runs (pass rate, latency, failure
+//! messages) attached as [`RepeatStats`].
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Instant;
+
+use crate::state::{FailureCluster, RepeatStats, TestResult, TestStatus};
+
+/// Runs per test for `--until-failure` without `--repeat`, so a test that
+/// never fails still finishes.
+pub const UNTIL_FAILURE_LIMIT: u32 = 100;
+
+/// Run a test `times` times (stopping at its first failure with
+/// `until_failure`). The result reported is the first failing run — so its
+/// error and assertions are what the reports show — else the last one, along
+/// with the variables that run exported. A single run is returned untouched.
+pub async fn run_repeated<V, F, Fut>(times: u32, until_failure: bool, mut run: F) -> (TestResult, V)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = (TestResult, V)>,
+{
+    let mut samples = Samples::default();
+    let mut timed_run = async || {
+        let start = Instant::now();
+        let (result, vars) = run().await;
+        (result, vars, start.elapsed().as_millis() as u64)
+    };
+
+    let (mut reported, mut exported, elapsed) = timed_run().await;
+    if times <= 1 {
+        return (reported, exported);
+    }
+    samples.record(&reported, elapsed);
+    while samples.runs() < times as usize && !(until_failure && samples.failed()) {
+        let (result, vars, elapsed) = timed_run().await;
+        samples.record(&result, elapsed);
+        if reported.status != TestStatus::Fail {
+            reported = result;
+            exported = vars;
+        }
+    }
+    (reported.with_repeat(samples.stats()), exported)
+}
+
+/// What each run of one test did.
+#[derive(Debug, Default)]
+struct Samples {
+    durations_ms: Vec<u64>,
+    passed: usize,
+    failures: Vec<String>,
+}
+
+impl Samples {
+    fn record(&mut self, result: &TestResult, duration_ms: u64) {
+        self.durations_ms.push(duration_ms);
+        match result.status {
+            TestStatus::Pass => self.passed += 1,
+            TestStatus::Fail | TestStatus::Skip => self
+                .failures
+                .push(result.error_message.clone().unwrap_or_default()),
+        }
+    }
+
+    fn runs(&self) -> usize {
+        self.durations_ms.len()
+    }
+
+    fn failed(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    fn stats(&self) -> RepeatStats {
+        let mut sorted = self.durations_ms.clone();
+        sorted.sort_unstable();
+        let median_ms = match sorted.len() {
+            0 => 0,
+            n if n % 2 == 1 => sorted[n / 2],
+            n => (sorted[n / 2 - 1] + sorted[n / 2]) / 2,
+        };
+        let runs = self.runs();
+        RepeatStats {
+            runs,
+            passed: self.passed,
+            pass_rate: if runs == 0 {
+                0.0
+            } else {
+                self.passed as f64 / runs as f64
+            },
+            min_ms: sorted.first().copied().unwrap_or(0),
+            median_ms,
+            max_ms: sorted.last().copied().unwrap_or(0),
+            failures: cluster_failures(&self.failures),
+        }
+    }
+}
+
+/// Group failure messages that differ only in their numbers (ports, ids,
+/// durations), keyed on the first line — most frequent first, ties in the
+/// order they were first seen.
+fn cluster_failures(messages: &[String]) -> Vec<FailureCluster> {
+    let mut clusters: Vec<FailureCluster> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+    for message in messages {
+        let first_line = message.lines().next().unwrap_or_default().trim();
+        let index = *by_key.entry(mask_numbers(first_line)).or_insert_with(|| {
+            clusters.push(FailureCluster {
+                message: first_line.to_string(),
+                count: 0,
+            });
+            clusters.len() - 1
+        });
+        clusters[index].count += 1;
+    }
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.count));
+    clusters
+}
+
+fn mask_numbers(line: &str) -> String {
+    let mut masked = String::with_capacity(line.len());
+    let mut in_number = false;
+    for c in line.chars() {
+        if c.is_ascii_digit() {
+            if !in_number {
+                masked.push('#');
+            }
+            in_number = true;
+        } else {
+            masked.push(c);
+            in_number = false;
+        }
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcomes(results: Vec<TestResult>) -> impl FnMut() -> std::future::Ready<(TestResult, ())> {
+        let mut results = results.into_iter();
+        move || {
+            std::future::ready((
+                results
+                    .next()
+                    .unwrap_or_else(|| TestResult::pass("t", 0, None)),
+                (),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn repeat_reports_first_failure_with_clusters() {
+        let run = outcomes(vec![
+            TestResult::pass("t", 0, None),
+            TestResult::fail("t", "timeout after 31ms".into(), 0, None),
+            TestResult::fail("t", "status mismatch".into(), 0, None),
+            TestResult::fail("t", "timeout after 407ms\nat line 3".into(), 0, None),
+        ]);
+        let (result, ()) = run_repeated(5, false, run).await;
+        assert_eq!(result.status, TestStatus::Fail);
+        assert_eq!(result.error_message.as_deref(), Some("timeout after 31ms"));
+        let stats = result.repeat.unwrap();
+        assert_eq!((stats.runs, stats.passed), (5, 2));
+        assert!((stats.pass_rate - 0.4).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.failures,
+            vec![
+                FailureCluster {
+                    message: "timeout after 31ms".into(),
+                    count: 2
+                },
+                FailureCluster {
+                    message: "status mismatch".into(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn until_failure_stops_at_the_first_failure() {
+        let run = outcomes(vec![
+            TestResult::pass("t", 0, None),
+            TestResult::fail("t", "boom".into(), 0, None),
+        ]);
+        let (result, ()) = run_repeated(UNTIL_FAILURE_LIMIT, true, run).await;
+        assert_eq!(result.repeat.unwrap().runs, 2);
+    }
+
+    #[tokio::test]
+    async fn single_run_carries_no_stats() {
+        let (result, ()) = run_repeated(1, false, outcomes(vec![])).await;
+        assert!(result.repeat.is_none());
+    }
+}
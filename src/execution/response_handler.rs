@@ -5,23 +5,20 @@ use crate::parser::GctfDocument;
 use crate::parser::ast::{InlineOptions, Section, SectionContent, SectionType};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::sync::LazyLock;
 
 pub struct ResponseHandler {
     no_assert: bool,
     assertion_engine: AssertionEngine,
 }
 
-static PLUGIN_REGISTRY: LazyLock<Arc<dyn apif_assert::registry::PluginRegistry>> =
-    LazyLock::new(|| Arc::new(crate::execution::plugin_dir::build_plugin_manager()));
-
 impl ResponseHandler {
     /// Create new response handler
     pub fn new(no_assert: bool) -> Self {
         Self {
             no_assert,
-            assertion_engine: AssertionEngine::with_registry(PLUGIN_REGISTRY.clone()),
+            assertion_engine: AssertionEngine::with_registry(
+                crate::execution::plugin_dir::plugin_registry(),
+            ),
         }
     }
 
@@ -327,7 +324,7 @@ impl ResponseHandler {
 }
 
 // Most tests here construct a `ResponseHandler`, which forces this file's own
-// `PLUGIN_REGISTRY` lazy static (`build_plugin_manager()` → `fs::metadata` on
+// plugin registry (`build_plugin_manager()` → `fs::metadata` on
 // configured plugin dirs) — blocked under miri isolation.
 #[cfg(all(test, not(miri)))]
 mod tests {
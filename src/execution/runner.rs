@@ -1,8 +1,9 @@
 use super::super::parser::GctfDocument;
 use super::runner_helpers;
+use super::trace;
 use super::{AssertionHandler, RequestHandler, RequestSendResult, ResponseHandler};
 use crate::assert::{AssertionEngine, JsonComparator, get_json_diff};
-use crate::grpc::{GrpcClient, GrpcClientConfig};
+use crate::grpc::{GrpcClient, GrpcClientConfig, MetadataMap};
 use crate::optimizer;
 use crate::parser::ast::{SectionContent, SectionType};
 use crate::plugins::AssertionTiming;
@@ -13,15 +14,10 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::sync::LazyLock;
 use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
 
-/// Global plugin registry used by all assertion engines.
-static PLUGIN_REGISTRY: LazyLock<Arc<dyn apif_assert::registry::PluginRegistry>> =
-    LazyLock::new(|| Arc::new(crate::execution::plugin_dir::build_plugin_manager()));
-
 /// Execution plan for inspect workflow visualization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionPlan {
@@ -145,14 +141,16 @@ pub struct ExecutionSummary {
 }
 
 struct AssertionContext<'a> {
-    headers: &'a HashMap<String, String>,
-    trailers: &'a HashMap<String, String>,
+    headers: &'a MetadataMap,
+    trailers: &'a MetadataMap,
     timing: Option<&'a AssertionTiming>,
     /// EXTRACT-bound variables, so `$name` references in ASSERTS resolve.
     variables: &'a HashMap<String, Value>,
     /// Wire protocol that produced this response (`"grpc"`/`"grpc-web"`/
     /// `"connectrpc"`) — forwarded to assertion plugins via `PluginContext`.
     protocol: &'static str,
+    /// The call's trace span, which records each evaluated block.
+    span: Option<&'a trace::CallSpan>,
 }
 
 impl ExecutionPlan {
@@ -622,12 +620,17 @@ pub struct TestExecutionResult {
     /// `true` when at least one REQUEST needed more than one attempt to
     /// succeed — a Pass with `retried = true` is flaky, not a clean pass.
     pub retried: bool,
+    /// How many times the test ran under `--retry`; set by the caller's retry
+    /// loop, `None` for a bare runner result.
+    pub attempts: Option<u32>,
     /// Real wall-clock duration of each document in the chain, in source
     /// order — only populated on the whole-chain result [`ChainAccumulator`]
     /// produces (per-document results have their own duration in
     /// `call_duration_ms` instead). Lets reporters give each document its
     /// actual timing instead of splitting the total evenly.
     pub document_durations_ms: Vec<u64>,
+    /// W3C trace of the chain run, when the runner traces (`run --trace`).
+    pub trace: Option<apif_state::TraceInfo>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -675,7 +678,9 @@ impl TestExecutionResult {
             grpc_status: None,
             assertions: Vec::new(),
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
+            trace: None,
         }
     }
 
@@ -692,7 +697,9 @@ impl TestExecutionResult {
             grpc_status: None,
             assertions: Vec::new(),
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
+            trace: None,
         }
     }
 
@@ -706,6 +713,11 @@ impl TestExecutionResult {
         self
     }
 
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = Some(attempts);
+        self
+    }
+
     pub fn with_grpc_status(mut self, code: u32) -> Self {
         self.grpc_status = Some(code);
         self
@@ -790,7 +802,9 @@ impl ChainAccumulator {
             grpc_status: self.grpc_status,
             assertions: self.assertions,
             retried: self.retried,
+            attempts: None,
             document_durations_ms: self.document_durations_ms,
+            trace: None,
         }
     }
 }
@@ -802,6 +816,8 @@ pub struct TestRunner {
     write_mode: bool,
     verbose: bool,
     protocol_override: Option<crate::grpc::WireProtocol>,
+    /// CLI `--proxy`; a document's OPTIONS `proxy:` takes precedence.
+    proxy: Option<String>,
     /// Client-side connection pool slot. Threaded into `GrpcClientConfig` so the
     /// transport channel cache keys by it — distinct ids open distinct channels.
     connection_id: u64,
@@ -809,6 +825,20 @@ pub struct TestRunner {
     /// write mode, so reports (e.g. Allure attachments) can show what actually
     /// happened. Off by default: skips the extra buffering unless requested.
     capture_exchange: bool,
+    /// `run --record`/`--replay`: calls are recorded into, or answered from,
+    /// this cassette.
+    cassette: Option<Arc<crate::grpc::cassette::Cassette>>,
+    /// `run --trace`: each chain run is a trace, its calls carry `traceparent`.
+    tracer: Option<Arc<trace::Tracer>>,
+    /// `--seed`: makes template functions (`uuid()`, `fake.*`, `random_int`)
+    /// reproducible. `None` draws fresh values every run.
+    template_seed: Option<u64>,
+    /// Project environment (`run --env`) variables every chain starts from;
+    /// `_setup.gctf` captures and data-row values override them.
+    base_variables: HashMap<String, Value>,
+    /// Project environment `GRPC_ADDRESS`: used by documents without an
+    /// ADDRESS section, ahead of `$GRPCTESTIFY_ADDRESS`.
+    default_address: Option<String>,
     assertion_engine: AssertionEngine,
     coverage_collector: Option<Arc<CoverageCollector>>,
     request_handler: RequestHandler,
@@ -880,9 +910,17 @@ impl TestRunner {
             write_mode,
             verbose,
             protocol_override: None,
+            proxy: None,
             connection_id: 0,
             capture_exchange: false,
-            assertion_engine: AssertionEngine::with_registry(PLUGIN_REGISTRY.clone()),
+            cassette: None,
+            tracer: None,
+            template_seed: None,
+            base_variables: HashMap::new(),
+            default_address: None,
+            assertion_engine: AssertionEngine::with_registry(
+                crate::execution::plugin_dir::plugin_registry(),
+            ),
             coverage_collector: coverage_collector.clone(),
             request_handler: RequestHandler::new(no_assert, verbose, coverage_collector.clone()),
             response_handler: ResponseHandler::new(no_assert),
@@ -896,6 +934,19 @@ impl TestRunner {
         self
     }
 
+    /// Set the HTTP CONNECT proxy (`--proxy`) used unless a document's OPTIONS
+    /// names its own.
+    pub fn with_proxy(mut self, proxy: Option<String>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// Whether `--write` snapshots responses back into the test files. A
+    /// captured response alone doesn't say so: reports capture one too.
+    pub fn write_mode(&self) -> bool {
+        self.write_mode
+    }
+
     /// Ask the runner to capture headers/trailers/response messages for every
     /// test, not just in write mode, so reports can show what actually
     /// happened. Capped internally — see [`apif_state::CapturedExchange`].
@@ -904,6 +955,20 @@ impl TestRunner {
         self
     }
 
+    /// Record every call into `cassette`, or answer every call from it
+    /// without dialing, depending on its mode.
+    pub fn with_cassette(mut self, cassette: Option<Arc<crate::grpc::cassette::Cassette>>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    /// Trace every chain run: one trace per test, a span per call and per
+    /// assertion block, and `traceparent` in every call's metadata.
+    pub fn with_tracer(mut self, tracer: Option<Arc<trace::Tracer>>) -> Self {
+        self.tracer = tracer;
+        self
+    }
+
     /// Assign the connection-pool slot for this runner. Distinct ids map to
     /// distinct cached transport channels (see `GrpcClientConfig::connection_id`).
     pub fn with_connection_id(mut self, connection_id: u64) -> Self {
@@ -911,6 +976,44 @@ impl TestRunner {
         self
     }
 
+    /// Seed the RNG behind template functions so `uuid()`, `fake.*` and
+    /// `random_int` produce the same values on every run of a given test.
+    pub fn with_template_seed(mut self, seed: Option<u64>) -> Self {
+        self.template_seed = seed;
+        self
+    }
+
+    /// Variables (e.g. from a project environment) that every chain run
+    /// starts with, beneath any per-run initial variables.
+    pub fn with_base_variables(mut self, variables: HashMap<String, Value>) -> Self {
+        self.base_variables = variables;
+        self
+    }
+
+    /// Address for documents without an ADDRESS section, taking precedence
+    /// over `$GRPCTESTIFY_ADDRESS`.
+    pub fn with_default_address(mut self, address: Option<String>) -> Self {
+        self.default_address = address;
+        self
+    }
+
+    /// One template context per chain run. With a seed, the stream is keyed by
+    /// the file and its data row index, so each row and each file gets its own
+    /// reproducible sequence instead of all repeating the same values. Other
+    /// starting variables (env, fixtures, dependencies) stay out of the key:
+    /// a changed token must not reshuffle every generated id.
+    fn template_context(
+        &self,
+        document: &GctfDocument,
+        row: Option<usize>,
+    ) -> runner_helpers::TemplateContext {
+        let scope = match row {
+            Some(row) => format!("{}#{row}", document.file_path),
+            None => document.file_path.clone(),
+        };
+        runner_helpers::TemplateContext::new(self.template_seed, &scope)
+    }
+
     /// Run a test document chain.
     /// Walks the `next_document` linked list, accumulating EXTRACT variables
     /// between documents. Fail-fast: stops on first failure.
@@ -924,17 +1027,61 @@ impl TestRunner {
         document: &GctfDocument,
         initial_variables: HashMap<String, Value>,
     ) -> Result<TestExecutionResult> {
-        let mut variables = initial_variables;
+        Ok(self
+            .run_test_exporting_vars(document, initial_variables)
+            .await?
+            .0)
+    }
+
+    /// Run a test document chain like [`run_test_with_variables`], and also
+    /// return the variables the chain bound or changed on top of its starting
+    /// map (its EXTRACT results). These are what a file hands to the tests
+    /// that list it in `META.depends_on`.
+    pub async fn run_test_exporting_vars(
+        &self,
+        document: &GctfDocument,
+        initial_variables: HashMap<String, Value>,
+    ) -> Result<(TestExecutionResult, HashMap<String, Value>)> {
+        self.run_chain_exporting_vars(document, initial_variables, None)
+            .await
+    }
+
+    /// [`run_test_exporting_vars`] for data row `row` of a parameterized file,
+    /// so `--seed` gives each row its own template sequence.
+    pub async fn run_row_exporting_vars(
+        &self,
+        document: &GctfDocument,
+        initial_variables: HashMap<String, Value>,
+        row: usize,
+    ) -> Result<(TestExecutionResult, HashMap<String, Value>)> {
+        self.run_chain_exporting_vars(document, initial_variables, Some(row))
+            .await
+    }
+
+    async fn run_chain_exporting_vars(
+        &self,
+        document: &GctfDocument,
+        initial_variables: HashMap<String, Value>,
+        row: Option<usize>,
+    ) -> Result<(TestExecutionResult, HashMap<String, Value>)> {
+        let mut variables = self.base_variables.clone();
+        variables.extend(initial_variables);
+        let start = variables.clone();
         let mut acc = ChainAccumulator::default();
+        let templates = self.template_context(document, row);
+        let test_trace = self.start_trace(document);
 
         for doc in document.iter_chain() {
-            let result = self.run_one(doc, &mut variables).await?;
+            let result = self
+                .run_one(doc, &mut variables, &templates, test_trace.as_ref())
+                .await?;
             if acc.absorb(result) {
                 break;
             }
         }
 
-        Ok(acc.into_result())
+        variables.retain(|name, value| start.get(name) != Some(value));
+        Ok((Self::finish_trace(acc.into_result(), test_trace), variables))
     }
 
     /// Run a test document chain like [`run_test_with_variables`], but also
@@ -946,17 +1093,42 @@ impl TestRunner {
         &self,
         document: &GctfDocument,
     ) -> Result<(TestExecutionResult, HashMap<String, Value>)> {
-        let mut variables: HashMap<String, Value> = HashMap::new();
+        let mut variables = self.base_variables.clone();
         let mut acc = ChainAccumulator::default();
+        let templates = self.template_context(document, None);
+        let test_trace = self.start_trace(document);
 
         for doc in document.iter_chain() {
-            let result = self.run_one(doc, &mut variables).await?;
+            let result = self
+                .run_one(doc, &mut variables, &templates, test_trace.as_ref())
+                .await?;
             if acc.absorb(result) {
                 break;
             }
         }
 
-        Ok((acc.into_result(), variables))
+        Ok((Self::finish_trace(acc.into_result(), test_trace), variables))
+    }
+
+    fn start_trace(&self, document: &GctfDocument) -> Option<trace::TestTrace> {
+        self.tracer
+            .as_ref()
+            .map(|tracer| tracer.start_test(&document.file_path))
+    }
+
+    /// Close the chain's root span and note its trace on the result.
+    fn finish_trace(
+        mut result: TestExecutionResult,
+        test_trace: Option<trace::TestTrace>,
+    ) -> TestExecutionResult {
+        if let Some(test_trace) = test_trace {
+            let error = match &result.status {
+                TestExecutionStatus::Fail(message) => Some(message.as_str()),
+                TestExecutionStatus::Pass => None,
+            };
+            result.trace = Some(test_trace.finish(error));
+        }
+        result
     }
 
     /// Run a single document, sharing variables with the chain.
@@ -964,6 +1136,8 @@ impl TestRunner {
         &self,
         document: &GctfDocument,
         variables: &mut HashMap<String, Value>,
+        templates: &runner_helpers::TemplateContext,
+        test_trace: Option<&trace::TestTrace>,
     ) -> Result<TestExecutionResult> {
         let effective_dry_run = self.dry_run;
         let effective_no_assert = self.no_assert;
@@ -1015,7 +1189,31 @@ impl TestRunner {
             }
         }
 
-        let address = runner_helpers::effective_address(document, self.protocol_override);
+        let address = document
+            .get_address(self.default_address.as_deref())
+            .unwrap_or_else(|| runner_helpers::effective_address(document, self.protocol_override));
+        let address =
+            match runner_helpers::try_interpolate_variables(&address, variables, templates) {
+                Ok(interpolated) => interpolated.unwrap_or(address),
+                Err(e) => {
+                    return Ok(TestExecutionResult::fail(
+                        format!("Template error in ADDRESS: {e}"),
+                        None,
+                    ));
+                }
+            };
+        let mut unresolved = Vec::new();
+        runner_helpers::find_unresolved_placeholders(&address, variables, &mut unresolved);
+        if !unresolved.is_empty() {
+            return Ok(TestExecutionResult::fail(
+                format!(
+                    "Unresolved variable placeholder(s) in ADDRESS: {}",
+                    runner_helpers::format_unresolved_placeholders(&unresolved)
+                ),
+                None,
+            )
+            .with_failure_kind(FailureKind::Assertion));
+        }
 
         let (package, service, method) = match document.parse_endpoint() {
             Some(e) => e,
@@ -1051,17 +1249,26 @@ impl TestRunner {
         // undefined/typo'd placeholder being shipped verbatim in metadata.
         let request_metadata = match document.get_request_headers() {
             Some(headers) => {
-                let mut substituted = HashMap::with_capacity(headers.len());
+                let mut substituted = MetadataMap::with_capacity(headers.len());
                 let mut unresolved = Vec::new();
                 for (key, val) in headers {
                     let new_val =
-                        runner_helpers::interpolate_variables(&val, variables).unwrap_or(val);
+                        match runner_helpers::try_interpolate_variables(&val, variables, templates)
+                        {
+                            Ok(interpolated) => interpolated.unwrap_or(val),
+                            Err(e) => {
+                                return Ok(TestExecutionResult::fail(
+                                    format!("Template error in REQUEST_HEADERS '{key}': {e}"),
+                                    None,
+                                ));
+                            }
+                        };
                     runner_helpers::find_unresolved_placeholders(
                         &new_val,
                         variables,
                         &mut unresolved,
                     );
-                    substituted.insert(key, new_val);
+                    substituted.append(key, new_val);
                 }
                 if !unresolved.is_empty() {
                     tracing::debug!(
@@ -1082,6 +1289,15 @@ impl TestRunner {
             }
             None => None,
         };
+        let call_span = test_trace.map(|t| t.start_call(&full_service, &method, &address));
+        let request_metadata = match &call_span {
+            Some(span) => {
+                let mut metadata = request_metadata.unwrap_or_default();
+                span.inject(&mut metadata);
+                Some(metadata)
+            }
+            None => request_metadata,
+        };
 
         let client_config = GrpcClientConfig {
             address,
@@ -1104,6 +1320,7 @@ impl TestRunner {
                     .unwrap_or(crate::grpc::WireProtocol::Grpc)
             }),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            proxy: runner_helpers::effective_proxy(document, self.proxy.as_deref()),
         };
 
         let client_protocol = client_config.protocol;
@@ -1121,7 +1338,20 @@ impl TestRunner {
                 "reflection"
             }
         );
-        let client = GrpcClient::new(client_config).await?;
+        // A call is keyed by its document's first line; a document without
+        // sections has no place in a cassette and just runs live.
+        let cassette = self.cassette.as_ref().zip(document.sections.first());
+        let client = match cassette {
+            Some((cassette, first)) => {
+                let key = crate::grpc::cassette::CallKey::new(
+                    &document.file_path,
+                    first.start_line,
+                    format!("{full_service}/{method}"),
+                );
+                GrpcClient::with_cassette(client_config, cassette.clone(), key).await?
+            }
+            None => GrpcClient::new(client_config).await?,
+        };
 
         // Get input/output message types for field coverage tracking
         let input_message_type = client
@@ -1185,8 +1415,8 @@ impl TestRunner {
         let mut last_error_message: Option<String> = None;
         let mut last_error_json: Option<Value> = None;
         let mut last_error_timing: Option<AssertionTiming> = None;
-        let mut captured_headers: HashMap<String, String> = HashMap::new();
-        let mut captured_trailers: HashMap<String, String> = HashMap::new();
+        let mut captured_headers = MetadataMap::new();
+        let mut captured_trailers = MetadataMap::new();
         let mut failure_reasons: Vec<String> = Vec::new();
         let mut assertion_records: Vec<apif_state::AssertionRecord> = Vec::new();
         let mut assertion_timing = AssertionScopeTimingState::default();
@@ -1226,7 +1456,13 @@ impl TestRunner {
 
         let mut skip_next_section = false;
 
-        let mut captured_response = if effective_write_mode || self.capture_exchange {
+        // Recording drains the whole response stream so cassettes keep the
+        // trailers and any messages past the last RESPONSE.
+        let recording = self
+            .cassette
+            .as_ref()
+            .is_some_and(|c| c.mode() == crate::grpc::cassette::CassetteMode::Record);
+        let mut captured_response = if effective_write_mode || self.capture_exchange || recording {
             Some(crate::grpc::GrpcResponse::new())
         } else {
             None
@@ -1329,7 +1565,19 @@ impl TestRunner {
 
                         for mut request_value in request_values {
                             if !matches!(section.content, SectionContent::Empty) {
-                                self.substitute_variables(&mut request_value, variables);
+                                if let Err(e) = runner_helpers::try_substitute_variables(
+                                    &mut request_value,
+                                    variables,
+                                    templates,
+                                ) {
+                                    return Ok(TestExecutionResult::fail(
+                                        format!(
+                                            "Template error in REQUEST at line {}: {e}",
+                                            section.start_line
+                                        ),
+                                        Some(start_time.elapsed().as_millis() as u64),
+                                    ));
+                                }
                                 let mut unresolved = Vec::new();
                                 runner_helpers::collect_unresolved_placeholders(
                                     &request_value,
@@ -1357,6 +1605,7 @@ impl TestRunner {
                                 (&self.coverage_collector, &input_message_type)
                             {
                                 collector.record_fields_from_json(msg_type, &request_value);
+                                collector.record_variants_from_json(msg_type, &request_value);
                             }
 
                             let Some(tx_ref) = tx.as_mut() else {
@@ -1510,6 +1759,12 @@ impl TestRunner {
                                         let now_elapsed_ms =
                                             start_time.elapsed().as_millis() as u64;
 
+                                        if let (Some(collector), Some(msg_type)) =
+                                            (&self.coverage_collector, &output_message_type)
+                                        {
+                                            collector.record_variants_from_json(msg_type, &msg);
+                                        }
+
                                         let msg_for_state = msg.clone();
                                         last_message = Some(msg_for_state.clone());
                                         if section.inline_options.with_asserts {
@@ -1534,7 +1789,13 @@ impl TestRunner {
 
                                         if !effective_no_assert {
                                             let mut expected = expected_template.clone();
-                                            self.substitute_variables(&mut expected, variables);
+                                            self.substitute_expected(
+                                                &mut expected,
+                                                variables,
+                                                templates,
+                                                section.start_line,
+                                                &mut failure_reasons,
+                                            );
 
                                             if let (Some(collector), Some(msg_type)) =
                                                 (&self.coverage_collector, &output_message_type)
@@ -1723,6 +1984,7 @@ impl TestRunner {
                                             timing: scope_timing.as_ref(),
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 }
@@ -1762,6 +2024,7 @@ impl TestRunner {
                                             timing: last_error_timing.as_ref(),
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 } else if let Some(error_message) = &last_error_message {
@@ -1779,6 +2042,7 @@ impl TestRunner {
                                             timing: last_error_timing.as_ref(),
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 }
@@ -1832,6 +2096,11 @@ impl TestRunner {
                                 if let Some(resp) = &mut captured_response {
                                     resp.messages.push(msg.clone());
                                 }
+                                if let (Some(collector), Some(msg_type)) =
+                                    (&self.coverage_collector, &output_message_type)
+                                {
+                                    collector.record_variants_from_json(msg_type, &msg);
+                                }
 
                                 let should_format_message =
                                     tracing::enabled!(tracing::Level::DEBUG) || effective_no_assert;
@@ -1867,6 +2136,7 @@ impl TestRunner {
                                             timing: scope_timing.as_ref(),
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 }
@@ -1900,6 +2170,7 @@ impl TestRunner {
                                             timing: None,
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 }
@@ -1937,6 +2208,7 @@ impl TestRunner {
                                             timing: last_error_timing.as_ref(),
                                             variables: &*variables,
                                             protocol: protocol_str(client_protocol),
+                                            span: call_span.as_ref(),
                                         },
                                     );
                                 } else {
@@ -2031,7 +2303,13 @@ impl TestRunner {
                                             &section.content
                                         {
                                             let mut expected = expected_json.clone();
-                                            self.substitute_variables(&mut expected, variables);
+                                            self.substitute_expected(
+                                                &mut expected,
+                                                variables,
+                                                templates,
+                                                section.start_line,
+                                                &mut failure_reasons,
+                                            );
 
                                             // Try to extract tonic Status from anyhow::Error
                                             let (matches, got, mismatch_reason) = if let Some(
@@ -2182,6 +2460,7 @@ impl TestRunner {
                                                         timing: last_error_timing.as_ref(),
                                                         variables: &*variables,
                                                         protocol: protocol_str(client_protocol),
+                                                        span: call_span.as_ref(),
                                                     },
                                                 );
                                             }
@@ -2296,7 +2575,13 @@ impl TestRunner {
                                 if !effective_no_assert {
                                     if let SectionContent::Json(expected_json) = &section.content {
                                         let mut expected = expected_json.clone();
-                                        self.substitute_variables(&mut expected, variables);
+                                        self.substitute_expected(
+                                            &mut expected,
+                                            variables,
+                                            templates,
+                                            section.start_line,
+                                            &mut failure_reasons,
+                                        );
 
                                         if !super::error_handler::ErrorHandler::status_matches_expected_with_options(
                                         &status,
@@ -2352,6 +2637,7 @@ impl TestRunner {
                                                 timing: last_error_timing.as_ref(),
                                                 variables: &*variables,
                                                 protocol: protocol_str(client_protocol),
+                                                span: call_span.as_ref(),
                                             },
                                         );
                                         skip_next_section = true;
@@ -2475,6 +2761,18 @@ impl TestRunner {
         }
 
         let grpc_duration = start_time.elapsed().as_millis() as u64;
+        // The status the call ended with; none when it never reached a server.
+        let call_status = grpc_status.or((!transport_failure).then_some(0));
+
+        if let Some(span) = call_span {
+            span.finish(call_status, failure_reasons.first().map(String::as_str));
+        }
+
+        if let (Some(collector), Some(code)) = (&self.coverage_collector, call_status) {
+            let name = Self::grpc_code_name_from_numeric(i64::from(code))
+                .map_or_else(|| code.to_string(), str::to_string);
+            collector.record_status(&full_service, &method, &name);
+        }
 
         if !failure_reasons.is_empty() {
             // In write mode, assertion mismatches are expected because we are
@@ -2535,8 +2833,20 @@ impl TestRunner {
         self.response_handler.validate_document(document, response)
     }
 
-    fn substitute_variables(&self, value: &mut Value, variables: &HashMap<String, Value>) {
-        runner_helpers::substitute_variables(value, variables);
+    /// Substitute an expected RESPONSE/ERROR value. A template error is
+    /// reported as a failure reason; the comparison still runs so the diff
+    /// shows the placeholder that couldn't be evaluated.
+    fn substitute_expected(
+        &self,
+        expected: &mut Value,
+        variables: &HashMap<String, Value>,
+        templates: &runner_helpers::TemplateContext,
+        line: usize,
+        failure_reasons: &mut Vec<String>,
+    ) {
+        if let Err(e) = runner_helpers::try_substitute_variables(expected, variables, templates) {
+            failure_reasons.push(format!("Template error at line {line}: {e}"));
+        }
     }
 
     #[expect(clippy::too_many_arguments)]
@@ -2568,6 +2878,7 @@ impl TestRunner {
 
         let lines_to_evaluate: &[String] = optimized_lines.as_deref().unwrap_or(lines);
 
+        let started = std::time::SystemTime::now();
         let result = self.assertion_handler.evaluate_assertions_for_section(
             lines_to_evaluate,
             target_value,
@@ -2580,6 +2891,10 @@ impl TestRunner {
             assertion_context.protocol,
         );
 
+        if let Some(span) = assertion_context.span {
+            let failed = result.records.iter().filter(|r| !r.passed).count();
+            span.record_assertions(started, start_line, result.records.len(), failed);
+        }
         if !result.passed {
             failure_reasons.extend(result.failure_messages);
         }
@@ -3207,41 +3522,71 @@ chat.ChatService/SendMessages
     #[test]
     #[cfg_attr(miri, ignore)]
     fn substitute_variables_exact_match_preserves_type() {
-        let runner = TestRunner::new(false, 30, false, false, false, None);
         let mut value = json!("{{ count }}");
         let mut vars = HashMap::new();
         vars.insert("count".to_string(), json!(42));
 
-        runner.substitute_variables(&mut value, &vars);
+        runner_helpers::substitute_variables(&mut value, &vars);
         assert_eq!(value, json!(42));
     }
 
     #[test]
     #[cfg_attr(miri, ignore)]
     fn substitute_variables_interpolation_single_pass() {
-        let runner = TestRunner::new(false, 30, false, false, false, None);
         let mut value = json!("id={{id}}, user={{ user }}, ok={{ok}}");
         let mut vars = HashMap::new();
         vars.insert("id".to_string(), json!(7));
         vars.insert("user".to_string(), json!("alice"));
         vars.insert("ok".to_string(), json!(true));
 
-        runner.substitute_variables(&mut value, &vars);
+        runner_helpers::substitute_variables(&mut value, &vars);
         assert_eq!(value, json!("id=7, user=alice, ok=true"));
     }
 
     #[test]
     #[cfg_attr(miri, ignore)]
     fn substitute_variables_keeps_unknown_placeholder() {
-        let runner = TestRunner::new(false, 30, false, false, false, None);
         let mut value = json!("hello {{known}} and {{unknown}}");
         let mut vars = HashMap::new();
         vars.insert("known".to_string(), json!("world"));
 
-        runner.substitute_variables(&mut value, &vars);
+        runner_helpers::substitute_variables(&mut value, &vars);
         assert_eq!(value, json!("hello world and {{unknown}}"));
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn seeded_template_context_is_keyed_by_file_and_row() {
+        let runner =
+            TestRunner::new(false, 30, false, false, false, None).with_template_seed(Some(9));
+        let doc = |path: &str| GctfDocument {
+            file_path: path.to_string(),
+            sections: Vec::new(),
+            metadata: Default::default(),
+            next_document: None,
+        };
+        let draw = |document: &GctfDocument, row: Option<usize>, token: &str| {
+            let ctx = runner.template_context(document, row);
+            let vars = HashMap::from([("token".to_string(), json!(token))]);
+            let mut value = json!({
+                "id": "{{uuid()}}",
+                "n": "{{random_int(0, 1000000)}}",
+                "at": "{{now()}}",
+            });
+            runner_helpers::try_substitute_variables(&mut value, &vars, &ctx).unwrap();
+            value
+        };
+
+        let first = draw(&doc("a.gctf"), Some(1), "t1");
+        assert!(first["n"].is_i64(), "{first}");
+        assert_eq!(first["at"], json!("2025-01-01T00:00:00Z"));
+        assert_eq!(first, draw(&doc("a.gctf"), Some(1), "t1"));
+        assert_eq!(first, draw(&doc("a.gctf"), Some(1), "t2"));
+        assert_ne!(first, draw(&doc("a.gctf"), Some(2), "t1"));
+        assert_ne!(first, draw(&doc("a.gctf"), None, "t1"));
+        assert_ne!(first, draw(&doc("b.gctf"), Some(1), "t1"));
+    }
+
     #[test]
     fn test_expected_values_for_response_section() {
         use crate::parser::ast::{InlineOptions, Section, SectionContent, SectionSpan};
@@ -3461,8 +3806,8 @@ chat.ChatService/SendMessages
         ];
         let mut failures = Vec::new();
         let mut assertion_records = Vec::new();
-        let headers: HashMap<String, String> = HashMap::new();
-        let trailers: HashMap<String, String> = HashMap::new();
+        let headers = MetadataMap::new();
+        let trailers = MetadataMap::new();
 
         runner.run_assertions(
             &lines,
@@ -3477,6 +3822,7 @@ chat.ChatService/SendMessages
                 timing: None,
                 variables: &HashMap::new(),
                 protocol: "grpc",
+                span: None,
             },
         );
 
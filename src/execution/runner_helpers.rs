@@ -1 +1,2 @@
 pub use apif_execution::helpers::*;
+pub use apif_execution::template::TemplateContext;
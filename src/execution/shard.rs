@@ -0,0 +1,309 @@
+//! `--shard K/N`: split a collected suite across machines deterministically.
+//!
+//! The unit of work is a test file, except that files joined by
+//! `META.depends_on` move together so a prerequisite runs on one shard only,
+//! and a directory with a `_setup.gctf`/`_teardown.gctf` moves as a whole so
+//! its fixtures run once, next to all of its tests. Units are dealt
+//! largest-first onto the lightest shard; their weight is the file count, or
+//! with `--shard-timings` the durations a prior `--log-format json` report
+//! recorded for them.
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::Shard;
+use crate::execution::depends::{DependencyGraph, normalize_path};
+
+/// Milliseconds per test file, from a JSON report.
+pub type Timings = HashMap<PathBuf, u64>;
+
+/// Read per-file durations from a `--log-format json` report. Data rows
+/// (`file#[row=…]`) add up into their file.
+pub fn load_timings(path: &Path) -> Result<Timings> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read shard timings: {}", path.display()))?;
+    let report: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Shard timings are not a JSON report: {}", path.display()))?;
+    let results = report
+        .get("results")
+        .and_then(|r| r.as_array())
+        .with_context(|| {
+            format!(
+                "Shard timings have no \"results\" (expected a `--log-format json` report): {}",
+                path.display()
+            )
+        })?;
+
+    let mut timings = Timings::new();
+    for result in results {
+        let (Some(name), Some(duration)) = (
+            result.get("name").and_then(|n| n.as_str()),
+            result.get("duration_ms").and_then(|d| d.as_u64()),
+        ) else {
+            continue;
+        };
+        let file = name.split_once("#[").map_or(name, |(file, _)| file);
+        *timings.entry(normalize_path(Path::new(file))).or_default() += duration;
+    }
+    Ok(timings)
+}
+
+/// The tests that belong to `shard`, in their original order. `fixture_dirs`
+/// are the directories with a setup or teardown fixture; `dependencies` is the
+/// `META.depends_on` graph built from `tests`. What the shard's tests depend
+/// on is left for the caller to add (see [`crate::execution::depends::add_dependencies`]).
+pub fn select(
+    tests: Vec<PathBuf>,
+    fixture_dirs: &HashSet<PathBuf>,
+    dependencies: &DependencyGraph,
+    shard: Shard,
+    timings: Option<&Timings>,
+) -> Vec<PathBuf> {
+    let fixture_dirs: HashSet<PathBuf> = fixture_dirs.iter().map(|d| normalize_path(d)).collect();
+    let fixture_dir_of = |file: &Path| {
+        file.parent()
+            .map(normalize_path)
+            .filter(|dir| fixture_dirs.contains(dir))
+    };
+    let mut groups = Groups::default();
+    for test in &tests {
+        groups.find(&normalize_path(test));
+    }
+    for file in tests
+        .iter()
+        .map(|t| normalize_path(t))
+        .chain(dependencies.files().cloned())
+    {
+        if let Some(dir) = fixture_dir_of(&file) {
+            groups.union(&file, &dir);
+        }
+        for dep in dependencies.dependencies(&file) {
+            groups.union(&file, dep);
+            if let Some(dir) = fixture_dir_of(dep) {
+                groups.union(dep, &dir);
+            }
+        }
+    }
+
+    // Unit key (its first test) → its tests and fixture directories. Sorted
+    // keys keep the deal independent of the order files were collected in.
+    let mut by_root: HashMap<PathBuf, (Vec<&PathBuf>, BTreeSet<PathBuf>)> = HashMap::new();
+    for test in &tests {
+        let root = groups.find(&normalize_path(test));
+        let (files, dirs) = by_root.entry(root).or_default();
+        files.push(test);
+        dirs.extend(fixture_dir_of(test));
+    }
+    let units: BTreeMap<PathBuf, (Vec<&PathBuf>, BTreeSet<PathBuf>)> = by_root
+        .into_values()
+        .filter_map(|(files, dirs)| {
+            let key = files.iter().map(|f| normalize_path(f)).min()?;
+            Some((key, (files, dirs)))
+        })
+        .collect();
+
+    let weigh = Weigh::new(timings, &tests);
+    let mut weighted: Vec<(u64, &PathBuf, &Vec<&PathBuf>)> = units
+        .iter()
+        .map(|(key, (files, dirs))| {
+            let fixtures: u64 = dirs
+                .iter()
+                .flat_map(|dir| ["_setup.gctf", "_teardown.gctf"].map(|name| dir.join(name)))
+                .map(|fixture| weigh.known(&fixture))
+                .sum();
+            let weight = files.iter().map(|f| weigh.file(f)).sum::<u64>() + fixtures;
+            (weight, key, files)
+        })
+        .collect();
+    weighted.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+
+    let mut load = vec![0u64; shard.total];
+    let mut selected: HashSet<&PathBuf> = HashSet::new();
+    for (weight, _, files) in weighted {
+        let lightest = (0..shard.total).min_by_key(|&i| (load[i], i)).unwrap_or(0);
+        load[lightest] += weight;
+        if lightest + 1 == shard.index {
+            selected.extend(files.iter().copied());
+        }
+    }
+
+    tests
+        .iter()
+        .filter(|t| selected.contains(t))
+        .cloned()
+        .collect()
+}
+
+/// Union-find over normalized paths: tests, their dependencies and fixture
+/// directories, joined into the groups that must share a shard.
+#[derive(Default)]
+struct Groups {
+    parent: HashMap<PathBuf, PathBuf>,
+}
+
+impl Groups {
+    fn find(&mut self, node: &Path) -> PathBuf {
+        let parent = match self.parent.get(node) {
+            Some(parent) if parent != node => parent.clone(),
+            Some(_) => return node.to_path_buf(),
+            None => {
+                self.parent.insert(node.to_path_buf(), node.to_path_buf());
+                return node.to_path_buf();
+            }
+        };
+        let root = self.find(&parent);
+        self.parent.insert(node.to_path_buf(), root.clone());
+        root
+    }
+
+    fn union(&mut self, a: &Path, b: &Path) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent.insert(a, b);
+        }
+    }
+}
+
+/// How much a file weighs: its recorded duration, the mean recorded duration
+/// for files the report doesn't know, or 1 each without timings.
+struct Weigh<'a> {
+    timings: Option<&'a Timings>,
+    unknown: u64,
+}
+
+impl<'a> Weigh<'a> {
+    fn new(timings: Option<&'a Timings>, tests: &[PathBuf]) -> Self {
+        let known: Vec<u64> = timings
+            .map(|t| {
+                tests
+                    .iter()
+                    .filter_map(|f| t.get(&normalize_path(f)).copied())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let unknown = if known.is_empty() {
+            1
+        } else {
+            (known.iter().sum::<u64>() / known.len() as u64).max(1)
+        };
+        Self { timings, unknown }
+    }
+
+    fn file(&self, file: &Path) -> u64 {
+        match self.timings {
+            Some(timings) => timings
+                .get(&normalize_path(file))
+                .copied()
+                .unwrap_or(self.unknown),
+            None => 1,
+        }
+    }
+
+    /// A fixture's recorded duration; fixtures without one add nothing.
+    fn known(&self, file: &Path) -> u64 {
+        self.timings
+            .and_then(|t| t.get(&normalize_path(file)))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    fn shard(index: usize, total: usize) -> Shard {
+        Shard { index, total }
+    }
+
+    #[test]
+    fn shards_partition_the_suite() {
+        let tests = paths(&["a.gctf", "b.gctf", "c.gctf", "d.gctf", "e.gctf"]);
+        let mut seen = Vec::new();
+        for k in 1..=3 {
+            let part = select(
+                tests.clone(),
+                &HashSet::new(),
+                &DependencyGraph::default(),
+                shard(k, 3),
+                None,
+            );
+            assert!(part.len() <= 2, "{part:?}");
+            seen.extend(part);
+        }
+        seen.sort();
+        assert_eq!(seen, tests);
+    }
+
+    #[test]
+    fn fixture_directories_stay_together() {
+        let tests = paths(&["fx/a.gctf", "fx/b.gctf", "fx/c.gctf", "x.gctf", "y.gctf"]);
+        let fixture_dirs = HashSet::from([PathBuf::from("fx")]);
+        let first = select(
+            tests.clone(),
+            &fixture_dirs,
+            &DependencyGraph::default(),
+            shard(1, 2),
+            None,
+        );
+        let second = select(
+            tests,
+            &fixture_dirs,
+            &DependencyGraph::default(),
+            shard(2, 2),
+            None,
+        );
+        assert_eq!(first, paths(&["fx/a.gctf", "fx/b.gctf", "fx/c.gctf"]));
+        assert_eq!(second, paths(&["x.gctf", "y.gctf"]));
+    }
+
+    #[test]
+    fn timings_balance_by_duration() {
+        let tests = paths(&["slow.gctf", "a.gctf", "b.gctf", "c.gctf"]);
+        let timings = Timings::from([
+            (PathBuf::from("slow.gctf"), 9_000),
+            (PathBuf::from("a.gctf"), 3_000),
+            (PathBuf::from("b.gctf"), 3_000),
+        ]);
+        // c.gctf is unknown and weighs the mean of the known files (5s).
+        let first = select(
+            tests.clone(),
+            &HashSet::new(),
+            &DependencyGraph::default(),
+            shard(1, 2),
+            Some(&timings),
+        );
+        let second = select(
+            tests,
+            &HashSet::new(),
+            &DependencyGraph::default(),
+            shard(2, 2),
+            Some(&timings),
+        );
+        assert_eq!(first, paths(&["slow.gctf"]));
+        assert_eq!(second, paths(&["a.gctf", "b.gctf", "c.gctf"]));
+    }
+
+    #[test]
+    fn timings_sum_data_rows_into_their_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.json");
+        std::fs::write(
+            &report,
+            r#"{"results": [
+                {"name": "./t/a.gctf#[row=0 id=1]", "duration_ms": 40},
+                {"name": "t/a.gctf#[row=1 id=2]", "duration_ms": 60},
+                {"name": "t/b.gctf", "duration_ms": 7}
+            ]}"#,
+        )
+        .unwrap();
+        let timings = load_timings(&report).unwrap();
+        assert_eq!(timings[&PathBuf::from("t/a.gctf")], 100);
+        assert_eq!(timings[&PathBuf::from("t/b.gctf")], 7);
+    }
+}
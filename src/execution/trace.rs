@@ -0,0 +1,477 @@
+//! `run --trace` / `--otel-endpoint` / `--otel-file`: W3C trace context for
+//! test runs, so a failing test leads straight to its server-side trace.
+//!
+//! Each test (chain run) is one trace: a root span for the test, a CLIENT
+//! span per gRPC call — its id is what the call's `traceparent` carries — and
+//! an INTERNAL span per evaluated assertion block. Reports show the trace id;
+//! with an exporter configured, the run's spans are sent at the end as one
+//! OTLP/HTTP-JSON request, or appended to a file as one OTLP-JSON line.
+
+use anyhow::{Context, Result, bail};
+use apif_state::TraceInfo;
+use serde_json::{Value, json};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::grpc::MetadataMap;
+
+/// Placeholder in `--trace-url` replaced by the trace id.
+const TRACE_ID_PLACEHOLDER: &str = "{trace_id}";
+/// How long an OTLP endpoint gets to accept the run's spans.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where the run's spans go once it ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceExport {
+    /// OTLP/HTTP-JSON collector: the base URL (`/v1/traces` is appended) or
+    /// the full traces URL.
+    Endpoint(String),
+    /// OTLP-JSON file: one `ExportTraceServiceRequest` per line, appended.
+    File(PathBuf),
+}
+
+impl TraceExport {
+    fn traces_url(endpoint: &str) -> String {
+        let endpoint = endpoint.trim_end_matches('/');
+        if endpoint.ends_with("/v1/traces") {
+            endpoint.to_string()
+        } else {
+            format!("{endpoint}/v1/traces")
+        }
+    }
+}
+
+/// Span kinds as OTLP numbers them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpanKind {
+    Internal = 1,
+    Client = 3,
+}
+
+#[derive(Debug, Clone)]
+enum AttributeValue {
+    String(String),
+    Int(i64),
+}
+
+#[derive(Debug, Clone)]
+struct SpanRecord {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    name: String,
+    kind: SpanKind,
+    start: SystemTime,
+    end: SystemTime,
+    attributes: Vec<(&'static str, AttributeValue)>,
+    /// `Some` marks the span as failed, with this status message.
+    error: Option<String>,
+}
+
+/// Trace context for a whole run: hands out one [`TestTrace`] per test and
+/// collects every finished span for export.
+#[derive(Debug)]
+pub struct Tracer {
+    /// W3C `baggage` header value sent with every call, already encoded.
+    baggage: Option<String>,
+    url_template: Option<String>,
+    export: Option<TraceExport>,
+    spans: Mutex<Vec<SpanRecord>>,
+}
+
+impl Tracer {
+    /// `baggage` is `key=value` pairs (`--trace-baggage`); `url_template`
+    /// links a trace id to a trace viewer (`--trace-url`).
+    pub fn new(
+        baggage: &[String],
+        url_template: Option<String>,
+        export: Option<TraceExport>,
+    ) -> Result<Self> {
+        Ok(Self {
+            baggage: encode_baggage(baggage)?,
+            url_template,
+            export,
+            spans: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Start the trace of one test run.
+    pub fn start_test(self: &Arc<Self>, name: &str) -> TestTrace {
+        TestTrace {
+            tracer: Arc::clone(self),
+            trace_id: random_id(),
+            span_id: random_id(),
+            name: name.to_string(),
+            start: SystemTime::now(),
+        }
+    }
+
+    /// The id and viewer link reports show for a trace.
+    fn trace_info(&self, trace_id: &[u8; 16]) -> TraceInfo {
+        let trace_id = hex(trace_id);
+        TraceInfo {
+            url: self
+                .url_template
+                .as_ref()
+                .map(|template| template.replace(TRACE_ID_PLACEHOLDER, &trace_id)),
+            trace_id,
+        }
+    }
+
+    fn record(&self, span: SpanRecord) {
+        self.spans
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(span);
+    }
+
+    /// Send or write every span recorded so far (a no-op without an
+    /// exporter). Returns how many spans went out.
+    pub async fn export(&self) -> Result<usize> {
+        let Some(export) = &self.export else {
+            return Ok(0);
+        };
+        let spans = std::mem::take(&mut *self.spans.lock().unwrap_or_else(|e| e.into_inner()));
+        if spans.is_empty() {
+            return Ok(0);
+        }
+        let body = serde_json::to_string(&otlp_request(&spans))?;
+        match export {
+            TraceExport::Endpoint(endpoint) => {
+                let url = TraceExport::traces_url(endpoint);
+                reqwest::Client::new()
+                    .post(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .timeout(EXPORT_TIMEOUT)
+                    .body(body)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .with_context(|| format!("Failed to export spans to {url}"))?;
+            }
+            TraceExport::File(path) => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                writeln!(file, "{body}")
+                    .with_context(|| format!("Failed to write spans to {}", path.display()))?;
+            }
+        }
+        Ok(spans.len())
+    }
+}
+
+/// The trace of one test run: its root span, open until [`TestTrace::finish`].
+#[derive(Debug)]
+pub struct TestTrace {
+    tracer: Arc<Tracer>,
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    name: String,
+    start: SystemTime,
+}
+
+impl TestTrace {
+    /// Open the CLIENT span of one gRPC call in this test.
+    pub fn start_call(&self, service: &str, method: &str, address: &str) -> CallSpan {
+        CallSpan {
+            tracer: Arc::clone(&self.tracer),
+            trace_id: self.trace_id,
+            span_id: random_id(),
+            parent_span_id: self.span_id,
+            service: service.to_string(),
+            method: method.to_string(),
+            address: address.to_string(),
+            start: SystemTime::now(),
+        }
+    }
+
+    /// End the test's root span; `error` is its failure message.
+    pub fn finish(self, error: Option<&str>) -> TraceInfo {
+        self.tracer.record(SpanRecord {
+            trace_id: self.trace_id,
+            span_id: self.span_id,
+            parent_span_id: None,
+            name: format!("test {}", self.name),
+            kind: SpanKind::Internal,
+            start: self.start,
+            end: SystemTime::now(),
+            attributes: vec![("code.filepath", AttributeValue::String(self.name.clone()))],
+            error: error.map(str::to_string),
+        });
+        self.tracer.trace_info(&self.trace_id)
+    }
+}
+
+/// The span of one gRPC call (request, stream and all), open until
+/// [`CallSpan::finish`]. Assertion blocks evaluated on the call are its
+/// children.
+#[derive(Debug)]
+pub struct CallSpan {
+    tracer: Arc<Tracer>,
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: [u8; 8],
+    service: String,
+    method: String,
+    address: String,
+    start: SystemTime,
+}
+
+impl CallSpan {
+    /// Add `traceparent` (and `baggage`, when configured) to the call's
+    /// metadata. Values the test sets itself in REQUEST_HEADERS win.
+    pub fn inject(&self, metadata: &mut MetadataMap) {
+        if !metadata.contains_key("traceparent") {
+            metadata.insert(
+                "traceparent",
+                format!("00-{}-{}-01", hex(&self.trace_id), hex(&self.span_id)),
+            );
+        }
+        if let Some(baggage) = &self.tracer.baggage
+            && !metadata.contains_key("baggage")
+        {
+            metadata.insert("baggage", baggage.clone());
+        }
+    }
+
+    /// Record an assertion block evaluated on this call, from `start` until now.
+    pub fn record_assertions(&self, start: SystemTime, line: usize, total: usize, failed: usize) {
+        self.tracer.record(SpanRecord {
+            trace_id: self.trace_id,
+            span_id: random_id(),
+            parent_span_id: Some(self.span_id),
+            name: format!("ASSERTS line {line}"),
+            kind: SpanKind::Internal,
+            start,
+            end: SystemTime::now(),
+            attributes: vec![
+                ("code.lineno", AttributeValue::Int(to_i64(line))),
+                ("assertions.total", AttributeValue::Int(to_i64(total))),
+                ("assertions.failed", AttributeValue::Int(to_i64(failed))),
+            ],
+            error: (failed > 0).then(|| format!("{failed} of {total} assertion(s) failed")),
+        });
+    }
+
+    /// End the call's span with the gRPC status it ended with, if any, and
+    /// the first failure recorded on it.
+    pub fn finish(self, grpc_status: Option<u32>, error: Option<&str>) {
+        let mut attributes = vec![
+            ("rpc.system", AttributeValue::String("grpc".into())),
+            ("rpc.service", AttributeValue::String(self.service.clone())),
+            ("rpc.method", AttributeValue::String(self.method.clone())),
+            ("server.address", AttributeValue::String(self.address)),
+        ];
+        if let Some(code) = grpc_status {
+            attributes.push(("rpc.grpc.status_code", AttributeValue::Int(code.into())));
+        }
+        self.tracer.record(SpanRecord {
+            trace_id: self.trace_id,
+            span_id: self.span_id,
+            parent_span_id: Some(self.parent_span_id),
+            name: format!("{}/{}", self.service, self.method),
+            kind: SpanKind::Client,
+            start: self.start,
+            end: SystemTime::now(),
+            attributes,
+            error: error.map(str::to_string),
+        });
+    }
+}
+
+/// Build the W3C `baggage` header value from `key=value` pairs, percent-
+/// encoding whatever a baggage value may not contain.
+fn encode_baggage(pairs: &[String]) -> Result<Option<String>> {
+    let mut members = Vec::with_capacity(pairs.len());
+    for pair in pairs {
+        let Some((key, value)) = pair.split_once('=') else {
+            bail!("--trace-baggage expects KEY=VALUE, got '{pair}'");
+        };
+        let key = key.trim();
+        if key.is_empty()
+            || !key
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+        {
+            bail!("--trace-baggage key '{key}' is not a valid token");
+        }
+        let mut encoded = String::with_capacity(value.len());
+        for byte in value.trim().bytes() {
+            match byte {
+                0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E if byte != b'%' => {
+                    encoded.push(char::from(byte));
+                }
+                _ => encoded.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        members.push(format!("{key}={encoded}"));
+    }
+    Ok((!members.is_empty()).then(|| members.join(",")))
+}
+
+/// The run's spans as an OTLP `ExportTraceServiceRequest` in its JSON
+/// encoding: ids in hex, 64-bit integers as strings.
+fn otlp_request(spans: &[SpanRecord]) -> Value {
+    let spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let mut value = json!({
+                "traceId": hex(&span.trace_id),
+                "spanId": hex(&span.span_id),
+                "name": span.name,
+                "kind": span.kind as i32,
+                "startTimeUnixNano": unix_nanos(span.start),
+                "endTimeUnixNano": unix_nanos(span.end),
+                "attributes": span.attributes.iter().map(|(key, value)| json!({
+                    "key": key,
+                    "value": match value {
+                        AttributeValue::String(s) => json!({ "stringValue": s }),
+                        AttributeValue::Int(n) => json!({ "intValue": n.to_string() }),
+                    },
+                })).collect::<Vec<_>>(),
+                "status": match &span.error {
+                    Some(message) => json!({ "code": 2, "message": message }),
+                    None => json!({ "code": 1 }),
+                },
+            });
+            if let Some(parent) = &span.parent_span_id {
+                value["parentSpanId"] = json!(hex(parent));
+            }
+            value
+        })
+        .collect();
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": env!("CARGO_PKG_NAME") } },
+                    { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } },
+                ],
+            },
+            "scopeSpans": [{
+                "scope": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// A random, non-zero id (all-zero trace and span ids are invalid).
+fn random_id<const N: usize>() -> [u8; N] {
+    loop {
+        let id: [u8; N] = rand::random();
+        if id.iter().any(|&b| b != 0) {
+            return id;
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+        .to_string()
+}
+
+fn to_i64(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_share_the_test_trace_and_nest_under_it() {
+        let tracer = Arc::new(
+            Tracer::new(
+                &["env=ci run".to_string()],
+                Some("https://traces.example/{trace_id}".into()),
+                None,
+            )
+            .unwrap(),
+        );
+        let test = tracer.start_test("tests/a.gctf");
+        let call = test.start_call("grpc.health.v1.Health", "Check", "localhost:1");
+
+        let mut metadata = MetadataMap::new();
+        call.inject(&mut metadata);
+        let traceparent = metadata.get("traceparent").unwrap().clone();
+        assert_eq!(metadata.get("baggage").unwrap(), "env=ci%20run");
+
+        call.record_assertions(SystemTime::now(), 11, 2, 1);
+        call.finish(Some(0), None);
+        let info = test.finish(Some("boom"));
+
+        let parts: Vec<&str> = traceparent.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[1], info.trace_id);
+        assert_eq!(
+            info.url.as_deref(),
+            Some(format!("https://traces.example/{}", info.trace_id).as_str())
+        );
+
+        let spans = tracer.spans.lock().unwrap().clone();
+        let request = otlp_request(&spans);
+        let spans = request["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(spans.len(), 3);
+        assert!(spans.iter().all(|s| s["traceId"] == info.trace_id));
+        let call_span = spans.iter().find(|s| s["kind"] == 3).unwrap();
+        assert_eq!(call_span["spanId"], parts[2]);
+        assert_eq!(call_span["name"], "grpc.health.v1.Health/Check");
+        let asserts = spans
+            .iter()
+            .find(|s| s["name"] == "ASSERTS line 11")
+            .unwrap();
+        assert_eq!(asserts["parentSpanId"], parts[2]);
+        assert_eq!(asserts["status"]["code"], 2);
+        let root = spans
+            .iter()
+            .find(|s| s.get("parentSpanId").is_none())
+            .unwrap();
+        assert_eq!(call_span["parentSpanId"], root["spanId"]);
+    }
+
+    #[test]
+    fn explicit_traceparent_header_wins() {
+        let tracer = Arc::new(Tracer::new(&[], None, None).unwrap());
+        let call = tracer.start_test("t").start_call("s", "M", "a");
+        let mut metadata = MetadataMap::new();
+        metadata.insert("traceparent", "00-custom");
+        call.inject(&mut metadata);
+        assert_eq!(metadata.get("traceparent").unwrap(), "00-custom");
+        assert!(metadata.get("baggage").is_none());
+    }
+
+    #[test]
+    fn baggage_and_endpoint_are_normalized() {
+        assert!(encode_baggage(&["novalue".into()]).is_err());
+        assert!(encode_baggage(&["bad key=v".into()]).is_err());
+        assert_eq!(
+            encode_baggage(&["a=1".into(), "b=x,y%".into()]).unwrap(),
+            Some("a=1,b=x%2Cy%25".to_string())
+        );
+        assert_eq!(
+            TraceExport::traces_url("http://localhost:4318/"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            TraceExport::traces_url("http://c/v1/traces"),
+            "http://c/v1/traces"
+        );
+    }
+}
@@ -0,0 +1,504 @@
+//! `run --watch`: which files a run depends on, and which of its tests a
+//! change to one of them affects.
+//!
+//! A [`WatchSet`] is built from the run's arguments before every cycle, so
+//! new tests and newly referenced protos are picked up as they appear:
+//!
+//! - a test file re-runs itself and the tests that `META.depends_on` it
+//! - a `_setup.gctf`/`_teardown.gctf` re-runs every test in its directory
+//! - a `.proto` re-runs the tests that list it (or an import path holding it)
+//!   in PROTO, and those whose ENDPOINT names a service it defines
+//! - a `BENCH.sources` file re-runs the tests reading it; the `--data`
+//!   source, a `.rhai` plugin or anything else in `.grpctestify/` re-runs all
+
+use anyhow::Result;
+use notify::{EventKind, RecursiveMode, Watcher as _};
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use crate::cli::args::RunArgs;
+use crate::execution::depends::{self, normalize_path};
+use crate::parser::{self, ast::GctfDocument};
+use crate::utils::FileUtils;
+
+/// How long a burst of events must stay quiet before a cycle starts — an
+/// editor's save is often a write, a rename and a chmod in quick succession.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// The absolute, `.`/`..`-free form paths are compared in: notify reports
+/// absolute paths, while test paths are usually given relative.
+pub fn path_key(path: &Path) -> PathBuf {
+    normalize_path(&std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+/// What one test file reads besides itself.
+#[derive(Debug, Default)]
+struct TestInputs {
+    file: PathBuf,
+    /// PROTO `files` and `descriptor`.
+    protos: Vec<PathBuf>,
+    /// PROTO `import_paths`.
+    import_dirs: Vec<PathBuf>,
+    /// `package.Service` of every ENDPOINT in the chain.
+    services: BTreeSet<String>,
+    /// `BENCH.sources` files.
+    sources: Vec<PathBuf>,
+    /// `META.depends_on` files.
+    depends_on: Vec<PathBuf>,
+}
+
+/// Everything a run reads, in [`path_key`] form.
+#[derive(Debug, Default)]
+pub struct WatchSet {
+    /// Directories watched with their subdirectories.
+    recursive: BTreeSet<PathBuf>,
+    /// Directories watched for their own entries only.
+    shallow: BTreeSet<PathBuf>,
+    /// The run's test paths that are directories: a new `.gctf` below one
+    /// is a new test.
+    test_dirs: Vec<PathBuf>,
+    tests: Vec<TestInputs>,
+    /// `--data`.
+    data: Option<PathBuf>,
+    /// `.rhai` plugin directories.
+    plugin_dirs: Vec<PathBuf>,
+    /// The project's `.grpctestify` directory.
+    project_dir: Option<PathBuf>,
+    /// What the run itself writes: reports, coverage, cassettes, caches.
+    outputs: Vec<PathBuf>,
+}
+
+/// What a batch of changes calls for.
+#[derive(Debug, Default, PartialEq)]
+pub struct Affected {
+    /// Test files to re-run, in [`path_key`] form.
+    pub tests: BTreeSet<PathBuf>,
+    /// Re-run the whole selection.
+    pub everything: bool,
+    /// A proto or descriptor changed: cached descriptor pools are stale.
+    pub protos: bool,
+    /// A plugin changed: the plugin registry must be rebuilt.
+    pub plugins: bool,
+}
+
+impl Affected {
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty() && !self.everything
+    }
+}
+
+impl WatchSet {
+    pub fn build(args: &RunArgs) -> Self {
+        let mut set = Self::default();
+        let mut files = Vec::new();
+        for path in &args.test_paths {
+            if path.is_dir() {
+                set.recursive.insert(path_key(path));
+                set.test_dirs.push(path_key(path));
+                files.extend(FileUtils::collect_test_files(path, &args.exclude));
+            } else {
+                set.watch_file(path);
+                files.push(path.clone());
+            }
+        }
+        for file in files {
+            let inputs = match parser::parse_gctf(&file) {
+                Ok(doc) => test_inputs(&doc, &file),
+                // Still watched: fixing the syntax error is a change too.
+                Err(_) => TestInputs {
+                    file: path_key(&file),
+                    ..TestInputs::default()
+                },
+            };
+            for path in inputs
+                .protos
+                .iter()
+                .chain(&inputs.sources)
+                .chain(&inputs.depends_on)
+            {
+                set.watch_file(path);
+            }
+            set.recursive.extend(inputs.import_dirs.iter().cloned());
+            set.tests.push(inputs);
+        }
+
+        if let Some(data) = &args.data {
+            set.watch_file(data);
+            set.data = Some(path_key(data));
+        }
+        let plugin_dirs = crate::plugins::rhai_plugin::user_plugin_dir()
+            .into_iter()
+            .chain([crate::plugins::rhai_plugin::project_plugin_dir()]);
+        for dir in plugin_dirs.filter(|d| d.is_dir()) {
+            set.recursive.insert(path_key(&dir));
+            set.plugin_dirs.push(path_key(&dir));
+        }
+        let start = crate::serve::project::env_search_start(&args.test_paths);
+        if let Some(project) = crate::serve::project::find_project_dir(&start) {
+            set.recursive.insert(path_key(&project));
+            set.outputs.push(path_key(&project.join("cache")));
+            set.project_dir = Some(path_key(&project));
+        }
+        set.outputs.extend(
+            [&args.log_output, &args.coverage_output, &args.record]
+                .into_iter()
+                .flatten()
+                .map(|p| path_key(p)),
+        );
+        set
+    }
+
+    fn watch_file(&mut self, file: &Path) {
+        if let Some(dir) = path_key(file).parent() {
+            self.shallow.insert(dir.to_path_buf());
+        }
+    }
+
+    /// Directories to watch, each with whether to descend into it.
+    pub fn dirs(&self) -> impl Iterator<Item = (&PathBuf, bool)> {
+        self.recursive
+            .iter()
+            .map(|d| (d, true))
+            .chain(self.shallow.iter().map(|d| (d, false)))
+    }
+
+    /// The tests `changed` (in [`path_key`] form) affect.
+    pub fn affected(&self, changed: &BTreeSet<PathBuf>) -> Affected {
+        let mut affected = Affected::default();
+        for path in changed {
+            if self.outputs.iter().any(|out| path.starts_with(out)) || is_scratch_file(path) {
+                continue;
+            }
+            let extension = path.extension().and_then(|e| e.to_str());
+            if self.plugin_dirs.iter().any(|dir| path.starts_with(dir)) {
+                if extension == Some("rhai") {
+                    affected.plugins = true;
+                    affected.everything = true;
+                }
+                continue;
+            }
+            if self.data.as_ref() == Some(path)
+                || self
+                    .project_dir
+                    .as_ref()
+                    .is_some_and(|dir| path.starts_with(dir))
+            {
+                affected.everything = true;
+                continue;
+            }
+            match extension {
+                Some("gctf") => self.gctf_changed(path, &mut affected.tests),
+                Some("proto") => {
+                    affected.protos = true;
+                    let services = proto_services(path);
+                    affected.tests.extend(
+                        self.tests
+                            .iter()
+                            .filter(|t| {
+                                t.protos.contains(path)
+                                    || t.import_dirs.iter().any(|d| path.starts_with(d))
+                                    || !t.services.is_disjoint(&services)
+                            })
+                            .map(|t| t.file.clone()),
+                    );
+                }
+                _ => {
+                    for test in &self.tests {
+                        if test.protos.contains(path) {
+                            affected.protos = true;
+                            affected.tests.insert(test.file.clone());
+                        }
+                        if test.sources.contains(path) {
+                            affected.tests.insert(test.file.clone());
+                        }
+                    }
+                }
+            }
+        }
+        self.add_dependents(&mut affected.tests);
+        affected
+    }
+
+    fn gctf_changed(&self, path: &Path, tests: &mut BTreeSet<PathBuf>) {
+        let dir = path.parent();
+        match path.file_name().and_then(|n| n.to_str()) {
+            Some("_setup.gctf" | "_teardown.gctf") => tests.extend(
+                self.tests
+                    .iter()
+                    .filter(|t| t.file.parent() == dir && !is_fixture(&t.file))
+                    .map(|t| t.file.clone()),
+            ),
+            _ => {
+                let known = self.tests.iter().any(|t| t.file == path);
+                let new_test = self.test_dirs.iter().any(|d| path.starts_with(d));
+                if (known || new_test) && path.is_file() {
+                    tests.insert(path.to_path_buf());
+                }
+            }
+        }
+    }
+
+    /// Extend `tests` with every test that (transitively) depends on one.
+    fn add_dependents(&self, tests: &mut BTreeSet<PathBuf>) {
+        let mut pending: Vec<PathBuf> = tests.iter().cloned().collect();
+        while let Some(file) = pending.pop() {
+            for test in &self.tests {
+                if test.depends_on.contains(&file) && tests.insert(test.file.clone()) {
+                    pending.push(test.file.clone());
+                }
+            }
+        }
+    }
+}
+
+fn is_fixture(file: &Path) -> bool {
+    matches!(
+        file.file_name().and_then(|n| n.to_str()),
+        Some("_setup.gctf" | "_teardown.gctf")
+    )
+}
+
+/// Editor swap/backup files and `write_atomic`'s temp files.
+fn is_scratch_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    name.starts_with(".grpctestify-")
+        || name.ends_with('~')
+        || name.ends_with(".swp")
+        || name.ends_with(".swx")
+}
+
+fn test_inputs(doc: &GctfDocument, file: &Path) -> TestInputs {
+    let mut inputs = TestInputs {
+        file: path_key(file),
+        depends_on: depends::dependencies_of(doc, file)
+            .iter()
+            .map(|p| path_key(p))
+            .collect(),
+        ..TestInputs::default()
+    };
+    let mut current = Some(doc);
+    while let Some(doc) = current {
+        if let Some(proto) = crate::execution::runner_helpers::build_proto_config(doc, file) {
+            let paths = proto.files.iter().chain(&proto.descriptor);
+            inputs.protos.extend(paths.map(|p| path_key(Path::new(p))));
+            inputs
+                .import_dirs
+                .extend(proto.import_paths.iter().map(|p| path_key(Path::new(p))));
+        }
+        if let Some(endpoint) = doc.get_endpoint()
+            && let Some((service, _)) = endpoint.trim().split_once('/')
+        {
+            inputs.services.insert(service.to_string());
+        }
+        if let Some(sources) = doc.bench_key_values().and_then(|b| b.get("sources"))
+            && let Ok(defs) =
+                serde_yaml_ng::from_str::<Vec<crate::bench::sources::SourceDefinition>>(sources)
+        {
+            inputs.sources.extend(
+                defs.iter()
+                    .map(|d| path_key(&FileUtils::resolve_relative_path(file, &d.file))),
+            );
+        }
+        current = doc.next_document.as_deref();
+    }
+    inputs
+}
+
+/// `package.Service` names a `.proto` file defines. A light scan, not a
+/// parse: enough to tell which ENDPOINTs a changed file can concern.
+fn proto_services(path: &Path) -> BTreeSet<String> {
+    let Ok(source) = std::fs::read_to_string(path) else {
+        return BTreeSet::new();
+    };
+    let mut package = String::new();
+    let mut services = Vec::new();
+    for line in source.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("package") => {
+                package = words
+                    .next()
+                    .unwrap_or_default()
+                    .trim_end_matches(';')
+                    .to_string();
+            }
+            Some("service") => {
+                if let Some(name) = words.next() {
+                    services.push(name.trim_end_matches('{').to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    services
+        .into_iter()
+        .map(|service| {
+            if package.is_empty() {
+                service
+            } else {
+                format!("{package}.{service}")
+            }
+        })
+        .collect()
+}
+
+/// File-system events for a [`WatchSet`]'s directories.
+pub struct Watcher {
+    inner: notify::RecommendedWatcher,
+    watched: HashSet<(PathBuf, bool)>,
+    events: mpsc::UnboundedReceiver<PathBuf>,
+}
+
+impl Watcher {
+    pub fn start(set: &WatchSet) -> Result<Self> {
+        let (tx, events) = mpsc::unbounded_channel();
+        let inner = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else {
+                return;
+            };
+            // Reads (our own runs open every test) and metadata-only changes
+            // are not edits.
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+            ) && !matches!(
+                event.kind,
+                EventKind::Modify(notify::event::ModifyKind::Metadata(_))
+            ) {
+                for path in event.paths {
+                    let _ = tx.send(path_key(&path));
+                }
+            }
+        })?;
+        let mut watcher = Self {
+            inner,
+            watched: HashSet::new(),
+            events,
+        };
+        watcher.update(set);
+        Ok(watcher)
+    }
+
+    /// Start watching the directories `set` added since the last update.
+    pub fn update(&mut self, set: &WatchSet) {
+        for (dir, recursive) in set.dirs() {
+            if !dir.is_dir() || !self.watched.insert((dir.clone(), recursive)) {
+                continue;
+            }
+            let mode = if recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            if let Err(e) = self.inner.watch(dir, mode) {
+                tracing::warn!("Cannot watch {}: {}.", dir.display(), e);
+            }
+        }
+    }
+
+    /// Number of directories being watched.
+    pub fn watched(&self) -> usize {
+        self.watched.len()
+    }
+
+    /// Wait for a change, then for the burst it starts to settle. `None` once
+    /// the watcher has stopped.
+    pub async fn next_batch(&mut self) -> Option<BTreeSet<PathBuf>> {
+        let mut batch = BTreeSet::from([self.events.recv().await?]);
+        while let Ok(Some(path)) = tokio::time::timeout(SETTLE, self.events.recv()).await {
+            batch.insert(path);
+        }
+        Some(batch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn args(paths: &[&Path]) -> RunArgs {
+        use clap::Parser;
+        let mut argv = vec!["grpctestify".to_string(), "run".to_string()];
+        argv.extend(paths.iter().map(|p| p.display().to_string()));
+        let cli = crate::cli::Cli::parse_from(argv);
+        cli.get_run_args().clone()
+    }
+
+    fn changed(paths: &[&Path]) -> BTreeSet<PathBuf> {
+        paths.iter().map(|p| path_key(p)).collect()
+    }
+
+    #[test]
+    fn changes_map_to_the_tests_they_affect() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let protos = root.join("protos/shop.proto");
+        write(
+            &protos,
+            "syntax = \"proto3\";\npackage shop; // orders\nservice Orders {\n  rpc Get (R) returns (R);\n}\n",
+        );
+        write(
+            &root.join("t/a.gctf"),
+            "--- ENDPOINT ---\nshop.Orders/Get\n\n--- REQUEST ---\n{}\n",
+        );
+        write(
+            &root.join("t/b.gctf"),
+            "--- META ---\ndepends_on: [a.gctf]\n\n--- ENDPOINT ---\nother.Users/Get\n\n--- REQUEST ---\n{}\n",
+        );
+        write(
+            &root.join("t/sub/c.gctf"),
+            "--- ENDPOINT ---\nother.Users/List\n\n--- REQUEST ---\n{}\n",
+        );
+        write(&root.join("t/sub/_setup.gctf"), "");
+        let set = WatchSet::build(&args(&[&root.join("t")]));
+
+        let affected = set.affected(&changed(&[&protos]));
+        assert!(affected.protos);
+        // a.gctf calls a service the proto defines; b.gctf depends on a.gctf.
+        assert_eq!(
+            affected.tests,
+            changed(&[&root.join("t/a.gctf"), &root.join("t/b.gctf")])
+        );
+
+        let affected = set.affected(&changed(&[&root.join("t/sub/_setup.gctf")]));
+        assert_eq!(affected.tests, changed(&[&root.join("t/sub/c.gctf")]));
+
+        write(&root.join("t/new.gctf"), "");
+        let affected = set.affected(&changed(&[&root.join("t/new.gctf")]));
+        assert_eq!(affected.tests, changed(&[&root.join("t/new.gctf")]));
+
+        let affected = set.affected(&changed(&[&root.join("t/.a.gctf.swp")]));
+        assert!(affected.is_empty());
+    }
+
+    #[test]
+    fn proto_sections_and_bench_sources_are_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(&root.join("api/v1.proto"), "syntax = \"proto3\";\n");
+        write(&root.join("rows.csv"), "id\n1\n");
+        write(
+            &root.join("t/a.gctf"),
+            "--- PROTO ---\nfiles: ../api/v1.proto\n\n--- BENCH ---\nsources:\n  - file: ../rows.csv\n\n--- ENDPOINT ---\nx.Y/Z\n\n--- REQUEST ---\n{}\n",
+        );
+        let set = WatchSet::build(&args(&[&root.join("t")]));
+        assert!(set.dirs().any(|(d, _)| d == &path_key(&root.join("api"))));
+
+        let affected = set.affected(&changed(&[&root.join("api/v1.proto")]));
+        assert_eq!(affected.tests, changed(&[&root.join("t/a.gctf")]));
+        let affected = set.affected(&changed(&[&root.join("rows.csv")]));
+        assert_eq!(affected.tests, changed(&[&root.join("t/a.gctf")]));
+        assert!(!affected.protos);
+    }
+}
@@ -30,6 +30,7 @@ impl CallClientFactory for GrpcClientFactory {
             connection_id: 0,
             protocol: WireProtocol::Grpc,
             version: env!("CARGO_PKG_VERSION").to_string(),
+            proxy: None,
         };
 
         let client = GrpcClient::new(grpc_config).await?;
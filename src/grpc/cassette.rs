@@ -0,0 +1,670 @@
+//! Record/replay cassettes: `run --record <dir>` persists every call's
+//! exchange, `run --replay <dir>` answers calls from them without touching the
+//! network.
+//!
+//! A cassette is one JSON file per test file (`<dir>/<test path>.json`). Each
+//! interaction is keyed by its document (the line the document starts on) and
+//! a hash of the endpoint plus the request messages, and holds the exchange as
+//! an ordered timeline of sent and received messages, so interleaved bidi
+//! calls replay in the order they were recorded.
+
+use anyhow::{Context, Result};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use super::{GrpcError, MetadataMap, RpcMode, StreamItem};
+use apif_grpc_transport::encoding::{base64_decode, base64_encode};
+
+/// Bumped when the file layout changes incompatibly.
+const CASSETTE_VERSION: u32 = 1;
+
+pub type ResponseStream = Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// Identifies one call site: a document of a test file and its endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallKey {
+    /// Test file path, relative to the working directory when under it.
+    pub file: String,
+    /// Line the document's first section starts on.
+    pub document: usize,
+    /// `package.Service/Method`
+    pub endpoint: String,
+}
+
+impl CallKey {
+    pub fn new(file_path: &str, document: usize, endpoint: String) -> Self {
+        let path = Path::new(file_path);
+        let file = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| path.strip_prefix(cwd).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        Self {
+            file,
+            document,
+            endpoint,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteFile {
+    version: u32,
+    interactions: Vec<Interaction>,
+}
+
+/// One recorded call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    document: usize,
+    endpoint: String,
+    /// SHA-256 of the endpoint and request messages.
+    request_hash: String,
+    /// Request metadata as sent; informational, not matched on.
+    #[serde(default, skip_serializing_if = "MetadataMap::is_empty")]
+    metadata: MetadataMap,
+    events: Vec<Event>,
+    #[serde(default, skip_serializing_if = "MetadataMap::is_empty")]
+    headers: MetadataMap,
+    #[serde(default, skip_serializing_if = "MetadataMap::is_empty")]
+    trailers: MetadataMap,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RecordedError>,
+    duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Event {
+    Send(Value),
+    Receive(Value),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedError {
+    code: u32,
+    message: String,
+    /// Binary `grpc-status-details-bin`, base64.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    details: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    metadata: BTreeMap<String, String>,
+}
+
+impl Interaction {
+    fn sends(&self) -> impl Iterator<Item = &Value> {
+        self.events.iter().filter_map(|event| match event {
+            Event::Send(value) => Some(value),
+            Event::Receive(_) => None,
+        })
+    }
+
+    /// Timeline position just past the `n`th sent message (0 = the start).
+    fn position_after_sends(&self, n: usize) -> usize {
+        if n == 0 {
+            return 0;
+        }
+        let mut seen = 0;
+        for (i, event) in self.events.iter().enumerate() {
+            if matches!(event, Event::Send(_)) {
+                seen += 1;
+                if seen == n {
+                    return i + 1;
+                }
+            }
+        }
+        self.events.len()
+    }
+}
+
+fn request_hash(endpoint: &str, requests: &[&Value]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(endpoint.as_bytes());
+    for request in requests {
+        hasher.update(b"\n");
+        hasher.update(request.to_string().as_bytes());
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The cassettes of one run, held in memory: recorded interactions until
+/// [`Cassette::save`], replayed ones loaded per test file on first use.
+pub struct Cassette {
+    mode: CassetteMode,
+    dir: PathBuf,
+    files: Mutex<HashMap<String, Vec<Interaction>>>,
+}
+
+impl Cassette {
+    pub fn record(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            dir: dir.into(),
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn replay(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        anyhow::ensure!(
+            dir.is_dir(),
+            "Cassette directory {} does not exist (record one with --record)",
+            dir.display()
+        );
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            dir,
+            files: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    /// `<dir>/<test path>.json`; components that would leave `dir` are dropped.
+    fn cassette_path(&self, file: &str) -> PathBuf {
+        let mut path = self.dir.clone();
+        for component in Path::new(file).components() {
+            if let Component::Normal(part) = component {
+                path.push(part);
+            }
+        }
+        path.as_mut_os_string().push(".json");
+        path
+    }
+
+    fn load(&self, file: &str) -> Result<Vec<Interaction>> {
+        let path = self.cassette_path(file);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let cassette: CassetteFile = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid cassette {}", path.display()))?;
+        anyhow::ensure!(
+            cassette.version == CASSETTE_VERSION,
+            "Cassette {} has version {}, expected {CASSETTE_VERSION}; re-record it with --record",
+            path.display(),
+            cassette.version
+        );
+        Ok(cassette.interactions)
+    }
+
+    /// Store a finished recording. A call recorded twice with the same
+    /// requests (retries, `@repeat`) keeps its latest exchange.
+    fn insert(&self, file: String, interaction: Interaction) {
+        let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let interactions = files.entry(file).or_default();
+        interactions.retain(|existing| {
+            existing.document != interaction.document
+                || existing.request_hash != interaction.request_hash
+        });
+        interactions.push(interaction);
+    }
+
+    /// Write every recorded test file's cassette, replacing what was there.
+    /// Returns the number of interactions written.
+    pub fn save(&self) -> Result<usize> {
+        let files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+        let mut written = 0;
+        for (file, interactions) in files.iter() {
+            let path = self.cassette_path(file);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            let mut interactions = interactions.clone();
+            interactions.sort_by_key(|i| i.document);
+            let cassette = CassetteFile {
+                version: CASSETTE_VERSION,
+                interactions,
+            };
+            std::fs::write(&path, serde_json::to_string_pretty(&cassette)? + "\n")
+                .with_context(|| format!("Failed to write cassette {}", path.display()))?;
+            written += cassette.interactions.len();
+        }
+        Ok(written)
+    }
+
+    /// Wrap a live call so its exchange is recorded as it happens. The
+    /// interaction is stored once the response stream handed to
+    /// [`Recording::wrap_responses`] is dropped; a call that never got that
+    /// far (dial or startup failure) is not recorded.
+    pub fn record_call(
+        self: &Arc<Self>,
+        key: &CallKey,
+        metadata: MetadataMap,
+        requests: impl Stream<Item = Value> + Send + 'static,
+    ) -> (Recording, Pin<Box<dyn Stream<Item = Value> + Send>>) {
+        let pending = Arc::new(Mutex::new(Some(PendingInteraction {
+            cassette: Arc::clone(self),
+            key: key.clone(),
+            metadata,
+            events: Vec::new(),
+            headers: MetadataMap::new(),
+            trailers: MetadataMap::new(),
+            error: None,
+            started: std::time::Instant::now(),
+        })));
+        let tap = Arc::clone(&pending);
+        let requests = requests.inspect(move |request| {
+            with_pending(&tap, |pending| {
+                pending.events.push(Event::Send(request.clone()))
+            });
+        });
+        let recording = Recording {
+            pending,
+            responded: false,
+        };
+        (recording, Box::pin(requests))
+    }
+
+    /// Answer a call from the cassette. Fails when no recorded interaction
+    /// of this document matches the requests.
+    ///
+    /// Unary, client- and server-streaming calls read every request before
+    /// choosing; bidi (or unknown) calls commit to the first interaction whose
+    /// recorded requests so far match once it has a response to send, since
+    /// the client may be waiting for it before sending more.
+    pub async fn replay_call(
+        &self,
+        key: &CallKey,
+        requests: impl Stream<Item = Value> + Send + 'static,
+        rpc_mode: Option<RpcMode>,
+    ) -> Result<(MetadataMap, ResponseStream)> {
+        let candidates: Vec<Interaction> = {
+            let mut files = self.files.lock().unwrap_or_else(|e| e.into_inner());
+            let interactions = match files.get(&key.file) {
+                Some(interactions) => interactions,
+                None => {
+                    let loaded = self.load(&key.file)?;
+                    files.entry(key.file.clone()).or_insert(loaded)
+                }
+            };
+            interactions
+                .iter()
+                .filter(|i| i.document == key.document && i.endpoint == key.endpoint)
+                .cloned()
+                .collect()
+        };
+        anyhow::ensure!(
+            !candidates.is_empty(),
+            "replay: no recorded call for {} at {}:{} in {}",
+            key.endpoint,
+            key.file,
+            key.document,
+            self.cassette_path(&key.file).display()
+        );
+
+        let eager = matches!(rpc_mode, None | Some(RpcMode::Bidi));
+        let mut requests: Pin<Box<dyn Stream<Item = Value> + Send>> = Box::pin(requests);
+        let mut candidates = candidates;
+        let mut received: Vec<Value> = Vec::new();
+        let mut ended = false;
+        let chosen = loop {
+            candidates.retain(|c| {
+                let sends: Vec<&Value> = c.sends().collect();
+                sends.len() >= received.len()
+                    && sends.iter().zip(&received).all(|(a, b)| *a == b)
+                    && (!ended || sends.len() == received.len())
+            });
+            let Some(first) = candidates.first() else {
+                let last = received.last().map(Value::to_string).unwrap_or_default();
+                anyhow::bail!(
+                    "replay: no recorded call for {} at {}:{} matches request #{} {last}",
+                    key.endpoint,
+                    key.file,
+                    key.document,
+                    received.len()
+                );
+            };
+            let position = first.position_after_sends(received.len());
+            let wants_request = matches!(first.events.get(position), Some(Event::Send(_)));
+            if ended || (eager && !wants_request) {
+                break candidates.swap_remove(0);
+            }
+            match requests.next().await {
+                Some(request) => received.push(request),
+                None => ended = true,
+            }
+        };
+
+        let position = chosen.position_after_sends(received.len());
+        let headers = chosen.headers.clone();
+        let responses = replay_stream(chosen, position, requests, received.len());
+        Ok((headers, responses))
+    }
+}
+
+/// The rest of a chosen interaction's timeline: recorded responses in order,
+/// checking each further request against the recording, then its trailers
+/// or error.
+fn replay_stream(
+    interaction: Interaction,
+    position: usize,
+    requests: Pin<Box<dyn Stream<Item = Value> + Send>>,
+    sent: usize,
+) -> ResponseStream {
+    struct State {
+        interaction: Interaction,
+        position: usize,
+        requests: Pin<Box<dyn Stream<Item = Value> + Send>>,
+        sent: usize,
+        done: bool,
+    }
+    let state = State {
+        interaction,
+        position,
+        requests,
+        sent,
+        done: false,
+    };
+    Box::pin(stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        loop {
+            match state.interaction.events.get(state.position).cloned() {
+                Some(Event::Receive(message)) => {
+                    state.position += 1;
+                    return Some((Ok(StreamItem::Message(message)), state));
+                }
+                Some(Event::Send(expected)) => {
+                    state.position += 1;
+                    state.sent += 1;
+                    let actual = state.requests.next().await;
+                    if actual.as_ref() != Some(&expected) {
+                        state.done = true;
+                        let actual = actual.map(|v| v.to_string());
+                        let message = format!(
+                            "replay: request #{} differs from the recording: expected {expected}, got {}",
+                            state.sent,
+                            actual.as_deref().unwrap_or("end of stream")
+                        );
+                        return Some((Err(GrpcError::new(2, message)), state));
+                    }
+                }
+                None => {
+                    state.done = true;
+                    if let Some(error) = state.interaction.error.take() {
+                        let details = base64_decode(error.details.as_bytes()).unwrap_or_default();
+                        let metadata = error.metadata.into_iter().collect();
+                        let error =
+                            GrpcError::with_metadata(error.code, error.message, details, metadata);
+                        return Some((Err(error), state));
+                    }
+                    if state.interaction.trailers.is_empty() {
+                        return None;
+                    }
+                    let trailers = std::mem::take(&mut state.interaction.trailers);
+                    return Some((Ok(StreamItem::Trailers(trailers)), state));
+                }
+            }
+        }
+    }))
+}
+
+struct PendingInteraction {
+    cassette: Arc<Cassette>,
+    key: CallKey,
+    metadata: MetadataMap,
+    events: Vec<Event>,
+    headers: MetadataMap,
+    trailers: MetadataMap,
+    error: Option<RecordedError>,
+    started: std::time::Instant,
+}
+
+type SharedPending = Arc<Mutex<Option<PendingInteraction>>>;
+
+fn with_pending(pending: &SharedPending, f: impl FnOnce(&mut PendingInteraction)) {
+    if let Some(pending) = pending.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        f(pending);
+    }
+}
+
+/// A call being recorded, stored in its cassette when dropped.
+pub struct Recording {
+    pending: SharedPending,
+    responded: bool,
+}
+
+impl Recording {
+    /// Tap the response side of the call.
+    pub fn wrap_responses(
+        mut self,
+        headers: &MetadataMap,
+        responses: ResponseStream,
+    ) -> ResponseStream {
+        self.responded = true;
+        with_pending(&self.pending, |pending| pending.headers = headers.clone());
+        Box::pin(responses.inspect(move |item| {
+            with_pending(&self.pending, |pending| match item {
+                Ok(StreamItem::Message(message)) => {
+                    pending.events.push(Event::Receive(message.clone()))
+                }
+                Ok(StreamItem::Trailers(trailers)) => pending.trailers.extend_from(trailers),
+                Err(error) => {
+                    pending.error = Some(RecordedError {
+                        code: error.code,
+                        message: error.message.clone(),
+                        details: if error.details.is_empty() {
+                            String::new()
+                        } else {
+                            base64_encode(&error.details)
+                        },
+                        metadata: error
+                            .metadata
+                            .iter()
+                            .map(|(k, v)| (k.clone(), v.clone()))
+                            .collect(),
+                    })
+                }
+            })
+        }))
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        if !self.responded {
+            return;
+        }
+        let Some(pending) = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+        else {
+            return;
+        };
+        let sends: Vec<&Value> = pending
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Send(value) => Some(value),
+                Event::Receive(_) => None,
+            })
+            .collect();
+        let request_hash = request_hash(&pending.key.endpoint, &sends);
+        let interaction = Interaction {
+            document: pending.key.document,
+            endpoint: pending.key.endpoint.clone(),
+            request_hash,
+            metadata: pending.metadata,
+            events: pending.events,
+            headers: pending.headers,
+            trailers: pending.trailers,
+            error: pending.error,
+            duration_ms: pending.started.elapsed().as_millis() as u64,
+        };
+        pending.cassette.insert(pending.key.file, interaction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> CallKey {
+        CallKey {
+            file: "tests/users.gctf".to_string(),
+            document: 1,
+            endpoint: "demo.Users/Get".to_string(),
+        }
+    }
+
+    async fn record(
+        cassette: &Arc<Cassette>,
+        requests: Vec<Value>,
+        responses: Vec<Result<StreamItem, GrpcError>>,
+    ) {
+        let (recording, requests) =
+            cassette.record_call(&key(), MetadataMap::new(), stream::iter(requests));
+        let _: Vec<Value> = requests.collect().await;
+        let responses =
+            recording.wrap_responses(&MetadataMap::new(), Box::pin(stream::iter(responses)));
+        let _: Vec<_> = responses.collect().await;
+    }
+
+    async fn replay(
+        cassette: &Cassette,
+        requests: Vec<Value>,
+        rpc_mode: Option<RpcMode>,
+    ) -> Result<Vec<Result<StreamItem, GrpcError>>> {
+        let (_, responses) = cassette
+            .replay_call(&key(), stream::iter(requests), rpc_mode)
+            .await?;
+        Ok(responses.collect().await)
+    }
+
+    #[tokio::test]
+    async fn recorded_calls_replay_by_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(Cassette::record(dir.path()));
+        record(
+            &recorder,
+            vec![serde_json::json!({"id": 1})],
+            vec![Ok(StreamItem::Message(serde_json::json!({"name": "Ada"})))],
+        )
+        .await;
+        record(
+            &recorder,
+            vec![serde_json::json!({"id": 2})],
+            vec![Err(GrpcError::new(5, "no such user"))],
+        )
+        .await;
+        assert_eq!(recorder.save().unwrap(), 2);
+        assert!(dir.path().join("tests/users.gctf.json").exists());
+
+        let player = Cassette::replay(dir.path()).unwrap();
+        let items = replay(
+            &player,
+            vec![serde_json::json!({"id": 2})],
+            Some(RpcMode::Unary),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(&items[..], [Err(e)] if e.code == 5 && e.message == "no such user"));
+
+        let items = replay(
+            &player,
+            vec![serde_json::json!({"id": 1})],
+            Some(RpcMode::Unary),
+        )
+        .await
+        .unwrap();
+        assert!(
+            matches!(&items[..], [Ok(StreamItem::Message(m))] if m == &serde_json::json!({"name": "Ada"}))
+        );
+
+        let err = replay(
+            &player,
+            vec![serde_json::json!({"id": 3})],
+            Some(RpcMode::Unary),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("matches request #1 {\"id\":3}"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn rerecording_the_same_requests_keeps_the_latest() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(Cassette::record(dir.path()));
+        for name in ["old", "new"] {
+            record(
+                &recorder,
+                vec![serde_json::json!({"id": 1})],
+                vec![Ok(StreamItem::Message(serde_json::json!({"name": name})))],
+            )
+            .await;
+        }
+        assert_eq!(recorder.save().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn bidi_replay_answers_before_the_client_finishes_sending() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = Arc::new(Cassette::record(dir.path()));
+        let (recording, requests) = recorder.record_call(
+            &key(),
+            MetadataMap::new(),
+            stream::iter(vec![serde_json::json!(1), serde_json::json!(2)]),
+        );
+        let mut requests = requests;
+        let mut responses = recording.wrap_responses(
+            &MetadataMap::new(),
+            Box::pin(stream::iter(vec![
+                Ok(StreamItem::Message(serde_json::json!("a"))),
+                Ok(StreamItem::Message(serde_json::json!("b"))),
+            ])),
+        );
+        // Recorded interleaved: 1, a, 2, b.
+        requests.next().await;
+        responses.next().await;
+        requests.next().await;
+        responses.next().await;
+        drop((requests, responses));
+        recorder.save().unwrap();
+
+        let player = Cassette::replay(dir.path()).unwrap();
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        tx.send(serde_json::json!(1)).await.unwrap();
+        let (_, mut responses) = player
+            .replay_call(
+                &key(),
+                tokio_stream::wrappers::ReceiverStream::new(rx),
+                Some(RpcMode::Bidi),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(responses.next().await, Some(Ok(StreamItem::Message(m))) if m == "a"));
+        tx.send(serde_json::json!(2)).await.unwrap();
+        assert!(matches!(responses.next().await, Some(Ok(StreamItem::Message(m))) if m == "b"));
+        drop(tx);
+        assert!(responses.next().await.is_none());
+    }
+}
@@ -1,14 +1,20 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
 use anyhow::Result;
+use apif_grpc_transport::MetadataMap;
 use apif_grpc_transport::client::GrpcClient as _;
 use futures::stream::{Stream, StreamExt};
 use prost::Message;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::Arc;
+
+use super::cassette::{CallKey, Cassette, CassetteMode};
 
 pub struct GrpcClient {
     inner: ClientInner,
+    /// `run --record`/`--replay`: the cassette this call goes through.
+    cassette: Option<CassetteCall>,
 }
 
 enum ClientInner {
@@ -17,6 +23,17 @@ enum ClientInner {
         config: apif_grpc_transport::config::GrpcClientConfig,
         pool: Option<prost_reflect::DescriptorPool>,
     },
+    /// Answered from a cassette; never dials.
+    Replay {
+        pool: Option<prost_reflect::DescriptorPool>,
+    },
+}
+
+#[derive(Clone)]
+struct CassetteCall {
+    cassette: Arc<Cassette>,
+    key: CallKey,
+    metadata: MetadataMap,
 }
 
 impl GrpcClient {
@@ -26,21 +43,51 @@ impl GrpcClient {
                 inner: ClientInner::Tonic(
                     apif_grpc_transport::tonic::client::TonicGrpcClient::new(config).await?,
                 ),
+                cassette: None,
             }),
             apif_grpc_transport::config::WireProtocol::GrpcWeb
             | apif_grpc_transport::config::WireProtocol::ConnectRpc => {
                 let pool = build_pool_from_config(&config);
                 Ok(Self {
                     inner: ClientInner::Http { config, pool },
+                    cassette: None,
                 })
             }
         }
     }
 
+    /// A client whose calls are recorded into, or replayed from, `cassette`
+    /// under `key`. Replaying never dials: the descriptor pool comes from the
+    /// config's descriptor set, if any.
+    pub async fn with_cassette(
+        config: apif_grpc_transport::config::GrpcClientConfig,
+        cassette: Arc<Cassette>,
+        key: CallKey,
+    ) -> Result<Self> {
+        let metadata = config.metadata.clone().unwrap_or_default();
+        let mut client = match cassette.mode() {
+            CassetteMode::Record => Self::new(config).await?,
+            CassetteMode::Replay => Self {
+                inner: ClientInner::Replay {
+                    pool: build_pool_from_config(&config),
+                },
+                cassette: None,
+            },
+        };
+        client.cassette = Some(CassetteCall {
+            cassette,
+            key,
+            metadata,
+        });
+        Ok(client)
+    }
+
     pub fn descriptor_pool(&self) -> &prost_reflect::DescriptorPool {
         match &self.inner {
             ClientInner::Tonic(c) => c.descriptor_pool(),
-            ClientInner::Http { pool, .. } => pool.as_ref().unwrap_or(&EMPTY_POOL),
+            ClientInner::Http { pool, .. } | ClientInner::Replay { pool } => {
+                pool.as_ref().unwrap_or(&EMPTY_POOL)
+            }
         }
     }
 
@@ -50,7 +97,7 @@ impl GrpcClient {
                 let pool = c.descriptor_pool();
                 Self::describe_pool(pool, symbol)
             }
-            ClientInner::Http { .. } => {
+            ClientInner::Http { .. } | ClientInner::Replay { .. } => {
                 anyhow::bail!("describe is not supported for HTTP transport")
             }
         }
@@ -109,7 +156,41 @@ impl GrpcClient {
         requests: impl Stream<Item = Value> + Send + 'static,
         rpc_mode: Option<RpcMode>,
     ) -> Result<(
-        HashMap<String, String>,
+        MetadataMap,
+        Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send + 'static>>,
+    )> {
+        let Some(call) = self.cassette.clone() else {
+            return self
+                .call_live(service_name, method_name, requests, rpc_mode)
+                .await;
+        };
+        match call.cassette.mode() {
+            CassetteMode::Replay => {
+                call.cassette
+                    .replay_call(&call.key, requests, rpc_mode)
+                    .await
+            }
+            CassetteMode::Record => {
+                let (recording, requests) =
+                    call.cassette
+                        .record_call(&call.key, call.metadata, requests);
+                let (headers, responses) = self
+                    .call_live(service_name, method_name, requests, rpc_mode)
+                    .await?;
+                let responses = recording.wrap_responses(&headers, responses);
+                Ok((headers, responses))
+            }
+        }
+    }
+
+    async fn call_live(
+        &mut self,
+        service_name: &str,
+        method_name: &str,
+        requests: impl Stream<Item = Value> + Send + 'static,
+        rpc_mode: Option<RpcMode>,
+    ) -> Result<(
+        MetadataMap,
         Pin<Box<dyn Stream<Item = Result<StreamItem, GrpcError>> + Send + 'static>>,
     )> {
         match &mut self.inner {
@@ -133,7 +214,7 @@ impl GrpcClient {
                 let request_body = if needs_collect {
                     let all: Vec<Value> = requests.collect().await;
                     if all.is_empty() {
-                        return Ok((HashMap::new(), Box::pin(stream::iter(vec![]))));
+                        return Ok((MetadataMap::new(), Box::pin(stream::iter(vec![]))));
                     }
                     if all.len() == 1 {
                         all.into_iter().next().unwrap()
@@ -183,7 +264,7 @@ impl GrpcClient {
                                 messages,
                                 trailers,
                                 error,
-                                HashMap::new(),
+                                MetadataMap::new(),
                             ))),
                         ));
                     }
@@ -205,6 +286,9 @@ impl GrpcClient {
                 let items = Self::convert_result(messages, trailers, error, headers.clone());
                 Ok((headers, Box::pin(stream::iter(items))))
             }
+            ClientInner::Replay { .. } => {
+                anyhow::bail!("a replaying client makes no live calls")
+            }
         }
     }
 
@@ -219,7 +303,7 @@ impl GrpcClient {
             .call_stream(service_name, method_name, stream, None)
             .await?;
         let mut messages = Vec::new();
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         while let Some(item) = stream.next().await {
             match item.map_err(|e| anyhow::anyhow!("gRPC error: {} {}", e.code, e.message))? {
                 StreamItem::Message(m) => messages.push(m),
@@ -241,15 +325,15 @@ impl GrpcClient {
     /// override trailer entries), preserving the prior StreamItem error shape.
     fn convert_result(
         messages: Vec<Value>,
-        trailers: HashMap<String, String>,
+        trailers: MetadataMap,
         error: Option<GrpcError>,
-        headers: HashMap<String, String>,
+        headers: MetadataMap,
     ) -> Vec<Result<StreamItem, GrpcError>> {
         let mut items: Vec<Result<StreamItem, GrpcError>> = Vec::new();
 
         if let Some(err) = error {
-            let mut err_trailers = trailers;
-            for (k, v) in &headers {
+            let mut err_trailers = HashMap::new();
+            for (k, v) in trailers.iter().chain(headers.iter()) {
                 err_trailers.entry(k.clone()).or_insert_with(|| v.clone());
             }
             items.push(Err(GrpcError::with_metadata(
@@ -277,10 +361,10 @@ mod convert_result_tests {
 
     #[test]
     fn structured_error_passes_through_with_trailers_folded() {
-        let mut trailers = HashMap::new();
-        trailers.insert("x-trace".to_string(), "t-1".to_string());
-        let mut headers = HashMap::new();
-        headers.insert("x-h".to_string(), "hv".to_string());
+        let mut trailers = MetadataMap::new();
+        trailers.append("x-trace", "t-1");
+        let mut headers = MetadataMap::new();
+        headers.append("x-h", "hv");
 
         // A message that itself contains the old formatting markers must survive
         // verbatim — the exact bug the deleted string parser had.
@@ -299,13 +383,13 @@ mod convert_result_tests {
 
     #[test]
     fn no_error_emits_messages_then_trailers() {
-        let mut trailers = HashMap::new();
-        trailers.insert("grpc-status".to_string(), "0".to_string());
+        let mut trailers = MetadataMap::new();
+        trailers.append("grpc-status", "0");
         let items = GrpcClient::convert_result(
             vec![Value::String("m".into())],
             trailers,
             None,
-            HashMap::new(),
+            MetadataMap::new(),
         );
         assert_eq!(items.len(), 2);
         assert!(matches!(items[0], Ok(StreamItem::Message(_))));
@@ -315,9 +399,9 @@ mod convert_result_tests {
 
 #[derive(Debug, Clone)]
 pub struct TestResponse {
-    pub headers: HashMap<String, String>,
+    pub headers: MetadataMap,
     pub messages: Vec<Value>,
-    pub trailers: HashMap<String, String>,
+    pub trailers: MetadataMap,
 }
 
 fn build_pool_from_config(config: &GrpcClientConfig) -> Option<prost_reflect::DescriptorPool> {
@@ -1,3 +1,4 @@
+use crate::grpc::MetadataMap;
 use anyhow::Result;
 use serde_json::Value;
 use std::collections::HashMap;
@@ -7,7 +8,7 @@ pub struct ParsedGrpcurl {
     pub address: String,
     pub symbol: String,
     pub request_body: Value,
-    pub headers: HashMap<String, String>,
+    pub headers: MetadataMap,
     pub tls: HashMap<String, String>,
     pub options: HashMap<String, String>,
     pub proto: HashMap<String, String>,
@@ -15,7 +16,7 @@ pub struct ParsedGrpcurl {
 
 impl ParsedGrpcurl {
     pub fn parse(args: &[String]) -> Result<Self> {
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         let mut tls = HashMap::new();
         let mut options = HashMap::new();
         let mut proto = HashMap::new();
@@ -42,7 +43,7 @@ impl ParsedGrpcurl {
                 "-H" | "-rpc-header" | "-reflect-header" => {
                     let value = next_value(args, i, token)?;
                     let (k, v) = parse_header(value)?;
-                    headers.insert(k, v);
+                    headers.append(k, v);
                     i += 1;
                 }
                 "-d" => {
@@ -1,22 +1,23 @@
 pub mod adapter;
+pub mod cassette;
 pub mod client;
 pub mod grpcurl_invocation;
 pub mod proxy;
 pub mod web;
 
+pub use apif_grpc_transport::MetadataMap;
 pub use apif_grpc_transport::config::{
     CompressionMode, GrpcClientConfig, ProtoConfig, TlsConfig, WireProtocol,
 };
 pub use apif_grpc_transport::error::GrpcError;
 pub use apif_grpc_transport::tonic::client::TonicGrpcClient;
-pub use apif_grpc_transport::transport::{TransportResult, default_address_for};
+pub use apif_grpc_transport::transport::{TransportResult, default_address_for, unix_socket_path};
 pub use apif_grpc_transport::types::{EndpointMeta, GrpcResponse, MethodInfo, RpcMode, StreamItem};
 pub use client::GrpcClient;
 
 use anyhow::Result;
 use apif_grpc_transport::client::GrpcClient as GrpcClientTrait;
 use serde_json::Value;
-use std::collections::HashMap;
 
 pub enum TransportRef {
     Tonic(Box<dyn GrpcClientTrait>),
@@ -50,8 +51,8 @@ impl TransportRef {
                     Ok(resp) => resp.into(),
                     Err(e) => TransportResult {
                         messages: vec![],
-                        headers: HashMap::new(),
-                        trailers: HashMap::new(),
+                        headers: MetadataMap::new(),
+                        trailers: MetadataMap::new(),
                         // Transport-level failure with no gRPC status of its own
                         // (connect refused, non-JSON HTTP error, …) → UNKNOWN(2),
                         // matching the code the old string parser produced.
@@ -86,14 +87,14 @@ async fn execute_tonic(
         Err(e) => {
             return TransportResult {
                 messages: vec![],
-                headers: HashMap::new(),
-                trailers: HashMap::new(),
+                headers: MetadataMap::new(),
+                trailers: MetadataMap::new(),
                 error: Some(e),
             };
         }
     };
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error: Option<GrpcError> = None;
     use futures::StreamExt;
     while let Some(item) = response_stream.next().await {
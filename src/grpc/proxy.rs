@@ -1,70 +1,9 @@
-// Proxy environment variable detection.
-//
-// gRPC transport (tonic/hyper 1.x) does not support HTTP CONNECT proxies natively.
-// This module reads the conventional proxy env vars and surfaces them for logging
-// so operators are not surprised when proxy settings appear to be ignored.
+// Proxy environment handling, shared with the transport crate: native gRPC
+// channels tunnel through HTTP CONNECT proxies (see
+// `apif_grpc_transport::tonic::proxy`), and the reqwest-based gRPC-Web/Connect
+// transports honour the same variables and overrides.
 
-use std::env;
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ProxyEnv {
-    pub http_proxy: Option<String>,
-    pub https_proxy: Option<String>,
-    pub no_proxy: Option<String>,
-}
-
-impl ProxyEnv {
-    /// Read proxy settings from the environment (lowercase wins over uppercase).
-    pub fn from_env() -> Self {
-        Self {
-            http_proxy: read_proxy_var("http_proxy", "HTTP_PROXY"),
-            https_proxy: read_proxy_var("https_proxy", "HTTPS_PROXY"),
-            no_proxy: read_proxy_var("no_proxy", "NO_PROXY"),
-        }
-    }
-
-    /// Build from explicit values (used in tests and for dependency injection).
-    pub fn new(
-        http_proxy: Option<String>,
-        https_proxy: Option<String>,
-        no_proxy: Option<String>,
-    ) -> Self {
-        Self {
-            http_proxy,
-            https_proxy,
-            no_proxy,
-        }
-    }
-
-    /// Returns true if any proxy variable is set.
-    pub fn any_set(&self) -> bool {
-        self.http_proxy.is_some() || self.https_proxy.is_some() || self.no_proxy.is_some()
-    }
-
-    /// Emit tracing warnings when proxy vars that will be ignored are detected.
-    /// NO_PROXY is intentionally excluded — it is informational only.
-    pub fn warn_if_set(&self) {
-        if let Some(v) = &self.http_proxy {
-            tracing::warn!(
-                "HTTP_PROXY={} is set but gRPC transport does not support HTTP CONNECT proxies; \
-                 the variable will be ignored.",
-                v
-            );
-        }
-        if let Some(v) = &self.https_proxy {
-            tracing::warn!(
-                "HTTPS_PROXY={} is set but gRPC transport does not support HTTP CONNECT proxies; \
-                 the variable will be ignored.",
-                v
-            );
-        }
-    }
-}
-
-/// Read a proxy variable: lowercase takes priority over uppercase.
-fn read_proxy_var(lower: &str, upper: &str) -> Option<String> {
-    env::var(lower).ok().or_else(|| env::var(upper).ok())
-}
+pub use apif_grpc_transport::tonic::proxy::{NO_PROXY_OVERRIDE, ProxyEnv, ProxyUrl};
 
 #[cfg(test)]
 mod tests {
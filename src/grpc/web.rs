@@ -1,22 +1,46 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
 use anyhow::{Context, Result, anyhow};
+use apif_grpc_transport::MetadataMap;
+use apif_grpc_transport::encoding::{base64_decode, base64_decode_lenient, base64_encode};
 use prost::Message;
 use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, SerializeOptions};
 use serde_json::Value;
 use std::collections::HashMap;
 
-type ResponseHeaders = HashMap<String, String>;
+type ResponseHeaders = MetadataMap;
 
 fn extract_headers(headers: &reqwest::header::HeaderMap) -> ResponseHeaders {
-    let mut map = HashMap::new();
+    let mut map = MetadataMap::with_capacity(headers.len());
     for (k, v) in headers {
         if let Ok(val) = v.to_str() {
-            map.insert(k.as_str().to_ascii_lowercase(), val.to_string());
+            let key = k.as_str().to_ascii_lowercase();
+            let val = canonical_metadata_value(&key, val);
+            map.append(key, val);
         }
     }
     map
 }
 
+/// Binary (`-bin`) metadata arrives as base64 that may be unpadded; re-encode
+/// it padded so HTTP and native gRPC responses compare equal in assertions.
+/// Other values (and undecodable binary ones) pass through unchanged.
+fn canonical_metadata_value(key: &str, value: &str) -> String {
+    if MetadataMap::is_binary_key(key)
+        && let Some(bytes) = base64_decode_lenient(value.as_bytes())
+    {
+        return base64_encode(&bytes);
+    }
+    value.to_string()
+}
+
+/// Wire form of a request `-bin` value: the test file holds base64 (padded or
+/// not); gRPC asks senders to emit it unpadded.
+fn wire_binary_metadata_value(key: &str, value: &str) -> Result<String> {
+    let bytes = base64_decode_lenient(value.as_bytes())
+        .ok_or_else(|| anyhow!("binary metadata '{key}' is not valid base64: {value:?}"))?;
+    Ok(base64_encode(&bytes).trim_end_matches('=').to_string())
+}
+
 /// Response metadata a `.gctf` test can assert on via `@header(...)`: everything
 /// except the framing-level `grpc-*` status headers and HTTP content headers.
 fn public_response_headers(headers: ResponseHeaders) -> ResponseHeaders {
@@ -45,100 +69,6 @@ fn decode_grpc_web_body(body: Vec<u8>, headers: &ResponseHeaders) -> Vec<u8> {
     }
 }
 
-/// Decode a standard-alphabet base64 body, ignoring ASCII whitespace/newlines.
-/// gRPC-Web text encodes the whole response stream as one base64 blob; returns
-/// `None` on any invalid input so the caller can fall back to the raw bytes.
-fn base64_decode(input: &[u8]) -> Option<Vec<u8>> {
-    fn val(b: u8) -> Option<u8> {
-        match b {
-            b'A'..=b'Z' => Some(b - b'A'),
-            b'a'..=b'z' => Some(b - b'a' + 26),
-            b'0'..=b'9' => Some(b - b'0' + 52),
-            b'+' => Some(62),
-            b'/' => Some(63),
-            _ => None,
-        }
-    }
-    let mut out = Vec::with_capacity(input.len() / 4 * 3);
-    let mut quad = [0u8; 4];
-    let mut n = 0;
-    let mut pads = 0;
-    for &b in input {
-        if b.is_ascii_whitespace() {
-            continue;
-        }
-        if b == b'=' {
-            quad[n] = 0;
-            pads += 1;
-            n += 1;
-        } else {
-            if pads != 0 {
-                return None; // data after padding
-            }
-            quad[n] = val(b)?;
-            n += 1;
-        }
-        if n == 4 {
-            out.push((quad[0] << 2) | (quad[1] >> 4));
-            if pads < 2 {
-                out.push((quad[1] << 4) | (quad[2] >> 2));
-            }
-            if pads < 1 {
-                out.push((quad[2] << 6) | quad[3]);
-            }
-            n = 0;
-            pads = 0;
-        }
-    }
-    if n != 0 {
-        return None; // truncated (non-multiple-of-4) input
-    }
-    Some(out)
-}
-
-/// Standard-alphabet base64 encoder (with `=` padding). Used to emit
-/// gRPC-Web text (base64) request bodies.
-fn base64_encode(input: &[u8]) -> String {
-    const A: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
-    for chunk in input.chunks(3) {
-        let b0 = chunk[0] as usize;
-        let b1 = chunk.get(1).copied().unwrap_or(0) as usize;
-        let b2 = chunk.get(2).copied().unwrap_or(0) as usize;
-        out.push(A[b0 >> 2] as char);
-        out.push(A[((b0 & 0x03) << 4) | (b1 >> 4)] as char);
-        out.push(if chunk.len() > 1 {
-            A[((b1 & 0x0f) << 2) | (b2 >> 6)] as char
-        } else {
-            '='
-        });
-        out.push(if chunk.len() > 2 {
-            A[b2 & 0x3f] as char
-        } else {
-            '='
-        });
-    }
-    out
-}
-
-/// Decode base64 that MAY omit padding: gRPC binary metadata (`-bin` headers
-/// such as `grpc-status-details-bin`) is base64 and frequently sent unpadded.
-/// Whitespace is ignored; the tail is re-padded so the strict [`base64_decode`]
-/// does the actual decoding.
-fn base64_decode_lenient(input: &[u8]) -> Option<Vec<u8>> {
-    let mut cleaned: Vec<u8> = input
-        .iter()
-        .copied()
-        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
-        .collect();
-    match cleaned.len() % 4 {
-        0 => {}
-        1 => return None, // invalid base64 length
-        r => cleaned.resize(cleaned.len() + (4 - r), b'='),
-    }
-    base64_decode(&cleaned)
-}
-
 /// gzip (RFC 1952) a message payload — the on-wire encoding gRPC calls `gzip`.
 fn gzip_compress(data: &[u8]) -> Result<Vec<u8>> {
     use flate2::{Compression, write::GzEncoder};
@@ -293,8 +223,8 @@ pub async fn execute_web_with_mode(
 #[derive(Debug, Default)]
 pub struct WebResponse {
     pub messages: Vec<Value>,
-    pub headers: HashMap<String, String>,
-    pub trailers: HashMap<String, String>,
+    pub headers: MetadataMap,
+    pub trailers: MetadataMap,
     pub error: Option<GrpcError>,
 }
 
@@ -303,7 +233,7 @@ pub struct WebResponse {
 /// numeric token is parsed directly; an unrecognized token is UNKNOWN(2).
 fn grpc_code_from_token(token: &str) -> u32 {
     token.parse::<u32>().unwrap_or(match token {
-        "cancelled" => 1,
+        "cancelled" | "canceled" => 1,
         "unknown" => 2,
         "invalid_argument" => 3,
         "deadline_exceeded" => 4,
@@ -515,13 +445,13 @@ async fn connect_rpc_unary_json(
     // if the server treated our unframed request as streaming.
     match serde_json::from_slice::<Value>(&response_bytes) {
         Ok(v) => {
-            let trailers = HashMap::new();
+            let trailers = MetadataMap::new();
             let mut error = None;
             if let Some(grpc_status) = headers.get("grpc-status").filter(|s| *s != "0") {
                 let msg = headers.get("grpc-message").cloned().unwrap_or_default();
                 error = Some(trailer_status_error(grpc_status, msg));
             }
-            let response_headers: HashMap<String, String> = headers
+            let response_headers: MetadataMap = headers
                 .into_iter()
                 .filter(|(k, _)| {
                     !k.starts_with("grpc-") && *k != "content-type" && *k != "content-length"
@@ -541,7 +471,7 @@ async fn connect_rpc_unary_json(
             if !messages.is_empty() || error.is_some() {
                 return Ok(WebResponse {
                     messages,
-                    headers: HashMap::new(),
+                    headers: MetadataMap::new(),
                     trailers,
                     error,
                 });
@@ -593,7 +523,7 @@ async fn connect_rpc_unary_proto(
         .with_context(|| "Failed to decode protobuf response")?;
     let result = dynamic_message_to_json(&msg);
 
-    let trailers = HashMap::new();
+    let trailers = MetadataMap::new();
     let mut error = None;
     if let Some(grpc_status) = headers.get("grpc-status").filter(|s| *s != "0") {
         let msg = headers.get("grpc-message").cloned().unwrap_or_default();
@@ -826,7 +756,7 @@ async fn parse_grpc_web_response<S>(
     body_stream: &mut S,
     output_desc: Option<&MessageDescriptor>,
     headers: &ResponseHeaders,
-) -> Result<(Vec<Value>, HashMap<String, String>, Option<GrpcError>)>
+) -> Result<(Vec<Value>, MetadataMap, Option<GrpcError>)>
 where
     S: Stream<Item = Result<Vec<u8>>> + Unpin,
 {
@@ -935,15 +865,15 @@ pub(crate) fn encode_multi_request_grpc_web(requests: &[Value]) -> Vec<u8> {
 pub(crate) fn parse_connect_framed_public(
     data: &[u8],
     output_desc: Option<&prost_reflect::MessageDescriptor>,
-    headers: &HashMap<String, String>,
-) -> (Vec<Value>, HashMap<String, String>, Option<GrpcError>) {
+    headers: &MetadataMap,
+) -> (Vec<Value>, MetadataMap, Option<GrpcError>) {
     parse_connect_framed(data, output_desc, headers)
 }
 
 /// Public wrapper for parse_grpc_web_framed_json so client.rs can use it.
 pub(crate) fn parse_grpc_web_framed_json_public(
     data: &[u8],
-) -> (Vec<Value>, HashMap<String, String>, Option<GrpcError>) {
+) -> (Vec<Value>, MetadataMap, Option<GrpcError>) {
     parse_grpc_web_framed_json(data)
 }
 
@@ -997,19 +927,24 @@ fn effective_request_timeout_secs(configured: u64) -> u64 {
 }
 
 /// Cache key for a built reqwest client: only the fields that influence the
-/// client build (TLS material + request timeout). `reqwest::Client` owns a
+/// client build (TLS material, request timeout, Unix socket, explicit proxy).
+/// `reqwest::Client` owns a
 /// connection pool, so reusing one instance across requests enables keep-alive
 /// and avoids re-reading CA/cert/key PEM from disk on every call.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct HttpClientCacheKey {
     timeout_seconds: u64,
     tls_config: Option<TlsConfig>,
+    unix_socket: Option<std::path::PathBuf>,
+    proxy: Option<String>,
 }
 
 fn http_client_cache_key(config: &GrpcClientConfig) -> HttpClientCacheKey {
     HttpClientCacheKey {
         timeout_seconds: config.timeout_seconds,
         tls_config: config.tls_config.clone(),
+        unix_socket: super::unix_socket_path(&config.address),
+        proxy: config.proxy.clone(),
     }
 }
 
@@ -1052,6 +987,33 @@ pub(crate) fn build_http_client(config: &GrpcClientConfig) -> Result<reqwest::Cl
         .connect_timeout(std::time::Duration::from_secs(5))
         .user_agent(&user_agent);
 
+    if let Some(path) = super::unix_socket_path(&config.address) {
+        #[cfg(unix)]
+        {
+            req_builder = req_builder.unix_socket(path);
+        }
+        #[cfg(not(unix))]
+        return Err(anyhow!(
+            "Unix domain socket address '{}' is not supported on this platform",
+            path.display()
+        ));
+    }
+
+    // reqwest already follows HTTP(S)_PROXY/NO_PROXY; an explicit override
+    // replaces that, with NO_PROXY still applied as for native gRPC.
+    match config.proxy.as_deref().map(str::trim) {
+        Some(p) if p.eq_ignore_ascii_case(super::proxy::NO_PROXY_OVERRIDE) => {
+            req_builder = req_builder.no_proxy();
+        }
+        Some(p) => {
+            let proxy = reqwest::Proxy::all(p)
+                .context("Invalid proxy URL")?
+                .no_proxy(reqwest::NoProxy::from_env());
+            req_builder = req_builder.proxy(proxy);
+        }
+        None => {}
+    }
+
     if let Some(ref tls) = config.tls_config {
         if tls.insecure_skip_verify {
             req_builder = req_builder.danger_accept_invalid_certs(true);
@@ -1104,7 +1066,8 @@ pub(crate) fn build_http_client(config: &GrpcClientConfig) -> Result<reqwest::Cl
 }
 
 /// Resolve the POST URL for a service/method, honoring an explicit scheme in
-/// `config.address` and defaulting to https when TLS is configured.
+/// `config.address` and defaulting to https when TLS is configured. A `unix:`
+/// target gets a `localhost` URL; the client itself dials the socket.
 fn request_url(config: &GrpcClientConfig, service_name: &str, method_name: &str) -> String {
     let path = format!("/{}/{}", service_name, method_name);
     let scheme = if config.tls_config.is_some() {
@@ -1112,7 +1075,9 @@ fn request_url(config: &GrpcClientConfig, service_name: &str, method_name: &str)
     } else {
         "http"
     };
-    if config.address.starts_with("http://") || config.address.starts_with("https://") {
+    if super::unix_socket_path(&config.address).is_some() {
+        format!("{}://localhost{}", scheme, path)
+    } else if config.address.starts_with("http://") || config.address.starts_with("https://") {
         format!("{}{}", config.address, path)
     } else {
         format!("{}://{}{}", scheme, config.address, path)
@@ -1143,7 +1108,12 @@ fn build_post_request(
             if k.eq_ignore_ascii_case("user-agent") || k.eq_ignore_ascii_case(GRPC_WEB_TEXT_FLAG) {
                 continue;
             }
-            http_req = http_req.header(k.as_str(), v.as_str());
+            // `header` appends, so a repeated key goes out as repeated headers.
+            if MetadataMap::is_binary_key(k) {
+                http_req = http_req.header(k.as_str(), wire_binary_metadata_value(k, v)?);
+            } else {
+                http_req = http_req.header(k.as_str(), v.as_str());
+            }
         }
     }
     Ok(http_req)
@@ -1277,7 +1247,7 @@ fn hex_val(b: u8) -> Option<u8> {
 
 fn parse_grpc_web_trailers(
     payload: &[u8],
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     let text = String::from_utf8_lossy(payload);
@@ -1285,7 +1255,9 @@ fn parse_grpc_web_trailers(
         // gRPC-Web / tonic-web emit `grpc-status:0` with NO space after the colon;
         // split on the first ':' and trim so both `key:value` and `key: value` work.
         if let Some((k, v)) = line.split_once(':') {
-            trailers.insert(k.trim().to_ascii_lowercase(), percent_decode(v.trim()));
+            let key = k.trim().to_ascii_lowercase();
+            let val = canonical_metadata_value(&key, &percent_decode(v.trim()));
+            trailers.append(key, val);
         }
     }
     if let Some(status) = trailers.get("grpc-status").filter(|s| *s != "0") {
@@ -1313,7 +1285,7 @@ fn parse_grpc_web_trailers(
 /// In-body trailers win: if the body already produced a `grpc-status`, do nothing.
 fn apply_grpc_web_header_trailers(
     headers: &ResponseHeaders,
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     if trailers.contains_key("grpc-status") {
@@ -1366,7 +1338,7 @@ fn push_grpc_web_frame_json(
     flags: u8,
     raw: &[u8],
     messages: &mut Vec<Value>,
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     if flags & 0x80 != 0 {
@@ -1384,7 +1356,7 @@ fn push_grpc_web_frame_proto(
     raw: &[u8],
     output_desc: &MessageDescriptor,
     messages: &mut Vec<Value>,
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     if flags & 0x80 != 0 {
@@ -1396,11 +1368,9 @@ fn push_grpc_web_frame_proto(
     }
 }
 
-fn parse_grpc_web_framed_json(
-    data: &[u8],
-) -> (Vec<Value>, HashMap<String, String>, Option<GrpcError>) {
+fn parse_grpc_web_framed_json(data: &[u8]) -> (Vec<Value>, MetadataMap, Option<GrpcError>) {
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error = None;
     let mut offset = 0;
 
@@ -1415,9 +1385,9 @@ fn parse_grpc_web_framed_json(
 fn parse_grpc_web_framed_proto(
     data: &[u8],
     output_desc: &MessageDescriptor,
-) -> (Vec<Value>, HashMap<String, String>, Option<GrpcError>) {
+) -> (Vec<Value>, MetadataMap, Option<GrpcError>) {
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error = None;
     let mut offset = 0;
 
@@ -1451,13 +1421,13 @@ fn parse_grpc_web_framed_proto(
 async fn parse_grpc_web_stream<S>(
     mut chunks: S,
     output_desc: Option<&MessageDescriptor>,
-) -> Result<(Vec<Value>, HashMap<String, String>, Option<GrpcError>)>
+) -> Result<(Vec<Value>, MetadataMap, Option<GrpcError>)>
 where
     S: Stream<Item = Result<Vec<u8>>> + Unpin,
 {
     let mut decoder = FrameDecoder::new();
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error = None;
 
     while let Some(chunk) = chunks.next().await {
@@ -1589,7 +1559,7 @@ fn encode_connect_envelope_compressed(
 /// fallback — is carried as a structured [`GrpcError`].
 fn parse_connect_end_stream(
     payload: &[u8],
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     let Ok(v) = serde_json::from_slice::<Value>(payload) else {
@@ -1598,20 +1568,18 @@ fn parse_connect_end_stream(
 
     if let Some(meta) = v.get("metadata").and_then(|m| m.as_object()) {
         for (k, val) in meta {
-            let joined = match val {
-                Value::Array(a) => a
-                    .iter()
-                    .map(|x| {
-                        x.as_str()
-                            .map(str::to_string)
-                            .unwrap_or_else(|| x.to_string())
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", "),
-                Value::String(s) => s.clone(),
-                other => other.to_string(),
+            let key = k.to_ascii_lowercase();
+            let values = match val {
+                Value::Array(a) => a.clone(),
+                other => vec![other.clone()],
             };
-            trailers.insert(k.to_ascii_lowercase(), joined);
+            for x in values {
+                let x = x
+                    .as_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| x.to_string());
+                trailers.append(key.clone(), canonical_metadata_value(&key, &x));
+            }
         }
     }
 
@@ -1631,9 +1599,9 @@ fn push_connect_frame(
     flags: u8,
     payload: &[u8],
     output_desc: Option<&MessageDescriptor>,
-    headers: &HashMap<String, String>,
+    headers: &MetadataMap,
     messages: &mut Vec<Value>,
-    trailers: &mut HashMap<String, String>,
+    trailers: &mut MetadataMap,
     error: &mut Option<GrpcError>,
 ) {
     let is_end_stream = flags & 0x02 != 0;
@@ -1684,10 +1652,10 @@ fn connect_unframed_fallback(
 fn parse_connect_framed(
     data: &[u8],
     output_desc: Option<&MessageDescriptor>,
-    headers: &HashMap<String, String>,
-) -> (Vec<Value>, HashMap<String, String>, Option<GrpcError>) {
+    headers: &MetadataMap,
+) -> (Vec<Value>, MetadataMap, Option<GrpcError>) {
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error = None;
     let mut decoder = FrameDecoder::new();
     decoder.extend(data);
@@ -1717,14 +1685,14 @@ fn parse_connect_framed(
 async fn parse_connect_stream<S>(
     mut chunks: S,
     output_desc: Option<&MessageDescriptor>,
-    headers: &HashMap<String, String>,
-) -> Result<(Vec<Value>, HashMap<String, String>, Option<GrpcError>)>
+    headers: &MetadataMap,
+) -> Result<(Vec<Value>, MetadataMap, Option<GrpcError>)>
 where
     S: Stream<Item = Result<Vec<u8>>> + Unpin,
 {
     let mut decoder = FrameDecoder::new();
     let mut messages = Vec::new();
-    let mut trailers = HashMap::new();
+    let mut trailers = MetadataMap::new();
     let mut error = None;
 
     while let Some(chunk) = chunks.next().await {
@@ -1771,7 +1739,7 @@ mod tests {
     #[test]
     fn parse_grpc_web_trailers_handles_no_space_after_colon() {
         // Real tonic-web / gRPC-Web emit `grpc-status:0` with NO space after ':'.
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         parse_grpc_web_trailers(
             b"grpc-status:0\r\ngrpc-message:ok\r\n",
@@ -1783,7 +1751,7 @@ mod tests {
         assert!(error.is_none(), "status 0 is not an error");
 
         // A non-zero no-space status trailer must still build the error.
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         parse_grpc_web_trailers(
             b"grpc-status:5\r\ngrpc-message:boom\r\n",
@@ -1807,7 +1775,7 @@ mod tests {
             "CAUSBGJvb20".to_string(),
         );
 
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         apply_grpc_web_header_trailers(&headers, &mut trailers, &mut error);
 
@@ -1832,7 +1800,7 @@ mod tests {
         let mut headers = ResponseHeaders::new();
         headers.insert("grpc-status".to_string(), "5".to_string());
 
-        let mut trailers = HashMap::from([("grpc-status".to_string(), "0".to_string())]);
+        let mut trailers = MetadataMap::from([("grpc-status".to_string(), "0".to_string())]);
         let mut error = None;
         apply_grpc_web_header_trailers(&headers, &mut trailers, &mut error);
 
@@ -2008,6 +1976,23 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         );
     }
 
+    #[test]
+    fn request_url_uses_localhost_for_unix_targets() {
+        let config = GrpcClientConfig {
+            address: "unix:///run/app.sock".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            request_url(&config, "pkg.Svc", "Call"),
+            "http://localhost/pkg.Svc/Call"
+        );
+        let plain = GrpcClientConfig::default();
+        assert_ne!(
+            http_client_cache_key(&plain),
+            http_client_cache_key(&config)
+        );
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)]
     fn cached_http_client_reuses_same_config() {
@@ -2173,7 +2158,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_grpc_web_trailers_case_folding() {
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         let payload = b"Grpc-Status: 3\nGRPC-MESSAGE: bad";
         parse_grpc_web_trailers(payload, &mut trailers, &mut error);
@@ -2184,7 +2169,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_grpc_web_trailers_mixed_case() {
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         let payload = b"Grpc-Status: 4\nGrpc-Message: deadline exceeded";
         parse_grpc_web_trailers(payload, &mut trailers, &mut error);
@@ -2194,7 +2179,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_grpc_web_trailers_success() {
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         let payload = b"grpc-status: 0";
         parse_grpc_web_trailers(payload, &mut trailers, &mut error);
@@ -2203,7 +2188,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_grpc_web_trailers_custom_metadata() {
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         let payload = b"custom-key: custom-value\nx-trace-id: abc123";
         parse_grpc_web_trailers(payload, &mut trailers, &mut error);
@@ -2260,7 +2245,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         let msg = json!({"key": "val"});
         let body = serde_json::to_vec(&msg).unwrap();
         let framed = encode_connect_envelope(&body, false);
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
 
         let (messages, trailers, error) = parse_connect_framed(&framed, None, &headers);
         assert_eq!(messages.len(), 1);
@@ -2274,7 +2259,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         let err = json!({"code": "unavailable", "message": "service down"});
         let body = serde_json::to_vec(&err).unwrap();
         let framed = encode_connect_envelope(&body, true);
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
 
         let (messages, trailers, error) = parse_connect_framed(&framed, None, &headers);
         assert!(messages.is_empty());
@@ -2287,7 +2272,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
     #[test]
     fn parse_connect_framed_end_stream_empty_with_header_error() {
         let framed = encode_connect_envelope(b"", true);
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         headers.insert("grpc-status".to_string(), "5".to_string());
         headers.insert("grpc-message".to_string(), "not found".to_string());
 
@@ -2300,7 +2285,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
     #[test]
     fn parse_connect_framed_end_stream_empty_no_error() {
         let framed = encode_connect_envelope(b"", true);
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
 
         let (messages, trailers, error) = parse_connect_framed(&framed, None, &headers);
         assert!(messages.is_empty());
@@ -2310,7 +2295,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_connect_framed_multiple_data_frames() {
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
         let mut all_framed = Vec::new();
         for i in 0..3 {
             let msg = json!({"seq": i});
@@ -2430,7 +2415,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         raw.extend_from_slice(trailer);
 
         let encoded = base64_encode(&raw).into_bytes();
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         headers.insert(
             "content-type".to_string(),
             "application/grpc-web-text+json".to_string(),
@@ -2448,7 +2433,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
     #[test]
     fn decode_grpc_web_body_passthrough_binary() {
         let raw = vec![0x00, 0x00, 0x00, 0x00, 0x01, 0x42];
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         headers.insert(
             "content-type".to_string(),
             "application/grpc-web+proto".to_string(),
@@ -2472,7 +2457,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
                 "x-multi": ["a", "b"]
             }
         });
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         parse_connect_end_stream(
             &serde_json::to_vec(&end).unwrap(),
@@ -2481,7 +2466,8 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         );
 
         assert_eq!(trailers.get("x-ratelimit").unwrap(), "0");
-        assert_eq!(trailers.get("x-multi").unwrap(), "a, b");
+        // Multi-valued entries keep every value rather than being joined.
+        assert_eq!(trailers.get_all("x-multi").collect::<Vec<_>>(), ["a", "b"]);
         let e = error.unwrap();
         assert_eq!(e.code, 8, "resource_exhausted maps to 8");
         assert_eq!(e.message, "quota hit");
@@ -2493,7 +2479,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
     #[test]
     fn parse_connect_end_stream_metadata_only_no_error() {
         let end = json!({"metadata": {"trace-id": ["abc123"]}});
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         parse_connect_end_stream(
             &serde_json::to_vec(&end).unwrap(),
@@ -2506,7 +2492,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn parse_connect_framed_streaming_end_stream_surfaces_trailers_and_error() {
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
         // A data frame, then a Connect end-of-stream frame with nested error + metadata.
         let mut framed =
             encode_connect_envelope(&serde_json::to_vec(&json!({"n": 1})).unwrap(), false);
@@ -2530,7 +2516,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
     #[test]
     fn public_response_headers_strips_framing_headers() {
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         headers.insert("grpc-status".to_string(), "0".to_string());
         headers.insert("grpc-message".to_string(), "ok".to_string());
         headers.insert(
@@ -2618,7 +2604,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
 
         // Same guarantee through the Connect end-of-stream parser.
         let end = json!({"error": {"code": "internal", "message": nasty}});
-        let mut trailers = HashMap::new();
+        let mut trailers = MetadataMap::new();
         let mut error = None;
         parse_connect_end_stream(
             &serde_json::to_vec(&end).unwrap(),
@@ -2721,7 +2707,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
     fn grpc_web_text_config() -> GrpcClientConfig {
         GrpcClientConfig {
             protocol: WireProtocol::GrpcWeb,
-            metadata: Some(HashMap::from([(
+            metadata: Some(MetadataMap::from([(
                 GRPC_WEB_TEXT_FLAG.to_string(),
                 "true".to_string(),
             )])),
@@ -2736,7 +2722,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         assert!(!grpc_web_text_enabled(&GrpcClientConfig::default()));
         // Falsey value -> binary.
         let off = GrpcClientConfig {
-            metadata: Some(HashMap::from([(
+            metadata: Some(MetadataMap::from([(
                 GRPC_WEB_TEXT_FLAG.to_string(),
                 "false".to_string(),
             )])),
@@ -2760,7 +2746,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         assert_eq!(framed[0], 0x00, "uncompressed data-frame flag");
 
         // Round-trip through the response decoder path (text bodies are base64).
-        let mut headers = HashMap::new();
+        let mut headers = MetadataMap::new();
         headers.insert("content-type".to_string(), content_type);
         let decoded = decode_grpc_web_body(body, &headers);
         let (messages, _t, _e) = parse_grpc_web_framed_json(&decoded);
@@ -2844,7 +2830,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
         assert_eq!(frames.concat(), encode_multi_request(&reqs));
 
         // Each frame is one intact, in-order envelope.
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
         for (i, frame) in frames.iter().take(reqs.len()).enumerate() {
             let (msgs, _t, _e) = parse_connect_framed(frame, None, &headers);
             assert_eq!(msgs.len(), 1);
@@ -2925,7 +2911,7 @@ a9iy8oFRmGwJBQb5oxLGtdLhWOyhRANCAAQTC9x4TBp/gTmAGuIHWKFvEBrXpgRG
             true,
         ));
 
-        let headers = HashMap::new();
+        let headers = MetadataMap::new();
         let (want_m, want_t, _e) = parse_connect_framed(&data, None, &headers);
         assert_eq!(want_m.len(), 2);
         assert_eq!(want_t.get("x-trace").unwrap(), "t-1");
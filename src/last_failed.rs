@@ -0,0 +1,189 @@
+//! Per-test outcome cache behind `run --last-failed` / `--failed-first`.
+//!
+//! Every run merges its results into a JSON cache: tests that ran get their
+//! new status, tests that didn't keep their old one (so a `--last-failed` or
+//! `--shard` run only refines the picture), and entries whose file is gone are
+//! dropped. Entries are keyed by the name a result is reported under — the
+//! file, or `format_row_name`'s `file#[row=…]` for a data row, so a failing
+//! row is rerun on its own — with the file part in [`path_key`] form, so runs
+//! from another directory or with `./`-prefixed paths find the same entries.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::execution::watch::path_key;
+use crate::state::{TestResult, TestStatus};
+
+/// 2: keys in `path_key` form.
+const VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Pass,
+    Fail,
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub outcome: Outcome,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LastRun {
+    version: u32,
+    tests: BTreeMap<String, Entry>,
+}
+
+/// Where the cache lives: `.grpctestify/cache/last-run.json` in the nearest
+/// project of the run's first path. Outside a project there is none: creating
+/// `./.grpctestify` would turn the working directory into a project for
+/// `--env`, quarantine and plugin lookup.
+pub fn cache_path(test_paths: &[PathBuf]) -> Option<PathBuf> {
+    let start = crate::serve::project::env_search_start(test_paths);
+    crate::serve::project::find_project_dir(&start)
+        .map(|project| project.join("cache").join("last-run.json"))
+}
+
+/// The file part of a result name (`file#[row=…]` → `file`).
+pub fn file_of(name: &str) -> &str {
+    name.split_once("#[").map_or(name, |(file, _)| file)
+}
+
+/// A result name as the cache keys it: the file part in `path_key` form,
+/// the row suffix kept.
+pub fn key(name: &str) -> String {
+    let file = file_of(name);
+    let row = &name[file.len()..];
+    format!("{}{row}", path_key(Path::new(file)).display())
+}
+
+impl LastRun {
+    /// Read the cache; a missing or unreadable one is empty (it is only ever
+    /// an optimization).
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<Self>(&raw).ok())
+            .filter(|cache| cache.version == VERSION)
+            .unwrap_or_default()
+    }
+
+    /// Merge `results` in and write the cache back.
+    pub fn update<'a>(
+        path: &Path,
+        results: impl IntoIterator<Item = &'a TestResult>,
+    ) -> Result<()> {
+        let mut cache = Self::load(path);
+        cache.version = VERSION;
+        for result in results {
+            let outcome = match result.status {
+                TestStatus::Pass => Outcome::Pass,
+                TestStatus::Fail => Outcome::Fail,
+                TestStatus::Skip => Outcome::Skip,
+            };
+            cache.tests.insert(
+                key(&result.name),
+                Entry {
+                    outcome,
+                    duration_ms: result.duration_ms,
+                },
+            );
+        }
+        cache
+            .tests
+            .retain(|name, _| Path::new(file_of(name)).is_file());
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        // Written aside and renamed so concurrent runs never read half a file.
+        let tmp = path.with_extension(format!("json.{}", std::process::id()));
+        std::fs::write(&tmp, serde_json::to_string_pretty(&cache)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Names of the tests that did not pass last time they ran (failed, or
+    /// skipped because a dependency failed).
+    pub fn failed(&self) -> HashSet<&str> {
+        self.tests
+            .iter()
+            .filter(|(_, entry)| entry.outcome != Outcome::Pass)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+/// Whether the test reported as `name` (a file or one of its rows) failed
+/// last time.
+pub fn name_failed(failed: &HashSet<&str>, name: &str) -> bool {
+    failed.contains(key(name).as_str())
+}
+
+/// Whether `file` has a failed entry of its own or for any of its rows.
+pub fn file_failed(failed: &HashSet<&str>, file: &Path) -> bool {
+    let file = path_key(file);
+    failed
+        .iter()
+        .any(|name| Path::new(file_of(name)) == file.as_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_merges_and_drops_deleted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.gctf");
+        let b = dir.path().join("b.gctf");
+        std::fs::write(&a, "").unwrap();
+        std::fs::write(&b, "").unwrap();
+        let name = |p: &Path| p.to_string_lossy().to_string();
+        let row = format!("{}#[row=1 id=2]", name(&b));
+        let cache = dir.path().join(".grpctestify/cache/last-run.json");
+
+        LastRun::update(
+            &cache,
+            &[
+                TestResult::pass(name(&a), 1, None),
+                TestResult::fail(row.clone(), "boom".into(), 1, None),
+            ],
+        )
+        .unwrap();
+        // A later partial run only touches what it ran.
+        LastRun::update(&cache, &[TestResult::fail(name(&a), "x".into(), 1, None)]).unwrap();
+        let last = LastRun::load(&cache);
+        let failed = last.failed();
+        assert!(name_failed(&failed, &name(&a)));
+        assert!(name_failed(&failed, &row));
+        assert!(file_failed(&failed, &b));
+        // However the path was spelled when it ran.
+        let dotted = dir.path().join(".").join("b.gctf");
+        assert!(file_failed(&failed, &dotted));
+        assert!(name_failed(
+            &failed,
+            &format!("{}#[row=1 id=2]", name(&dotted))
+        ));
+
+        std::fs::remove_file(&b).unwrap();
+        LastRun::update(&cache, &[] as &[TestResult]).unwrap();
+        assert_eq!(LastRun::load(&cache).failed().len(), 1);
+    }
+
+    #[test]
+    fn unreadable_cache_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("last-run.json");
+        std::fs::write(&cache, "{not json").unwrap();
+        assert!(LastRun::load(&cache).failed().is_empty());
+    }
+}
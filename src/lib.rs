@@ -6,8 +6,10 @@ pub mod config;
 pub mod diagnostics;
 pub mod execution;
 pub mod grpc;
+pub mod last_failed;
 pub mod logging;
 pub mod lsp;
+pub mod mock;
 pub mod only_changed;
 pub mod optimizer;
 pub mod parser;
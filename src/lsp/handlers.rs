@@ -20,7 +20,7 @@ use crate::plugins::PluginPurity;
 /// Get hover documentation for a section type
 pub fn get_section_hover(section_type: &SectionType) -> Option<String> {
     match section_type {
-        SectionType::Address => Some("**ADDRESS**\n\nServer address in `host:port` format.".to_string()),
+        SectionType::Address => Some("**ADDRESS**\n\nServer address in `host:port` format, or a `unix:/path` / `unix-abstract:name` socket.".to_string()),
         SectionType::Endpoint => Some("**ENDPOINT**\n\ngRPC endpoint in `package.Service/Method` format.".to_string()),
         SectionType::Request => Some("**REQUEST**\n\nRequest payload in JSON/JSON5 format.\n\nSupports:\n- Comments (`//`, `/* */`, `#`)\n- Trailing commas\n- Unquoted keys\n- Single-quoted strings".to_string()),
         SectionType::Response => Some("**RESPONSE**\n\nExpected response with inline options.\n\nOptions:\n- `with_asserts` - Run ASSERTS\n- `partial` - Subset comparison\n- `tolerance` - Numeric tolerance\n- `redact` - Redact fields\n- `unordered_arrays` - Order-independent".to_string()),
@@ -30,7 +30,7 @@ pub fn get_section_hover(section_type: &SectionType) -> Option<String> {
         SectionType::Proto => Some("**PROTO**\n\nProto file configuration.\n\nKeys:\n- `descriptor` - Path to .desc file\n- `files` - Comma-separated proto files\n- `import_paths` - Import paths".to_string()),
         SectionType::Options => Some("**OPTIONS**\n\nTest execution options.".to_string()),
         SectionType::Extract => Some("**EXTRACT**\n\nVariable extraction using JQ paths.\n\nExample:\n```\nuser_id: .id\ntoken: .auth.token\n```\n\nUse in REQUEST: `${user_id}`".to_string()),
-        SectionType::Asserts => Some("**ASSERTS**\n\nAssertion expressions.\n\nOperators: `==`, `!=`, `>`, `<`, `>=`, `<=`, `contains`, `matches`, `startsWith`, `endsWith`\nValidators: `@is_uuid`, `@is_email`, `@is_ip`, `@is_url`, `@is_timestamp`, `@is_base64`, `@is_json`\nState: `@is_empty`, `@has_value`, `@len`\nScope: `@scope.index`, `@scope.message_count`\nTiming: `@elapsed_ms`, `@total_elapsed_ms`\nMetadata: `@header`, `@has_header`, `@trailer`, `@has_trailer`, `@env`\nType methods: `@url.*`, `@email.*`, `@ip.version`, `@uuid.version`, `@json.key`\nJQ: `select`, `length`, `startswith`".to_string()),
+        SectionType::Asserts => Some("**ASSERTS**\n\nAssertion expressions.\n\nOperators: `==`, `!=`, `>`, `<`, `>=`, `<=`, `contains`, `matches`, `startsWith`, `endsWith`\nValidators: `@is_uuid`, `@is_email`, `@is_ip`, `@is_url`, `@is_timestamp`, `@is_base64`, `@is_json`\nState: `@is_empty`, `@has_value`, `@len`\nScope: `@scope.index`, `@scope.message_count`\nTiming: `@elapsed_ms`, `@total_elapsed_ms`\nMetadata: `@header`, `@headers`, `@has_header`, `@trailer`, `@trailers`, `@has_trailer`, `@env`\nType methods: `@url.*`, `@email.*`, `@ip.version`, `@uuid.version`, `@json.key`\nJQ: `select`, `length`, `startswith`".to_string()),
         SectionType::Meta => Some("**META**\n\nFile-level metadata (YAML).\n\nMust be first section in file.\n\nOnly 0 or 1 per file.".to_string()),
         SectionType::Bench => Some(bench_hover_doc()),
         SectionType::Dataset => Some("**DATASET**\n\nInline data-driven test rows (YAML list of objects).\n\nEach row's fields become `{{dataset.field}}` template variables, expanding this file into one test case per row — the same mechanism as `run --data`, but self-contained in the file.\n\nMutually exclusive with `--data`. Only 0 or 1 per file.".to_string()),
@@ -441,6 +441,18 @@ pub fn get_var_hover(
     doc: &crate::parser::GctfDocument,
     line_0based: usize,
     character: u32,
+) -> Option<tower_lsp::lsp_types::Hover> {
+    get_var_hover_with_env(doc, line_0based, character, None)
+}
+
+/// [`get_var_hover`], falling back to the selected project environment for
+/// variables no preceding EXTRACT defines. Values from `.env.<name>.local`
+/// (secrets) are not shown.
+pub fn get_var_hover_with_env(
+    doc: &crate::parser::GctfDocument,
+    line_0based: usize,
+    character: u32,
+    env: Option<&crate::serve::project::ProjectEnv>,
 ) -> Option<tower_lsp::lsp_types::Hover> {
     use tower_lsp::lsp_types::{Hover, HoverContents, MarkedString};
 
@@ -463,6 +475,17 @@ pub fn get_var_hover(
     let var_content = line_str[open_brace + 2..char_pos + close_brace].trim();
     let var_name = var_content.split_whitespace().next()?;
 
+    // Built-in functions, filters and `fake.*` are evaluated by the runner, not
+    // bound by EXTRACT.
+    if apif_execution::template::is_expression(var_content) || var_name.starts_with("fake.") {
+        return Some(Hover {
+            contents: HoverContents::Scalar(MarkedString::String(format!(
+                "**Template expression**\n\n`{var_content}` is evaluated at run time (see `--seed` for reproducible values)."
+            ))),
+            range: None,
+        });
+    }
+
     // Find which document this line belongs to
     let current_doc_idx = find_document_index_at_line(doc, line_0based);
 
@@ -494,6 +517,22 @@ pub fn get_var_hover(
         }
     }
 
+    if let Some(env) = env
+        && let Some(value) = env.variables.get(var_name)
+    {
+        let (shown, file) = if env.local_keys.contains(var_name) {
+            ("(hidden)".to_string(), format!(".env.{}.local", env.name))
+        } else {
+            (format!("`{value}`"), format!(".env.{}", env.name))
+        };
+        let hover_text =
+            format!("**Environment variable: `{var_name}`**\n\nValue: {shown}\nSource: `{file}`");
+        return Some(Hover {
+            contents: HoverContents::Scalar(MarkedString::String(hover_text)),
+            range: None,
+        });
+    }
+
     // Variable not found in preceding documents
     let hover_text = format!(
         "**Unknown variable: `{}`**\n\nNo EXTRACT definition found in preceding documents.",
@@ -1250,6 +1289,47 @@ pub fn collect_all_diagnostics(content: &str, file_name: &str) -> Vec<Diagnostic
     diags
 }
 
+/// Schema-aware diagnostics (unknown fields, wrong JSON types, invalid enum
+/// names, ...) for documents with a PROTO section. Kept out of
+/// [`collect_all_diagnostics`] because it reads and compiles the referenced
+/// proto files, which the playground's no-file-I/O diagnostics endpoint must
+/// not do.
+pub fn collect_schema_diagnostics(
+    doc: &crate::parser::GctfDocument,
+    content: &str,
+) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = content.lines().collect();
+    crate::semantics::schema::collect_schema_findings(doc, None)
+        .into_iter()
+        .map(|finding| {
+            let line = finding.line.saturating_sub(1) as u32;
+            let line_text = lines.get(line as usize).copied().unwrap_or("");
+            let severity = if finding.is_error() {
+                DiagnosticSeverity::ERROR
+            } else {
+                DiagnosticSeverity::WARNING
+            };
+            let message = match &finding.hint {
+                Some(hint) => format!("{}\n{hint}", finding.message),
+                None => finding.message.clone(),
+            };
+            let token = if finding.token.is_empty() {
+                line_text.trim()
+            } else {
+                finding.token.as_str()
+            };
+            Diagnostic {
+                range: token_range_on_line(line_text, line, token),
+                severity: Some(severity),
+                code: Some(NumberOrString::String(finding.rule_id.to_string())),
+                source: Some("grpctestify-schema".to_string()),
+                message,
+                ..Diagnostic::default()
+            }
+        })
+        .collect()
+}
+
 /// `check`/`inspect` already flag `--- HEADERS ---` (deprecated alias for
 /// All deprecation warnings (HEADERS alias + kebab OPTIONS keys + kebab
 /// `#[...]` attributes) from the one shared `detect_deprecations` (§7.1) — the
@@ -1874,6 +1954,33 @@ test.Service/Method
         assert!(!diagnostics.is_empty());
     }
 
+    #[test]
+    fn collect_schema_diagnostics_underlines_the_unknown_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let proto = dir.path().join("greet.proto");
+        std::fs::write(
+            &proto,
+            "syntax = \"proto3\";\npackage g;\nservice S { rpc Hi (Req) returns (Req); }\nmessage Req { string name = 1; }\n",
+        )
+        .unwrap();
+        let content = format!(
+            "--- ENDPOINT ---\ng.S/Hi\n\n--- PROTO ---\nfiles: {}\nimport_paths: {}\n\n--- REQUEST ---\n{{\"nam\": \"x\"}}\n",
+            proto.display(),
+            dir.path().display()
+        );
+        let doc = parser::parse_gctf_from_str(&content, "t.gctf").unwrap();
+        let diags = collect_schema_diagnostics(&doc, &content);
+        assert_eq!(diags.len(), 1, "{diags:?}");
+        assert_eq!(
+            diags[0].code,
+            Some(NumberOrString::String("SCHEMA_UNKNOWN_FIELD".into()))
+        );
+        assert_eq!(
+            diags[0].range,
+            Range::new(Position::new(8, 2), Position::new(8, 5))
+        );
+    }
+
     #[test]
     fn collect_optimizer_diagnostics_canonical_operator_rule() {
         let content = r#"--- ENDPOINT ---
@@ -2031,6 +2138,39 @@ test.Service/Method
         assert!(hover.is_some());
     }
 
+    #[test]
+    fn get_var_hover_falls_back_to_the_project_env_and_hides_local_values() {
+        let content = "--- ENDPOINT ---\nsvc.S/M\n\n--- REQUEST ---\n{\"t\": \"{{TENANT}}\", \"k\": \"{{TOKEN}}\"}\n";
+        let doc = parser::parse_gctf_from_str(content, "t.gctf").unwrap();
+        let env = crate::serve::project::ProjectEnv {
+            name: "staging".to_string(),
+            variables: [("TENANT", "acme"), ("TOKEN", "s3cret")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            local_keys: ["TOKEN".to_string()].into_iter().collect(),
+            address: None,
+        };
+        let text = |character| match get_var_hover_with_env(&doc, 4, character, Some(&env))
+            .unwrap()
+            .contents
+        {
+            HoverContents::Scalar(MarkedString::String(s)) => s,
+            other => panic!("{other:?}"),
+        };
+
+        let tenant = text(10);
+        assert!(
+            tenant.contains("`acme`") && tenant.contains("`.env.staging`"),
+            "{tenant}"
+        );
+        let token = text(28);
+        assert!(
+            !token.contains("s3cret") && token.contains(".env.staging.local"),
+            "{token}"
+        );
+    }
+
     #[test]
     fn section_index_at_line_half_open() {
         // Leading comment + blank push the first header off line 0.
@@ -10,7 +10,7 @@ use tower_lsp::{Client, LanguageServer, LspService, Server, lsp_types::*};
 use crate::config;
 use crate::grpc::client::{GrpcClient, GrpcClientConfig};
 use crate::grpc::{ProtoConfig, WireProtocol};
-use crate::lsp::handlers::{self, get_var_hover, get_variable_completions};
+use crate::lsp::handlers::{self, get_variable_completions};
 use crate::lsp::proto_definition;
 use crate::lsp::variable_definition;
 use crate::parser::ast::SectionType;
@@ -30,6 +30,9 @@ pub struct GrpctestifyLsp {
     semantic_tokens_cache: VersionedMap<SemanticTokens>,
     inlay_hints_cache: VersionedMap<Vec<InlayHint>>,
     endpoint_completion_cache: EndpointCompletionCache,
+    /// `lsp --env`: project environment for `{{VAR}}` hover; `None` follows
+    /// settings.json `active_env`.
+    env: Option<String>,
 }
 
 impl GrpctestifyLsp {
@@ -43,9 +46,16 @@ impl GrpctestifyLsp {
             semantic_tokens_cache: Arc::new(RwLock::new(HashMap::new())),
             inlay_hints_cache: Arc::new(RwLock::new(HashMap::new())),
             endpoint_completion_cache: Arc::new(RwLock::new(HashMap::new())),
+            env: None,
         }
     }
 
+    /// Select the project environment used for `{{VAR}}` hover.
+    pub fn with_env(mut self, env: Option<String>) -> Self {
+        self.env = env;
+        self
+    }
+
     fn parse_string_list(raw: &str) -> Vec<String> {
         let trimmed = raw.trim();
         if trimmed.is_empty() {
@@ -138,6 +148,7 @@ impl GrpctestifyLsp {
             connection_id: 0,
             protocol,
             version: env!("CARGO_PKG_VERSION").to_string(),
+            proxy: None,
         };
 
         let created = tokio::time::timeout(SCHEMA_TIMEOUT, GrpcClient::new(config)).await;
@@ -660,7 +671,9 @@ impl GrpctestifyLsp {
         // Cache the parse under the version that matches `content` (passed in
         // atomically with it), never a re-read that a concurrent edit may
         // have already bumped.
+        let mut lsp_diags = handlers::collect_all_diagnostics(content, &file_name);
         if let Ok(document) = parser::parse_gctf_from_str(content, &file_name) {
+            lsp_diags.extend(handlers::collect_schema_diagnostics(&document, content));
             self.parsed_docs
                 .write()
                 .await
@@ -671,7 +684,6 @@ impl GrpctestifyLsp {
                 .insert(uri.to_string(), version);
         }
 
-        let lsp_diags = handlers::collect_all_diagnostics(content, &file_name);
         self.client
             .publish_diagnostics(uri.clone(), lsp_diags, None)
             .await;
@@ -984,9 +996,21 @@ impl LanguageServer for GrpctestifyLsp {
         {
             let line0 = position.line as usize;
 
-            // First check if cursor is on a {{var}} reference
-            if let Some(var_hover) = get_var_hover(&doc, position.line as usize, position.character)
-            {
+            // First check if cursor is on a {{var}} reference. The environment
+            // is re-read per hover so edits to .env files show up immediately.
+            let env = std::path::Path::new(&doc.file_path)
+                .parent()
+                .and_then(|dir| {
+                    crate::serve::project::resolve_project_env(dir, self.env.as_deref())
+                        .ok()
+                        .flatten()
+                });
+            if let Some(var_hover) = handlers::get_var_hover_with_env(
+                &doc,
+                position.line as usize,
+                position.character,
+                env.as_ref(),
+            ) {
                 return Ok(Some(var_hover));
             }
 
@@ -1646,11 +1670,12 @@ fn infer_active_parameter(line: &str, open_paren_abs: usize, cursor_idx: usize)
     commas
 }
 
-pub async fn start_lsp_server() -> Result<()> {
+pub async fn start_lsp_server(env: Option<String>) -> Result<()> {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(GrpctestifyLsp::new);
+    let (service, socket) =
+        LspService::new(move |client| GrpctestifyLsp::new(client).with_env(env.clone()));
     Server::new(stdin, stdout, socket).serve(service).await;
 
     Ok(())
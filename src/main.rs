@@ -38,10 +38,12 @@ async fn main() -> Result<()> {
     }
 
     let command = Cli::command().before_long_help(help_logo());
-    let cli = match Cli::from_arg_matches(&command.get_matches()) {
+    let matches = command.get_matches();
+    let mut cli = match Cli::from_arg_matches(&matches) {
         Ok(cli) => cli,
         Err(e) => e.exit(),
     };
+    commands::apply_file_config(&mut cli, &matches)?;
 
     use tracing_subscriber::EnvFilter;
     use tracing_subscriber::fmt::writer::BoxMakeWriter;
@@ -116,7 +118,11 @@ async fn main() -> Result<()> {
         Some(Commands::Query(args)) => commands::handle_query(args),
         Some(Commands::Health(args)) => commands::handle_health(args).await,
         Some(Commands::Play(args)) => commands::handle_play(args).await,
+        Some(Commands::Mock(args)) => commands::handle_mock(args).await,
         Some(Commands::Scaffold(args)) => commands::handle_scaffold(args).await,
+        Some(Commands::Coverage(args)) => commands::handle_coverage(args),
+        Some(Commands::SchemaDiff(args)) => commands::handle_schema_diff(args).await,
+        Some(Commands::Config(args)) => commands::handle_config(args),
         Some(Commands::Plugins(args)) => {
             use cli::args::PluginsAction;
             let action = args.action.clone();
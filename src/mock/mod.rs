@@ -0,0 +1,318 @@
+//! `grpctestify mock`: a stub gRPC server answering from `.gctf` files.
+//!
+//! Calls arrive over native gRPC (h2c), gRPC-Web or Connect on one port and
+//! are matched against the stubs loaded by [`stub::load_stubs`]; gRPC server
+//! reflection describes the loaded descriptors so clients can discover the
+//! mocked services.
+
+pub mod stub;
+mod wire;
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::body::{Body, Bytes};
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use futures::StreamExt;
+use http_body::Frame;
+use prost::Message;
+use prost_reflect::{DynamicMessage, MethodDescriptor};
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+use apif_grpc_transport::encoding::base64_encode;
+use stub::{Session, StubStatus};
+pub use stub::{StubSet, load_stubs};
+use wire::{Codec, MessageReader, Protocol, Wire};
+
+/// The mock's router: reflection for the loaded descriptors, and every other
+/// path answered from the stubs.
+pub fn build_app(stubs: Arc<StubSet>) -> Result<axum::Router> {
+    let files = prost_types::FileDescriptorSet {
+        file: stubs.pool.file_descriptor_protos().cloned().collect(),
+    };
+    let reflection = || {
+        tonic_reflection::server::Builder::configure().register_file_descriptor_set(files.clone())
+    };
+    let v1 = reflection().build_v1()?;
+    let v1alpha = reflection().build_v1alpha()?;
+
+    Ok(tonic::service::Routes::new(v1)
+        .add_service(v1alpha)
+        .into_axum_router()
+        .fallback(move |request: Request| handle_call(stubs.clone(), request))
+        // Browsers call gRPC-Web and Connect endpoints cross-origin.
+        .layer(tower_http::cors::CorsLayer::permissive()))
+}
+
+async fn handle_call(stubs: Arc<StubSet>, request: Request) -> Response {
+    let Some(wire) = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(Wire::from_content_type)
+    else {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "expected a gRPC, gRPC-Web or Connect content type",
+        )
+            .into_response();
+    };
+    let endpoint = request.uri().path().trim_start_matches('/').to_string();
+    let call = Call {
+        method: stubs.method(&endpoint),
+        endpoint,
+        wire,
+        stubs,
+    };
+    let (parts, body) = request.into_parts();
+    let reader = MessageReader::new(wire, &parts.headers, body);
+
+    if wire.protocol == Protocol::ConnectUnary {
+        let mut messages = Vec::new();
+        let status = call.run(reader, &mut messages).await;
+        return connect_unary_response(wire, messages, status);
+    }
+
+    let (tx, rx) = mpsc::channel(16);
+    tokio::spawn(async move {
+        let mut out = FramedOutput {
+            wire,
+            tx,
+            text: matches!(wire.protocol, Protocol::GrpcWeb { text: true }).then(Vec::new),
+        };
+        let status = call.run(reader, &mut out).await;
+        out.finish(status.as_ref()).await;
+    });
+    let body = http_body_util::StreamBody::new(
+        tokio_stream::wrappers::ReceiverStream::new(rx).map(Ok::<_, Infallible>),
+    );
+    let mut response = Response::new(Body::new(body));
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(wire.content_type()),
+    );
+    response
+}
+
+/// One incoming call.
+struct Call {
+    stubs: Arc<StubSet>,
+    endpoint: String,
+    method: Option<MethodDescriptor>,
+    wire: Wire,
+}
+
+/// Where a call's response messages go.
+trait Sink {
+    async fn message(&mut self, payload: Vec<u8>);
+}
+
+/// Connect unary collects its message for a single-body response.
+impl Sink for Vec<Vec<u8>> {
+    async fn message(&mut self, payload: Vec<u8>) {
+        self.push(payload);
+    }
+}
+
+/// Streamed protocols frame each message onto the response body.
+struct FramedOutput {
+    wire: Wire,
+    tx: mpsc::Sender<Frame<Bytes>>,
+    /// gRPC-Web text is one base64 blob, so its frames are buffered.
+    text: Option<Vec<u8>>,
+}
+
+impl Sink for FramedOutput {
+    async fn message(&mut self, payload: Vec<u8>) {
+        self.write(wire::frame(0, &payload)).await;
+    }
+}
+
+impl FramedOutput {
+    async fn write(&mut self, bytes: Vec<u8>) {
+        match &mut self.text {
+            Some(buf) => buf.extend(bytes),
+            // A send error means the client went away; nothing to report to.
+            None => {
+                let _ = self.tx.send(Frame::data(Bytes::from(bytes))).await;
+            }
+        }
+    }
+
+    /// End the call with `status` (`None` is OK).
+    async fn finish(mut self, status: Option<&StubStatus>) {
+        match self.wire.protocol {
+            Protocol::Grpc => {
+                let (code, message) = wire::status_fields(status);
+                let mut trailers = HeaderMap::new();
+                if let Ok(code) = HeaderValue::from_str(&code) {
+                    trailers.insert("grpc-status", code);
+                }
+                if let Ok(message) = HeaderValue::from_str(&message)
+                    && !message.is_empty()
+                {
+                    trailers.insert("grpc-message", message);
+                }
+                let _ = self.tx.send(Frame::trailers(trailers)).await;
+            }
+            Protocol::GrpcWeb { .. } => {
+                self.write(wire::frame(0x80, &wire::grpc_web_trailers(status)))
+                    .await;
+                if let Some(buf) = self.text.take() {
+                    let encoded = Bytes::from(base64_encode(&buf));
+                    let _ = self.tx.send(Frame::data(encoded)).await;
+                }
+            }
+            Protocol::ConnectStream => {
+                self.write(wire::frame(0x02, &wire::connect_end_stream(status)))
+                    .await;
+            }
+            Protocol::ConnectUnary => {}
+        }
+    }
+}
+
+fn connect_unary_response(
+    wire: Wire,
+    messages: Vec<Vec<u8>>,
+    status: Option<StubStatus>,
+) -> Response {
+    if let Some(status) = status {
+        let code = StatusCode::from_u16(wire::connect_http_status(status.code))
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        return (
+            code,
+            [(header::CONTENT_TYPE, "application/json")],
+            wire::connect_error(&status).to_string(),
+        )
+            .into_response();
+    }
+    // Unary has exactly one response message; a streaming stub called as
+    // unary answers with its first.
+    let body = messages.into_iter().next().unwrap_or_default();
+    ([(header::CONTENT_TYPE, wire.content_type())], body).into_response()
+}
+
+impl Call {
+    /// Feed the client's messages to the stubs and send their replies to
+    /// `sink`. Returns the status the call ends with (`None` is OK).
+    async fn run(&self, mut reader: MessageReader, sink: &mut impl Sink) -> Option<StubStatus> {
+        match self.exchange(&mut reader, sink).await {
+            Ok((source, status)) => {
+                let code = status
+                    .as_ref()
+                    .map(|s| format!(" (status {})", s.code))
+                    .unwrap_or_default();
+                println!(
+                    "{} {} → {source}{code}",
+                    crate::report::style::pass_icon(),
+                    self.endpoint
+                );
+                status
+            }
+            Err(status) => {
+                println!(
+                    "{} {}: {}",
+                    crate::report::style::fail_icon(),
+                    self.endpoint,
+                    status.message
+                );
+                Some(status)
+            }
+        }
+    }
+
+    /// The stub exchange proper: `Ok` with the matched stub's source and its
+    /// ERROR status, `Err` when the call could not be answered from a stub.
+    async fn exchange(
+        &self,
+        reader: &mut MessageReader,
+        sink: &mut impl Sink,
+    ) -> Result<(String, Option<StubStatus>), StubStatus> {
+        let stubs = self.stubs.get(&self.endpoint);
+        if stubs.is_empty() {
+            return Err(StubStatus::new(
+                12,
+                format!("no stub for {}", self.endpoint),
+            ));
+        }
+        if self.wire.codec == Codec::Proto && self.method.is_none() {
+            return Err(StubStatus::new(
+                12,
+                format!(
+                    "no schema for {}: pass --proto/--descriptor or add a PROTO section",
+                    self.endpoint
+                ),
+            ));
+        }
+
+        let mut session = Session::new(stubs);
+        while let Some(payload) = reader.next().await? {
+            let message = self.decode(&payload)?;
+            let replies = session
+                .receive(message)
+                .ok_or_else(|| self.no_match(session.received().last()))?;
+            for reply in replies {
+                sink.message(self.encode(reply)?).await;
+            }
+        }
+        let last = session.received().last().cloned();
+        let reply = session
+            .finish()
+            .ok_or_else(|| self.no_match(last.as_ref()))?;
+        for message in reply.messages {
+            sink.message(self.encode(message)?).await;
+        }
+        Ok((reply.stub.source.clone(), reply.stub.error.clone()))
+    }
+
+    fn no_match(&self, last: Option<&Value>) -> StubStatus {
+        let request = last.map(Value::to_string).unwrap_or_default();
+        StubStatus::new(
+            5,
+            format!("no stub for {} matches request {request}", self.endpoint),
+        )
+    }
+
+    /// A client message as JSON, normalized like the stubs' REQUEST bodies.
+    fn decode(&self, payload: &[u8]) -> Result<Value, StubStatus> {
+        let input = self.method.as_ref().map(MethodDescriptor::input);
+        match (self.wire.codec, &input) {
+            (Codec::Proto, Some(input)) => DynamicMessage::decode(input.clone(), payload)
+                .map(|message| stub::message_to_json(&message))
+                .map_err(|e| {
+                    StubStatus::new(3, format!("cannot decode {}: {e}", input.full_name()))
+                }),
+            _ => serde_json::from_slice::<Value>(payload)
+                .map(|value| stub::normalize(&value, input.as_ref()))
+                .map_err(|e| StubStatus::new(3, format!("request is not valid JSON: {e}"))),
+        }
+    }
+
+    /// A stub's RESPONSE body on the wire, placeholders (`{{ uuid() }}`...)
+    /// evaluated.
+    fn encode(&self, body: &Value) -> Result<Vec<u8>, StubStatus> {
+        let mut body = body.clone();
+        crate::execution::runner_helpers::substitute_variables(&mut body, &HashMap::new());
+        let output = self.method.as_ref().map(MethodDescriptor::output);
+        match (self.wire.codec, output) {
+            (Codec::Proto, Some(output)) => DynamicMessage::deserialize(output.clone(), &body)
+                .map(|message| message.encode_to_vec())
+                .map_err(|e| {
+                    StubStatus::new(
+                        13,
+                        format!("stub RESPONSE does not fit {}: {e}", output.full_name()),
+                    )
+                }),
+            (_, output) => {
+                let body = stub::normalize(&body, output.as_ref());
+                Ok(body.to_string().into_bytes())
+            }
+        }
+    }
+}
@@ -0,0 +1,516 @@
+//! Stubs loaded from `.gctf` files, and the matching of incoming calls
+//! against them.
+//!
+//! Every document of a file (each link of a multi-document chain) with an
+//! ENDPOINT is one stub. Its REQUEST, RESPONSE and ERROR sections are read as
+//! the script of one call: the requests a client sends, the messages sent
+//! back, and the status the call ends with.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor};
+use serde_json::Value;
+
+use crate::execution::error_handler::ErrorHandler;
+use crate::parser::{
+    self,
+    ast::{GctfDocument, SectionContent, SectionType},
+};
+use crate::utils::FileUtils;
+
+/// One step of a stub's exchange, in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    /// A message the client is expected to send. `partial` (from
+    /// `--- REQUEST partial ---`) matches when these fields agree, ignoring
+    /// any others.
+    Request { body: Value, partial: bool },
+    /// A message sent back to the client.
+    Response(Value),
+}
+
+/// The status a stub ends its call with, from its ERROR section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StubStatus {
+    pub code: u32,
+    pub message: String,
+}
+
+impl StubStatus {
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Stub {
+    /// `file:line` of the document, for the request log.
+    pub source: String,
+    pub steps: Vec<Step>,
+    pub error: Option<StubStatus>,
+}
+
+impl Stub {
+    fn requests(&self) -> impl Iterator<Item = (&Value, bool)> {
+        self.steps.iter().filter_map(|step| match step {
+            Step::Request { body, partial } => Some((body, *partial)),
+            Step::Response(_) => None,
+        })
+    }
+
+    /// A stub without REQUEST sections answers any call to its method.
+    fn is_catch_all(&self) -> bool {
+        self.requests().next().is_none()
+    }
+
+    /// Exact stubs win over partial ones, and both over catch-alls.
+    fn rank(&self) -> u8 {
+        if self.is_catch_all() {
+            2
+        } else if self.requests().any(|(_, partial)| partial) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Whether the messages received so far are the start of this stub's
+    /// requests.
+    fn accepts_prefix(&self, received: &[Value]) -> bool {
+        if self.is_catch_all() {
+            return true;
+        }
+        let mut requests = self.requests();
+        received.iter().all(|actual| {
+            requests
+                .next()
+                .is_some_and(|(expected, partial)| request_matches(expected, actual, partial))
+        })
+    }
+
+    /// Index of the step right after the `n`-th (0-based) request.
+    fn step_after_request(&self, n: usize) -> usize {
+        self.steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| matches!(step, Step::Request { .. }))
+            .nth(n)
+            .map_or(self.steps.len(), |(i, _)| i + 1)
+    }
+
+    /// The responses from step `cursor` up to the next request; advances
+    /// `cursor` past them.
+    fn responses_from(&self, cursor: &mut usize) -> Vec<&Value> {
+        let mut out = Vec::new();
+        while let Some(Step::Response(body)) = self.steps.get(*cursor) {
+            out.push(body);
+            *cursor += 1;
+        }
+        out
+    }
+}
+
+/// Whether `actual` matches a stub's `expected` request.
+pub fn request_matches(expected: &Value, actual: &Value, partial: bool) -> bool {
+    if partial {
+        is_subset(expected, actual)
+    } else {
+        expected == actual
+    }
+}
+
+/// Every field of `expected` is present in `actual` with a matching value.
+/// Arrays must have the same length and match element-wise.
+fn is_subset(expected: &Value, actual: &Value) -> bool {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|a| is_subset(value, a))),
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().zip(actual).all(|(e, a)| is_subset(e, a))
+        }
+        _ => expected == actual,
+    }
+}
+
+/// The matching state of one call, fed the client's messages as they arrive.
+///
+/// Candidates are narrowed message by message. A stub is committed to early
+/// only when it answers between requests (an interleaved bidi exchange), since
+/// the client may wait for those replies before sending more; otherwise the
+/// choice is made once the client half-closes.
+pub struct Session<'a> {
+    candidates: Vec<&'a Stub>,
+    received: Vec<Value>,
+    committed: Option<(&'a Stub, usize)>,
+}
+
+/// The stub chosen for a call and the messages it still has to send.
+pub struct Reply<'a> {
+    pub stub: &'a Stub,
+    pub messages: Vec<&'a Value>,
+}
+
+impl<'a> Session<'a> {
+    pub fn new(stubs: &'a [Stub]) -> Self {
+        let mut candidates: Vec<&Stub> = stubs.iter().collect();
+        candidates.sort_by_key(|stub| stub.rank());
+        Self {
+            candidates,
+            received: Vec::new(),
+            committed: None,
+        }
+    }
+
+    /// The messages received so far.
+    pub fn received(&self) -> &[Value] {
+        &self.received
+    }
+
+    /// Take the next client message. Returns the messages to send right away,
+    /// or `None` when no stub matches any more.
+    pub fn receive(&mut self, message: Value) -> Option<Vec<&'a Value>> {
+        let index = self.received.len();
+        self.received.push(message);
+
+        if let Some((stub, cursor)) = &mut self.committed {
+            let Some(Step::Request { body, partial }) = stub.steps.get(*cursor) else {
+                return None;
+            };
+            if !request_matches(body, &self.received[index], *partial) {
+                return None;
+            }
+            *cursor += 1;
+            return Some(stub.responses_from(cursor));
+        }
+
+        let received = &self.received;
+        self.candidates.retain(|stub| stub.accepts_prefix(received));
+        let best = *self.candidates.first()?;
+        let mut cursor = best.step_after_request(index);
+        let interleaved = matches!(best.steps.get(cursor), Some(Step::Response(_)))
+            && best.requests().count() > index + 1;
+        if !interleaved {
+            return Some(Vec::new());
+        }
+        let messages = best.responses_from(&mut cursor);
+        self.committed = Some((best, cursor));
+        Some(messages)
+    }
+
+    /// The client finished sending: pick the stub (if not committed already)
+    /// and return what it still sends. `None` when no stub matches.
+    pub fn finish(self) -> Option<Reply<'a>> {
+        if let Some((stub, mut cursor)) = self.committed {
+            let messages = stub.responses_from(&mut cursor);
+            // A stub still waiting for requests does not match a stream
+            // that ended early.
+            return (cursor == stub.steps.len()).then_some(Reply { stub, messages });
+        }
+        let received = self.received.len();
+        let stub = self
+            .candidates
+            .into_iter()
+            .find(|stub| stub.is_catch_all() || stub.requests().count() == received)?;
+        let messages = stub
+            .steps
+            .iter()
+            .filter_map(|step| match step {
+                Step::Response(body) => Some(body),
+                Step::Request { .. } => None,
+            })
+            .collect();
+        Some(Reply { stub, messages })
+    }
+}
+
+/// Every stub of a mock server, keyed by `package.Service/Method`, and the
+/// descriptors that describe them.
+pub struct StubSet {
+    pub stubs: BTreeMap<String, Vec<Stub>>,
+    pub pool: DescriptorPool,
+    pub files: usize,
+}
+
+impl StubSet {
+    pub fn get(&self, endpoint: &str) -> &[Stub] {
+        self.stubs.get(endpoint).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn method(&self, endpoint: &str) -> Option<MethodDescriptor> {
+        let (service, method) = endpoint.split_once('/')?;
+        self.pool
+            .get_service_by_name(service)?
+            .methods()
+            .find(|m| m.name() == method)
+    }
+
+    pub fn stub_count(&self) -> usize {
+        self.stubs.values().map(Vec::len).sum()
+    }
+}
+
+/// Load every `.gctf` file under `paths`. `flag_pool` (from `--proto` /
+/// `--descriptor`) is merged with the PROTO sections of the files.
+pub fn load_stubs(paths: &[PathBuf], flag_pool: Option<DescriptorPool>) -> Result<StubSet> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            files.extend(FileUtils::collect_test_files(path, &[]));
+        } else if path.is_file() {
+            files.push(path.clone());
+        } else {
+            anyhow::bail!("Path not found: {}", path.display());
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let mut pool = flag_pool.unwrap_or_default();
+    let mut documents = Vec::with_capacity(files.len());
+    for file in &files {
+        let doc = parser::parse_gctf(file)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+        for link in doc.iter_chain() {
+            if let Some(loaded) = crate::semantics::schema::load_document_pool(link) {
+                let loaded = loaded
+                    .with_context(|| format!("Failed to load PROTO for {}", file.display()))?;
+                pool.add_file_descriptor_protos(loaded.file_descriptor_protos().cloned())
+                    .with_context(|| format!("Conflicting PROTO in {}", file.display()))?;
+            }
+        }
+        documents.push(doc);
+    }
+
+    let mut set = StubSet {
+        stubs: BTreeMap::new(),
+        pool,
+        files: files.len(),
+    };
+    for doc in &documents {
+        for link in doc.iter_chain() {
+            let Some(endpoint) = link.get_endpoint() else {
+                continue;
+            };
+            let endpoint = endpoint.trim_start_matches('/').to_string();
+            let input = set.method(&endpoint).map(|m| m.input());
+            let stub = build_stub(link, input.as_ref());
+            set.stubs.entry(endpoint).or_default().push(stub);
+        }
+    }
+    Ok(set)
+}
+
+fn build_stub(doc: &GctfDocument, input: Option<&MessageDescriptor>) -> Stub {
+    let line = doc.sections.first().map_or(0, |s| s.start_line) + 1;
+    let mut stub = Stub {
+        source: format!("{}:{line}", doc.file_path),
+        steps: Vec::new(),
+        error: None,
+    };
+    for section in &doc.sections {
+        let bodies: Vec<&Value> = match &section.content {
+            SectionContent::Json(value) => vec![value],
+            SectionContent::JsonLines(values) => values.iter().collect(),
+            _ => Vec::new(),
+        };
+        match section.section_type {
+            SectionType::Request => {
+                let partial = section.inline_options.partial;
+                stub.steps
+                    .extend(bodies.into_iter().map(|body| Step::Request {
+                        body: normalize(body, input),
+                        partial,
+                    }));
+            }
+            SectionType::Response => {
+                stub.steps
+                    .extend(bodies.into_iter().cloned().map(Step::Response));
+            }
+            SectionType::Error => stub.error = stub_status(&section.content),
+            _ => {}
+        }
+    }
+    stub
+}
+
+/// An ERROR section as a status: `{"code": 5, "message": "..."}`, where the
+/// code may also be a name (`"NOT_FOUND"`, `"NotFound"`); a bare string is
+/// the message of an UNKNOWN error.
+fn stub_status(content: &SectionContent) -> Option<StubStatus> {
+    match content {
+        SectionContent::Json(Value::Object(error)) => {
+            let code = match error.get("code") {
+                Some(Value::Number(n)) => n.as_u64().map_or(2, |n| n as u32),
+                Some(Value::String(name)) => code_from_name(name).unwrap_or(2),
+                _ => 2,
+            };
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            Some(StubStatus::new(code, message))
+        }
+        SectionContent::Json(Value::String(message)) | SectionContent::Single(message) => {
+            Some(StubStatus::new(2, message.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Map a status code name in any common spelling to its number.
+pub fn code_from_name(name: &str) -> Option<u32> {
+    if let Ok(code) = name.parse() {
+        return Some(code);
+    }
+    let wanted = name.replace('_', "").to_ascii_lowercase();
+    if wanted == "canceled" {
+        return Some(1);
+    }
+    (0..=16)
+        .find(|&code| {
+            ErrorHandler::grpc_code_name_from_numeric(code)
+                .is_some_and(|known| known.eq_ignore_ascii_case(&wanted))
+        })
+        .map(|code| code as u32)
+}
+
+/// Round-trip `value` through `desc` so that equivalent JSON spellings
+/// (`camelCase` names, `"1"` for an int64, omitted defaults) compare equal.
+/// Values that don't fit the message are kept as written.
+pub fn normalize(value: &Value, desc: Option<&MessageDescriptor>) -> Value {
+    let Some(desc) = desc else {
+        return value.clone();
+    };
+    DynamicMessage::deserialize(desc.clone(), value)
+        .map(|message| message_to_json(&message))
+        .unwrap_or_else(|_| value.clone())
+}
+
+pub fn message_to_json(message: &DynamicMessage) -> Value {
+    let options = prost_reflect::SerializeOptions::new().use_proto_field_name(true);
+    message
+        .serialize_with_options(serde_json::value::Serializer, &options)
+        .unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stub(steps: Vec<Step>) -> Stub {
+        Stub {
+            source: "test.gctf:1".to_string(),
+            steps,
+            error: None,
+        }
+    }
+
+    fn request(body: Value) -> Step {
+        Step::Request {
+            body,
+            partial: false,
+        }
+    }
+
+    #[test]
+    fn partial_matches_a_subset_of_fields() {
+        let expected = json!({"user": {"id": 1}});
+        let actual = json!({"user": {"id": 1, "name": "a"}, "trace": true});
+        assert!(request_matches(&expected, &actual, true));
+        assert!(!request_matches(&expected, &actual, false));
+        assert!(!request_matches(&json!({"user": {"id": 2}}), &actual, true));
+    }
+
+    #[test]
+    fn exact_beats_partial_beats_catch_all() {
+        let stubs = vec![
+            stub(vec![Step::Response(json!({"from": "catch-all"}))]),
+            stub(vec![
+                Step::Request {
+                    body: json!({"id": 1}),
+                    partial: true,
+                },
+                Step::Response(json!({"from": "partial"})),
+            ]),
+            stub(vec![
+                request(json!({"id": 1})),
+                Step::Response(json!({"from": "exact"})),
+            ]),
+        ];
+        let reply = |message: Value| {
+            let mut session = Session::new(&stubs);
+            session.receive(message)?;
+            session.finish().map(|r| r.messages[0].clone())
+        };
+        assert_eq!(reply(json!({"id": 1})), Some(json!({"from": "exact"})));
+        assert_eq!(
+            reply(json!({"id": 1, "x": 2})),
+            Some(json!({"from": "partial"}))
+        );
+        assert_eq!(reply(json!({"id": 9})), Some(json!({"from": "catch-all"})));
+    }
+
+    #[test]
+    fn client_stream_must_send_every_request() {
+        let stubs = vec![stub(vec![
+            request(json!({"n": 1})),
+            request(json!({"n": 2})),
+            Step::Response(json!({"sum": 3})),
+        ])];
+        let mut session = Session::new(&stubs);
+        assert_eq!(session.receive(json!({"n": 1})), Some(vec![]));
+        assert!(session.finish().is_none());
+
+        let mut session = Session::new(&stubs);
+        session.receive(json!({"n": 1}));
+        session.receive(json!({"n": 2}));
+        assert_eq!(session.finish().unwrap().messages, vec![&json!({"sum": 3})]);
+    }
+
+    #[test]
+    fn interleaved_bidi_answers_before_the_stream_ends() {
+        let stubs = vec![stub(vec![
+            request(json!({"q": "a"})),
+            Step::Response(json!({"a": 1})),
+            request(json!({"q": "b"})),
+            Step::Response(json!({"a": 2})),
+        ])];
+        let mut session = Session::new(&stubs);
+        assert_eq!(
+            session.receive(json!({"q": "a"})),
+            Some(vec![&json!({"a": 1})])
+        );
+        assert_eq!(
+            session.receive(json!({"q": "b"})),
+            Some(vec![&json!({"a": 2})])
+        );
+        assert!(session.finish().unwrap().messages.is_empty());
+
+        let mut session = Session::new(&stubs);
+        session.receive(json!({"q": "a"}));
+        assert_eq!(session.receive(json!({"q": "zzz"})), None);
+    }
+
+    #[test]
+    fn status_codes_accept_numbers_and_names() {
+        assert_eq!(code_from_name("5"), Some(5));
+        assert_eq!(code_from_name("NOT_FOUND"), Some(5));
+        assert_eq!(code_from_name("NotFound"), Some(5));
+        assert_eq!(code_from_name("canceled"), Some(1));
+        assert_eq!(code_from_name("bogus"), None);
+        assert_eq!(
+            stub_status(&SectionContent::Json(
+                json!({"code": "unavailable", "message": "down"})
+            )),
+            Some(StubStatus::new(14, "down"))
+        );
+    }
+}
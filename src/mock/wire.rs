@@ -0,0 +1,340 @@
+//! The three wire protocols a mock call can arrive over, told apart by
+//! content type, and their message framing.
+//!
+//! Native gRPC, gRPC-Web and Connect streaming share the 5-byte
+//! `[flags][len:4][payload]` frame; they differ in how the call's status is
+//! sent (HTTP/2 trailers, a `0x80` trailer frame, a `0x02` end-stream frame).
+//! Connect unary sends the bare message and reports errors through the HTTP
+//! status.
+
+use apif_grpc_transport::encoding::base64_decode;
+use axum::body::{Body, Bytes};
+use axum::http::HeaderMap;
+use futures::StreamExt;
+
+use super::stub::StubStatus;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Grpc,
+    /// `text` is the base64 `grpc-web-text` variant.
+    GrpcWeb {
+        text: bool,
+    },
+    ConnectUnary,
+    ConnectStream,
+}
+
+/// How messages are encoded inside the framing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Proto,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wire {
+    pub protocol: Protocol,
+    pub codec: Codec,
+}
+
+impl Wire {
+    /// Recognize a request content type (parameters ignored).
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let base = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        let (protocol, codec) = match base.as_str() {
+            "application/grpc" | "application/grpc+proto" => (Protocol::Grpc, Codec::Proto),
+            "application/grpc+json" => (Protocol::Grpc, Codec::Json),
+            "application/grpc-web" | "application/grpc-web+proto" => {
+                (Protocol::GrpcWeb { text: false }, Codec::Proto)
+            }
+            "application/grpc-web+json" => (Protocol::GrpcWeb { text: false }, Codec::Json),
+            "application/grpc-web-text" | "application/grpc-web-text+proto" => {
+                (Protocol::GrpcWeb { text: true }, Codec::Proto)
+            }
+            "application/grpc-web-text+json" => (Protocol::GrpcWeb { text: true }, Codec::Json),
+            "application/connect+proto" => (Protocol::ConnectStream, Codec::Proto),
+            "application/connect+json" => (Protocol::ConnectStream, Codec::Json),
+            "application/proto" => (Protocol::ConnectUnary, Codec::Proto),
+            "application/json" => (Protocol::ConnectUnary, Codec::Json),
+            _ => return None,
+        };
+        Some(Self { protocol, codec })
+    }
+
+    /// The content type of the response.
+    pub fn content_type(&self) -> &'static str {
+        match (self.protocol, self.codec) {
+            (Protocol::Grpc, Codec::Proto) => "application/grpc",
+            (Protocol::Grpc, Codec::Json) => "application/grpc+json",
+            (Protocol::GrpcWeb { text: false }, Codec::Proto) => "application/grpc-web+proto",
+            (Protocol::GrpcWeb { text: false }, Codec::Json) => "application/grpc-web+json",
+            (Protocol::GrpcWeb { text: true }, Codec::Proto) => "application/grpc-web-text+proto",
+            (Protocol::GrpcWeb { text: true }, Codec::Json) => "application/grpc-web-text+json",
+            (Protocol::ConnectStream, Codec::Proto) => "application/connect+proto",
+            (Protocol::ConnectStream, Codec::Json) => "application/connect+json",
+            (Protocol::ConnectUnary, Codec::Proto) => "application/proto",
+            (Protocol::ConnectUnary, Codec::Json) => "application/json",
+        }
+    }
+
+    /// The header a client names its message compression in.
+    fn encoding_header(&self) -> &'static str {
+        match self.protocol {
+            Protocol::Grpc | Protocol::GrpcWeb { .. } => "grpc-encoding",
+            Protocol::ConnectStream => "connect-content-encoding",
+            Protocol::ConnectUnary => "content-encoding",
+        }
+    }
+}
+
+/// One length-prefixed frame.
+pub fn frame(flags: u8, payload: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(payload.len() + 5);
+    buf.push(flags);
+    buf.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    buf.extend_from_slice(payload);
+    buf
+}
+
+fn gunzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(data).read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// Reads the client's messages off a request body as they arrive.
+pub struct MessageReader {
+    wire: Wire,
+    gzip: bool,
+    body: axum::body::BodyDataStream,
+    buf: Vec<u8>,
+    done: bool,
+}
+
+impl MessageReader {
+    pub fn new(wire: Wire, headers: &HeaderMap, body: Body) -> Self {
+        let gzip = headers
+            .get(wire.encoding_header())
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.eq_ignore_ascii_case("gzip"));
+        Self {
+            wire,
+            gzip,
+            body: body.into_data_stream(),
+            buf: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// The next message payload, `None` once the client half-closed.
+    pub async fn next(&mut self) -> Result<Option<Vec<u8>>, StubStatus> {
+        match self.wire.protocol {
+            // The whole body is the one message (or one base64 blob).
+            Protocol::ConnectUnary => {
+                if self.done {
+                    return Ok(None);
+                }
+                let body = self.read_all().await?;
+                return if self.gzip {
+                    gunzip(&body).map(Some).map_err(invalid_body)
+                } else {
+                    Ok(Some(body))
+                };
+            }
+            Protocol::GrpcWeb { text: true } if !self.done => {
+                let body = self.read_all().await?;
+                self.buf = base64_decode(&body).ok_or_else(|| {
+                    StubStatus::new(3, "grpc-web-text request body is not valid base64")
+                })?;
+            }
+            _ => {}
+        }
+
+        loop {
+            if let Some((flags, payload)) = self.pop_frame() {
+                // Trailer frames carry no message. Connect's end-stream flag
+                // belongs to responses; clients that set it on their last
+                // request still mean it as a message.
+                if flags & 0x80 != 0 {
+                    continue;
+                }
+                return if flags & 0x01 != 0 {
+                    gunzip(&payload).map(Some).map_err(invalid_body)
+                } else {
+                    Ok(Some(payload))
+                };
+            }
+            if self.done {
+                return Ok(None);
+            }
+            match self.body.next().await {
+                Some(Ok(chunk)) => self.buf.extend_from_slice(&chunk),
+                Some(Err(e)) => return Err(invalid_body(e)),
+                None => self.done = true,
+            }
+        }
+    }
+
+    async fn read_all(&mut self) -> Result<Vec<u8>, StubStatus> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.body.next().await {
+            body.extend_from_slice(&chunk.map_err(invalid_body)?);
+        }
+        self.done = true;
+        Ok(body)
+    }
+
+    fn pop_frame(&mut self) -> Option<(u8, Vec<u8>)> {
+        if self.buf.len() < 5 {
+            return None;
+        }
+        let len = u32::from_be_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]) as usize;
+        if self.buf.len() < 5 + len {
+            return None;
+        }
+        let flags = self.buf[0];
+        let payload = self.buf[5..5 + len].to_vec();
+        self.buf.drain(..5 + len);
+        Some((flags, payload))
+    }
+}
+
+fn invalid_body(e: impl std::fmt::Display) -> StubStatus {
+    StubStatus::new(3, format!("failed to read request body: {e}"))
+}
+
+/// `grpc-message` is percent-encoded on the wire.
+pub fn percent_encode(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        if (0x20..0x7f).contains(&byte) && byte != b'%' {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// The `grpc-status` / `grpc-message` pair for a call's outcome.
+pub fn status_fields(status: Option<&StubStatus>) -> (String, String) {
+    match status {
+        Some(status) => (status.code.to_string(), percent_encode(&status.message)),
+        None => ("0".to_string(), String::new()),
+    }
+}
+
+/// The gRPC-Web trailer frame payload.
+pub fn grpc_web_trailers(status: Option<&StubStatus>) -> Vec<u8> {
+    let (code, message) = status_fields(status);
+    let mut text = format!("grpc-status:{code}\r\n");
+    if !message.is_empty() {
+        text.push_str(&format!("grpc-message:{message}\r\n"));
+    }
+    text.into_bytes()
+}
+
+/// Connect's code names, indexed by status code.
+const CONNECT_CODES: [&str; 17] = [
+    "ok",
+    "canceled",
+    "unknown",
+    "invalid_argument",
+    "deadline_exceeded",
+    "not_found",
+    "already_exists",
+    "permission_denied",
+    "resource_exhausted",
+    "failed_precondition",
+    "aborted",
+    "out_of_range",
+    "unimplemented",
+    "internal",
+    "unavailable",
+    "data_loss",
+    "unauthenticated",
+];
+
+/// A Connect error object, `{"code": "not_found", "message": "..."}`.
+pub fn connect_error(status: &StubStatus) -> serde_json::Value {
+    let code = CONNECT_CODES
+        .get(status.code as usize)
+        .copied()
+        .unwrap_or("unknown");
+    serde_json::json!({ "code": code, "message": status.message })
+}
+
+/// The HTTP status a Connect unary error is sent with.
+pub fn connect_http_status(code: u32) -> u16 {
+    match code {
+        1 => 499,
+        3 | 9 | 11 => 400,
+        4 => 504,
+        5 => 404,
+        6 | 10 => 409,
+        7 => 403,
+        8 => 429,
+        12 => 501,
+        14 => 503,
+        16 => 401,
+        _ => 500,
+    }
+}
+
+/// Connect's end-of-stream envelope payload.
+pub fn connect_end_stream(status: Option<&StubStatus>) -> Bytes {
+    let body = match status {
+        Some(status) => serde_json::json!({ "error": connect_error(status) }),
+        None => serde_json::json!({}),
+    };
+    Bytes::from(body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_types_select_protocol_and_codec() {
+        let wire =
+            Wire::from_content_type("application/grpc-web-text+json; charset=utf-8").unwrap();
+        assert_eq!(wire.protocol, Protocol::GrpcWeb { text: true });
+        assert_eq!(wire.codec, Codec::Json);
+        assert_eq!(wire.content_type(), "application/grpc-web-text+json");
+        assert_eq!(
+            Wire::from_content_type("application/json")
+                .unwrap()
+                .protocol,
+            Protocol::ConnectUnary
+        );
+        assert!(Wire::from_content_type("text/plain").is_none());
+    }
+
+    #[tokio::test]
+    async fn reader_splits_frames_and_skips_trailer_frames() {
+        let wire = Wire::from_content_type("application/grpc-web+json").unwrap();
+        let mut body = frame(0, b"{\"a\":1}");
+        body.extend(frame(0x80, b"grpc-status:0\r\n"));
+        body.extend(frame(0, b"{\"a\":2}"));
+        let mut reader = MessageReader::new(wire, &HeaderMap::new(), Body::from(body));
+        assert_eq!(reader.next().await.unwrap().unwrap(), b"{\"a\":1}");
+        assert_eq!(reader.next().await.unwrap().unwrap(), b"{\"a\":2}");
+        assert!(reader.next().await.unwrap().is_none());
+    }
+
+    #[test]
+    fn grpc_message_is_percent_encoded() {
+        let status = StubStatus::new(5, "no user 100% gone\n");
+        assert_eq!(
+            grpc_web_trailers(Some(&status)),
+            b"grpc-status:5\r\ngrpc-message:no user 100%25 gone%0A\r\n"
+        );
+    }
+}
@@ -218,6 +218,30 @@ fn extract_test_name(path: &str) -> String {
         .to_string()
 }
 
+/// META.links, then the test's trace when `--trace-url` gave it a link.
+fn build_links(result: &TestResult) -> Option<Vec<Link>> {
+    let mut links: Vec<Link> = result
+        .meta
+        .links
+        .iter()
+        .map(|url| Link {
+            name: None,
+            url: url.clone(),
+            link_type: "custom",
+        })
+        .collect();
+    if let Some(trace) = &result.trace
+        && let Some(url) = &trace.url
+    {
+        links.push(Link {
+            name: Some(format!("Trace {}", trace.trace_id)),
+            url: url.clone(),
+            link_type: "custom",
+        });
+    }
+    (!links.is_empty()).then_some(links)
+}
+
 fn extract_suite_name(path: &str) -> String {
     std::path::Path::new(path)
         .parent()
@@ -720,6 +744,12 @@ impl Reporter for AllureReporter {
                 value: package,
             });
         }
+        if let Some(trace) = &result.trace {
+            labels.push(Label {
+                name: "trace_id".to_string(),
+                value: trace.trace_id.clone(),
+            });
+        }
 
         let attachments = write_exchange_attachment(&self.output_dir, &uuid, &result.exchange)
             .map(|attachment| vec![attachment]);
@@ -740,22 +770,7 @@ impl Reporter for AllureReporter {
             steps,
             attachments,
             description: result.meta.summary.clone(),
-            links: if result.meta.links.is_empty() {
-                None
-            } else {
-                Some(
-                    result
-                        .meta
-                        .links
-                        .iter()
-                        .map(|url| Link {
-                            name: None,
-                            url: url.clone(),
-                            link_type: "custom",
-                        })
-                        .collect(),
-                )
-            },
+            links: build_links(result),
         };
 
         let file_name = format!("{}-result.json", uuid);
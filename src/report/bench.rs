@@ -30,6 +30,10 @@ pub struct BenchSummary {
     pub fastest_ns: u64,
     pub slowest_ns: u64,
     pub rps_observed: f64,
+    /// Highest arrival rate that held every adaptive guard (`mode: adaptive`
+    /// only; `None` when even the starting rate breached).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sustainable_rps: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +59,31 @@ pub struct BenchThresholdResult {
     pub reason: Option<String>,
 }
 
+/// One fixed-rate probe window of an adaptive run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchAdaptiveStep {
+    pub target_rps: f64,
+    pub observed_rps: f64,
+    pub count: u64,
+    pub errors: u64,
+    pub latency_p99_ns: u64,
+    pub sustainable: bool,
+    /// Guards this window breached (empty when sustainable).
+    pub breaches: Vec<String>,
+}
+
+/// Outcome of the adaptive rate search: the knee plus every probe window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchAdaptiveResult {
+    pub max_sustainable_rps: Option<f64>,
+    /// Lowest probed rate that breached a guard.
+    pub breaking_rps: Option<f64>,
+    pub stop_reason: String,
+    /// Guard expressions that decided each window (`metric: expr`).
+    pub guards: Vec<String>,
+    pub steps: Vec<BenchAdaptiveStep>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BenchDetail {
     pub timestamp: i64,
@@ -93,6 +122,8 @@ pub struct BenchReport {
     pub tags: BTreeMap<String, String>,
     pub sources_runtime: Option<SourcesRuntime>,
     pub per_endpoint: Vec<PerEndpointSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adaptive: Option<BenchAdaptiveResult>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -122,6 +153,7 @@ impl BenchReport {
             tags: BTreeMap::new(),
             sources_runtime: None,
             per_endpoint: Vec::new(),
+            adaptive: None,
         }
     }
 
@@ -160,6 +192,10 @@ impl BenchReport {
             "grpctestify_bench_rps_observed {}",
             self.summary.rps_observed
         ));
+        if let Some(rps) = self.summary.max_sustainable_rps {
+            lines.push("# TYPE grpctestify_bench_max_sustainable_rps gauge".to_string());
+            lines.push(format!("grpctestify_bench_max_sustainable_rps {rps}"));
+        }
 
         lines.push("# TYPE grpctestify_bench_threshold_passed gauge".to_string());
         for t in &self.threshold_evaluation {
@@ -206,6 +242,12 @@ impl BenchReport {
             format_ns(self.summary.average_ns)
         );
         let _ = writeln!(out, "   • Requests/sec: {:.2}", self.summary.rps_observed);
+        if let Some(adaptive) = &self.adaptive {
+            let knee = adaptive
+                .max_sustainable_rps
+                .map_or_else(|| "none".to_string(), |rps| format!("{rps:.2}"));
+            let _ = writeln!(out, "   • Max sustainable RPS: {knee}");
+        }
 
         if !self.threshold_evaluation.is_empty() {
             let passed = self
@@ -261,6 +303,7 @@ impl BenchReport {
             ));
         }
 
+        append_adaptive_steps(&mut out, self);
         append_status_and_errors(&mut out, self);
         let _ = writeln!(out, "{heavy}");
         out
@@ -371,6 +414,35 @@ impl BenchReport {
     }
 }
 
+fn append_adaptive_steps(out: &mut String, report: &BenchReport) {
+    let Some(adaptive) = &report.adaptive else {
+        return;
+    };
+    let light = crate::report::style::rule('─', 80);
+    let _ = writeln!(out, "{light}");
+    let _ = writeln!(
+        out,
+        "🧭 Adaptive search ({}; guards: {}):",
+        adaptive.stop_reason,
+        adaptive.guards.join(", ")
+    );
+    for step in &adaptive.steps {
+        let verdict = if step.sustainable {
+            "ok".to_string()
+        } else {
+            format!("breach: {}", step.breaches.join("; "))
+        };
+        let _ = writeln!(
+            out,
+            "   target {:>9.2} rps → {:>9.2} rps, p99={} — {}",
+            step.target_rps,
+            step.observed_rps,
+            format_ns(step.latency_p99_ns),
+            verdict
+        );
+    }
+}
+
 fn append_status_and_errors(out: &mut String, report: &BenchReport) {
     let light = crate::report::style::rule('─', 80);
     let _ = writeln!(out, "{light}");
@@ -467,6 +539,7 @@ mod tests {
             fastest_ns: 1_000,
             slowest_ns: 100_000,
             rps_observed: 500.0,
+            max_sustainable_rps: None,
         };
         report.threshold_evaluation.push(BenchThresholdResult {
             metric: "latency_ms.p(95)".to_string(),
@@ -554,6 +627,48 @@ mod tests {
         assert!(text.contains("Error distribution:"));
     }
 
+    #[test]
+    fn adaptive_result_surfaces_in_summary_and_prometheus() {
+        let mut report = sample_report();
+        report.summary.max_sustainable_rps = Some(240.0);
+        report.adaptive = Some(BenchAdaptiveResult {
+            max_sustainable_rps: Some(240.0),
+            breaking_rps: Some(320.0),
+            stop_reason: "converged".to_string(),
+            guards: vec!["latency_ms.p(99): <50".to_string()],
+            steps: vec![BenchAdaptiveStep {
+                target_rps: 320.0,
+                observed_rps: 301.5,
+                count: 1507,
+                errors: 0,
+                latency_p99_ns: 80_000_000,
+                sustainable: false,
+                breaches: vec!["latency_ms.p(99): 80.000ms >= 50".to_string()],
+            }],
+        });
+
+        let text = report.to_summary_text(false);
+        assert!(text.contains("Max sustainable RPS: 240.00"), "{text}");
+        assert!(text.contains("Adaptive search (converged"), "{text}");
+        assert!(text.contains("breach: latency_ms.p(99)"), "{text}");
+        assert!(
+            report
+                .to_prometheus_summary()
+                .contains("grpctestify_bench_max_sustainable_rps 240")
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["summary"]["max_sustainable_rps"], 240.0);
+        assert_eq!(json["adaptive"]["breaking_rps"], 320.0);
+    }
+
+    #[test]
+    fn non_adaptive_report_omits_adaptive_fields() {
+        let json = serde_json::to_value(sample_report()).unwrap();
+        assert!(json["summary"].get("max_sustainable_rps").is_none());
+        assert!(json.get("adaptive").is_none());
+    }
+
     #[test]
     fn summary_text_compact_omits_histogram() {
         let report = sample_report();
@@ -0,0 +1,248 @@
+//! Machine-readable `check`/`fmt` output for CI dashboards: SARIF (GitHub
+//! code scanning and other SARIF viewers) and GitLab Code Quality JSON,
+//! plus the rule catalog that documents every diagnostic code in them.
+
+use anyhow::{Result, bail};
+use apif_report::sarif::{RuleHelp, SarifTool};
+
+use crate::report::Diagnostic;
+
+const HOMEPAGE: &str = "https://gripmock.github.io/grpctestify-rust/";
+const DOCS_URL: &str =
+    "https://gripmock.github.io/grpctestify-rust/guides/features/code-scanning.html";
+
+/// `--format` of `check` and `fmt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticFormat {
+    Text,
+    Json,
+    Sarif,
+    GitLab,
+}
+
+impl DiagnosticFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            "gitlab" => Ok(Self::GitLab),
+            other => bail!("Unknown format '{other}' (expected text, json, sarif or gitlab)"),
+        }
+    }
+
+    /// SARIF and GitLab: the whole report is one JSON document on stdout,
+    /// so nothing else may be printed there.
+    pub fn is_code_scanning(self) -> bool {
+        matches!(self, Self::Sarif | Self::GitLab)
+    }
+
+    /// Renders `diagnostics` as a SARIF log or GitLab report (`None` for
+    /// text and JSON, which their commands print themselves).
+    pub fn render(self, diagnostics: &[Diagnostic]) -> Result<Option<String>> {
+        let value = match self {
+            Self::Sarif => apif_report::sarif::sarif_log(&tool(), diagnostics, rule_help),
+            Self::GitLab => apif_report::gitlab::code_quality_report(diagnostics),
+            Self::Text | Self::Json => return Ok(None),
+        };
+        Ok(Some(serde_json::to_string_pretty(&value)?))
+    }
+}
+
+fn tool() -> SarifTool {
+    SarifTool {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        information_uri: HOMEPAGE.to_string(),
+    }
+}
+
+/// What a diagnostic code flags and how to fix it.
+pub fn rule_help(code: &str) -> RuleHelp {
+    if let Ok(rule) = crate::optimizer::RuleId::try_from(code) {
+        return RuleHelp {
+            summary: format!(
+                "Assertion can be simplified — {}",
+                rule.proof_note()
+                    .unwrap_or("an equivalent, shorter form exists")
+            ),
+            help: format!(
+                "Applies when: {}. `fmt --write` makes this rewrite (at the same `--optimize` level).",
+                rule.preconditions()
+                    .unwrap_or("the rewrite is provably equivalent")
+            ),
+            help_uri: Some(format!("{DOCS_URL}#optimizer-rewrites")),
+        };
+    }
+    let (summary, help) = match code {
+        "PARSE_ERROR" => (
+            "The file is not a well-formed .gctf document",
+            "Fix the syntax the message points at; section headers look like `--- ENDPOINT ---`.",
+        ),
+        "FILE_NOT_FOUND" => (
+            "A path given on the command line does not exist",
+            "Check the path, or quote globs your shell did not expand.",
+        ),
+        "VALIDATION_ERROR" => (
+            "The document breaks a structural rule (missing or conflicting sections, bad OPTIONS)",
+            "Follow the message; `grpctestify explain` shows how the file is understood.",
+        ),
+        "VALIDATION_WARNING" | "VALIDATION_INFO" => (
+            "The document is valid but likely not what was meant",
+            "Follow the message; the hint, when present, names the canonical form.",
+        ),
+        "DEPRECATED_SECTION" => (
+            "Deprecated section name",
+            "Replace `--- HEADERS ---` with `--- REQUEST_HEADERS ---`; `fmt --write` does it for you.",
+        ),
+        "DEPRECATED_KEY_SPELLING" => (
+            "Deprecated kebab-case OPTIONS key or attribute",
+            "Use the snake_case spelling (`retry_delay`, `#[no_retry]`); `fmt --write` does it for you.",
+        ),
+        "SECTION_ORDER" => (
+            "Preamble sections are out of canonical order",
+            "Order is META, BENCH, DATASET, ADDRESS, ENDPOINT, TLS, PROTO, OPTIONS; `fmt --write` reorders them.",
+        ),
+        "SEM_T001" => (
+            "Comparison between values of incompatible types",
+            "Compare the field with a literal of its own type, e.g. a number without quotes.",
+        ),
+        "SEM_T002" => (
+            "Ordering operator used with a non-number right-hand side",
+            "`>`, `<`, `>=` and `<=` need a number or a time string on the right.",
+        ),
+        "SEM_T003" => (
+            "String operator used with a non-string right-hand side",
+            "`contains`, `startsWith`, `endsWith` and `matches` need a string on the right.",
+        ),
+        "SEM_T005" => (
+            "Operator not supported by the left-hand side's type",
+            "Use an operator the value supports, or convert it first (e.g. `@len(...)`).",
+        ),
+        "SEM_F001" => (
+            "Unknown assertion plugin",
+            "Fix the plugin name, or install the `.rhai` plugin that defines it.",
+        ),
+        "SEM_D001" => (
+            "Deprecated assertion plugin name",
+            "Use the canonical plugin name; `fmt --write` renames it for you.",
+        ),
+        "SEM_C001" => (
+            "Assertion compares two literals and never checks the response",
+            "Compare a response field instead, e.g. `.field == \"expected\"`.",
+        ),
+        "SEM_C002" => (
+            "Duplicate assertion",
+            "Remove the duplicate or make it check something else.",
+        ),
+        "SEM_C003" => (
+            "Assertion repeats what RESPONSE already pins",
+            "Remove the assertion, or drop the field from RESPONSE to check it only in ASSERTS.",
+        ),
+        "SCHEMA_LOAD_ERROR" => (
+            "The proto schema could not be loaded, so payloads went unchecked",
+            "Check the PROTO section or the `--proto`/`--descriptor` path.",
+        ),
+        "SCHEMA_UNKNOWN_METHOD" => (
+            "ENDPOINT names a method the schema does not define",
+            "Fix the `package.Service/Method` spelling or point at the right protos.",
+        ),
+        "SCHEMA_UNKNOWN_FIELD" => (
+            "Payload field the message type does not define",
+            "Fix the field name; proto3 JSON accepts the lowerCamelCase JSON name or the proto name.",
+        ),
+        "SCHEMA_TYPE_MISMATCH" => (
+            "Payload value has the wrong JSON type for its field",
+            "Use the proto3 JSON form: 64-bit integers may be strings, bytes are base64, enums are names.",
+        ),
+        "SCHEMA_INVALID_ENUM" => (
+            "Enum value the enum type does not define",
+            "Use one of the enum's value names.",
+        ),
+        "SCHEMA_ONEOF_CONFLICT" => (
+            "More than one member of a oneof is set",
+            "Keep a single member of each oneof.",
+        ),
+        "SCHEMA_MISSING_REQUIRED" => (
+            "A required field is missing",
+            "Add the field to the payload.",
+        ),
+        "DEPENDENCY_NOT_FOUND" => (
+            "META.depends_on names a file that does not exist",
+            "depends_on paths are relative to the file's own directory.",
+        ),
+        "DEPENDENCY_CYCLE" => (
+            "META.depends_on files depend on each other in a loop",
+            "Remove one depends_on entry in the loop; `run` would never start these tests.",
+        ),
+        "UNUSED_VARIABLE" => (
+            "EXTRACT variable that no later document uses",
+            "Remove the EXTRACT entry, or reference it via `{{var_name}}`.",
+        ),
+        "EMPTY_ASSERTS" => (
+            "ASSERTS section without assertions",
+            "Add assertion expressions or remove the section.",
+        ),
+        "LARGE_DATASET" => (
+            "Inline DATASET with many rows",
+            "Move the rows to a CSV/TSV/NDJSON file and use `run --data <file>`.",
+        ),
+        "BENCH_CONFIG_ERROR" => (
+            "Invalid BENCH section",
+            "Follow the message; `grpctestify explain` shows the resolved BENCH settings.",
+        ),
+        "NO_ERROR_CASE_COVERAGE" => (
+            "No test in the suite checks a gRPC error status",
+            "Add at least one test with an ERROR section.",
+        ),
+        "NO_ASSERTS_COVERAGE" => (
+            "No test in the suite has an ASSERTS section",
+            "ASSERTS checks specific fields without pinning the whole payload.",
+        ),
+        "FORMAT_NEEDED" => (
+            "The file is not in canonical format",
+            "Run `grpctestify fmt -w <file>`, or apply the attached fix.",
+        ),
+        "FORMAT_ERROR" => (
+            "The file could not be formatted",
+            "Fix the problem the message names, then run `fmt` again.",
+        ),
+        _ => ("grpctestify diagnostic", "See the message for details."),
+    };
+    RuleHelp {
+        summary: summary.to_string(),
+        help: help.to_string(),
+        help_uri: Some(format!("{DOCS_URL}#rules")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_optimizer_rule_gets_its_proof_note() {
+        let help = rule_help("OPT_B001");
+        assert!(
+            help.summary.contains("Boolean identity"),
+            "{}",
+            help.summary
+        );
+        assert!(help.help.contains("boolean plugin expr"), "{}", help.help);
+        assert!(rule_help("SEM_T001").summary.contains("incompatible types"));
+    }
+
+    #[test]
+    fn diagnostic_format_parses_case_insensitively() {
+        assert_eq!(
+            DiagnosticFormat::parse("SARIF").unwrap(),
+            DiagnosticFormat::Sarif
+        );
+        assert!(
+            DiagnosticFormat::parse("gitlab")
+                .unwrap()
+                .is_code_scanning()
+        );
+        assert!(DiagnosticFormat::parse("xml").is_err());
+    }
+}
@@ -0,0 +1,256 @@
+//! Common Test Report Format (CTRF) reporter.
+//!
+//! Writes one CTRF JSON document (<https://ctrf.io>): a summary of the run and
+//! one entry per test with its suite (the test's directory), META tags and
+//! owner, and whether it needed a retry to pass. gRPC specifics — the status
+//! code, quarantine, `--repeat` spread — go in each test's `extra`.
+
+use crate::execution::TestRunner;
+use crate::report::Reporter;
+use crate::state::{TestResult, TestResults, TestStatus};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
+use std::path::{Path, PathBuf};
+
+pub struct CtrfReporter {
+    output_path: PathBuf,
+}
+
+impl CtrfReporter {
+    pub fn new(output_path: PathBuf) -> Self {
+        Self { output_path }
+    }
+}
+
+impl Reporter for CtrfReporter {
+    fn on_suite_end(&self, results: &TestResults) -> Result<()> {
+        let report = serde_json::to_string_pretty(&build_report(results))
+            .context("Failed to serialize CTRF report")?;
+        std::fs::write(&self.output_path, report).with_context(|| {
+            format!(
+                "Failed to write CTRF report file: {}",
+                self.output_path.display()
+            )
+        })
+    }
+}
+
+fn build_report(results: &TestResults) -> Value {
+    let metrics = results.metrics();
+    let start = metrics.start_time.saturating_mul(1000);
+    let stop = start.saturating_add(i64::try_from(metrics.total_duration_ms).unwrap_or(i64::MAX));
+    let tests: Vec<Value> = results.all().iter().map(test_entry).collect();
+    json!({
+        "reportFormat": "CTRF",
+        "specVersion": "0.0.0",
+        "results": {
+            "tool": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "summary": {
+                "tests": results.total(),
+                "passed": results.passed(),
+                "failed": results.failed(),
+                "pending": 0,
+                // A quarantined failure is reported as skipped, as in JUnit,
+                // so CTRF consumers don't fail the build on it.
+                "skipped": results.skipped() + results.quarantined(),
+                "other": 0,
+                "start": start,
+                "stop": stop,
+            },
+            "tests": tests,
+        },
+    })
+}
+
+fn test_entry(result: &TestResult) -> Value {
+    let status = match result.status {
+        TestStatus::Pass => "passed",
+        TestStatus::Fail if result.quarantined => "skipped",
+        TestStatus::Fail => "failed",
+        TestStatus::Skip => "skipped",
+    };
+    let flaky = match &result.repeat {
+        Some(repeat) => repeat.passed > 0 && repeat.passed < repeat.runs,
+        None => {
+            result.status == TestStatus::Pass
+                && (result.retried || result.attempts.is_some_and(|n| n > 1))
+        }
+    };
+
+    let mut test = Map::new();
+    test.insert(
+        "name".into(),
+        json!(result.meta.name.as_deref().unwrap_or(&result.name)),
+    );
+    test.insert("status".into(), json!(status));
+    test.insert("duration".into(), json!(result.duration_ms));
+    if result.status == TestStatus::Fail && result.quarantined {
+        test.insert("rawStatus".into(), json!("failed"));
+    }
+    if let Some(message) = &result.error_message {
+        test.insert("message".into(), json!(message));
+    }
+    if let Some(trace) = failed_assertions(result) {
+        test.insert("trace".into(), json!(trace));
+    }
+    test.insert("filePath".into(), json!(result.name));
+    test.insert("suite".into(), json!(suite_name(&result.name)));
+    if !result.meta.tags.is_empty() {
+        test.insert("tags".into(), json!(result.meta.tags));
+    }
+    if let Some(attempts) = result.attempts {
+        test.insert("retries".into(), json!(attempts.saturating_sub(1)));
+    }
+    test.insert("flaky".into(), json!(flaky));
+
+    let mut extra = Map::new();
+    if let Some(owner) = &result.meta.owner {
+        extra.insert("owner".into(), json!(owner));
+    }
+    if let Some(summary) = &result.meta.summary {
+        extra.insert("summary".into(), json!(summary));
+    }
+    if !result.meta.links.is_empty() {
+        extra.insert("links".into(), json!(result.meta.links));
+    }
+    if let Some(code) = result.grpc_status {
+        extra.insert(
+            "grpcStatus".into(),
+            json!({
+                "code": code,
+                "name": TestRunner::grpc_code_name_from_numeric(i64::from(code)).unwrap_or("Unknown"),
+            }),
+        );
+    }
+    if let Some(trace) = &result.trace {
+        extra.insert("traceId".into(), json!(trace.trace_id));
+        if let Some(url) = &trace.url {
+            extra.insert("traceUrl".into(), json!(url));
+        }
+    }
+    if result.quarantined {
+        extra.insert("quarantined".into(), json!(true));
+    }
+    if let Some(repeat) = &result.repeat {
+        extra.insert("repeat".into(), json!(repeat));
+    }
+    if !extra.is_empty() {
+        test.insert("extra".into(), Value::Object(extra));
+    }
+    Value::Object(test)
+}
+
+/// Each failed assertion with its expected/actual, one per block.
+fn failed_assertions(result: &TestResult) -> Option<String> {
+    let blocks: Vec<String> = result
+        .assertions
+        .iter()
+        .filter(|a| !a.passed)
+        .map(|a| match (&a.expected, &a.actual) {
+            (Some(expected), Some(actual)) => format!(
+                "line {}: {}\n  expected: {expected}\n  actual:   {actual}",
+                a.line, a.expression
+            ),
+            _ => match &a.message {
+                Some(message) => format!("line {}: {}\n  {message}", a.line, a.expression),
+                None => format!("line {}: {}", a.line, a.expression),
+            },
+        })
+        .collect();
+    (!blocks.is_empty()).then(|| blocks.join("\n"))
+}
+
+/// A test's suite is its directory, as in the Allure report.
+fn suite_name(path: &str) -> String {
+    Path::new(path)
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("gRPC Tests")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{RepeatStats, TestMeta};
+
+    #[test]
+    fn ctrf_report_maps_meta_status_and_flakiness() {
+        let meta = TestMeta {
+            name: Some("Health check".into()),
+            tags: vec!["smoke".into()],
+            owner: Some("team-a".into()),
+            ..TestMeta::default()
+        };
+        let mut results = TestResults::new();
+        results.add(
+            TestResult::pass_with_meta("tests/health/a.gctf", 5, Some(4), meta)
+                .with_attempts(Some(3))
+                .with_grpc_status(Some(0)),
+        );
+        results.add(
+            TestResult::fail("tests/users/b.gctf", "boom".into(), 7, None)
+                .with_grpc_status(Some(5))
+                .with_quarantined(true),
+        );
+        results.add(
+            TestResult::fail("c.gctf", "flaked".into(), 3, None).with_repeat(RepeatStats {
+                runs: 4,
+                passed: 3,
+                pass_rate: 0.75,
+                min_ms: 1,
+                median_ms: 2,
+                max_ms: 3,
+                failures: vec![],
+            }),
+        );
+
+        let report = build_report(&results);
+        assert_eq!(report["reportFormat"], "CTRF");
+        let summary = &report["results"]["summary"];
+        assert_eq!(summary["tests"], 3);
+        assert_eq!(summary["passed"], 1);
+        assert_eq!(summary["failed"], 1);
+        assert_eq!(summary["skipped"], 1);
+
+        let tests = &report["results"]["tests"];
+        assert_eq!(tests[0]["name"], "Health check");
+        assert_eq!(tests[0]["suite"], "health");
+        assert_eq!(tests[0]["tags"][0], "smoke");
+        assert_eq!(tests[0]["extra"]["owner"], "team-a");
+        assert_eq!(tests[0]["retries"], 2);
+        assert_eq!(tests[0]["flaky"], true);
+        assert_eq!(tests[1]["status"], "skipped");
+        assert_eq!(tests[1]["rawStatus"], "failed");
+        assert_eq!(tests[1]["extra"]["grpcStatus"]["name"], "NotFound");
+        assert_eq!(tests[2]["status"], "failed");
+        assert_eq!(tests[2]["flaky"], true);
+        // Neither result recorded an attempt count, so CTRF gets no guess.
+        assert!(tests[1].get("retries").is_none());
+        assert!(tests[2].get("retries").is_none());
+    }
+
+    #[test]
+    fn ctrf_report_counts_retries_from_attempts() {
+        let mut results = TestResults::new();
+        results.add(TestResult::pass("first.gctf", 1, None).with_attempts(Some(1)));
+        results.add(
+            TestResult::pass("request.gctf", 1, None)
+                .with_retried(true)
+                .with_attempts(Some(1)),
+        );
+
+        let report = build_report(&results);
+        let tests = &report["results"]["tests"];
+        assert_eq!(tests[0]["retries"], 0);
+        assert_eq!(tests[0]["flaky"], false);
+        // A REQUEST-level resend inside the one run is flaky, but not a retry
+        // of the test.
+        assert_eq!(tests[1]["retries"], 0);
+        assert_eq!(tests[1]["flaky"], true);
+    }
+}
@@ -1,12 +1,15 @@
 //! Output reporters for test results.
 //! Agnostic reporters live in crates/apif-report.
-//! gRPC-specific reporters (allure, coverage, bench, kernel) stay local.
+//! gRPC-specific reporters (allure, coverage, bench, kernel, tap, ctrf) stay local.
 
 pub mod allure;
 pub mod bench;
+pub mod code_scanning;
 pub mod coverage;
+pub mod ctrf;
 pub mod kernel;
 pub mod rhai_reporter;
+pub mod tap;
 
 pub use apif_report::{
     ConsoleMode, ConsoleReporter, HtmlReporter, JsonReporter, JunitReporter, Reporter,
@@ -17,8 +20,10 @@ pub use apif_report::diagnostics::{
     AstOverview, BenchResolvedOption, CheckReport, CheckSummary, Diagnostic, DiagnosticSeverity,
     DocumentStructure, InspectReport, SectionInfo,
 };
-pub use apif_report::{console, diagnostics, html, json, junit, streaming, style};
+pub use apif_report::{console, diagnostics, gitlab, html, json, junit, sarif, streaming, style};
 
 pub use allure::AllureReporter;
 pub use coverage::CoverageCollector;
+pub use ctrf::CtrfReporter;
 pub use rhai_reporter::{load_all_configured_reporters, load_rhai_reporters};
+pub use tap::TapReporter;
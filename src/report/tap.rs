@@ -0,0 +1,208 @@
+//! TAP 14 reporter.
+//!
+//! One `ok`/`not ok` line per test, in run order. A failure carries a YAML
+//! diagnostic block with its message, the gRPC status the call ended with
+//! and each failed assertion's expected/actual, so TAP harnesses and
+//! aggregators show why it failed without the console log.
+
+use crate::execution::TestRunner;
+use crate::report::Reporter;
+use crate::state::{TestResult, TestResults, TestStatus};
+use anyhow::{Context, Result};
+use serde_json::{Map, Value, json};
+use std::path::PathBuf;
+
+pub struct TapReporter {
+    output_path: PathBuf,
+}
+
+impl TapReporter {
+    pub fn new(output_path: PathBuf) -> Self {
+        Self { output_path }
+    }
+}
+
+impl Reporter for TapReporter {
+    fn on_suite_end(&self, results: &TestResults) -> Result<()> {
+        std::fs::write(&self.output_path, render(results)?).with_context(|| {
+            format!(
+                "Failed to write TAP report file: {}",
+                self.output_path.display()
+            )
+        })
+    }
+}
+
+fn render(results: &TestResults) -> Result<String> {
+    let mut out = format!("TAP version 14\n1..{}\n", results.total());
+    for (index, result) in results.all().iter().enumerate() {
+        let number = index + 1;
+        let description = escape_description(result.meta.name.as_deref().unwrap_or(&result.name));
+        match result.status {
+            TestStatus::Pass => out.push_str(&format!("ok {number} - {description}\n")),
+            TestStatus::Skip => {
+                let reason = result.error_message.as_deref().unwrap_or("skipped");
+                out.push_str(&format!(
+                    "ok {number} - {description} # SKIP {}\n",
+                    single_line(reason)
+                ));
+            }
+            // TAP's TODO is exactly a quarantine: the failure is reported,
+            // but harnesses don't count it against the run.
+            TestStatus::Fail if result.quarantined => {
+                out.push_str(&format!(
+                    "not ok {number} - {description} # TODO quarantined\n"
+                ));
+                out.push_str(&yaml_block(&diagnostic(result))?);
+            }
+            TestStatus::Fail => {
+                out.push_str(&format!("not ok {number} - {description}\n"));
+                out.push_str(&yaml_block(&diagnostic(result))?);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// The YAML diagnostic of a failed test.
+fn diagnostic(result: &TestResult) -> Value {
+    let mut diag = Map::new();
+    diag.insert(
+        "message".into(),
+        json!(result.error_message.as_deref().unwrap_or("Test failed")),
+    );
+    diag.insert(
+        "severity".into(),
+        json!(if result.quarantined { "todo" } else { "fail" }),
+    );
+    diag.insert("file".into(), json!(result.name));
+    diag.insert("duration_ms".into(), json!(result.duration_ms));
+    if let Some(code) = result.grpc_status {
+        diag.insert(
+            "grpc_status".into(),
+            json!({
+                "code": code,
+                "name": TestRunner::grpc_code_name_from_numeric(i64::from(code)).unwrap_or("Unknown"),
+            }),
+        );
+    }
+    let failed: Vec<Value> = result
+        .assertions
+        .iter()
+        .filter(|a| !a.passed)
+        .map(|a| {
+            let mut entry = Map::new();
+            entry.insert("line".into(), json!(a.line));
+            entry.insert("expression".into(), json!(a.expression));
+            if let Some(expected) = &a.expected {
+                entry.insert("expected".into(), json!(expected));
+            }
+            if let Some(actual) = &a.actual {
+                entry.insert("actual".into(), json!(actual));
+            }
+            if a.expected.is_none()
+                && let Some(message) = &a.message
+            {
+                entry.insert("message".into(), json!(message));
+            }
+            Value::Object(entry)
+        })
+        .collect();
+    if !failed.is_empty() {
+        diag.insert("assertions".into(), Value::Array(failed));
+    }
+    if result.retried {
+        diag.insert("retried".into(), json!(true));
+    }
+    if let Some(trace) = &result.trace {
+        diag.insert("trace_id".into(), json!(trace.trace_id));
+        if let Some(url) = &trace.url {
+            diag.insert("trace_url".into(), json!(url));
+        }
+    }
+    Value::Object(diag)
+}
+
+/// `---`/`...`-fenced YAML, indented two spaces under its test line.
+fn yaml_block(diagnostic: &Value) -> Result<String> {
+    let yaml =
+        serde_yaml_ng::to_string(diagnostic).context("Failed to serialize TAP diagnostic")?;
+    let mut block = String::from("  ---\n");
+    for line in yaml.lines() {
+        block.push_str("  ");
+        block.push_str(line);
+        block.push('\n');
+    }
+    block.push_str("  ...\n");
+    Ok(block)
+}
+
+/// A test point description is one line, and TAP 14 reserves `#` (directive)
+/// and `\` (its escape).
+fn escape_description(name: &str) -> String {
+    single_line(name).replace('\\', "\\\\").replace('#', "\\#")
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AssertionRecord;
+
+    fn failed_assertion() -> AssertionRecord {
+        AssertionRecord {
+            line: 11,
+            expression: ".status == \"NOT_SERVING\"".into(),
+            passed: false,
+            elapsed_ms: 0,
+            message: None,
+            endpoint: None,
+            expected: Some("== \"NOT_SERVING\"".into()),
+            actual: Some("\"SERVING\"".into()),
+        }
+    }
+
+    #[test]
+    fn tap_lines_directives_and_diagnostics() {
+        let mut results = TestResults::new();
+        results.add(TestResult::pass("t/a#1.gctf", 5, Some(4)));
+        results.add(
+            TestResult::fail("t/b.gctf", "Validation failed".into(), 7, Some(6))
+                .with_assertions(vec![failed_assertion()])
+                .with_grpc_status(Some(0)),
+        );
+        results.add(TestResult::skip("t/c.gctf", "Skipped by --tags".into()));
+        results.add(
+            TestResult::fail("t/d.gctf", "Unavailable".into(), 1, None)
+                .with_grpc_status(Some(14))
+                .with_quarantined(true),
+        );
+
+        let tap = render(&results).unwrap();
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(lines[0], "TAP version 14");
+        assert_eq!(lines[1], "1..4");
+        assert_eq!(lines[2], "ok 1 - t/a\\#1.gctf");
+        assert_eq!(lines[3], "not ok 2 - t/b.gctf");
+        assert_eq!(lines[4], "  ---");
+        assert!(
+            tap.contains("  grpc_status:\n    code: 0\n    name: OK\n"),
+            "{tap}"
+        );
+        assert!(tap.contains("    expected: == \"NOT_SERVING\"\n"), "{tap}");
+        assert!(tap.contains("    actual: '\"SERVING\"'\n"), "{tap}");
+        assert!(
+            tap.contains("ok 3 - t/c.gctf # SKIP Skipped by --tags\n"),
+            "{tap}"
+        );
+        assert!(
+            tap.contains("not ok 4 - t/d.gctf # TODO quarantined\n"),
+            "{tap}"
+        );
+        assert!(tap.contains("    name: Unavailable\n"), "{tap}");
+        assert!(tap.ends_with("  ...\n"), "{tap}");
+    }
+}
@@ -1 +1,4 @@
 pub use apif_semantics::*;
+
+pub mod schema;
+pub mod schema_diff;
@@ -0,0 +1,807 @@
+//! Schema-aware checks: resolve a document's ENDPOINT through a
+//! `prost_reflect::DescriptorPool` and walk REQUEST/RESPONSE payloads against
+//! the method's input/output messages, and ERROR `details` against the
+//! messages their `@type`s name, using the proto3 JSON mapping rules `run`
+//! itself encodes with. Catches typo'd field names, wrong JSON types and
+//! invalid enum names before anything is sent.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+use prost_reflect::{DescriptorPool, FieldDescriptor, Kind, MessageDescriptor};
+use serde_json::Value;
+
+use crate::grpc::ProtoConfig;
+use crate::parser::ast::{GctfDocument, Section, SectionContent, SectionType};
+use crate::utils::section_content_line;
+
+pub const SCHEMA_LOAD_ERROR: &str = "SCHEMA_LOAD_ERROR";
+pub const SCHEMA_UNKNOWN_METHOD: &str = "SCHEMA_UNKNOWN_METHOD";
+pub const SCHEMA_UNKNOWN_FIELD: &str = "SCHEMA_UNKNOWN_FIELD";
+pub const SCHEMA_TYPE_MISMATCH: &str = "SCHEMA_TYPE_MISMATCH";
+pub const SCHEMA_INVALID_ENUM: &str = "SCHEMA_INVALID_ENUM";
+pub const SCHEMA_ONEOF_CONFLICT: &str = "SCHEMA_ONEOF_CONFLICT";
+pub const SCHEMA_MISSING_REQUIRED: &str = "SCHEMA_MISSING_REQUIRED";
+
+/// One schema violation. `line` is 1-based; `token` is the text on that line
+/// the finding is about (a field key, the endpoint), for editor underlining.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaFinding {
+    pub rule_id: &'static str,
+    pub line: usize,
+    pub token: String,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+impl SchemaFinding {
+    /// Everything but [`SCHEMA_LOAD_ERROR`] is a defect in the document. A
+    /// schema that can't be loaded only means the payloads went unchecked
+    /// (the protos may be generated later in CI), so it's a warning.
+    pub fn is_error(&self) -> bool {
+        self.rule_id != SCHEMA_LOAD_ERROR
+    }
+}
+
+/// Pools compiled from PROTO sections, keyed by the resolved config and
+/// invalidated when a listed file's mtime changes — the LSP re-checks on every
+/// keystroke and must not recompile the protos each time. Imported files are
+/// not fingerprinted; touching one of the listed files picks them up.
+static POOL_CACHE: LazyLock<Mutex<HashMap<String, CachedPool>>> = LazyLock::new(Default::default);
+
+/// Listed files' mtimes at load time, and the pool built from them.
+type CachedPool = (Vec<Option<SystemTime>>, Arc<DescriptorPool>);
+
+fn proto_config_fingerprint(config: &ProtoConfig) -> (String, Vec<Option<SystemTime>>) {
+    let paths: Vec<&String> = config.descriptor.iter().chain(&config.files).collect();
+    let key = format!(
+        "{:?}|{:?}|{:?}",
+        config.descriptor, config.files, config.import_paths
+    );
+    let mtimes = paths
+        .iter()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect();
+    (key, mtimes)
+}
+
+/// The pool a document's own PROTO section describes, or `None` when the
+/// document has no usable PROTO section.
+pub fn load_document_pool(doc: &GctfDocument) -> Option<anyhow::Result<Arc<DescriptorPool>>> {
+    let config =
+        crate::execution::runner_helpers::build_proto_config(doc, Path::new(&doc.file_path))?;
+    if config.descriptor.is_none() && config.files.is_empty() {
+        return None;
+    }
+    let (key, mtimes) = proto_config_fingerprint(&config);
+    if let Ok(cache) = POOL_CACHE.lock()
+        && let Some((cached_mtimes, pool)) = cache.get(&key)
+        && *cached_mtimes == mtimes
+    {
+        return Some(Ok(pool.clone()));
+    }
+    use apif_grpc_transport::tonic::descriptor::{
+        load_from_descriptor_file, load_from_proto_files,
+    };
+    let loaded = match &config.descriptor {
+        Some(descriptor) => load_from_descriptor_file(descriptor),
+        None => load_from_proto_files(&config.files, &config.import_paths),
+    };
+    Some(loaded.map(|pool| {
+        let pool = Arc::new(pool);
+        if let Ok(mut cache) = POOL_CACHE.lock() {
+            cache.insert(key, (mtimes, pool.clone()));
+        }
+        pool
+    }))
+}
+
+/// Check every document of the chain. A document's own PROTO section wins;
+/// `fallback` (from `check --proto/--descriptor`) covers documents without
+/// one. Documents with neither are skipped — `check` never dials a server.
+pub fn collect_schema_findings(
+    doc: &GctfDocument,
+    fallback: Option<&DescriptorPool>,
+) -> Vec<SchemaFinding> {
+    let mut findings = Vec::new();
+    for chain_doc in doc.iter_chain() {
+        match load_document_pool(chain_doc) {
+            Some(Ok(pool)) => check_document(chain_doc, &pool, &mut findings),
+            Some(Err(e)) => {
+                let line = chain_doc
+                    .first_section(SectionType::Proto)
+                    .map_or(1, |s| s.start_line + 1);
+                findings.push(SchemaFinding {
+                    rule_id: SCHEMA_LOAD_ERROR,
+                    line,
+                    token: "PROTO".to_string(),
+                    message: format!("Could not load the PROTO schema: {e:#}"),
+                    hint: Some(
+                        "Check the `files`/`import_paths`/`descriptor` paths (relative to this file)"
+                            .to_string(),
+                    ),
+                });
+            }
+            None => {
+                if let Some(pool) = fallback {
+                    check_document(chain_doc, pool, &mut findings);
+                }
+            }
+        }
+    }
+    findings
+}
+
+fn check_document(doc: &GctfDocument, pool: &DescriptorPool, findings: &mut Vec<SchemaFinding>) {
+    let Some(section) = doc.first_section(SectionType::Endpoint) else {
+        return;
+    };
+    let Some(endpoint) = doc.get_endpoint() else {
+        return;
+    };
+    if endpoint.contains("{{") {
+        return;
+    }
+    let line = section_content_line(section.start_line, 0);
+    let Some((service_name, method_name)) = endpoint.split_once('/') else {
+        return;
+    };
+
+    let Some(service) = pool.get_service_by_name(service_name) else {
+        let services: Vec<String> = pool.services().map(|s| s.full_name().to_string()).collect();
+        findings.push(SchemaFinding {
+            rule_id: SCHEMA_UNKNOWN_METHOD,
+            line,
+            token: service_name.to_string(),
+            message: format!("Service '{service_name}' is not defined in the schema"),
+            hint: Some(suggestion_hint(service_name, &services, "Known services")),
+        });
+        return;
+    };
+    let Some(method) = service.methods().find(|m| m.name() == method_name) else {
+        let methods: Vec<String> = service.methods().map(|m| m.name().to_string()).collect();
+        findings.push(SchemaFinding {
+            rule_id: SCHEMA_UNKNOWN_METHOD,
+            line,
+            token: method_name.to_string(),
+            message: format!("Service '{service_name}' has no method '{method_name}'"),
+            hint: Some(suggestion_hint(method_name, &methods, "Known methods")),
+        });
+        return;
+    };
+
+    for section in &doc.sections {
+        let (descriptor, check_required) = match section.section_type {
+            SectionType::Request => (method.input(), true),
+            // A response never carries defaults on the wire, so "missing" is
+            // not a defect there.
+            SectionType::Response => (method.output(), false),
+            SectionType::Error => {
+                if let SectionContent::Json(status) = &section.content {
+                    let mut walker = Walker {
+                        locator: Locator::new(section),
+                        check_required: false,
+                        findings,
+                    };
+                    walker.status_details(pool, status);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let values: &[Value] = match &section.content {
+            SectionContent::Json(v) => std::slice::from_ref(v),
+            SectionContent::JsonLines(vs) => vs,
+            _ => continue,
+        };
+        let mut walker = Walker {
+            locator: Locator::new(section),
+            check_required,
+            findings,
+        };
+        for value in values {
+            walker.message(&descriptor, value, "");
+        }
+    }
+}
+
+/// Maps a JSON key back to the section line it was written on. Keys are
+/// searched in document order (`serde_json` preserves it), so a cursor that
+/// only moves forward tells repeated names in nested objects apart.
+pub(super) struct Locator<'a> {
+    lines: Vec<&'a str>,
+    start_line: usize,
+    cursor: usize,
+}
+
+impl<'a> Locator<'a> {
+    pub(super) fn new(section: &'a Section) -> Self {
+        Self {
+            lines: section.raw_content.lines().collect(),
+            start_line: section.start_line,
+            cursor: 0,
+        }
+    }
+
+    pub(super) fn key_line(&mut self, key: &str) -> usize {
+        let quoted = format!("\"{key}\"");
+        let found = self.lines[self.cursor.min(self.lines.len())..]
+            .iter()
+            .position(|line| {
+                let trimmed = line.trim_start().trim_start_matches(['{', '[', ',', ' ']);
+                line.contains(&quoted)
+                    || trimmed
+                        .strip_prefix(key)
+                        .is_some_and(|rest| rest.trim_start().starts_with(':'))
+            });
+        if let Some(offset) = found {
+            self.cursor += offset;
+        }
+        self.current()
+    }
+
+    pub(super) fn current(&self) -> usize {
+        section_content_line(self.start_line, self.cursor)
+    }
+}
+
+struct Walker<'a, 'f> {
+    locator: Locator<'a>,
+    check_required: bool,
+    findings: &'f mut Vec<SchemaFinding>,
+}
+
+impl Walker<'_, '_> {
+    fn push(
+        &mut self,
+        rule_id: &'static str,
+        line: usize,
+        token: &str,
+        message: String,
+        hint: Option<String>,
+    ) {
+        self.findings.push(SchemaFinding {
+            rule_id,
+            line,
+            token: token.to_string(),
+            message,
+            hint,
+        });
+    }
+
+    fn message(&mut self, desc: &MessageDescriptor, value: &Value, path: &str) {
+        if self.well_known(desc, value, path) {
+            return;
+        }
+        let Value::Object(map) = value else {
+            let line = self.locator.current();
+            self.mismatch(line, path, &format!("message {}", desc.full_name()), value);
+            return;
+        };
+
+        let object_line = self.locator.current();
+        let mut oneofs: HashMap<String, String> = HashMap::new();
+        for (key, field_value) in map {
+            let line = self.locator.key_line(key);
+            let field_path = join_path(path, key);
+            let Some(field) = desc
+                .get_field_by_json_name(key)
+                .or_else(|| desc.get_field_by_name(key))
+            else {
+                let known: Vec<String> = desc.fields().map(|f| f.json_name().to_string()).collect();
+                self.push(
+                    SCHEMA_UNKNOWN_FIELD,
+                    line,
+                    key,
+                    format!(
+                        "Unknown field '{field_path}' — {} has no field '{key}'",
+                        desc.full_name()
+                    ),
+                    Some(suggestion_hint(key, &known, "Known fields")),
+                );
+                continue;
+            };
+            // `null` is the proto3 JSON spelling of "unset".
+            if field_value.is_null() {
+                continue;
+            }
+            if let Some(oneof) = field.containing_oneof().filter(|o| !o.is_synthetic()) {
+                if let Some(first) = oneofs.get(oneof.name()) {
+                    self.push(
+                        SCHEMA_ONEOF_CONFLICT,
+                        line,
+                        key,
+                        format!(
+                            "Fields '{first}' and '{key}' are both set, but belong to oneof '{}' of {}",
+                            oneof.name(),
+                            desc.full_name()
+                        ),
+                        Some("Set only one member of a oneof".to_string()),
+                    );
+                } else {
+                    oneofs.insert(oneof.name().to_string(), key.clone());
+                }
+            }
+            self.field(&field, field_value, &field_path, line);
+        }
+
+        if self.check_required {
+            for field in desc.fields().filter(|f| f.is_required()) {
+                let present = map
+                    .get(field.json_name())
+                    .or_else(|| map.get(field.name()))
+                    .is_some_and(|v| !v.is_null());
+                if !present {
+                    self.push(
+                        SCHEMA_MISSING_REQUIRED,
+                        object_line,
+                        "",
+                        format!(
+                            "Required field '{}' of {} is missing",
+                            join_path(path, field.json_name()),
+                            desc.full_name()
+                        ),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    /// ERROR `details`: each entry is an `Any` in its JSON form, checked
+    /// against the message its `@type` names. Types the schema doesn't define
+    /// (say, the `google.rpc` ones the runner decodes itself) go unchecked.
+    fn status_details(&mut self, pool: &DescriptorPool, status: &Value) {
+        let Some(details) = status.get("details").and_then(Value::as_array) else {
+            return;
+        };
+        self.locator.key_line("details");
+        for (index, detail) in details.iter().enumerate() {
+            let Some(type_url) = detail.get("@type").and_then(Value::as_str) else {
+                continue;
+            };
+            let type_name = type_url.rsplit('/').next().unwrap_or(type_url);
+            let Some(desc) = pool.get_message_by_name(type_name) else {
+                continue;
+            };
+            let mut fields = detail.as_object().cloned().unwrap_or_default();
+            fields.remove("@type");
+            self.message(&desc, &Value::Object(fields), &format!("details[{index}]"));
+        }
+    }
+
+    fn field(&mut self, field: &FieldDescriptor, value: &Value, path: &str, line: usize) {
+        if field.is_map() {
+            let Kind::Message(entry) = field.kind() else {
+                return;
+            };
+            let Value::Object(map) = value else {
+                self.mismatch(line, path, "map (JSON object)", value);
+                return;
+            };
+            let key_kind = entry.map_entry_key_field().kind();
+            let value_field = entry.map_entry_value_field();
+            for (key, entry_value) in map {
+                let entry_path = format!("{path}[{key}]");
+                let key_value = Value::String(key.clone());
+                if !matches!(key_kind, Kind::String) {
+                    self.scalar(&key_kind, &key_value, &entry_path, line);
+                }
+                if !entry_value.is_null() {
+                    self.kind(&value_field.kind(), entry_value, &entry_path, line);
+                }
+            }
+        } else if field.is_list() {
+            let Value::Array(items) = value else {
+                self.mismatch(line, path, "repeated (JSON array)", value);
+                return;
+            };
+            for (i, item) in items.iter().enumerate() {
+                self.kind(&field.kind(), item, &format!("{path}[{i}]"), line);
+            }
+        } else {
+            self.kind(&field.kind(), value, path, line);
+        }
+    }
+
+    fn kind(&mut self, kind: &Kind, value: &Value, path: &str, line: usize) {
+        match kind {
+            Kind::Message(desc) => self.message(desc, value, path),
+            _ => self.scalar(kind, value, path, line),
+        }
+    }
+
+    fn scalar(&mut self, kind: &Kind, value: &Value, path: &str, line: usize) {
+        // A `{{var}}` placeholder is substituted at run time; its type is
+        // unknown until then.
+        if value.as_str().is_some_and(|s| s.contains("{{")) {
+            return;
+        }
+        let ok = match kind {
+            Kind::Bool => value.is_boolean(),
+            Kind::String | Kind::Bytes => value.is_string(),
+            Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+                integer_in(value, i32::MIN as i128, i32::MAX as i128)
+            }
+            Kind::Uint32 | Kind::Fixed32 => integer_in(value, 0, u32::MAX as i128),
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+                integer_in(value, i64::MIN as i128, i64::MAX as i128)
+            }
+            Kind::Uint64 | Kind::Fixed64 => integer_in(value, 0, u64::MAX as i128),
+            Kind::Float | Kind::Double => match value {
+                Value::Number(_) => true,
+                Value::String(s) => {
+                    matches!(s.as_str(), "NaN" | "Infinity" | "-Infinity")
+                        || s.parse::<f64>().is_ok()
+                }
+                _ => false,
+            },
+            Kind::Enum(enum_desc) => match value {
+                Value::String(name) => {
+                    if enum_desc.get_value_by_name(name).is_none() {
+                        let names: Vec<String> =
+                            enum_desc.values().map(|v| v.name().to_string()).collect();
+                        self.push(
+                            SCHEMA_INVALID_ENUM,
+                            line,
+                            name,
+                            format!(
+                                "'{name}' is not a value of enum {} (field '{path}')",
+                                enum_desc.full_name()
+                            ),
+                            Some(suggestion_hint(name, &names, "Valid values")),
+                        );
+                    }
+                    true
+                }
+                // Open enums accept unknown numbers.
+                Value::Number(n) => n.is_i64(),
+                _ => false,
+            },
+            Kind::Message(_) => true,
+        };
+        if !ok {
+            self.mismatch(line, path, &kind_name(kind), value);
+        }
+    }
+
+    /// Well-known types with a special JSON form. Returns `true` when `desc`
+    /// was one of them (and has been checked).
+    fn well_known(&mut self, desc: &MessageDescriptor, value: &Value, path: &str) -> bool {
+        let line = self.locator.current();
+        let expected = match desc.full_name() {
+            "google.protobuf.Timestamp"
+            | "google.protobuf.Duration"
+            | "google.protobuf.FieldMask" => {
+                if value.is_string() {
+                    return true;
+                }
+                "string"
+            }
+            "google.protobuf.Value" => return true,
+            "google.protobuf.Struct" | "google.protobuf.Any" => {
+                if value.is_object() {
+                    return true;
+                }
+                "JSON object"
+            }
+            "google.protobuf.ListValue" => {
+                if value.is_array() {
+                    return true;
+                }
+                "JSON array"
+            }
+            "google.protobuf.DoubleValue"
+            | "google.protobuf.FloatValue"
+            | "google.protobuf.Int64Value"
+            | "google.protobuf.UInt64Value"
+            | "google.protobuf.Int32Value"
+            | "google.protobuf.UInt32Value"
+            | "google.protobuf.BoolValue"
+            | "google.protobuf.StringValue"
+            | "google.protobuf.BytesValue" => {
+                if let Some(inner) = desc.get_field_by_name("value") {
+                    self.scalar(&inner.kind(), value, path, line);
+                }
+                return true;
+            }
+            _ => return false,
+        };
+        self.mismatch(
+            line,
+            path,
+            &format!("{} ({expected})", desc.full_name()),
+            value,
+        );
+        true
+    }
+
+    fn mismatch(&mut self, line: usize, path: &str, expected: &str, value: &Value) {
+        let shown = if path.is_empty() { "message" } else { path };
+        let token = path
+            .rsplit('.')
+            .next()
+            .and_then(|leaf| leaf.split('[').next())
+            .unwrap_or_default();
+        self.push(
+            SCHEMA_TYPE_MISMATCH,
+            line,
+            token,
+            format!(
+                "Field '{shown}' expects {expected}, got {} {}",
+                json_type_name(value),
+                truncate(&value.to_string())
+            ),
+            None,
+        );
+    }
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// Integral JSON number, or a string holding one (the proto3 JSON form of
+/// 64-bit integers), within `[min, max]`.
+fn integer_in(value: &Value, min: i128, max: i128) -> bool {
+    let parsed = match value {
+        Value::Number(n) => n
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+            .or_else(|| {
+                n.as_f64()
+                    .filter(|f| f.fract() == 0.0 && f.abs() < 1e38)
+                    .map(|f| f as i128)
+            }),
+        Value::String(s) => s.trim().parse::<i128>().ok(),
+        _ => None,
+    };
+    parsed.is_some_and(|n| (min..=max).contains(&n))
+}
+
+fn kind_name(kind: &Kind) -> String {
+    match kind {
+        Kind::Message(m) => format!("message {}", m.full_name()),
+        Kind::Enum(e) => format!("enum {}", e.full_name()),
+        other => format!("{other:?}").to_ascii_lowercase(),
+    }
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn truncate(s: &str) -> String {
+    const MAX: usize = 40;
+    if s.chars().count() <= MAX {
+        s.to_string()
+    } else {
+        format!("{}…", s.chars().take(MAX).collect::<String>())
+    }
+}
+
+/// "Did you mean 'x'?" when a candidate is within a small edit distance,
+/// otherwise a (capped) list of the candidates.
+fn suggestion_hint(unknown: &str, candidates: &[String], label: &str) -> String {
+    let best = candidates
+        .iter()
+        .map(|c| (c, edit_distance(&unknown.to_lowercase(), &c.to_lowercase())))
+        .filter(|(c, d)| *d <= (c.len() / 3).max(2))
+        .min_by_key(|(_, d)| *d);
+    if let Some((c, _)) = best {
+        return format!("Did you mean '{c}'?");
+    }
+    const SHOWN: usize = 10;
+    let mut list = candidates
+        .iter()
+        .take(SHOWN)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if candidates.len() > SHOWN {
+        list.push_str(", …");
+    }
+    if list.is_empty() {
+        list.push_str("(none)");
+    }
+    format!("{label}: {list}")
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Load a pool for `check --proto <file|dir>` / `--descriptor <set>`.
+pub fn load_flag_pool(
+    proto: Option<&Path>,
+    descriptor: Option<&Path>,
+) -> anyhow::Result<Option<DescriptorPool>> {
+    use apif_grpc_transport::tonic::descriptor::{
+        load_from_descriptor_file, load_from_proto_files,
+    };
+    if let Some(descriptor) = descriptor {
+        return load_from_descriptor_file(&descriptor.to_string_lossy()).map(Some);
+    }
+    let Some(proto) = proto else {
+        return Ok(None);
+    };
+    let (files, import_paths): (Vec<PathBuf>, PathBuf) = if proto.is_dir() {
+        let files = crate::commands::scaffold::collect_proto_files(proto)?;
+        anyhow::ensure!(
+            !files.is_empty(),
+            "No .proto files found in directory: {}",
+            proto.display()
+        );
+        (files, proto.to_path_buf())
+    } else {
+        let parent = proto
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        (vec![proto.to_path_buf()], parent)
+    };
+    let to_strings = |paths: &[PathBuf]| -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect()
+    };
+    load_from_proto_files(&to_strings(&files), &to_strings(&[import_paths])).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
+    use super::*;
+
+    const PROTO: &str = r#"
+syntax = "proto2";
+package shop;
+import "google/protobuf/timestamp.proto";
+service Orders {
+  rpc Create (CreateOrder) returns (Order);
+}
+enum Status { STATUS_UNKNOWN = 0; PENDING = 1; SHIPPED = 2; }
+message Item { optional string sku = 1; optional int32 quantity = 2; }
+message CreateOrder {
+  required string customer_id = 1;
+  optional int64 total_cents = 2;
+  repeated Item items = 3;
+  map<string, int32> tags = 4;
+  oneof delivery { string address = 5; string pickup_point = 6; }
+  optional google.protobuf.Timestamp due = 7;
+}
+message Order { optional Status status = 1; optional double weight = 2; }
+message Violation { optional string field = 1; optional string description = 2; }
+message OrderRejected { repeated Violation violations = 1; }
+"#;
+
+    fn pool() -> DescriptorPool {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("shop.proto");
+        std::fs::write(&file, PROTO).unwrap();
+        load_flag_pool(Some(&file), None).unwrap().unwrap()
+    }
+
+    fn findings(source: &str) -> Vec<SchemaFinding> {
+        let doc = crate::parser::parse_gctf_from_str(source, "t.gctf").unwrap();
+        collect_schema_findings(&doc, Some(&pool()))
+    }
+
+    fn codes(found: &[SchemaFinding]) -> Vec<&'static str> {
+        found.iter().map(|f| f.rule_id).collect()
+    }
+
+    #[test]
+    fn valid_payloads_have_no_findings() {
+        let found = findings(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\n  \"customerId\": \"c-1\",\n  \"total_cents\": \"1999\",\n  \"items\": [{\"sku\": \"A\", \"quantity\": 2}],\n  \"tags\": {\"gift\": 1},\n  \"address\": \"{{addr}}\",\n  \"due\": \"2026-01-01T00:00:00Z\"\n}\n\n--- RESPONSE ---\n{\"status\": \"PENDING\", \"weight\": \"NaN\"}\n",
+        );
+        assert!(found.is_empty(), "{found:?}");
+    }
+
+    #[test]
+    fn unknown_field_points_at_its_line_with_a_suggestion() {
+        let found = findings(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\n  \"customerId\": \"c-1\",\n  \"items\": [{\"skew\": \"A\"}]\n}\n",
+        );
+        assert_eq!(codes(&found), [SCHEMA_UNKNOWN_FIELD]);
+        assert_eq!(found[0].line, 7);
+        assert_eq!(found[0].token, "skew");
+        assert!(found[0].message.contains("items[0].skew"), "{found:?}");
+        assert_eq!(found[0].hint.as_deref(), Some("Did you mean 'sku'?"));
+    }
+
+    #[test]
+    fn wrong_json_types_and_enum_names_are_reported() {
+        let found = findings(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\"customerId\": \"c\", \"total_cents\": \"lots\", \"items\": {}, \"tags\": {\"a\": 1.5}}\n\n--- RESPONSE ---\n{\"status\": \"SHIPED\"}\n",
+        );
+        assert_eq!(
+            codes(&found),
+            [
+                SCHEMA_TYPE_MISMATCH,
+                SCHEMA_TYPE_MISMATCH,
+                SCHEMA_TYPE_MISMATCH,
+                SCHEMA_INVALID_ENUM
+            ]
+        );
+        assert!(found[0].message.contains("'total_cents' expects int64"));
+        assert!(found[1].message.contains("repeated"));
+        assert!(found[2].message.contains("tags[a]"));
+        assert_eq!(found[3].hint.as_deref(), Some("Did you mean 'SHIPPED'?"));
+    }
+
+    #[test]
+    fn oneof_conflicts_and_missing_required_fields() {
+        let found = findings(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\"address\": \"x\", \"pickupPoint\": \"y\"}\n",
+        );
+        assert_eq!(
+            codes(&found),
+            [SCHEMA_ONEOF_CONFLICT, SCHEMA_MISSING_REQUIRED]
+        );
+        assert!(found[1].message.contains("customerId"));
+    }
+
+    #[test]
+    fn unknown_service_and_method() {
+        let found = findings("--- ENDPOINT ---\nshop.Orders/Crate\n\n--- REQUEST ---\n{}\n");
+        assert_eq!(codes(&found), [SCHEMA_UNKNOWN_METHOD]);
+        assert_eq!(found[0].hint.as_deref(), Some("Did you mean 'Create'?"));
+
+        let found = findings("--- ENDPOINT ---\nshop.Order/Create\n\n--- REQUEST ---\n{}\n");
+        assert_eq!(codes(&found), [SCHEMA_UNKNOWN_METHOD]);
+        assert!(found[0].message.contains("Service 'shop.Order'"));
+    }
+
+    #[test]
+    fn error_details_are_checked_against_their_type() {
+        let found = findings(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\"customerId\": \"c\"}\n\n--- ERROR ---\n{\n  \"code\": 3,\n  \"details\": [\n    {\n      \"@type\": \"type.googleapis.com/shop.OrderRejected\",\n      \"violations\": [{\"feild\": \"sku\"}]\n    },\n    {\"@type\": \"type.googleapis.com/google.rpc.ErrorInfo\", \"reason\": \"X\"}\n  ]\n}\n",
+        );
+        assert_eq!(codes(&found), [SCHEMA_UNKNOWN_FIELD], "{found:?}");
+        assert_eq!(found[0].line, 13);
+        assert_eq!(found[0].token, "feild");
+        assert!(
+            found[0].message.contains("details[0].violations[0].feild"),
+            "{found:?}"
+        );
+        assert_eq!(found[0].hint.as_deref(), Some("Did you mean 'field'?"));
+    }
+
+    #[test]
+    fn documents_without_a_schema_are_skipped() {
+        let doc = crate::parser::parse_gctf_from_str(
+            "--- ENDPOINT ---\nshop.Orders/Create\n\n--- REQUEST ---\n{\"nope\": 1}\n",
+            "t.gctf",
+        )
+        .unwrap();
+        assert!(collect_schema_findings(&doc, None).is_empty());
+    }
+}
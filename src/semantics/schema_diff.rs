@@ -0,0 +1,860 @@
+//! `schema-diff`: compare two descriptor pools, classify every change as
+//! breaking or not for the JSON-speaking clients `.gctf` tests are, and find
+//! the test lines each breaking change would hit.
+//!
+//! Fields are matched by number, the way the wire does. Renames count as
+//! breaking: tests (and every other proto3 JSON client) address fields by
+//! name. Impact is resolved against the *old* schema, the one the suite was
+//! written for.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use prost_reflect::{
+    DescriptorPool, EnumDescriptor, FieldDescriptor, Kind, MessageDescriptor, MethodDescriptor,
+};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::schema::Locator;
+use crate::parser::ast::{GctfDocument, SectionContent, SectionType};
+use crate::utils::section_content_line;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    ServiceRemoved,
+    MethodRemoved,
+    MethodInputChanged,
+    MethodOutputChanged,
+    StreamingChanged,
+    MessageRemoved,
+    FieldRemoved,
+    FieldRenamed,
+    FieldNumberChanged,
+    FieldNumberReused,
+    FieldTypeChanged,
+    FieldLabelChanged,
+    EnumRemoved,
+    EnumValueRemoved,
+    EnumValueNumberChanged,
+    ServiceAdded,
+    MethodAdded,
+    MessageAdded,
+    FieldAdded,
+    EnumAdded,
+    EnumValueAdded,
+}
+
+impl ChangeKind {
+    pub fn is_breaking(self) -> bool {
+        !matches!(
+            self,
+            Self::ServiceAdded
+                | Self::MethodAdded
+                | Self::MessageAdded
+                | Self::FieldAdded
+                | Self::EnumAdded
+                | Self::EnumValueAdded
+        )
+    }
+}
+
+/// What a change is about, in the old schema's terms — the key impact
+/// lookup matches test references against.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Target {
+    Service(String),
+    Method(String, String),
+    Message(String),
+    Field(String, u32),
+    Enum(String),
+    EnumValue(String, String),
+    None,
+}
+
+/// One test line a breaking change touches.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Impact {
+    pub file: String,
+    pub line: usize,
+    pub section: &'static str,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    pub breaking: bool,
+    /// `package.Service/Method`, `package.Message.field`, `package.Enum.VALUE`…
+    pub subject: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub impacts: Vec<Impact>,
+    #[serde(skip)]
+    target: Target,
+}
+
+impl SchemaChange {
+    fn new(kind: ChangeKind, subject: String, message: String, target: Target) -> Self {
+        Self {
+            kind,
+            breaking: kind.is_breaking(),
+            subject,
+            message,
+            impacts: Vec::new(),
+            target,
+        }
+    }
+}
+
+/// Every change from `old` to `new`, breaking ones first, then by subject.
+/// Well-known types (`google.protobuf.*`) are left out: both pools carry them.
+pub fn diff_pools(old: &DescriptorPool, new: &DescriptorPool) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    diff_services(old, new, &mut changes);
+    diff_messages(old, new, &mut changes);
+    diff_enums(old, new, &mut changes);
+    changes.sort_by(|a, b| {
+        b.breaking
+            .cmp(&a.breaking)
+            .then_with(|| a.subject.cmp(&b.subject))
+            .then_with(|| a.kind.cmp(&b.kind))
+    });
+    changes
+}
+
+fn is_well_known(package: &str) -> bool {
+    package == "google.protobuf" || package.starts_with("google.protobuf.")
+}
+
+fn diff_services(old: &DescriptorPool, new: &DescriptorPool, changes: &mut Vec<SchemaChange>) {
+    for service in old.services() {
+        let name = service.full_name();
+        let Some(new_service) = new.get_service_by_name(name) else {
+            changes.push(SchemaChange::new(
+                ChangeKind::ServiceRemoved,
+                name.to_string(),
+                format!("Service {name} was removed"),
+                Target::Service(name.to_string()),
+            ));
+            continue;
+        };
+        for method in service.methods() {
+            let subject = format!("{name}/{}", method.name());
+            let target = Target::Method(name.to_string(), method.name().to_string());
+            let Some(new_method) = new_service.methods().find(|m| m.name() == method.name()) else {
+                changes.push(SchemaChange::new(
+                    ChangeKind::MethodRemoved,
+                    subject.clone(),
+                    format!("Method {subject} was removed"),
+                    target,
+                ));
+                continue;
+            };
+            if method.input().full_name() != new_method.input().full_name() {
+                changes.push(SchemaChange::new(
+                    ChangeKind::MethodInputChanged,
+                    subject.clone(),
+                    format!(
+                        "Request type of {subject} changed from {} to {}",
+                        method.input().full_name(),
+                        new_method.input().full_name()
+                    ),
+                    target.clone(),
+                ));
+            }
+            if method.output().full_name() != new_method.output().full_name() {
+                changes.push(SchemaChange::new(
+                    ChangeKind::MethodOutputChanged,
+                    subject.clone(),
+                    format!(
+                        "Response type of {subject} changed from {} to {}",
+                        method.output().full_name(),
+                        new_method.output().full_name()
+                    ),
+                    target.clone(),
+                ));
+            }
+            if streaming_mode(&method) != streaming_mode(&new_method) {
+                changes.push(SchemaChange::new(
+                    ChangeKind::StreamingChanged,
+                    subject.clone(),
+                    format!(
+                        "{subject} changed from {} to {}",
+                        streaming_mode(&method),
+                        streaming_mode(&new_method)
+                    ),
+                    target,
+                ));
+            }
+        }
+        for method in new_service.methods() {
+            if !service.methods().any(|m| m.name() == method.name()) {
+                let subject = format!("{name}/{}", method.name());
+                changes.push(SchemaChange::new(
+                    ChangeKind::MethodAdded,
+                    subject.clone(),
+                    format!("Method {subject} was added"),
+                    Target::None,
+                ));
+            }
+        }
+    }
+    for service in new.services() {
+        if old.get_service_by_name(service.full_name()).is_none() {
+            changes.push(SchemaChange::new(
+                ChangeKind::ServiceAdded,
+                service.full_name().to_string(),
+                format!("Service {} was added", service.full_name()),
+                Target::None,
+            ));
+        }
+    }
+}
+
+fn streaming_mode(method: &MethodDescriptor) -> &'static str {
+    match (method.is_client_streaming(), method.is_server_streaming()) {
+        (false, false) => "unary",
+        (false, true) => "server streaming",
+        (true, false) => "client streaming",
+        (true, true) => "bidirectional streaming",
+    }
+}
+
+/// Messages worth comparing: user-defined, not the synthetic map entries
+/// (their changes show up as the map field's type).
+fn user_messages(pool: &DescriptorPool) -> impl Iterator<Item = MessageDescriptor> + '_ {
+    pool.all_messages()
+        .filter(|m| !m.is_map_entry() && !is_well_known(m.package_name()))
+}
+
+fn diff_messages(old: &DescriptorPool, new: &DescriptorPool, changes: &mut Vec<SchemaChange>) {
+    for message in user_messages(old) {
+        let name = message.full_name();
+        match new.get_message_by_name(name) {
+            Some(new_message) => diff_fields(&message, &new_message, changes),
+            None => changes.push(SchemaChange::new(
+                ChangeKind::MessageRemoved,
+                name.to_string(),
+                format!("Message {name} was removed"),
+                Target::Message(name.to_string()),
+            )),
+        }
+    }
+    for message in user_messages(new) {
+        if old.get_message_by_name(message.full_name()).is_none() {
+            changes.push(SchemaChange::new(
+                ChangeKind::MessageAdded,
+                message.full_name().to_string(),
+                format!("Message {} was added", message.full_name()),
+                Target::None,
+            ));
+        }
+    }
+}
+
+fn diff_fields(old: &MessageDescriptor, new: &MessageDescriptor, changes: &mut Vec<SchemaChange>) {
+    let message = old.full_name();
+    for field in old.fields() {
+        let number = field.number();
+        let subject = format!("{message}.{}", field.name());
+        let target = Target::Field(message.to_string(), number);
+        match new.get_field(number) {
+            Some(new_field) if new_field.name() == field.name() => {
+                let (old_type, new_type) = (field_type(&field), field_type(&new_field));
+                if label(&field) != label(&new_field) {
+                    changes.push(SchemaChange::new(
+                        ChangeKind::FieldLabelChanged,
+                        subject,
+                        format!(
+                            "Field {} (#{number}) changed from {old_type} to {new_type}",
+                            field.name()
+                        ),
+                        target,
+                    ));
+                } else if old_type != new_type {
+                    changes.push(SchemaChange::new(
+                        ChangeKind::FieldTypeChanged,
+                        subject,
+                        format!(
+                            "Field {} (#{number}) changed type from {old_type} to {new_type}",
+                            field.name()
+                        ),
+                        target,
+                    ));
+                }
+            }
+            Some(new_field) if field_type(&new_field) == field_type(&field) => {
+                changes.push(SchemaChange::new(
+                    ChangeKind::FieldRenamed,
+                    subject,
+                    format!(
+                        "Field {} (#{number}) was renamed to {}",
+                        field.name(),
+                        new_field.name()
+                    ),
+                    target,
+                ));
+            }
+            Some(new_field) => changes.push(SchemaChange::new(
+                ChangeKind::FieldNumberReused,
+                subject,
+                format!(
+                    "Field number {number} ({} {}) is reused by {} {}",
+                    field_type(&field),
+                    field.name(),
+                    field_type(&new_field),
+                    new_field.name()
+                ),
+                target,
+            )),
+            None => match new.get_field_by_name(field.name()) {
+                Some(new_field) => changes.push(SchemaChange::new(
+                    ChangeKind::FieldNumberChanged,
+                    subject,
+                    format!(
+                        "Field {} changed number from {number} to {}",
+                        field.name(),
+                        new_field.number()
+                    ),
+                    target,
+                )),
+                None => changes.push(SchemaChange::new(
+                    ChangeKind::FieldRemoved,
+                    subject,
+                    format!("Field {} (#{number}) was removed", field.name()),
+                    target,
+                )),
+            },
+        }
+    }
+    for field in new.fields() {
+        if old.get_field(field.number()).is_none() && old.get_field_by_name(field.name()).is_none()
+        {
+            changes.push(SchemaChange::new(
+                ChangeKind::FieldAdded,
+                format!("{message}.{}", field.name()),
+                format!(
+                    "Field {} {} (#{}) was added",
+                    field_type(&field),
+                    field.name(),
+                    field.number()
+                ),
+                Target::None,
+            ));
+        }
+    }
+}
+
+fn label(field: &FieldDescriptor) -> &'static str {
+    if field.is_map() {
+        "map"
+    } else if field.is_list() {
+        "repeated"
+    } else {
+        "singular"
+    }
+}
+
+/// The field's type as a proto author would write it.
+fn field_type(field: &FieldDescriptor) -> String {
+    if field.is_map()
+        && let Kind::Message(entry) = field.kind()
+    {
+        return format!(
+            "map<{}, {}>",
+            kind_name(&entry.map_entry_key_field().kind()),
+            kind_name(&entry.map_entry_value_field().kind())
+        );
+    }
+    if field.is_list() {
+        format!("repeated {}", kind_name(&field.kind()))
+    } else {
+        kind_name(&field.kind())
+    }
+}
+
+fn kind_name(kind: &Kind) -> String {
+    match kind {
+        Kind::Message(m) => m.full_name().to_string(),
+        Kind::Enum(e) => e.full_name().to_string(),
+        other => format!("{other:?}").to_ascii_lowercase(),
+    }
+}
+
+fn user_enums(pool: &DescriptorPool) -> impl Iterator<Item = EnumDescriptor> + '_ {
+    pool.all_enums()
+        .filter(|e| !is_well_known(e.package_name()))
+}
+
+fn diff_enums(old: &DescriptorPool, new: &DescriptorPool, changes: &mut Vec<SchemaChange>) {
+    for old_enum in user_enums(old) {
+        let name = old_enum.full_name();
+        let Some(new_enum) = new.get_enum_by_name(name) else {
+            changes.push(SchemaChange::new(
+                ChangeKind::EnumRemoved,
+                name.to_string(),
+                format!("Enum {name} was removed"),
+                Target::Enum(name.to_string()),
+            ));
+            continue;
+        };
+        for value in old_enum.values() {
+            let subject = format!("{name}.{}", value.name());
+            let target = Target::EnumValue(name.to_string(), value.name().to_string());
+            match new_enum.get_value_by_name(value.name()) {
+                None => changes.push(SchemaChange::new(
+                    ChangeKind::EnumValueRemoved,
+                    subject,
+                    format!(
+                        "Enum value {} ({}) was removed",
+                        value.name(),
+                        value.number()
+                    ),
+                    target,
+                )),
+                Some(new_value) if new_value.number() != value.number() => {
+                    changes.push(SchemaChange::new(
+                        ChangeKind::EnumValueNumberChanged,
+                        subject,
+                        format!(
+                            "Enum value {} changed number from {} to {}",
+                            value.name(),
+                            value.number(),
+                            new_value.number()
+                        ),
+                        target,
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        for value in new_enum.values() {
+            if old_enum.get_value_by_name(value.name()).is_none() {
+                changes.push(SchemaChange::new(
+                    ChangeKind::EnumValueAdded,
+                    format!("{name}.{}", value.name()),
+                    format!("Enum value {} ({}) was added", value.name(), value.number()),
+                    Target::None,
+                ));
+            }
+        }
+    }
+    for new_enum in user_enums(new) {
+        if old.get_enum_by_name(new_enum.full_name()).is_none() {
+            changes.push(SchemaChange::new(
+                ChangeKind::EnumAdded,
+                new_enum.full_name().to_string(),
+                format!("Enum {} was added", new_enum.full_name()),
+                Target::None,
+            ));
+        }
+    }
+}
+
+/// Schema elements one test line refers to.
+#[derive(Default)]
+pub struct References {
+    by_target: BTreeMap<Target, BTreeSet<Impact>>,
+}
+
+impl References {
+    fn add(&mut self, target: Target, impact: Impact) {
+        self.by_target.entry(target).or_default().insert(impact);
+    }
+
+    /// Record what `doc`'s chain refers to, resolved through `pool`. A
+    /// document whose method `pool` doesn't know contributes only its
+    /// ENDPOINT line; a templated ENDPOINT contributes nothing.
+    pub fn collect(&mut self, doc: &GctfDocument, pool: &DescriptorPool) {
+        for chain_doc in doc.iter_chain() {
+            self.collect_document(chain_doc, pool);
+        }
+    }
+
+    fn collect_document(&mut self, doc: &GctfDocument, pool: &DescriptorPool) {
+        let (Some(section), Some(endpoint)) =
+            (doc.first_section(SectionType::Endpoint), doc.get_endpoint())
+        else {
+            return;
+        };
+        let Some((service_name, method_name)) = endpoint.split_once('/') else {
+            return;
+        };
+        if endpoint.contains("{{") {
+            return;
+        }
+        let line = section_content_line(section.start_line, 0);
+        let impact = |line: usize, section: &'static str, text: &str| Impact {
+            file: doc.file_path.clone(),
+            line,
+            section,
+            text: text.trim().to_string(),
+        };
+        let endpoint_impact = impact(line, "ENDPOINT", &endpoint);
+        self.add(
+            Target::Service(service_name.to_string()),
+            endpoint_impact.clone(),
+        );
+        self.add(
+            Target::Method(service_name.to_string(), method_name.to_string()),
+            endpoint_impact,
+        );
+        let Some(method) = pool
+            .get_service_by_name(service_name)
+            .and_then(|s| s.methods().find(|m| m.name() == method_name))
+        else {
+            return;
+        };
+
+        for section in &doc.sections {
+            let (descriptor, name) = match section.section_type {
+                SectionType::Request => (method.input(), "REQUEST"),
+                SectionType::Response => (method.output(), "RESPONSE"),
+                SectionType::Asserts => {
+                    let lines = section.raw_content.lines().enumerate();
+                    for (idx, text) in lines {
+                        let line = section_content_line(section.start_line, idx);
+                        for target in assertion_references(&method.output(), text) {
+                            self.add(target, impact(line, "ASSERTS", text));
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            let values: &[Value] = match &section.content {
+                SectionContent::Json(v) => std::slice::from_ref(v),
+                SectionContent::JsonLines(vs) => vs,
+                _ => continue,
+            };
+            let lines: Vec<&str> = section.raw_content.lines().collect();
+            let mut found = Vec::new();
+            let mut locator = Locator::new(section);
+            for value in values {
+                payload_references(&descriptor, value, &mut locator, &mut found);
+            }
+            for (target, line) in found {
+                let idx = line.saturating_sub(section.start_line + 2);
+                let text = lines.get(idx).copied().unwrap_or_default();
+                self.add(target, impact(line, name, text));
+            }
+        }
+    }
+
+    /// Attach to every breaking change the test lines that refer to it.
+    pub fn attach(&self, changes: &mut [SchemaChange]) {
+        for change in changes.iter_mut().filter(|c| c.breaking) {
+            let impacts: BTreeSet<Impact> = match &change.target {
+                // A removed enum hits every line using one of its values.
+                Target::Enum(name) => self
+                    .by_target
+                    .iter()
+                    .filter(|(t, _)| matches!(t, Target::EnumValue(e, _) if e == name))
+                    .flat_map(|(_, impacts)| impacts.iter().cloned())
+                    .collect(),
+                target => self.by_target.get(target).cloned().unwrap_or_default(),
+            };
+            change.impacts = impacts.into_iter().collect();
+        }
+    }
+}
+
+/// Fields, nested messages and enum values a payload sets, with the line
+/// each is written on.
+fn payload_references(
+    desc: &MessageDescriptor,
+    value: &Value,
+    locator: &mut Locator<'_>,
+    found: &mut Vec<(Target, usize)>,
+) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    found.push((
+        Target::Message(desc.full_name().to_string()),
+        locator.current(),
+    ));
+    for (key, field_value) in map {
+        let line = locator.key_line(key);
+        let Some(field) = desc
+            .get_field_by_json_name(key)
+            .or_else(|| desc.get_field_by_name(key))
+        else {
+            continue;
+        };
+        found.push((
+            Target::Field(desc.full_name().to_string(), field.number()),
+            line,
+        ));
+        let items: Vec<&Value> = match field_value {
+            Value::Array(items) if field.is_list() => items.iter().collect(),
+            Value::Object(entries) if field.is_map() => entries.values().collect(),
+            other => vec![other],
+        };
+        let kind = if field.is_map() {
+            match field.kind() {
+                Kind::Message(entry) => entry.map_entry_value_field().kind(),
+                other => other,
+            }
+        } else {
+            field.kind()
+        };
+        for item in items {
+            match &kind {
+                Kind::Message(nested) => payload_references(nested, item, locator, found),
+                Kind::Enum(enum_desc) => {
+                    if let Value::String(name) = item {
+                        found.push((
+                            Target::EnumValue(enum_desc.full_name().to_string(), name.clone()),
+                            line,
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Response fields an assertion line reads (`.user.email`, `.items[0].sku`)
+/// and enum values it compares them with.
+fn assertion_references(output: &MessageDescriptor, text: &str) -> Vec<Target> {
+    let mut found = Vec::new();
+    let literals = string_literals(text);
+    for path in field_paths(text) {
+        let mut current = output.clone();
+        for segment in path {
+            let Some(field) = current
+                .get_field_by_json_name(&segment)
+                .or_else(|| current.get_field_by_name(&segment))
+            else {
+                break;
+            };
+            found.push(Target::Field(
+                current.full_name().to_string(),
+                field.number(),
+            ));
+            match field.kind() {
+                Kind::Message(nested) if field.is_map() => {
+                    match nested.map_entry_value_field().kind() {
+                        Kind::Message(value) => current = value,
+                        _ => break,
+                    }
+                }
+                Kind::Message(nested) => current = nested,
+                Kind::Enum(enum_desc) => {
+                    for literal in &literals {
+                        if enum_desc.get_value_by_name(literal).is_some() {
+                            found.push(Target::EnumValue(
+                                enum_desc.full_name().to_string(),
+                                literal.clone(),
+                            ));
+                        }
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+    found
+}
+
+/// `.a.b[0].c` paths in an assertion, as their field names. A `.` right
+/// after a word, `)`, `]` or a quote continues an expression rather than
+/// starting a path, and string literals are skipped.
+fn field_paths(text: &str) -> Vec<Vec<String>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut paths = Vec::new();
+    let mut i = 0;
+    let mut in_string = false;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' && (i == 0 || chars[i - 1] != '\\') {
+            in_string = !in_string;
+            i += 1;
+            continue;
+        }
+        let starts_path = !in_string
+            && c == '.'
+            && (i == 0 || !(chars[i - 1].is_alphanumeric() || "_)]\"'".contains(chars[i - 1])));
+        if !starts_path {
+            i += 1;
+            continue;
+        }
+        let mut path = Vec::new();
+        while i < chars.len() && chars[i] == '.' {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                end += 1;
+            }
+            if end == start {
+                break;
+            }
+            path.push(chars[start..end].iter().collect());
+            i = end;
+            // Skip `[index]` / `["key"]` between segments.
+            while i < chars.len() && chars[i] == '[' {
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+                i += 1;
+            }
+        }
+        if !path.is_empty() {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+fn string_literals(text: &str) -> Vec<String> {
+    text.split('"')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used, clippy::expect_used)] // audited safe
+    use super::*;
+
+    fn pool(source: &str) -> DescriptorPool {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api.proto");
+        std::fs::write(&path, source).unwrap();
+        crate::semantics::schema::load_flag_pool(Some(&path), None)
+            .unwrap()
+            .unwrap()
+    }
+
+    const OLD: &str = r#"
+syntax = "proto3";
+package shop;
+service Orders {
+  rpc Get (GetOrder) returns (Order);
+  rpc Watch (GetOrder) returns (Order);
+  rpc Cancel (GetOrder) returns (Order);
+}
+enum Status { STATUS_UNKNOWN = 0; PENDING = 1; SHIPPED = 2; }
+message GetOrder { string id = 1; }
+message Order {
+  string id = 1;
+  string customer = 2;
+  int64 total = 3;
+  Status status = 4;
+  repeated string tags = 5;
+  string note = 6;
+}
+"#;
+
+    const NEW: &str = r#"
+syntax = "proto3";
+package shop;
+service Orders {
+  rpc Get (GetOrder) returns (Order);
+  rpc Watch (GetOrder) returns (stream Order);
+  rpc Track (GetOrder) returns (Order);
+}
+enum Status { STATUS_UNKNOWN = 0; SHIPPED = 2; DELIVERED = 3; }
+message GetOrder { string id = 1; }
+message Order {
+  string id = 1;
+  string customer_name = 2;
+  string total = 3;
+  Status status = 4;
+  string tags = 5;
+  int32 priority = 6;
+  string comment = 7;
+}
+"#;
+
+    fn kinds(changes: &[SchemaChange]) -> Vec<(ChangeKind, &str)> {
+        changes
+            .iter()
+            .map(|c| (c.kind, c.subject.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let changes = diff_pools(&pool(OLD), &pool(NEW));
+        let kinds = kinds(&changes);
+        for expected in [
+            (ChangeKind::StreamingChanged, "shop.Orders/Watch"),
+            (ChangeKind::MethodRemoved, "shop.Orders/Cancel"),
+            (ChangeKind::FieldRenamed, "shop.Order.customer"),
+            (ChangeKind::FieldTypeChanged, "shop.Order.total"),
+            (ChangeKind::FieldLabelChanged, "shop.Order.tags"),
+            (ChangeKind::FieldNumberReused, "shop.Order.note"),
+            (ChangeKind::EnumValueRemoved, "shop.Status.PENDING"),
+            (ChangeKind::MethodAdded, "shop.Orders/Track"),
+            (ChangeKind::FieldAdded, "shop.Order.comment"),
+            (ChangeKind::EnumValueAdded, "shop.Status.DELIVERED"),
+        ] {
+            assert!(kinds.contains(&expected), "{expected:?} not in {kinds:?}");
+        }
+        assert!(changes.iter().all(|c| !c.subject.starts_with("google.")));
+        let first_additive = changes.iter().position(|c| !c.breaking).unwrap();
+        assert!(changes[first_additive..].iter().all(|c| !c.breaking));
+    }
+
+    #[test]
+    fn no_changes_between_identical_schemas() {
+        assert!(diff_pools(&pool(OLD), &pool(OLD)).is_empty());
+    }
+
+    #[test]
+    fn impacts_point_at_request_response_and_assert_lines() {
+        let source = "--- ENDPOINT ---\n\
+                      shop.Orders/Get\n\
+                      \n\
+                      --- REQUEST ---\n\
+                      {\"id\": \"1\"}\n\
+                      \n\
+                      --- RESPONSE ---\n\
+                      {\n  \"customer\": \"ann\",\n  \"status\": \"PENDING\"\n}\n\
+                      \n\
+                      --- ASSERTS ---\n\
+                      .total > 10\n\
+                      @len(.tags) == 2\n";
+        let doc = crate::parser::parse_gctf_from_str(source, "t.gctf").unwrap();
+        let old = pool(OLD);
+        let mut changes = diff_pools(&old, &pool(NEW));
+        let mut references = References::default();
+        references.collect(&doc, &old);
+        references.attach(&mut changes);
+
+        let lines = |subject: &str| -> Vec<(usize, &'static str)> {
+            changes
+                .iter()
+                .find(|c| c.subject == subject)
+                .unwrap()
+                .impacts
+                .iter()
+                .map(|i| (i.line, i.section))
+                .collect()
+        };
+        assert_eq!(lines("shop.Order.customer"), vec![(9, "RESPONSE")]);
+        assert_eq!(lines("shop.Status.PENDING"), vec![(10, "RESPONSE")]);
+        assert_eq!(lines("shop.Order.total"), vec![(14, "ASSERTS")]);
+        assert_eq!(lines("shop.Order.tags"), vec![(15, "ASSERTS")]);
+        assert!(lines("shop.Orders/Cancel").is_empty());
+    }
+
+    #[test]
+    fn field_paths_skip_strings_and_method_calls() {
+        assert_eq!(
+            field_paths(r#".items[0].sku == "a.b" && @len(.tags) > .x.y"#),
+            vec![
+                vec!["items".to_string(), "sku".to_string()],
+                vec!["tags".to_string()],
+                vec!["x".to_string(), "y".to_string()],
+            ]
+        );
+    }
+}
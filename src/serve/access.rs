@@ -0,0 +1,400 @@
+//! Access control for a playground shared over the network: who may use it
+//! (`--auth-token`, `--htpasswd`), cross-site request protection,
+//! `--read-only`, and the `--audit-log` of state-changing requests.
+
+use anyhow::{Context, Result};
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, HeaderValue, Method, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use sha2::{Digest, Sha256};
+
+use super::PlayState;
+use super::htpasswd::{Htpasswd, constant_time_eq};
+
+/// Cookie a browser gets after opening the playground with `?token=`.
+const TOKEN_COOKIE: &str = "grpctestify_play_token";
+/// The largest request body read for the audit log; axum's own JSON limit.
+const AUDIT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+/// Request fields copied into audit entries. Bodies and headers are not.
+const AUDIT_FIELDS: &[&str] = &[
+    "endpoint",
+    "address",
+    "collection_path",
+    "path",
+    "from",
+    "to",
+];
+
+/// The `play` flags that restrict access. The default is an open,
+/// writable playground without an audit log.
+#[derive(Default)]
+pub struct AccessControl {
+    token: Option<String>,
+    users: Option<Htpasswd>,
+    pub read_only: bool,
+    audit: Option<AuditLog>,
+    /// SHA-256 of `Authorization: Basic` values already verified, to their
+    /// user: SHA-crypt is slow on purpose, and the cache shouldn't hold the
+    /// passwords it saves checking.
+    verified: Mutex<HashMap<Vec<u8>, String>>,
+}
+
+enum AuditLog {
+    Stdout,
+    File(Mutex<std::fs::File>),
+}
+
+/// The authenticated user of a request: the htpasswd user name, or
+/// `token` for `--auth-token`.
+#[derive(Clone, Debug)]
+pub struct AuthUser(pub String);
+
+impl AccessControl {
+    pub fn new(
+        token: Option<String>,
+        htpasswd: Option<&Path>,
+        read_only: bool,
+        audit_log: Option<&Path>,
+    ) -> Result<Self> {
+        if token.as_deref().is_some_and(str::is_empty) {
+            anyhow::bail!("--auth-token must not be empty");
+        }
+        let users = htpasswd.map(Htpasswd::load).transpose()?;
+        let audit = match audit_log {
+            None => None,
+            Some(path) if path == Path::new("-") => Some(AuditLog::Stdout),
+            Some(path) => {
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                Some(AuditLog::File(Mutex::new(file)))
+            }
+        };
+        Ok(Self {
+            token,
+            users,
+            read_only,
+            audit,
+            verified: Mutex::default(),
+        })
+    }
+
+    pub fn requires_auth(&self) -> bool {
+        self.token.is_some() || self.users.is_some()
+    }
+
+    /// One line for the startup banner, e.g. `htpasswd (3 users), read-only`.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.token.is_some() {
+            parts.push("token".to_string());
+        }
+        if let Some(users) = &self.users {
+            let n = users.len();
+            parts.push(format!(
+                "htpasswd ({n} user{})",
+                if n == 1 { "" } else { "s" }
+            ));
+        }
+        if self.read_only {
+            parts.push("read-only".to_string());
+        }
+        if self.audit.is_some() {
+            parts.push("audit log".to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    fn token_matches(&self, candidate: &str) -> bool {
+        self.token
+            .as_deref()
+            .is_some_and(|t| constant_time_eq(t.as_bytes(), candidate.as_bytes()))
+    }
+
+    fn user(&self, headers: &HeaderMap) -> Option<String> {
+        if let Some(auth) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        {
+            if let Some(token) = auth.strip_prefix("Bearer ") {
+                return self
+                    .token_matches(token.trim())
+                    .then(|| "token".to_string());
+            }
+            if let Some(basic) = auth.strip_prefix("Basic ") {
+                return self.basic_user(basic.trim());
+            }
+        }
+        headers
+            .get_all(header::COOKIE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(';'))
+            .filter_map(|c| c.trim().strip_prefix(TOKEN_COOKIE)?.strip_prefix('='))
+            .any(|token| self.token_matches(token))
+            .then(|| "token".to_string())
+    }
+
+    fn basic_user(&self, encoded: &str) -> Option<String> {
+        let users = self.users.as_ref()?;
+        let key = Sha256::digest(encoded.as_bytes()).to_vec();
+        let mut verified = self.verified.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(user) = verified.get(&key) {
+            return Some(user.clone());
+        }
+        let decoded = apif_grpc_transport::encoding::base64_decode(encoded.as_bytes())?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let (user, password) = decoded.split_once(':')?;
+        if !users.verify(user, password) {
+            return None;
+        }
+        if verified.len() >= 1024 {
+            verified.clear();
+        }
+        verified.insert(key, user.to_string());
+        Some(user.to_string())
+    }
+
+    fn audit(&self, entry: &serde_json::Value) {
+        let Some(log) = &self.audit else {
+            return;
+        };
+        let line = entry.to_string();
+        match log {
+            AuditLog::Stdout => println!("{line}"),
+            AuditLog::File(file) => {
+                let mut file = file.lock().unwrap_or_else(|e| e.into_inner());
+                if let Err(e) = writeln!(file, "{line}") {
+                    tracing::warn!("Failed to write the audit log: {}.", e);
+                }
+            }
+        }
+    }
+}
+
+/// The `token` parameter of a query string, percent- and `+`-decoded.
+fn query_token(query: &str) -> Option<String> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == "token")
+        .map(|(_, value)| value.into_owned())
+}
+
+fn is_unsafe(method: &Method) -> bool {
+    !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+/// Rejects requests without valid credentials; `/api/health` stays open for
+/// load balancers. A browser opened at `/?token=...` gets the token as a
+/// cookie and is redirected to the page without it.
+pub async fn authenticate(
+    State(state): State<Arc<PlayState>>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let access = &state.access;
+    if !access.requires_auth() || req.uri().path() == "/api/health" {
+        return next.run(req).await;
+    }
+
+    if req.method() == Method::GET
+        && let Some(token) = req.uri().query().and_then(query_token)
+        && access.token_matches(&token)
+    {
+        let cookie = format!("{TOKEN_COOKIE}={token}; Path=/; HttpOnly; SameSite=Strict");
+        let mut response = (
+            StatusCode::SEE_OTHER,
+            [(header::LOCATION, req.uri().path().to_string())],
+        )
+            .into_response();
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            response.headers_mut().insert(header::SET_COOKIE, cookie);
+        }
+        return response;
+    }
+
+    match access.user(req.headers()) {
+        Some(user) => {
+            req.extensions_mut().insert(AuthUser(user));
+            next.run(req).await
+        }
+        None => {
+            let challenge = if access.users.is_some() {
+                "Basic realm=\"grpctestify play\", charset=\"UTF-8\""
+            } else {
+                "Bearer"
+            };
+            (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, challenge)],
+                "Authentication required",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Cross-site request forgery guard: a state-changing request a browser
+/// sends on another site's behalf carries that site's `Origin` (or
+/// `Sec-Fetch-Site: cross-site`). Cookies and basic-auth credentials ride
+/// along, so such requests are refused. Clients that send neither header,
+/// such as `curl`, aren't browsers and can't be forged this way.
+pub async fn reject_cross_site(req: Request<Body>, next: Next) -> Response {
+    if is_unsafe(req.method()) && !same_origin(req.headers()) {
+        return (StatusCode::FORBIDDEN, "Cross-site request refused").into_response();
+    }
+    next.run(req).await
+}
+
+fn same_origin(headers: &HeaderMap) -> bool {
+    let value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(origin) = value("origin") {
+        // Behind a reverse proxy the browser's host is the forwarded one. A
+        // cross-site page can't add that header without a CORS preflight,
+        // which this server never grants.
+        let hosts = [
+            value("x-forwarded-host").and_then(|h| h.split(',').next()),
+            value("host"),
+        ];
+        return origin.split_once("://").is_some_and(|(_, authority)| {
+            hosts
+                .into_iter()
+                .flatten()
+                .any(|host| host.trim().eq_ignore_ascii_case(authority))
+        });
+    }
+    !matches!(value("sec-fetch-site"), Some("cross-site" | "same-site"))
+}
+
+/// Refuses the routes that write collections, shares, settings or `.env`
+/// files under `--read-only`.
+pub async fn read_only_guard(
+    State(state): State<Arc<PlayState>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if state.access.read_only {
+        return (StatusCode::FORBIDDEN, "The playground is read-only").into_response();
+    }
+    next.run(req).await
+}
+
+/// Writes one audit entry per state-changing request: who, from where, the
+/// route, the outcome, and what it named (endpoint, address, file path).
+pub async fn audit(
+    State(state): State<Arc<PlayState>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if state.access.audit.is_none() || !is_unsafe(req.method()) {
+        return next.run(req).await;
+    }
+
+    let (parts, body) = req.into_parts();
+    let Ok(bytes) = axum::body::to_bytes(body, AUDIT_BODY_LIMIT).await else {
+        return (StatusCode::PAYLOAD_TOO_LARGE, "Request body too large").into_response();
+    };
+    let mut entry = serde_json::json!({
+        "timestamp": apif_cfg_runtime::now_unix_millis(),
+        "user": parts.extensions.get::<AuthUser>().map(|u| u.0.clone()),
+        "remote": parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|c| c.0.to_string()),
+        "method": parts.method.as_str(),
+        "path": parts.uri.path(),
+    });
+    if let Ok(serde_json::Value::Object(fields)) = serde_json::from_slice(&bytes) {
+        for &key in AUDIT_FIELDS {
+            if let Some(value) = fields.get(key).filter(|v| v.is_string()) {
+                entry[if key == "path" { "file" } else { key }] = value.clone();
+            }
+        }
+    }
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+    entry["status"] = response.status().as_u16().into();
+    state.access.audit(&entry);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in pairs {
+            map.insert(*k, HeaderValue::from_str(v).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn same_origin_compares_origin_with_host() {
+        assert!(same_origin(&headers(&[
+            ("host", "play.internal:4755"),
+            ("origin", "http://play.internal:4755"),
+        ])));
+        assert!(!same_origin(&headers(&[
+            ("host", "play.internal:4755"),
+            ("origin", "https://evil.example"),
+        ])));
+        assert!(!same_origin(&headers(&[
+            ("host", "play.internal:4755"),
+            ("origin", "null"),
+        ])));
+        assert!(same_origin(&headers(&[
+            ("host", "127.0.0.1:4755"),
+            ("x-forwarded-host", "play.example.com"),
+            ("origin", "https://play.example.com"),
+        ])));
+        assert!(!same_origin(&headers(&[("sec-fetch-site", "cross-site")])));
+        assert!(same_origin(&headers(&[("sec-fetch-site", "same-origin")])));
+        assert!(same_origin(&headers(&[("host", "play.internal:4755")])));
+    }
+
+    #[test]
+    fn token_is_read_from_bearer_or_cookie() {
+        let access = AccessControl::new(Some("s3cret".into()), None, false, None).unwrap();
+        assert_eq!(
+            access.user(&headers(&[("authorization", "Bearer s3cret")])),
+            Some("token".into())
+        );
+        assert_eq!(
+            access.user(&headers(&[(
+                "cookie",
+                "theme=dark; grpctestify_play_token=s3cret"
+            )])),
+            Some("token".into())
+        );
+        assert_eq!(
+            access.user(&headers(&[("authorization", "Bearer s3cre")])),
+            None
+        );
+        assert_eq!(access.user(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn query_token_is_url_decoded() {
+        assert_eq!(query_token("token=s3cret").as_deref(), Some("s3cret"));
+        assert_eq!(
+            query_token("theme=dark&token=a%2Bb+c%26d").as_deref(),
+            Some("a+b c&d")
+        );
+        assert_eq!(query_token("tokens=s3cret"), None);
+    }
+}
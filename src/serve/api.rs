@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use super::{PlayState, ShareState};
-use std::collections::HashMap;
+use crate::grpc::MetadataMap;
 
 /// Consistent JSON error response.
 #[derive(Serialize)]
@@ -19,7 +19,7 @@ pub struct ApiError {
 /// component — filenames like `foo..gctf` are legal — so split on both `/`
 /// and `\` (Windows) and check components. Absolute paths (unix `/…`,
 /// Windows `C:\…` or `\\server\…`) are rejected too.
-fn reject_traversal(path: &str) -> Result<(), (StatusCode, String)> {
+pub(crate) fn reject_traversal(path: &str) -> Result<(), (StatusCode, String)> {
     let not_found = || (StatusCode::NOT_FOUND, "File not found".to_string());
     if path.starts_with('/') || path.starts_with('\\') {
         return Err(not_found());
@@ -86,7 +86,7 @@ fn tls_config_from_request(
 /// Defense in depth: the canonicalized result must stay inside the
 /// canonicalized collections dir (catches anything reject_traversal missed
 /// and symlinks pointing outside the collection roots).
-fn resolve_file(state: &PlayState, rel: &str) -> Option<std::path::PathBuf> {
+pub(crate) fn resolve_file(state: &PlayState, rel: &str) -> Option<std::path::PathBuf> {
     for dir in &state.collections_dirs {
         let fp = dir.join(rel);
         if fp.exists()
@@ -187,7 +187,7 @@ pub struct ImportGrpcurlRequest {
 pub struct ImportGrpcurlResponse {
     pub endpoint: String,
     pub address: String,
-    pub headers: std::collections::HashMap<String, String>,
+    pub headers: MetadataMap,
     pub body: String,
     pub plaintext: bool,
 }
@@ -205,7 +205,9 @@ pub struct CollectionItem {
 pub struct CollectionParsed {
     pub endpoint: String,
     pub address: String,
-    pub headers: crate::parser::OrderedStringMap,
+    /// Repeated `REQUEST_HEADERS` keys come back as arrays, the way
+    /// `/api/call` takes them.
+    pub headers: MetadataMap,
     pub bodies: Vec<String>,
     pub asserts: Vec<String>,
     pub extracts: crate::parser::OrderedStringMap,
@@ -258,7 +260,7 @@ fn parse_collection(doc: &crate::parser::GctfDocument) -> CollectionParsed {
 
     let endpoint = get_section(SectionType::Endpoint).unwrap_or_default();
     let address = get_section(SectionType::Address).unwrap_or_default();
-    let headers = get_kv(SectionType::RequestHeaders);
+    let headers = doc.get_request_headers().unwrap_or_default();
 
     let bodies: Vec<String> = doc
         .sections
@@ -374,7 +376,7 @@ pub struct SaveRequestStructured {
     pub path: String,
     pub endpoint: String,
     pub address: Option<String>,
-    pub headers: Option<std::collections::HashMap<String, String>>,
+    pub headers: Option<MetadataMap>,
     pub bodies: Option<Vec<String>>,
     /// When set, preserves non-REQUEST sections from the original file
     pub original_path: Option<String>,
@@ -389,7 +391,7 @@ pub struct CallRequest {
     /// Raw body strings — each string is parsed as JSON on the backend.
     /// When set, overrides `body`. Preserves int64 precision.
     pub bodies_raw: Option<Vec<String>>,
-    pub headers: Option<std::collections::HashMap<String, String>>,
+    pub headers: Option<MetadataMap>,
     pub tls: Option<bool>,
     pub tls_insecure: Option<bool>,
     pub tls_ca: Option<String>,
@@ -415,8 +417,8 @@ pub struct CallResponse {
     pub success: bool,
     /// Always an array — one element for unary, multiple for streaming, empty for errors
     pub messages: Vec<serde_json::Value>,
-    pub headers: std::collections::HashMap<String, String>,
-    pub trailers: std::collections::HashMap<String, String>,
+    pub headers: MetadataMap,
+    pub trailers: MetadataMap,
     pub error: Option<String>,
 }
 
@@ -767,6 +769,7 @@ pub async fn reflect_server(
         connection_id: 0,
         protocol: parse_protocol(req.protocol.as_deref()),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
     let client = match crate::grpc::GrpcClient::new(config).await {
@@ -918,6 +921,7 @@ async fn resolve_endpoint_descriptors(
         connection_id: 0,
         protocol: parse_protocol(req.protocol.as_deref()),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
     let client = crate::grpc::GrpcClient::new(grpc_config)
@@ -1252,11 +1256,20 @@ pub async fn generate_grpcurl(Json(req): Json<CallRequest>) -> Json<GrpcurlRespo
     }
 }
 
-/// POST /api/call — execute a gRPC call directly, return raw response
-pub async fn execute_call(
-    State(state): State<Arc<PlayState>>,
-    Json(req): Json<CallRequest>,
-) -> Result<Json<CallResponse>, (StatusCode, String)> {
+/// A [`CallRequest`] resolved against its collection file: the client
+/// config and the request messages to send.
+pub(crate) struct PreparedCall {
+    pub config: crate::grpc::GrpcClientConfig,
+    pub service: String,
+    pub method: String,
+    pub messages: Vec<serde_json::Value>,
+}
+
+/// Shared by [`execute_call`] and the streaming [`super::live`] endpoint.
+pub(crate) async fn prepare_call(
+    state: &Arc<PlayState>,
+    req: &CallRequest,
+) -> Result<PreparedCall, (StatusCode, String)> {
     let parts: Vec<&str> = req.endpoint.split('/').collect();
     if parts.len() != 2 {
         return Err((
@@ -1327,25 +1340,25 @@ pub async fn execute_call(
     // File ADDRESS wins over client state, matching /api/run's trust model.
     let address = file_address
         .or(req.address.clone())
+        .or_else(crate::config::file_address)
         .unwrap_or_else(|| crate::grpc::default_address_for(protocol).to_string());
 
     let env_ref = req.environment.as_ref();
-    let substituted_headers: Option<std::collections::HashMap<String, String>> =
-        req.headers.as_ref().map(|h| {
-            h.iter()
-                .map(|(k, v)| {
-                    let mut val = v.clone();
-                    if let Some(env) = env_ref {
-                        for (ek, ev) in env {
-                            val = val.replace(&format!("{{{{{}}}}}", ek), ev);
-                        }
+    let substituted_headers: Option<MetadataMap> = req.headers.as_ref().map(|h| {
+        h.iter()
+            .map(|(k, v)| {
+                let mut val = v.clone();
+                if let Some(env) = env_ref {
+                    for (ek, ev) in env {
+                        val = val.replace(&format!("{{{{{}}}}}", ek), ev);
                     }
-                    (k.clone(), val)
-                })
-                .collect()
-        });
+                }
+                (k.clone(), val)
+            })
+            .collect()
+    });
 
-    let grpc_config = crate::grpc::GrpcClientConfig {
+    let config = crate::grpc::GrpcClientConfig {
         address: address.to_string(),
         timeout_seconds: 30,
         tls_config,
@@ -1356,24 +1369,61 @@ pub async fn execute_call(
         connection_id: 0,
         protocol,
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
+    Ok(PreparedCall {
+        config,
+        service: full_service,
+        method: method_name,
+        messages,
+    })
+}
+
+/// Append one entry to the session's project history, if in project mode.
+pub(crate) async fn append_history(state: &PlayState, session_id: &str, entry: &serde_json::Value) {
+    let Ok(root) = require_project(state) else {
+        return;
+    };
+    if let Ok(line) = serde_json::to_string(entry) {
+        let _guard = state.history_lock.lock().await;
+        let sid = session_id.to_string();
+        tokio::task::spawn_blocking(move || {
+            super::project::append_history_entry(&root, &sid, &line).ok();
+        })
+        .await
+        .ok();
+    }
+}
+
+/// POST /api/call — execute a gRPC call directly, return raw response
+pub async fn execute_call(
+    State(state): State<Arc<PlayState>>,
+    Json(req): Json<CallRequest>,
+) -> Result<Json<CallResponse>, (StatusCode, String)> {
+    let PreparedCall {
+        config: grpc_config,
+        service: full_service,
+        method: method_name,
+        messages,
+    } = prepare_call(&state, &req).await?;
+
     let mut transport = match crate::grpc::TransportRef::new(&grpc_config).await {
         Ok(t) => t,
         Err(e) => {
             return Ok(Json(CallResponse {
                 success: false,
                 messages: vec![],
-                headers: HashMap::new(),
-                trailers: HashMap::new(),
+                headers: MetadataMap::new(),
+                trailers: MetadataMap::new(),
                 error: Some(e.to_string()),
             }));
         }
     };
 
     let mut response_messages = Vec::new();
-    let mut resp_headers = HashMap::new();
-    let mut response_trailers = HashMap::new();
+    let mut resp_headers = MetadataMap::new();
+    let mut response_trailers = MetadataMap::new();
     let mut response_error = None;
 
     for msg_val in messages {
@@ -1395,36 +1445,22 @@ pub async fn execute_call(
 
     let success = response_error.is_none();
 
-    if let Some(sid) = req.session_id.clone() {
-        let hist_body = req.bodies_raw.clone().unwrap_or_default();
-        let hist_headers = req.headers.clone().unwrap_or_default();
-        if let Ok(root) = require_project(&state) {
-            let entry = serde_json::json!({
-                "id": uuid::Uuid::new_v4().to_string(),
-                "timestamp": apif_cfg_runtime::now_unix_millis(),
-                "endpoint": req.endpoint,
-                "bodies": hist_body,
-                "headers": hist_headers,
-                "response": {
-                    "status": if success { "ok" } else { "error" },
-                    "error": response_error.clone(),
-                    "messages": response_messages.clone(),
-                    "headers": resp_headers.clone(),
-                    "trailers": response_trailers.clone(),
-                },
-            });
-
-            if let Ok(line) = serde_json::to_string(&entry) {
-                let _guard = state.history_lock.lock().await;
-                let root = root.to_path_buf();
-                let sid = sid.clone();
-                tokio::task::spawn_blocking(move || {
-                    super::project::append_history_entry(&root, &sid, &line).ok();
-                })
-                .await
-                .ok();
-            }
-        }
+    if let Some(sid) = &req.session_id {
+        let entry = serde_json::json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "timestamp": apif_cfg_runtime::now_unix_millis(),
+            "endpoint": req.endpoint,
+            "bodies": req.bodies_raw.clone().unwrap_or_default(),
+            "headers": req.headers.clone().unwrap_or_default(),
+            "response": {
+                "status": if success { "ok" } else { "error" },
+                "error": response_error.clone(),
+                "messages": response_messages.clone(),
+                "headers": resp_headers.clone(),
+                "trailers": response_trailers.clone(),
+            },
+        });
+        append_history(&state, sid, &entry).await;
     }
 
     Ok(Json(CallResponse {
@@ -1461,8 +1497,8 @@ pub struct RunTestResponse {
     pub call_duration_ms: Option<u64>,
     pub assertions: Vec<RunAssertionResult>,
     pub response_messages: Vec<serde_json::Value>,
-    pub headers: HashMap<String, String>,
-    pub trailers: HashMap<String, String>,
+    pub headers: MetadataMap,
+    pub trailers: MetadataMap,
 }
 
 /// Runs a saved `.gctf` file through the same [`crate::execution::runner::TestRunner`]
@@ -1484,6 +1520,7 @@ pub async fn execute_test(
     .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
     let exec = crate::execution::runner::TestRunner::new(false, 30, false, false, false, None)
+        .with_default_address(crate::config::env_or_file_address())
         .with_capture_exchange(true)
         .run_test(&document)
         .await
@@ -1510,12 +1547,10 @@ pub async fn execute_test(
 
     let (response_messages, headers, trailers) = match exec.captured_response {
         Some(r) => (r.messages, r.headers, r.trailers),
-        None => (vec![], HashMap::new(), HashMap::new()),
+        None => (vec![], MetadataMap::new(), MetadataMap::new()),
     };
 
-    if let Some(sid) = req.session_id.clone()
-        && let Ok(root) = require_project(&state)
-    {
+    if let Some(sid) = &req.session_id {
         let entry = serde_json::json!({
             "id": uuid::Uuid::new_v4().to_string(),
             "timestamp": apif_cfg_runtime::now_unix_millis(),
@@ -1532,15 +1567,7 @@ pub async fn execute_test(
             },
         });
 
-        if let Ok(line) = serde_json::to_string(&entry) {
-            let _guard = state.history_lock.lock().await;
-            let root = root.to_path_buf();
-            tokio::task::spawn_blocking(move || {
-                super::project::append_history_entry(&root, &sid, &line).ok();
-            })
-            .await
-            .ok();
-        }
+        append_history(&state, sid, &entry).await;
     }
 
     Ok(Json(RunTestResponse {
@@ -1557,7 +1584,7 @@ pub async fn execute_test(
 #[derive(Deserialize)]
 pub struct ShareRequest {
     pub endpoint: String,
-    pub headers: Option<std::collections::HashMap<String, String>>,
+    pub headers: Option<MetadataMap>,
     pub bodies: Vec<String>,
     pub address: Option<String>,
     pub protocol: Option<String>,
@@ -1828,29 +1855,11 @@ pub async fn project_env_merged(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .unwrap_or_default();
 
-    fn parse_dotenv(s: &str) -> std::collections::HashMap<String, String> {
-        let mut map = std::collections::HashMap::new();
-        for line in s.lines() {
-            let line = line.trim();
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            if let Some(eq) = line.find('=') {
-                let key = line[..eq].trim().to_string();
-                let val = line[eq + 1..].trim().to_string();
-                if !key.is_empty() {
-                    map.insert(key, val);
-                }
-            }
-        }
-        map
-    }
-    let shared = parse_dotenv(&shared_raw);
-    let local = parse_dotenv(&local_raw);
-    let mut variables = shared;
-    for (k, v) in local {
-        variables.insert(k, v);
-    }
+    let mut variables: std::collections::HashMap<String, String> =
+        super::project::parse_dotenv(&shared_raw)
+            .into_iter()
+            .collect();
+    variables.extend(super::project::parse_dotenv(&local_raw));
 
     let address = variables.remove("GRPC_ADDRESS");
     Ok(Json(EnvMergedResponse {
@@ -2125,6 +2134,8 @@ mod tests {
             project_settings: None,
             history_lock: tokio::sync::Mutex::new(()),
             collections_mtime: Arc::new(AtomicU64::new(0)),
+            live_calls: Default::default(),
+            access: Default::default(),
         };
         assert!(resolve_file(&state, "foo.gctf").is_none());
     }
@@ -2145,6 +2156,8 @@ mod tests {
             project_settings: None,
             history_lock: tokio::sync::Mutex::new(()),
             collections_mtime: Arc::new(AtomicU64::new(0)),
+            live_calls: Default::default(),
+            access: Default::default(),
         });
 
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -2173,6 +2186,8 @@ mod tests {
             project_settings: None,
             history_lock: tokio::sync::Mutex::new(()),
             collections_mtime: Arc::new(AtomicU64::new(0)),
+            live_calls: Default::default(),
+            access: Default::default(),
         });
 
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -2202,6 +2217,8 @@ mod tests {
             project_settings: None,
             history_lock: tokio::sync::Mutex::new(()),
             collections_mtime: Arc::new(AtomicU64::new(0)),
+            live_calls: Default::default(),
+            access: Default::default(),
         });
 
         let rt = tokio::runtime::Runtime::new().unwrap();
@@ -2234,6 +2251,8 @@ mod tests {
             project_settings: None,
             history_lock: tokio::sync::Mutex::new(()),
             collections_mtime: Arc::new(AtomicU64::new(0)),
+            live_calls: Default::default(),
+            access: Default::default(),
         });
 
         let rt = tokio::runtime::Runtime::new().unwrap();
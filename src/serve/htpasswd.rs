@@ -0,0 +1,114 @@
+//! `play --htpasswd`: users and their password hashes, one `user:hash` per
+//! line. Hashes are bcrypt (`$2y$`, `$2b$`, `$2a$`), as written by Apache's
+//! `htpasswd -B`, or SHA-crypt (`$5$` SHA-256, `$6$` SHA-512), as written by
+//! `openssl passwd -5`/`-6` or `mkpasswd -m sha-512`. `pwhash` checks them.
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Hash prefixes `verify` accepts. Apache's default `$apr1$` (MD5) isn't
+/// one of them.
+const SUPPORTED: [&str; 5] = ["$2y$", "$2b$", "$2a$", "$5$", "$6$"];
+
+/// Users from an htpasswd file.
+#[derive(Debug, Default)]
+pub struct Htpasswd {
+    users: HashMap<String, String>,
+    /// A hash from the file, checked for unknown users so they take as long
+    /// to turn away as a wrong password does.
+    decoy: String,
+}
+
+impl Htpasswd {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid htpasswd file {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut users = HashMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((user, hash)) = line.split_once(':') else {
+                bail!("line {}: expected `user:hash`", i + 1);
+            };
+            if !SUPPORTED.iter().any(|prefix| hash.starts_with(prefix)) {
+                bail!(
+                    "line {}: the hash for `{user}` is neither bcrypt nor SHA-crypt; \
+                     create it with `htpasswd -B` or `openssl passwd -6`",
+                    i + 1
+                );
+            }
+            users.insert(user.to_string(), hash.to_string());
+        }
+        let Some(decoy) = users.values().next().cloned() else {
+            bail!("no users");
+        };
+        Ok(Self { users, decoy })
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// Whether `password` is `user`'s password.
+    pub fn verify(&self, user: &str, password: &str) -> bool {
+        match self.users.get(user) {
+            Some(hash) => pwhash::unix::verify(password, hash),
+            None => {
+                let _ = pwhash::unix::verify(password, &self.decoy);
+                false
+            }
+        }
+    }
+}
+
+/// Byte equality whose running time doesn't depend on where they differ.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // From the SHA-crypt specification and pwhash's bcrypt examples.
+    const SHA256: &str = "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5";
+    const SHA512: &str = "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1";
+    const BCRYPT: &str = "$2y$05$bvIG6Nmid91Mu9RcmmWZfO5HJIMCT8riNW0hEp8f6/FuA2/mHZFpe";
+
+    #[test]
+    fn verifies_bcrypt_and_sha_crypt_users() {
+        let users = Htpasswd::parse(&format!(
+            "# team\nalice:{SHA256}\ncarol:{SHA512}\nbob:{BCRYPT}\n"
+        ))
+        .unwrap();
+        assert_eq!(users.len(), 3);
+        assert!(users.verify("alice", "Hello world!"));
+        assert!(!users.verify("alice", "hello world!"));
+        assert!(users.verify("carol", "Hello world!"));
+        assert!(users.verify("bob", "password"));
+        assert!(!users.verify("bob", "Hello world!"));
+        assert!(!users.verify("dave", "password"));
+    }
+
+    #[test]
+    fn rejects_unsupported_hashes() {
+        let apr1 = Htpasswd::parse("bob:$apr1$abcdefgh$0123456789abcdefghijkl").unwrap_err();
+        assert!(
+            format!("{apr1:#}")
+                .contains("line 1: the hash for `bob` is neither bcrypt nor SHA-crypt"),
+            "{apr1:#}"
+        );
+        assert!(Htpasswd::parse("# nobody\n").is_err());
+    }
+}
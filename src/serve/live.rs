@@ -0,0 +1,407 @@
+//! Interactive calls for the playground.
+//!
+//! `POST /api/call/stream` takes the same body as `/api/call` but answers
+//! with a server-sent event stream: the call's id, then each request sent,
+//! the response headers, every response message and the trailers as they
+//! arrive, and a final `end`. While the call is open the UI can push more
+//! request messages, half-close the request stream, or cancel the call
+//! through the `/api/call/stream/{id}` routes.
+//!
+//! The whole exchange is appended to the session history as one entry with
+//! a timestamped `transcript`.
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use super::PlayState;
+use super::api::{CallRequest, PreparedCall, append_history, prepare_call};
+use crate::grpc::{GrpcClient, MetadataMap, RpcMode, StreamItem};
+
+/// Open interactive calls, by id.
+#[derive(Default)]
+pub struct LiveCalls(std::sync::Mutex<HashMap<String, LiveCall>>);
+
+struct LiveCall {
+    control: mpsc::UnboundedSender<Control>,
+    /// Set once the request stream is closed, by the UI or because the
+    /// method takes a single request.
+    half_closed: bool,
+}
+
+enum Control {
+    Send(Value),
+    Close,
+    Cancel,
+}
+
+impl LiveCalls {
+    fn with<T>(&self, id: &str, f: impl FnOnce(&mut LiveCall) -> T) -> Option<T> {
+        let mut calls = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        calls.get_mut(id).map(f)
+    }
+
+    fn insert(&self, id: String, call: LiveCall) {
+        let mut calls = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        calls.insert(id, call);
+    }
+
+    fn remove(&self, id: &str) {
+        let mut calls = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        calls.remove(id);
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SendRequest {
+    /// Raw JSON, like `bodies_raw`, so int64 values keep their precision.
+    pub body_raw: Option<String>,
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// POST /api/call/stream — open a call and stream its events.
+pub async fn open_call(
+    State(state): State<Arc<PlayState>>,
+    Json(req): Json<CallRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let prepared = prepare_call(&state, &req).await?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let (control_tx, control_rx) = mpsc::unbounded_channel();
+    let (events_tx, events_rx) = mpsc::channel(64);
+
+    state.live_calls.insert(
+        id.clone(),
+        LiveCall {
+            control: control_tx,
+            half_closed: false,
+        },
+    );
+    tokio::spawn(drive(state, id, req, prepared, control_rx, events_tx));
+
+    let events = ReceiverStream::new(events_rx).map(Ok::<_, std::convert::Infallible>);
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// POST /api/call/stream/{id}/send — push one more request message.
+pub async fn send_message(
+    State(state): State<Arc<PlayState>>,
+    Path(id): Path<String>,
+    Json(req): Json<SendRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let message = match &req.body_raw {
+        Some(raw) => serde_json::from_str(raw)
+            .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid JSON body: {e}")))?,
+        None => req.body,
+    };
+    control(&state, &id, |call| {
+        if call.half_closed {
+            return Err((
+                StatusCode::CONFLICT,
+                "The request stream is closed".to_string(),
+            ));
+        }
+        call.control.send(Control::Send(message)).ok();
+        Ok(StatusCode::ACCEPTED)
+    })
+}
+
+/// POST /api/call/stream/{id}/close — half-close the request stream.
+pub async fn close_requests(
+    State(state): State<Arc<PlayState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    control(&state, &id, |call| {
+        if !call.half_closed {
+            call.half_closed = true;
+            call.control.send(Control::Close).ok();
+        }
+        Ok(StatusCode::ACCEPTED)
+    })
+}
+
+/// DELETE /api/call/stream/{id} — cancel the call.
+pub async fn cancel_call(
+    State(state): State<Arc<PlayState>>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    control(&state, &id, |call| {
+        call.control.send(Control::Cancel).ok();
+        Ok(StatusCode::ACCEPTED)
+    })
+}
+
+fn control(
+    state: &PlayState,
+    id: &str,
+    f: impl FnOnce(&mut LiveCall) -> Result<StatusCode, (StatusCode, String)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .live_calls
+        .with(id, f)
+        .unwrap_or_else(|| Err((StatusCode::NOT_FOUND, "No such open call".to_string())))
+}
+
+/// Everything that happened on one call, for the events and the history.
+struct Session {
+    started: Instant,
+    events: mpsc::Sender<Event>,
+    transcript: Vec<Value>,
+    sent: Vec<Value>,
+    messages: Vec<Value>,
+    headers: MetadataMap,
+    trailers: MetadataMap,
+    error: Option<String>,
+    /// The UI stopped listening; the call is cancelled.
+    gone: bool,
+}
+
+impl Session {
+    async fn emit(&mut self, event: &str, data: Value) {
+        let elapsed_ms = self.started.elapsed().as_millis() as u64;
+        self.transcript
+            .push(json!({"elapsed_ms": elapsed_ms, "event": event, "data": data}));
+        let sse = Event::default().event(event).data(data.to_string());
+        if self.events.send(sse).await.is_err() {
+            self.gone = true;
+        }
+    }
+
+    async fn send(&mut self, requests: &Option<mpsc::Sender<Value>>, message: Value) {
+        let Some(requests) = requests else {
+            return;
+        };
+        if requests.send(message.clone()).await.is_ok() {
+            self.sent.push(message.clone());
+            self.emit("sent", json!({"message": message})).await;
+        }
+    }
+}
+
+enum Outcome {
+    Ok,
+    Error,
+    Cancelled,
+}
+
+async fn drive(
+    state: Arc<PlayState>,
+    id: String,
+    req: CallRequest,
+    prepared: PreparedCall,
+    mut control: mpsc::UnboundedReceiver<Control>,
+    events: mpsc::Sender<Event>,
+) {
+    let mut session = Session {
+        started: Instant::now(),
+        events,
+        transcript: Vec::new(),
+        sent: Vec::new(),
+        messages: Vec::new(),
+        headers: MetadataMap::new(),
+        trailers: MetadataMap::new(),
+        error: None,
+        gone: false,
+    };
+    let outcome = run(&state, &id, prepared, &mut control, &mut session).await;
+    state.live_calls.remove(&id);
+
+    let status = match outcome {
+        Outcome::Ok => "ok",
+        Outcome::Error => "error",
+        Outcome::Cancelled => "cancelled",
+    };
+    let elapsed_ms = session.started.elapsed().as_millis() as u64;
+    session
+        .emit(
+            "end",
+            json!({"status": status, "error": session.error.clone(), "elapsed_ms": elapsed_ms}),
+        )
+        .await;
+
+    if let Some(sid) = &req.session_id {
+        let entry = json!({
+            "id": id,
+            "timestamp": apif_cfg_runtime::now_unix_millis(),
+            "kind": "stream",
+            "endpoint": req.endpoint,
+            "bodies": session.sent.iter().map(Value::to_string).collect::<Vec<_>>(),
+            "headers": req.headers.clone().unwrap_or_default(),
+            "transcript": session.transcript,
+            "response": {
+                "status": status,
+                "error": session.error,
+                "messages": session.messages,
+                "headers": session.headers,
+                "trailers": session.trailers,
+            },
+        });
+        append_history(&state, sid, &entry).await;
+    }
+    // The event stream ends when `session` drops here, so a UI that saw it
+    // end can read the entry back from the history.
+}
+
+async fn run(
+    state: &PlayState,
+    id: &str,
+    prepared: PreparedCall,
+    control: &mut mpsc::UnboundedReceiver<Control>,
+    session: &mut Session,
+) -> Outcome {
+    let PreparedCall {
+        config,
+        service,
+        method,
+        messages,
+    } = prepared;
+
+    let mut client = match GrpcClient::new(config).await {
+        Ok(client) => client,
+        Err(e) => {
+            session.error = Some(e.to_string());
+            session.emit("error", json!({"error": e.to_string()})).await;
+            return Outcome::Error;
+        }
+    };
+    let rpc_mode = client
+        .descriptor_pool()
+        .get_service_by_name(&service)
+        .and_then(|s| s.methods().find(|m| m.name() == method))
+        .map(
+            |m| match (m.is_client_streaming(), m.is_server_streaming()) {
+                (true, true) => RpcMode::Bidi,
+                (true, false) => RpcMode::ClientStream,
+                (false, true) => RpcMode::ServerStream,
+                (false, false) => RpcMode::Unary,
+            },
+        );
+    let streams_requests = matches!(rpc_mode, Some(RpcMode::ClientStream | RpcMode::Bidi));
+    session
+        .emit("call", json!({"id": id, "mode": rpc_mode.map(mode_name)}))
+        .await;
+
+    // Notices the UI leaving even while the server is silent.
+    let listener = session.events.clone();
+    let (tx, rx) = mpsc::channel(crate::execution::runner_helpers::REQUEST_CHANNEL_BUFFER);
+    let mut requests = Some(tx);
+    // The call waits for its first request, so it runs alongside the sends.
+    let mut call = tokio::spawn(async move {
+        client
+            .call_stream(&service, &method, ReceiverStream::new(rx), rpc_mode)
+            .await
+    });
+    for message in messages {
+        session.send(&requests, message).await;
+    }
+    if !streams_requests {
+        requests = None;
+        state.live_calls.with(id, |call| call.half_closed = true);
+    }
+
+    let opened = loop {
+        tokio::select! {
+            opened = &mut call => break opened,
+            () = listener.closed() => session.gone = true,
+            Some(c) = control.recv() => {
+                if !apply(c, &mut requests, session).await {
+                    call.abort();
+                    return Outcome::Cancelled;
+                }
+            }
+        }
+        if session.gone {
+            call.abort();
+            return Outcome::Cancelled;
+        }
+    };
+    let mut responses = match opened {
+        Ok(Ok((headers, responses))) => {
+            session.headers = headers.clone();
+            session.emit("headers", json!({"headers": headers})).await;
+            responses
+        }
+        Ok(Err(e)) => {
+            session.error = Some(e.to_string());
+            session.emit("error", json!({"error": e.to_string()})).await;
+            return Outcome::Error;
+        }
+        Err(e) => {
+            session.error = Some(e.to_string());
+            session.emit("error", json!({"error": e.to_string()})).await;
+            return Outcome::Error;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            item = responses.next() => match item {
+                Some(Ok(StreamItem::Message(message))) => {
+                    session.messages.push(message.clone());
+                    session.emit("message", json!({"message": message})).await;
+                }
+                Some(Ok(StreamItem::Trailers(trailers))) => {
+                    session.trailers.extend(trailers.clone());
+                    session.emit("trailers", json!({"trailers": trailers})).await;
+                }
+                Some(Err(e)) => {
+                    session.error = Some(e.to_string());
+                    session.emit("error", json!({"error": e.to_string()})).await;
+                    return Outcome::Error;
+                }
+                None => return Outcome::Ok,
+            },
+            () = listener.closed() => session.gone = true,
+            Some(c) = control.recv() => {
+                if !apply(c, &mut requests, session).await {
+                    return Outcome::Cancelled;
+                }
+            }
+        }
+        if session.gone {
+            return Outcome::Cancelled;
+        }
+    }
+}
+
+fn mode_name(mode: RpcMode) -> &'static str {
+    match mode {
+        RpcMode::Unary => "unary",
+        RpcMode::ServerStream => "server_streaming",
+        RpcMode::ClientStream => "client_streaming",
+        RpcMode::Bidi => "bidi_streaming",
+    }
+}
+
+/// Act on a control message; `false` cancels the call. Dropping the request
+/// sender half-closes the request stream.
+async fn apply(
+    control: Control,
+    requests: &mut Option<mpsc::Sender<Value>>,
+    session: &mut Session,
+) -> bool {
+    match control {
+        Control::Send(message) => session.send(requests, message).await,
+        Control::Close => {
+            if requests.take().is_some() {
+                session.emit("closed", json!({})).await;
+            }
+        }
+        Control::Cancel => return false,
+    }
+    true
+}
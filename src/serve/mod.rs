@@ -21,9 +21,13 @@ pub struct HealthResponse {
     pub status: String,
 }
 
+pub mod access;
 pub mod api;
 pub mod assets;
+pub mod htpasswd;
+pub mod live;
 pub mod project;
+pub mod suite;
 
 pub struct PlayState {
     /// Primary collections dir (first in `collections_dirs`), backward compat
@@ -38,13 +42,17 @@ pub struct PlayState {
     /// Monotonic timestamp bumped on every collection/env change.
     /// Frontend polls /api/info and compares this value for auto-reload.
     pub collections_mtime: Arc<AtomicU64>,
+    /// Calls opened through `/api/call/stream` that are still running.
+    pub live_calls: live::LiveCalls,
+    /// Authentication, read-only mode and audit log (`play --auth-token`, ...).
+    pub access: access::AccessControl,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareState {
     pub id: String,
     pub endpoint: String,
-    pub headers: std::collections::HashMap<String, String>,
+    pub headers: crate::grpc::MetadataMap,
     pub bodies: Vec<String>,
     pub address: Option<String>,
     pub protocol: Option<String>,
@@ -103,6 +111,8 @@ pub struct InfoResponse {
     /// Monotonic counter that increments on every collection/env change.
     /// Frontend uses this for auto-reload without polling the full list.
     pub collections_mtime: u64,
+    /// `play --read-only`: saving, deleting and editing environments are refused.
+    pub read_only: bool,
 }
 
 /// GET /api/info — unified startup info (version + health + project)
@@ -117,6 +127,7 @@ pub async fn info_handler(State(state): State<Arc<PlayState>>) -> Json<InfoRespo
         status: "ok".into(),
         project,
         collections_mtime: state.collections_mtime.load(Ordering::Relaxed),
+        read_only: state.access.read_only,
     })
 }
 
@@ -326,37 +337,59 @@ pub fn build_app(state: Arc<PlayState>) -> Router {
         .route("/assets/{*path}", get(static_handler))
         .route("/api/collections", get(api::list_collections))
         .route("/api/collections/{*path}", get(api::get_collection))
-        .route("/api/save", post(api::save_collection))
-        .route(
-            "/api/save-structured",
-            post(api::save_collection_structured),
-        )
         .route("/api/call", post(api::execute_call))
+        .route("/api/call/stream", post(live::open_call))
+        .route("/api/call/stream/{id}/send", post(live::send_message))
+        .route("/api/call/stream/{id}/close", post(live::close_requests))
+        .route("/api/call/stream/{id}", delete(live::cancel_call))
         .route("/api/run", post(api::execute_test))
+        .route("/api/run/suite", post(suite::run_suite))
+        .route("/api/bench", post(suite::run_bench))
         .route("/api/diagnostics", post(api::get_diagnostics))
         .route("/api/reflect", post(api::reflect_server))
         .route("/api/import-grpcurl", post(api::import_grpcurl))
         .route("/api/grpcurl", post(api::generate_grpcurl))
         .route("/api/schema-fill", post(api::schema_fill))
         .route("/api/proto-source", post(api::proto_source))
-        .route("/api/proto-upload", post(api::proto_upload))
         .route("/api/proto-files", get(api::proto_files))
-        .route("/api/dir/{*path}", post(api::create_directory))
-        .route("/api/move", post(api::move_item))
-        .route("/api/collections/{*path}", delete(api::delete_collection))
-        .route("/api/share", post(api::create_share))
         .route("/api/share/{id}", get(api::get_share))
         .route("/api/version", get(version_handler))
         .route("/api/health", get(health_handler))
         .route("/api/info", get(info_handler));
 
+    // Everything that writes collections, shares, settings or `.env` files;
+    // refused under `play --read-only`.
+    let write_routes = Router::new()
+        .route("/api/save", post(api::save_collection))
+        .route(
+            "/api/save-structured",
+            post(api::save_collection_structured),
+        )
+        .route("/api/proto-upload", post(api::proto_upload))
+        .route("/api/dir/{*path}", post(api::create_directory))
+        .route("/api/move", post(api::move_item))
+        .route("/api/collections/{*path}", delete(api::delete_collection))
+        .route("/api/share", post(api::create_share))
+        .route("/api/project/settings", put(api::project_put_settings))
+        .route("/api/project/env/{name}", put(api::project_env_put))
+        .route(
+            "/api/project/env/{name}/local",
+            put(api::project_env_local_put),
+        )
+        .route(
+            "/api/project/env/{name}/local",
+            delete(api::project_env_local_delete),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            access::read_only_guard,
+        ));
+
     let project_routes = Router::new()
         .route("/api/project/info", get(api::project_info))
         .route("/api/project/settings", get(api::project_get_settings))
-        .route("/api/project/settings", put(api::project_put_settings))
         .route("/api/project/env/list", get(api::project_env_list))
         .route("/api/project/env/{name}", get(api::project_env_get))
-        .route("/api/project/env/{name}", put(api::project_env_put))
         .route(
             "/api/project/env/{name}/merged",
             get(api::project_env_merged),
@@ -365,28 +398,35 @@ pub fn build_app(state: Arc<PlayState>) -> Router {
             "/api/project/env/{name}/local",
             get(api::project_env_local_get),
         )
-        .route(
-            "/api/project/env/{name}/local",
-            put(api::project_env_local_put),
-        )
-        .route(
-            "/api/project/env/{name}/local",
-            delete(api::project_env_local_delete),
-        )
         .route("/api/project/history", get(api::project_history_get));
 
     // Note: no CORS layer on purpose — the web UI is served same-origin and
     // only fetches relative /api paths; permissive CORS would let any website
     // in the user's browser drive this server.
     base_routes
+        .merge(write_routes)
         .merge(project_routes)
         .route("/{*path}", get(spa_fallback))
+        .layer(axum::middleware::from_fn(access::reject_cross_site))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            access::audit,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            access::authenticate,
+        ))
         .layer(axum::middleware::from_fn(access_log_middleware))
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
 
-pub async fn start_play_server(host: &str, port: u16, dir: PathBuf) -> Result<()> {
+pub async fn start_play_server(
+    host: &str,
+    port: u16,
+    dir: PathBuf,
+    access: access::AccessControl,
+) -> Result<()> {
     let project_root = project::detect_project(&dir);
 
     let collections_dir = project_root
@@ -426,6 +466,8 @@ pub async fn start_play_server(host: &str, port: u16, dir: PathBuf) -> Result<()
     let collections_dir_display = collections_dir.display().to_string();
 
     let collections_mtime = Arc::new(AtomicU64::new(0));
+    let access_summary = access.describe();
+    let unauthenticated = !access.requires_auth();
 
     // Start file watcher (runs for the lifetime of the process)
     let w_mtime = collections_mtime.clone();
@@ -450,6 +492,8 @@ pub async fn start_play_server(host: &str, port: u16, dir: PathBuf) -> Result<()
             .and_then(|r| project::load_project_settings(r).ok()),
         history_lock: tokio::sync::Mutex::new(()),
         collections_mtime,
+        live_calls: Default::default(),
+        access,
     });
 
     tokio::task::spawn_blocking(move || {
@@ -497,10 +541,24 @@ pub async fn start_play_server(host: &str, port: u16, dir: PathBuf) -> Result<()
         }
     }
     println!("   dirs     {dir}", dir = collections_dir_display);
+    if let Some(summary) = access_summary {
+        println!("   access   {summary}");
+    }
+    if !bound_loopback && unauthenticated {
+        println!(
+            "   {yellow}⚠ listening on {host} without authentication; pass --auth-token or --htpasswd{reset}",
+            yellow = ansi!(ANSI_YELLOW),
+            reset = ansi!(ANSI_RESET),
+        );
+    }
     println!();
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
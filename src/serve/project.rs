@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -150,6 +151,139 @@ pub fn delete_dotenv_local(root: &Path, name: &str) -> Result<()> {
     delete_text_file(&env_local_path(root, name))
 }
 
+/// Parse `KEY=VALUE` lines, skipping blanks and `#` comments. Values are taken
+/// verbatim (no quote stripping), exactly as the playground shows them.
+pub fn parse_dotenv(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// `--env` value that opts out of the settings.json `active_env` default.
+pub const NO_ENV: &str = "none";
+
+/// A project environment as CLI commands see it: `.env.<name>` overlaid with
+/// the gitignored `.env.<name>.local`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProjectEnv {
+    pub name: String,
+    /// `{{KEY}}` variables. Empty values are placeholders for secrets that
+    /// were never filled in, so they are dropped: the runner then reports the
+    /// placeholder as unresolved instead of sending an empty string.
+    pub variables: BTreeMap<String, String>,
+    /// Keys whose value comes from the `.local` file (kept out of hover text).
+    pub local_keys: BTreeSet<String>,
+    /// `GRPC_ADDRESS`: the target for documents without an ADDRESS section.
+    pub address: Option<String>,
+}
+
+impl ProjectEnv {
+    /// Variables as the runner's initial variable map.
+    pub fn variable_map(&self) -> HashMap<String, serde_json::Value> {
+        self.variables
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+            .collect()
+    }
+}
+
+/// Find the `.grpctestify` project directory in `start` or its ancestors.
+pub fn find_project_dir(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    start.ancestors().find_map(detect_project)
+}
+
+/// Load environment `name` from a `.grpctestify` directory. Fails when neither
+/// `.env.<name>` nor `.env.<name>.local` exists.
+pub fn load_project_env(project_dir: &Path, name: &str) -> Result<ProjectEnv> {
+    // Same rule the playground's env endpoints apply.
+    if name.is_empty() || name.contains(['/', '\\', ':']) || name.contains("..") {
+        bail!("Invalid environment name '{name}'");
+    }
+    let shared = read_dotenv(project_dir, name)?;
+    let local = read_dotenv_local(project_dir, name)?;
+    if shared.is_none() && local.is_none() {
+        let known = list_env_files(project_dir).unwrap_or_default();
+        bail!(
+            "Environment '{name}' not found in {} (available: {})",
+            project_dir.display(),
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        );
+    }
+
+    let mut env = ProjectEnv {
+        name: name.to_string(),
+        ..ProjectEnv::default()
+    };
+    for (key, value) in parse_dotenv(shared.as_deref().unwrap_or_default()) {
+        env.local_keys.remove(&key);
+        env.variables.insert(key, value);
+    }
+    for (key, value) in parse_dotenv(local.as_deref().unwrap_or_default()) {
+        if !value.is_empty() {
+            env.local_keys.insert(key.clone());
+            env.variables.insert(key, value);
+        }
+    }
+    env.variables.retain(|_, v| !v.is_empty());
+    env.address = env.variables.remove("GRPC_ADDRESS");
+    env.local_keys.remove("GRPC_ADDRESS");
+    Ok(env)
+}
+
+/// Resolve the environment for a CLI command started at `start` (a test path
+/// or the working directory). `requested` is the `--env` value: a name, or
+/// [`NO_ENV`]. Without it, the project's `active_env` from settings.json is
+/// used when one is set and its file exists.
+pub fn resolve_project_env(start: &Path, requested: Option<&str>) -> Result<Option<ProjectEnv>> {
+    if requested == Some(NO_ENV) {
+        return Ok(None);
+    }
+    let project_dir = find_project_dir(start);
+    match (requested, project_dir) {
+        (Some(name), Some(dir)) => load_project_env(&dir, name).map(Some),
+        (Some(name), None) => bail!(
+            "--env {name}: no .grpctestify project found in {} or its parents",
+            start.display()
+        ),
+        (None, Some(dir)) => {
+            let Some(active) = load_project_settings(&dir)
+                .ok()
+                .and_then(|settings| settings.active_env)
+            else {
+                return Ok(None);
+            };
+            // A stale active_env (file deleted since) just means "no env".
+            Ok(load_project_env(&dir, &active).ok())
+        }
+        (None, None) => Ok(None),
+    }
+}
+
+/// Where to look for the project of a command given `paths`: the first path
+/// (its directory, for a file), else the working directory.
+pub fn env_search_start(paths: &[PathBuf]) -> PathBuf {
+    match paths.first() {
+        Some(path) if path.is_file() => path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map_or_else(|| PathBuf::from("."), Path::to_path_buf),
+        Some(path) => path.clone(),
+        None => PathBuf::from("."),
+    }
+}
+
 /// List session IDs from history/*.jsonl files.
 pub fn list_history_sessions(root: &Path) -> Result<Vec<String>> {
     let dir = root.join("history");
@@ -342,4 +476,43 @@ mod tests {
         assert!(root.join("history/abc-123_XYZ.jsonl").is_file());
         assert!(!root.parent().unwrap().join("pwned.jsonl").exists());
     }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn cli_env_merges_local_over_shared_and_defaults_to_active_env() {
+        let dir = tempfile::tempdir().unwrap();
+        init_project_dir(dir.path()).unwrap();
+        let project = dir.path().join(".grpctestify");
+        write_dotenv(
+            &project,
+            "staging",
+            "# shared\nGRPC_ADDRESS=staging:443\nTENANT=acme\nTOKEN=\nREGION=eu\n",
+        )
+        .unwrap();
+        write_dotenv_local(&project, "staging", "TOKEN=s3cret\nREGION=\n").unwrap();
+        let nested = dir.path().join("tests/orders");
+        fs::create_dir_all(&nested).unwrap();
+
+        let env = resolve_project_env(&nested, Some("staging"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(env.address.as_deref(), Some("staging:443"));
+        let expected: BTreeMap<String, String> =
+            [("REGION", "eu"), ("TENANT", "acme"), ("TOKEN", "s3cret")]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+        assert_eq!(env.variables, expected);
+        assert_eq!(env.local_keys.iter().collect::<Vec<_>>(), ["TOKEN"]);
+
+        // `init` makes "example" active; its empty GRPC_ADDRESS is no address.
+        let default = resolve_project_env(&nested, None).unwrap().unwrap();
+        assert_eq!(default.name, "example");
+        assert_eq!(default.address, None);
+
+        assert_eq!(resolve_project_env(&nested, Some(NO_ENV)).unwrap(), None);
+        let missing = resolve_project_env(&nested, Some("prod")).unwrap_err();
+        assert!(missing.to_string().contains("available: example, staging"));
+        assert!(resolve_project_env(&nested, Some("../x")).is_err());
+    }
 }
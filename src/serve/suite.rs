@@ -0,0 +1,319 @@
+//! Suite runs and benchmarks for the playground.
+//!
+//! `POST /api/run/suite` runs a collections directory through the same
+//! runner as `grpctestify run` — fixtures, datasets, `META.depends_on`,
+//! parallelism and tag filters included — and streams its `--stream` events
+//! (`suite_start`, `test_start`, `test_pass`/`test_fail`/`test_skip`,
+//! `suite_end`) as server-sent events of the same name, then the HTML report.
+//!
+//! `POST /api/bench` runs `grpctestify bench` over a collection and streams
+//! a `progress` snapshot every `progress_interval`, then the full report.
+//!
+//! Both stop when the UI stops listening.
+
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use super::PlayState;
+use super::api::{append_history, reject_traversal, resolve_file};
+use crate::cli::args::{BenchArgs, Cli, Commands};
+use crate::commands::bench::{self, BenchConfigResolved, BenchObserver};
+
+#[derive(Deserialize)]
+pub struct SuiteRequest {
+    /// Directory (or single file) relative to the collections dir; empty
+    /// for all of it.
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub skip_tags: Vec<String>,
+    /// Worker count, or `auto`; defaults to `run`'s default.
+    pub parallel: Option<String>,
+    /// Project environment; defaults to settings.json `active_env`.
+    pub env: Option<String>,
+    pub session_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BenchRequest {
+    pub path: String,
+    /// Any `bench` duration, e.g. `30s`.
+    pub duration: Option<String>,
+    pub requests: Option<u64>,
+    pub concurrency: Option<u32>,
+    pub max_rps: Option<f64>,
+    pub mode: Option<String>,
+    pub profile: Option<String>,
+    /// Defaults to `1s` rather than `bench`'s `5s`, for a live view.
+    pub progress_interval: Option<String>,
+    pub env: Option<String>,
+    pub session_id: Option<String>,
+}
+
+type EventSender = mpsc::UnboundedSender<Event>;
+
+fn send(events: &EventSender, name: &str, data: &Value) {
+    events
+        .send(Event::default().event(name).data(data.to_string()))
+        .ok();
+}
+
+/// The collections path a suite or bench targets, absolute.
+fn resolve_target(state: &PlayState, rel: &str) -> Result<PathBuf, (StatusCode, String)> {
+    reject_traversal(rel)?;
+    resolve_file(state, rel).ok_or((StatusCode::NOT_FOUND, "File not found".to_string()))
+}
+
+/// Parse `argv` as the CLI would, so the embedded run gets exactly the
+/// defaults and validation of the real command.
+fn parse_cli(argv: Vec<String>) -> Result<Cli, (StatusCode, String)> {
+    Cli::try_parse_from(argv)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string().trim().to_string()))
+}
+
+/// `--name=value` keeps a value that starts with `-` from reading as a flag.
+fn push_flag(argv: &mut Vec<String>, name: &str, value: Option<impl ToString>) {
+    if let Some(value) = value {
+        argv.push(format!("--{name}={}", value.to_string()));
+    }
+}
+
+/// POST /api/run/suite — run a directory and stream its test events.
+pub async fn run_suite(
+    State(state): State<Arc<PlayState>>,
+    Json(req): Json<SuiteRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let target = resolve_target(&state, &req.path)?;
+    let report_dir =
+        tempfile::tempdir().map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let report_path = report_dir.path().join("report.html");
+
+    let mut argv: Vec<String> = ["grpctestify", "run", "--log-format=html"]
+        .map(String::from)
+        .into();
+    push_flag(&mut argv, "log-output", Some(report_path.display()));
+    push_flag(&mut argv, "parallel", req.parallel.as_ref());
+    push_flag(&mut argv, "env", req.env.as_ref());
+    for tag in &req.tags {
+        push_flag(&mut argv, "tags", Some(tag));
+    }
+    for tag in &req.skip_tags {
+        push_flag(&mut argv, "skip-tags", Some(tag));
+    }
+    argv.push("--".to_string());
+    argv.push(target.display().to_string());
+    let cli = parse_cli(argv)?;
+
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        drive_suite(&state, &req, cli, &report_path, events_tx).await;
+        drop(report_dir);
+    });
+
+    let events = UnboundedReceiverStream::new(events_rx).map(Ok::<_, std::convert::Infallible>);
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+async fn drive_suite(
+    state: &PlayState,
+    req: &SuiteRequest,
+    cli: Cli,
+    report_path: &std::path::Path,
+    events: EventSender,
+) {
+    let sink_events = events.clone();
+    let sink = Box::new(move |line: &str| {
+        let name = serde_json::from_str::<Value>(line)
+            .ok()
+            .and_then(|event| event["event"].as_str().map(str::to_string))
+            .unwrap_or_else(|| "message".to_string());
+        sink_events
+            .send(Event::default().event(name).data(line))
+            .ok();
+    });
+    let cancel = Arc::new(AtomicBool::new(false));
+    let run_cancel = Arc::clone(&cancel);
+    let mut run = tokio::spawn(async move {
+        let args = cli.get_run_args().clone();
+        crate::commands::run::run_streaming(&cli, &args, sink, &run_cancel).await
+    });
+
+    // A UI that leaves stops the run like a bench: started tests and the
+    // teardown fixtures finish, nothing new starts, and the partial summary
+    // still lands in the history.
+    let joined = tokio::select! {
+        joined = &mut run => joined,
+        () = events.closed() => {
+            cancel.store(true, Ordering::Relaxed);
+            (&mut run).await
+        }
+    };
+    let cancelled = cancel.load(Ordering::Relaxed);
+    let (status, error, summary) = match joined {
+        Ok(Ok(results)) => {
+            let html = tokio::fs::read_to_string(report_path)
+                .await
+                .unwrap_or_default();
+            send(&events, "report", &json!({"html": html}));
+            let status = match () {
+                () if cancelled => "cancelled",
+                () if results.all_passed() => "passed",
+                () => "failed",
+            };
+            let summary = json!({
+                "total": results.total(),
+                "passed": results.passed(),
+                "failed": results.failed(),
+                "skipped": results.skipped(),
+                "duration_ms": results.metrics.total_duration_ms,
+            });
+            (status, None, Some(summary))
+        }
+        Ok(Err(e)) => ("error", Some(format!("{e:#}")), None),
+        Err(e) => ("error", Some(e.to_string()), None),
+    };
+    if let Some(error) = &error {
+        send(&events, "error", &json!({"error": error}));
+    }
+
+    if let Some(sid) = &req.session_id {
+        let entry = json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "timestamp": apif_cfg_runtime::now_unix_millis(),
+            "kind": "suite",
+            "collection_path": req.path,
+            "tags": req.tags,
+            "skip_tags": req.skip_tags,
+            "response": {
+                "status": status,
+                "error": error,
+                "summary": summary,
+            },
+        });
+        append_history(state, sid, &entry).await;
+    }
+}
+
+/// POST /api/bench — benchmark a collection and stream progress snapshots.
+pub async fn run_bench(
+    State(state): State<Arc<PlayState>>,
+    Json(req): Json<BenchRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let target = resolve_target(&state, &req.path)?;
+
+    let mut argv: Vec<String> = ["grpctestify", "bench"].map(String::from).into();
+    push_flag(
+        &mut argv,
+        "progress-interval",
+        Some(req.progress_interval.as_deref().unwrap_or("1s")),
+    );
+    push_flag(&mut argv, "duration", req.duration.as_ref());
+    push_flag(&mut argv, "requests", req.requests);
+    push_flag(&mut argv, "concurrency", req.concurrency);
+    push_flag(&mut argv, "max-rps", req.max_rps);
+    push_flag(&mut argv, "mode", req.mode.as_ref());
+    push_flag(&mut argv, "profile", req.profile.as_ref());
+    push_flag(&mut argv, "env", req.env.as_ref());
+    argv.push("--".to_string());
+    argv.push(target.display().to_string());
+    let Some(Commands::Bench(args)) = parse_cli(argv)?.command else {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Not a bench command".to_string(),
+        ));
+    };
+    let config = bench::resolve_config(&args, &args.test_paths)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("{e:#}")))?;
+
+    let (events_tx, events_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        drive_bench(&state, &req, args, config, events_tx).await;
+    });
+
+    let events = UnboundedReceiverStream::new(events_rx).map(Ok::<_, std::convert::Infallible>);
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+async fn drive_bench(
+    state: &PlayState,
+    req: &BenchRequest,
+    args: BenchArgs,
+    config: BenchConfigResolved,
+    events: EventSender,
+) {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let progress_events = events.clone();
+    let observer = Arc::new(BenchObserver {
+        on_progress: Box::new(move |snapshot| {
+            send(&progress_events, "progress", &json!(snapshot));
+        }),
+        cancel: Arc::clone(&cancel),
+    });
+    let mut run = tokio::spawn(async move {
+        bench::run_benchmark(&args.test_paths, &config, &args.exclude, Some(observer)).await
+    });
+
+    // A UI that leaves stops the run the way Ctrl-C does: in-flight
+    // requests finish and the partial report still lands in the history.
+    let joined = tokio::select! {
+        joined = &mut run => joined,
+        () = events.closed() => {
+            cancel.store(true, Ordering::Relaxed);
+            (&mut run).await
+        }
+    };
+    let cancelled = cancel.load(Ordering::Relaxed);
+    let (status, error, report) = match joined {
+        Ok(Ok(report)) => {
+            // Failed the way `bench` exits non-zero: nothing measured, or a
+            // threshold missed.
+            let measured = report.summary.count == 0 || report.summary.ok > 0;
+            let status = match () {
+                () if cancelled => "cancelled",
+                () if measured && report.thresholds_passed() => "passed",
+                () => "failed",
+            };
+            let report = serde_json::to_value(&report).unwrap_or_default();
+            send(&events, "report", &report);
+            (status, None, Some(report))
+        }
+        Ok(Err(e)) => ("error", Some(format!("{e:#}")), None),
+        Err(e) => ("error", Some(e.to_string()), None),
+    };
+    if let Some(error) = &error {
+        send(&events, "error", &json!({"error": error}));
+    }
+
+    if let Some(sid) = &req.session_id {
+        let entry = json!({
+            "id": uuid::Uuid::new_v4().to_string(),
+            "timestamp": apif_cfg_runtime::now_unix_millis(),
+            "kind": "bench",
+            "collection_path": req.path,
+            "response": {
+                "status": status,
+                "error": error,
+                "summary": report.as_ref().map(|r| r["summary"].clone()),
+            },
+        });
+        append_history(state, sid, &entry).await;
+    }
+}
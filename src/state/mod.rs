@@ -1,6 +1,6 @@
 // Thin shim — all implementation lives in crates/apif-state.
 pub use apif_state::{
-    AssertionRecord, CapturedExchange, ConfigSummary, ExecutionMetrics, TestMeta, TestResult,
-    TestResults, TestStatus,
+    AssertionRecord, CapturedExchange, ConfigSummary, ExecutionMetrics, FailureCluster,
+    RepeatStats, TestMeta, TestResult, TestResults, TestStatus, TraceInfo,
 };
 pub use apif_state::{metrics, result};
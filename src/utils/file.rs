@@ -192,7 +192,6 @@ fn format_inline_options(options: &InlineOptions) -> String {
 mod tests {
     use super::*;
     use crate::polyfill::runtime;
-    use std::collections::HashMap;
     use tempfile::NamedTempFile;
 
     #[cfg_attr(miri, ignore)]
@@ -229,8 +228,8 @@ mod tests {
         });
 
         let response = crate::grpc::GrpcResponse {
-            headers: HashMap::new(),
-            trailers: HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![json!({"result": "new"})],
             error: None,
         };
@@ -256,8 +255,8 @@ mod tests {
         std::fs::write(temp_file.path(), content).unwrap();
         let doc = crate::parser::parse_gctf(temp_file.path()).unwrap();
         let response = crate::grpc::GrpcResponse {
-            headers: HashMap::new(),
-            trailers: HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![serde_json::json!({"result": "new"})],
             error: None,
         };
@@ -277,8 +276,8 @@ mod tests {
         std::fs::write(temp_file.path(), content).unwrap();
         let doc = crate::parser::parse_gctf(temp_file.path()).unwrap();
         let response = crate::grpc::GrpcResponse {
-            headers: HashMap::new(),
-            trailers: HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![serde_json::json!({"status": "ok"})],
             error: None,
         };
@@ -297,8 +296,8 @@ mod tests {
         std::fs::write(temp_file.path(), content).unwrap();
         let doc = crate::parser::parse_gctf(temp_file.path()).unwrap();
         let response = crate::grpc::GrpcResponse {
-            headers: std::collections::HashMap::new(),
-            trailers: std::collections::HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![
                 serde_json::json!({"index": 10}),
                 serde_json::json!({"index": 11}),
@@ -324,8 +323,8 @@ mod tests {
         let doc = crate::parser::parse_gctf(temp_file.path()).unwrap();
         // Nothing captured (e.g. server down) — snapshot must not be emptied.
         let response = crate::grpc::GrpcResponse {
-            headers: HashMap::new(),
-            trailers: HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![],
             error: None,
         };
@@ -351,8 +350,8 @@ mod tests {
         std::fs::write(&path, content).unwrap();
         let doc = crate::parser::parse_gctf(&path).unwrap();
         let response = crate::grpc::GrpcResponse {
-            headers: HashMap::new(),
-            trailers: HashMap::new(),
+            headers: crate::grpc::MetadataMap::new(),
+            trailers: crate::grpc::MetadataMap::new(),
             messages: vec![serde_json::json!({"result": "new"})],
             error: None,
         };
@@ -3,7 +3,6 @@
 
 use grpctestify::report::{AllureReporter, Reporter};
 use grpctestify::state::{CapturedExchange, ConfigSummary, TestMeta, TestResult, TestStatus};
-use std::collections::HashMap;
 
 #[test]
 fn allure_reporter_passing_test() {
@@ -21,9 +20,14 @@ fn allure_reporter_passing_test() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     };
 
     // Allure writes files in on_test_end, not on_suite_end
@@ -114,9 +118,14 @@ fn allure_reporter_mixed_results() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
     reporter.on_test_end(
@@ -136,9 +145,14 @@ fn allure_reporter_mixed_results() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -175,9 +189,14 @@ fn allure_reporter_labels_present() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -223,9 +242,14 @@ fn allure_reporter_test_name_from_path() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -263,9 +287,14 @@ fn allure_reporter_timestamps() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -306,9 +335,14 @@ fn allure_reporter_tags_and_owner_labels() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -371,9 +405,14 @@ tasktracker.TaskService/GetTask
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -447,9 +486,14 @@ tasktracker.TaskService/GetTask
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -506,12 +550,17 @@ fn allure_reporter_row_params_present() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: vec![
                 ("id".to_string(), "1".to_string()),
                 ("user".to_string(), "alice".to_string()),
             ],
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -559,9 +608,14 @@ fn allure_reporter_display_name_from_meta() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -599,9 +653,14 @@ fn allure_reporter_description_and_links_from_meta() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -644,9 +703,14 @@ fn allure_reporter_no_description_or_links_when_meta_empty() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -669,11 +733,11 @@ fn allure_reporter_writes_exchange_attachment() {
     let temp_dir = tempfile::TempDir::new().expect("Failed to create temp dir");
     let reporter = AllureReporter::new(temp_dir.path().to_path_buf());
 
-    let mut headers = HashMap::new();
-    headers.insert("content-type".to_string(), "application/grpc".to_string());
+    let mut headers = grpctestify::grpc::MetadataMap::new();
+    headers.append("content-type", "application/grpc");
     let exchange = CapturedExchange::capture(
         headers,
-        HashMap::new(),
+        grpctestify::grpc::MetadataMap::new(),
         vec![serde_json::json!({"message": "Hello, World!"})],
     );
 
@@ -690,9 +754,14 @@ fn allure_reporter_writes_exchange_attachment() {
             assertions: Vec::new(),
             exchange: Some(exchange),
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -763,9 +832,14 @@ fn allure_reporter_no_attachment_when_exchange_absent() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     );
 
@@ -260,3 +260,68 @@ async fn call_bench_direct_mode_reaches_the_real_env_address() {
         String::from_utf8_lossy(&output.stderr)
     );
 }
+
+/// `mode: adaptive` ramps the arrival rate while windows hold and reports the
+/// highest rate that held. A healthy local server sustains the whole 5→10 rps
+/// range, so the search stops at the `load_end` cap with that as the knee.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn bench_adaptive_reports_max_sustainable_rps() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("adaptive.gctf");
+    std::fs::write(
+        &file,
+        format!(
+            "--- ADDRESS ---\n{address}\n\n--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- BENCH ---\nmode: adaptive\nduration: 10s\nconcurrency: 4\nload_start: 5\nload_end: 10\nload_step_duration: 1s\n\n--- REQUEST ---\n{{}}\n\n--- RESPONSE partial ---\n{{}}\n"
+        ),
+    )
+    .unwrap();
+
+    let output = cli_command()
+        .args(["bench", &file.to_string_lossy(), "--log-format", "json"])
+        .output()
+        .expect("failed to run bench");
+    assert!(
+        output.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["tags"]["exec_model"], "adaptive");
+    assert_eq!(report["summary"]["max_sustainable_rps"], 10.0);
+    assert_eq!(report["adaptive"]["stop_reason"], "rate_cap_reached");
+    let targets: Vec<f64> = report["adaptive"]["steps"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["target_rps"].as_f64().unwrap())
+        .collect();
+    assert_eq!(targets, [5.0, 10.0]);
+}
+
+/// When no probed rate holds the guards there is no knee: the report says so
+/// and the run fails its thresholds, judged at the first (starting) window.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn bench_adaptive_without_a_sustainable_rate_fails() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("adaptive-breach.gctf");
+    std::fs::write(
+        &file,
+        format!(
+            "--- ADDRESS ---\n{address}\n\n--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- BENCH ---\nmode: adaptive\nduration: 10s\nload_start: 4\nload_step_duration: 250ms\nthresholds.latency_ms.p(50): <0\n\n--- REQUEST ---\n{{}}\n\n--- RESPONSE partial ---\n{{}}\n"
+        ),
+    )
+    .unwrap();
+
+    let output = cli_command()
+        .args(["bench", &file.to_string_lossy(), "--log-format", "json"])
+        .output()
+        .expect("failed to run bench");
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["summary"].get("max_sustainable_rps").is_none());
+    assert!(report["adaptive"]["max_sustainable_rps"].is_null());
+    assert_eq!(report["adaptive"]["stop_reason"], "converged");
+    assert_eq!(report["threshold_evaluation"][0]["passed"], false);
+}
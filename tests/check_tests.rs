@@ -506,3 +506,209 @@ fn every_example_passes_check() {
         "expected a PASSED summary, got:\n{stdout}"
     );
 }
+
+const SCHEMA_PROTO: &str = "syntax = \"proto3\";\npackage demo;\nservice Users {\n  rpc Get (GetUser) returns (User);\n}\nenum Role { ROLE_UNSPECIFIED = 0; ADMIN = 1; }\nmessage GetUser { int64 id = 1; }\nmessage User { string name = 1; Role role = 2; }\nmessage NotFound { string resource = 1; }\n";
+
+/// A document's own PROTO section is picked up without any flag, and each
+/// schema violation comes back as its own JSON diagnostic on the right line.
+#[test]
+fn check_validates_payloads_against_the_proto_section() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("protos")).unwrap();
+    std::fs::write(dir.path().join("protos/users.proto"), SCHEMA_PROTO).unwrap();
+    let file = dir.path().join("users.gctf");
+    std::fs::write(
+        &file,
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n--- PROTO ---\nfiles: protos/users.proto\nimport_paths: protos\n\n--- REQUEST ---\n{\n  \"id\": \"abc\"\n}\n\n--- RESPONSE ---\n{\n  \"nmae\": \"Ada\",\n  \"role\": \"ROOT\"\n}\n",
+    )
+    .unwrap();
+
+    let output = support::cli_command()
+        .args(["check", "--format", "json", &file.to_string_lossy()])
+        .output()
+        .expect("failed to run check");
+    assert!(!output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let found: Vec<(String, u64)> = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"].as_str().unwrap().starts_with("SCHEMA_"))
+        .map(|d| {
+            (
+                d["code"].as_str().unwrap().to_string(),
+                d["range"]["start"]["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("SCHEMA_TYPE_MISMATCH".to_string(), 10),
+            ("SCHEMA_UNKNOWN_FIELD".to_string(), 15),
+            ("SCHEMA_INVALID_ENUM".to_string(), 16),
+        ]
+    );
+}
+
+/// `--proto <dir>` covers documents without a PROTO section; an unknown
+/// method is reported against the ENDPOINT line.
+#[test]
+fn check_proto_flag_reports_unknown_methods() {
+    let dir = tempfile::tempdir().unwrap();
+    let protos = dir.path().join("protos");
+    std::fs::create_dir(&protos).unwrap();
+    std::fs::write(protos.join("users.proto"), SCHEMA_PROTO).unwrap();
+    let file = dir.path().join("get.gctf");
+    std::fs::write(
+        &file,
+        "--- ENDPOINT ---\ndemo.Users/Fetch\n\n--- REQUEST ---\n{\"id\": 1}\n\n--- RESPONSE ---\n{\"name\": \"Ada\"}\n",
+    )
+    .unwrap();
+
+    let output = support::cli_command()
+        .args([
+            "check",
+            "--proto",
+            &protos.to_string_lossy(),
+            &file.to_string_lossy(),
+        ])
+        .output()
+        .expect("failed to run check");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("get.gctf:2: [SCHEMA_UNKNOWN_METHOD]")
+            && stdout.contains("Known methods: Get"),
+        "{stdout}"
+    );
+
+    // Without a schema source the same file passes: check never dials a server.
+    let output = support::cli_command()
+        .args(["check", &file.to_string_lossy()])
+        .output()
+        .expect("failed to run check");
+    assert!(output.status.success());
+}
+
+/// ERROR `details` are checked against the message their `@type` names.
+#[test]
+fn check_validates_error_details_against_their_type() {
+    let dir = tempfile::tempdir().unwrap();
+    let protos = dir.path().join("protos");
+    std::fs::create_dir(&protos).unwrap();
+    std::fs::write(protos.join("users.proto"), SCHEMA_PROTO).unwrap();
+    let file = dir.path().join("missing.gctf");
+    std::fs::write(
+        &file,
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n--- REQUEST ---\n{\"id\": 1}\n\n--- ERROR ---\n{\n  \"code\": 5,\n  \"details\": [{\"@type\": \"type.googleapis.com/demo.NotFound\", \"resouce\": \"user\"}]\n}\n",
+    )
+    .unwrap();
+
+    let output = support::cli_command()
+        .args([
+            "check",
+            "--proto",
+            &protos.to_string_lossy(),
+            &file.to_string_lossy(),
+        ])
+        .output()
+        .expect("failed to run check");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("missing.gctf:10: [SCHEMA_UNKNOWN_FIELD]")
+            && stdout.contains("details[0].resouce")
+            && stdout.contains("Did you mean 'resource'?"),
+        "{stdout}"
+    );
+}
+
+/// `check --format sarif` gives code-scanning dashboards one rule per code
+/// (with help text), results narrowed to the offending text, and the
+/// optimizer's rewrite as a fix; suite-wide notes have no file to point at.
+#[test]
+fn check_sarif_maps_rules_ranges_and_fixes() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("sarif.gctf");
+    std::fs::write(
+        &file,
+        "--- ADDRESS ---\nlocalhost:50051\n\n--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- REQUEST ---\n{}\n\n--- ASSERTS ---\n  !!@is_uuid(.id)\n",
+    )
+    .unwrap();
+
+    let output = support::cli_command()
+        .current_dir(dir.path())
+        .args(["check", "--format", "sarif", "sarif.gctf"])
+        .output()
+        .expect("failed to run check");
+    let sarif = support::parse_json_stdout(&output);
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+
+    let rule = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["id"] == "OPT_B017")
+        .unwrap_or_else(|| panic!("no OPT_B017 rule: {sarif}"));
+    assert!(!rule["help"]["text"].as_str().unwrap().is_empty());
+
+    let result = run["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["ruleId"] == "OPT_B017")
+        .unwrap();
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "sarif.gctf");
+    assert_eq!(
+        location["region"],
+        serde_json::json!({"startLine": 11, "startColumn": 3, "endLine": 11, "endColumn": 18})
+    );
+    let replacement = &result["fixes"][0]["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "@is_uuid(.id)");
+
+    let notes = run["invocations"][0]["toolExecutionNotifications"]
+        .as_array()
+        .unwrap();
+    assert!(
+        notes
+            .iter()
+            .any(|n| n["descriptor"]["id"] == "NO_ERROR_CASE_COVERAGE"),
+        "{sarif}"
+    );
+}
+
+/// `fmt --check --format gitlab` reports unformatted files as Code Quality
+/// issues on stdout, still exiting non-zero.
+#[test]
+fn fmt_check_reports_gitlab_code_quality() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("ugly.gctf"),
+        "--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- REQUEST ---\n{\"a\":1}\n\n--- RESPONSE ---\n{}\n",
+    )
+    .unwrap();
+
+    let output = support::cli_command()
+        .current_dir(dir.path())
+        .args(["fmt", "--check", "--format", "gitlab", "ugly.gctf"])
+        .output()
+        .expect("failed to run fmt");
+    assert!(!output.status.success());
+    let issues = support::parse_json_stdout_any_status(&output);
+    let issues = issues.as_array().unwrap();
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0]["check_name"], "FORMAT_NEEDED");
+    assert_eq!(issues[0]["location"]["path"], "ugly.gctf");
+    assert_eq!(issues[0]["location"]["lines"]["begin"], 5);
+    assert_eq!(issues[0]["fingerprint"].as_str().unwrap().len(), 64);
+
+    let output = support::cli_command()
+        .current_dir(dir.path())
+        .args(["fmt", "--write", "--format", "sarif", "ugly.gctf"])
+        .output()
+        .expect("failed to run fmt");
+    assert!(!output.status.success());
+}
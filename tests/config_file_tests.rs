@@ -0,0 +1,77 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `grpctestify.toml` / `.grpctestify/config.yaml` merged under CLI flags.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{run_in, spawn_health_server};
+
+/// A project whose config file targets `address` and writes a JSON report.
+/// The test file has no ADDRESS section.
+fn project(config: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("grpctestify.toml"), config).unwrap();
+    let tests = dir.path().join("tests");
+    std::fs::create_dir_all(&tests).unwrap();
+    std::fs::write(
+        tests.join("health.gctf"),
+        "--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST ---\n{}\n\n\
+         --- RESPONSE ---\n{\"status\": \"SERVING\"}\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn config_file_supplies_defaults_and_flags_override_them() {
+    let address = spawn_health_server().await;
+    let dir = project(&format!(
+        "[general]\naddress = \"{address}\"\nlog_format = \"json\"\nlog_output = \"report.json\"\n"
+    ));
+
+    // Run from the test directory: the config is found by walking up, and
+    // its relative log_output resolves against the project root.
+    let output = run_in(&dir.path().join("tests"), &["run", "."]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.path().join("report.json").is_file());
+
+    std::fs::remove_file(dir.path().join("report.json")).unwrap();
+    let output = run_in(dir.path(), &["tests", "--log-output", "flag.json"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.path().join("flag.json").is_file());
+    assert!(!dir.path().join("report.json").exists());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn config_show_annotates_each_value_with_its_source() {
+    let dir = project("[general]\ntimeout = 7\nparallel = 2\n");
+    let user = dir.path().join(".config/grpctestify");
+    std::fs::create_dir_all(&user).unwrap();
+    std::fs::write(
+        user.join("config.yaml"),
+        "general:\n  timeout: 60\n  retry: 2\n",
+    )
+    .unwrap();
+
+    let output = run_in(dir.path(), &["config", "show", "tests", "--format", "json"]).await;
+    assert!(output.status.success(), "{output:?}");
+    let shown: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let settings = &shown["settings"];
+    assert_eq!(shown["files"].as_array().unwrap().len(), 2);
+    assert_eq!(settings["general.timeout"]["value"], 7);
+    assert!(
+        settings["general.timeout"]["source"]
+            .as_str()
+            .unwrap()
+            .ends_with("grpctestify.toml")
+    );
+    assert_eq!(settings["general.retry"]["value"], 2);
+    assert!(
+        settings["general.retry"]["source"]
+            .as_str()
+            .unwrap()
+            .ends_with("config.yaml")
+    );
+    assert_eq!(settings["general.parallel"]["value"], "2");
+    assert_eq!(settings["progress.mode"]["source"], "default");
+}
@@ -246,6 +246,7 @@ fn connect_config(address: &str, descriptor: Option<&str>) -> GrpcClientConfig {
         connection_id: 0,
         protocol: WireProtocol::ConnectRpc,
         version: "test".to_string(),
+        proxy: None,
     }
 }
 
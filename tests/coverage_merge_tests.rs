@@ -0,0 +1,174 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `coverage merge` combines the JSON coverage reports of several shards, and
+//! `--coverage-min-*` / `--min-*` fail with the methods and fields that are
+//! still uncovered.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{health_test, run_in, run_isolated, spawn_health_server};
+
+/// One shard's report for `shop.Orders { Get, Cancel }`, both
+/// `GetOrder { id } -> Order { id, status }`.
+fn shard(get: u64, cancel: u64, order_fields: &[&str]) -> String {
+    let missing: Vec<&str> = ["id", "status"]
+        .into_iter()
+        .filter(|f| !order_fields.contains(f))
+        .collect();
+    let method = |name: &str, calls: u64| {
+        serde_json::json!({
+            "name": name,
+            "calls": calls,
+            "input_type": "shop.GetOrder",
+            "output_type": "shop.Order",
+        })
+    };
+    serde_json::json!({
+        "files": [{
+            "uri": "grpc://Orders",
+            "service": "shop.Orders",
+            "statements": {"covered": 0, "total": 2},
+            "methods": [method("Cancel", cancel), method("Get", get)],
+        }],
+        "messages": [
+            {"message_type": "shop.GetOrder", "covered_fields": ["id"], "total_fields": 1},
+            {
+                "message_type": "shop.Order",
+                "covered_fields": order_fields,
+                "total_fields": 2,
+                "missing_fields": missing,
+            },
+        ],
+        "summary": {"covered": 0, "total": 0},
+        "field_summary": {"covered": 0, "total": 0},
+    })
+    .to_string()
+}
+
+fn shards() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.json"), shard(2, 0, &["id"])).unwrap();
+    std::fs::write(dir.path().join("b.json"), shard(1, 1, &[])).unwrap();
+    dir
+}
+
+#[test]
+fn merge_combines_shards() {
+    let dir = shards();
+    let output = run_isolated(
+        dir.path(),
+        &[
+            "coverage",
+            "merge",
+            "a.json",
+            "b.json",
+            "-o",
+            "out/merged.json",
+            "--min-methods",
+            "100",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+
+    let merged: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("out/merged.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        merged["summary"],
+        serde_json::json!({"covered": 2, "total": 2})
+    );
+    assert_eq!(merged["files"][0]["methods"][1]["calls"], 3);
+    assert_eq!(
+        merged["field_summary"],
+        serde_json::json!({"covered": 2, "total": 3})
+    );
+
+    let text = run_isolated(
+        dir.path(),
+        &["coverage", "merge", "a.json", "b.json", "--format", "text"],
+    );
+    let stdout = String::from_utf8_lossy(&text.stdout);
+    assert!(stdout.contains("Coverage: 100.0% (2/2)"), "{stdout}");
+    assert!(stdout.contains("shop.Order (1/2)"), "{stdout}");
+}
+
+#[test]
+fn merge_fails_under_the_minimum_with_what_is_uncovered() {
+    let dir = shards();
+    let output = run_isolated(
+        dir.path(),
+        &[
+            "coverage",
+            "merge",
+            "a.json",
+            "b.json",
+            "--min-service",
+            "Orders=100:80",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Field coverage of shop.Orders is 66.7% (2/3), below the 80% minimum"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("shop.Order.status"), "{stderr}");
+    assert!(!stderr.contains("Method coverage"), "{stderr}");
+
+    let typo = run_isolated(
+        dir.path(),
+        &["coverage", "merge", "a.json", "--min-service", "Carts=50"],
+    );
+    assert!(!typo.status.success());
+    assert!(
+        String::from_utf8_lossy(&typo.stderr).contains("no service `Carts`"),
+        "{typo:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn run_gates_on_minimum_coverage() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("health.gctf"),
+        health_test(&address, "SERVING"),
+    )
+    .unwrap();
+
+    let below = run_in(
+        dir.path(),
+        &[
+            "run",
+            "health.gctf",
+            "--coverage-min-methods",
+            "100",
+            "--coverage-format",
+            "json",
+            "--coverage-output",
+            "coverage.json",
+        ],
+    )
+    .await;
+    let overridden = run_in(
+        dir.path(),
+        &[
+            "run",
+            "health.gctf",
+            "--coverage-min-service",
+            "grpc.health.v1.Health=50",
+            "--coverage-output",
+            "coverage.txt",
+        ],
+    )
+    .await;
+
+    assert_eq!(below.status.code(), Some(1), "{below:?}");
+    let stderr = String::from_utf8_lossy(&below.stderr);
+    assert!(stderr.contains("Coverage is below the minimum"), "{stderr}");
+    assert!(stderr.contains("grpc.health.v1.Health/Watch"), "{stderr}");
+    assert!(!stderr.contains("grpc.health.v1.Health/Check"), "{stderr}");
+    // The report is still written, so CI can upload it.
+    assert!(dir.path().join("coverage.json").exists());
+
+    assert!(overridden.status.success(), "{overridden:?}");
+}
@@ -0,0 +1,80 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --coverage` records the status codes each method returned and the
+//! enum values seen in requests and responses, against the server's schema.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{health_test, run_in, spawn_health_server};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn coverage_reports_status_codes_and_enum_values() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("serving.gctf"),
+        health_test(&address, "SERVING"),
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("unknown.gctf"),
+        format!(
+            "--- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- REQUEST ---\n{{\"service\": \"nope\"}}\n\n\
+             --- ERROR ---\n{{\"code\": 5, \"message\": \"service not registered\"}}\n"
+        ),
+    )
+    .unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            ".",
+            "--coverage",
+            "--coverage-format",
+            "json",
+            "--coverage-output",
+            "coverage.json",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("coverage.json")).unwrap())
+            .unwrap();
+    let health = report["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["service"] == "grpc.health.v1.Health")
+        .unwrap_or_else(|| panic!("no Health service in {report}"));
+    let check = health["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["name"] == "Check")
+        .unwrap();
+    assert_eq!(
+        check["statuses"],
+        serde_json::json!({"NotFound": 1, "OK": 1}),
+        "{check}"
+    );
+    assert_eq!(report["error_summary"]["covered"], 1, "{report}");
+
+    let serving_status = report["enums"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|e| e["name"] == "grpc.health.v1.HealthCheckResponse.ServingStatus")
+        .unwrap_or_else(|| panic!("no ServingStatus enum in {report}"));
+    assert_eq!(serving_status["covered"], serde_json::json!(["SERVING"]));
+    assert!(
+        serving_status["missing"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("NOT_SERVING")),
+        "{serving_status}"
+    );
+}
@@ -0,0 +1,116 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `META.depends_on`: a test runs after the files it depends on, starting
+//! with their EXTRACT variables, and is skipped when one of them fails.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{run_in, run_isolated, spawn_health_server};
+
+fn write(dir: &std::path::Path, rel: &str, content: &str) {
+    let path = dir.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+/// `auth/login.gctf` extracts the health status; `orders/create.gctf` only
+/// passes if it sees that variable.
+fn project(address: &str, login_expects: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "tests/auth/login.gctf",
+        &format!(
+            "--- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- REQUEST ---\n{{}}\n\n\
+             --- RESPONSE ---\n{{\"status\": \"{login_expects}\"}}\n\n\
+             --- EXTRACT ---\nstatus_val = .status\n"
+        ),
+    );
+    write(
+        dir.path(),
+        "tests/orders/create.gctf",
+        &format!(
+            "--- META ---\ndepends_on: [../auth/login.gctf]\n\n\
+             --- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- REQUEST ---\n{{}}\n\n\
+             --- RESPONSE ---\n{{\"status\": \"{{{{status_val}}}}\"}}\n"
+        ),
+    );
+    dir
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn dependency_runs_first_and_hands_over_its_variables() {
+    let address = spawn_health_server().await;
+    let dir = project(&address, "SERVING");
+
+    // Only the dependent is selected; its prerequisite is pulled in.
+    let output = run_in(dir.path(), &["run", "tests/orders", "--parallel", "4"]).await;
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Running 2 tests"), "{stdout}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failed_dependency_skips_its_dependents() {
+    let address = spawn_health_server().await;
+    let dir = project(&address, "NOT_SERVING");
+
+    let output = run_in(dir.path(), &["--verbose", "run", "tests"]).await;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Skipped: dependency tests/auth/login.gctf did not pass"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn cycles_fail_run_and_check() {
+    let dir = tempfile::tempdir().unwrap();
+    let test = |dep: &str| {
+        format!(
+            "--- META ---\ndepends_on: [{dep}]\n\n\
+             --- ADDRESS ---\n127.0.0.1:1\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- REQUEST ---\n{{}}\n\n\
+             --- RESPONSE ---\n{{}}\n"
+        )
+    };
+    write(dir.path(), "a.gctf", &test("b.gctf"));
+    write(dir.path(), "b.gctf", &test("a.gctf"));
+
+    let output = run_isolated(dir.path(), &["check", "a.gctf"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("DEPENDENCY_CYCLE"), "{stdout}");
+    assert!(stdout.contains("a.gctf → b.gctf → a.gctf"), "{stdout}");
+
+    let output = run_isolated(dir.path(), &["run", "a.gctf"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Dependency cycle: a.gctf → b.gctf"),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn check_reports_a_missing_dependency() {
+    let dir = tempfile::tempdir().unwrap();
+    write(
+        dir.path(),
+        "a.gctf",
+        "--- META ---\ndepends_on: [gone.gctf]\n\n\
+         --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST ---\n{}\n",
+    );
+    let output = run_isolated(dir.path(), &["check", "a.gctf"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("META.depends_on file not found: gone.gctf"),
+        "{stdout}"
+    );
+}
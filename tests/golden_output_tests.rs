@@ -72,6 +72,15 @@ fn scrub(text: &str, tmp_root: &Path) -> String {
     s = s.replace("<TMPDIR>\\\\", &format!("<TMPDIR>{target_sep}"));
     s = s.replace("<TMPDIR>\\", &format!("<TMPDIR>{target_sep}"));
 
+    // CTRF names a test's suite after its directory — here the tmp root's
+    // own random name, which the path replacements above never see alone.
+    if let Some(base) = tmp_root.file_name().and_then(|n| n.to_str()) {
+        s = s.replace(
+            &format!("\"suite\": \"{base}\""),
+            "\"suite\": \"<TMPDIR_NAME>\"",
+        );
+    }
+
     let http_date =
         regex::Regex::new(r"[A-Za-z]{3}, \d{2} [A-Za-z]{3} \d{4} \d{2}:\d{2}:\d{2} GMT").unwrap();
     s = http_date.replace_all(&s, "<HTTP_DATE>").into_owned();
@@ -371,6 +380,16 @@ async fn golden_allure() {
     run_report_form("run_allure", "allure").await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn golden_tap() {
+    run_report_form("run_tap", "tap").await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn golden_ctrf() {
+    run_report_form("run_ctrf", "ctrf").await;
+}
+
 /// §2.3: every form must exit 0 on an all-passing suite, independent of the
 /// golden content checks above (which deliberately use a mixed pass+fail
 /// suite to exercise both rendering paths).
@@ -422,7 +441,7 @@ async fn exit_code_is_zero_on_an_all_passing_suite_for_every_form() {
         "--stream must exit 0 on an all-passing suite"
     );
 
-    for format in ["json", "yaml", "junit", "html", "allure"] {
+    for format in ["json", "yaml", "junit", "html", "allure", "tap", "ctrf"] {
         let out_path = dir.path().join(format!("report-pass.{format}"));
         let output = support::cli_command()
             .env("NO_COLOR", "1")
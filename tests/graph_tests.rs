@@ -75,3 +75,27 @@ fn graph_on_directory_with_no_test_files_reports_nothing() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("No .gctf test files found"));
 }
+
+#[test]
+fn graph_mermaid_draws_depends_on_edges() {
+    let dir = tempfile::tempdir().unwrap();
+    write(dir.path(), "login.gctf", UNARY);
+    write(
+        dir.path(),
+        "order.gctf",
+        &format!("--- META ---\ndepends_on: [login.gctf]\n\n{UNARY}"),
+    );
+
+    let output = run_cli(&[
+        "graph",
+        &dir.path().to_string_lossy(),
+        "--format",
+        "mermaid",
+    ]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("n0 -. depends_on .-> n1"),
+        "stdout: {stdout}"
+    );
+}
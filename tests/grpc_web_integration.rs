@@ -4,6 +4,7 @@
 use std::sync::Arc;
 
 use grpctestify::assert::engine::{AssertionEngine, AssertionResult};
+use grpctestify::grpc::MetadataMap;
 use grpctestify::grpc::client::{CompressionMode, GrpcClientConfig, ProtoConfig, WireProtocol};
 use grpctestify::grpc::web::execute_web;
 use grpctestify::plugins::PluginManager;
@@ -48,6 +49,14 @@ impl EchoService for WebEcho {
         &self,
         request: Request<echo_proto::HelloRequest>,
     ) -> Result<Response<HelloResponse>, Status> {
+        // Repeated and binary request metadata, echoed back as leading metadata.
+        let tags: Vec<_> = request
+            .metadata()
+            .get_all("x-tag")
+            .iter()
+            .cloned()
+            .collect();
+        let trace = request.metadata().get_bin("x-trace-bin").cloned();
         let message = request.into_inner().message;
         if message == "boom" {
             return Err(Status::with_details(
@@ -63,6 +72,12 @@ impl EchoService for WebEcho {
         response
             .metadata_mut()
             .insert("x-custom-header", "custom-value".parse().unwrap());
+        for tag in tags {
+            response.metadata_mut().append("x-tag", tag);
+        }
+        if let Some(trace) = trace {
+            response.metadata_mut().insert_bin("x-trace-bin", trace);
+        }
         Ok(response)
     }
 
@@ -171,6 +186,7 @@ fn web_config(
         connection_id: 0,
         protocol: WireProtocol::GrpcWeb,
         version: "test".to_string(),
+        proxy: None,
     }
 }
 
@@ -402,6 +418,53 @@ async fn grpc_web_leading_metadata_header() {
     assert_eq!(header, AssertionResult::Pass, "headers: {:?}", resp.headers);
 }
 
+#[tokio::test]
+async fn grpc_web_repeated_and_binary_metadata_round_trip() {
+    let address = spawn_web_server().await;
+    let desc = descriptor_file();
+    let mut config = web_config(
+        &address,
+        &desc.path().to_string_lossy(),
+        CompressionMode::None,
+    );
+    let mut metadata = MetadataMap::new();
+    metadata.append("x-tag", "blue");
+    metadata.append("x-tag", "green");
+    // Unpadded in the test file; the server sees the decoded bytes `trace`.
+    metadata.append("x-trace-bin", "dHJhY2U");
+    config.metadata = Some(metadata);
+
+    let resp = execute_web(
+        &config,
+        "echo.EchoService",
+        "SayHello",
+        json!({ "message": "World" }),
+    )
+    .await
+    .expect("grpc-web unary call");
+
+    assert_eq!(
+        resp.headers.get_all("x-tag").collect::<Vec<_>>(),
+        ["blue", "green"]
+    );
+    // Received binary values are normalized to padded standard base64.
+    assert_eq!(
+        resp.headers.get("x-trace-bin").map(String::as_str),
+        Some("dHJhY2U=")
+    );
+
+    let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
+    let result = engine
+        .evaluate(
+            "@len(@headers(\"x-tag\")) == 2",
+            &json!({}),
+            Some(&resp.headers),
+            Some(&resp.trailers),
+        )
+        .expect("evaluate @headers");
+    assert_eq!(result, AssertionResult::Pass, "headers: {:?}", resp.headers);
+}
+
 #[tokio::test]
 async fn grpc_web_server_streaming() {
     let address = spawn_web_server().await;
@@ -47,6 +47,7 @@ fn args(address: String, service: &str) -> HealthArgs {
         watch: false,
         interval: 1.0,
         watch_timeout: 60.0,
+        proxy: None,
     }
 }
 
@@ -189,3 +190,57 @@ async fn watch_mode_retries_through_unreachable_server() {
         .expect_err("must eventually give up, not hang or panic");
     assert!(err.to_string().contains("timed out"), "{err}");
 }
+
+/// Like [`spawn_health_server`], but listening on a Unix domain socket bound
+/// at `path` (a leading NUL byte selects the Linux abstract namespace).
+#[cfg(unix)]
+async fn spawn_unix_health_server(path: &std::path::Path) -> tonic_health::server::HealthReporter {
+    let (reporter, health_service) = tonic_health::server::health_reporter();
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .expect("build reflection service");
+
+    let listener = tokio::net::UnixListener::bind(path).expect("bind unix socket");
+    let incoming = tokio_stream::wrappers::UnixListenerStream::new(listener);
+
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(health_service)
+            .add_service(reflection_service)
+            .serve_with_incoming(incoming)
+            .await
+            .expect("health server run");
+    });
+
+    reporter
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn unix_socket_address_is_dialled() {
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("health.sock");
+    let reporter = spawn_unix_health_server(&socket).await;
+    reporter
+        .set_service_status("", ServingStatus::Serving)
+        .await;
+
+    handle_health(&args(format!("unix://{}", socket.display()), ""))
+        .await
+        .expect("SERVING over a unix socket must be Ok");
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn unix_abstract_address_is_dialled() {
+    let name = format!("grpctestify-health-{}", std::process::id());
+    let reporter = spawn_unix_health_server(std::path::Path::new(&format!("\0{name}"))).await;
+    reporter
+        .set_service_status("", ServingStatus::Serving)
+        .await;
+
+    handle_health(&args(format!("unix-abstract:{name}"), ""))
+        .await
+        .expect("SERVING over an abstract socket must be Ok");
+}
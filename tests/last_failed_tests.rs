@@ -0,0 +1,136 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --last-failed` / `--failed-first`, driven by the outcome cache every
+//! run inside a project leaves behind in `.grpctestify/cache/last-run.json`.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{health_test, run_in, spawn_health_server};
+
+fn stdout(output: &std::process::Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn last_failed_reruns_failures_then_everything() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let tests = dir.path().join("tests");
+    std::fs::create_dir_all(&tests).unwrap();
+    std::fs::create_dir(dir.path().join(".grpctestify")).unwrap();
+    std::fs::write(tests.join("a.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(tests.join("b.gctf"), health_test(&address, "NOT_SERVING")).unwrap();
+
+    let output = run_in(dir.path(), &["run", "tests"]).await;
+    assert!(!output.status.success());
+    assert!(
+        dir.path()
+            .join(".grpctestify/cache/last-run.json")
+            .is_file()
+    );
+
+    let output = run_in(dir.path(), &["run", "tests", "--last-failed"]).await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Running 1 test"), "{output:?}");
+
+    std::fs::write(tests.join("b.gctf"), health_test(&address, "SERVING")).unwrap();
+    let output = run_in(dir.path(), &["run", "tests", "--last-failed"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout(&output).contains("Running 1 test"), "{output:?}");
+
+    // Nothing failing any more: everything runs.
+    let output = run_in(dir.path(), &["run", "tests", "--last-failed"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout(&output).contains("Running 2 tests"), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failed_first_puts_failures_at_the_front() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join(".grpctestify")).unwrap();
+    std::fs::write(dir.path().join("a.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(
+        dir.path().join("z.gctf"),
+        health_test(&address, "NOT_SERVING"),
+    )
+    .unwrap();
+    run_in(dir.path(), &["run", "."]).await;
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            ".",
+            "--failed-first",
+            "--parallel",
+            "1",
+            "--log-format",
+            "json",
+            "--log-output",
+            "report.json",
+        ],
+    )
+    .await;
+    assert!(!output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("report.json")).unwrap())
+            .unwrap();
+    let first = report["results"][0]["name"].as_str().unwrap();
+    assert!(first.ends_with("z.gctf"), "{report}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn last_failed_reruns_only_the_failing_dataset_rows() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join(".grpctestify")).unwrap();
+    std::fs::write(
+        dir.path().join("rows.gctf"),
+        format!(
+            "--- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- DATASET ---\n- status: SERVING\n- status: NOT_SERVING\n- status: SERVING\n\n\
+             --- REQUEST ---\n{{}}\n\n\
+             --- RESPONSE ---\n{{\"status\": \"{{{{dataset.status}}}}\"}}\n"
+        ),
+    )
+    .unwrap();
+
+    let output = run_in(dir.path(), &["run", "rows.gctf"]).await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Running 3 tests"), "{output:?}");
+
+    let output = run_in(dir.path(), &["run", "rows.gctf", "--last-failed"]).await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Running 1 test"), "{output:?}");
+
+    // The same file spelled differently still finds its failing row.
+    let output = run_in(dir.path(), &["run", "./rows.gctf", "--last-failed"]).await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Running 1 test"), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn outside_a_project_nothing_is_cached() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(
+        dir.path().join("b.gctf"),
+        health_test(&address, "NOT_SERVING"),
+    )
+    .unwrap();
+
+    let output = run_in(dir.path(), &["run", "."]).await;
+    assert!(!output.status.success());
+    // A cache here would make the directory a project.
+    assert!(!dir.path().join(".grpctestify").exists());
+
+    let output = run_in(dir.path(), &["run", ".", "--last-failed"]).await;
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("Running 2 tests"), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("need a .grpctestify project"),
+        "{output:?}"
+    );
+}
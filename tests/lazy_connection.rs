@@ -29,6 +29,7 @@ async fn local_proto_files_descriptors_loaded() {
         connection_id: 0,
         protocol: Default::default(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        proxy: None,
     };
 
     // Proto files should NOT be rejected — protox compiles them successfully.
@@ -0,0 +1,216 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `mock`: the CLI's own `run` against a stub server built from `.gctf`
+//! files, over every wire protocol.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{cli_command, run_isolated};
+
+const USERS_PROTO: &str = "syntax = \"proto3\";\npackage demo;\n\
+service Users {\n  rpc Get (GetUser) returns (User);\n  rpc List (ListUsers) returns (stream User);\n}\n\
+message GetUser { int32 id = 1; string trace = 2; }\n\
+message ListUsers { string team = 1; }\n\
+message User { int32 id = 1; string name = 2; }\n";
+
+/// Stubs for `demo.Users`, and client tests expecting exactly what they
+/// serve. The client tests have no PROTO section: native gRPC resolves the
+/// schema through the mock's reflection, gRPC-Web and Connect speak JSON.
+fn project() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let write = |rel: &str, content: &str| {
+        let path = dir.path().join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    };
+    write("protos/users.proto", USERS_PROTO);
+
+    write(
+        "stubs/get.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 1}\n\n\
+         --- RESPONSE ---\n{\"id\": 1, \"name\": \"Ada\"}\n",
+    );
+    write(
+        "stubs/traced.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST partial ---\n{\"trace\": \"on\"}\n\n\
+         --- RESPONSE ---\n{\"id\": 0, \"name\": \"Traced\"}\n",
+    );
+    write(
+        "stubs/missing.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 404}\n\n\
+         --- ERROR ---\n{\"code\": \"NOT_FOUND\", \"message\": \"user not found\"}\n",
+    );
+    write(
+        "stubs/list.gctf",
+        "--- ENDPOINT ---\ndemo.Users/List\n\n\
+         --- REQUEST ---\n{\"team\": \"core\"}\n\n\
+         --- RESPONSE ---\n{\"id\": 1, \"name\": \"Ada\"}\n\n\
+         --- RESPONSE ---\n{\"id\": 2, \"name\": \"Grace\"}\n",
+    );
+
+    write(
+        "client/get.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 1}\n\n\
+         --- RESPONSE ---\n{\"id\": 1, \"name\": \"Ada\"}\n",
+    );
+    write(
+        "client/traced.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 7, \"trace\": \"on\"}\n\n\
+         --- RESPONSE ---\n{\"name\": \"Traced\"}\n",
+    );
+    write(
+        "client/missing.gctf",
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 404}\n\n\
+         --- ERROR ---\n{\"code\": 5, \"message\": \"user not found\"}\n",
+    );
+    write(
+        "client/list.gctf",
+        "--- ENDPOINT ---\ndemo.Users/List\n\n\
+         --- REQUEST ---\n{\"team\": \"core\"}\n\n\
+         --- RESPONSE ---\n{\"id\": 1, \"name\": \"Ada\"}\n\n\
+         --- RESPONSE ---\n{\"id\": 2, \"name\": \"Grace\"}\n",
+    );
+    dir
+}
+
+/// A running `grpctestify mock`, killed on drop.
+struct Mock {
+    child: std::process::Child,
+    address: String,
+}
+
+impl Drop for Mock {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn start_mock(dir: &std::path::Path, args: &[&str]) -> Mock {
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let child = cli_command()
+        .current_dir(dir)
+        .args(["mock", "--port", &port.to_string()])
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let address = format!("127.0.0.1:{port}");
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+    while std::net::TcpStream::connect(&address).is_err() {
+        assert!(std::time::Instant::now() < deadline, "mock did not start");
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    Mock { child, address }
+}
+
+/// `run` in `dir` with the mock as the default target.
+fn run_against(dir: &std::path::Path, mock: &Mock, args: &[&str]) -> std::process::Output {
+    cli_command()
+        .current_dir(dir)
+        .env("HOME", dir)
+        .env_remove("XDG_CONFIG_HOME")
+        .env("GRPCTESTIFY_ADDRESS", &mock.address)
+        .arg("run")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn run_passes_against_the_mock_over_every_protocol() {
+    let dir = project();
+    let mock = start_mock(dir.path(), &["stubs", "--proto", "protos/users.proto"]);
+
+    for protocol in ["grpc", "grpc-web", "connectrpc"] {
+        let output = run_against(dir.path(), &mock, &["client", "--protocol", protocol]);
+        assert!(output.status.success(), "{protocol}: {output:?}");
+    }
+}
+
+#[test]
+fn unmatched_requests_fail_with_not_found() {
+    let dir = project();
+    let mock = start_mock(dir.path(), &["stubs", "--proto", "protos/users.proto"]);
+    std::fs::write(
+        dir.path().join("client/get.gctf"),
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST ---\n{\"id\": 99}\n\n\
+         --- ERROR ---\n{\"code\": 5, \"message\": \"no stub for demo.Users/Get matches request {\\\"id\\\":99}\"}\n",
+    )
+    .unwrap();
+
+    for protocol in ["grpc", "connectrpc"] {
+        let output = run_against(
+            dir.path(),
+            &mock,
+            &["client/get.gctf", "--protocol", protocol],
+        );
+        assert!(output.status.success(), "{protocol}: {output:?}");
+    }
+}
+
+#[test]
+fn grpc_web_text_and_json_without_a_schema() {
+    let dir = project();
+    // No --proto: stubs are matched as written, JSON content types only.
+    let mock = start_mock(dir.path(), &["stubs"]);
+    std::fs::write(
+        dir.path().join("client/get.gctf"),
+        "--- ENDPOINT ---\ndemo.Users/Get\n\n\
+         --- REQUEST_HEADERS ---\ngrpc-web-text: true\n\n\
+         --- REQUEST ---\n{\"id\": 1}\n\n\
+         --- RESPONSE ---\n{\"id\": 1, \"name\": \"Ada\"}\n",
+    )
+    .unwrap();
+
+    let output = run_against(
+        dir.path(),
+        &mock,
+        &["client/get.gctf", "--protocol", "grpc-web"],
+    );
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[test]
+fn reflection_lists_the_mocked_services() {
+    let dir = project();
+    let mock = start_mock(dir.path(), &["stubs", "--proto", "protos/users.proto"]);
+
+    let output = run_isolated(
+        dir.path(),
+        &[
+            "reflect",
+            "--address",
+            &mock.address,
+            "--plaintext",
+            "--list-methods",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("demo.Users") && stdout.contains("List"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn a_directory_without_stubs_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run_isolated(dir.path(), &["mock", "--port", "0"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("No stubs found"),
+        "{output:?}"
+    );
+}
@@ -1,8 +1,8 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
 use grpctestify::assert::engine::{AssertionEngine, AssertionResult};
+use grpctestify::grpc::MetadataMap;
 use grpctestify::plugins::PluginManager;
 use serde_json::json;
-use std::collections::HashMap;
 use std::sync::Arc;
 
 #[test]
@@ -135,8 +135,8 @@ fn len_plugin() {
 fn header_plugin() {
     let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
     let response = json!({});
-    let mut headers = HashMap::new();
-    headers.insert("content-type".to_string(), "application/json".to_string());
+    let mut headers = MetadataMap::new();
+    headers.append("content-type", "application/json");
 
     let result = engine
         .evaluate("@header(\"content-type\")", &response, Some(&headers), None)
@@ -447,8 +447,8 @@ fn len_edge_cases() {
 fn header_case_insensitive() {
     let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
     let response = json!({});
-    let mut headers = HashMap::new();
-    headers.insert("Content-Type".to_string(), "application/json".to_string());
+    let mut headers = MetadataMap::new();
+    headers.append("Content-Type", "application/json");
 
     // @has_header returns boolean - use explicit comparison
     let result = engine
@@ -467,8 +467,8 @@ fn header_case_insensitive() {
 fn trailer_plugin_basic() {
     let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
     let response = json!({});
-    let mut trailers = HashMap::new();
-    trailers.insert("grpc-status".to_string(), "0".to_string());
+    let mut trailers = MetadataMap::new();
+    trailers.append("grpc-status", "0");
 
     let result = engine
         .evaluate(
@@ -486,6 +486,35 @@ fn trailer_plugin_basic() {
     assert!(matches!(result, AssertionResult::Fail { .. }));
 }
 
+#[test]
+fn headers_and_trailers_plugins_return_every_value() {
+    let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
+    let response = json!({});
+    let mut headers = MetadataMap::new();
+    headers.append("set-cookie", "a=1");
+    headers.append("set-cookie", "b=2");
+    let mut trailers = MetadataMap::new();
+    trailers.append("x-warning", "slow");
+
+    for (expr, expected) in [
+        ("@len(@headers(\"set-cookie\")) == 2", true),
+        ("@headers(\"set-cookie\") contains \"b=2\"", true),
+        ("@header(\"set-cookie\") == \"a=1\"", true),
+        ("@trailers(\"x-warning\") contains \"slow\"", true),
+        ("@len(@trailers(\"x-missing\")) == 0", true),
+        ("@headers(\"set-cookie\") contains \"c=3\"", false),
+    ] {
+        let result = engine
+            .evaluate(expr, &response, Some(&headers), Some(&trailers))
+            .unwrap();
+        assert_eq!(
+            matches!(result, AssertionResult::Pass),
+            expected,
+            "{expr}: {result:?}"
+        );
+    }
+}
+
 #[test]
 fn multiple_plugins_in_one_assertion() {
     let engine = AssertionEngine::with_registry(Arc::new(PluginManager::new()));
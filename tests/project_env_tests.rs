@@ -0,0 +1,103 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `--env`: CLI runs against the playground's `.grpctestify` environments.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{run_in, spawn_health_server};
+
+/// A project whose `staging` env targets `address` (from the `.local` file, as
+/// a developer would keep it) and expects `SERVING`. The test file has no
+/// ADDRESS section.
+fn project(address: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join(".grpctestify");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        project.join("settings.json"),
+        r#"{"active_env": "staging"}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".env.staging"),
+        "GRPC_ADDRESS=\nEXPECTED_STATUS=SERVING\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".env.staging.local"),
+        format!("GRPC_ADDRESS={address}\n"),
+    )
+    .unwrap();
+    std::fs::write(
+        project.join(".env.broken"),
+        "GRPC_ADDRESS=127.0.0.1:1\nEXPECTED_STATUS=SERVING\n",
+    )
+    .unwrap();
+    let tests = dir.path().join("tests");
+    std::fs::create_dir_all(&tests).unwrap();
+    std::fs::write(
+        tests.join("health.gctf"),
+        "--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST ---\n{}\n\n\
+         --- RESPONSE ---\n{\"status\": \"{{EXPECTED_STATUS}}\"}\n",
+    )
+    .unwrap();
+    dir
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn explicit_env_supplies_address_and_variables() {
+    let address = spawn_health_server().await;
+    let dir = project(&address);
+
+    let output = run_in(dir.path(), &["run", "tests", "--env", "staging"]).await;
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn active_env_is_the_default_and_none_opts_out() {
+    let address = spawn_health_server().await;
+    let dir = project(&address);
+
+    let output = run_in(dir.path(), &["run", "tests/health.gctf"]).await;
+    assert!(output.status.success(), "{output:?}");
+
+    // No env: no GRPC_ADDRESS, so the file falls back to the default target.
+    let output = run_in(dir.path(), &["run", "tests", "--env", "none", "-t", "3"]).await;
+    assert!(!output.status.success(), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn env_selection_switches_the_target() {
+    let address = spawn_health_server().await;
+    let dir = project(&address);
+
+    let output = run_in(dir.path(), &["run", "tests", "--env", "broken", "-t", "3"]).await;
+    assert!(!output.status.success(), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn unknown_env_is_an_error_listing_the_known_ones() {
+    let dir = project("127.0.0.1:1");
+
+    let output = run_in(dir.path(), &["run", "tests", "--env", "prod"]).await;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Environment 'prod' not found") && stderr.contains("broken, staging"),
+        "{stderr}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn call_honours_the_env() {
+    let address = spawn_health_server().await;
+    let dir = project(&address);
+
+    let output = run_in(
+        dir.path(),
+        &["call", "tests/health.gctf", "--env", "staging"],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("SERVING"));
+}
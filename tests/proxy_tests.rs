@@ -0,0 +1,200 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! Native gRPC through an HTTP CONNECT proxy, against a local stand-in proxy
+//! that records every `CONNECT` it tunnels.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{cli_command, spawn_health_server};
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Request heads (`CONNECT` line plus headers) the stand-in has received.
+type Seen = Arc<Mutex<Vec<String>>>;
+
+/// Spawn a minimal CONNECT proxy on an ephemeral port. With `credentials`
+/// set, requests without the matching `Proxy-Authorization: Basic` header get
+/// a `407`. Returns the proxy's `host:port` and the log of request heads.
+async fn spawn_connect_proxy(credentials: Option<&'static str>) -> (String, Seen) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let seen: Seen = Arc::default();
+    let log = seen.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((client, _)) = listener.accept().await else {
+                return;
+            };
+            let log = log.clone();
+            tokio::spawn(async move {
+                let mut client = BufReader::new(client);
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    if client.read_line(&mut line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                log.lock().unwrap().push(head.clone());
+                let mut client = client.into_inner();
+                if let Some(expected) = credentials {
+                    let header = format!(
+                        "proxy-authorization: basic {}",
+                        apif_grpc_transport::encoding::base64_encode(expected.as_bytes())
+                    );
+                    if !head
+                        .to_ascii_lowercase()
+                        .contains(&header.to_ascii_lowercase())
+                    {
+                        let _ = client
+                            .write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n")
+                            .await;
+                        return;
+                    }
+                }
+                let target = head
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or_default()
+                    .to_string();
+                let Ok(mut upstream) = TcpStream::connect(&target).await else {
+                    let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await;
+                    return;
+                };
+                client
+                    .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+            });
+        }
+    });
+    (addr, seen)
+}
+
+/// Run the CLI with the proxy variables cleared, then `env` applied.
+async fn run_cli_with_env(args: &[&str], env: &[(&str, &str)]) -> std::process::Output {
+    let mut command = cli_command();
+    command.current_dir(env!("CARGO_MANIFEST_DIR")).args(args);
+    for var in [
+        "http_proxy",
+        "HTTP_PROXY",
+        "https_proxy",
+        "HTTPS_PROXY",
+        "no_proxy",
+        "NO_PROXY",
+    ] {
+        command.env_remove(var);
+    }
+    for (k, v) in env {
+        command.env(k, v);
+    }
+    tokio::task::spawn_blocking(move || command.output().unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn explicit_proxy_tunnels_with_basic_auth() {
+    let address = spawn_health_server().await;
+    let (proxy, seen) = spawn_connect_proxy(Some("ci-user:s3cret")).await;
+    let proxy_url = format!("http://ci-user:s3cret@{proxy}");
+
+    let output = run_cli_with_env(&["health", &address, "--proxy", &proxy_url], &[]).await;
+    assert!(output.status.success(), "{:?}", output);
+
+    let seen = seen.lock().unwrap();
+    assert!(!seen.is_empty(), "the call must go through the proxy");
+    assert!(
+        seen.iter()
+            .all(|head| head.starts_with(&format!("CONNECT {address} HTTP/1.1"))),
+        "{seen:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_proxy_env_is_honoured() {
+    let address = spawn_health_server().await;
+    let (proxy, seen) = spawn_connect_proxy(None).await;
+
+    let output = run_cli_with_env(
+        &["health", &address],
+        &[("HTTP_PROXY", &format!("http://{proxy}"))],
+    )
+    .await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!seen.lock().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_proxy_bypasses_the_proxy() {
+    let address = spawn_health_server().await;
+    let (proxy, seen) = spawn_connect_proxy(None).await;
+
+    let output = run_cli_with_env(
+        &["health", &address],
+        &[
+            ("HTTP_PROXY", &format!("http://{proxy}")),
+            ("NO_PROXY", "localhost,127.0.0.0/8"),
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn proxy_none_overrides_the_environment() {
+    let address = spawn_health_server().await;
+    let (proxy, seen) = spawn_connect_proxy(None).await;
+
+    let output = run_cli_with_env(
+        &["health", &address, "--proxy", "none"],
+        &[("HTTP_PROXY", &format!("http://{proxy}"))],
+    )
+    .await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rejected_credentials_fail_the_call() {
+    let address = spawn_health_server().await;
+    let (proxy, _seen) = spawn_connect_proxy(Some("ci-user:s3cret")).await;
+    let proxy_url = format!("http://ci-user:wrong@{proxy}");
+
+    let output = run_cli_with_env(
+        &["health", &address, "--proxy", &proxy_url, "--timeout", "3"],
+        &[],
+    )
+    .await;
+    assert!(!output.status.success(), "{:?}", output);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn options_proxy_routes_a_run() {
+    let address = spawn_health_server().await;
+    let (proxy, seen) = spawn_connect_proxy(None).await;
+    let dir = tempfile::tempdir().unwrap();
+    let test = dir.path().join("health.gctf");
+    std::fs::write(
+        &test,
+        format!(
+            "--- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- OPTIONS ---\nproxy: http://{proxy}\n\n\
+             --- REQUEST ---\n{{}}\n\n\
+             --- RESPONSE ---\n{{\"status\": \"SERVING\"}}\n"
+        ),
+    )
+    .unwrap();
+
+    let output = run_cli_with_env(&["run", test.to_str().unwrap()], &[]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!seen.lock().unwrap().is_empty());
+}
@@ -0,0 +1,74 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --record` against a live server, then `run --replay` with the server
+//! unreachable.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{health_test, health_test_for_service, run_in, run_isolated, spawn_health_server};
+
+/// An address nothing listens on: replay must not need the server.
+const DEAD_ADDRESS: &str = "127.0.0.1:1";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn recorded_run_replays_without_the_server() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let test = dir.path().join("tests/health.gctf");
+    std::fs::create_dir_all(test.parent().unwrap()).unwrap();
+    std::fs::write(&test, health_test(&address, "SERVING")).unwrap();
+
+    let output = run_in(dir.path(), &["run", "tests", "--record", "cassettes"]).await;
+    assert!(output.status.success(), "{output:?}");
+    let cassette =
+        std::fs::read_to_string(dir.path().join("cassettes/tests/health.gctf.json")).unwrap();
+    let cassette: serde_json::Value = serde_json::from_str(&cassette).unwrap();
+    let interaction = &cassette["interactions"][0];
+    assert_eq!(interaction["endpoint"], "grpc.health.v1.Health/Check");
+    assert_eq!(
+        interaction["events"],
+        serde_json::json!([{"send": {}}, {"receive": {"status": "SERVING"}}])
+    );
+
+    std::fs::write(&test, health_test(DEAD_ADDRESS, "SERVING")).unwrap();
+    let output = run_in(dir.path(), &["run", "tests", "--replay", "cassettes"]).await;
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replay_fails_on_an_unrecorded_request() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let test = dir.path().join("health.gctf");
+    std::fs::write(&test, health_test(&address, "SERVING")).unwrap();
+    let output = run_in(dir.path(), &["run", "health.gctf", "--record", "cassettes"]).await;
+    assert!(output.status.success(), "{output:?}");
+
+    std::fs::write(
+        &test,
+        health_test_for_service(DEAD_ADDRESS, "billing", "SERVING"),
+    )
+    .unwrap();
+    let output = run_in(dir.path(), &["run", "health.gctf", "--replay", "cassettes"]).await;
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("replay: no recorded call for grpc.health.v1.Health/Check"),
+        "{output:?}"
+    );
+}
+
+#[test]
+fn replay_needs_an_existing_cassette_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("health.gctf"),
+        health_test(DEAD_ADDRESS, "SERVING"),
+    )
+    .unwrap();
+    let output = run_isolated(dir.path(), &["run", "health.gctf", "--replay", "missing"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Cassette directory missing"),
+        "{output:?}"
+    );
+}
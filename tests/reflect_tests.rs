@@ -191,3 +191,52 @@ async fn reflect_describe_json_emits_a_json_schema() {
     assert!(values.contains(&"SERVING"));
     assert!(values.contains(&"NOT_SERVING"));
 }
+
+/// A health server that registers only the legacy `grpc.reflection.v1alpha`
+/// service, like pre-1.60 grpc-go servers.
+async fn spawn_v1alpha_only_health_server() -> String {
+    let (reporter, health_service) = tonic_health::server::health_reporter();
+    reporter
+        .set_service_status("", tonic_health::ServingStatus::Serving)
+        .await;
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1alpha()
+        .expect("build v1alpha reflection service");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind ephemeral port");
+    let addr = listener.local_addr().expect("local addr");
+    let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .add_service(health_service)
+            .add_service(reflection_service)
+            .serve_with_incoming(incoming)
+            .await
+            .expect("health server run");
+    });
+    addr.to_string()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reflect_falls_back_to_v1alpha() {
+    let address = spawn_v1alpha_only_health_server().await;
+    let output = run_cli(&[
+        "reflect",
+        "--address",
+        &address,
+        "--plaintext",
+        "--list-methods",
+    ]);
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("grpc.health.v1.Health/Check"),
+        "a v1alpha-only server must still be reflected: {stdout}"
+    );
+    assert!(
+        !stdout.contains("ServerReflection"),
+        "the reflection service itself must not be listed: {stdout}"
+    );
+}
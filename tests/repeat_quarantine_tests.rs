@@ -0,0 +1,154 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --repeat` / `--until-failure` and quarantined tests, whose failures
+//! are reported but leave the exit code alone.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{health_test, run_in, spawn_health_server};
+
+fn report(dir: &std::path::Path, name: &str) -> String {
+    std::fs::read_to_string(dir.join(name)).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn repeat_attaches_run_statistics() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("ok.gctf"), health_test(&address, "SERVING")).unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            "ok.gctf",
+            "--repeat",
+            "3",
+            "--log-format",
+            "json",
+            "--log-output",
+            "report.json",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_str(&report(dir.path(), "report.json")).unwrap();
+    let repeat = &json["results"][0]["repeat"];
+    assert_eq!(repeat["runs"], 3, "{json}");
+    assert_eq!(repeat["passed"], 3, "{json}");
+    assert_eq!(repeat["pass_rate"], 1.0, "{json}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn until_failure_stops_at_the_first_failing_run() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("bad.gctf"),
+        health_test(&address, "NOT_SERVING"),
+    )
+    .unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            "bad.gctf",
+            "--until-failure",
+            "--log-format",
+            "json",
+            "--log-output",
+            "report.json",
+        ],
+    )
+    .await;
+    assert!(!output.status.success(), "{output:?}");
+    let json: serde_json::Value = serde_json::from_str(&report(dir.path(), "report.json")).unwrap();
+    let repeat = &json["results"][0]["repeat"];
+    assert_eq!(repeat["runs"], 1, "{json}");
+    assert_eq!(repeat["failures"][0]["count"], 1, "{json}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Repeat Results"),
+        "{output:?}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn meta_quarantined_failure_passes_the_run() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("flaky.gctf"),
+        format!(
+            "--- META ---\nquarantine: true\n\n{}",
+            health_test(&address, "NOT_SERVING")
+        ),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("ok.gctf"), health_test(&address, "SERVING")).unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            ".",
+            "--log-format",
+            "json",
+            "--log-output",
+            "report.json",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Quarantined Failures"),
+        "{output:?}"
+    );
+    let json: serde_json::Value = serde_json::from_str(&report(dir.path(), "report.json")).unwrap();
+    assert_eq!(json["failed"], 0, "{json}");
+    assert_eq!(json["quarantined"], 1, "{json}");
+    let flaky = json["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["name"].as_str().unwrap().ends_with("flaky.gctf"))
+        .unwrap();
+    assert_eq!(flaky["status"], "Fail", "{json}");
+    assert_eq!(flaky["quarantined"], true, "{json}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn quarantine_file_lists_tests_and_junit_skips_them() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let tests = dir.path().join("tests/flaky");
+    std::fs::create_dir_all(&tests).unwrap();
+    std::fs::create_dir(dir.path().join(".grpctestify")).unwrap();
+    std::fs::write(
+        dir.path().join(".grpctestify/quarantine"),
+        "# known flaky\ntests/flaky/\n",
+    )
+    .unwrap();
+    std::fs::write(tests.join("bad.gctf"), health_test(&address, "NOT_SERVING")).unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            "tests",
+            "--log-format",
+            "junit",
+            "--log-output",
+            "report.xml",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+    let xml = report(dir.path(), "report.xml");
+    assert!(xml.contains("Quarantined failure"), "{xml}");
+    assert!(xml.contains(r#"failures="0""#), "{xml}");
+
+    // Off the list it fails the run again.
+    std::fs::write(dir.path().join(".grpctestify/quarantine"), "").unwrap();
+    let output = run_in(dir.path(), &["run", "tests"]).await;
+    assert!(!output.status.success(), "{output:?}");
+}
@@ -103,9 +103,14 @@ fn junit_reporter_skipped_test() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     };
     results.add(skip_result);
 
@@ -137,9 +142,14 @@ fn json_reporter_on_suite_end() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     };
     results.add(pass_result);
 
@@ -208,9 +218,14 @@ fn json_reporter_round_trip() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     });
     results.add(TestResult {
         name: "test_b.gctf".to_string(),
@@ -223,9 +238,14 @@ fn json_reporter_round_trip() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     });
 
     // Act
@@ -262,9 +282,14 @@ fn console_reporter_verbose_mode() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     };
     reporter.on_test_end("test_verbose.gctf", &result);
 }
@@ -292,9 +317,14 @@ fn console_reporter_dots_mode() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     };
     let fail1 = TestResult::fail("test2.gctf", "error".to_string(), 20, None);
     reporter.on_test_end("test1.gctf", &pass1);
@@ -348,9 +378,14 @@ fn console_reporter_print_slowest_tests() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
         TestResult {
             name: "slow.gctf".to_string(),
@@ -363,9 +398,14 @@ fn console_reporter_print_slowest_tests() {
             assertions: Vec::new(),
             exchange: None,
             retried: false,
+            attempts: None,
             document_durations_ms: Vec::new(),
             row_params: Vec::new(),
             config_summary: ConfigSummary::default(),
+            quarantined: false,
+            repeat: None,
+            grpc_status: None,
+            trace: None,
         },
     ];
 
@@ -425,6 +465,7 @@ fn coverage_stats() {
 fn coverage_file() {
     let file = grpctestify::report::coverage::CoverageFile {
         uri: "grpc://test.Service".to_string(),
+        service: None,
         statements: grpctestify::report::coverage::CoverageStats {
             covered: 2,
             total: 5,
@@ -482,9 +523,14 @@ fn junit_reporter_tags_in_properties() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     });
 
     let result = reporter.on_suite_end(&results);
@@ -520,9 +566,14 @@ fn json_reporter_includes_meta() {
         assertions: Vec::new(),
         exchange: None,
         retried: false,
+        attempts: None,
         document_durations_ms: Vec::new(),
         row_params: Vec::new(),
         config_summary: ConfigSummary::default(),
+        quarantined: false,
+        repeat: None,
+        grpc_status: None,
+        trace: None,
     });
 
     let result = reporter.on_suite_end(&results);
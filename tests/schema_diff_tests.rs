@@ -0,0 +1,127 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `schema-diff <old> <new>`: the exit code gates on breaking changes, and
+//! each breaking change lists the suite lines it hits.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{run_in, run_isolated, spawn_health_server};
+
+const OLD: &str = r#"syntax = "proto3";
+package users;
+service Users {
+  rpc Get (GetUser) returns (User);
+  rpc Delete (GetUser) returns (User);
+}
+enum Role { ROLE_UNKNOWN = 0; ADMIN = 1; GUEST = 2; }
+message GetUser { string id = 1; }
+message User { string id = 1; string email = 2; Role role = 3; }
+"#;
+
+const TEST: &str = "--- ENDPOINT ---
+users.Users/Get
+
+--- REQUEST ---
+{\"id\": \"42\"}
+
+--- RESPONSE ---
+{
+  \"id\": \"42\",
+  \"role\": \"GUEST\"
+}
+
+--- ASSERTS ---
+.email endsWith \"@example.com\"
+";
+
+fn setup(new: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("v1")).unwrap();
+    std::fs::create_dir_all(dir.path().join("v2")).unwrap();
+    std::fs::create_dir_all(dir.path().join("tests")).unwrap();
+    std::fs::write(dir.path().join("v1/users.proto"), OLD).unwrap();
+    std::fs::write(dir.path().join("v2/users.proto"), new).unwrap();
+    std::fs::write(dir.path().join("tests/get_user.gctf"), TEST).unwrap();
+    dir
+}
+
+#[test]
+fn breaking_changes_fail_and_name_the_lines_they_hit() {
+    let new = OLD
+        .replace("string email = 2;", "string mail = 2;")
+        .replace(" GUEST = 2;", "")
+        .replace("  rpc Delete (GetUser) returns (User);\n", "");
+    let dir = setup(&new);
+
+    let output = run_isolated(
+        dir.path(),
+        &[
+            "schema-diff",
+            "v1",
+            "v2/users.proto",
+            "--tests",
+            "tests",
+            "--format",
+            "json",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["breaking"], 3, "{report}");
+    assert_eq!(report["impacted_tests"][0], "tests/get_user.gctf");
+
+    let change = |subject: &str| {
+        report["changes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["subject"] == subject)
+            .unwrap_or_else(|| panic!("no change for {subject} in {report}"))
+            .clone()
+    };
+    let renamed = change("users.User.email");
+    assert_eq!(renamed["kind"], "field_renamed");
+    assert_eq!(renamed["impacts"][0]["section"], "ASSERTS");
+    assert_eq!(renamed["impacts"][0]["line"], 14);
+
+    let removed_value = change("users.Role.GUEST");
+    assert_eq!(removed_value["kind"], "enum_value_removed");
+    assert_eq!(removed_value["impacts"][0]["section"], "RESPONSE");
+    assert_eq!(removed_value["impacts"][0]["line"], 10);
+    assert_eq!(removed_value["impacts"][0]["text"], "\"role\": \"GUEST\"");
+
+    let removed_method = change("users.Users/Delete");
+    assert_eq!(removed_method["kind"], "method_removed");
+    assert!(removed_method.get("impacts").is_none(), "{removed_method}");
+}
+
+#[test]
+fn additive_changes_pass() {
+    let new = OLD
+        .replace(" GUEST = 2;", " GUEST = 2; OWNER = 3;")
+        .replace("Role role = 3; }", "Role role = 3; string name = 4; }");
+    let dir = setup(&new);
+
+    let output = run_isolated(dir.path(), &["schema-diff", "v1", "v2", "--tests", "tests"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Field string name (#4) was added"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("PASS: 2 non-breaking change(s)"),
+        "{stdout}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn reflects_a_server_on_either_side() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let output = run_in(dir.path(), &["schema-diff", &address, &address]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("No schema changes"),
+        "{output:?}"
+    );
+}
@@ -23,6 +23,24 @@ fn test_app(collections_dir: PathBuf) -> Router {
         project_settings: None,
         history_lock: tokio::sync::Mutex::new(()),
         collections_mtime: Arc::new(AtomicU64::new(0)),
+        live_calls: Default::default(),
+        access: Default::default(),
+    });
+    serve::build_app(state)
+}
+
+/// A test app restricted by `play --auth-token`/`--htpasswd`/`--read-only`/`--audit-log`.
+fn test_app_with_access(collections_dir: PathBuf, access: serve::access::AccessControl) -> Router {
+    let state = Arc::new(PlayState {
+        collections_dir: collections_dir.clone(),
+        collections_dirs: vec![collections_dir.clone()],
+        shares_dir: collections_dir.join("shares"),
+        project_root: None,
+        project_settings: None,
+        history_lock: tokio::sync::Mutex::new(()),
+        collections_mtime: Arc::new(AtomicU64::new(0)),
+        live_calls: Default::default(),
+        access,
     });
     serve::build_app(state)
 }
@@ -41,6 +59,8 @@ fn test_app_project(dir: PathBuf) -> Router {
         project_settings: grpctestify::serve::project::load_project_settings(&project_root).ok(),
         history_lock: tokio::sync::Mutex::new(()),
         collections_mtime: Arc::new(AtomicU64::new(0)),
+        live_calls: Default::default(),
+        access: Default::default(),
     });
     serve::build_app(state)
 }
@@ -207,6 +227,32 @@ async fn save_structured() {
     assert_eq!(body["parsed"]["address"], "h:9");
 }
 
+#[tokio::test]
+async fn parsed_collection_keeps_repeated_headers() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = start_server(test_app(dir.path().to_path_buf())).await;
+
+    let content = serde_json::json!({"path": "h.gctf", "content": "--- ENDPOINT ---\nsvc.M/C\n--- REQUEST_HEADERS ---\nx-tag: a\nx-tag: b\nx-user: ada\n--- REQUEST ---\n{}\n"});
+    let (status, _) = post_json(&url, "/api/save", &content).await;
+    assert_eq!(status, 200);
+
+    let (_, body) = get_json(&url, "/api/collections/h.gctf").await;
+    assert_eq!(
+        body["parsed"]["headers"],
+        serde_json::json!({"x-tag": ["a", "b"], "x-user": "ada"})
+    );
+
+    // Sent back as the UI gets it, the repeats survive a structured save.
+    let req = serde_json::json!({"path": "h.gctf", "endpoint": "svc.M/C", "headers": body["parsed"]["headers"]});
+    let (status, _) = post_json(&url, "/api/save-structured", &req).await;
+    assert_eq!(status, 200);
+    let (_, body) = get_json(&url, "/api/collections/h.gctf").await;
+    assert_eq!(
+        body["parsed"]["headers"]["x-tag"],
+        serde_json::json!(["a", "b"])
+    );
+}
+
 #[tokio::test]
 async fn save_traversal() {
     let url = start_server(test_app(PathBuf::from("examples"))).await;
@@ -819,3 +865,592 @@ async fn reflect_honors_client_cert_fields() {
         "expected the client-cert-path to have actually been read: {error}"
     );
 }
+
+// ─── /api/call/stream — interactive calls over server-sent events ─────────
+
+/// Reads `event:`/`data:` pairs off a server-sent event response.
+struct EventReader {
+    resp: reqwest::Response,
+    buf: String,
+}
+
+impl EventReader {
+    async fn next(&mut self) -> Option<(String, serde_json::Value)> {
+        loop {
+            if let Some(end) = self.buf.find("\n\n") {
+                let block: String = self.buf.drain(..end + 2).collect();
+                let field = |name: &str| {
+                    block
+                        .lines()
+                        .find_map(|l| l.strip_prefix(name))
+                        .map(|v| v.trim_start().to_string())
+                };
+                if let (Some(event), Some(data)) = (field("event:"), field("data:")) {
+                    return Some((event, serde_json::from_str(&data).unwrap()));
+                }
+                continue;
+            }
+            let chunk = self.resp.chunk().await.unwrap()?;
+            self.buf.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+
+    async fn until(&mut self, event: &str) -> serde_json::Value {
+        loop {
+            let (name, data) = self.next().await.expect("stream ended");
+            if name == event {
+                return data;
+            }
+        }
+    }
+}
+
+async fn open_stream(url: &str, body: &serde_json::Value) -> EventReader {
+    open_events(url, "/api/call/stream", body).await
+}
+
+async fn open_events(url: &str, path: &str, body: &serde_json::Value) -> EventReader {
+    let resp = reqwest::Client::new()
+        .post(format!("{url}{path}"))
+        .json(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    EventReader {
+        resp,
+        buf: String::new(),
+    }
+}
+
+/// `Health/Watch` never ends on its own: the first message must reach the
+/// UI while the call is still open, and cancelling it is recorded.
+#[tokio::test]
+async fn call_stream_forwards_messages_as_they_arrive_and_cancels() {
+    let address = support::spawn_health_server().await;
+    let dir = setup_project_dir("call-stream");
+    let url = start_server(test_app_project(dir.clone())).await;
+
+    let mut events = open_stream(
+        &url,
+        &serde_json::json!({
+            "endpoint": "grpc.health.v1.Health/Watch",
+            "body": {},
+            "address": address,
+            "session_id": "live-session",
+        }),
+    )
+    .await;
+    let call = events.until("call").await;
+    assert_eq!(call["mode"], "server_streaming");
+    let id = call["id"].as_str().unwrap().to_string();
+    assert_eq!(events.until("sent").await["message"], serde_json::json!({}));
+    events.until("headers").await;
+    assert_eq!(
+        events.until("message").await["message"],
+        serde_json::json!({"status": "SERVING"})
+    );
+
+    // A server-streaming call takes one request, so its stream is closed.
+    let (status, _) = post_json(
+        &url,
+        &format!("/api/call/stream/{id}/send"),
+        &serde_json::json!({"body": {}}),
+    )
+    .await;
+    assert_eq!(status, 409);
+
+    assert_eq!(
+        delete_req(&url, &format!("/api/call/stream/{id}")).await,
+        202
+    );
+    assert_eq!(events.until("end").await["status"], "cancelled");
+    assert!(events.next().await.is_none(), "the event stream ends");
+    assert_eq!(
+        delete_req(&url, &format!("/api/call/stream/{id}")).await,
+        404
+    );
+
+    let (_, history) = get_json(&url, "/api/project/history").await;
+    let entry = &history["live-session"][0];
+    assert_eq!(entry["kind"], "stream", "{history:#}");
+    assert_eq!(entry["id"], id.as_str());
+    assert_eq!(entry["response"]["status"], "cancelled");
+    assert_eq!(
+        entry["response"]["messages"],
+        serde_json::json!([{"status": "SERVING"}])
+    );
+    let transcript: Vec<&str> = entry["transcript"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        transcript,
+        ["call", "sent", "headers", "message", "end"],
+        "{entry:#}"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn call_stream_reports_errors_and_unknown_calls() {
+    let address = support::spawn_health_server().await;
+    let url = start_server(test_app(PathBuf::from("examples"))).await;
+
+    let mut events = open_stream(
+        &url,
+        &serde_json::json!({
+            "endpoint": "grpc.health.v1.Health/Check",
+            "body": {"service": "nope"},
+            "address": address,
+        }),
+    )
+    .await;
+    let error = events.until("error").await;
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap()
+            .contains("service not registered"),
+        "{error}"
+    );
+    assert_eq!(events.until("end").await["status"], "error");
+
+    let (status, _) = post_json(&url, "/api/call/stream/nope/close", &serde_json::json!({})).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn call_stream_cancels_when_the_ui_disconnects() {
+    let address = support::spawn_health_server().await;
+    let dir = setup_project_dir("call-stream-gone");
+    let url = start_server(test_app_project(dir.clone())).await;
+
+    let mut events = open_stream(
+        &url,
+        &serde_json::json!({
+            "endpoint": "grpc.health.v1.Health/Watch",
+            "body": {},
+            "address": address,
+            "session_id": "gone",
+        }),
+    )
+    .await;
+    events.until("message").await;
+    drop(events);
+
+    let mut history = serde_json::Value::Null;
+    for _ in 0..50 {
+        (_, history) = get_json(&url, "/api/project/history").await;
+        if history["gone"].is_array() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(
+        history["gone"][0]["response"]["status"], "cancelled",
+        "{history:#}"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Shared playgrounds: auth, CSRF, read-only, audit ─────────
+
+/// `alice` / `Hello world!` (the SHA-crypt specification's test vector).
+const HTPASSWD: &str = "alice:$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5\n";
+
+#[tokio::test]
+async fn auth_token_is_required_by_header_or_cookie() {
+    let dir = tempfile::tempdir().unwrap();
+    let access =
+        serve::access::AccessControl::new(Some("s3cret".into()), None, false, None).unwrap();
+    let url = start_server(test_app_with_access(dir.path().to_path_buf(), access)).await;
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let denied = client
+        .get(format!("{url}/api/collections"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(denied.status().as_u16(), 401);
+    assert_eq!(denied.headers()["www-authenticate"], "Bearer");
+    let (status, _) = get_json(&url, "/api/health").await;
+    assert_eq!(status, 200, "health stays open for load balancers");
+
+    let bearer = client
+        .get(format!("{url}/api/collections"))
+        .bearer_auth("s3cret")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bearer.status().as_u16(), 200);
+
+    let login = client
+        .get(format!("{url}/?token=s3cret"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(login.status().as_u16(), 303);
+    assert_eq!(login.headers()["location"], "/");
+    let cookie = login.headers()["set-cookie"].to_str().unwrap();
+    assert!(cookie.contains("HttpOnly"), "{cookie}");
+    let with_cookie = client
+        .get(format!("{url}/api/collections"))
+        .header("cookie", cookie.split(';').next().unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(with_cookie.status().as_u16(), 200);
+
+    let wrong = client
+        .get(format!("{url}/?token=guess"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn htpasswd_users_sign_in_with_basic_auth() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".htpasswd"), HTPASSWD).unwrap();
+    let access =
+        serve::access::AccessControl::new(None, Some(&dir.path().join(".htpasswd")), false, None)
+            .unwrap();
+    let url = start_server(test_app_with_access(dir.path().to_path_buf(), access)).await;
+    let client = reqwest::Client::new();
+    let status = |user: &'static str, password: &'static str| {
+        let request = client
+            .get(format!("{url}/api/collections"))
+            .basic_auth(user, Some(password));
+        async move { request.send().await.unwrap() }
+    };
+
+    assert_eq!(status("alice", "Hello world!").await.status().as_u16(), 200);
+    let denied = status("alice", "hello").await;
+    assert_eq!(denied.status().as_u16(), 401);
+    assert!(
+        denied.headers()["www-authenticate"]
+            .to_str()
+            .unwrap()
+            .starts_with("Basic "),
+        "the browser prompts for a login"
+    );
+    assert_eq!(status("bob", "Hello world!").await.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn cross_site_writes_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = start_server(test_app(dir.path().to_path_buf())).await;
+    let client = reqwest::Client::new();
+    let save = |origin: String| {
+        client
+            .post(format!("{url}/api/save"))
+            .header("origin", origin)
+            .json(&serde_json::json!({"path": "a.gctf", "content": "--- ENDPOINT ---\na.B/C\n"}))
+            .send()
+    };
+
+    let forged = save("https://evil.example".into()).await.unwrap();
+    assert_eq!(forged.status().as_u16(), 403);
+    assert!(!dir.path().join("a.gctf").exists());
+
+    let same_origin = save(url.clone()).await.unwrap();
+    assert_eq!(same_origin.status().as_u16(), 200);
+    assert!(dir.path().join("a.gctf").exists());
+}
+
+#[tokio::test]
+async fn read_only_refuses_writes_but_runs_calls() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("keep.gctf"), "--- ENDPOINT ---\na.B/C\n").unwrap();
+    let access = serve::access::AccessControl::new(None, None, true, None).unwrap();
+    let url = start_server(test_app_with_access(dir.path().to_path_buf(), access)).await;
+
+    let (status, _) = post_json(
+        &url,
+        "/api/save",
+        &serde_json::json!({"path": "new.gctf", "content": "--- ENDPOINT ---\na.B/C\n"}),
+    )
+    .await;
+    assert_eq!(status, 403);
+    assert_eq!(delete_req(&url, "/api/collections/keep.gctf").await, 403);
+    let (status, _) = put_json(&url, "/api/project/env/staging", &serde_json::json!({})).await;
+    assert_eq!(status, 403);
+    assert!(dir.path().join("keep.gctf").exists());
+
+    let (status, body) = get_json(&url, "/api/collections/keep.gctf").await;
+    assert_eq!(status, 200, "{body:#}");
+    let (_, info) = get_json(&url, "/api/info").await;
+    assert_eq!(info["read_only"], true);
+    let (status, _) = post_json(
+        &url,
+        "/api/call",
+        &serde_json::json!({"endpoint": "x.Y/z", "body": {}, "address": "127.0.0.1:1"}),
+    )
+    .await;
+    assert_eq!(status, 200, "calls still run");
+}
+
+#[tokio::test]
+async fn audit_log_records_who_called_what() {
+    let address = support::spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".htpasswd"), HTPASSWD).unwrap();
+    let audit = dir.path().join("audit.ndjson");
+    let access = serve::access::AccessControl::new(
+        None,
+        Some(&dir.path().join(".htpasswd")),
+        false,
+        Some(&audit),
+    )
+    .unwrap();
+    let url = start_server(test_app_with_access(dir.path().to_path_buf(), access)).await;
+
+    let resp = reqwest::Client::new()
+        .post(format!("{url}/api/call"))
+        .basic_auth("alice", Some("Hello world!"))
+        .json(&serde_json::json!({
+            "endpoint": "grpc.health.v1.Health/Check",
+            "body": {},
+            "address": address,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["success"], true, "the body reaches the handler intact");
+
+    let log = std::fs::read_to_string(&audit).unwrap();
+    let entries: Vec<serde_json::Value> = log
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 1, "{log}");
+    assert_eq!(entries[0]["user"], "alice");
+    assert_eq!(entries[0]["method"], "POST");
+    assert_eq!(entries[0]["path"], "/api/call");
+    assert_eq!(entries[0]["endpoint"], "grpc.health.v1.Health/Check");
+    assert_eq!(entries[0]["address"], address.as_str());
+    assert_eq!(entries[0]["status"], 200);
+}
+
+// ─── /api/run/suite and /api/bench — whole collections ─────────
+
+fn health_check_gctf(address: &str, tags: &str, status: &str) -> String {
+    format!(
+        "--- META ---\ntags: [{tags}]\n\n--- ADDRESS ---\n{address}\n\n--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- REQUEST ---\n{{}}\n\n--- ASSERTS ---\n.status == \"{status}\"\n"
+    )
+}
+
+/// A tag-filtered directory runs through `run`'s runner: one event per test
+/// start and end in the `--stream` shape, then the HTML report.
+#[tokio::test]
+async fn run_suite_streams_test_events_and_the_html_report() {
+    let address = support::spawn_health_server().await;
+    let dir = setup_project_dir("suite");
+    let suite = dir.join(".grpctestify").join("collections").join("suite");
+    std::fs::create_dir_all(&suite).unwrap();
+    let files = [
+        ("pass.gctf", "smoke", "SERVING"),
+        ("fail.gctf", "smoke", "NOT_SERVING"),
+        ("slow.gctf", "nightly", "SERVING"),
+    ];
+    for (name, tags, status) in files {
+        std::fs::write(suite.join(name), health_check_gctf(&address, tags, status)).unwrap();
+    }
+    let url = start_server(test_app_project(dir.clone())).await;
+
+    let mut events = open_events(
+        &url,
+        "/api/run/suite",
+        &serde_json::json!({
+            "path": "suite",
+            "tags": ["smoke"],
+            "parallel": "1",
+            "session_id": "suite-session",
+        }),
+    )
+    .await;
+    let mut seen = Vec::new();
+    let report = loop {
+        let (name, data) = events.next().await.expect("stream ended");
+        if name == "report" {
+            break data;
+        }
+        assert_eq!(data["event"], name.as_str(), "{data}");
+        seen.push((name, data));
+    };
+    assert!(events.next().await.is_none(), "the event stream ends");
+
+    assert_eq!(seen[0].0, "suite_start");
+    assert_eq!(seen[0].1["testCount"], 2);
+    let ended = |event: &str| -> Vec<String> {
+        seen.iter()
+            .filter(|(name, _)| name == event)
+            .map(|(_, data)| data["testId"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(ended("test_start").len(), 2);
+    assert!(ended("test_pass")[0].ends_with("pass.gctf"));
+    assert!(ended("test_fail")[0].ends_with("fail.gctf"));
+    let (last, suite_end) = seen.last().unwrap();
+    assert_eq!(last, "suite_end");
+    assert_eq!(suite_end["summary"]["passed"], 1);
+    assert_eq!(suite_end["summary"]["failed"], 1);
+    assert!(
+        report["html"].as_str().unwrap().contains("<html"),
+        "{report}"
+    );
+
+    let (_, history) = get_json(&url, "/api/project/history").await;
+    let entry = &history["suite-session"][0];
+    assert_eq!(entry["kind"], "suite", "{history:#}");
+    assert_eq!(entry["collection_path"], "suite");
+    assert_eq!(entry["response"]["status"], "failed");
+    assert_eq!(entry["response"]["summary"]["total"], 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+/// A UI that goes away stops the suite: the running test finishes, the
+/// rest never start, and the history records the cut-short run.
+#[tokio::test]
+async fn run_suite_stops_scheduling_when_the_ui_disconnects() {
+    let address = support::spawn_health_server().await;
+    let dir = setup_project_dir("suite-gone");
+    let suite = dir.join(".grpctestify").join("collections").join("slow");
+    std::fs::create_dir_all(&suite).unwrap();
+    // `Health/Watch` never ends, so each test lasts its one-second timeout.
+    for i in 0..5 {
+        std::fs::write(
+            suite.join(format!("watch_{i}.gctf")),
+            format!(
+                "--- OPTIONS ---\ntimeout: 1\n\n--- ADDRESS ---\n{address}\n\n\
+                 --- ENDPOINT ---\ngrpc.health.v1.Health/Watch\n\n--- REQUEST ---\n{{}}\n\n\
+                 --- ASSERTS ---\n.status == \"SERVING\"\n"
+            ),
+        )
+        .unwrap();
+    }
+    let url = start_server(test_app_project(dir.clone())).await;
+
+    let mut events = open_events(
+        &url,
+        "/api/run/suite",
+        &serde_json::json!({"path": "slow", "parallel": "1", "session_id": "gone"}),
+    )
+    .await;
+    events.until("test_start").await;
+    drop(events);
+
+    let mut history = serde_json::Value::Null;
+    for _ in 0..100 {
+        (_, history) = get_json(&url, "/api/project/history").await;
+        if history["gone"].is_array() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let response = &history["gone"][0]["response"];
+    assert_eq!(response["status"], "cancelled", "{history:#}");
+    let total = response["summary"]["total"].as_u64().unwrap();
+    assert!(
+        total < 5,
+        "tests kept starting after the UI left: {history:#}"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[tokio::test]
+async fn suite_and_bench_refuse_bad_selections() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("a.gctf"),
+        health_check_gctf("localhost:1", "smoke", "SERVING"),
+    )
+    .unwrap();
+    let url = start_server(test_app(dir.path().to_path_buf())).await;
+
+    let (status, _) = post_json(&url, "/api/run/suite", &serde_json::json!({"path": "../"})).await;
+    assert_eq!(status, 404);
+    let (status, body) = post_json(
+        &url,
+        "/api/bench",
+        &serde_json::json!({"path": "a.gctf", "duration": "soon"}),
+    )
+    .await;
+    assert_eq!(
+        status, 400,
+        "a bad bench option is refused up front: {body}"
+    );
+
+    let mut events = open_events(
+        &url,
+        "/api/run/suite",
+        &serde_json::json!({"path": "", "tags": ["nightly"]}),
+    )
+    .await;
+    let error = events.until("error").await;
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap()
+            .contains("No test files found"),
+        "{error}"
+    );
+}
+
+/// `bench` over a collection: live progress snapshots while it runs, then
+/// the full report.
+#[tokio::test]
+async fn bench_streams_progress_snapshots_and_the_report() {
+    let address = support::spawn_health_server().await;
+    let dir = setup_project_dir("bench");
+    let collections = dir.join(".grpctestify").join("collections");
+    std::fs::write(
+        collections.join("check.gctf"),
+        health_check_gctf(&address, "bench", "SERVING"),
+    )
+    .unwrap();
+    let url = start_server(test_app_project(dir.clone())).await;
+
+    let mut events = open_events(
+        &url,
+        "/api/bench",
+        &serde_json::json!({
+            "path": "check.gctf",
+            "duration": "1s",
+            "concurrency": 2,
+            "progress_interval": "100ms",
+            "session_id": "bench-session",
+        }),
+    )
+    .await;
+    let progress = events.until("progress").await;
+    assert!(progress["requests"].as_u64().unwrap() > 0, "{progress}");
+    assert!(progress["rps"].as_f64().unwrap() > 0.0, "{progress}");
+    assert_eq!(progress["error_pct"], 0.0);
+    assert!(progress["elapsed_s"].is_number(), "{progress}");
+    let report = events.until("report").await;
+    assert!(report["summary"]["count"].as_u64().unwrap() > 0, "{report}");
+    assert_eq!(report["summary"]["errors"], 0, "{report}");
+    assert!(events.next().await.is_none(), "the event stream ends");
+
+    let (_, history) = get_json(&url, "/api/project/history").await;
+    let entry = &history["bench-session"][0];
+    assert_eq!(entry["kind"], "bench", "{history:#}");
+    assert_eq!(entry["response"]["status"], "passed");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
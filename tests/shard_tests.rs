@@ -0,0 +1,151 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `--shard K/N`: every file lands in exactly one shard, fixture directories
+//! and `META.depends_on` groups stay whole, and `--shard-timings` balances by
+//! recorded duration.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::run_isolated;
+
+const TEST: &str =
+    "--- ENDPOINT ---\nsvc.Thing/Do\n\n--- REQUEST ---\n{}\n\n--- RESPONSE ---\n{}\n";
+
+fn project(files: &[&str]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for rel in files {
+        let path = dir.path().join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, TEST).unwrap();
+    }
+    dir
+}
+
+fn list_shard(dir: &std::path::Path, shard: &str, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["list", "tests", "--format", "text", "--shard", shard];
+    args.extend(extra);
+    let output = run_isolated(dir, &args);
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.replace('\\', "/"))
+        .collect()
+}
+
+#[test]
+fn shards_cover_every_file_once_and_keep_fixtures_with_their_tests() {
+    let dir = project(&[
+        "tests/users/_setup.gctf",
+        "tests/users/get.gctf",
+        "tests/users/list.gctf",
+        "tests/users/_teardown.gctf",
+        "tests/a.gctf",
+        "tests/b.gctf",
+        "tests/c.gctf",
+    ]);
+
+    let first = list_shard(dir.path(), "1/2", &[]);
+    let second = list_shard(dir.path(), "2/2", &[]);
+    let users: Vec<&String> = first
+        .iter()
+        .chain(&second)
+        .filter(|f| f.contains("users/"))
+        .collect();
+    assert_eq!(users.len(), 4, "{first:?} {second:?}");
+    let users_in_first = first.iter().filter(|f| f.contains("users/")).count();
+    assert!(users_in_first == 0 || users_in_first == 4, "{first:?}");
+
+    let mut all: Vec<String> = first.into_iter().chain(second).collect();
+    all.sort();
+    all.dedup();
+    assert_eq!(all.len(), 7);
+
+    // Same input, same answer.
+    assert_eq!(
+        list_shard(dir.path(), "1/2", &[]),
+        list_shard(dir.path(), "1/2", &[])
+    );
+}
+
+#[test]
+fn dependency_groups_share_a_shard() {
+    let dir = project(&[
+        "tests/auth/login.gctf",
+        "tests/a.gctf",
+        "tests/b.gctf",
+        "tests/c.gctf",
+    ]);
+    for name in ["create", "cancel"] {
+        let path = dir.path().join(format!("tests/orders/{name}.gctf"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            format!("--- META ---\ndepends_on: [\"../auth/login.gctf\"]\n\n{TEST}"),
+        )
+        .unwrap();
+    }
+
+    let first = list_shard(dir.path(), "1/2", &[]);
+    let second = list_shard(dir.path(), "2/2", &[]);
+    let group = |shard: &[String]| {
+        shard
+            .iter()
+            .filter(|f| f.contains("orders/") || f.contains("auth/"))
+            .count()
+    };
+    assert!(
+        (group(&first), group(&second)) == (3, 0) || (group(&first), group(&second)) == (0, 3),
+        "{first:?} {second:?}"
+    );
+    assert_eq!(first.len() + second.len(), 6, "{first:?} {second:?}");
+
+    // A prerequisite outside the listed path is what `run` would pull in.
+    let output = run_isolated(
+        dir.path(),
+        &["list", "tests/orders", "--format", "text", "--shard", "1/1"],
+    );
+    let listed = String::from_utf8_lossy(&output.stdout).replace('\\', "/");
+    assert!(listed.contains("tests/auth/login.gctf"), "{output:?}");
+}
+
+#[test]
+fn shard_timings_balance_by_duration() {
+    let dir = project(&[
+        "tests/slow.gctf",
+        "tests/a.gctf",
+        "tests/b.gctf",
+        "tests/c.gctf",
+    ]);
+    std::fs::write(
+        dir.path().join("report.json"),
+        r#"{"results": [
+            {"name": "tests/slow.gctf", "duration_ms": 9000},
+            {"name": "tests/a.gctf", "duration_ms": 1000},
+            {"name": "tests/b.gctf", "duration_ms": 1000},
+            {"name": "tests/c.gctf", "duration_ms": 1000}
+        ]}"#,
+    )
+    .unwrap();
+
+    let timed = ["--shard-timings", "report.json"];
+    assert_eq!(list_shard(dir.path(), "1/2", &timed), ["tests/slow.gctf"]);
+    assert_eq!(list_shard(dir.path(), "2/2", &timed).len(), 3);
+}
+
+#[test]
+fn run_executes_only_its_shard() {
+    let dir = project(&["tests/a.gctf", "tests/b.gctf", "tests/c.gctf"]);
+    let output = run_isolated(dir.path(), &["run", "tests", "--dry-run", "--shard", "3/3"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Running 1 test"),
+        "{output:?}"
+    );
+
+    // More shards than tests: the spare shard has nothing to do.
+    let output = run_isolated(dir.path(), &["run", "tests", "--dry-run", "--shard", "5/5"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("Shard 5/5 has no tests to run"),
+        "{output:?}"
+    );
+}
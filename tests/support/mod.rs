@@ -115,20 +115,59 @@ pub async fn spawn_health_server() -> String {
 }
 
 /// Run the CLI in `dir` as an isolated, trusted-plugins invocation: `dir` is
-/// both `$HOME` and the working directory, and script plugins are pre-approved
+/// both `$HOME` and the working directory (so no user config file applies
+/// unless the test writes one), and script plugins are pre-approved
 /// (`GRPCTESTIFY_TRUST_PLUGINS=1`) since a non-interactive test can't answer
-/// the trust prompt. The shared version of the per-file `run_in`/`run_isolated`
-/// copies.
+/// the trust prompt.
 pub fn run_isolated(dir: &std::path::Path, args: &[&str]) -> Output {
     cli_command()
         .current_dir(dir)
         .env("HOME", dir)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_CACHE_HOME")
         .env("GRPCTESTIFY_TRUST_PLUGINS", "1")
         .args(args)
         .output()
         .expect("failed to run CLI")
 }
 
+/// A `grpc.health.v1.Health/Check` test against `address` expecting status
+/// `expected` — the one-liner most CLI tests run against
+/// [`spawn_health_server`].
+pub fn health_test(address: &str, expected: &str) -> String {
+    format!(
+        "--- ADDRESS ---\n{address}\n\n\
+         --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST ---\n{{}}\n\n\
+         --- RESPONSE ---\n{{\"status\": \"{expected}\"}}\n"
+    )
+}
+
+/// [`health_test`] asking about `service`, for tests that need requests that
+/// differ.
+pub fn health_test_for_service(address: &str, service: &str, expected: &str) -> String {
+    format!(
+        "--- ADDRESS ---\n{address}\n\n\
+         --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST ---\n{{\"service\": \"{service}\"}}\n\n\
+         --- RESPONSE ---\n{{\"status\": \"{expected}\"}}\n"
+    )
+}
+
+/// [`run_isolated`] off the async runtime, for tests whose server (say,
+/// [`spawn_health_server`]) runs on that runtime and must keep serving while
+/// the CLI blocks.
+pub async fn run_in(dir: &std::path::Path, args: &[&str]) -> Output {
+    let dir = dir.to_path_buf();
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    tokio::task::spawn_blocking(move || {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        run_isolated(&dir, &args)
+    })
+    .await
+    .unwrap()
+}
+
 /// Compare `actual` against `tests/golden/<rel>`, where `rel` includes the
 /// `.golden` suffix and any subdirectory (`"output_forms/run_json.golden"`).
 ///
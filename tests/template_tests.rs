@@ -0,0 +1,78 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! Template functions and filters in `{{ }}` placeholders, end to end against
+//! a local health server.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{cli_command, health_test, run_in, spawn_health_server};
+
+async fn run_file(content: String, env: &[(&str, &str)]) -> std::process::Output {
+    let dir = tempfile::tempdir().unwrap();
+    let test = dir.path().join("template.gctf");
+    std::fs::write(&test, content).unwrap();
+    let mut command = cli_command();
+    command.args(["run", test.to_str().unwrap(), "--seed", "7"]);
+    for (k, v) in env {
+        command.env(k, v);
+    }
+    tokio::task::spawn_blocking(move || {
+        let output = command.output().unwrap();
+        drop(dir);
+        output
+    })
+    .await
+    .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn functions_and_filters_resolve_in_every_section() {
+    let address = spawn_health_server().await;
+    let output = run_file(
+        "--- ADDRESS ---\n{{ env(\"TEMPLATE_TEST_ADDR\") }}\n\n\
+         --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+         --- REQUEST_HEADERS ---\nx-request-id: {{ uuid() }}\nx-user: {{ fake.email | lower }}\n\n\
+         --- REQUEST ---\n{\"service\": \"{{ missing | default('') }}\"}\n\n\
+         --- RESPONSE ---\n{\"status\": \"{{ 'serving' | upper }}\"}\n"
+            .to_string(),
+        &[("TEMPLATE_TEST_ADDR", &address)],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn a_failing_expression_fails_the_test_before_sending() {
+    let address = spawn_health_server().await;
+    let output = run_file(
+        format!(
+            "--- ADDRESS ---\n{address}\n\n\
+             --- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n\
+             --- REQUEST ---\n{{\"service\": \"{{{{ nope() }}}}\"}}\n\n\
+             --- RESPONSE ---\n{{\"status\": \"SERVING\"}}\n"
+        ),
+        &[],
+    )
+    .await;
+    assert!(!output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Template error in REQUEST")
+            && stdout.contains("unknown function 'nope()'"),
+        "{stdout}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn data_columns_that_are_not_expressions_still_substitute() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("health.gctf"),
+        health_test(&address, "{{rows.expected-status}}"),
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("rows.csv"), "expected-status\nSERVING\n").unwrap();
+
+    let output = run_in(dir.path(), &["run", "health.gctf", "--data", "rows.csv"]).await;
+    assert!(output.status.success(), "{output:?}");
+}
@@ -0,0 +1,223 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --trace` / `--otel-endpoint` / `--otel-file`: the traceparent the
+//! server sees, the trace id in the reports and the exported OTLP spans all
+//! name the same trace.
+
+#[path = "support/mod.rs"]
+mod support;
+use std::sync::{Arc, Mutex};
+use support::{health_test, run_in};
+
+use axum::Router;
+use axum::routing::post;
+
+type Captured = Arc<Mutex<Vec<String>>>;
+
+/// Health server recording the `traceparent` and `baggage` of every call.
+async fn spawn_traced_health_server() -> (String, Captured) {
+    let captured: Captured = Arc::default();
+    let seen = captured.clone();
+    let interceptor = move |request: tonic::Request<()>| {
+        for key in ["traceparent", "baggage"] {
+            if let Some(value) = request.metadata().get(key) {
+                seen.lock()
+                    .unwrap()
+                    .push(format!("{key}: {}", value.to_str().unwrap()));
+            }
+        }
+        Ok(request)
+    };
+    let (reporter, health_service) = tonic_health::server::health_reporter();
+    reporter
+        .set_service_status("", tonic_health::ServingStatus::Serving)
+        .await;
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .unwrap();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let incoming = tokio_stream::wrappers::TcpListenerStream::new(listener);
+    tokio::spawn(async move {
+        tonic::transport::Server::builder()
+            .layer(tonic::service::InterceptorLayer::new(interceptor))
+            .add_service(health_service)
+            .add_service(reflection_service)
+            .serve_with_incoming(incoming)
+            .await
+            .unwrap();
+    });
+    (addr.to_string(), captured)
+}
+
+/// Local OTLP/HTTP stand-in: keeps every body POSTed to `/v1/traces`.
+async fn spawn_otlp_collector() -> (String, Captured) {
+    let captured: Captured = Arc::default();
+    let bodies = captured.clone();
+    let app = Router::new().route(
+        "/v1/traces",
+        post(move |body: String| {
+            let bodies = bodies.clone();
+            async move {
+                bodies.lock().unwrap().push(body);
+                "{}"
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{addr}"), captured)
+}
+
+/// The spans of an OTLP-JSON export request, flattened.
+fn spans(export: &serde_json::Value) -> Vec<serde_json::Value> {
+    export["resourceSpans"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|rs| rs["scopeSpans"].as_array().unwrap().clone())
+        .flat_map(|ss| ss["spans"].as_array().unwrap().clone())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn otel_endpoint_exports_the_trace_the_server_saw() {
+    let (address, headers) = spawn_traced_health_server().await;
+    let (collector, bodies) = spawn_otlp_collector().await;
+    let dir = tempfile::tempdir().unwrap();
+    // An ASSERTS section too, so the export carries an assertion span.
+    let test = format!(
+        "{}\n--- ASSERTS ---\n.status == \"SERVING\"\n",
+        health_test(&address, "SERVING")
+    );
+    std::fs::write(dir.path().join("health.gctf"), test).unwrap();
+
+    let output = run_in(
+        dir.path(),
+        &[
+            "run",
+            "health.gctf",
+            "--otel-endpoint",
+            &collector,
+            "--trace-baggage",
+            "env=ci",
+            "--trace-url",
+            "http://jaeger.local/trace/{trace_id}",
+            "--log-format",
+            "json",
+            "--log-output",
+            "report.json",
+        ],
+    )
+    .await;
+    assert!(output.status.success(), "{output:?}");
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.path().join("report.json")).unwrap())
+            .unwrap();
+    let trace = &report["results"][0]["trace"];
+    let trace_id = trace["trace_id"].as_str().unwrap().to_string();
+    assert_eq!(trace_id.len(), 32, "{report}");
+    assert_eq!(
+        trace["url"],
+        format!("http://jaeger.local/trace/{trace_id}"),
+        "{report}"
+    );
+
+    let headers = headers.lock().unwrap().clone();
+    let traceparent = headers
+        .iter()
+        .find_map(|h| h.strip_prefix("traceparent: "))
+        .unwrap_or_else(|| panic!("no traceparent in {headers:?}"));
+    let parts: Vec<&str> = traceparent.split('-').collect();
+    assert_eq!(parts[0], "00");
+    assert_eq!(parts[1], trace_id);
+    assert!(
+        headers.contains(&"baggage: env=ci".to_string()),
+        "{headers:?}"
+    );
+
+    let bodies = bodies.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 1, "one export per run: {bodies:?}");
+    let export: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+    let spans = spans(&export);
+    assert!(spans.iter().all(|s| s["traceId"] == trace_id), "{export}");
+    let call = spans
+        .iter()
+        .find(|s| s["name"] == "grpc.health.v1.Health/Check")
+        .unwrap_or_else(|| panic!("no call span in {export}"));
+    assert_eq!(
+        call["spanId"], parts[2],
+        "the call span is the traceparent parent"
+    );
+    assert_eq!(call["kind"], 3);
+    let root = spans
+        .iter()
+        .find(|s| s.get("parentSpanId").is_none())
+        .unwrap_or_else(|| panic!("no test span in {export}"));
+    assert_eq!(call["parentSpanId"], root["spanId"]);
+    assert!(
+        spans
+            .iter()
+            .any(|s| s["name"].as_str().unwrap().starts_with("ASSERTS")
+                && s["parentSpanId"] == call["spanId"]),
+        "{export}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn otel_file_appends_one_line_per_run() {
+    let (address, _headers) = spawn_traced_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("health.gctf"),
+        health_test(&address, "SERVING"),
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        let output = run_in(
+            dir.path(),
+            &[
+                "run",
+                "health.gctf",
+                "--otel-file",
+                "traces/spans.jsonl",
+                "--log-format",
+                "junit",
+                "--log-output",
+                "junit.xml",
+            ],
+        )
+        .await;
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    let file = std::fs::read_to_string(dir.path().join("traces/spans.jsonl")).unwrap();
+    let lines: Vec<&str> = file.lines().collect();
+    assert_eq!(lines.len(), 2, "{file}");
+    let last: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+    let trace_id = spans(&last)[0]["traceId"].as_str().unwrap().to_string();
+
+    let junit = std::fs::read_to_string(dir.path().join("junit.xml")).unwrap();
+    assert!(
+        junit.contains(&format!("<property name=\"trace_id\" value=\"{trace_id}\"")),
+        "{junit}"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn calls_are_untraced_by_default() {
+    let (address, headers) = spawn_traced_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("health.gctf"),
+        health_test(&address, "SERVING"),
+    )
+    .unwrap();
+
+    let output = run_in(dir.path(), &["run", "health.gctf"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert!(headers.lock().unwrap().is_empty());
+}
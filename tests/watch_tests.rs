@@ -0,0 +1,129 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)] // test/bench code
+//! `run --watch`: a full first run, then one compact cycle per change that
+//! runs only the affected tests.
+
+#[path = "support/mod.rs"]
+mod support;
+use support::{cli_command, health_test, spawn_health_server};
+
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// The watcher process, killed on drop, with its stdout as a line channel.
+struct Watching {
+    child: Child,
+    lines: mpsc::Receiver<String>,
+}
+
+impl Watching {
+    fn start(dir: &std::path::Path) -> Self {
+        let mut child = cli_command()
+            .current_dir(dir)
+            .env("HOME", dir)
+            .env_remove("XDG_CONFIG_HOME")
+            .env_remove("XDG_CACHE_HOME")
+            .env("NO_COLOR", "1")
+            .args(["run", "tests", "--watch"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line.unwrap_or_default()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { child, lines }
+    }
+
+    /// Lines up to and including the first one containing `needle`.
+    fn until(&self, needle: &str) -> Vec<String> {
+        let mut seen = Vec::new();
+        loop {
+            match self.lines.recv_timeout(Duration::from_secs(30)) {
+                Ok(line) => {
+                    let found = line.contains(needle);
+                    seen.push(line);
+                    if found {
+                        return seen;
+                    }
+                }
+                Err(_) => panic!("no line containing {needle:?}; got {seen:#?}"),
+            }
+        }
+    }
+}
+
+impl Drop for Watching {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn watch_reruns_only_what_a_change_affects() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let tests = dir.path().join("tests");
+    std::fs::create_dir_all(tests.join("fx")).unwrap();
+    std::fs::write(tests.join("a.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(tests.join("b.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(tests.join("fx/c.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(tests.join("fx/d.gctf"), health_test(&address, "SERVING")).unwrap();
+    std::fs::write(
+        tests.join("fx/_setup.gctf"),
+        health_test(&address, "SERVING"),
+    )
+    .unwrap();
+
+    let watching = tokio::task::spawn_blocking({
+        let dir = dir.path().to_path_buf();
+        move || {
+            let watching = Watching::start(&dir);
+            let first = watching.until("Watching");
+            (watching, first)
+        }
+    })
+    .await
+    .unwrap();
+    let (watching, first) = watching;
+    assert!(
+        first.iter().any(|l| l.contains("Running 4 tests")),
+        "{first:#?}"
+    );
+    assert!(first.iter().any(|l| l.contains("✓ 5 passed")), "{first:#?}");
+
+    // One test edited: it alone runs, and its failure is listed.
+    std::fs::write(tests.join("b.gctf"), health_test(&address, "NOT_SERVING")).unwrap();
+    let (watching, cycle) = tokio::task::spawn_blocking(move || {
+        let cycle = watching.until("Watching");
+        (watching, cycle)
+    })
+    .await
+    .unwrap();
+    let text = cycle.join("\n");
+    assert!(text.contains("↻ Changed: tests/b.gctf"), "{text}");
+    assert!(text.contains("Running 1 test"), "{text}");
+    assert!(text.contains("✗ 1 failed · 0 passed"), "{text}");
+    assert!(text.contains("b.gctf"), "{text}");
+
+    // A fixture edited: its whole directory runs again.
+    std::fs::write(
+        tests.join("fx/_setup.gctf"),
+        format!("{}\n", health_test(&address, "SERVING")),
+    )
+    .unwrap();
+    let cycle = tokio::task::spawn_blocking(move || watching.until("Watching"))
+        .await
+        .unwrap();
+    let text = cycle.join("\n");
+    assert!(text.contains("Running 2 tests"), "{text}");
+    assert!(text.contains("✓ 3 passed"), "{text}");
+}
@@ -6,7 +6,7 @@
 
 #[path = "support/mod.rs"]
 mod support;
-use support::run_cli;
+use support::{run_cli, run_in, spawn_health_server};
 
 /// `run --write` against a down server must fail and must NOT rewrite the
 /// test file (previously it emptied the RESPONSE section and exited 0).
@@ -47,6 +47,23 @@ fn write_mode_down_server_keeps_file_and_fails() {
     );
 }
 
+/// Reports capture the response too (`--capture-exchange`, or a report
+/// format that shows the exchange); only `--write` may write it back.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn captured_exchange_without_write_keeps_file() {
+    let address = spawn_health_server().await;
+    let dir = tempfile::tempdir().unwrap();
+    let content = format!(
+        "--- ADDRESS ---\n{address}\n\n--- ENDPOINT ---\ngrpc.health.v1.Health/Check\n\n--- REQUEST ---\n{{}}\n\n--- RESPONSE ---\n# hand-written\n{{ \"status\": \"SERVING\" }}\n"
+    );
+    let test_path = dir.path().join("health.gctf");
+    std::fs::write(&test_path, &content).unwrap();
+
+    let output = run_in(dir.path(), &["run", "--capture-exchange", "health.gctf"]).await;
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(std::fs::read_to_string(&test_path).unwrap(), content);
+}
+
 /// An empty (or fully filtered) test set must exit non-zero so CI cannot
 /// silently pass on a path or --tags typo.
 #[test]
@@ -4,6 +4,7 @@ import { StatusBar } from './StatusBar';
 import { Sidebar } from '../collections/Sidebar';
 import { HistoryPanel } from '../history/HistoryPanel';
 import { ImportPanel } from '../collections/ImportPanel';
+import { RunsPanel } from '../runs/RunsPanel';
 import { RequestPanel } from '../request/RequestPanel';
 import { ResponsePanel } from '../response/ResponsePanel';
 import { GctfInfoPanel } from '../request/GctfInfoPanel';
@@ -11,13 +12,14 @@ import { useStore } from '../../lib/store';
 import { useDeepLink } from '../../lib/routing';
 import { KeyboardShortcutHelp } from '../ui/KeyboardShortcutHelp';
 import { SHORTCUT_DEFINITIONS, matchesHotkey, matchesDigitShortcut, isInputFocused } from '../../lib/hotkeys';
-import { FolderOpen, Clock, Upload } from 'lucide-react';
+import { FolderOpen, Clock, Upload, Gauge } from 'lucide-react';
 
-type SidebarTab = 'collections' | 'history' | 'import';
+type SidebarTab = 'collections' | 'history' | 'runs' | 'import';
 
 const SIDEBAR_TABS: { key: SidebarTab; label: string; icon: React.ReactNode }[] = [
   { key: 'collections', label: 'Collections', icon: <FolderOpen size={13} /> },
   { key: 'history', label: 'History',    icon: <Clock size={13} /> },
+  { key: 'runs',     label: 'Runs',      icon: <Gauge size={13} /> },
   { key: 'import',   label: 'Import',    icon: <Upload size={13} /> },
 ];
 
@@ -176,6 +178,7 @@ export function PlayLayout() {
           <div style={{ flex: 1, overflow: 'auto' }}>
             {sidebarTab === 'collections' && <Sidebar />}
             {sidebarTab === 'history' && <HistoryPanel />}
+            {sidebarTab === 'runs' && <RunsPanel />}
             {sidebarTab === 'import' && <ImportPanel />}
           </div>
         </aside>
@@ -1,5 +1,6 @@
 import { useStore } from '../../lib/store';
 import { colors } from '../../lib/theme';
+import { metadataRows, metadataFromRows } from '../../lib/types';
 import { Plus, X } from 'lucide-react';
 import { EnvVarToolbar } from './EnvVarToolbar';
 
@@ -20,18 +21,20 @@ export function HeadersEditor() {
     return ae ? s.environments.find(e => e.name === ae) : null;
   });
 
-  const entries = Object.entries(request.headers);
+  // One row per value: a header repeated in REQUEST_HEADERS gets a row for
+  // each of its values.
+  const entries = metadataRows(request.headers);
 
-  const set = (key: string, value: string, oldKey?: string) => {
-    const h = { ...request.headers };
-    if (oldKey !== undefined && oldKey !== key) delete h[oldKey];
-    if (key) h[key] = value;
-    else delete h[oldKey!];
-    setRequestHeaders(h);
+  const set = (index: number, key: string, value: string) => {
+    setRequestHeaders(metadataFromRows(entries.map((row, i) => i === index ? [key, value] as [string, string] : row)));
+  };
+
+  const remove = (index: number) => {
+    setRequestHeaders(metadataFromRows(entries.filter((_, i) => i !== index)));
   };
 
   const add = () => {
-    setRequestHeaders({ ...request.headers, '': '' });
+    setRequestHeaders(metadataFromRows([...entries, ['', '']]));
   };
 
   const allValues = entries.map(([, v]) => v).join('\n');
@@ -50,14 +53,14 @@ export function HeadersEditor() {
           <div key={i} style={{ display: 'flex', gap: 6, marginBottom: 4, alignItems: 'center' }}>
             <input
               value={k}
-              onChange={e => set(e.target.value, v, k)}
+              onChange={e => set(i, e.target.value, v)}
               placeholder="Key"
               style={{ flex: 1, padding: '4px 8px', fontSize: 12, borderRadius: 4, border: '1px solid var(--border)', background: 'var(--bg-primary)', color: 'var(--text-primary)', outline: 'none', fontFamily: 'monospace' }}
             />
             <div style={{ position: 'relative', flex: 2 }}>
               <input
                 value={v}
-                onChange={e => set(k, e.target.value, k)}
+                onChange={e => set(i, k, e.target.value)}
                 placeholder="Value"
                 title={isDifferent ? `Resolves to: ${resolved}` : undefined}
                 style={{
@@ -77,7 +80,7 @@ export function HeadersEditor() {
                 {resolved}
               </span>
             )}
-            <button onClick={() => set('', '', k)} style={{ background: 'none', border: 'none', cursor: 'pointer', padding: 4, color: 'var(--text-muted)', flexShrink: 0 }}>
+            <button onClick={() => remove(i)} style={{ background: 'none', border: 'none', cursor: 'pointer', padding: 4, color: 'var(--text-muted)', flexShrink: 0 }}>
               <X size={14} />
             </button>
           </div>
@@ -56,7 +56,8 @@ export function RequestPanel() {
   const modeMenuRef = useRef<HTMLDivElement>(null);
   const inputRef = useRef<HTMLInputElement>(null);
 
-  const isExecuting = useStore(s => s.response?.status) === 'pending';
+  const responseStatus = useStore(s => s.response?.status);
+  const isExecuting = responseStatus === 'pending' || responseStatus === 'streaming';
   const canExecute = !!request.endpoint && !isExecuting;
 
   
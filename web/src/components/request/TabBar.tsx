@@ -1,6 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { useStore, isTabDirty } from '../../lib/store';
-import type { Tab } from '../../lib/types';
+import type { Metadata, Tab } from '../../lib/types';
 import { encodeCollectionLink } from '../../lib/deeplink';
 import { copyToClipboard } from '../../lib/clipboard';
 import { Plus, X, XCircle, FileSymlink, Pencil, Trash2, Share2, Terminal, ChevronLeft, ChevronRight } from 'lucide-react';
@@ -31,7 +31,8 @@ export function TabBar() {
   const rawContent = useStore(s => s.rawContent);
   const rawOriginal = useStore(s => s.rawOriginal);
   const getGrpcurlCommand = useStore(s => s.getGrpcurlCommand);
-  const isExecuting = useStore(s => s.response?.status) === 'pending';
+  const responseStatus = useStore(s => s.response?.status);
+  const isExecuting = responseStatus === 'pending' || responseStatus === 'streaming';
   const modal = useModal();
   const toast = useToast();
 
@@ -162,7 +163,7 @@ export function TabBar() {
     if (!tab) return;
     setSharing(true);
 
-    const filteredHeaders: Record<string, string> = {};
+    const filteredHeaders: Metadata = {};
     for (const [key, val] of Object.entries(tab.headers)) {
       if (shareHeaders[key] !== false) {
         filteredHeaders[key] = val;
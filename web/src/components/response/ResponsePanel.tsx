@@ -3,6 +3,7 @@ import { useState } from 'react';
 import { useStore } from '../../lib/store';
 import { colors } from '../../lib/theme';
 import { Loader2, Check, X, Layers, Clock, ArrowRight } from 'lucide-react';
+import { StreamControls } from './StreamControls';
 
 function msgPreview(msg: unknown, maxLen = 60): string {
   const s = JSON.stringify(msg);
@@ -20,7 +21,7 @@ export function ResponsePanel() {
 
   const msgs: unknown[] = response?.messages ?? [];
   const msgCount = msgs.length;
-  const isStreaming = msgCount > 1;
+  const isStreaming = msgCount > 1 || response?.status === 'streaming';
 
   
   const totalMs = response?.durationMs ?? 0;
@@ -36,6 +37,11 @@ export function ResponsePanel() {
             <Loader2 size={14} className="animate-spin" /> executing…
           </span>
         )}
+        {response?.status === 'streaming' && (
+          <span style={{ display: 'flex', alignItems: 'center', gap: 4, fontSize: 12, color: colors.accent }}>
+            <Loader2 size={14} className="animate-spin" /> open · {msgCount} msg{msgCount !== 1 ? 's' : ''}
+          </span>
+        )}
         {response?.status === 'ok' && (
           <span style={{ display: 'flex', alignItems: 'center', gap: 4, fontSize: 12, color: colors.success }}>
             <Check size={14} /> {msgCount} msg{msgCount !== 1 ? 's' : ''}
@@ -65,6 +71,7 @@ export function ResponsePanel() {
 
       {response && response.status !== 'pending' && (
         <>
+          <StreamControls />
           <div style={{ display: 'flex', borderBottom: '1px solid var(--border)', marginBottom: 8 }}>
             {(response.assertions ? (['response', 'assertions', 'headers'] as const) : (['response', 'headers'] as const)).map(tab => (
               <button key={tab} onClick={() => setResponseTab(tab)} style={{
@@ -85,7 +92,7 @@ export function ResponsePanel() {
             <div>
               {msgCount === 0 && (
                 <div style={{ padding: 12, fontSize: 12, color: 'var(--text-muted)' }}>
-                  {response.error ? response.error : 'No response messages'}
+                  {response.error ? response.error : response.status === 'streaming' ? 'Waiting for messages…' : 'No response messages'}
                 </div>
               )}
 
@@ -111,7 +118,7 @@ export function ResponsePanel() {
                       >
                         <span style={{ width: 32, color: 'var(--text-muted)', fontFamily: 'monospace', fontSize: 11 }}>#{i + 1}</span>
                         <span style={{ width: 60, display: 'flex', alignItems: 'center', gap: 2, color: 'var(--text-muted)', fontSize: 11, fontFamily: 'monospace' }}>
-                          <Clock size={10} /> {Math.round(perMsgMs * (i + 1))}ms
+                          <Clock size={10} /> {response.messageTimesMs?.[i] ?? Math.round(perMsgMs * (i + 1))}ms
                         </span>
                         <span style={{ flex: 1, fontFamily: 'monospace', fontSize: 11, color: 'var(--text-primary)', overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}>
                           {msgPreview(msg)}
@@ -0,0 +1,61 @@
+import { useState } from 'react';
+import { useStore } from '../../lib/store';
+import { useToast } from '../ui/ToastContext';
+import { btn, input, css } from '../../lib/theme';
+import { Send, CornerDownRight, Square } from 'lucide-react';
+
+/** Send/close/cancel for a call open on `/api/call/stream`. Sending and
+ * closing only apply while the request stream is open (client and bidi
+ * streaming); cancel works for any open call. */
+export function StreamControls() {
+  const live = useStore(s => s.response?.live);
+  const sendStreamMessage = useStore(s => s.sendStreamMessage);
+  const closeStream = useStore(s => s.closeStream);
+  const cancel = useStore(s => s.cancel);
+  const toast = useToast();
+  const [body, setBody] = useState('{}');
+  const [busy, setBusy] = useState(false);
+
+  if (!live) return null;
+
+  const run = async (action: () => Promise<void>) => {
+    setBusy(true);
+    try {
+      await action();
+    } catch (err: any) {
+      toast.error(err?.message || String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div style={{ border: '1px solid var(--border)', borderRadius: 6, padding: 8, marginBottom: 8 }}>
+      <div style={{ ...css.flexBetween, marginBottom: live.requestsOpen ? 6 : 0 }}>
+        <span style={css.label}>{live.requestsOpen ? 'Request stream open' : 'Waiting for the server'}</span>
+        <div style={{ display: 'flex', gap: 6 }}>
+          {live.requestsOpen && (
+            <button onClick={() => run(closeStream)} disabled={busy} style={btn('default', 'sm')}
+              title="Half-close the request stream: the server sees the end of your messages">
+              <CornerDownRight size={12} /> Close requests
+            </button>
+          )}
+          <button onClick={cancel} style={btn('danger', 'sm')}>
+            <Square size={12} /> Cancel call
+          </button>
+        </div>
+      </div>
+      {live.requestsOpen && (
+        <div style={{ display: 'flex', gap: 6 }}>
+          <textarea value={body} onChange={e => setBody(e.target.value)} rows={3} spellCheck={false}
+            onKeyDown={e => { if (e.key === 'Enter' && (e.ctrlKey || e.metaKey)) { e.preventDefault(); e.stopPropagation(); run(() => sendStreamMessage(body)); } }}
+            style={{ ...input, ...css.mono, flex: 1, fontSize: 12, resize: 'vertical' }} />
+          <button onClick={() => run(() => sendStreamMessage(body))} disabled={busy} style={{ ...btn('primary', 'sm'), alignSelf: 'flex-start' }}
+            title="Send this message (Ctrl+Enter)">
+            <Send size={12} /> Send
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,194 @@
+import { useState, useMemo } from 'react';
+import { useStore } from '../../lib/store';
+import { btn, colors, css } from '../../lib/theme';
+import type { BenchProgress, CollectionRun, SuiteTest } from '../../lib/types';
+import { Play, Gauge, Square, Loader2, Check, X, Minus, FileText } from 'lucide-react';
+
+type RunKind = CollectionRun['kind'];
+
+const field: React.CSSProperties = {
+  width: '100%', boxSizing: 'border-box', padding: '4px 6px', fontSize: 11, borderRadius: 5,
+  border: '1px solid var(--border)', background: 'var(--bg-primary)', color: 'var(--text-primary)', outline: 'none',
+};
+
+function splitList(v: string): string[] {
+  return v.split(',').map(s => s.trim()).filter(Boolean);
+}
+
+const STATUS_COLOR: Record<CollectionRun['status'], string> = {
+  running: colors.accent,
+  passed: colors.success,
+  failed: colors.error,
+  cancelled: colors.warning,
+  error: colors.error,
+};
+
+function TestIcon({ status }: { status: SuiteTest['status'] }) {
+  switch (status) {
+    case 'running': return <Loader2 size={11} className="animate-spin" color={colors.accent} />;
+    case 'passed': return <Check size={11} color={colors.success} />;
+    case 'failed': return <X size={11} color={colors.error} />;
+    default: return <Minus size={11} color="var(--text-muted)" />;
+  }
+}
+
+/** Requests per second over the run, as a sparkline. */
+function RpsSparkline({ progress }: { progress: BenchProgress[] }) {
+  if (progress.length < 2) return null;
+  const w = 220, h = 36;
+  const max = Math.max(...progress.map(p => p.rps), 1);
+  const points = progress
+    .map((p, i) => `${(i / (progress.length - 1)) * w},${h - (p.rps / max) * (h - 2) - 1}`)
+    .join(' ');
+  return (
+    <svg width="100%" viewBox={`0 0 ${w} ${h}`} preserveAspectRatio="none" style={{ display: 'block', marginBottom: 6 }}>
+      <polyline points={points} fill="none" stroke={colors.accent} strokeWidth={1.5} />
+    </svg>
+  );
+}
+
+function openReport(html: string) {
+  const url = URL.createObjectURL(new Blob([html], { type: 'text/html' }));
+  window.open(url, '_blank', 'noopener');
+  setTimeout(() => URL.revokeObjectURL(url), 60_000);
+}
+
+const ms = (ns: number) => `${(ns / 1e6).toFixed(1)}ms`;
+
+/** Run a collections folder like `grpctestify run`, or benchmark it, and
+ * follow the run live: tests as they finish and the HTML report for a suite,
+ * throughput snapshots and the summary for a benchmark. */
+export function RunsPanel() {
+  const collections = useStore(s => s.collections);
+  const run = useStore(s => s.collectionRun);
+  const runSuite = useStore(s => s.runSuite);
+  const runBench = useStore(s => s.runBench);
+  const stop = useStore(s => s.stopCollectionRun);
+
+  const [kind, setKind] = useState<RunKind>('suite');
+  const [path, setPath] = useState('');
+  const [tags, setTags] = useState('');
+  const [skipTags, setSkipTags] = useState('');
+  const [parallel, setParallel] = useState('');
+  const [duration, setDuration] = useState('10s');
+  const [concurrency, setConcurrency] = useState('4');
+  const [maxRps, setMaxRps] = useState('');
+
+  const targets = useMemo(
+    () => collections.filter(c => kind === 'suite' ? c.is_dir : true).map(c => c.path),
+    [collections, kind],
+  );
+  const running = run?.status === 'running';
+
+  const start = () => {
+    if (kind === 'suite') {
+      runSuite({ path, tags: splitList(tags), skipTags: splitList(skipTags), parallel: parallel || undefined });
+    } else {
+      runBench({ path, duration: duration || undefined, concurrency: Number(concurrency) || undefined, maxRps: Number(maxRps) || undefined });
+    }
+  };
+
+  const latest = run?.progress[run.progress.length - 1];
+  const finished = run?.tests.filter(t => t.status !== 'running').length ?? 0;
+
+  return (
+    <div style={{ padding: 8, display: 'flex', flexDirection: 'column', gap: 6 }}>
+      <div style={{ display: 'flex', gap: 4 }}>
+        {(['suite', 'bench'] as const).map(k => (
+          <button key={k} onClick={() => setKind(k)} style={{
+            ...btn(kind === k ? 'primary' : 'default', 'sm'), flex: 1,
+          }}>
+            {k === 'suite' ? <><Play size={11} /> Suite</> : <><Gauge size={11} /> Bench</>}
+          </button>
+        ))}
+      </div>
+
+      <label style={css.label}>{kind === 'suite' ? 'Folder' : 'File or folder'}</label>
+      <input list="runs-targets" value={path} onChange={e => setPath(e.target.value)}
+        placeholder={kind === 'suite' ? 'all collections' : 'orders/create.gctf'} style={field} />
+      <datalist id="runs-targets">
+        {targets.map(t => <option key={t} value={t} />)}
+      </datalist>
+
+      {kind === 'suite' ? (
+        <>
+          <input value={tags} onChange={e => setTags(e.target.value)} placeholder="tags (comma-separated)" style={field} />
+          <input value={skipTags} onChange={e => setSkipTags(e.target.value)} placeholder="skip tags" style={field} />
+          <input value={parallel} onChange={e => setParallel(e.target.value)} placeholder="parallel (number or auto)" style={field} />
+        </>
+      ) : (
+        <div style={{ display: 'flex', gap: 4 }}>
+          <input value={duration} onChange={e => setDuration(e.target.value)} placeholder="duration" title="Duration, e.g. 30s" style={field} />
+          <input value={concurrency} onChange={e => setConcurrency(e.target.value)} placeholder="concurrency" title="Concurrency" style={field} />
+          <input value={maxRps} onChange={e => setMaxRps(e.target.value)} placeholder="max rps" title="Max requests per second" style={field} />
+        </div>
+      )}
+
+      {running ? (
+        <button onClick={stop} style={btn('danger', 'sm')}><Square size={11} /> Stop</button>
+      ) : (
+        <button onClick={start} disabled={kind === 'bench' && !path} style={btn('primary', 'sm')}>
+          {kind === 'suite' ? <><Play size={11} /> Run suite</> : <><Gauge size={11} /> Run benchmark</>}
+        </button>
+      )}
+
+      {run && (
+        <div style={{ borderTop: '1px solid var(--border)', paddingTop: 6, display: 'flex', flexDirection: 'column', gap: 4 }}>
+          <div style={css.flexBetween}>
+            <span style={{ ...css.truncate, fontSize: 11, fontFamily: 'monospace' }} title={run.path || 'all collections'}>
+              {run.kind === 'suite' ? 'suite' : 'bench'} · {run.path || 'all'}
+            </span>
+            <span style={css.badge(`${STATUS_COLOR[run.status]}18`, STATUS_COLOR[run.status])}>{run.status}</span>
+          </div>
+          {run.error && <div style={{ fontSize: 11, color: colors.error, whiteSpace: 'pre-wrap' }}>{run.error}</div>}
+
+          {run.kind === 'suite' && (
+            <>
+              <div style={{ fontSize: 11, color: 'var(--text-secondary)' }}>
+                {run.summary
+                  ? `${run.summary.passed ?? 0} passed · ${run.summary.failed ?? 0} failed · ${run.summary.skipped ?? 0} skipped · ${run.summary.duration ?? 0}ms`
+                  : `${finished}/${run.testCount ?? '?'} done`}
+              </div>
+              <div style={{ display: 'flex', flexDirection: 'column', gap: 1 }}>
+                {run.tests.map((t, i) => (
+                  <div key={`${t.id}-${i}`} title={t.message || t.id} style={{ display: 'flex', alignItems: 'center', gap: 5, fontSize: 11, padding: '2px 0' }}>
+                    <TestIcon status={t.status} />
+                    <span style={{ ...css.truncate, flex: 1, fontFamily: 'monospace' }}>{t.id.split('/').pop()}</span>
+                    {t.durationMs != null && <span style={{ fontSize: 10, color: 'var(--text-muted)' }}>{t.durationMs}ms</span>}
+                  </div>
+                ))}
+              </div>
+              {run.reportHtml && (
+                <button onClick={() => openReport(run.reportHtml!)} style={btn('default', 'sm')}>
+                  <FileText size={11} /> Open HTML report
+                </button>
+              )}
+            </>
+          )}
+
+          {run.kind === 'bench' && (
+            <>
+              <RpsSparkline progress={run.progress} />
+              {latest && (
+                <div style={{ fontSize: 11, fontFamily: 'monospace', color: 'var(--text-secondary)' }}>
+                  t={latest.elapsed_s.toFixed(1)}s · {latest.requests} req · {latest.rps.toFixed(1)} rps
+                  {latest.target_rps > 0 && ` / ${latest.target_rps.toFixed(0)}`} · {latest.error_pct.toFixed(1)}% err
+                  {latest.latency_ms != null && ` · ${latest.latency_ms.toFixed(1)}ms`}
+                </div>
+              )}
+              {run.benchSummary && (
+                <div style={{ fontSize: 11, fontFamily: 'monospace', display: 'grid', gridTemplateColumns: 'auto 1fr', gap: '1px 8px' }}>
+                  <span style={{ color: 'var(--text-muted)' }}>requests</span><span>{run.benchSummary.count} ({run.benchSummary.errors} errors)</span>
+                  <span style={{ color: 'var(--text-muted)' }}>rps</span><span>{run.benchSummary.rps_observed.toFixed(1)}</span>
+                  <span style={{ color: 'var(--text-muted)' }}>average</span><span>{ms(run.benchSummary.average_ns)}</span>
+                  <span style={{ color: 'var(--text-muted)' }}>fastest</span><span>{ms(run.benchSummary.fastest_ns)}</span>
+                  <span style={{ color: 'var(--text-muted)' }}>slowest</span><span>{ms(run.benchSummary.slowest_ns)}</span>
+                </div>
+              )}
+            </>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,4 +1,4 @@
-import type { Environment } from './types';
+import type { Environment, Metadata } from './types';
 
 
 export function substituteEnv(s: string, env: Environment | null | undefined): string {
@@ -14,16 +14,16 @@ export function substituteEnv(s: string, env: Environment | null | undefined): s
 
 export function applyEnvironment(
   endpoint: string,
-  headers: Record<string, string>,
+  headers: Metadata,
   bodies: string[],
   env: Environment | null | undefined,
-): { endpoint: string; headers: Record<string, string>; bodies: string[]; address: string | null } {
+): { endpoint: string; headers: Metadata; bodies: string[]; address: string | null } {
   if (!env) return { endpoint, headers, bodies, address: null };
 
   return {
     endpoint: substituteEnv(endpoint, env),
     headers: Object.fromEntries(
-      Object.entries(headers).map(([k, v]) => [k, substituteEnv(v, env)]),
+      Object.entries(headers).map(([k, v]) => [k, Array.isArray(v) ? v.map(x => substituteEnv(x, env)) : substituteEnv(v, env)]),
     ),
     bodies: bodies.map(b => substituteEnv(b, env)),
     address: env.address || null,
@@ -57,10 +57,10 @@ export function mergeEnvironments(envs: Environment[]): Environment | null {
 
 export function applyEnvironmentMulti(
   endpoint: string,
-  headers: Record<string, string>,
+  headers: Metadata,
   bodies: string[],
   envs: Environment[],
-): { endpoint: string; headers: Record<string, string>; bodies: string[]; address: string | null } {
+): { endpoint: string; headers: Metadata; bodies: string[]; address: string | null } {
   const merged = mergeEnvironments(envs);
   return applyEnvironment(endpoint, headers, bodies, merged);
 }
@@ -0,0 +1,25 @@
+import { describe, it, expect } from 'vitest';
+import { parseServerEvents } from './sse';
+
+describe('parseServerEvents', () => {
+  it('parses named events with JSON data', () => {
+    const { events, rest } = parseServerEvents('event: call\ndata: {"id":"a"}\n\nevent: message\ndata: {"message":{}}\n\n');
+    expect(events).toEqual([
+      { event: 'call', data: { id: 'a' } },
+      { event: 'message', data: { message: {} } },
+    ]);
+    expect(rest).toBe('');
+  });
+
+  it('keeps a partial event for the next chunk', () => {
+    const first = parseServerEvents('event: end\ndata: {"sta');
+    expect(first.events).toEqual([]);
+    const second = parseServerEvents(first.rest + 'tus":"ok"}\n\n');
+    expect(second.events).toEqual([{ event: 'end', data: { status: 'ok' } }]);
+  });
+
+  it('drops keep-alive comments and accepts CRLF', () => {
+    const { events } = parseServerEvents(':\r\n\r\nevent: closed\r\ndata: {}\r\n\r\n');
+    expect(events).toEqual([{ event: 'closed', data: {} }]);
+  });
+});
@@ -0,0 +1,64 @@
+/** One server-sent event: its `event:` name and parsed JSON `data:`. */
+export interface ServerEvent {
+  event: string;
+  data: unknown;
+}
+
+/** Split complete events off the front of `buffer`; `rest` is the partial
+ * tail still waiting for its blank line. Comment-only blocks (keep-alives)
+ * are dropped. */
+export function parseServerEvents(buffer: string): { events: ServerEvent[]; rest: string } {
+  const blocks = buffer.replace(/\r\n/g, '\n').split('\n\n');
+  const rest = blocks.pop() ?? '';
+  const events: ServerEvent[] = [];
+  for (const block of blocks) {
+    let event = 'message';
+    const data: string[] = [];
+    for (const line of block.split('\n')) {
+      if (line.startsWith('event:')) event = line.slice(6).trim();
+      else if (line.startsWith('data:')) data.push(line.slice(5).replace(/^ /, ''));
+    }
+    if (data.length === 0) continue;
+    const text = data.join('\n');
+    let parsed: unknown = text;
+    try { parsed = JSON.parse(text); } catch { }
+    events.push({ event, data: parsed });
+  }
+  return { events, rest };
+}
+
+/** Read a `text/event-stream` response to its end, handing each event to
+ * `onEvent` as it arrives. `EventSource` only does GET, and the playground's
+ * streams are POSTs, so this reads the body itself. */
+export async function readServerEvents(res: Response, onEvent: (e: ServerEvent) => void): Promise<void> {
+  if (!res.body) return;
+  const reader = res.body.getReader();
+  const decoder = new TextDecoder();
+  let buffer = '';
+  for (;;) {
+    const { done, value } = await reader.read();
+    if (done) break;
+    buffer += decoder.decode(value, { stream: true });
+    const { events, rest } = parseServerEvents(buffer);
+    buffer = rest;
+    events.forEach(onEvent);
+  }
+  parseServerEvents(buffer + '\n\n').events.forEach(onEvent);
+}
+
+/** POST `body` as JSON to an event-stream endpoint and read it through
+ * `onEvent`. A non-2xx answer (a bad selection, say) throws its text. */
+export async function postEventStream(url: string, body: unknown, onEvent: (e: ServerEvent) => void, signal?: AbortSignal): Promise<Response> {
+  const res = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json', Accept: 'text/event-stream' },
+    body: JSON.stringify(body),
+    signal,
+  });
+  if (!res.ok) {
+    const text = await res.text().catch(() => '');
+    throw new Error(text || `Server returned ${res.status} ${res.statusText}`);
+  }
+  await readServerEvents(res, onEvent);
+  return res;
+}
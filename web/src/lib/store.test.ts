@@ -1,6 +1,6 @@
 import { describe, it, expect } from 'vitest';
 import { useStore, isRequestDirty, isTabDirty } from './store';
-import type { CollectionParsed, Tab } from './types';
+import type { CollectionParsed, Metadata, Tab } from './types';
 
 function makeParsed(overrides: Partial<CollectionParsed> = {}): CollectionParsed {
   return {
@@ -22,7 +22,7 @@ function makeTab(overrides: Partial<Tab> = {}): Tab {
   };
 }
 
-function withState(request: { endpoint: string; headers: Record<string, string>; bodies: string[] }, workspaceOriginal: CollectionParsed | null) {
+function withState(request: { endpoint: string; headers: Metadata; bodies: string[] }, workspaceOriginal: CollectionParsed | null) {
   return { ...useStore.getState(), request, workspaceOriginal };
 }
 
@@ -58,6 +58,12 @@ describe('isRequestDirty', () => {
     const orig = makeParsed({ headers: { a: '1', b: '2' } });
     expect(isRequestDirty(withState({ endpoint: orig.endpoint, headers: { b: '2', a: '1' }, bodies: orig.bodies }, orig))).toBe(false);
   });
+
+  it('compares the values of a repeated header in order', () => {
+    const orig = makeParsed({ headers: { 'x-tag': ['a', 'b'] } });
+    expect(isRequestDirty(withState({ endpoint: orig.endpoint, headers: { 'x-tag': ['a', 'b'] }, bodies: orig.bodies }, orig))).toBe(false);
+    expect(isRequestDirty(withState({ endpoint: orig.endpoint, headers: { 'x-tag': ['b', 'a'] }, bodies: orig.bodies }, orig))).toBe(true);
+  });
 });
 
 describe('isTabDirty', () => {
@@ -1,10 +1,12 @@
 import { create } from 'zustand';
-import type { PlayStore, HistoryEntry, CallResult, CollectionParsed, Tab, StoredTab, TabsStorage, Environment, WireProtocol, ReflectResponse } from './types';
+import type { PlayStore, HistoryEntry, CallResult, CollectionParsed, Tab, StoredTab, TabsStorage, Environment, Metadata, WireProtocol, ReflectResponse, CollectionRun, SuiteTest, BenchProgress, BenchSummary } from './types';
 import { ENVS_KEY, ACTIVE_ENV_KEY, TABS_KEY, SETTINGS_KEY, defaultAddressFor, isAddressAtDefault } from './types';
 import type { ClientSettings } from './types';
 import { LRUCache } from './cache';
 import { getSessionId } from './session';
 import { applyEnvironment, substituteEnv } from './env';
+import { postEventStream } from './sse';
+import type { ServerEvent } from './sse';
 
 function now() { return Date.now(); }
 function id() { return Math.random().toString(36).slice(2, 9); }
@@ -15,6 +17,9 @@ const historyCache = new LRUCache<string, HistoryEntry>(1000);
 // Per-tab AbortControllers so executing in one tab never cancels another.
 const abortControllers = new Map<string, AbortController>();
 let reflectController: AbortController | null = null;
+// The Runs panel's suite run or benchmark; aborting it closes the event
+// stream, which stops the run on the server.
+let collectionRunController: AbortController | null = null;
 
 const EMPTY_REQUEST = { endpoint: '', headers: {}, bodies: DEFAULT_BODIES };
 
@@ -91,6 +96,93 @@ function snapshot(state: PlayStore, tabId: string, overrides?: Partial<Tab>): Ta
 }
 
 
+/** The active environment with its muted variables left out, or null. */
+function effectiveEnvironment(st: PlayStore): Environment | null {
+  const activeEnv = st.activeEnvironment
+    ? st.environments.find(e => e.name === st.activeEnvironment)
+    : null;
+  if (!activeEnv) return null;
+  return {
+    ...activeEnv,
+    variables: Object.fromEntries(
+      Object.entries(activeEnv.variables)
+        .filter(([k]) => !(activeEnv.mutedVariables || []).includes(k))
+    ),
+  };
+}
+
+/** Start a suite run or benchmark and mirror its events into `collectionRun`.
+ * A new run replaces (and stops) the one before it. */
+async function followCollectionRun(kind: CollectionRun['kind'], path: string, url: string, body: unknown) {
+  collectionRunController?.abort();
+  const controller = new AbortController();
+  collectionRunController = controller;
+
+  let run: CollectionRun = { kind, path, status: 'running', testCount: null, tests: [], summary: null, progress: [], benchSummary: null, reportHtml: null, error: null };
+  const update = (patch: Partial<CollectionRun>) => {
+    run = { ...run, ...patch };
+    if (collectionRunController === controller) useStore.setState({ collectionRun: run });
+  };
+  update({});
+
+  const onEvent = ({ event, data }: ServerEvent) => {
+    const d = (data ?? {}) as any;
+    switch (event) {
+      case 'suite_start':
+        update({ testCount: d.testCount ?? null });
+        break;
+      case 'test_start':
+        update({ tests: [...run.tests, { id: d.testId, status: 'running', durationMs: null, message: null }] });
+        break;
+      case 'test_pass':
+      case 'test_fail':
+      case 'test_skip': {
+        const status = event === 'test_pass' ? 'passed' : event === 'test_fail' ? 'failed' : 'skipped';
+        const done: SuiteTest = { id: d.testId, status, durationMs: d.duration ?? null, message: d.message ?? null };
+        const started = run.tests.some(t => t.id === d.testId && t.status === 'running');
+        update({ tests: started ? run.tests.map(t => t.id === d.testId && t.status === 'running' ? done : t) : [...run.tests, done] });
+        break;
+      }
+      case 'suite_end':
+        update({ summary: d.summary ?? null });
+        break;
+      case 'progress':
+        update({ progress: [...run.progress, d as BenchProgress].slice(-120) });
+        break;
+      case 'report':
+        if (kind === 'suite') {
+          update({ reportHtml: d.html || null, status: (run.summary?.failed ?? 0) > 0 ? 'failed' : 'passed' });
+        } else {
+          // Failed the way `bench` exits non-zero: nothing measured, or a threshold missed.
+          const summary = (d.summary ?? null) as BenchSummary | null;
+          const measured = !summary || summary.count === 0 || summary.ok > 0;
+          const thresholds = ((d.threshold_evaluation ?? []) as { passed: boolean }[]).every(t => t.passed);
+          update({ benchSummary: summary, status: measured && thresholds ? 'passed' : 'failed' });
+        }
+        break;
+      case 'error':
+        update({ status: 'error', error: d.error || 'The run failed' });
+        break;
+    }
+  };
+
+  try {
+    await postEventStream(url, body, onEvent, controller.signal);
+    if (run.status === 'running') update({ status: 'error', error: 'The run ended without a report' });
+  } catch (err: any) {
+    if (err?.name === 'AbortError') update({ status: 'cancelled' });
+    else update({ status: 'error', error: err?.message || String(err) });
+  } finally {
+    if (collectionRunController === controller) collectionRunController = null;
+  }
+}
+
+/** POST to one of an open call's control routes; throws the server's reason. */
+async function controlCall(url: string, init: RequestInit): Promise<void> {
+  const res = await fetch(url, init);
+  if (!res.ok) throw new Error((await res.text().catch(() => '')) || `Server returned ${res.status}`);
+}
+
 export function effectiveTls(st: PlayStore) {
   const env = st.activeEnvironment
     ? st.environments.find(e => e.name === st.activeEnvironment)
@@ -107,13 +199,13 @@ export function effectiveTls(st: PlayStore) {
   return { tls: st.tls, tlsInsecure: st.tlsInsecure, tlsCa: st.tlsCa, tlsCert: st.tlsCert, tlsKey: st.tlsKey };
 }
 
-function headersEqual(a: Record<string, string>, b: Record<string, string>): boolean {
+function headersEqual(a: Metadata, b: Metadata): boolean {
   const keys = Object.keys(a);
   if (keys.length !== Object.keys(b).length) return false;
-  return keys.every(k => a[k] === b[k]);
+  return keys.every(k => JSON.stringify(a[k]) === JSON.stringify(b[k]));
 }
 
-function structuredDirty(endpoint: string, headers: Record<string, string>, bodies: string[], orig: CollectionParsed | null): boolean {
+function structuredDirty(endpoint: string, headers: Metadata, bodies: string[], orig: CollectionParsed | null): boolean {
   if (!orig) return false;
   return (
     endpoint !== orig.endpoint ||
@@ -367,6 +459,7 @@ export const useStore = create<PlayStore>((set, get) => ({
   showHotkeyHelp: false,
   runStatus: 'idle',
   runMode: 'execute',
+  collectionRun: null,
   environments: (() => {
     try { return JSON.parse(localStorage.getItem(ENVS_KEY) || '[]'); }
     catch { return []; }
@@ -521,6 +614,12 @@ export const useStore = create<PlayStore>((set, get) => ({
   cancel: () => {
     const st = get();
     const tabId = st.activeTabId;
+    // An open call ends through its own `end` event, keeping what arrived.
+    const live = st.response?.live;
+    if (live) {
+      fetch(`/api/call/stream/${encodeURIComponent(live.id)}`, { method: 'DELETE' }).catch(() => {});
+      return;
+    }
     let aborted = false;
     if (tabId) {
       const c = abortControllers.get(tabId);
@@ -782,28 +881,16 @@ export const useStore = create<PlayStore>((set, get) => ({
       return patch;
     });
 
-    const activeEnv = st.activeEnvironment
-      ? st.environments.find(e => e.name === st.activeEnvironment)
-      : null;
-
     if (!st.request.endpoint) {
       const errResult: CallResult = { status: 'error', statusCode: null, messages: [], headers: {}, trailers: {}, error: 'Enter a gRPC endpoint', durationMs: null };
       writeResponse(errResult);
       return;
     }
 
-
-    const effectiveEnv = activeEnv ? {
-      ...activeEnv,
-      variables: Object.fromEntries(
-        Object.entries(activeEnv.variables)
-          .filter(([k]) => !(activeEnv.mutedVariables || []).includes(k))
-      ),
-    } : null;
-
+    const effectiveEnv = effectiveEnvironment(st);
     const substituted = applyEnvironment(st.request.endpoint, st.request.headers, st.request.bodies, effectiveEnv);
 
-    const effectiveAddress = activeEnv
+    const effectiveAddress = effectiveEnv
       ? substituteEnv(address, effectiveEnv) || address
       : address;
 
@@ -824,58 +911,11 @@ export const useStore = create<PlayStore>((set, get) => ({
     const pending: CallResult = { status: 'pending', statusCode: null, messages: [], headers: {}, trailers: {}, error: null, durationMs: null };
     writeResponse(pending);
 
-    const start = performance.now();
-    try {
-      const filteredBodies = substituted.bodies.filter(b => b.trim() && b !== '');
-      const bodies_raw = filteredBodies.length > 0 ? filteredBodies : undefined;
-
-      const res = await fetch('/api/call', {
-        method: 'POST',
-        headers: { 'Content-Type': 'application/json' },
-        body: JSON.stringify({
-          endpoint: substituted.endpoint,
-          bodies_raw,
-          headers: Object.keys(substituted.headers).length > 0 ? substituted.headers : undefined,
-          tls: tls || undefined, tls_insecure: tls ? tlsInsecure : undefined,
-          tls_ca: tls ? (tlsCa || undefined) : undefined,
-          tls_cert: tls ? (tlsCert || undefined) : undefined,
-          tls_key: tls ? (tlsKey || undefined) : undefined,
-          address: effectiveAddress || undefined,
-          protocol: protocol || undefined,
-          environment: Object.keys(st.environment).length > 0 ? st.environment : undefined,
-          collection_path: workspacePath || undefined,
-          session_id: st.sessionId || undefined,
-        }),
-        signal,
-      });
-      clearController();
-
-      let data: any;
-      try { data = await res.json(); } catch {
-        const errResult: CallResult = { status: 'error', statusCode: res.status, messages: [], headers: {}, trailers: {}, error: `Server returned ${res.status} ${res.statusText}`, durationMs: Math.round(performance.now() - start) };
-        const errEntry: HistoryEntry = { id: id(), timestamp: now(), endpoint: st.request.endpoint, bodies: st.request.bodies, headers: st.request.headers, response: errResult };
-        historyCache.put(errEntry.id, errEntry);
-        saveHistoryToStorage();
-        const totalError = get().totalError + 1;
-        saveTotals(get().totalOk, totalError);
-        writeResponse(errResult, { history: historyCache.values(), totalError });
-        return;
-      }
-
-      const durationMs = Math.round(performance.now() - start);
-      const result: CallResult = {
-        status: data.success ? 'ok' : 'error',
-        statusCode: res.status,
-        messages: data.messages ?? [],
-        headers: data.headers || {},
-        trailers: data.trailers || {},
-        error: data.error || null,
-        durationMs,
-      };
+    const record = (result: CallResult) => {
       const entry: HistoryEntry = { id: id(), timestamp: now(), endpoint: st.request.endpoint, bodies: st.request.bodies, headers: st.request.headers, response: result };
       historyCache.put(entry.id, entry);
       saveHistoryToStorage();
-      if (data.success) {
+      if (result.status === 'ok') {
         const totalOk = get().totalOk + 1;
         saveTotals(totalOk, get().totalError);
         writeResponse(result, { history: historyCache.values(), totalOk });
@@ -884,24 +924,111 @@ export const useStore = create<PlayStore>((set, get) => ({
         saveTotals(get().totalOk, totalError);
         writeResponse(result, { history: historyCache.values(), totalError });
       }
+    };
+
+    // The call streams over server-sent events: each message shows as it
+    // arrives, and while the call is open the tab can push more requests,
+    // half-close the request stream or cancel it (see `live`).
+    const start = performance.now();
+    let result: CallResult = pending;
+    let ended = false;
+    const update = (patch: Partial<CallResult>) => {
+      result = { ...result, ...patch };
+      writeResponse(result);
+    };
+    const onEvent = ({ event, data }: ServerEvent) => {
+      const d = (data ?? {}) as any;
+      switch (event) {
+        case 'call': {
+          const mode: string | null = d.mode ?? null;
+          update({ status: 'streaming', live: { id: d.id, mode, requestsOpen: mode === 'client_streaming' || mode === 'bidi_streaming' } });
+          break;
+        }
+        case 'headers':
+          update({ headers: d.headers || {} });
+          break;
+        case 'message':
+          update({
+            messages: [...result.messages, d.message],
+            messageTimesMs: [...(result.messageTimesMs ?? []), Math.round(performance.now() - start)],
+          });
+          break;
+        case 'trailers':
+          update({ trailers: { ...result.trailers, ...(d.trailers || {}) } });
+          break;
+        case 'closed':
+          if (result.live) update({ live: { ...result.live, requestsOpen: false } });
+          break;
+        case 'error':
+          update({ error: d.error || 'Call failed' });
+          break;
+        case 'end':
+          ended = true;
+          result = {
+            ...result,
+            status: d.status === 'error' ? 'error' : 'ok',
+            error: d.error ?? result.error,
+            durationMs: d.elapsed_ms ?? Math.round(performance.now() - start),
+            live: undefined,
+          };
+          break;
+      }
+    };
+
+    try {
+      const filteredBodies = substituted.bodies.filter(b => b.trim() && b !== '');
+      const bodies_raw = filteredBodies.length > 0 ? filteredBodies : undefined;
+
+      const res = await postEventStream('/api/call/stream', {
+        endpoint: substituted.endpoint,
+        bodies_raw,
+        headers: Object.keys(substituted.headers).length > 0 ? substituted.headers : undefined,
+        tls: tls || undefined, tls_insecure: tls ? tlsInsecure : undefined,
+        tls_ca: tls ? (tlsCa || undefined) : undefined,
+        tls_cert: tls ? (tlsCert || undefined) : undefined,
+        tls_key: tls ? (tlsKey || undefined) : undefined,
+        address: effectiveAddress || undefined,
+        protocol: protocol || undefined,
+        environment: Object.keys(st.environment).length > 0 ? st.environment : undefined,
+        collection_path: workspacePath || undefined,
+        session_id: st.sessionId || undefined,
+      }, onEvent, signal);
+      clearController();
+
+      if (!ended) {
+        result = { ...result, status: 'error', error: result.error || 'The call ended without a result', live: undefined };
+      }
+      record({ ...result, statusCode: res.status, durationMs: result.durationMs ?? Math.round(performance.now() - start) });
     } catch (err: any) {
       clearController();
       if (err?.name === 'AbortError') {
         writeResponse(null);
         return;
       }
-      const errResult: CallResult = { status: 'error', statusCode: null, messages: [], headers: {}, trailers: {}, error: err?.message || String(err), durationMs: Math.round(performance.now() - start) };
-      const errEntry: HistoryEntry = { id: id(), timestamp: now(), endpoint: st.request.endpoint, bodies: st.request.bodies, headers: st.request.headers, response: errResult };
-      historyCache.put(errEntry.id, errEntry);
-      saveHistoryToStorage();
-      const totalError = get().totalError + 1;
-      saveTotals(get().totalOk, totalError);
-      writeResponse(errResult, { history: historyCache.values(), totalError });
+      record({ ...result, status: 'error', error: err?.message || String(err), durationMs: Math.round(performance.now() - start), live: undefined });
     } finally {
       if (timeoutId !== undefined) clearTimeout(timeoutId);
     }
   },
 
+  sendStreamMessage: async (body: string) => {
+    const st = get();
+    const live = st.response?.live;
+    if (!live?.requestsOpen) return;
+    const [substituted] = applyEnvironment('', {}, [body], effectiveEnvironment(st)).bodies;
+    await controlCall(`/api/call/stream/${encodeURIComponent(live.id)}/send`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ body_raw: substituted }),
+    });
+  },
+
+  closeStream: async () => {
+    const live = get().response?.live;
+    if (!live?.requestsOpen) return;
+    await controlCall(`/api/call/stream/${encodeURIComponent(live.id)}/close`, { method: 'POST' });
+  },
+
   runTest: async () => {
     const st = get();
     const { workspacePath, sessionId } = st;
@@ -967,6 +1094,30 @@ export const useStore = create<PlayStore>((set, get) => ({
     }
   },
 
+  runSuite: async (opts) => {
+    await followCollectionRun('suite', opts.path, '/api/run/suite', {
+      path: opts.path,
+      tags: opts.tags.length > 0 ? opts.tags : undefined,
+      skip_tags: opts.skipTags.length > 0 ? opts.skipTags : undefined,
+      parallel: opts.parallel || undefined,
+      session_id: get().sessionId || undefined,
+    });
+  },
+
+  runBench: async (opts) => {
+    await followCollectionRun('bench', opts.path, '/api/bench', {
+      path: opts.path,
+      duration: opts.duration || undefined,
+      concurrency: opts.concurrency || undefined,
+      max_rps: opts.maxRps || undefined,
+      session_id: get().sessionId || undefined,
+    });
+  },
+
+  stopCollectionRun: () => {
+    collectionRunController?.abort();
+  },
+
   loadRawContent: async () => {
     const st = get();
     if (!st.workspacePath) return;
@@ -1,5 +1,5 @@
 import { describe, it, expect } from 'vitest';
-import { defaultAddressFor, isAddressAtDefault } from './types';
+import { defaultAddressFor, isAddressAtDefault, metadataRows, metadataFromRows } from './types';
 
 describe('defaultAddressFor', () => {
   it('grpc → 4770', () => {
@@ -34,3 +34,12 @@ describe('isAddressAtDefault', () => {
     expect(isAddressAtDefault('localhost:4770', 'grpc-web')).toBe(false);
   });
 });
+
+describe('metadataRows', () => {
+  it('gives a repeated key one row per value and round-trips', () => {
+    const metadata = { 'x-tag': ['a', 'b'], 'x-user': 'ada' };
+    const rows = metadataRows(metadata);
+    expect(rows).toEqual([['x-tag', 'a'], ['x-tag', 'b'], ['x-user', 'ada']]);
+    expect(metadataFromRows(rows)).toEqual(metadata);
+  });
+});
@@ -17,9 +17,29 @@ export function isAddressAtDefault(address: string, protocol: WireProtocol): boo
   return address === defaultAddressFor(protocol);
 }
 
+/** Request metadata as the server sends and takes it: a repeated key (two
+ * `x-tag:` lines in REQUEST_HEADERS) holds its values as an array. */
+export type Metadata = Record<string, string | string[]>;
+
+/** One `[key, value]` pair per value, repeated keys in order. */
+export function metadataRows(metadata: Metadata): [string, string][] {
+  return Object.entries(metadata).flatMap(([k, v]) =>
+    (Array.isArray(v) ? v : [v]).map(value => [k, value] as [string, string]));
+}
+
+/** The inverse of `metadataRows`: a key seen twice becomes an array. */
+export function metadataFromRows(rows: [string, string][]): Metadata {
+  const out: Metadata = {};
+  for (const [k, v] of rows) {
+    const prev = out[k];
+    out[k] = prev === undefined ? v : Array.isArray(prev) ? [...prev, v] : [prev, v];
+  }
+  return out;
+}
+
 export interface RequestConfig {
   endpoint: string;
-  headers: Record<string, string>;
+  headers: Metadata;
   bodies: string[];
 }
 
@@ -33,8 +53,18 @@ export interface RunAssertionResult {
   actual: string | null;
 }
 
+/** A call still open on `/api/call/stream`, for the send/close/cancel controls. */
+export interface LiveCall {
+  id: string;
+  /** `unary`, `server_streaming`, `client_streaming` or `bidi_streaming`; null when reflection couldn't tell. */
+  mode: string | null;
+  /** True while the UI may still push request messages. */
+  requestsOpen: boolean;
+}
+
 export interface CallResult {
-  status: 'ok' | 'error' | 'pending';
+  /** `streaming` while a `/api/call/stream` call is open and its messages arrive. */
+  status: 'ok' | 'error' | 'pending' | 'streaming';
   statusCode: number | null;
 
   messages: unknown[];
@@ -44,6 +74,70 @@ export interface CallResult {
   durationMs: number | null;
   /** Set only when this result came from `/api/run` (full .gctf ASSERTS/EXTRACT run), not a raw `/api/call`. */
   assertions?: RunAssertionResult[];
+  /** When each of `messages` arrived, in ms since the call started; streamed calls only. */
+  messageTimesMs?: number[];
+  /** Set while the call is open; cleared by its `end` event. */
+  live?: LiveCall;
+}
+
+/** One test of a `/api/run/suite` run, filled in from its `--stream` events. */
+export interface SuiteTest {
+  id: string;
+  status: 'running' | 'passed' | 'failed' | 'skipped';
+  durationMs: number | null;
+  message: string | null;
+}
+
+/** A `progress` event of `/api/bench` (see `ProgressSnapshot` in src/commands/bench.rs). */
+export interface BenchProgress {
+  elapsed_s: number;
+  requests: number;
+  rps: number;
+  target_rps: number;
+  error_pct: number;
+  latency_ms: number | null;
+}
+
+/** The `summary` of a bench report (see `BenchSummary` in src/report/bench.rs). */
+export interface BenchSummary {
+  count: number;
+  ok: number;
+  errors: number;
+  average_ns: number;
+  fastest_ns: number;
+  slowest_ns: number;
+  rps_observed: number;
+}
+
+/** A suite run or benchmark started from the Runs panel. */
+export interface CollectionRun {
+  kind: 'suite' | 'bench';
+  path: string;
+  status: 'running' | 'passed' | 'failed' | 'cancelled' | 'error';
+  /** From `suite_start`; null until it arrives and for benchmarks. */
+  testCount: number | null;
+  tests: SuiteTest[];
+  /** `suite_end`'s summary: total, passed, failed, skipped, duration. */
+  summary: Record<string, number> | null;
+  progress: BenchProgress[];
+  benchSummary: BenchSummary | null;
+  /** The suite's HTML report, from its `report` event. */
+  reportHtml: string | null;
+  error: string | null;
+}
+
+export interface SuiteOptions {
+  path: string;
+  tags: string[];
+  skipTags: string[];
+  parallel?: string;
+}
+
+export interface BenchOptions {
+  path: string;
+  duration?: string;
+  concurrency?: number;
+  maxRps?: number;
 }
 
 export interface HistoryEntry {
@@ -51,7 +145,7 @@ export interface HistoryEntry {
   timestamp: number;
   endpoint: string;
   bodies: string[];
-  headers: Record<string, string>;
+  headers: Metadata;
   response: CallResult;
 }
 
@@ -109,7 +203,7 @@ export interface ProtoSourceResponse {
 export interface CollectionParsed {
   endpoint: string;
   address: string;
-  headers: Record<string, string>;
+  headers: Metadata;
   bodies: string[];
   asserts: string[];
   extracts: Record<string, string>;
@@ -160,7 +254,7 @@ export interface ClientSettings {
 export interface ShareState {
   id: string;
   endpoint: string;
-  headers: Record<string, string>;
+  headers: Metadata;
   bodies: string[];
   address: string | null;
   protocol: string | null;
@@ -198,7 +292,7 @@ export interface Tab {
   id: string;
   label: string;
   endpoint: string;
-  headers: Record<string, string>;
+  headers: Metadata;
   bodies: string[];
   environment: Record<string, string>;
   response: CallResult | null;
@@ -232,7 +326,7 @@ export interface StoredTab {
   i: string;
   l: string;
   e: string;
-  h: Record<string, string>;
+  h: Metadata;
   b: string[];
   c: string | null;
   v?: Record<string, string>;
@@ -290,6 +384,7 @@ export interface PlayStore {
   showHotkeyHelp: boolean;
   runStatus: 'idle' | 'running';
   runMode: 'execute' | 'run';
+  collectionRun: CollectionRun | null;
 
   requestTimeoutMs: number;
   setAddress: (v: string) => void;
@@ -305,7 +400,7 @@ export interface PlayStore {
   addRequestBody: () => void;
   removeRequestBody: (idx: number) => void;
   setRequestBodies: (v: string[]) => void;
-  setRequestHeaders: (v: Record<string, string>) => void;
+  setRequestHeaders: (v: Metadata) => void;
   setRequestTab: (v: RequestTab) => void;
   setGctfTab: (v: GctfTab) => void;
   setResponseTab: (v: ResponseTab) => void;
@@ -320,8 +415,18 @@ export interface PlayStore {
   saveWorkspace: () => Promise<void>;
   saveWorkspaceAs: (name: string) => Promise<void>;
   execute: () => Promise<void>;
+  /** Push one more request message into the active tab's open call. */
+  sendStreamMessage: (body: string) => Promise<void>;
+  /** Half-close the active tab's request stream. */
+  closeStream: () => Promise<void>;
   runTest: () => Promise<void>;
   setRunMode: (v: 'execute' | 'run') => void;
+  /** Run a collections directory through `/api/run/suite`, following its events. */
+  runSuite: (opts: SuiteOptions) => Promise<void>;
+  /** Benchmark a collection through `/api/bench`, following its progress. */
+  runBench: (opts: BenchOptions) => Promise<void>;
+  /** Stop the suite run or benchmark by closing its event stream. */
+  stopCollectionRun: () => void;
   loadRawContent: () => Promise<void>;
   setRawContent: (v: string) => void;
   saveRawContent: () => Promise<void>;